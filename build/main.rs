@@ -5,16 +5,64 @@ use api::Api;
 
 const RAYLIB_API_PATH: &str = "raylib/parser/output/raylib_api.json";
 
+/// raylib's `OPENGL_VERSION` cmake option for the selected `opengl_*` feature, which sets `GRAPHICS_API_OPENGL_*`
+fn opengl_version() -> Option<&'static str> {
+    let versions = [
+        (cfg!(feature = "opengl_43"), "4.3"),
+        (cfg!(feature = "opengl_33"), "3.3"),
+        (cfg!(feature = "opengl_21"), "2.1"),
+        (cfg!(feature = "opengl_11"), "1.1"),
+        (cfg!(feature = "opengl_es2"), "ES 2.0"),
+    ];
+
+    let mut selected = versions
+        .iter()
+        .filter(|(enabled, _)| *enabled)
+        .map(|&(_, version)| version);
+
+    let version = selected.next();
+
+    if selected.next().is_some() {
+        panic!("only one of the opengl_* features can be enabled");
+    }
+
+    version
+}
+
+/// Is GLFW built for Wayland instead of X11 on Linux and BSDs?
+///
+/// GLFW 3.3 supports one window system per build, the `x11` feature only states the default.
+fn use_wayland() -> bool {
+    cfg!(feature = "wayland")
+}
+
 fn build_raylib() {
-    let dest = cmake::Config::new("raylib")
+    let mut config = cmake::Config::new("raylib");
+
+    config
         .define("BUILD_EXAMPLES", "OFF")
         .define("CMAKE_BUILD_TYPE", "Release")
         .profile(if cfg!(debug_assertions) {
             "Debug"
         } else {
             "Release"
-        })
-        .build();
+        });
+
+    // defaults to OpenGL 3.3 on desktop
+    if let Some(version) = opengl_version() {
+        config.define("OPENGL_VERSION", version);
+    }
+
+    // requests a debug context, with OpenGL 4.3 rlgl also logs the driver's debug messages
+    if cfg!(feature = "opengl_debug") {
+        config.cflag("-DRLGL_ENABLE_OPENGL_DEBUG_CONTEXT");
+    }
+
+    if cfg!(unix) && !cfg!(target_os = "macos") {
+        config.define("USE_WAYLAND", if use_wayland() { "ON" } else { "OFF" });
+    }
+
+    let dest = config.build();
 
     println!(
         "cargo:rustc-link-search=native={}",
@@ -29,30 +77,183 @@ fn build_raylib() {
         dest.join("lib32").display()
     );
 
-    if cfg!(windows) {
+    link_platform_libs();
+
+    println!("cargo:rustc-link-lib=static=raylib");
+}
+
+/// System libraries a static raylib depends on
+fn link_platform_libs() {
+    // the build script runs on the host, `cfg!` would describe it instead of the target
+    let family = env::var("CARGO_CFG_TARGET_FAMILY").unwrap_or_default();
+    let os = env::var("CARGO_CFG_TARGET_OS").unwrap_or_default();
+
+    if os == "windows" {
         println!("cargo:rustc-link-lib=dylib=winmm");
         println!("cargo:rustc-link-lib=dylib=gdi32");
         println!("cargo:rustc-link-lib=dylib=user32");
         println!("cargo:rustc-link-lib=dylib=shell32");
-    } else if cfg!(target_os = "macos") {
+    } else if os == "macos" {
         println!("cargo:rustc-link-search=native=/usr/local/lib");
         println!("cargo:rustc-link-lib=framework=OpenGL");
         println!("cargo:rustc-link-lib=framework=Cocoa");
         println!("cargo:rustc-link-lib=framework=IOKit");
         println!("cargo:rustc-link-lib=framework=CoreFoundation");
         println!("cargo:rustc-link-lib=framework=CoreVideo");
-    } else if cfg!(unix) {
+    } else if family == "unix" {
         println!("cargo:rustc-link-search=/usr/local/lib");
-        println!("cargo:rustc-link-lib=X11");
+
+        if use_wayland() {
+            println!("cargo:rustc-link-lib=wayland-client");
+            println!("cargo:rustc-link-lib=wayland-cursor");
+            println!("cargo:rustc-link-lib=wayland-egl");
+            println!("cargo:rustc-link-lib=xkbcommon");
+        } else {
+            println!("cargo:rustc-link-lib=X11");
+        }
+    }
+}
+
+/// Link a preinstalled raylib instead of building it, returns whether it's linked statically or `None` if there's none to link
+///
+/// `RAYLIB_LIB_DIR` is the directory with the library and `RAYLIB_LINK_KIND` is `static` or `dylib`
+/// (the default). Without `RAYLIB_LIB_DIR`, the `system-raylib` feature finds raylib with pkg-config.
+fn link_prebuilt_raylib() -> Option<bool> {
+    println!("cargo:rerun-if-env-changed=RAYLIB_LIB_DIR");
+    println!("cargo:rerun-if-env-changed=RAYLIB_LINK_KIND");
+
+    let statik = match env::var("RAYLIB_LINK_KIND").as_deref() {
+        Ok("static") => true,
+        Ok("dylib") | Err(_) => false,
+        Ok(kind) => panic!("RAYLIB_LINK_KIND must be `static` or `dylib`, not `{kind}`"),
+    };
+
+    if let Ok(dir) = env::var("RAYLIB_LIB_DIR") {
+        println!("cargo:rustc-link-search=native={dir}");
+
+        if statik {
+            println!("cargo:rustc-link-lib=static=raylib");
+            link_platform_libs();
+        } else {
+            println!("cargo:rustc-link-lib=dylib=raylib");
+        }
+
+        return Some(statik);
     }
 
-    println!("cargo:rustc-link-lib=static=raylib");
+    link_pkg_config_raylib(statik).then_some(statik)
+}
+
+/// Find raylib with pkg-config and link it
+#[cfg(feature = "system-raylib")]
+fn link_pkg_config_raylib(statik: bool) -> bool {
+    // the bindings are generated for 4.5
+    if let Err(error) = pkg_config::Config::new()
+        .range_version("4.5".."4.6")
+        .statik(statik)
+        .probe("raylib")
+    {
+        panic!("raylib 4.5 wasn't found with pkg-config, set RAYLIB_LIB_DIR to the directory with the library instead: {error}");
+    }
+
+    true
+}
+
+#[cfg(not(feature = "system-raylib"))]
+fn link_pkg_config_raylib(_statik: bool) -> bool {
+    false
+}
+
+/// Directory with a single header library that isn't shipped with the crate, from `env_var`
+///
+/// Panics if `env_var` isn't set or the directory doesn't contain `header`.
+#[cfg(any(feature = "physac", feature = "raygui", feature = "video"))]
+fn required_include_dir(header: &str, env_var: &str) -> PathBuf {
+    println!("cargo:rerun-if-env-changed={}", env_var);
+
+    let include_dir = match env::var_os(env_var) {
+        Some(dir) => PathBuf::from(dir),
+        None => panic!("set {env_var} to the directory containing {header}"),
+    };
+
+    let path = include_dir.join(header);
+
+    println!("cargo:rerun-if-changed={}", path.display());
+
+    if !path.exists() {
+        panic!(
+            "{header} not found in '{}', {env_var} must be the directory containing it",
+            include_dir.display()
+        );
+    }
+
+    include_dir
+}
+
+#[cfg(feature = "physac")]
+fn build_physac() {
+    // physac is a single header library, not a part of raylib's repo
+    let include_dir = required_include_dir("physac.h", "PHYSAC_INCLUDE_DIR");
+
+    println!("cargo:rerun-if-changed=build/physac.c");
+
+    cc::Build::new()
+        .file("build/physac.c")
+        .include(include_dir)
+        .include("raylib/src")
+        .compile("physac");
+}
+
+#[cfg(feature = "raygui")]
+fn build_raygui() {
+    // raygui is a single header library, not a part of raylib's repo
+    let include_dir = required_include_dir("raygui.h", "RAYGUI_INCLUDE_DIR");
+
+    println!("cargo:rerun-if-changed=build/raygui.c");
+
+    cc::Build::new()
+        .file("build/raygui.c")
+        .include(include_dir)
+        .include("raylib/src")
+        .compile("raygui");
+}
+
+#[cfg(feature = "video")]
+fn build_pl_mpeg() {
+    // pl_mpeg is a single header library, not a part of raylib's repo
+    let include_dir = required_include_dir("pl_mpeg.h", "PL_MPEG_INCLUDE_DIR");
+
+    println!("cargo:rerun-if-changed=build/pl_mpeg.c");
+
+    cc::Build::new()
+        .file("build/pl_mpeg.c")
+        .include(include_dir)
+        .compile("pl_mpeg");
 }
 
 fn main() {
     println!("cargo:rerun-if-changed={}", RAYLIB_API_PATH);
 
-    build_raylib();
+    let statik = link_prebuilt_raylib().unwrap_or_else(|| {
+        build_raylib();
+        true
+    });
+
+    // a static raylib contains GLFW, a shared one doesn't export its functions (e.g. on Windows)
+    println!("cargo:rustc-check-cfg=cfg(raylib_glfw)");
+
+    if statik {
+        println!("cargo:rustc-cfg=raylib_glfw");
+    }
+
+    #[cfg(feature = "physac")]
+    build_physac();
+
+    #[cfg(feature = "raygui")]
+    build_raygui();
+
+    #[cfg(feature = "video")]
+    build_pl_mpeg();
 
     let api_text = fs::read_to_string(RAYLIB_API_PATH).expect("Unable to read raylib api file");
     let api: Api = serde_json::from_str(&api_text).unwrap();
@@ -0,0 +1,19 @@
+// Compiles physac's implementation, the declarations are in src/physac.rs
+#define PHYSAC_IMPLEMENTATION
+#include "physac.h"
+
+#include <stddef.h>
+
+// src/physac.rs declares physac's structs by hand for physac 1.1
+#if PHYSAC_MAX_VERTICES != 24
+#error "src/physac.rs expects PHYSAC_MAX_VERTICES to be 24"
+#endif
+
+// Layout of the structs as compiled, compared with the Rust declarations by Physics::init
+const size_t rust_raylib_physac_layout[] = {
+    sizeof(Matrix2x2),
+    sizeof(PhysicsVertexData),
+    sizeof(PhysicsShape),
+    sizeof(PhysicsBodyData),
+    offsetof(PhysicsBodyData, shape),
+};
@@ -0,0 +1,21 @@
+// Compiles pl_mpeg's implementation, the declarations are in src/video.rs
+#define PL_MPEG_IMPLEMENTATION
+#include "pl_mpeg.h"
+
+#include <stddef.h>
+
+// src/video.rs declares pl_mpeg's structs by hand, with interleaved audio samples
+#if PLM_AUDIO_SAMPLES_PER_FRAME != 1152
+#error "src/video.rs expects PLM_AUDIO_SAMPLES_PER_FRAME to be 1152"
+#endif
+
+#ifdef PLM_AUDIO_SEPARATE_CHANNELS
+#error "src/video.rs expects interleaved audio samples"
+#endif
+
+// Layout of the structs as compiled, compared with the Rust declarations by VideoPlayer::from_file
+const size_t rust_raylib_pl_mpeg_layout[] = {
+    sizeof(plm_plane_t),
+    sizeof(plm_frame_t),
+    sizeof(plm_samples_t),
+};
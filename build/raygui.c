@@ -0,0 +1,35 @@
+// Compiles raygui's implementation, the declarations are in src/gui.rs
+#define RAYGUI_IMPLEMENTATION
+#include "raygui.h"
+
+// src/gui.rs declares raygui's enums by hand for raygui 3.6,
+// a declaration that doesn't match makes the array size negative and fails the build
+#define CHECK_DECLARATION(name, value) typedef char rust_raylib_check_##name[((name) == (value)) ? 1 : -1]
+
+CHECK_DECLARATION(STATE_DISABLED, 3);
+CHECK_DECLARATION(TEXT_ALIGN_RIGHT, 2);
+CHECK_DECLARATION(LABEL, 1);
+CHECK_DECLARATION(BUTTON, 2);
+CHECK_DECLARATION(TOGGLE, 3);
+CHECK_DECLARATION(SLIDER, 4);
+CHECK_DECLARATION(PROGRESSBAR, 5);
+CHECK_DECLARATION(CHECKBOX, 6);
+CHECK_DECLARATION(COMBOBOX, 7);
+CHECK_DECLARATION(DROPDOWNBOX, 8);
+CHECK_DECLARATION(TEXTBOX, 9);
+CHECK_DECLARATION(VALUEBOX, 10);
+CHECK_DECLARATION(SPINNER, 11);
+CHECK_DECLARATION(LISTVIEW, 12);
+CHECK_DECLARATION(COLORPICKER, 13);
+CHECK_DECLARATION(SCROLLBAR, 14);
+CHECK_DECLARATION(STATUSBAR, 15);
+CHECK_DECLARATION(BORDER_COLOR_DISABLED, 9);
+CHECK_DECLARATION(TEXT_COLOR_DISABLED, 11);
+CHECK_DECLARATION(BORDER_WIDTH, 12);
+CHECK_DECLARATION(TEXT_PADDING, 13);
+CHECK_DECLARATION(TEXT_ALIGNMENT, 14);
+CHECK_DECLARATION(RESERVED, 15);
+CHECK_DECLARATION(TEXT_SIZE, 16);
+CHECK_DECLARATION(TEXT_SPACING, 17);
+CHECK_DECLARATION(LINE_COLOR, 18);
+CHECK_DECLARATION(BACKGROUND_COLOR, 19);
@@ -1,12 +1,22 @@
 use std::{
+    cell::Cell,
+    collections::HashMap,
     ffi::{CStr, CString},
+    ops::Range,
+    path::Path,
+    sync::atomic::{AtomicBool, AtomicU32, Ordering},
     time::Duration,
 };
 
-use crate::ffi;
+use crate::{
+    error::RaylibError,
+    ffi,
+    math::{vec3, Camera3D, Vector3},
+};
 
 /// Audio file format
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AudioFormat {
     /// Wave
     Wav,
@@ -35,6 +45,25 @@ impl AudioFormat {
     }
 }
 
+static AUDIO_INITIALIZED: AtomicBool = AtomicBool::new(false);
+
+/// Incremented by every audio device initialization
+static AUDIO_GENERATION: AtomicU32 = AtomicU32::new(0);
+
+/// Is the audio device alive? Audio buffers can't be unloaded after it's closed
+#[inline]
+pub(crate) fn is_audio_alive() -> bool {
+    AUDIO_INITIALIZED.load(Ordering::Relaxed)
+}
+
+/// Generation of the audio device that's alive, `None` if it's closed
+///
+/// Audio buffers can only be unloaded by the device they were loaded with, not a later one.
+#[inline]
+fn audio_generation() -> Option<u32> {
+    is_audio_alive().then(|| AUDIO_GENERATION.load(Ordering::Relaxed))
+}
+
 /// An object that handles audio playback
 #[derive(Debug)]
 pub struct AudioDevice(());
@@ -48,6 +77,9 @@ impl AudioDevice {
         }
 
         if unsafe { ffi::IsAudioDeviceReady() } {
+            AUDIO_GENERATION.fetch_add(1, Ordering::Relaxed);
+            AUDIO_INITIALIZED.store(true, Ordering::Relaxed);
+
             Some(Self(()))
         } else {
             None
@@ -65,6 +97,8 @@ impl Drop for AudioDevice {
     #[inline]
     fn drop(&mut self) {
         unsafe { ffi::CloseAudioDevice() }
+
+        AUDIO_INITIALIZED.store(false, Ordering::Relaxed);
     }
 }
 
@@ -102,21 +136,21 @@ impl Wave {
 
     /// Load wave data from file
     #[inline]
-    pub fn from_file(file_name: &str) -> Option<Self> {
-        let file_name = CString::new(file_name).unwrap();
+    pub fn from_file(file_name: &str) -> Result<Self, RaylibError> {
+        let c_file_name = RaylibError::file_name(file_name)?;
 
-        let raw = unsafe { ffi::LoadWave(file_name.as_ptr()) };
+        let raw = unsafe { ffi::LoadWave(c_file_name.as_ptr()) };
 
         if unsafe { ffi::IsWaveReady(raw.clone()) } {
-            Some(Self { raw })
+            Ok(Self { raw })
         } else {
-            None
+            Err(RaylibError::decode_file(file_name))
         }
     }
 
     /// Load wave from memory buffer
     #[inline]
-    pub fn from_memory(file_data: &[u8], format: AudioFormat) -> Option<Self> {
+    pub fn from_memory(file_data: &[u8], format: AudioFormat) -> Result<Self, RaylibError> {
         let raw = unsafe {
             ffi::LoadWaveFromMemory(
                 format.as_cstr().as_ptr(),
@@ -126,16 +160,19 @@ impl Wave {
         };
 
         if unsafe { ffi::IsWaveReady(raw.clone()) } {
-            Some(Self { raw })
+            Ok(Self { raw })
         } else {
-            None
+            Err(RaylibError::DecodeFailed(format!("{format:?} wave data")))
         }
     }
 
     /// Export wave data to file, returns true on success
     #[inline]
     pub fn export(&self, file_name: &str) -> bool {
-        let file_name = CString::new(file_name).unwrap();
+        let file_name = match CString::new(file_name) {
+            Ok(file_name) => file_name,
+            Err(_) => return false,
+        };
 
         unsafe { ffi::ExportWave(self.raw.clone(), file_name.as_ptr()) }
     }
@@ -143,7 +180,10 @@ impl Wave {
     /// Export wave sample data to code (.h), returns true on success
     #[inline]
     pub fn export_as_code(&self, file_name: &str) -> bool {
-        let file_name = CString::new(file_name).unwrap();
+        let file_name = match CString::new(file_name) {
+            Ok(file_name) => file_name,
+            Err(_) => return false,
+        };
 
         unsafe { ffi::ExportWaveAsCode(self.raw.clone(), file_name.as_ptr()) }
     }
@@ -172,12 +212,13 @@ impl Wave {
     pub fn load_samples(&self) -> Vec<f32> {
         let samples = unsafe { ffi::LoadWaveSamples(self.raw.clone()) };
 
-        let mut vec = Vec::new();
         let len = (self.frame_count() * self.channels()) as usize;
 
-        for i in 0..len {
-            vec.push(unsafe { samples.add(i).read() });
-        }
+        let vec = if samples.is_null() || len == 0 {
+            Vec::new()
+        } else {
+            unsafe { std::slice::from_raw_parts(samples as *const f32, len) }.to_vec()
+        };
 
         unsafe {
             ffi::UnloadWaveSamples(samples);
@@ -228,12 +269,19 @@ impl Drop for Wave {
 }
 
 /// AudioStream, custom audio stream
+///
+/// Not `#[repr(transparent)]`, it keeps the size of its buffers and the generation of its audio device
+/// next to the raw stream.
 #[derive(Debug)]
-#[repr(transparent)]
 pub struct AudioStream {
-    raw: ffi::AudioStream,
+    pub(crate) raw: ffi::AudioStream,
+    buffer_frames: u32,
+    generation: u32,
 }
 
+/// Size set with [`AudioStream::set_default_buffer_size`], 0 lets raylib pick one
+static DEFAULT_BUFFER_FRAMES: AtomicU32 = AtomicU32::new(0);
+
 impl AudioStream {
     /// Frequency (samples per second)
     #[inline]
@@ -254,18 +302,110 @@ impl AudioStream {
     }
 
     /// Load audio stream (to stream raw audio pcm data)
-    #[inline]
     pub fn new(sample_rate: u32, sample_size: u32, channels: u32) -> Option<Self> {
-        let raw = unsafe { ffi::LoadAudioStream(sample_rate, sample_size, channels) };
+        let default_frames = DEFAULT_BUFFER_FRAMES.load(Ordering::Relaxed);
+
+        // raylib derives the size from the device's sample rate when there's no default,
+        // use the stream's so it's known (raylib only ever makes it larger)
+        let buffer_frames = match default_frames {
+            0 => (sample_rate / 30).max(1),
+            frames => frames,
+        };
+
+        Self::with_buffer_frames(sample_rate, sample_size, channels, buffer_frames)
+    }
+
+    /// Load audio stream with buffers of `buffer_frames` frames, leaving the default size as it was
+    fn with_buffer_frames(
+        sample_rate: u32,
+        sample_size: u32,
+        channels: u32,
+        buffer_frames: u32,
+    ) -> Option<Self> {
+        let default_frames = DEFAULT_BUFFER_FRAMES.load(Ordering::Relaxed);
+
+        let raw = unsafe {
+            ffi::SetAudioStreamBufferSizeDefault(buffer_frames as _);
+            let raw = ffi::LoadAudioStream(sample_rate, sample_size, channels);
+            ffi::SetAudioStreamBufferSizeDefault(default_frames as _);
+
+            raw
+        };
 
         if unsafe { ffi::IsAudioStreamReady(raw.clone()) } {
-            Some(Self { raw })
+            Some(Self {
+                raw,
+                buffer_frames,
+                generation: AUDIO_GENERATION.load(Ordering::Relaxed),
+            })
         } else {
             None
         }
     }
 
+    /// Most frames one update can take (the size of one of the stream's two buffers)
+    #[inline]
+    pub fn max_frames_per_update(&self) -> u32 {
+        self.buffer_frames
+    }
+
+    /// Update audio stream buffers with 16bit samples, interleaved by channel
+    ///
+    /// Returns `false` without updating if the stream isn't 16bit, the length isn't a multiple of the channel count
+    /// or it's more than [`AudioStream::max_frames_per_update`].
+    #[inline]
+    pub fn update_samples(&mut self, samples: &[i16]) -> bool {
+        self.update_typed(samples, 16)
+    }
+
+    /// Update audio stream buffers with 32bit float samples, interleaved by channel
+    ///
+    /// Returns `false` without updating if the stream isn't 32bit, the length isn't a multiple of the channel count
+    /// or it's more than [`AudioStream::max_frames_per_update`].
+    #[inline]
+    pub fn update_samples_f32(&mut self, samples: &[f32]) -> bool {
+        self.update_typed(samples, 32)
+    }
+
+    fn update_typed<T>(&mut self, samples: &[T], sample_size: u32) -> bool {
+        let channels = self.channels().max(1) as usize;
+        let frame_count = samples.len() / channels;
+
+        if self.sample_size() != sample_size {
+            #[cfg(debug_assertions)]
+            crate::core::warn(&format!(
+                "AudioStream: got {sample_size}bit samples for a {}bit stream",
+                self.sample_size()
+            ));
+
+            return false;
+        }
+
+        if samples.len() % channels != 0 || frame_count > self.buffer_frames as usize {
+            #[cfg(debug_assertions)]
+            crate::core::warn(&format!(
+                "AudioStream: {} samples aren't whole frames of {channels} channels, or over {} frames",
+                samples.len(),
+                self.buffer_frames
+            ));
+
+            return false;
+        }
+
+        unsafe {
+            ffi::UpdateAudioStream(
+                self.raw.clone(),
+                samples.as_ptr() as *const _,
+                frame_count as _,
+            )
+        }
+
+        true
+    }
+
     /// Update audio stream buffers with data
+    ///
+    /// See [`AudioStream::update_samples`] and [`AudioStream::update_samples_f32`] for checked updates.
     #[inline]
     pub fn update(&mut self, data: &[u8], frame_count: u32) {
         unsafe {
@@ -331,12 +471,38 @@ impl AudioStream {
         unsafe { ffi::SetAudioStreamPan(self.raw.clone(), pan) }
     }
 
-    /// Default size for new audio streams
+    /// Default size for new audio streams (and music) in frames, 0 lets raylib pick one
     #[inline]
     pub fn set_default_buffer_size(size: usize) {
+        DEFAULT_BUFFER_FRAMES.store(size as u32, Ordering::Relaxed);
         unsafe { ffi::SetAudioStreamBufferSizeDefault(size as _) }
     }
 
+    /// Create a stream that's filled by a procedural audio [`Generator`]
+    ///
+    /// Samples are 32bit float, `buffer_frames` is the size of each stream buffer in frames.
+    /// `None` if `buffer_frames` is 0 or the stream couldn't be loaded, the default buffer size
+    /// isn't changed.
+    #[inline]
+    pub fn from_generator<G: Generator>(
+        generator: G,
+        sample_rate: u32,
+        channels: u32,
+        buffer_frames: u32,
+    ) -> Option<GeneratorStream<G>> {
+        if buffer_frames == 0 {
+            return None;
+        }
+
+        let stream = Self::with_buffer_frames(sample_rate, 32, channels, buffer_frames)?;
+
+        Some(GeneratorStream {
+            stream,
+            generator,
+            buffer: vec![0.; (buffer_frames * channels) as usize],
+        })
+    }
+
     /// Get the 'raw' ffi type
     /// Take caution when cloning so it doesn't outlive the original
     #[inline]
@@ -356,24 +522,132 @@ impl AudioStream {
     /// # Safety
     /// * The raw object must be correctly initialized
     /// * The raw object should be unique. Otherwise, make sure its clones don't outlive the newly created object.
+    /// * `buffer_frames` must not be over the size of one of its buffers in frames
+    /// * It must have been loaded with the audio device that's alive
     #[inline]
-    pub unsafe fn from_raw(raw: ffi::AudioStream) -> Self {
-        Self { raw }
+    pub unsafe fn from_raw(raw: ffi::AudioStream, buffer_frames: u32) -> Self {
+        Self {
+            raw,
+            buffer_frames,
+            generation: AUDIO_GENERATION.load(Ordering::Relaxed),
+        }
     }
 }
 
 impl Drop for AudioStream {
     #[inline]
     fn drop(&mut self) {
-        unsafe { ffi::UnloadAudioStream(self.raw.clone()) }
+        if audio_generation() == Some(self.generation) {
+            unsafe { ffi::UnloadAudioStream(self.raw.clone()) }
+        }
+    }
+}
+
+/// Procedural audio source
+pub trait Generator {
+    /// Fill `out` with interleaved 32bit float samples (`out.len() / channels` frames)
+    fn fill(&mut self, out: &mut [f32], sample_rate: u32, channels: u32);
+}
+
+impl<F: FnMut(&mut [f32], u32, u32)> Generator for F {
+    #[inline]
+    fn fill(&mut self, out: &mut [f32], sample_rate: u32, channels: u32) {
+        self(out, sample_rate, channels)
+    }
+}
+
+/// An audio stream that's refilled from a [`Generator`], see [`AudioStream::from_generator`]
+#[derive(Debug)]
+pub struct GeneratorStream<G: Generator> {
+    stream: AudioStream,
+    generator: G,
+    buffer: Vec<f32>,
+}
+
+impl<G: Generator> GeneratorStream<G> {
+    /// Refill all processed stream buffers, call it once per frame
+    pub fn update(&mut self, _device: &mut AudioDevice) {
+        let sample_rate = self.stream.sample_rate();
+        let channels = self.stream.channels();
+
+        while self.stream.is_processed() {
+            self.generator.fill(&mut self.buffer, sample_rate, channels);
+
+            if !self.stream.update_samples_f32(&self.buffer) {
+                break;
+            }
+        }
+    }
+
+    /// Underlying audio stream
+    #[inline]
+    pub fn stream(&self) -> &AudioStream {
+        &self.stream
+    }
+
+    /// Underlying audio stream
+    #[inline]
+    pub fn stream_mut(&mut self) -> &mut AudioStream {
+        &mut self.stream
+    }
+
+    /// The generator
+    #[inline]
+    pub fn generator(&self) -> &G {
+        &self.generator
+    }
+
+    /// The generator
+    #[inline]
+    pub fn generator_mut(&mut self) -> &mut G {
+        &mut self.generator
+    }
+
+    /// Play audio stream, buffers are filled before the playback starts
+    #[inline]
+    pub fn play(&mut self, device: &mut AudioDevice) {
+        self.update(device);
+        self.stream.play(device)
+    }
+
+    /// Pause audio stream
+    #[inline]
+    pub fn pause(&self, device: &mut AudioDevice) {
+        self.stream.pause(device)
+    }
+
+    /// Resume audio stream
+    #[inline]
+    pub fn resume(&self, device: &mut AudioDevice) {
+        self.stream.resume(device)
+    }
+
+    /// Stop audio stream
+    #[inline]
+    pub fn stop(&self, device: &mut AudioDevice) {
+        self.stream.stop(device)
+    }
+
+    /// Check if audio stream is playing
+    #[inline]
+    pub fn is_playing(&self, device: &mut AudioDevice) -> bool {
+        self.stream.is_playing(device)
+    }
+
+    /// Take the generator back, unloading the stream
+    #[inline]
+    pub fn into_generator(self) -> G {
+        self.generator
     }
 }
 
 /// Sound
+///
+/// Not `#[repr(transparent)]`, it keeps the generation of its audio device next to the raw sound.
 #[derive(Debug)]
-#[repr(transparent)]
 pub struct Sound {
     raw: ffi::Sound,
+    generation: u32,
 }
 
 impl Sound {
@@ -385,27 +659,33 @@ impl Sound {
 
     /// Load sound from file
     #[inline]
-    pub fn from_file(filname: &str) -> Option<Self> {
-        let file_name = CString::new(filname).unwrap();
+    pub fn from_file(file_name: &str) -> Result<Self, RaylibError> {
+        let c_file_name = RaylibError::file_name(file_name)?;
 
-        let raw = unsafe { ffi::LoadSound(file_name.as_ptr()) };
+        let raw = unsafe { ffi::LoadSound(c_file_name.as_ptr()) };
 
         if unsafe { ffi::IsSoundReady(raw.clone()) } {
-            Some(Self { raw })
+            Ok(Self {
+                raw,
+                generation: AUDIO_GENERATION.load(Ordering::Relaxed),
+            })
         } else {
-            None
+            Err(RaylibError::decode_file(file_name))
         }
     }
 
     /// Load sound from wave data
     #[inline]
-    pub fn from_wave(wave: &Wave) -> Option<Self> {
+    pub fn from_wave(wave: &Wave) -> Result<Self, RaylibError> {
         let raw = unsafe { ffi::LoadSoundFromWave(wave.raw.clone()) };
 
         if unsafe { ffi::IsSoundReady(raw.clone()) } {
-            Some(Self { raw })
+            Ok(Self {
+                raw,
+                generation: AUDIO_GENERATION.load(Ordering::Relaxed),
+            })
         } else {
-            None
+            Err(RaylibError::NotReady("sound"))
         }
     }
 
@@ -469,6 +749,25 @@ impl Sound {
         unsafe { ffi::SetSoundPan(self.raw.clone(), pan) }
     }
 
+    /// Play a sound at a fixed position in the world, heard by `listener`
+    #[inline]
+    pub fn play_3d(&self, listener: &AudioListener, position: Vector3, device: &mut AudioDevice) {
+        let spatial = listener.spatialize(position, vec3::new(0., 0., 0.));
+
+        self.set_spatial(spatial, device);
+        self.play(device);
+    }
+
+    /// Set volume, pan and pitch computed by [`AudioListener::spatialize`]
+    ///
+    /// Call it every frame for sounds following moving objects.
+    #[inline]
+    pub fn set_spatial(&self, spatial: Spatialization, device: &mut AudioDevice) {
+        self.set_volume(spatial.volume, device);
+        self.set_pan(spatial.pan, device);
+        self.set_pitch(spatial.pitch, device);
+    }
+
     /// Get the 'raw' ffi type
     /// Take caution when cloning so it doesn't outlive the original
     #[inline]
@@ -488,24 +787,47 @@ impl Sound {
     /// # Safety
     /// * The raw object must be correctly initialized
     /// * The raw object should be unique. Otherwise, make sure its clones don't outlive the newly created object.
+    /// * It must have been loaded with the audio device that's alive
     #[inline]
     pub unsafe fn from_raw(raw: ffi::Sound) -> Self {
-        Self { raw }
+        Self {
+            raw,
+            generation: AUDIO_GENERATION.load(Ordering::Relaxed),
+        }
     }
 }
 
 impl Drop for Sound {
     #[inline]
     fn drop(&mut self) {
-        unsafe { ffi::UnloadSound(self.raw.clone()) }
+        if audio_generation() == Some(self.generation) {
+            unsafe { ffi::UnloadSound(self.raw.clone()) }
+        }
     }
 }
 
+/// Buffer size music is loaded with when there's no default (1/30 s at the usual 48kHz, like raylib picks)
+const MUSIC_BUFFER_FRAMES: u32 = 1600;
+
 /// Music, audio stream, anything longer than ~10 seconds should be streamed
+///
+/// Not `#[repr(transparent)]` like the other wrappers, it keeps its buffer size, loop region and
+/// the generation of its audio device next to the raw music. Nothing casts it to [`ffi::Music`], use [`Music::as_raw`] instead.
 #[derive(Debug)]
-#[repr(transparent)]
 pub struct Music {
     raw: ffi::Music,
+    buffer_frames: u32,
+    loop_region: Option<LoopRegion>,
+    generation: u32,
+}
+
+/// Loop region of a [`Music`], in frames
+#[derive(Debug)]
+struct LoopRegion {
+    start: u32,
+    end: u32,
+    /// Where the last jump went, `None` once the playback got there
+    jumped_to: Cell<Option<u32>>,
 }
 
 impl Music {
@@ -529,35 +851,61 @@ impl Music {
 
     /// Load music stream from file
     #[inline]
-    pub fn from_file(file_name: &str) -> Option<Self> {
-        let file_name = CString::new(file_name).unwrap();
+    pub fn from_file(file_name: &str) -> Result<Self, RaylibError> {
+        let c_file_name = RaylibError::file_name(file_name)?;
 
-        let raw = unsafe { ffi::LoadMusicStream(file_name.as_ptr()) };
+        let (raw, buffer_frames) =
+            Self::load_with_known_buffer(|| unsafe { ffi::LoadMusicStream(c_file_name.as_ptr()) });
 
         if unsafe { ffi::IsMusicReady(raw.clone()) } {
-            Some(Self { raw })
+            Ok(Self {
+                raw,
+                buffer_frames,
+                loop_region: None,
+                generation: AUDIO_GENERATION.load(Ordering::Relaxed),
+            })
         } else {
-            None
+            Err(RaylibError::decode_file(file_name))
         }
     }
     /// Load music stream from data
     #[inline]
-    pub fn from_memory(data: &[u8], format: AudioFormat) -> Option<Self> {
-        let raw = unsafe {
+    pub fn from_memory(data: &[u8], format: AudioFormat) -> Result<Self, RaylibError> {
+        let (raw, buffer_frames) = Self::load_with_known_buffer(|| unsafe {
             ffi::LoadMusicStreamFromMemory(
                 format.as_cstr().as_ptr(),
                 data.as_ptr(),
                 data.len() as _,
             )
-        };
+        });
 
         if unsafe { ffi::IsMusicReady(raw.clone()) } {
-            Some(Self { raw })
+            Ok(Self {
+                raw,
+                buffer_frames,
+                loop_region: None,
+                generation: AUDIO_GENERATION.load(Ordering::Relaxed),
+            })
         } else {
-            None
+            Err(RaylibError::DecodeFailed(format!("{format:?} music data")))
         }
     }
 
+    /// Load with a buffer size that's known, the loop region needs it (see [`AudioStream::new`])
+    fn load_with_known_buffer(load: impl FnOnce() -> ffi::Music) -> (ffi::Music, u32) {
+        let default_frames = DEFAULT_BUFFER_FRAMES.load(Ordering::Relaxed);
+        let buffer_frames = match default_frames {
+            0 => MUSIC_BUFFER_FRAMES,
+            frames => frames,
+        };
+
+        unsafe { ffi::SetAudioStreamBufferSizeDefault(buffer_frames as _) }
+        let raw = load();
+        unsafe { ffi::SetAudioStreamBufferSizeDefault(default_frames as _) }
+
+        (raw, buffer_frames)
+    }
+
     /// Start music playing
     #[inline]
     pub fn play(&self, _device: &mut AudioDevice) {
@@ -570,10 +918,92 @@ impl Music {
         unsafe { ffi::IsMusicStreamPlaying(self.raw.clone()) }
     }
 
+    /// Loop points (start, end), if set
+    #[inline]
+    pub fn loop_points(&self) -> Option<(Duration, Duration)> {
+        let rate = self.raw.stream.sampleRate as f64;
+
+        self.loop_region.as_ref().map(|region| {
+            (
+                Duration::from_secs_f64(region.start as f64 / rate),
+                Duration::from_secs_f64(region.end as f64 / rate),
+            )
+        })
+    }
+
+    /// Set loop points: when the playback reaches `end`, it continues from `start`
+    ///
+    /// [`Music::update`] makes the jump ahead of the playback, when raylib decodes the audio at `end`,
+    /// so it's gapless. It's made between two of the stream's buffers, within half a buffer of `end`
+    /// (1/60 s by default), and the difference is carried over so the loop stays in time.
+    ///
+    /// Looping is enabled automatically, so the stream doesn't stop before reaching `end`.
+    /// Fails if `start` isn't before `end` or `end` is past the end of the music.
+    pub fn set_loop_points(&mut self, start: Duration, end: Duration) -> Result<(), RaylibError> {
+        let rate = self.raw.stream.sampleRate as f64;
+        let (start_frame, end_frame) = (start.as_secs_f64() * rate, end.as_secs_f64() * rate);
+
+        if start_frame >= end_frame || end_frame > self.raw.frameCount as f64 {
+            return Err(RaylibError::InvalidArgument(format!(
+                "loop points {start:?}..{end:?} aren't an increasing range within the music"
+            )));
+        }
+
+        self.loop_region = Some(LoopRegion {
+            start: start_frame as u32,
+            end: end_frame as u32,
+            jumped_to: Cell::new(None),
+        });
+        self.raw.looping = true;
+
+        Ok(())
+    }
+
+    /// Remove loop points, the whole track is looped (if looping is enabled)
+    #[inline]
+    pub fn clear_loop_points(&mut self) {
+        self.loop_region = None;
+    }
+
     /// Updates buffers for music streaming
+    ///
+    /// Also handles loop points (see [`Music::set_loop_points`]).
     #[inline]
     pub fn update(&self, _device: &mut AudioDevice) {
         unsafe { ffi::UpdateMusicStream(self.raw.clone()) }
+
+        if let Some(region) = &self.loop_region {
+            self.update_loop(region);
+        }
+    }
+
+    /// Jump back to the loop start once the decoder reaches the loop end
+    ///
+    /// Seeking only moves the decoder, the audio already in the buffers still plays,
+    /// so the jump is heard when the playback gets to where the decoder was.
+    fn update_loop(&self, region: &LoopRegion) {
+        let rate = self.raw.stream.sampleRate as f32;
+        let played = (unsafe { ffi::GetMusicTimePlayed(self.raw.clone()) } * rate) as u32;
+
+        // right after a jump the played time still counts the audio buffered before it
+        // (and wraps around to the end of the track if that's more than the jump target)
+        if let Some(target) = region.jumped_to.get() {
+            if played > target + self.buffer_frames {
+                return;
+            }
+
+            region.jumped_to.set(None);
+        }
+
+        // after an update both buffers are full, the decoder is one to two buffers ahead
+        let decoded = played + self.buffer_frames * 3 / 2;
+
+        if decoded >= region.end {
+            let target = region.start + (decoded - region.end) % (region.end - region.start);
+
+            unsafe { ffi::SeekMusicStream(self.raw.clone(), target as f32 / rate) }
+            region.jumped_to.set(Some(target));
+        }
     }
 
     /// Stop music playing
@@ -618,6 +1048,14 @@ impl Music {
         unsafe { ffi::SetMusicPan(self.raw.clone(), pan) }
     }
 
+    /// Set volume, pan and pitch computed by [`AudioListener::spatialize`], e.g. for a radio in the world
+    #[inline]
+    pub fn set_spatial(&self, spatial: Spatialization, device: &mut AudioDevice) {
+        self.set_volume(spatial.volume, device);
+        self.set_pan(spatial.pan, device);
+        self.set_pitch(spatial.pitch, device);
+    }
+
     /// Get music time length
     #[inline]
     pub fn get_time_length(&self, _device: &mut AudioDevice) -> Duration {
@@ -649,35 +1087,1081 @@ impl Music {
     /// # Safety
     /// * The raw object must be correctly initialized
     /// * The raw object should be unique. Otherwise, make sure its clones don't outlive the newly created object.
+    /// * It must have been loaded with the audio device that's alive
     #[inline]
     pub unsafe fn from_raw(raw: ffi::Music) -> Self {
-        Self { raw }
+        // raylib's default, 1/30 s at the device's sample rate (usually the music's)
+        let buffer_frames = (raw.stream.sampleRate / 30).max(1);
+
+        Self {
+            raw,
+            buffer_frames,
+            loop_region: None,
+            generation: AUDIO_GENERATION.load(Ordering::Relaxed),
+        }
     }
 }
 
 impl Drop for Music {
     #[inline]
     fn drop(&mut self) {
-        unsafe { ffi::UnloadMusicStream(self.raw.clone()) }
+        if audio_generation() == Some(self.generation) {
+            unsafe { ffi::UnloadMusicStream(self.raw.clone()) }
+        }
     }
 }
 
-//pub type AudioCallback = Option<unsafe extern "C" fn(bufferData: *mut core::ffi::c_void, frames: u32, )>;
+/// Volume, pan and pitch of a sound at some position, see [`AudioListener::spatialize`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Spatialization {
+    /// Distance attenuation (1.0 is max level), multiply it with the sound's own volume if it has one
+    pub volume: f32,
+    /// Pan from the direction of the sound (0.5 is center)
+    pub pan: f32,
+    /// Doppler shift (1.0 is base level)
+    pub pitch: f32,
+}
 
-/*
-    /// Audio thread callback to request new data
+/// Listener of 3D positional sounds, usually following the camera
+///
+/// raylib only mixes in stereo, so sounds behind the listener can't be told apart from sounds in front of it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AudioListener {
+    /// Listener position
+    pub position: Vector3,
+    /// Direction the listener faces
+    pub forward: Vector3,
+    /// Listener up vector
+    pub up: Vector3,
+    /// Listener velocity in world units per second, for the doppler effect
+    pub velocity: Vector3,
+    /// Distance up to which sounds play at full volume
+    /// Default: 1.0
+    pub ref_distance: f32,
+    /// Distance from which sounds are silent
+    /// Default: 100.0
+    pub max_distance: f32,
+    /// How fast sounds get quieter past `ref_distance`, 0.0 disables distance attenuation
+    /// Default: 1.0
+    pub rolloff: f32,
+    /// Strength of the doppler effect, 0.0 disables it
+    /// Default: 1.0
+    pub doppler_factor: f32,
+    /// Speed of sound in world units per second
+    /// Default: 343.0 (meters)
+    pub speed_of_sound: f32,
+}
+
+impl AudioListener {
+    /// Create listener at `position` facing `forward`
     #[inline]
-    pub fn SetAudioStreamCallback(stream: AudioStream, callback: AudioCallback);
-    /// Attach audio stream processor to stream
+    pub fn new(position: Vector3, forward: Vector3, up: Vector3) -> Self {
+        Self {
+            position,
+            forward,
+            up,
+            velocity: vec3::new(0., 0., 0.),
+            ref_distance: 1.,
+            max_distance: 100.,
+            rolloff: 1.,
+            doppler_factor: 1.,
+            speed_of_sound: 343.,
+        }
+    }
+
+    /// Create listener at the camera, facing its target
     #[inline]
-    pub fn AttachAudioStreamProcessor(stream: AudioStream, processor: AudioCallback);
-    /// Detach audio stream processor from stream
+    pub fn from_camera(camera: &Camera3D) -> Self {
+        Self::new(
+            camera.position,
+            vec3::sub(camera.target, camera.position),
+            camera.up,
+        )
+    }
+
+    /// Move the listener to the camera, keeping the other settings
     #[inline]
-    pub fn DetachAudioStreamProcessor(stream: AudioStream, processor: AudioCallback);
-    /// Attach audio stream processor to the entire audio pipeline
+    pub fn follow_camera(&mut self, camera: &Camera3D) {
+        self.position = camera.position;
+        self.forward = vec3::sub(camera.target, camera.position);
+        self.up = camera.up;
+    }
+
+    /// Set listener velocity
     #[inline]
-    pub fn AttachAudioMixedProcessor(processor: AudioCallback);
-    /// Detach audio stream processor from the entire audio pipeline
+    pub fn with_velocity(mut self, velocity: Vector3) -> Self {
+        self.velocity = velocity;
+        self
+    }
+
+    /// Set the distance attenuation: full volume up to `ref_distance`, silent from `max_distance`
     #[inline]
-    pub fn DetachAudioMixedProcessor(processor: AudioCallback);
-*/
+    pub fn with_distances(mut self, ref_distance: f32, max_distance: f32) -> Self {
+        self.ref_distance = ref_distance;
+        self.max_distance = max_distance;
+        self
+    }
+
+    /// Set how fast sounds get quieter past the reference distance
+    #[inline]
+    pub fn with_rolloff(mut self, rolloff: f32) -> Self {
+        self.rolloff = rolloff;
+        self
+    }
+
+    /// Set strength of the doppler effect
+    #[inline]
+    pub fn with_doppler_factor(mut self, doppler_factor: f32) -> Self {
+        self.doppler_factor = doppler_factor;
+        self
+    }
+
+    /// Set speed of sound in world units per second
+    #[inline]
+    pub fn with_speed_of_sound(mut self, speed_of_sound: f32) -> Self {
+        self.speed_of_sound = speed_of_sound;
+        self
+    }
+
+    /// Compute how a sound at `position` moving with `velocity` is heard
+    pub fn spatialize(&self, position: Vector3, velocity: Vector3) -> Spatialization {
+        let to_sound = vec3::sub(position, self.position);
+        let distance = vec3::length(to_sound);
+        let direction = vec3::normalize(to_sound);
+
+        // inverse distance, clamped like OpenAL's default model, cut off at the max distance
+        let volume = if distance >= self.max_distance {
+            0.
+        } else {
+            let clamped = distance.max(self.ref_distance);
+            let falloff = self.ref_distance + self.rolloff * (clamped - self.ref_distance);
+
+            if falloff > 0. {
+                (self.ref_distance / falloff).min(1.)
+            } else {
+                1.
+            }
+        };
+
+        let right = vec3::normalize(vec3::cross(self.forward, self.up));
+        let side = vec3::dot(direction, right);
+
+        // raylib 4.5 pans towards the left channel as pan goes to 1.0
+        let pan = 0.5 - 0.5 * side;
+
+        let pitch = if self.doppler_factor > 0. && self.speed_of_sound > 0. {
+            let limit = self.speed_of_sound * 0.5;
+            let listener_speed =
+                (self.doppler_factor * vec3::dot(self.velocity, direction)).clamp(-limit, limit);
+            let sound_speed =
+                (self.doppler_factor * vec3::dot(velocity, direction)).clamp(-limit, limit);
+
+            (self.speed_of_sound + listener_speed) / (self.speed_of_sound + sound_speed)
+        } else {
+            1.
+        };
+
+        Spatialization { volume, pan, pitch }
+    }
+}
+
+/// A named sound event: a set of variations played round-robin with randomized volume and pitch
+#[derive(Debug)]
+pub struct SoundEvent {
+    variations: Vec<Sound>,
+    /// Volume range, a random value from it is used for every playback
+    /// Default: 1.0..1.0
+    pub volume: Range<f32>,
+    /// Pitch range, a random value from it is used for every playback
+    /// Default: 1.0..1.0
+    pub pitch: Range<f32>,
+    next: usize,
+}
+
+impl SoundEvent {
+    /// Create an event from sound variations
+    #[inline]
+    pub fn new(variations: Vec<Sound>) -> Self {
+        Self {
+            variations,
+            volume: 1.0..1.0,
+            pitch: 1.0..1.0,
+            next: 0,
+        }
+    }
+
+    /// Set volume randomization range
+    #[inline]
+    pub fn with_volume(mut self, volume: Range<f32>) -> Self {
+        self.volume = volume;
+        self
+    }
+
+    /// Set pitch randomization range
+    #[inline]
+    pub fn with_pitch(mut self, pitch: Range<f32>) -> Self {
+        self.pitch = pitch;
+        self
+    }
+
+    /// Sound variations
+    #[inline]
+    pub fn variations(&self) -> &[Sound] {
+        &self.variations
+    }
+
+    /// Add a sound variation
+    #[inline]
+    pub fn add_variation(&mut self, sound: Sound) {
+        self.variations.push(sound);
+    }
+}
+
+/// Description of a sound event in an [`AudioBankManifest`]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SoundEventDesc {
+    /// Event name
+    pub name: String,
+    /// Sound files (relative to the manifest base directory), played round-robin
+    pub files: Vec<String>,
+    /// Volume randomization range
+    pub volume: Range<f32>,
+    /// Pitch randomization range
+    pub pitch: Range<f32>,
+}
+
+/// List of sound events to load into an [`AudioBank`]
+///
+/// With the `serde` feature enabled it can be deserialized from any format supported by serde.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AudioBankManifest {
+    /// Sound events
+    pub events: Vec<SoundEventDesc>,
+}
+
+/// A collection of named sound events
+#[derive(Debug)]
+pub struct AudioBank {
+    events: HashMap<String, SoundEvent>,
+    gain: f32,
+    rng_state: u32,
+}
+
+impl AudioBank {
+    /// Create an empty bank
+    ///
+    /// Randomization is seeded from the system time, set the seed (e.g. from a [`GameRng`](crate::replay::GameRng))
+    /// to make it reproducible
+    #[inline]
+    pub fn new() -> Self {
+        let seed = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.subsec_nanos())
+            .unwrap_or(0);
+
+        Self {
+            events: HashMap::new(),
+            gain: 1.,
+            rng_state: seed | 1,
+        }
+    }
+
+    /// Load all sound files in a directory
+    ///
+    /// Every file becomes a variation of the event named after the file, with a trailing `_<number>` removed,
+    /// so `footstep_grass_1.wav` and `footstep_grass_2.wav` are both variations of `footstep_grass`.
+    ///
+    /// Fails if the directory can't be read or a sound fails to load.
+    pub fn from_directory(dir_path: impl AsRef<Path>) -> Result<Self, RaylibError> {
+        let dir_path = dir_path.as_ref();
+        let mut bank = Self::new();
+        let mut paths: Vec<_> = std::fs::read_dir(dir_path)
+            .map_err(|_| RaylibError::FileNotFound(dir_path.to_path_buf()))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| {
+                path.extension()
+                    .and_then(|ext| ext.to_str())
+                    .map(|ext| {
+                        matches!(
+                            ext.to_ascii_lowercase().as_str(),
+                            "wav" | "ogg" | "mp3" | "qoa" | "flac"
+                        )
+                    })
+                    .unwrap_or(false)
+            })
+            .collect();
+
+        paths.sort();
+
+        for path in paths {
+            let stem = match path.file_stem() {
+                Some(stem) => RaylibError::path_str(Path::new(stem))?,
+                None => continue,
+            };
+            let name = match stem.rsplit_once('_') {
+                Some((base, num)) if !num.is_empty() && num.bytes().all(|b| b.is_ascii_digit()) => {
+                    base
+                }
+                _ => stem,
+            };
+
+            let sound = Sound::from_file(RaylibError::path_str(&path)?)?;
+
+            match bank.events.get_mut(name) {
+                Some(event) => event.add_variation(sound),
+                None => {
+                    bank.events
+                        .insert(name.to_string(), SoundEvent::new(vec![sound]));
+                }
+            }
+        }
+
+        Ok(bank)
+    }
+
+    /// Load sound events described by a manifest, file paths are relative to `base_dir`
+    ///
+    /// Fails if any sound fails to load.
+    pub fn from_manifest(
+        manifest: &AudioBankManifest,
+        base_dir: impl AsRef<Path>,
+    ) -> Result<Self, RaylibError> {
+        let mut bank = Self::new();
+
+        for desc in manifest.events.iter() {
+            let mut variations = Vec::with_capacity(desc.files.len());
+
+            for file in desc.files.iter() {
+                let path = base_dir.as_ref().join(file);
+
+                variations.push(Sound::from_file(RaylibError::path_str(&path)?)?);
+            }
+
+            bank.insert(
+                &desc.name,
+                SoundEvent::new(variations)
+                    .with_volume(desc.volume.clone())
+                    .with_pitch(desc.pitch.clone()),
+            );
+        }
+
+        Ok(bank)
+    }
+
+    /// Add (or replace) a sound event
+    #[inline]
+    pub fn insert(&mut self, name: &str, event: SoundEvent) -> Option<SoundEvent> {
+        self.events.insert(name.to_string(), event)
+    }
+
+    /// Remove a sound event
+    #[inline]
+    pub fn remove(&mut self, name: &str) -> Option<SoundEvent> {
+        self.events.remove(name)
+    }
+
+    /// Get a sound event
+    #[inline]
+    pub fn get(&self, name: &str) -> Option<&SoundEvent> {
+        self.events.get(name)
+    }
+
+    /// Get a sound event
+    #[inline]
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut SoundEvent> {
+        self.events.get_mut(name)
+    }
+
+    /// Names of all the events in the bank
+    #[inline]
+    pub fn event_names(&self) -> impl Iterator<Item = &str> {
+        self.events.keys().map(|name| name.as_str())
+    }
+
+    /// Volume multiplier applied to every event
+    #[inline]
+    pub fn gain(&self) -> f32 {
+        self.gain
+    }
+
+    /// Set volume multiplier applied to every event, e.g. `mixer.effective_volume(AudioBus::Sfx)`
+    #[inline]
+    pub fn set_gain(&mut self, gain: f32) {
+        self.gain = gain;
+    }
+
+    /// Set the seed for volume/pitch randomization
+    #[inline]
+    pub fn set_seed(&mut self, seed: u32) {
+        self.rng_state = seed | 1;
+    }
+
+    /// Play the next variation of an event with randomized volume and pitch
+    ///
+    /// Returns the played sound, `None` if there's no such event or it has no variations.
+    pub fn play(&mut self, name: &str, device: &mut AudioDevice) -> Option<&Sound> {
+        let volume_t = self.next_random();
+        let pitch_t = self.next_random();
+
+        let event = self.events.get_mut(name)?;
+
+        if event.variations.is_empty() {
+            return None;
+        }
+
+        let index = event.next % event.variations.len();
+        event.next = index + 1;
+
+        let volume = event.volume.start + (event.volume.end - event.volume.start) * volume_t;
+        let pitch = event.pitch.start + (event.pitch.end - event.pitch.start) * pitch_t;
+
+        let sound = &event.variations[index];
+
+        sound.set_volume(volume * self.gain, device);
+        sound.set_pitch(pitch, device);
+        sound.play(device);
+
+        Some(sound)
+    }
+
+    /// Stop all sounds of an event
+    #[inline]
+    pub fn stop(&self, name: &str, device: &mut AudioDevice) {
+        if let Some(event) = self.events.get(name) {
+            for sound in event.variations.iter() {
+                sound.stop(device);
+            }
+        }
+    }
+
+    /// Stop all sounds in the bank
+    #[inline]
+    pub fn stop_all(&self, device: &mut AudioDevice) {
+        for sound in self
+            .events
+            .values()
+            .flat_map(|event| event.variations.iter())
+        {
+            sound.stop(device);
+        }
+    }
+
+    /// xorshift32, returns a value in [0..1)
+    #[inline]
+    fn next_random(&mut self) -> f32 {
+        let mut x = self.rng_state;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.rng_state = x;
+
+        (x >> 8) as f32 / (1u32 << 24) as f32
+    }
+}
+
+impl Default for AudioBank {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Audio bus, a group of sounds sharing volume and mute settings
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AudioBus {
+    /// Root bus, every other bus is routed into it by default
+    Master,
+    /// Music
+    Music,
+    /// Sound effects
+    Sfx,
+    /// Voice/dialogue
+    Voice,
+    /// User-defined bus
+    Custom(u32),
+}
+
+/// Bus settings
+#[derive(Clone, Debug, PartialEq)]
+struct BusState {
+    volume: f32,
+    muted: bool,
+    parent: Option<AudioBus>,
+}
+
+/// Unique id of a sound registered in an [`AudioMixer`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SoundId(usize);
+
+/// Unique id of a music stream registered in an [`AudioMixer`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct MusicId(usize);
+
+#[derive(Debug)]
+struct MixerEntry<T> {
+    audio: T,
+    bus: AudioBus,
+    volume: f32,
+}
+
+/// Routes sounds and music through [`AudioBus`]es and applies their effective volumes
+///
+/// raylib only has a single master volume, so the mixer multiplies the volume of each sound
+/// with the volumes of the buses along its route and applies the result whenever something changes.
+#[derive(Debug)]
+pub struct AudioMixer {
+    buses: HashMap<AudioBus, BusState>,
+    sounds: Vec<Option<MixerEntry<Sound>>>,
+    music: Vec<Option<MixerEntry<Music>>>,
+}
+
+impl AudioMixer {
+    /// Create a mixer with the standard buses (Master, Music, Sfx, Voice)
+    #[inline]
+    pub fn new() -> Self {
+        let mut mixer = Self {
+            buses: HashMap::new(),
+            sounds: Vec::new(),
+            music: Vec::new(),
+        };
+
+        mixer.buses.insert(
+            AudioBus::Master,
+            BusState {
+                volume: 1.,
+                muted: false,
+                parent: None,
+            },
+        );
+        mixer.add_bus(AudioBus::Music, AudioBus::Master);
+        mixer.add_bus(AudioBus::Sfx, AudioBus::Master);
+        mixer.add_bus(AudioBus::Voice, AudioBus::Master);
+
+        mixer
+    }
+
+    /// Add a bus routed into `parent`, does nothing if the bus already exists
+    ///
+    /// Returns `false` if `parent` doesn't exist or `bus` is `Master`.
+    #[inline]
+    pub fn add_bus(&mut self, bus: AudioBus, parent: AudioBus) -> bool {
+        if bus == AudioBus::Master || !self.buses.contains_key(&parent) {
+            return false;
+        }
+
+        self.buses.entry(bus).or_insert(BusState {
+            volume: 1.,
+            muted: false,
+            parent: Some(parent),
+        });
+
+        true
+    }
+
+    /// Route a bus into another one
+    ///
+    /// Returns `false` if either bus doesn't exist, `bus` is `Master` or the routing would create a cycle.
+    pub fn set_bus_parent(
+        &mut self,
+        bus: AudioBus,
+        parent: AudioBus,
+        device: &mut AudioDevice,
+    ) -> bool {
+        if bus == AudioBus::Master
+            || !self.buses.contains_key(&bus)
+            || !self.buses.contains_key(&parent)
+        {
+            return false;
+        }
+
+        let mut current = Some(parent);
+
+        while let Some(b) = current {
+            if b == bus {
+                return false;
+            }
+
+            current = self.buses[&b].parent;
+        }
+
+        if let Some(state) = self.buses.get_mut(&bus) {
+            state.parent = Some(parent);
+        }
+
+        self.apply(device);
+
+        true
+    }
+
+    /// Get bus volume (not considering parent buses)
+    #[inline]
+    pub fn bus_volume(&self, bus: AudioBus) -> Option<f32> {
+        self.buses.get(&bus).map(|state| state.volume)
+    }
+
+    /// Set bus volume (1.0 is max level)
+    #[inline]
+    pub fn set_bus_volume(&mut self, bus: AudioBus, volume: f32, device: &mut AudioDevice) {
+        if let Some(state) = self.buses.get_mut(&bus) {
+            state.volume = volume;
+            self.apply(device);
+        }
+    }
+
+    /// Is the bus muted
+    #[inline]
+    pub fn is_bus_muted(&self, bus: AudioBus) -> bool {
+        self.buses
+            .get(&bus)
+            .map(|state| state.muted)
+            .unwrap_or(false)
+    }
+
+    /// Mute/unmute a bus (and everything routed into it)
+    #[inline]
+    pub fn set_bus_muted(&mut self, bus: AudioBus, muted: bool, device: &mut AudioDevice) {
+        if let Some(state) = self.buses.get_mut(&bus) {
+            state.muted = muted;
+            self.apply(device);
+        }
+    }
+
+    /// Volume of a bus considering its parents and mute state
+    ///
+    /// A bus that wasn't added (e.g. a [`AudioBus::Custom`] one) is routed into `Master`.
+    pub fn effective_volume(&self, bus: AudioBus) -> f32 {
+        let mut volume = 1.;
+        let mut current = Some(bus);
+
+        while let Some(b) = current {
+            match self.buses.get(&b) {
+                Some(state) if state.muted => return 0.,
+                Some(state) => {
+                    volume *= state.volume;
+                    current = state.parent;
+                }
+                // Master always exists
+                None => current = Some(AudioBus::Master),
+            }
+        }
+
+        volume
+    }
+
+    /// Register a sound on a bus
+    #[inline]
+    pub fn add_sound(&mut self, sound: Sound, bus: AudioBus, device: &mut AudioDevice) -> SoundId {
+        sound.set_volume(self.effective_volume(bus), device);
+
+        self.sounds.push(Some(MixerEntry {
+            audio: sound,
+            bus,
+            volume: 1.,
+        }));
+
+        SoundId(self.sounds.len() - 1)
+    }
+
+    /// Unregister a sound, returning it
+    #[inline]
+    pub fn remove_sound(&mut self, id: SoundId) -> Option<Sound> {
+        self.sounds
+            .get_mut(id.0)
+            .and_then(|entry| entry.take())
+            .map(|entry| entry.audio)
+    }
+
+    /// Get a registered sound
+    #[inline]
+    pub fn sound(&self, id: SoundId) -> Option<&Sound> {
+        self.sounds
+            .get(id.0)
+            .and_then(|entry| entry.as_ref())
+            .map(|entry| &entry.audio)
+    }
+
+    /// Set the volume of a registered sound (relative to its bus)
+    #[inline]
+    pub fn set_sound_volume(&mut self, id: SoundId, volume: f32, device: &mut AudioDevice) {
+        let bus_volume = match self.sounds.get(id.0).and_then(|entry| entry.as_ref()) {
+            Some(entry) => self.effective_volume(entry.bus),
+            None => return,
+        };
+
+        if let Some(Some(entry)) = self.sounds.get_mut(id.0) {
+            entry.volume = volume;
+            entry.audio.set_volume(volume * bus_volume, device);
+        }
+    }
+
+    /// Move a registered sound to another bus
+    #[inline]
+    pub fn set_sound_bus(&mut self, id: SoundId, bus: AudioBus, device: &mut AudioDevice) {
+        let bus_volume = self.effective_volume(bus);
+
+        if let Some(Some(entry)) = self.sounds.get_mut(id.0) {
+            entry.bus = bus;
+            entry.audio.set_volume(entry.volume * bus_volume, device);
+        }
+    }
+
+    /// Play a registered sound
+    #[inline]
+    pub fn play_sound(&self, id: SoundId, device: &mut AudioDevice) {
+        if let Some(sound) = self.sound(id) {
+            sound.play(device);
+        }
+    }
+
+    /// Register a music stream on a bus
+    #[inline]
+    pub fn add_music(&mut self, music: Music, bus: AudioBus, device: &mut AudioDevice) -> MusicId {
+        music.set_volume(self.effective_volume(bus), device);
+
+        self.music.push(Some(MixerEntry {
+            audio: music,
+            bus,
+            volume: 1.,
+        }));
+
+        MusicId(self.music.len() - 1)
+    }
+
+    /// Unregister a music stream, returning it
+    #[inline]
+    pub fn remove_music(&mut self, id: MusicId) -> Option<Music> {
+        self.music
+            .get_mut(id.0)
+            .and_then(|entry| entry.take())
+            .map(|entry| entry.audio)
+    }
+
+    /// Get a registered music stream
+    #[inline]
+    pub fn music(&self, id: MusicId) -> Option<&Music> {
+        self.music
+            .get(id.0)
+            .and_then(|entry| entry.as_ref())
+            .map(|entry| &entry.audio)
+    }
+
+    /// Get a registered music stream
+    #[inline]
+    pub fn music_mut(&mut self, id: MusicId) -> Option<&mut Music> {
+        self.music
+            .get_mut(id.0)
+            .and_then(|entry| entry.as_mut())
+            .map(|entry| &mut entry.audio)
+    }
+
+    /// Set the volume of a registered music stream (relative to its bus)
+    #[inline]
+    pub fn set_music_volume(&mut self, id: MusicId, volume: f32, device: &mut AudioDevice) {
+        let bus_volume = match self.music.get(id.0).and_then(|entry| entry.as_ref()) {
+            Some(entry) => self.effective_volume(entry.bus),
+            None => return,
+        };
+
+        if let Some(Some(entry)) = self.music.get_mut(id.0) {
+            entry.volume = volume;
+            entry.audio.set_volume(volume * bus_volume, device);
+        }
+    }
+
+    /// Move a registered music stream to another bus
+    #[inline]
+    pub fn set_music_bus(&mut self, id: MusicId, bus: AudioBus, device: &mut AudioDevice) {
+        let bus_volume = self.effective_volume(bus);
+
+        if let Some(Some(entry)) = self.music.get_mut(id.0) {
+            entry.bus = bus;
+            entry.audio.set_volume(entry.volume * bus_volume, device);
+        }
+    }
+
+    /// Updates buffers of all registered music streams
+    #[inline]
+    pub fn update(&mut self, device: &mut AudioDevice) {
+        for entry in self.music.iter_mut().flatten() {
+            entry.audio.update(device);
+        }
+    }
+
+    /// Re-apply effective volumes to all registered sounds and music
+    pub fn apply(&mut self, device: &mut AudioDevice) {
+        let volumes: HashMap<AudioBus, f32> = self
+            .buses
+            .keys()
+            .map(|&bus| (bus, self.effective_volume(bus)))
+            .collect();
+
+        for entry in self.sounds.iter().flatten() {
+            let bus_volume = volumes.get(&entry.bus).copied().unwrap_or(1.);
+
+            entry.audio.set_volume(entry.volume * bus_volume, device);
+        }
+
+        for entry in self.music.iter().flatten() {
+            let bus_volume = volumes.get(&entry.bus).copied().unwrap_or(1.);
+
+            entry.audio.set_volume(entry.volume * bus_volume, device);
+        }
+    }
+}
+
+impl Default for AudioMixer {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Playback state of a [`MusicPlayer`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MusicPlayerState {
+    /// Not playing
+    Stopped,
+    /// Playing the intro track
+    Intro,
+    /// Playing the looped track
+    Loop,
+}
+
+/// Plays an optional intro track once, then switches to a looped track
+///
+/// [`MusicPlayer::update`] starts the loop once the intro has less than half a frame left,
+/// so with an update every frame the tracks are at most about half a frame apart or overlap by as much.
+#[derive(Debug)]
+pub struct MusicPlayer {
+    intro: Option<Music>,
+    main: Music,
+    state: MusicPlayerState,
+    paused: bool,
+}
+
+impl MusicPlayer {
+    /// Create a player for a looped track without an intro
+    #[inline]
+    pub fn new(mut main: Music) -> Self {
+        main.set_looping(true);
+
+        Self {
+            intro: None,
+            main,
+            state: MusicPlayerState::Stopped,
+            paused: false,
+        }
+    }
+
+    /// Create a player for an intro + loop pair
+    #[inline]
+    pub fn with_intro(mut intro: Music, main: Music) -> Self {
+        intro.set_looping(false);
+
+        Self {
+            intro: Some(intro),
+            ..Self::new(main)
+        }
+    }
+
+    /// Load an (optional) intro and a looped track from files
+    #[inline]
+    pub fn from_files(
+        intro_file_name: Option<&str>,
+        loop_file_name: &str,
+    ) -> Result<Self, RaylibError> {
+        let main = Music::from_file(loop_file_name)?;
+
+        match intro_file_name {
+            Some(intro) => Ok(Self::with_intro(Music::from_file(intro)?, main)),
+            None => Ok(Self::new(main)),
+        }
+    }
+
+    /// Current playback state
+    #[inline]
+    pub fn state(&self) -> MusicPlayerState {
+        self.state
+    }
+
+    /// The intro track
+    #[inline]
+    pub fn intro(&self) -> Option<&Music> {
+        self.intro.as_ref()
+    }
+
+    /// The looped track
+    #[inline]
+    pub fn main(&self) -> &Music {
+        &self.main
+    }
+
+    /// The looped track, e.g. to set loop points on it
+    #[inline]
+    pub fn main_mut(&mut self) -> &mut Music {
+        &mut self.main
+    }
+
+    /// Start playing from the beginning (the intro, if there is one)
+    #[inline]
+    pub fn play(&mut self, device: &mut AudioDevice) {
+        self.stop(device);
+        self.paused = false;
+
+        match &self.intro {
+            Some(intro) => {
+                intro.play(device);
+                self.state = MusicPlayerState::Intro;
+            }
+            None => {
+                self.main.play(device);
+                self.state = MusicPlayerState::Loop;
+            }
+        }
+    }
+
+    /// Stop playing
+    #[inline]
+    pub fn stop(&mut self, device: &mut AudioDevice) {
+        if let Some(intro) = &self.intro {
+            intro.stop(device);
+        }
+        self.main.stop(device);
+
+        self.state = MusicPlayerState::Stopped;
+    }
+
+    /// Pause playing
+    #[inline]
+    pub fn pause(&mut self, device: &mut AudioDevice) {
+        if let Some(music) = self.current() {
+            music.pause(device);
+            self.paused = true;
+        }
+    }
+
+    /// Resume playing
+    #[inline]
+    pub fn resume(&mut self, device: &mut AudioDevice) {
+        if let Some(music) = self.current() {
+            music.resume(device);
+            self.paused = false;
+        }
+    }
+
+    /// Check if music is playing
+    #[inline]
+    pub fn is_playing(&self) -> bool {
+        self.state != MusicPlayerState::Stopped && !self.paused
+    }
+
+    /// Set volume for both tracks (1.0 is max level)
+    #[inline]
+    pub fn set_volume(&self, volume: f32, device: &mut AudioDevice) {
+        if let Some(intro) = &self.intro {
+            intro.set_volume(volume, device);
+        }
+        self.main.set_volume(volume, device);
+    }
+
+    /// Set pitch for both tracks (1.0 is base level)
+    #[inline]
+    pub fn set_pitch(&self, pitch: f32, device: &mut AudioDevice) {
+        if let Some(intro) = &self.intro {
+            intro.set_pitch(pitch, device);
+        }
+        self.main.set_pitch(pitch, device);
+    }
+
+    /// Set pan for both tracks (0.5 is center)
+    #[inline]
+    pub fn set_pan(&self, pan: f32, device: &mut AudioDevice) {
+        if let Some(intro) = &self.intro {
+            intro.set_pan(pan, device);
+        }
+        self.main.set_pan(pan, device);
+    }
+
+    /// Updates buffers for music streaming and switches from the intro to the loop when it ends,
+    /// call it every frame
+    pub fn update(&mut self, device: &mut AudioDevice) {
+        match self.state {
+            MusicPlayerState::Stopped => {}
+            MusicPlayerState::Intro => {
+                if let Some(intro) = &self.intro {
+                    intro.update(device);
+
+                    // the next update comes about a frame later (hitches aside), start the loop now if
+                    // the intro ends before half of that, it still plays its last buffers meanwhile
+                    let remaining = intro
+                        .get_time_length(device)
+                        .saturating_sub(intro.get_time_played(device));
+                    let frame_time = unsafe { ffi::GetFrameTime() }.max(0.).min(0.1);
+                    let lead = Duration::from_secs_f32(frame_time / 2.);
+
+                    if !self.paused && (remaining <= lead || !intro.is_playing(device)) {
+                        self.main.play(device);
+                        self.main.update(device);
+                        self.state = MusicPlayerState::Loop;
+                    }
+                }
+            }
+            MusicPlayerState::Loop => {
+                if let Some(intro) = self.intro.as_ref().filter(|intro| intro.is_playing(device)) {
+                    intro.update(device);
+                }
+
+                self.main.update(device)
+            }
+        }
+    }
+
+    #[inline]
+    fn current(&self) -> Option<&Music> {
+        match self.state {
+            MusicPlayerState::Stopped => None,
+            MusicPlayerState::Intro => self.intro.as_ref(),
+            MusicPlayerState::Loop => Some(&self.main),
+        }
+    }
+}
+
+//pub type AudioCallback = Option<unsafe extern "C" fn(bufferData: *mut core::ffi::c_void, frames: u32, )>;
+
+/*
+    /// Audio thread callback to request new data
+    #[inline]
+    pub fn SetAudioStreamCallback(stream: AudioStream, callback: AudioCallback);
+    /// Attach audio stream processor to stream
+    #[inline]
+    pub fn AttachAudioStreamProcessor(stream: AudioStream, processor: AudioCallback);
+    /// Detach audio stream processor from stream
+    #[inline]
+    pub fn DetachAudioStreamProcessor(stream: AudioStream, processor: AudioCallback);
+    /// Attach audio stream processor to the entire audio pipeline
+    #[inline]
+    pub fn AttachAudioMixedProcessor(processor: AudioCallback);
+    /// Detach audio stream processor from the entire audio pipeline
+    #[inline]
+    pub fn DetachAudioMixedProcessor(processor: AudioCallback);
+*/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unregistered_bus_follows_master() {
+        let mut mixer = AudioMixer::new();
+
+        mixer.buses.get_mut(&AudioBus::Master).unwrap().volume = 0.5;
+        assert_eq!(mixer.effective_volume(AudioBus::Custom(7)), 0.5);
+
+        mixer.buses.get_mut(&AudioBus::Master).unwrap().muted = true;
+        assert_eq!(mixer.effective_volume(AudioBus::Custom(7)), 0.);
+    }
+}
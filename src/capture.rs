@@ -0,0 +1,243 @@
+use crate::{
+    color::Color,
+    texture::{Image, Texture2D},
+};
+use nokhwa::{
+    pixel_format::RgbAFormat,
+    utils::{ApiBackend, CameraIndex, RequestedFormat, RequestedFormatType},
+    Camera,
+};
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, TryRecvError},
+        Arc,
+    },
+    thread,
+};
+
+/// Frames waiting to be uploaded, newer frames are dropped while it's full
+const FRAME_QUEUE: usize = 2;
+
+/// Webcam or other capture device
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CaptureDevice {
+    /// Index to open the device with
+    pub index: u32,
+    /// Name shown to users
+    pub name: String,
+}
+
+/// Connected capture devices, empty if they can't be queried
+pub fn capture_devices() -> Vec<CaptureDevice> {
+    let devices = match nokhwa::query(ApiBackend::Auto) {
+        Ok(devices) => devices,
+        Err(_) => return Vec::new(),
+    };
+
+    devices
+        .into_iter()
+        .filter_map(|info| match info.index() {
+            CameraIndex::Index(index) => Some(CaptureDevice {
+                index: *index,
+                name: info.human_name(),
+            }),
+            CameraIndex::String(_) => None,
+        })
+        .collect()
+}
+
+struct Frame {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+/// Frames of a capture device as an updating texture
+///
+/// Needs the `capture` feature. The device is opened and read on a worker thread (with v4l2,
+/// AVFoundation or Media Foundation depending on the platform), [`CameraCapture::update`]
+/// uploads the latest frame.
+pub struct CameraCapture {
+    texture: Texture2D,
+    frames: Receiver<Frame>,
+    stop: Arc<AtomicBool>,
+    active: bool,
+    frame_count: u64,
+}
+
+impl fmt::Debug for CameraCapture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CameraCapture")
+            .field("texture", &self.texture)
+            .field("active", &self.active)
+            .field("frame_count", &self.frame_count)
+            .finish_non_exhaustive()
+    }
+}
+
+impl CameraCapture {
+    /// Open a capture device at its highest frame rate, `None` if it can't be opened
+    ///
+    /// Blocks until the device has started streaming.
+    /// The texture is black until the first frame arrives.
+    pub fn open(index: u32) -> Option<Self> {
+        let (opened_sender, opened) = mpsc::channel();
+        let (frame_sender, frames) = mpsc::sync_channel(FRAME_QUEUE);
+        let stop = Arc::new(AtomicBool::new(false));
+        let worker_stop = stop.clone();
+
+        // some backends can't move cameras between threads, so it's created on the worker
+        thread::Builder::new()
+            .name("camera capture".to_string())
+            .spawn(move || {
+                let format = RequestedFormat::new::<RgbAFormat>(
+                    RequestedFormatType::AbsoluteHighestFrameRate,
+                );
+
+                let mut camera = match Camera::new(CameraIndex::Index(index), format) {
+                    Ok(camera) => camera,
+                    Err(_err) => {
+                        #[cfg(debug_assertions)]
+                        crate::core::warn(&format!(
+                            "CameraCapture: can't open device {}: {}",
+                            index, _err
+                        ));
+
+                        let _ = opened_sender.send(None);
+                        return;
+                    }
+                };
+
+                if let Err(_err) = camera.open_stream() {
+                    #[cfg(debug_assertions)]
+                    crate::core::warn(&format!(
+                        "CameraCapture: can't start device {}: {}",
+                        index, _err
+                    ));
+
+                    let _ = opened_sender.send(None);
+                    return;
+                }
+
+                let resolution = camera.resolution();
+                let _ = opened_sender.send(Some((resolution.width(), resolution.height())));
+
+                while !worker_stop.load(Ordering::Relaxed) {
+                    let image = match camera
+                        .frame()
+                        .and_then(|frame| frame.decode_image::<RgbAFormat>())
+                    {
+                        Ok(image) => image,
+                        Err(_) => break,
+                    };
+
+                    let frame = Frame {
+                        width: image.width(),
+                        height: image.height(),
+                        pixels: image.into_raw(),
+                    };
+
+                    match frame_sender.try_send(frame) {
+                        Ok(()) | Err(mpsc::TrySendError::Full(_)) => {}
+                        Err(mpsc::TrySendError::Disconnected(_)) => break,
+                    }
+                }
+
+                let _ = camera.stop_stream();
+            })
+            .ok()?;
+
+        let (width, height) = opened.recv().ok().flatten()?;
+
+        let image = Image::generate_color(width, height, Color::BLACK);
+        let texture = Texture2D::from_image(&image).ok()?;
+
+        Some(Self {
+            texture,
+            frames,
+            stop,
+            active: true,
+            frame_count: 0,
+        })
+    }
+
+    /// Texture showing the latest frame
+    #[inline]
+    pub fn texture(&self) -> &Texture2D {
+        &self.texture
+    }
+
+    /// Frame width in pixels
+    #[inline]
+    pub fn width(&self) -> u32 {
+        self.texture.width()
+    }
+
+    /// Frame height in pixels
+    #[inline]
+    pub fn height(&self) -> u32 {
+        self.texture.height()
+    }
+
+    /// Number of frames uploaded so far
+    #[inline]
+    pub fn frame_count(&self) -> u64 {
+        self.frame_count
+    }
+
+    /// Check if the device is still delivering frames (it stops when unplugged or on errors)
+    #[inline]
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Upload the latest frame, `true` if there was a new one
+    ///
+    /// Call once per frame. The texture is recreated if the frame size changed.
+    pub fn update(&mut self) -> bool {
+        let mut latest = None;
+
+        loop {
+            match self.frames.try_recv() {
+                Ok(frame) => latest = Some(frame),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    self.active = false;
+                    break;
+                }
+            }
+        }
+
+        let frame = match latest {
+            Some(frame) => frame,
+            None => return false,
+        };
+
+        if (frame.width, frame.height) != (self.texture.width(), self.texture.height()) {
+            let image = Image::generate_color(frame.width, frame.height, Color::BLACK);
+
+            match Texture2D::from_image(&image) {
+                Ok(texture) => self.texture = texture,
+                Err(_) => return false,
+            }
+        }
+
+        if self.texture.update(&frame.pixels) {
+            self.frame_count += 1;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl Drop for CameraCapture {
+    #[inline]
+    fn drop(&mut self) {
+        // the worker exits after its current frame
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,218 @@
+use crate::{
+    collision::{get_capsule_cast_box, get_capsule_cast_obb, Capsule, MeshCollider, OrientedBox},
+    math::{vec3, BoundingBox, RayCollision, Vector3},
+};
+
+/// Static geometry a character collides with
+#[derive(Clone, Debug)]
+pub enum StaticCollider {
+    /// Axis-aligned box
+    Box(BoundingBox),
+    /// Oriented box
+    OrientedBox(OrientedBox),
+    /// Triangle mesh
+    Mesh(MeshCollider),
+}
+
+impl StaticCollider {
+    /// Get collision info for a capsule moving by `motion` against the collider
+    #[inline]
+    pub fn get_capsule_cast(&self, capsule: &Capsule, motion: Vector3) -> RayCollision {
+        match self {
+            Self::Box(bbox) => get_capsule_cast_box(capsule, motion, *bbox),
+            Self::OrientedBox(obb) => get_capsule_cast_obb(capsule, motion, obb),
+            Self::Mesh(mesh) => mesh.get_capsule_cast(capsule, motion),
+        }
+    }
+}
+
+/// Kinematic capsule character: moves and slides against static colliders, detects ground and climbs steps
+///
+/// Up is +Y. The character doesn't have a velocity, apply gravity etc. to the motion yourself.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CharacterController3D {
+    /// Position of the bottom of the capsule (the feet)
+    pub position: Vector3,
+    /// Total height of the capsule, including the caps
+    pub height: f32,
+    /// Capsule radius
+    pub radius: f32,
+    /// Steepest slope (in degrees) that counts as ground
+    pub max_slope: f32,
+    /// Highest step the character climbs without jumping
+    pub step_offset: f32,
+    /// Gap kept between the capsule and colliders
+    pub skin_width: f32,
+    /// Maximum number of slides per movement
+    pub max_slides: u32,
+    grounded: bool,
+    ground_normal: Option<Vector3>,
+}
+
+impl CharacterController3D {
+    /// Create new character controller with default slope limit (45 degrees), step offset (a quarter of the height),
+    /// skin width (0.01) and max slides (4)
+    #[inline]
+    pub fn new(position: Vector3, height: f32, radius: f32) -> Self {
+        Self {
+            position,
+            height,
+            radius,
+            max_slope: 45.,
+            step_offset: height / 4.,
+            skin_width: 0.01,
+            max_slides: 4,
+            grounded: false,
+            ground_normal: None,
+        }
+    }
+
+    /// Collision capsule at the current position
+    #[inline]
+    pub fn capsule(&self) -> Capsule {
+        let radius = self.radius.min(self.height / 2.);
+
+        Capsule::new(
+            vec3::add(self.position, vec3::new(0., radius, 0.)),
+            vec3::add(self.position, vec3::new(0., self.height - radius, 0.)),
+            radius,
+        )
+    }
+
+    /// Is the character standing on walkable ground (as of the last movement)?
+    #[inline]
+    pub fn is_grounded(&self) -> bool {
+        self.grounded
+    }
+
+    /// Normal of the ground the character stands on (as of the last movement)
+    #[inline]
+    pub fn ground_normal(&self) -> Option<Vector3> {
+        self.ground_normal
+    }
+
+    /// Move the character, sliding along whatever it hits
+    ///
+    /// Returns the actual displacement.
+    #[inline]
+    pub fn move_and_slide(&mut self, motion: Vector3, colliders: &[StaticCollider]) -> Vector3 {
+        let start = self.position;
+
+        let horizontal = vec3::new(motion.x, 0., motion.z);
+        let vertical = vec3::new(0., motion.y, 0.);
+
+        if vec3::length(horizontal) > 0. {
+            self.move_horizontal(horizontal, colliders);
+        }
+        if motion.y != 0. {
+            self.slide(vertical, colliders, false);
+        }
+
+        self.update_ground(colliders);
+
+        vec3::sub(self.position, start)
+    }
+
+    fn move_horizontal(&mut self, motion: Vector3, colliders: &[StaticCollider]) {
+        let start = self.position;
+        let blocked = self.slide(motion, colliders, true);
+
+        if !blocked || !self.grounded || self.step_offset <= 0. {
+            return;
+        }
+
+        // try again from `step_offset` higher, then go back down onto the step
+        let slid = self.position;
+        self.position = start;
+
+        self.slide(vec3::new(0., self.step_offset, 0.), colliders, false);
+        self.slide(motion, colliders, true);
+
+        let down = vec3::new(0., -(self.step_offset + self.skin_width), 0.);
+        let hit = self.cast(down, colliders);
+
+        let moved =
+            |from: Vector3, to: Vector3| vec3::length(vec3::new(to.x - from.x, 0., to.z - from.z));
+
+        if hit.hit
+            && self.is_walkable(hit.normal)
+            && moved(start, self.position) > moved(start, slid)
+        {
+            self.position.y -= (hit.distance - self.skin_width).max(0.);
+        } else {
+            self.position = slid;
+        }
+    }
+
+    /// Returns whether the movement was blocked by a non-walkable surface
+    fn slide(&mut self, motion: Vector3, colliders: &[StaticCollider], horizontal: bool) -> bool {
+        let mut remaining = motion;
+        let mut blocked = false;
+
+        for _ in 0..self.max_slides {
+            let length = vec3::length(remaining);
+
+            if length <= f32::EPSILON {
+                break;
+            }
+
+            let hit = self.cast(remaining, colliders);
+
+            if !hit.hit {
+                self.position = vec3::add(self.position, remaining);
+                break;
+            }
+
+            let travel = (hit.distance - self.skin_width).max(0.).min(length);
+            self.position = vec3::add(self.position, vec3::scale(remaining, travel / length));
+
+            let mut normal = hit.normal;
+
+            if horizontal && !self.is_walkable(normal) {
+                blocked = true;
+
+                // walls and steep slopes block horizontal movement instead of lifting the character
+                normal = vec3::normalize(vec3::new(normal.x, 0., normal.z));
+            }
+
+            let left = vec3::scale(remaining, 1. - travel / length);
+            remaining = vec3::sub(left, vec3::scale(normal, vec3::dot(left, normal)));
+        }
+
+        blocked
+    }
+
+    fn update_ground(&mut self, colliders: &[StaticCollider]) {
+        let hit = self.cast(vec3::new(0., -2. * self.skin_width, 0.), colliders);
+
+        if hit.hit && self.is_walkable(hit.normal) {
+            self.grounded = true;
+            self.ground_normal = Some(hit.normal);
+        } else {
+            self.grounded = false;
+            self.ground_normal = None;
+        }
+    }
+
+    fn cast(&self, motion: Vector3, colliders: &[StaticCollider]) -> RayCollision {
+        let capsule = self.capsule();
+
+        colliders
+            .iter()
+            .map(|collider| collider.get_capsule_cast(&capsule, motion))
+            .filter(|hit| hit.hit)
+            .min_by(|a, b| a.distance.total_cmp(&b.distance))
+            .unwrap_or(RayCollision {
+                hit: false,
+                distance: 0.,
+                point: vec3::new(0., 0., 0.),
+                normal: vec3::new(0., 0., 0.),
+            })
+    }
+
+    #[inline]
+    fn is_walkable(&self, normal: Vector3) -> bool {
+        normal.y >= self.max_slope.to_radians().cos()
+    }
+}
@@ -1,6 +1,9 @@
 use crate::{
     ffi,
-    math::{BoundingBox, Matrix, Ray, RayCollision, Rectangle, Vector2, Vector3},
+    math::{
+        columns, transform_point, vec3, BoundingBox, Matrix, Quaternion, Ray, RayCollision,
+        Rectangle, Vector2, Vector3,
+    },
     model::Mesh,
 };
 
@@ -155,3 +158,796 @@ pub fn get_ray_collision_quad(
         ffi::GetRayCollisionQuad(ray.into(), p1.into(), p2.into(), p3.into(), p4.into()).into()
     }
 }
+
+/// Oriented bounding box
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OrientedBox {
+    /// Box center
+    pub center: Vector3,
+    /// Half of the box size along each of its axes
+    pub half_extents: Vector3,
+    /// Local axes of the box (orthonormal)
+    pub axes: [Vector3; 3],
+}
+
+impl OrientedBox {
+    /// Create new oriented box rotated by a quaternion
+    #[inline]
+    pub fn new(center: Vector3, half_extents: Vector3, rotation: Quaternion) -> Self {
+        let Quaternion { v, s } = rotation;
+        let (x, y, z, w) = (v.x, v.y, v.z, s);
+
+        Self {
+            center,
+            half_extents,
+            axes: [
+                vec3::new(
+                    1. - 2. * (y * y + z * z),
+                    2. * (x * y + z * w),
+                    2. * (x * z - y * w),
+                ),
+                vec3::new(
+                    2. * (x * y - z * w),
+                    1. - 2. * (x * x + z * z),
+                    2. * (y * z + x * w),
+                ),
+                vec3::new(
+                    2. * (x * z + y * w),
+                    2. * (y * z - x * w),
+                    1. - 2. * (x * x + y * y),
+                ),
+            ],
+        }
+    }
+
+    /// Oriented box of a bounding box transformed by a matrix (e.g. a model's bounding box and transform)
+    ///
+    /// The matrix must not contain shear.
+    #[inline]
+    pub fn from_bounding_box(bbox: BoundingBox, transform: &Matrix) -> Self {
+        let m = columns(transform);
+        let center = bbox.center();
+        let extents = bbox.extents();
+
+        let column = |i: usize| vec3::new(m[i][0], m[i][1], m[i][2]);
+        let scales = [
+            vec3::length(column(0)),
+            vec3::length(column(1)),
+            vec3::length(column(2)),
+        ];
+
+        Self {
+            center: transform_point(&m, [center.x, center.y, center.z]),
+            half_extents: vec3::new(
+                extents.x * scales[0],
+                extents.y * scales[1],
+                extents.z * scales[2],
+            ),
+            axes: [
+                vec3::normalize(column(0)),
+                vec3::normalize(column(1)),
+                vec3::normalize(column(2)),
+            ],
+        }
+    }
+
+    /// Closest point inside (or on the surface of) the box
+    #[inline]
+    pub fn closest_point(&self, point: Vector3) -> Vector3 {
+        let d = vec3::sub(point, self.center);
+        let half = [
+            self.half_extents.x,
+            self.half_extents.y,
+            self.half_extents.z,
+        ];
+
+        self.axes
+            .iter()
+            .zip(half)
+            .fold(self.center, |result, (axis, half)| {
+                let dist = vec3::dot(d, *axis).clamp(-half, half);
+
+                vec3::add(result, vec3::scale(*axis, dist))
+            })
+    }
+
+    /// Check if a point is inside the box (or on its surface)
+    #[inline]
+    pub fn contains(&self, point: Vector3) -> bool {
+        let d = vec3::sub(point, self.center);
+        let half = [
+            self.half_extents.x,
+            self.half_extents.y,
+            self.half_extents.z,
+        ];
+
+        self.axes
+            .iter()
+            .zip(half)
+            .all(|(axis, half)| vec3::dot(d, *axis).abs() <= half)
+    }
+}
+
+/// Capsule, a sphere swept along a segment
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Capsule {
+    /// Segment start (center of the first cap)
+    pub start: Vector3,
+    /// Segment end (center of the second cap)
+    pub end: Vector3,
+    /// Radius
+    pub radius: f32,
+}
+
+impl Capsule {
+    /// Create new capsule
+    #[inline]
+    pub const fn new(start: Vector3, end: Vector3, radius: f32) -> Self {
+        Self { start, end, radius }
+    }
+
+    /// Capsule translated by an offset
+    #[inline]
+    pub fn translated(&self, offset: Vector3) -> Self {
+        Self {
+            start: vec3::add(self.start, offset),
+            end: vec3::add(self.end, offset),
+            radius: self.radius,
+        }
+    }
+
+    /// Bounding box of the capsule
+    #[inline]
+    pub fn bounding_box(&self) -> BoundingBox {
+        let r = vec3::new(self.radius, self.radius, self.radius);
+
+        BoundingBox::new(vec3::sub(self.start, r), vec3::add(self.start, r)).merge(
+            &BoundingBox::new(vec3::sub(self.end, r), vec3::add(self.end, r)),
+        )
+    }
+}
+
+/// Check collision between two oriented boxes (separating axis test)
+#[inline]
+#[allow(clippy::needless_range_loop)]
+pub fn check_collision_obbs(a: &OrientedBox, b: &OrientedBox) -> bool {
+    const EPSILON: f32 = 1e-6;
+
+    let ea = [a.half_extents.x, a.half_extents.y, a.half_extents.z];
+    let eb = [b.half_extents.x, b.half_extents.y, b.half_extents.z];
+
+    // rotation of b in a's frame
+    let r: [[f32; 3]; 3] =
+        std::array::from_fn(|i| std::array::from_fn(|j| vec3::dot(a.axes[i], b.axes[j])));
+    // epsilon counters arithmetic errors when edges are parallel
+    let abs_r = r.map(|row| row.map(|v| v.abs() + EPSILON));
+
+    let d = vec3::sub(b.center, a.center);
+    let t = [
+        vec3::dot(d, a.axes[0]),
+        vec3::dot(d, a.axes[1]),
+        vec3::dot(d, a.axes[2]),
+    ];
+
+    // a's axes
+    for i in 0..3 {
+        let rb = eb[0] * abs_r[i][0] + eb[1] * abs_r[i][1] + eb[2] * abs_r[i][2];
+
+        if t[i].abs() > ea[i] + rb {
+            return false;
+        }
+    }
+
+    // b's axes
+    for j in 0..3 {
+        let ra = ea[0] * abs_r[0][j] + ea[1] * abs_r[1][j] + ea[2] * abs_r[2][j];
+        let dist = t[0] * r[0][j] + t[1] * r[1][j] + t[2] * r[2][j];
+
+        if dist.abs() > ra + eb[j] {
+            return false;
+        }
+    }
+
+    // cross products of the axes
+    for i in 0..3 {
+        let (i1, i2) = ((i + 1) % 3, (i + 2) % 3);
+
+        for j in 0..3 {
+            let (j1, j2) = ((j + 1) % 3, (j + 2) % 3);
+
+            let ra = ea[i1] * abs_r[i2][j] + ea[i2] * abs_r[i1][j];
+            let rb = eb[j1] * abs_r[i][j2] + eb[j2] * abs_r[i][j1];
+            let dist = t[i2] * r[i1][j] - t[i1] * r[i2][j];
+
+            if dist.abs() > ra + rb {
+                return false;
+            }
+        }
+    }
+
+    true
+}
+
+/// Check collision between oriented box and sphere
+#[inline]
+pub fn check_collision_obb_sphere(obb: &OrientedBox, center: Vector3, radius: f32) -> bool {
+    vec3::length(vec3::sub(obb.closest_point(center), center)) <= radius
+}
+
+/// Check collision between two capsules
+#[inline]
+pub fn check_collision_capsules(a: &Capsule, b: &Capsule) -> bool {
+    let (p, q) = closest_points_segments(a.start, a.end, b.start, b.end);
+
+    vec3::length(vec3::sub(p, q)) <= a.radius + b.radius
+}
+
+/// Check collision between capsule and sphere
+#[inline]
+pub fn check_collision_capsule_sphere(capsule: &Capsule, center: Vector3, radius: f32) -> bool {
+    let p = closest_point_segment(capsule.start, capsule.end, center);
+
+    vec3::length(vec3::sub(p, center)) <= capsule.radius + radius
+}
+
+/// Check collision between capsule and bounding box
+#[inline]
+pub fn check_collision_capsule_box(capsule: &Capsule, bbox: BoundingBox) -> bool {
+    let (dist, _, _) =
+        segment_distance(capsule.start, capsule.end, &|p| closest_point_box(bbox, p));
+
+    dist <= capsule.radius
+}
+
+/// Check collision between capsule and oriented box
+#[inline]
+pub fn check_collision_capsule_obb(capsule: &Capsule, obb: &OrientedBox) -> bool {
+    let (dist, _, _) = segment_distance(capsule.start, capsule.end, &|p| obb.closest_point(p));
+
+    dist <= capsule.radius
+}
+
+/// Get collision info for a sphere moving by `motion` against a box (swept sphere)
+///
+/// `distance` is how far the sphere travels before touching, `point` is the contact point on the box.
+#[inline]
+pub fn get_sphere_cast_box(
+    center: Vector3,
+    radius: f32,
+    motion: Vector3,
+    bbox: BoundingBox,
+) -> RayCollision {
+    sweep(center, center, radius, motion, &|p| {
+        closest_point_box(bbox, p)
+    })
+}
+
+/// Get collision info for a capsule moving by `motion` against a box (swept capsule)
+///
+/// `distance` is how far the capsule travels before touching, `point` is the contact point on the box.
+#[inline]
+pub fn get_capsule_cast_box(capsule: &Capsule, motion: Vector3, bbox: BoundingBox) -> RayCollision {
+    sweep(capsule.start, capsule.end, capsule.radius, motion, &|p| {
+        closest_point_box(bbox, p)
+    })
+}
+
+/// Get collision info for a capsule moving by `motion` against an oriented box (swept capsule)
+///
+/// `distance` is how far the capsule travels before touching, `point` is the contact point on the box.
+#[inline]
+pub fn get_capsule_cast_obb(capsule: &Capsule, motion: Vector3, obb: &OrientedBox) -> RayCollision {
+    sweep(capsule.start, capsule.end, capsule.radius, motion, &|p| {
+        obb.closest_point(p)
+    })
+}
+
+/// Get collision info for a sphere moving by `motion` against a triangle (swept sphere)
+#[inline]
+pub fn get_sphere_cast_triangle(
+    center: Vector3,
+    radius: f32,
+    motion: Vector3,
+    triangle: [Vector3; 3],
+) -> RayCollision {
+    sweep(center, center, radius, motion, &|p| {
+        closest_point_triangle(p, triangle)
+    })
+}
+
+/// Static triangle mesh with a bounding volume hierarchy, for casts against detailed geometry
+#[derive(Clone, Debug)]
+pub struct MeshCollider {
+    triangles: Vec<[Vector3; 3]>,
+    nodes: Vec<BvhNode>,
+}
+
+#[derive(Clone, Debug)]
+struct BvhNode {
+    bbox: BoundingBox,
+    /// First triangle for leaves, first child for inner nodes (the second one follows it)
+    first: usize,
+    /// Triangle count, 0 for inner nodes
+    count: usize,
+}
+
+impl MeshCollider {
+    const LEAF_SIZE: usize = 4;
+
+    /// Build from a list of triangles (in world space)
+    #[inline]
+    pub fn from_triangles(triangles: Vec<[Vector3; 3]>) -> Self {
+        let mut collider = Self {
+            triangles,
+            nodes: Vec::new(),
+        };
+
+        if !collider.triangles.is_empty() {
+            collider.nodes.push(BvhNode {
+                bbox: BoundingBox::new(vec3::new(0., 0., 0.), vec3::new(0., 0., 0.)),
+                first: 0,
+                count: 0,
+            });
+            collider.build(0, 0, collider.triangles.len());
+        }
+
+        collider
+    }
+
+    /// Build from mesh vertex data (must be available on the CPU), transformed into world space
+    #[inline]
+    pub fn from_mesh(mesh: &Mesh, transform: Matrix) -> Self {
+        Self::from_triangles(mesh_triangles(mesh, transform))
+    }
+
+    /// Triangles of the collider
+    #[inline]
+    pub fn triangles(&self) -> &[[Vector3; 3]] {
+        &self.triangles
+    }
+
+    /// Bounding box of the whole collider, `None` if it's empty
+    #[inline]
+    pub fn bounding_box(&self) -> Option<BoundingBox> {
+        self.nodes.first().map(|node| node.bbox)
+    }
+
+    /// Get collision info between ray and the collider
+    #[inline]
+    pub fn get_ray_collision(&self, ray: Ray) -> RayCollision {
+        let mut result = no_collision();
+
+        self.visit(
+            &|bbox: BoundingBox| {
+                let hit = get_ray_collision_box(ray, bbox);
+
+                hit.hit || bbox.contains(ray.position)
+            },
+            &mut |[p1, p2, p3]: [Vector3; 3]| {
+                let hit = get_ray_collision_triangle(ray, p1, p2, p3);
+
+                if hit.hit && (!result.hit || hit.distance < result.distance) {
+                    result = hit;
+                }
+            },
+        );
+
+        result
+    }
+
+    /// Get collision info for a sphere moving by `motion` against the collider (swept sphere)
+    #[inline]
+    pub fn get_sphere_cast(&self, center: Vector3, radius: f32, motion: Vector3) -> RayCollision {
+        self.get_capsule_cast(&Capsule::new(center, center, radius), motion)
+    }
+
+    /// Get collision info for a capsule moving by `motion` against the collider (swept capsule)
+    #[inline]
+    pub fn get_capsule_cast(&self, capsule: &Capsule, motion: Vector3) -> RayCollision {
+        let start = capsule.bounding_box();
+        let swept = start.merge(&capsule.translated(motion).bounding_box());
+
+        let mut result = no_collision();
+
+        self.visit(
+            &|bbox: BoundingBox| check_collision_boxes(bbox, swept),
+            &mut |triangle: [Vector3; 3]| {
+                let hit = sweep(capsule.start, capsule.end, capsule.radius, motion, &|p| {
+                    closest_point_triangle(p, triangle)
+                });
+
+                if hit.hit && (!result.hit || hit.distance < result.distance) {
+                    result = hit;
+                }
+            },
+        );
+
+        result
+    }
+
+    /// Check collision between capsule and the collider
+    #[inline]
+    pub fn check_collision_capsule(&self, capsule: &Capsule) -> bool {
+        let bounds = capsule.bounding_box();
+        let mut collides = false;
+
+        self.visit(
+            &|bbox: BoundingBox| check_collision_boxes(bbox, bounds),
+            &mut |triangle: [Vector3; 3]| {
+                let (dist, _, _) = segment_distance(capsule.start, capsule.end, &|p| {
+                    closest_point_triangle(p, triangle)
+                });
+
+                collides |= dist <= capsule.radius;
+            },
+        );
+
+        collides
+    }
+
+    fn build(&mut self, node: usize, first: usize, count: usize) {
+        let bbox = self.triangles[first..(first + count)]
+            .iter()
+            .flatten()
+            .fold(None, |bbox: Option<BoundingBox>, p| {
+                let point = BoundingBox::new(*p, *p);
+
+                Some(bbox.map_or(point, |bbox| bbox.merge(&point)))
+            })
+            .unwrap();
+
+        self.nodes[node].bbox = bbox;
+
+        if count <= Self::LEAF_SIZE {
+            self.nodes[node].first = first;
+            self.nodes[node].count = count;
+
+            return;
+        }
+
+        // split at the median along the longest axis
+        let size = vec3::sub(bbox.max, bbox.min);
+        let axis = if size.x >= size.y && size.x >= size.z {
+            0
+        } else if size.y >= size.z {
+            1
+        } else {
+            2
+        };
+
+        let centroid = |tri: &[Vector3; 3]| -> f32 {
+            let sum = vec3::add(vec3::add(tri[0], tri[1]), tri[2]);
+
+            [sum.x, sum.y, sum.z][axis]
+        };
+
+        let half = count / 2;
+        self.triangles[first..(first + count)]
+            .select_nth_unstable_by(half, |a, b| centroid(a).total_cmp(&centroid(b)));
+
+        let child = self.nodes.len();
+        self.nodes[node].first = child;
+        self.nodes[node].count = 0;
+
+        self.nodes.push(self.nodes[node].clone());
+        self.nodes.push(self.nodes[node].clone());
+
+        self.build(child, first, half);
+        self.build(child + 1, first + half, count - half);
+    }
+
+    fn visit(&self, test: &dyn Fn(BoundingBox) -> bool, f: &mut dyn FnMut([Vector3; 3])) {
+        if self.nodes.is_empty() {
+            return;
+        }
+
+        let mut stack = vec![0];
+
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+
+            if !test(node.bbox) {
+                continue;
+            }
+
+            if node.count == 0 {
+                stack.push(node.first);
+                stack.push(node.first + 1);
+            } else {
+                for triangle in self.triangles[node.first..(node.first + node.count)].iter() {
+                    f(*triangle);
+                }
+            }
+        }
+    }
+}
+
+/// Mesh triangles (vertex data must be available on the CPU), transformed into world space
+pub(crate) fn mesh_triangles(mesh: &Mesh, transform: Matrix) -> Vec<[Vector3; 3]> {
+    let m = columns(&transform);
+    let raw = mesh.as_raw();
+
+    if raw.vertices.is_null() {
+        return Vec::new();
+    }
+
+    let vertices = unsafe {
+        std::slice::from_raw_parts(raw.vertices as *const [f32; 3], raw.vertexCount as _)
+    };
+    let vertex = |i: usize| transform_point(&m, vertices[i]);
+
+    if raw.indices.is_null() {
+        (0..vertices.len() / 3)
+            .map(|i| [vertex(i * 3), vertex(i * 3 + 1), vertex(i * 3 + 2)])
+            .collect()
+    } else {
+        let indices = unsafe {
+            std::slice::from_raw_parts(raw.indices as *const u16, raw.triangleCount as usize * 3)
+        };
+
+        indices
+            .chunks_exact(3)
+            .map(|tri| {
+                [
+                    vertex(tri[0] as _),
+                    vertex(tri[1] as _),
+                    vertex(tri[2] as _),
+                ]
+            })
+            .collect()
+    }
+}
+
+#[inline]
+fn no_collision() -> RayCollision {
+    RayCollision {
+        hit: false,
+        distance: 0.,
+        point: vec3::new(0., 0., 0.),
+        normal: vec3::new(0., 0., 0.),
+    }
+}
+
+fn closest_point_box(bbox: BoundingBox, point: Vector3) -> Vector3 {
+    vec3::new(
+        point.x.clamp(bbox.min.x, bbox.max.x),
+        point.y.clamp(bbox.min.y, bbox.max.y),
+        point.z.clamp(bbox.min.z, bbox.max.z),
+    )
+}
+
+fn closest_point_segment(a: Vector3, b: Vector3, point: Vector3) -> Vector3 {
+    let ab = vec3::sub(b, a);
+    let len_sq = vec3::dot(ab, ab);
+
+    if len_sq <= f32::EPSILON {
+        return a;
+    }
+
+    let t = (vec3::dot(vec3::sub(point, a), ab) / len_sq).clamp(0., 1.);
+
+    vec3::lerp(a, b, t)
+}
+
+/// Closest points between segments p1-q1 and p2-q2
+fn closest_points_segments(
+    p1: Vector3,
+    q1: Vector3,
+    p2: Vector3,
+    q2: Vector3,
+) -> (Vector3, Vector3) {
+    let d1 = vec3::sub(q1, p1);
+    let d2 = vec3::sub(q2, p2);
+    let r = vec3::sub(p1, p2);
+
+    let a = vec3::dot(d1, d1);
+    let e = vec3::dot(d2, d2);
+    let f = vec3::dot(d2, r);
+
+    if a <= f32::EPSILON && e <= f32::EPSILON {
+        return (p1, p2);
+    }
+
+    let (s, t) = if a <= f32::EPSILON {
+        (0., (f / e).clamp(0., 1.))
+    } else {
+        let c = vec3::dot(d1, r);
+
+        if e <= f32::EPSILON {
+            ((-c / a).clamp(0., 1.), 0.)
+        } else {
+            let b = vec3::dot(d1, d2);
+            let denom = a * e - b * b;
+
+            let mut s = if denom != 0. {
+                ((b * f - c * e) / denom).clamp(0., 1.)
+            } else {
+                0.
+            };
+            let mut t = (b * s + f) / e;
+
+            if t < 0. {
+                t = 0.;
+                s = (-c / a).clamp(0., 1.);
+            } else if t > 1. {
+                t = 1.;
+                s = ((b - c) / a).clamp(0., 1.);
+            }
+
+            (s, t)
+        }
+    };
+
+    (vec3::lerp(p1, q1, s), vec3::lerp(p2, q2, t))
+}
+
+pub(crate) fn closest_point_triangle(p: Vector3, [a, b, c]: [Vector3; 3]) -> Vector3 {
+    let ab = vec3::sub(b, a);
+    let ac = vec3::sub(c, a);
+    let ap = vec3::sub(p, a);
+
+    let d1 = vec3::dot(ab, ap);
+    let d2 = vec3::dot(ac, ap);
+    if d1 <= 0. && d2 <= 0. {
+        return a;
+    }
+
+    let bp = vec3::sub(p, b);
+    let d3 = vec3::dot(ab, bp);
+    let d4 = vec3::dot(ac, bp);
+    if d3 >= 0. && d4 <= d3 {
+        return b;
+    }
+
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0. && d1 >= 0. && d3 <= 0. {
+        return vec3::add(a, vec3::scale(ab, d1 / (d1 - d3)));
+    }
+
+    let cp = vec3::sub(p, c);
+    let d5 = vec3::dot(ab, cp);
+    let d6 = vec3::dot(ac, cp);
+    if d6 >= 0. && d5 <= d6 {
+        return c;
+    }
+
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0. && d2 >= 0. && d6 <= 0. {
+        return vec3::add(a, vec3::scale(ac, d2 / (d2 - d6)));
+    }
+
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0. && (d4 - d3) >= 0. && (d5 - d6) >= 0. {
+        return vec3::lerp(b, c, (d4 - d3) / ((d4 - d3) + (d5 - d6)));
+    }
+
+    let denom = 1. / (va + vb + vc);
+
+    vec3::add(
+        a,
+        vec3::add(vec3::scale(ab, vb * denom), vec3::scale(ac, vc * denom)),
+    )
+}
+
+/// Distance from segment a-b to a convex shape given by its closest point function,
+/// along with the closest points on the segment and the shape
+fn segment_distance(
+    a: Vector3,
+    b: Vector3,
+    closest: &dyn Fn(Vector3) -> Vector3,
+) -> (f32, Vector3, Vector3) {
+    let eval = |s: f32| {
+        let p = vec3::lerp(a, b, s);
+        let q = closest(p);
+
+        (vec3::length(vec3::sub(p, q)), p, q)
+    };
+
+    if vec3::length(vec3::sub(b, a)) <= f32::EPSILON {
+        return eval(0.);
+    }
+
+    // the distance is convex along the segment
+    let (mut lo, mut hi) = (0., 1.);
+
+    for _ in 0..32 {
+        let m1 = lo + (hi - lo) / 3.;
+        let m2 = hi - (hi - lo) / 3.;
+
+        if eval(m1).0 <= eval(m2).0 {
+            hi = m2;
+        } else {
+            lo = m1;
+        }
+    }
+
+    eval((lo + hi) / 2.)
+}
+
+/// Swept capsule (segment a-b with radius) moving by `motion` against a convex shape
+fn sweep(
+    a: Vector3,
+    b: Vector3,
+    radius: f32,
+    motion: Vector3,
+    closest: &dyn Fn(Vector3) -> Vector3,
+) -> RayCollision {
+    let eval = |t: f32| {
+        let offset = vec3::scale(motion, t);
+
+        segment_distance(vec3::add(a, offset), vec3::add(b, offset), closest)
+    };
+
+    let hit = |t: f32, (_, p, q): (f32, Vector3, Vector3)| {
+        let normal = vec3::normalize(vec3::sub(p, q));
+
+        RayCollision {
+            hit: true,
+            distance: t * vec3::length(motion),
+            point: q,
+            normal: if vec3::length(normal) > 0. {
+                normal
+            } else {
+                vec3::normalize(vec3::scale(motion, -1.))
+            },
+        }
+    };
+
+    let start = eval(0.);
+    if start.0 <= radius {
+        return hit(0., start);
+    }
+
+    // the distance is convex over time too, find when it's the smallest
+    let (mut lo, mut hi) = (0., 1.);
+
+    for _ in 0..32 {
+        let m1 = lo + (hi - lo) / 3.;
+        let m2 = hi - (hi - lo) / 3.;
+
+        if eval(m1).0 <= eval(m2).0 {
+            hi = m2;
+        } else {
+            lo = m1;
+        }
+    }
+
+    let closest_t = (lo + hi) / 2.;
+    if eval(closest_t).0 > radius {
+        return no_collision();
+    }
+
+    // then when it first gets within the radius
+    let (mut lo, mut hi) = (0., closest_t);
+
+    for _ in 0..32 {
+        let mid = (lo + hi) / 2.;
+
+        if eval(mid).0 <= radius {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
+    }
+
+    hit(hi, eval(hi))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::tests::{assert_near, rotate_translate};
+
+    #[test]
+    fn oriented_box_from_bounding_box() {
+        let bbox = BoundingBox::new(vec3::new(0., 0., 0.), vec3::new(1., 1., 1.));
+        let obb = OrientedBox::from_bounding_box(bbox, &rotate_translate());
+
+        assert_near(obb.center, vec3::new(4.5, 6.5, 7.5));
+        assert_near(obb.half_extents, vec3::new(0.5, 0.5, 0.5));
+        assert_near(obb.axes[0], vec3::new(0., 1., 0.));
+        assert_near(obb.axes[1], vec3::new(-1., 0., 0.));
+    }
+}
@@ -4,6 +4,7 @@ use crate::{
     texture::{get_pixel_data_size, PixelFormat},
 };
 use static_assertions::{assert_eq_align, assert_eq_size};
+use std::fmt;
 
 /// Color, 4 components, R8G8B8A8 (32bit)
 #[repr(C)]
@@ -287,6 +288,47 @@ impl Color {
         }
     }
 
+    /// Convert an sRGB Color (as in image files and the constants) to linear, alpha is unchanged
+    ///
+    /// Dark colors lose precision in 8 bits, prefer [`Color::normalize_linear`] for shader values.
+    #[inline]
+    pub fn to_linear(self) -> Self {
+        let Vector4 { x, y, z, .. } = self.normalize_linear();
+
+        Self {
+            r: to_byte(x),
+            g: to_byte(y),
+            b: to_byte(z),
+            a: self.a,
+        }
+    }
+
+    /// Convert a linear Color to sRGB, alpha is unchanged
+    #[inline]
+    pub fn to_srgb(self) -> Self {
+        let Vector4 { x, y, z, .. } = self.normalize();
+
+        Self {
+            r: to_byte(linear_to_srgb(x)),
+            g: to_byte(linear_to_srgb(y)),
+            b: to_byte(linear_to_srgb(z)),
+            a: self.a,
+        }
+    }
+
+    /// Get an sRGB Color as linear float [0..1], e.g. for shader values in a linear workflow
+    #[inline]
+    pub fn normalize_linear(self) -> Vector4 {
+        let Vector4 { x, y, z, w } = self.normalize();
+
+        Vector4 {
+            x: srgb_to_linear(x),
+            y: srgb_to_linear(y),
+            z: srgb_to_linear(z),
+            w,
+        }
+    }
+
     /// Get HSV values for a Color, hue [0..360], saturation/value [0..1]
     #[inline]
     pub fn to_hsv(self) -> Vector3 {
@@ -366,6 +408,30 @@ impl Color {
     }
 }
 
+// exact sRGB transfer functions, the 2.2 gamma approximation is off in dark colors
+#[inline]
+fn srgb_to_linear(value: f32) -> f32 {
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+#[inline]
+fn linear_to_srgb(value: f32) -> f32 {
+    if value <= 0.0031308 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1. / 2.4) - 0.055
+    }
+}
+
+#[inline]
+fn to_byte(value: f32) -> u8 {
+    (value.clamp(0., 1.) * 255.).round() as u8
+}
+
 impl From<Color> for ffi::Color {
     #[inline]
     fn from(val: Color) -> Self {
@@ -379,3 +445,11 @@ impl From<ffi::Color> for Color {
         unsafe { std::mem::transmute(value) }
     }
 }
+
+impl fmt::Display for Color {
+    /// Hex notation, `#RRGGBBAA`
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02X}{:02X}{:02X}{:02X}", self.r, self.g, self.b, self.a)
+    }
+}
@@ -1,9 +1,26 @@
-use crate::{drawing::DrawHandle, ffi, math::Vector2, texture::Image};
+use crate::{
+    audio::Wave,
+    drawing::DrawHandle,
+    error::RaylibError,
+    ffi,
+    math::{Rectangle, Vector2},
+    monitor::{glfw, Monitor, VideoMode, WINDOWED_STATE},
+    text::text_to_cstring,
+    texture::Image,
+};
 
 use std::{
-    ffi::{CStr, CString},
+    borrow::Cow,
+    collections::BTreeMap,
+    ffi::{c_char, c_int, c_void, CStr, CString},
+    fs, io,
     marker::PhantomData,
-    sync::atomic::{AtomicBool, Ordering},
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, AtomicU32, Ordering},
+        Mutex, TryLockError,
+    },
     time::Duration,
 };
 
@@ -13,6 +30,273 @@ pub use ffi::{
 };
 
 static INITIALIZED: AtomicBool = AtomicBool::new(false);
+pub(crate) static DRAWING: AtomicBool = AtomicBool::new(false);
+/// DPI scale of the window when the last frame ended, as `f32` bits, 0 before the first frame
+static WINDOW_SCALE: AtomicU32 = AtomicU32::new(0);
+/// Whether the DPI scale changed during the last frame
+static WINDOW_SCALE_CHANGED: AtomicBool = AtomicBool::new(false);
+/// Region the cursor is confined to, in window coordinates
+static CURSOR_CONFINEMENT: Mutex<Option<Rectangle>> = Mutex::new(None);
+
+/// Longest trace log message passed to [`Raylib::set_trace_log`] callbacks, in bytes
+pub const MAX_TRACE_LOG_LENGTH: usize = 1024;
+
+type TraceLogHandler = Box<dyn FnMut(TraceLogLevel, &str) + Send>;
+
+static TRACE_LOG: Mutex<Option<TraceLogHandler>> = Mutex::new(None);
+
+/// Messages logged while the handler was busy (e.g. by raylib from inside it), passed on when it returns
+static PENDING_TRACE_LOG: Mutex<Vec<(TraceLogLevel, String)>> = Mutex::new(Vec::new());
+
+// the printf family is inline in the UCRT headers, the legacy library exports it
+#[cfg_attr(target_env = "msvc", link(name = "legacy_stdio_definitions"))]
+extern "C" {
+    fn vsnprintf(
+        buffer: *mut c_char,
+        size: usize,
+        format: *const c_char,
+        args: *mut c_void,
+    ) -> c_int;
+}
+
+/// Format a raylib log message and pass it to the handler set with [`Raylib::set_trace_log`]
+unsafe extern "C" fn trace_log_callback(level: c_int, format: *const c_char, args: *mut c_void) {
+    let mut buffer = [0 as c_char; MAX_TRACE_LOG_LENGTH];
+
+    if format.is_null() || vsnprintf(buffer.as_mut_ptr(), buffer.len(), format, args) < 0 {
+        return;
+    }
+
+    let message = CStr::from_ptr(buffer.as_ptr()).to_string_lossy();
+
+    let level = match level {
+        1 => TraceLogLevel::Trace,
+        2 => TraceLogLevel::Debug,
+        3 => TraceLogLevel::Info,
+        4 => TraceLogLevel::Warning,
+        5 => TraceLogLevel::Error,
+        6 => TraceLogLevel::Fatal,
+        _ => TraceLogLevel::Info,
+    };
+
+    let mut handler = match TRACE_LOG.try_lock() {
+        Ok(handler) => handler,
+        Err(TryLockError::Poisoned(e)) => e.into_inner(),
+        Err(TryLockError::WouldBlock) => {
+            PENDING_TRACE_LOG
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .push((level, message.into_owned()));
+            return;
+        }
+    };
+
+    let mut messages = vec![(level, message.into_owned())];
+
+    while !messages.is_empty() {
+        for (level, message) in messages {
+            let callback = match handler.as_mut() {
+                Some(callback) => callback,
+                None => return,
+            };
+
+            // unwinding into C is undefined behavior
+            if panic::catch_unwind(AssertUnwindSafe(|| callback(level, &message))).is_err() {
+                // raylib logs to stdout again
+                *handler = None;
+                drop(handler);
+                ffi::SetTraceLogCallback(None);
+
+                warn(&format!(
+                    "trace log callback panicked on: {message}, it was removed"
+                ));
+                return;
+            }
+        }
+
+        messages =
+            std::mem::take(&mut *PENDING_TRACE_LOG.lock().unwrap_or_else(|e| e.into_inner()));
+    }
+}
+
+/// Log a warning of the wrappers through raylib's trace log
+///
+/// It reaches the handler set with [`Raylib::set_trace_log`] (e.g. the `log-bridge`), respects
+/// the trace log level, and is printed by raylib otherwise.
+pub(crate) fn warn(message: &str) {
+    let message = text_to_cstring(message);
+
+    unsafe {
+        ffi::TraceLog(
+            TraceLogLevel::Warning as _,
+            b"%s\0".as_ptr() as *const _,
+            message.as_ptr(),
+        )
+    }
+}
+
+/// Is the window (and its OpenGL context) alive?
+#[inline]
+pub(crate) fn is_window_alive() -> bool {
+    INITIALIZED.load(Ordering::Relaxed)
+}
+
+/// Incremented by every window initialization
+static CONTEXT_GENERATION: AtomicU32 = AtomicU32::new(0);
+
+/// Generations of the contexts the tracked GPU objects were created in
+///
+/// An id can be held more than once, by objects of different contexts.
+static CONTEXT_OBJECTS: Mutex<BTreeMap<ContextObject, Vec<u32>>> = Mutex::new(BTreeMap::new());
+
+/// GPU object owned by a resource
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum ContextObject {
+    /// Texture id
+    Texture(u32),
+    /// Framebuffer id
+    Framebuffer(u32),
+    /// Shader program id
+    Shader(u32),
+    /// Vertex array id
+    VertexArray(u32),
+    /// Objects owned through a CPU allocation (e.g. a mesh's vertex buffer ids), by its address
+    Alloc(usize),
+}
+
+/// Remember that an object belongs to the current context
+pub(crate) fn track_context_object(object: ContextObject) {
+    if !is_window_alive() {
+        return;
+    }
+
+    let generation = CONTEXT_GENERATION.load(Ordering::Relaxed);
+    let mut objects = CONTEXT_OBJECTS.lock().unwrap_or_else(|e| e.into_inner());
+
+    objects.entry(object).or_default().push(generation);
+}
+
+/// Forget an object of a dropped resource, whether it belongs to the current context (`None` if it wasn't tracked)
+///
+/// GPU resources outliving the [`Raylib`] handle (e.g. dropped while unwinding) must not be unloaded,
+/// neither after closing the window nor against a later context: their GPU memory went with their context,
+/// but the CPU side of the resource still has to be freed.
+pub(crate) fn untrack_context_object(object: ContextObject) -> Option<bool> {
+    let current = is_window_alive().then(|| CONTEXT_GENERATION.load(Ordering::Relaxed));
+    let mut objects = CONTEXT_OBJECTS.lock().unwrap_or_else(|e| e.into_inner());
+    let generations = objects.get_mut(&object)?;
+
+    // an object outliving its context can share its id with one of a later context, which of them
+    // is dropped can't be told apart: neither unloads it, and the older entries are kept for the rest
+    let unload = generations
+        .iter()
+        .all(|&generation| Some(generation) == current);
+    let index = generations
+        .iter()
+        .position(|&generation| Some(generation) == current)
+        .unwrap_or(0);
+
+    generations.swap_remove(index);
+
+    if generations.is_empty() {
+        objects.remove(&object);
+    }
+
+    Some(unload)
+}
+
+/// Forget an object of a dropped resource, `true` if it has to be unloaded
+///
+/// Untracked objects are unloaded while the window is alive.
+#[inline]
+pub(crate) fn release_context_object(object: ContextObject) -> bool {
+    untrack_context_object(object).unwrap_or_else(is_window_alive)
+}
+
+bitflags::bitflags! {
+    /// Config flags that only take effect when set before window initialization
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    pub struct InitFlags: u32 {
+        /// Set to try enabling MSAA 4X
+        const MSAA_4X_HINT = ConfigFlags::MSAA_4X_HINT.bits();
+        /// Set to try enabling interlaced video format (for V3D)
+        const INTERLACED_HINT = ConfigFlags::INTERLACED_HINT.bits();
+        /// Set to support HighDPI
+        const WINDOW_HIGHDPI = ConfigFlags::WINDOW_HIGHDPI.bits();
+        /// Set to allow transparent framebuffer
+        const WINDOW_TRANSPARENT = ConfigFlags::WINDOW_TRANSPARENT.bits();
+    }
+}
+
+bitflags::bitflags! {
+    /// Config flags that can be changed while the window is open
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    pub struct RuntimeFlags: u32 {
+        /// Set to try enabling V-Sync on GPU
+        const VSYNC_HINT = ConfigFlags::VSYNC_HINT.bits();
+        /// Set to run program in fullscreen
+        const FULLSCREEN_MODE = ConfigFlags::FULLSCREEN_MODE.bits();
+        /// Set to allow resizable window
+        const WINDOW_RESIZABLE = ConfigFlags::WINDOW_RESIZABLE.bits();
+        /// Set to disable window decoration (frame and buttons)
+        const WINDOW_UNDECORATED = ConfigFlags::WINDOW_UNDECORATED.bits();
+        /// Set to hide window
+        const WINDOW_HIDDEN = ConfigFlags::WINDOW_HIDDEN.bits();
+        /// Set to minimize window (iconify)
+        const WINDOW_MINIMIZED = ConfigFlags::WINDOW_MINIMIZED.bits();
+        /// Set to maximize window (expanded to monitor)
+        const WINDOW_MAXIMIZED = ConfigFlags::WINDOW_MAXIMIZED.bits();
+        /// Set to window non focused
+        const WINDOW_UNFOCUSED = ConfigFlags::WINDOW_UNFOCUSED.bits();
+        /// Set to window always on top
+        const WINDOW_TOPMOST = ConfigFlags::WINDOW_TOPMOST.bits();
+        /// Set to allow windows running while minimized
+        const WINDOW_ALWAYS_RUN = ConfigFlags::WINDOW_ALWAYS_RUN.bits();
+        /// Set to support mouse passthrough, only supported when WINDOW_UNDECORATED
+        const WINDOW_MOUSE_PASSTHROUGH = ConfigFlags::WINDOW_MOUSE_PASSTHROUGH.bits();
+    }
+}
+
+impl ConfigFlags {
+    /// Split the flags into the ones that only work before initialization and the ones that can be changed at runtime
+    #[inline]
+    pub fn split(self) -> (InitFlags, RuntimeFlags) {
+        (
+            InitFlags::from_bits_truncate(self.bits()),
+            RuntimeFlags::from_bits_truncate(self.bits()),
+        )
+    }
+}
+
+impl From<InitFlags> for ConfigFlags {
+    #[inline]
+    fn from(flags: InitFlags) -> Self {
+        ConfigFlags::from_bits_retain(flags.bits())
+    }
+}
+
+impl From<RuntimeFlags> for ConfigFlags {
+    #[inline]
+    fn from(flags: RuntimeFlags) -> Self {
+        ConfigFlags::from_bits_retain(flags.bits())
+    }
+}
+
+/// OpenGL context attributes the window got, see [`Raylib::get_context_info`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ContextInfo {
+    /// Depth buffer bits (GLFW requests 24)
+    pub depth_bits: u32,
+    /// Stencil buffer bits (GLFW requests 8), stencil modes need them
+    pub stencil_bits: u32,
+    /// MSAA samples, 0 without MSAA (see [`InitFlags::MSAA_4X_HINT`])
+    pub samples: u32,
+    /// Is the default framebuffer sRGB capable? See [`Raylib::set_srgb_framebuffer`]
+    pub srgb: bool,
+    /// Is it a debug context? Requested with the `opengl_debug` feature
+    pub debug: bool,
+}
 
 /// Main raylib handle
 #[derive(Debug)]
@@ -23,13 +307,14 @@ impl Raylib {
     #[inline]
     pub fn init_window(width: u32, height: u32, title: &str) -> Option<Self> {
         if !INITIALIZED.load(Ordering::Relaxed) {
-            let title = CString::new(title).unwrap();
+            let title = text_to_cstring(title);
 
             unsafe {
                 ffi::InitWindow(width as _, height as _, title.as_ptr());
             }
 
             if unsafe { ffi::IsWindowReady() } {
+                CONTEXT_GENERATION.fetch_add(1, Ordering::Relaxed);
                 INITIALIZED.store(true, Ordering::Relaxed);
 
                 Some(Self(PhantomData))
@@ -42,6 +327,8 @@ impl Raylib {
     }
 
     /// Initialize window and OpenGL context with config flags
+    ///
+    /// All flags are valid here, [`InitFlags`] can only be set this way.
     #[inline]
     pub fn init_window_ex(
         width: u32,
@@ -104,22 +391,99 @@ impl Raylib {
         unsafe { ffi::IsWindowResized() }
     }
 
+    /// Check if the window's DPI scale changed last frame, e.g. it was moved to a monitor with another scale
+    ///
+    /// See [`UiScale`] to follow the scale.
+    #[inline]
+    pub fn is_window_scale_changed(&self) -> bool {
+        WINDOW_SCALE_CHANGED.load(Ordering::Relaxed)
+    }
+
     /// Check if one specific window flag is enabled
     #[inline]
     pub fn is_window_state(&self, flag: ConfigFlags) -> bool {
         unsafe { ffi::IsWindowState(flag.bits()) }
     }
 
+    /// Get all currently enabled runtime window flags
+    #[inline]
+    pub fn get_window_state(&self) -> RuntimeFlags {
+        RuntimeFlags::all()
+            .iter()
+            .filter(|flag| self.is_window_state((*flag).into()))
+            .collect()
+    }
+
     /// Set window configuration state using flags (only PLATFORM_DESKTOP)
+    ///
+    /// Returns the flags that are actually enabled afterwards (out of the requested ones).
     #[inline]
-    pub fn set_window_state(&mut self, flags: ConfigFlags) {
+    pub fn set_window_state(&mut self, flags: RuntimeFlags) -> RuntimeFlags {
         unsafe { ffi::SetWindowState(flags.bits()) }
+
+        self.get_window_state() & flags
     }
 
     /// Clear window configuration state flags
+    ///
+    /// Returns the flags that are actually disabled afterwards (out of the requested ones).
     #[inline]
-    pub fn clear_window_state(&mut self, flags: ConfigFlags) {
+    pub fn clear_window_state(&mut self, flags: RuntimeFlags) -> RuntimeFlags {
         unsafe { ffi::ClearWindowState(flags.bits()) }
+
+        flags - self.get_window_state()
+    }
+
+    /// Set or clear a single runtime flag, returns whether it's enabled afterwards
+    #[inline]
+    fn toggle_window_state(&mut self, flag: RuntimeFlags, enabled: bool) -> bool {
+        if enabled {
+            self.set_window_state(flag);
+        } else {
+            self.clear_window_state(flag);
+        }
+
+        self.is_window_state(flag.into())
+    }
+
+    /// Enable/disable V-Sync, returns whether it's enabled afterwards
+    #[inline]
+    pub fn set_vsync(&mut self, enabled: bool) -> bool {
+        self.toggle_window_state(RuntimeFlags::VSYNC_HINT, enabled)
+    }
+
+    /// Enable/disable keeping the window on top of others, returns whether it's enabled afterwards
+    #[inline]
+    pub fn set_always_on_top(&mut self, enabled: bool) -> bool {
+        self.toggle_window_state(RuntimeFlags::WINDOW_TOPMOST, enabled)
+    }
+
+    /// Allow/disallow resizing the window, returns whether it's resizable afterwards
+    #[inline]
+    pub fn set_resizable(&mut self, enabled: bool) -> bool {
+        self.toggle_window_state(RuntimeFlags::WINDOW_RESIZABLE, enabled)
+    }
+
+    /// Show/hide window decorations, returns whether the window is decorated afterwards
+    #[inline]
+    pub fn set_decorated(&mut self, enabled: bool) -> bool {
+        !self.toggle_window_state(RuntimeFlags::WINDOW_UNDECORATED, !enabled)
+    }
+
+    /// Keep running while minimized, returns whether it's enabled afterwards
+    #[inline]
+    pub fn set_always_run(&mut self, enabled: bool) -> bool {
+        self.toggle_window_state(RuntimeFlags::WINDOW_ALWAYS_RUN, enabled)
+    }
+
+    /// Enter/leave fullscreen mode, returns whether the window is fullscreen afterwards
+    #[inline]
+    pub fn set_fullscreen(&mut self, enabled: bool) -> bool {
+        if self.is_window_fullscreen() != enabled {
+            self.toggle_fullscreen();
+        }
+
+        self.is_window_fullscreen()
     }
 
     /// Toggle window state: fullscreen/windowed (only PLATFORM_DESKTOP)
@@ -163,7 +527,7 @@ impl Raylib {
     /// Set title for window (only PLATFORM_DESKTOP)
     #[inline]
     pub fn set_window_title(&mut self, title: &str) {
-        let title = CString::new(title).unwrap();
+        let title = text_to_cstring(title);
 
         unsafe { ffi::SetWindowTitle(title.as_ptr()) }
     }
@@ -228,6 +592,77 @@ impl Raylib {
         unsafe { ffi::GetRenderHeight() as _ }
     }
 
+    /// Get the attributes of the OpenGL context and the default framebuffer
+    ///
+    /// raylib 4.5 resets GLFW's window hints when creating the window, so only MSAA (with
+    /// [`InitFlags::MSAA_4X_HINT`]) and the debug context (with the `opengl_debug` feature) can be requested,
+    /// everything else is GLFW's default. Check the result before relying on the stencil buffer or sRGB.
+    /// Everything is 0 or `false` if it can't be queried.
+    pub fn get_context_info(&self) -> ContextInfo {
+        use crate::drawing::gl;
+
+        let get_integerv = match gl::functions().get_integerv {
+            Some(get_integerv) => get_integerv,
+            None => return ContextInfo::default(),
+        };
+
+        let integer = |name| {
+            let mut value = 0;
+            unsafe { get_integerv(name, &mut value) };
+            value
+        };
+        let attachment = |attachment, name| {
+            gl::framebuffer_attachment_parameter(attachment, name).filter(|&value| value > 0)
+        };
+
+        // the default framebuffer has to be bound to query it
+        let bound = integer(gl::FRAMEBUFFER_BINDING);
+        if bound != 0 {
+            unsafe { gl::rlDisableFramebuffer() }
+        }
+
+        let info = ContextInfo {
+            // OpenGL 2.1 only has the old queries, core profiles only the new ones
+            depth_bits: attachment(gl::DEPTH, gl::FRAMEBUFFER_ATTACHMENT_DEPTH_SIZE)
+                .unwrap_or_else(|| integer(gl::DEPTH_BITS)) as _,
+            stencil_bits: attachment(gl::STENCIL, gl::FRAMEBUFFER_ATTACHMENT_STENCIL_SIZE)
+                .unwrap_or_else(|| integer(gl::STENCIL_BITS)) as _,
+            samples: integer(gl::SAMPLES).max(0) as _,
+            srgb: attachment(gl::BACK_LEFT, gl::FRAMEBUFFER_ATTACHMENT_COLOR_ENCODING)
+                == Some(gl::SRGB),
+            debug: integer(gl::CONTEXT_FLAGS) & gl::CONTEXT_FLAG_DEBUG_BIT != 0,
+        };
+
+        if bound != 0 {
+            unsafe { gl::rlEnableFramebuffer(bound as _) }
+        }
+
+        info
+    }
+
+    /// Convert colors written to sRGB capable framebuffers from linear to sRGB (off by default)
+    ///
+    /// Only for shaders that output linear colors, raylib's own colors are already sRGB.
+    /// See [`ContextInfo::srgb`] for the default framebuffer, render textures are never sRGB.
+    pub fn set_srgb_framebuffer(&mut self, enabled: bool) {
+        use crate::drawing::gl;
+
+        let functions = gl::functions();
+        let (Some(enable), Some(disable)) = (functions.enable, functions.disable) else {
+            return;
+        };
+
+        unsafe {
+            gl::flush_batch();
+
+            if enabled {
+                enable(gl::FRAMEBUFFER_SRGB);
+            } else {
+                disable(gl::FRAMEBUFFER_SRGB);
+            }
+        }
+    }
+
     /// Get number of connected monitors
     #[inline]
     pub fn get_monitor_count(&self) -> u32 {
@@ -302,10 +737,92 @@ impl Raylib {
         }
     }
 
+    /// Get a connected monitor
+    #[inline]
+    pub fn get_monitor(&self, monitor: u32) -> Option<Monitor> {
+        if monitor < self.get_monitor_count() {
+            Some(Monitor { index: monitor })
+        } else {
+            None
+        }
+    }
+
+    /// Get all connected monitors
+    #[inline]
+    pub fn get_monitors(&self) -> Vec<Monitor> {
+        (0..self.get_monitor_count())
+            .map(|index| Monitor { index })
+            .collect()
+    }
+
+    /// Switch to exclusive fullscreen on a monitor with a specific video mode (only PLATFORM_DESKTOP)
+    ///
+    /// The windowed position and size are saved and restored by [`Raylib::restore_windowed_mode`],
+    /// or automatically when `Raylib` is dropped (including during a panic).
+    /// Note that raylib doesn't know about the switch, so [`Raylib::is_window_fullscreen`] isn't affected.
+    ///
+    /// Returns `false` if the monitor or the window handle aren't available.
+    pub fn set_fullscreen_mode(&mut self, monitor: Monitor, mode: VideoMode) -> bool {
+        let window = self.glfw_window();
+        let handle = match monitor.glfw_handle() {
+            Some(handle) if !window.is_null() => handle,
+            _ => return false,
+        };
+
+        let mut state = WINDOWED_STATE.lock().unwrap_or_else(|e| e.into_inner());
+
+        if state.is_none() {
+            let [mut x, mut y, mut width, mut height] = [0; 4];
+
+            unsafe {
+                glfw::glfwGetWindowPos(window, &mut x as *mut _, &mut y as *mut _);
+                glfw::glfwGetWindowSize(window, &mut width as *mut _, &mut height as *mut _);
+            }
+
+            *state = Some([x, y, width, height]);
+        }
+
+        unsafe {
+            glfw::glfwSetWindowMonitor(
+                window,
+                handle,
+                0,
+                0,
+                mode.width as _,
+                mode.height as _,
+                mode.refresh_rate as _,
+            );
+        }
+
+        true
+    }
+
+    /// Leave exclusive fullscreen set by [`Raylib::set_fullscreen_mode`], restoring the windowed position and size
+    #[inline]
+    pub fn restore_windowed_mode(&mut self) {
+        let state = WINDOWED_STATE
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take();
+        let window = self.glfw_window();
+
+        if let (Some([x, y, width, height]), false) = (state, window.is_null()) {
+            unsafe {
+                glfw::glfwSetWindowMonitor(window, std::ptr::null_mut(), x, y, width, height, 0);
+            }
+        }
+    }
+
+    /// The GLFW window (`GetWindowHandle` returns the native handle instead, or null on Linux)
+    #[inline]
+    fn glfw_window(&self) -> *mut core::ffi::c_void {
+        unsafe { glfw::glfwGetCurrentContext() }
+    }
+
     /// Set clipboard text content
     #[inline]
     pub fn set_clipboard_text(&mut self, text: &str) {
-        let text = CString::new(text).unwrap();
+        let text = text_to_cstring(text);
 
         unsafe { ffi::SetClipboardText(text.as_ptr()) }
     }
@@ -387,6 +904,83 @@ impl Raylib {
         unsafe { ffi::DisableCursor() }
     }
 
+    /// Confine the cursor to a region of the window (in window coordinates), `None` to release it
+    ///
+    /// Uses `ClipCursor` on Windows; elsewhere the cursor is moved back into the region every frame.
+    /// The confinement is lifted while the window isn't focused.
+    #[inline]
+    pub fn confine_cursor(&mut self, region: Option<Rectangle>) {
+        *CURSOR_CONFINEMENT.lock().unwrap_or_else(|e| e.into_inner()) = region;
+
+        self.apply_cursor_confinement();
+    }
+
+    /// Region the cursor is confined to
+    #[inline]
+    pub fn get_cursor_confinement(&self) -> Option<Rectangle> {
+        *CURSOR_CONFINEMENT.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Re-apply the confinement, called every frame since focus changes and window moves invalidate it
+    fn apply_cursor_confinement(&mut self) {
+        let region = match self.get_cursor_confinement() {
+            Some(region) if self.is_window_focused() => Some(region),
+            _ => None,
+        };
+
+        #[cfg(windows)]
+        win32::clip_cursor(self.get_window_handle(), region);
+
+        #[cfg(not(windows))]
+        if let Some(region) = region {
+            let pos = self.get_mouse_position();
+            let x = pos.x.min(region.x + region.width - 1.).max(region.x);
+            let y = pos.y.min(region.y + region.height - 1.).max(region.y);
+
+            if x != pos.x || y != pos.y {
+                self.set_mouse_position(x as _, y as _);
+            }
+        }
+    }
+
+    /// Tell the input method where the text being edited is (in window coordinates),
+    /// so its composition and candidate windows appear next to the text field
+    ///
+    /// Only supported on Windows, returns `false` elsewhere.
+    /// Draw the text being composed there with [`Raylib::ime_composition`].
+    #[inline]
+    pub fn set_ime_rect(&mut self, rect: Rectangle) -> bool {
+        #[cfg(windows)]
+        {
+            win32::set_ime_rect(self.get_window_handle(), rect)
+        }
+
+        #[cfg(not(windows))]
+        {
+            let _ = rect;
+            false
+        }
+    }
+
+    /// Text the input method is composing (preedit) and its cursor, `None` when nothing is being composed
+    ///
+    /// The text isn't typed yet, draw it at the caret of the text field until
+    /// [`Raylib::get_char_pressed`] returns it once it's committed.
+    /// Only supported on Windows, where it's read from the window's input context, `None` elsewhere:
+    /// GLFW 3.3 only reports committed characters.
+    #[inline]
+    pub fn ime_composition(&self) -> Option<ImeState> {
+        #[cfg(windows)]
+        {
+            win32::ime_composition(self.get_window_handle())
+        }
+
+        #[cfg(not(windows))]
+        {
+            None
+        }
+    }
+
     /// Check if cursor is on the screen
     #[inline]
     pub fn is_cursor_on_screen(&self) -> bool {
@@ -406,18 +1000,24 @@ impl Raylib {
     }
 
     /// Get time for last frame drawn (delta time)
+    ///
+    /// Game logic that has to play back the same way in replays should use a [`GameClock`](crate::replay::GameClock)
     #[inline]
     pub fn get_frame_time(&self) -> Duration {
         Duration::from_secs_f32(unsafe { ffi::GetFrameTime() })
     }
 
     /// Get elapsed time since InitWindow()
+    ///
+    /// Wall-clock time, see [`GameClock::time`](crate::replay::GameClock::time) for replayable game time
     #[inline]
     pub fn get_time(&self) -> Duration {
         Duration::from_secs_f64(unsafe { ffi::GetTime() })
     }
 
     /// Get a random value between min and max (both included)
+    ///
+    /// The generator's state can't be saved, use a [`GameRng`](crate::replay::GameRng) for replayable randomness
     #[inline]
     pub fn get_random_value(&self, min: i32, max: i32) -> i32 {
         unsafe { ffi::GetRandomValue(min, max) }
@@ -432,7 +1032,10 @@ impl Raylib {
     /// Takes a screenshot of current screen (file_name extension defines format)
     #[inline]
     pub fn take_screenshot(&mut self, file_name: &str) {
-        let file_name = CString::new(file_name).unwrap();
+        let file_name = match CString::new(file_name) {
+            Ok(file_name) => file_name,
+            Err(_) => return,
+        };
 
         unsafe { ffi::TakeScreenshot(file_name.as_ptr()) }
     }
@@ -440,7 +1043,10 @@ impl Raylib {
     /// Open URL with default system browser (if available)
     #[inline]
     pub fn open_url(&self, url: &str) {
-        let url = CString::new(url).unwrap();
+        let url = match CString::new(url) {
+            Ok(url) => url,
+            Err(_) => return,
+        };
 
         unsafe { ffi::OpenURL(url.as_ptr()) }
     }
@@ -452,6 +1058,9 @@ impl Raylib {
     }
 
     /// Load dropped filepaths
+    ///
+    /// Allocates a new `Vec` and `String`s on every call,
+    /// see [`Raylib::get_dropped_files_into`] and [`Raylib::load_dropped_files`]
     #[inline]
     pub fn get_dropped_files(&self) -> Vec<String> {
         let path_list = unsafe { ffi::LoadDroppedFiles() };
@@ -470,6 +1079,76 @@ impl Raylib {
         paths
     }
 
+    /// Load dropped filepaths into `buffer`, replacing its contents
+    ///
+    /// The `Vec` and the `String`s already in it are reused, so this doesn't allocate when they are large enough
+    #[inline]
+    pub fn get_dropped_files_into(&self, buffer: &mut Vec<String>) {
+        let path_list = unsafe { ffi::LoadDroppedFiles() };
+        let count = path_list.count as usize;
+
+        buffer.truncate(count);
+
+        for i in 0..count {
+            let path = unsafe { CStr::from_ptr(path_list.paths.add(i).read()) }.to_string_lossy();
+
+            match buffer.get_mut(i) {
+                Some(string) => {
+                    string.clear();
+                    string.push_str(&path);
+                }
+                None => buffer.push(path.into_owned()),
+            }
+        }
+
+        unsafe {
+            ffi::UnloadDroppedFiles(path_list);
+        }
+    }
+
+    /// Load dropped filepaths without copying them
+    ///
+    /// The paths are unloaded when the returned list is dropped
+    #[inline]
+    pub fn load_dropped_files(&mut self) -> DroppedFiles<'_> {
+        DroppedFiles {
+            raw: unsafe { ffi::LoadDroppedFiles() },
+            _marker: PhantomData,
+        }
+    }
+
+    /// Load dropped filepaths as paths, keeping paths that aren't valid UTF-8 intact
+    #[inline]
+    pub fn get_dropped_paths(&self) -> Vec<PathBuf> {
+        let path_list = unsafe { ffi::LoadDroppedFiles() };
+
+        let paths = (0..path_list.count as usize)
+            .map(|i| cstr_to_path(unsafe { CStr::from_ptr(path_list.paths.add(i).read()) }))
+            .collect();
+
+        unsafe {
+            ffi::UnloadDroppedFiles(path_list);
+        }
+
+        paths
+    }
+
+    /// Call `f` with every file dropped since the last call, call it once per frame
+    ///
+    /// raylib keeps collecting dropped files until they're loaded, so every drop is seen once
+    /// (as long as nothing else loads the dropped files in between).
+    #[inline]
+    pub fn on_file_dropped<F>(&mut self, mut f: F)
+    where
+        F: FnMut(DroppedFile),
+    {
+        if self.is_file_dropped() {
+            for path in self.get_dropped_paths() {
+                f(DroppedFile { path });
+            }
+        }
+    }
+
     /// Check if a key has been pressed once
     #[inline]
     pub fn is_key_pressed(&self, key: KeyboardKey) -> bool {
@@ -583,7 +1262,10 @@ impl Raylib {
     /// Set internal gamepad mappings (SDL_GameControllerDB)
     #[inline]
     pub fn set_gamepad_mappings(&mut self, mappings: &str) -> i32 {
-        let mappings = CString::new(mappings).unwrap();
+        let mappings = match CString::new(mappings) {
+            Ok(mappings) => mappings,
+            Err(_) => return 0,
+        };
 
         unsafe { ffi::SetGamepadMappings(mappings.as_ptr()) }
     }
@@ -753,10 +1435,16 @@ impl Raylib {
     /// Setup canvas (framebuffer) to start drawing
     #[inline]
     pub fn begin_drawing(&mut self) -> DrawHandle {
+        if self.get_cursor_confinement().is_some() {
+            self.apply_cursor_confinement();
+        }
+
         unsafe {
             ffi::BeginDrawing();
         }
 
+        DRAWING.store(true, Ordering::Relaxed);
+
         DrawHandle(self)
     }
 
@@ -765,11 +1453,463 @@ impl Raylib {
     pub fn set_trace_log_level(&mut self, level: TraceLogLevel) {
         unsafe { ffi::SetTraceLogLevel(level as _) }
     }
+
+    /// Route raylib's log messages to a callback instead of stdout, e.g. into the `log` crate
+    ///
+    /// Call it before [`Raylib::init_window`] to also get the initialization messages. Messages are
+    /// truncated to [`MAX_TRACE_LOG_LENGTH`] bytes. The callback may run on the audio thread, messages
+    /// logged while it runs are passed to it after it returns. A panicking callback is removed.
+    pub fn set_trace_log<F>(callback: F)
+    where
+        F: FnMut(TraceLogLevel, &str) + Send + 'static,
+    {
+        *TRACE_LOG.lock().unwrap_or_else(|e| e.into_inner()) = Some(Box::new(callback));
+
+        unsafe { ffi::SetTraceLogCallback(Some(trace_log_callback)) }
+    }
+
+    /// Remove the callback set with [`Raylib::set_trace_log`], raylib logs to stdout again
+    pub fn reset_trace_log() {
+        unsafe { ffi::SetTraceLogCallback(None) }
+
+        *TRACE_LOG.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
 }
 
 impl Drop for Raylib {
     #[inline]
     fn drop(&mut self) {
+        // a leaked `DrawHandle` would leave raylib mid-frame
+        if DRAWING.swap(false, Ordering::Relaxed) {
+            crate::stats::end_frame();
+            unsafe { ffi::EndDrawing() }
+        }
+
+        self.restore_windowed_mode();
+        self.confine_cursor(None);
+        WINDOW_SCALE.store(0, Ordering::Relaxed);
+        WINDOW_SCALE_CHANGED.store(false, Ordering::Relaxed);
+        crate::stats::release();
+
         unsafe { ffi::CloseWindow() }
+
+        crate::model::forget_extra_buffers();
+        crate::drawing::gl::forget_functions();
+        INITIALIZED.store(false, Ordering::Relaxed);
+    }
+}
+
+/// Cursor clipping and input method composition through the Win32 API
+#[cfg(windows)]
+#[allow(non_snake_case)]
+mod win32 {
+    use super::ImeState;
+    use crate::math::Rectangle;
+    use core::ffi::{c_int, c_long, c_void};
+
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    struct RECT {
+        left: c_long,
+        top: c_long,
+        right: c_long,
+        bottom: c_long,
+    }
+
+    #[repr(C)]
+    struct POINT {
+        x: c_long,
+        y: c_long,
+    }
+
+    #[repr(C)]
+    struct COMPOSITIONFORM {
+        dwStyle: u32,
+        ptCurrentPos: POINT,
+        rcArea: RECT,
+    }
+
+    #[repr(C)]
+    struct CANDIDATEFORM {
+        dwIndex: u32,
+        dwStyle: u32,
+        ptCurrentPos: POINT,
+        rcArea: RECT,
+    }
+
+    const CFS_POINT: u32 = 0x0002;
+    const CFS_EXCLUDE: u32 = 0x0080;
+    const GCS_COMPSTR: u32 = 0x0008;
+    const GCS_CURSORPOS: u32 = 0x0080;
+
+    #[link(name = "user32")]
+    extern "system" {
+        fn ClipCursor(rect: *const RECT) -> c_int;
+        fn ClientToScreen(hwnd: *mut c_void, point: *mut POINT) -> c_int;
+    }
+
+    #[link(name = "imm32")]
+    extern "system" {
+        fn ImmGetContext(hwnd: *mut c_void) -> *mut c_void;
+        fn ImmReleaseContext(hwnd: *mut c_void, himc: *mut c_void) -> c_int;
+        fn ImmSetCompositionWindow(himc: *mut c_void, form: *const COMPOSITIONFORM) -> c_int;
+        fn ImmSetCandidateWindow(himc: *mut c_void, form: *const CANDIDATEFORM) -> c_int;
+        fn ImmGetCompositionStringW(
+            himc: *mut c_void,
+            index: u32,
+            buf: *mut c_void,
+            len: u32,
+        ) -> c_long;
+    }
+
+    /// Place the IME composition window at the text field and keep the candidate list from covering it
+    pub(super) fn set_ime_rect(hwnd: *mut c_void, rect: Rectangle) -> bool {
+        if hwnd.is_null() {
+            return false;
+        }
+
+        let area = RECT {
+            left: rect.x as _,
+            top: rect.y as _,
+            right: (rect.x + rect.width) as _,
+            bottom: (rect.y + rect.height) as _,
+        };
+
+        unsafe {
+            let himc = ImmGetContext(hwnd);
+
+            if himc.is_null() {
+                return false;
+            }
+
+            ImmSetCompositionWindow(
+                himc,
+                &COMPOSITIONFORM {
+                    dwStyle: CFS_POINT,
+                    ptCurrentPos: POINT {
+                        x: area.left,
+                        y: area.top,
+                    },
+                    rcArea: area,
+                } as *const _,
+            );
+            ImmSetCandidateWindow(
+                himc,
+                &CANDIDATEFORM {
+                    dwIndex: 0,
+                    dwStyle: CFS_EXCLUDE,
+                    ptCurrentPos: POINT {
+                        x: area.left,
+                        y: area.bottom,
+                    },
+                    rcArea: area,
+                } as *const _,
+            );
+            ImmReleaseContext(hwnd, himc);
+        }
+
+        true
+    }
+
+    /// Composition string of the window's input context, `None` if it's empty
+    pub(super) fn ime_composition(hwnd: *mut c_void) -> Option<ImeState> {
+        if hwnd.is_null() {
+            return None;
+        }
+
+        unsafe {
+            let himc = ImmGetContext(hwnd);
+
+            if himc.is_null() {
+                return None;
+            }
+
+            // lengths are in bytes, the cursor position in UTF-16 code units
+            let len = ImmGetCompositionStringW(himc, GCS_COMPSTR, std::ptr::null_mut(), 0);
+            let state = if len > 0 {
+                let mut text = vec![0u16; len as usize / 2];
+                let len = ImmGetCompositionStringW(
+                    himc,
+                    GCS_COMPSTR,
+                    text.as_mut_ptr() as *mut _,
+                    (text.len() * 2) as u32,
+                );
+                text.truncate(len.max(0) as usize / 2);
+
+                let cursor = ImmGetCompositionStringW(himc, GCS_CURSORPOS, std::ptr::null_mut(), 0);
+                let cursor = (cursor.max(0) as usize).min(text.len());
+
+                Some(ImeState {
+                    // unpaired surrogates become U+FFFD, 3 bytes like the cursor counts them
+                    cursor: char::decode_utf16(text[..cursor].iter().copied())
+                        .map(|c| c.map_or(3, char::len_utf8))
+                        .sum(),
+                    text: String::from_utf16_lossy(&text),
+                })
+            } else {
+                None
+            };
+
+            ImmReleaseContext(hwnd, himc);
+
+            state.filter(|state| !state.text.is_empty())
+        }
+    }
+
+    /// Clip the cursor to a region of the window's client area, `None` releases it
+    pub(super) fn clip_cursor(hwnd: *mut c_void, region: Option<Rectangle>) {
+        let region = match region {
+            Some(region) if !hwnd.is_null() => region,
+            _ => {
+                unsafe { ClipCursor(std::ptr::null()) };
+                return;
+            }
+        };
+
+        let mut min = POINT {
+            x: region.x as _,
+            y: region.y as _,
+        };
+        let mut max = POINT {
+            x: (region.x + region.width) as _,
+            y: (region.y + region.height) as _,
+        };
+
+        unsafe {
+            ClientToScreen(hwnd, &mut min as *mut _);
+            ClientToScreen(hwnd, &mut max as *mut _);
+
+            ClipCursor(&RECT {
+                left: min.x,
+                top: min.y,
+                right: max.x,
+                bottom: max.y,
+            } as *const _);
+        }
+    }
+}
+
+/// Dropped filepaths, borrowed from raylib until dropped
+#[derive(Debug)]
+pub struct DroppedFiles<'a> {
+    raw: ffi::FilePathList,
+    _marker: PhantomData<&'a mut Raylib>,
+}
+
+impl<'a> DroppedFiles<'a> {
+    /// Number of paths
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.raw.count as usize
+    }
+
+    /// Are there no paths?
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.raw.count == 0
+    }
+
+    /// Get a path
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&CStr> {
+        if index < self.len() {
+            Some(unsafe { CStr::from_ptr(self.raw.paths.add(index).read()) })
+        } else {
+            None
+        }
+    }
+
+    /// Iterate over the paths, only allocates for paths that aren't valid UTF-8
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = Cow<'_, str>> {
+        (0..self.len()).filter_map(|i| self.get(i).map(CStr::to_string_lossy))
+    }
+}
+
+impl<'a> Drop for DroppedFiles<'a> {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe { ffi::UnloadDroppedFiles(self.raw.clone()) }
+    }
+}
+
+/// Path as given by raylib, which doesn't convert it on Linux and BSDs
+#[inline]
+fn cstr_to_path(path: &CStr) -> PathBuf {
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+
+        PathBuf::from(std::ffi::OsStr::from_bytes(path.to_bytes()))
+    }
+
+    // GLFW passes UTF-8 everywhere else
+    #[cfg(not(unix))]
+    {
+        PathBuf::from(path.to_string_lossy().into_owned())
+    }
+}
+
+/// A file dropped into the window, see [`Raylib::on_file_dropped`]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DroppedFile {
+    path: PathBuf,
+}
+
+impl DroppedFile {
+    /// Path of the file (or directory)
+    #[inline]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Take the path
+    #[inline]
+    pub fn into_path(self) -> PathBuf {
+        self.path
+    }
+
+    /// Lowercase extension without the dot, e.g. to pick a loader
+    #[inline]
+    pub fn extension(&self) -> Option<String> {
+        self.path
+            .extension()
+            .map(|extension| extension.to_string_lossy().to_lowercase())
+    }
+
+    /// Is it a directory?
+    #[inline]
+    pub fn is_dir(&self) -> bool {
+        self.path.is_dir()
+    }
+
+    /// Read the whole file
+    #[inline]
+    pub fn read(&self) -> io::Result<Vec<u8>> {
+        fs::read(&self.path)
+    }
+
+    /// Read the whole file as UTF-8 text
+    #[inline]
+    pub fn read_to_string(&self) -> io::Result<String> {
+        fs::read_to_string(&self.path)
+    }
+
+    /// Load the file as an image, fails if it isn't one raylib supports (see [`Image::from_file`])
+    #[inline]
+    pub fn load_image(&self) -> Result<Image, RaylibError> {
+        Image::from_file(RaylibError::path_str(&self.path)?)
+    }
+
+    /// Load the file as wave data, fails if it isn't a sound raylib supports (see [`Wave::from_file`])
+    #[inline]
+    pub fn load_wave(&self) -> Result<Wave, RaylibError> {
+        Wave::from_file(RaylibError::path_str(&self.path)?)
+    }
+}
+
+/// Compare the window's DPI scale with the one at the end of the last frame, call right after `EndDrawing`
+pub(crate) fn track_window_scale() {
+    let scale = unsafe { ffi::GetWindowScaleDPI() }.x;
+    let previous = f32::from_bits(WINDOW_SCALE.swap(scale.to_bits(), Ordering::Relaxed));
+
+    WINDOW_SCALE_CHANGED.store(previous != 0. && previous != scale, Ordering::Relaxed);
+}
+
+/// Text being composed by an input method, see [`Raylib::ime_composition`]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ImeState {
+    /// Composed text, not committed yet
+    pub text: String,
+    /// Cursor position in `text`, in bytes
+    pub cursor: usize,
+}
+
+/// UI scale factor following the window's DPI scale
+///
+/// Call [`UiScale::update`] once per frame, it reports when the window moved to a monitor with a different scale
+/// ([`Raylib::is_window_scale_changed`]), so fonts can be rasterized again at the new pixel size (e.g. with [`DynamicFont::set_base_size`](crate::text::DynamicFont::set_base_size)).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UiScale {
+    scale: f32,
+}
+
+impl UiScale {
+    /// Start with the current DPI scale of the window
+    #[inline]
+    pub fn new(raylib: &Raylib) -> Self {
+        Self {
+            scale: Self::current(raylib),
+        }
+    }
+
+    /// Current scale factor
+    #[inline]
+    pub fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    /// Follow the DPI scale if it changed last frame, returns the new scale
+    #[inline]
+    pub fn update(&mut self, raylib: &Raylib) -> Option<f32> {
+        if !raylib.is_window_scale_changed() {
+            return None;
+        }
+
+        let scale = Self::current(raylib);
+
+        if (scale - self.scale).abs() > f32::EPSILON {
+            self.scale = scale;
+
+            Some(scale)
+        } else {
+            None
+        }
+    }
+
+    /// Convert a length in logical pixels to physical pixels
+    #[inline]
+    pub fn px(&self, logical: f32) -> f32 {
+        logical * self.scale
+    }
+
+    /// Pixel size to rasterize a font at, to draw it crisply at a logical size
+    #[inline]
+    pub fn font_size(&self, logical: u32) -> u32 {
+        (logical as f32 * self.scale).round().max(1.) as u32
+    }
+
+    #[inline]
+    fn current(raylib: &Raylib) -> f32 {
+        let scale = raylib.get_window_scale_dpi().x;
+
+        if scale > 0. {
+            scale
+        } else {
+            1.
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn non_utf8_path_keeps_its_bytes() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let path = cstr_to_path(CStr::from_bytes_with_nul(b"/tmp/caf\xE9.PNG\0").unwrap());
+
+        assert_eq!(path.as_os_str().as_bytes(), b"/tmp/caf\xE9.PNG");
+        assert_eq!(DroppedFile { path }.extension().as_deref(), Some("png"));
+    }
+
+    #[test]
+    fn utf8_path_is_converted() {
+        let path = cstr_to_path(CStr::from_bytes_with_nul("/tmp/日本.txt\0".as_bytes()).unwrap());
+
+        assert_eq!(path, Path::new("/tmp/日本.txt"));
     }
 }
@@ -0,0 +1,531 @@
+use crate::{core::Raylib, ffi, math::Vector2, monitor::glfw, texture::Image, TraceLogLevel};
+use std::{
+    collections::VecDeque,
+    fmt,
+    fs::{self, File},
+    io::{self, Write},
+    panic,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Frame times kept for reports, about 10 seconds at 60 FPS
+pub const FRAME_HISTORY: usize = 600;
+
+struct LogBuffer {
+    lines: VecDeque<String>,
+    capacity: usize,
+}
+
+static LOG: Mutex<LogBuffer> = Mutex::new(LogBuffer {
+    lines: VecDeque::new(),
+    capacity: 0,
+});
+
+static FRAMES: Mutex<VecDeque<f32>> = Mutex::new(VecDeque::new());
+
+/// Last queried driver strings, for crash reports
+static GPU: Mutex<Option<GpuInfo>> = Mutex::new(None);
+
+/// Keep the last `lines` trace log messages for reports, still printing them like raylib does
+///
+/// Replaces the callback set with [`Raylib::set_trace_log`]. When routing the log somewhere else
+/// (e.g. with the `log-bridge` feature), call [`set_log_capacity`] and [`record_log`] from that callback instead.
+pub fn capture_trace_log(lines: usize) {
+    set_log_capacity(lines);
+
+    Raylib::set_trace_log(|level, message| {
+        record_log(level, message);
+        println!("{}: {message}", level_name(level));
+    });
+}
+
+/// Set how many log messages [`record_log`] keeps (none by default)
+pub fn set_log_capacity(lines: usize) {
+    let mut log = LOG.lock().unwrap_or_else(|e| e.into_inner());
+
+    log.capacity = lines;
+
+    while log.lines.len() > lines {
+        log.lines.pop_front();
+    }
+}
+
+/// Add a message to the log kept for reports, dropping the oldest one when it's full
+pub fn record_log(level: TraceLogLevel, message: &str) {
+    let mut log = LOG.lock().unwrap_or_else(|e| e.into_inner());
+
+    if log.capacity == 0 {
+        return;
+    }
+
+    if log.lines.len() == log.capacity {
+        log.lines.pop_front();
+    }
+
+    log.lines
+        .push_back(format!("{}: {message}", level_name(level)));
+}
+
+/// Log messages kept so far, oldest first
+pub fn recent_log() -> Vec<String> {
+    let log = LOG.lock().unwrap_or_else(|e| e.into_inner());
+
+    log.lines.iter().cloned().collect()
+}
+
+/// Frame times of the last [`FRAME_HISTORY`] frames in seconds, oldest first
+pub fn frame_times() -> Vec<f32> {
+    let frames = FRAMES.lock().unwrap_or_else(|e| e.into_inner());
+
+    frames.iter().copied().collect()
+}
+
+/// Record the last frame time, call right before `EndDrawing`
+pub(crate) fn record_frame() {
+    let mut frames = FRAMES.lock().unwrap_or_else(|e| e.into_inner());
+
+    if frames.len() == FRAME_HISTORY {
+        frames.pop_front();
+    }
+
+    frames.push_back(unsafe { ffi::GetFrameTime() });
+}
+
+#[inline]
+fn level_name(level: TraceLogLevel) -> &'static str {
+    match level {
+        TraceLogLevel::Trace => "TRACE",
+        TraceLogLevel::Debug => "DEBUG",
+        TraceLogLevel::Warning => "WARNING",
+        TraceLogLevel::Error => "ERROR",
+        TraceLogLevel::Fatal => "FATAL",
+        _ => "INFO",
+    }
+}
+
+/// OpenGL driver strings
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GpuInfo {
+    /// `GL_VENDOR`
+    pub vendor: String,
+    /// `GL_RENDERER`, usually the GPU model
+    pub renderer: String,
+    /// `GL_VERSION`, including the driver version
+    pub version: String,
+}
+
+impl GpuInfo {
+    /// Query the driver, `None` if `glGetString` can't be loaded
+    pub fn query(_raylib: &Raylib) -> Option<Self> {
+        const GL_VENDOR: u32 = 0x1F00;
+        const GL_RENDERER: u32 = 0x1F01;
+        const GL_VERSION: u32 = 0x1F02;
+
+        let get_string: unsafe extern "system" fn(u32) -> *const u8 = unsafe {
+            let ptr = glfw::glfwGetProcAddress(b"glGetString\0".as_ptr() as *const _);
+
+            if ptr.is_null() {
+                return None;
+            }
+
+            std::mem::transmute(ptr)
+        };
+
+        let get = |name| {
+            let ptr = unsafe { get_string(name) };
+
+            if ptr.is_null() {
+                String::new()
+            } else {
+                unsafe { std::ffi::CStr::from_ptr(ptr as *const _) }
+                    .to_string_lossy()
+                    .into_owned()
+            }
+        };
+
+        let info = Self {
+            vendor: get(GL_VENDOR),
+            renderer: get(GL_RENDERER),
+            version: get(GL_VERSION),
+        };
+
+        *GPU.lock().unwrap_or_else(|e| e.into_inner()) = Some(info.clone());
+
+        Some(info)
+    }
+}
+
+/// Window configuration
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WindowInfo {
+    /// Screen size in screen coordinates
+    pub screen_size: (u32, u32),
+    /// Framebuffer size in pixels (differs from the screen size on HighDPI displays)
+    pub render_size: (u32, u32),
+    /// Window position
+    pub position: Vector2,
+    /// Is it fullscreen?
+    pub fullscreen: bool,
+    /// Index of the monitor the window is on
+    pub monitor: u32,
+    /// FPS when the report was collected
+    pub fps: u32,
+}
+
+/// Connected monitor
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MonitorInfo {
+    /// Monitor name
+    pub name: String,
+    /// Current video mode size
+    pub size: (u32, u32),
+    /// Current refresh rate in Hz
+    pub refresh_rate: u32,
+    /// Physical size in millimetres
+    pub physical_size: (u32, u32),
+}
+
+/// Everything a bug report needs, see [`report`]
+#[derive(Clone, Debug, Default)]
+pub struct Report {
+    /// Version of this crate
+    pub crate_version: &'static str,
+    /// Version of raylib
+    pub raylib_version: &'static str,
+    /// Operating system, as in [`std::env::consts::OS`]
+    pub os: &'static str,
+    /// CPU architecture, as in [`std::env::consts::ARCH`]
+    pub arch: &'static str,
+    /// Seconds since the Unix epoch when the report was collected
+    pub timestamp: u64,
+    /// OpenGL driver (`None` without a window)
+    pub gpu: Option<GpuInfo>,
+    /// Window configuration (`None` without a window)
+    pub window: Option<WindowInfo>,
+    /// Connected monitors
+    pub monitors: Vec<MonitorInfo>,
+    /// Frame times in seconds, see [`frame_times`]
+    pub frame_times: Vec<f32>,
+    /// Log messages, see [`capture_trace_log`]
+    pub log: Vec<String>,
+    /// Panic message, for crash reports
+    pub panic: Option<String>,
+    /// Screenshot of the last frame
+    pub screenshot: Option<Image>,
+}
+
+impl Report {
+    /// Collect what doesn't need the window (versions, frame times and log)
+    pub fn new() -> Self {
+        Self {
+            crate_version: env!("CARGO_PKG_VERSION"),
+            raylib_version: crate::RAYLIB_VERSION,
+            os: std::env::consts::OS,
+            arch: std::env::consts::ARCH,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |since| since.as_secs()),
+            frame_times: frame_times(),
+            log: recent_log(),
+            ..Default::default()
+        }
+    }
+
+    /// Collect everything, optionally with a screenshot of the last frame
+    pub fn collect(rl: &Raylib, screenshot: bool) -> Self {
+        let monitors = rl
+            .get_monitors()
+            .into_iter()
+            .map(|monitor| {
+                let mode = monitor.current_video_mode();
+
+                MonitorInfo {
+                    name: monitor.name(),
+                    size: mode.map_or((0, 0), |mode| (mode.width, mode.height)),
+                    refresh_rate: mode.map_or(0, |mode| mode.refresh_rate),
+                    physical_size: monitor.physical_size(),
+                }
+            })
+            .collect();
+
+        Self {
+            gpu: GpuInfo::query(rl),
+            window: Some(WindowInfo {
+                screen_size: (rl.get_screen_width(), rl.get_screen_height()),
+                render_size: (rl.get_render_width(), rl.get_render_height()),
+                position: rl.get_window_position(),
+                fullscreen: rl.is_window_fullscreen(),
+                monitor: rl.get_current_monitor(),
+                fps: rl.get_fps(),
+            }),
+            monitors,
+            screenshot: if screenshot {
+                Image::from_screen(rl).ok()
+            } else {
+                None
+            },
+            ..Self::new()
+        }
+    }
+
+    /// Average, minimum and maximum FPS over the frame times
+    pub fn fps_summary(&self) -> Option<(f32, f32, f32)> {
+        let times: Vec<f32> = self
+            .frame_times
+            .iter()
+            .copied()
+            .filter(|&time| time > 0.)
+            .collect();
+
+        if times.is_empty() {
+            return None;
+        }
+
+        let average = times.len() as f32 / times.iter().sum::<f32>();
+        let min = 1. / times.iter().copied().fold(0., f32::max);
+        let max = 1. / times.iter().copied().fold(f32::INFINITY, f32::min);
+
+        Some((average, min, max))
+    }
+
+    /// Write the report as a zip with `report.txt`, `log.txt`, `frame_times.csv`
+    /// and `screenshot.png` (if there's one)
+    pub fn write_zip(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+
+        let mut zip = ZipWriter::new(Vec::new());
+
+        zip.add("report.txt", self.to_string().as_bytes())?;
+        zip.add("log.txt", self.log.join("\n").as_bytes())?;
+
+        let mut csv = String::from("frame,seconds\n");
+        for (frame, time) in self.frame_times.iter().enumerate() {
+            csv.push_str(&format!("{frame},{time}\n"));
+        }
+        zip.add("frame_times.csv", csv.as_bytes())?;
+
+        if let Some(screenshot) = &self.screenshot {
+            // raylib 4.5 can only export images to files
+            let temp = std::env::temp_dir()
+                .join(format!("rust-raylib-screenshot-{}.png", std::process::id()));
+
+            if screenshot.export(&temp.to_string_lossy()) {
+                let png = fs::read(&temp);
+                let _ = fs::remove_file(&temp);
+
+                zip.add("screenshot.png", &png?)?;
+            }
+        }
+
+        if let Some(dir) = path.parent() {
+            if !dir.as_os_str().is_empty() {
+                fs::create_dir_all(dir)?;
+            }
+        }
+
+        File::create(path)?.write_all(&zip.finish()?)
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "rust-raylib {}", self.crate_version)?;
+        writeln!(f, "raylib {}", self.raylib_version)?;
+        writeln!(f, "system: {} {}", self.os, self.arch)?;
+        writeln!(f, "timestamp: {}", self.timestamp)?;
+
+        if let Some(panic) = &self.panic {
+            writeln!(f, "\npanic: {panic}")?;
+        }
+
+        if let Some(gpu) = &self.gpu {
+            writeln!(f, "\nGPU: {} ({})", gpu.renderer, gpu.vendor)?;
+            writeln!(f, "OpenGL: {}", gpu.version)?;
+        }
+
+        if let Some(window) = &self.window {
+            writeln!(
+                f,
+                "\nwindow: {}x{} (render {}x{}) at {}, {}{}",
+                window.screen_size.0,
+                window.screen_size.1,
+                window.render_size.0,
+                window.render_size.1,
+                window.position.x,
+                window.position.y,
+                if window.fullscreen {
+                    ", fullscreen"
+                } else {
+                    ""
+                },
+            )?;
+            writeln!(f, "current monitor: {}", window.monitor)?;
+            writeln!(f, "FPS: {}", window.fps)?;
+        }
+
+        for (index, monitor) in self.monitors.iter().enumerate() {
+            writeln!(
+                f,
+                "monitor {index}: {} {}x{} @ {} Hz, {}x{} mm",
+                monitor.name,
+                monitor.size.0,
+                monitor.size.1,
+                monitor.refresh_rate,
+                monitor.physical_size.0,
+                monitor.physical_size.1,
+            )?;
+        }
+
+        if let Some((average, min, max)) = self.fps_summary() {
+            writeln!(
+                f,
+                "\nFPS over {} frames: {average:.1} average, {min:.1} min, {max:.1} max",
+                self.frame_times.len()
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Write a diagnostic report zip for bug reports, see [`Report::write_zip`]
+#[inline]
+pub fn report(rl: &Raylib, path: impl AsRef<Path>, screenshot: bool) -> io::Result<()> {
+    Report::collect(rl, screenshot).write_zip(path)
+}
+
+/// Write a report zip into `dir` when the program panics (`crash-<timestamp>.zip`)
+///
+/// The panic hook can't reach the window, so crash reports only have the versions, the panic message,
+/// the frame times and the log, plus the GPU if [`GpuInfo::query`] (or [`Report::collect`]) was called before.
+/// The previous panic hook still runs afterwards.
+pub fn write_report_on_panic(dir: impl Into<PathBuf>) {
+    let dir = dir.into();
+    let previous = panic::take_hook();
+
+    panic::set_hook(Box::new(move |info| {
+        let mut report = Report::new();
+        report.panic = Some(info.to_string());
+        report.gpu = GPU.lock().unwrap_or_else(|e| e.into_inner()).clone();
+
+        let path = dir.join(format!("crash-{}.zip", report.timestamp));
+
+        match report.write_zip(&path) {
+            Ok(()) => crate::core::warn(&format!("crash report written to {}", path.display())),
+            Err(error) => crate::core::warn(&format!("writing the crash report failed: {error}")),
+        }
+
+        previous(info);
+    }));
+}
+
+/// Minimal zip writer storing files uncompressed
+struct ZipWriter {
+    data: Vec<u8>,
+    central: Vec<u8>,
+    entries: u16,
+}
+
+impl ZipWriter {
+    #[inline]
+    fn new(data: Vec<u8>) -> Self {
+        Self {
+            data,
+            central: Vec::new(),
+            entries: 0,
+        }
+    }
+
+    fn add(&mut self, name: &str, contents: &[u8]) -> io::Result<()> {
+        let too_large = || io::Error::new(io::ErrorKind::InvalidInput, "zip64 isn't supported");
+
+        let offset = u32::try_from(self.data.len()).map_err(|_| too_large())?;
+        let size = u32::try_from(contents.len()).map_err(|_| too_large())?;
+        let crc = crc32(contents);
+
+        // version 2.0, UTF-8 names, stored, 1980-01-01 00:00
+        let common = |out: &mut Vec<u8>| {
+            out.extend_from_slice(&20u16.to_le_bytes());
+            out.extend_from_slice(&0x0800u16.to_le_bytes());
+            out.extend_from_slice(&0u16.to_le_bytes());
+            out.extend_from_slice(&0u16.to_le_bytes());
+            out.extend_from_slice(&0x0021u16.to_le_bytes());
+            out.extend_from_slice(&crc.to_le_bytes());
+            out.extend_from_slice(&size.to_le_bytes());
+            out.extend_from_slice(&size.to_le_bytes());
+            out.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            out.extend_from_slice(&0u16.to_le_bytes());
+        };
+
+        self.data.extend_from_slice(&0x0403_4b50u32.to_le_bytes());
+        common(&mut self.data);
+        self.data.extend_from_slice(name.as_bytes());
+        self.data.extend_from_slice(contents);
+
+        self.central
+            .extend_from_slice(&0x0201_4b50u32.to_le_bytes());
+        self.central.extend_from_slice(&20u16.to_le_bytes());
+        common(&mut self.central);
+        // comment length, disk, internal and external attributes
+        self.central.extend_from_slice(&[0; 10]);
+        self.central.extend_from_slice(&offset.to_le_bytes());
+        self.central.extend_from_slice(name.as_bytes());
+
+        self.entries += 1;
+
+        Ok(())
+    }
+
+    fn finish(mut self) -> io::Result<Vec<u8>> {
+        let offset = u32::try_from(self.data.len())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "zip64 isn't supported"))?;
+
+        self.data.extend_from_slice(&self.central);
+        self.data.extend_from_slice(&0x0605_4b50u32.to_le_bytes());
+        self.data.extend_from_slice(&[0; 4]);
+        self.data.extend_from_slice(&self.entries.to_le_bytes());
+        self.data.extend_from_slice(&self.entries.to_le_bytes());
+        self.data
+            .extend_from_slice(&(self.central.len() as u32).to_le_bytes());
+        self.data.extend_from_slice(&offset.to_le_bytes());
+        self.data.extend_from_slice(&0u16.to_le_bytes());
+
+        Ok(self.data)
+    }
+}
+
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0; 256];
+    let mut i = 0;
+
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                0xEDB8_8320 ^ (crc >> 1)
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+
+        table[i] = crc;
+        i += 1;
+    }
+
+    table
+};
+
+/// CRC-32 (ISO-HDLC, the one zip and png use)
+pub(crate) fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(!0u32, |crc, &byte| {
+        CRC32_TABLE[((crc ^ byte as u32) & 0xFF) as usize] ^ (crc >> 8)
+    })
+}
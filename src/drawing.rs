@@ -1,24 +1,589 @@
 use crate::{
     color::Color,
+    core::{track_window_scale, DRAWING},
+    diagnostics,
+    error::RaylibError,
     ffi,
-    math::{BoundingBox, Camera, Camera2D, Camera3D, Matrix, Ray, Rectangle, Vector2, Vector3},
+    math::{
+        columns, from_columns, multiply, planar_shadow, vec3, BoundingBox, Camera, Camera2D,
+        Camera3D, Matrix, Ray, Rectangle, Vector2, Vector3,
+    },
     model::{Material, Mesh, Model},
     shader::Shader,
-    text::Font,
-    texture::{NPatchInfo, RenderTexture2D, Texture, Texture2D},
+    stats,
+    text::{text_to_cstring, Font, StaticText, TextStyle},
+    texture::{Image, NPatchInfo, RenderTexture2D, Texture, Texture2D},
     vr::VrStereoConfig,
     Raylib,
 };
 
 use std::{
-    ffi::CString,
+    ffi::CStr,
     ops::{Deref, Range},
+    sync::{atomic::Ordering, Mutex},
 };
 
 pub use crate::ffi::BlendMode;
 
+// reusable point buffers for the functions taking point arrays, so they don't allocate every frame
+static SCRATCH_2D: Mutex<Vec<ffi::Vector2>> = Mutex::new(Vec::new());
+static SCRATCH_3D: Mutex<Vec<ffi::Vector3>> = Mutex::new(Vec::new());
+
+/// Collect `points` into a scratch buffer and pass it to `f`
+#[inline]
+fn with_scratch<T>(
+    scratch: &Mutex<Vec<T>>,
+    points: impl IntoIterator<Item = impl Into<T>>,
+    f: impl FnOnce(&mut [T]),
+) {
+    let mut buffer = match scratch.lock() {
+        Ok(buffer) => buffer,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+
+    buffer.clear();
+    buffer.extend(points.into_iter().map(Into::into));
+
+    f(&mut buffer);
+}
+
+/// Transform to draw a model's meshes with, `transform` on top of the model's own
+///
+/// Same as `MatrixMultiply(model.transform, transform)` in `DrawModelEx`, the model's transform applies first.
+fn model_mesh_transform(model: &Model, transform: Matrix) -> ffi::Matrix {
+    let (transform, model_transform) = (columns(&transform), columns(&model.transform()));
+
+    from_columns(&multiply(&transform, &model_transform)).into()
+}
+
+/// rlgl state functions, and OpenGL functions loaded through glfw for what rlgl 4.5 doesn't wrap
+pub(crate) mod gl {
+    use crate::monitor::glfw;
+    use core::ffi::{c_float, c_int, c_uchar, c_uint, c_void};
+    use std::sync::Mutex;
+
+    pub const STENCIL_TEST: c_uint = 0x0B90;
+    pub const STENCIL_BUFFER_BIT: c_uint = 0x0400;
+    pub const CULL_FACE: c_uint = 0x0B44;
+    pub const CULL_FACE_MODE: c_uint = 0x0B45;
+    pub const DEPTH_TEST: c_uint = 0x0B71;
+    pub const DEPTH_WRITEMASK: c_uint = 0x0B72;
+    pub const BLEND: c_uint = 0x0BE2;
+    pub const COLOR_WRITEMASK: c_uint = 0x0C23;
+    pub const FRONT: c_int = 0x0404;
+    pub const FRONT_AND_BACK: c_uint = 0x0408;
+    pub const POINT: c_uint = 0x1B00;
+    pub const FILL: c_uint = 0x1B02;
+    pub const TEXTURE0: c_uint = 0x84C0;
+    pub const TEXTURE_2D: c_uint = 0x0DE1;
+    pub const TEXTURE_2D_ARRAY: c_uint = 0x8C1A;
+    pub const TEXTURE_MAG_FILTER: c_uint = 0x2800;
+    pub const TEXTURE_MIN_FILTER: c_uint = 0x2801;
+    pub const TEXTURE_WRAP_S: c_uint = 0x2802;
+    pub const TEXTURE_WRAP_T: c_uint = 0x2803;
+    pub const NEAREST: c_int = 0x2600;
+    pub const LINEAR: c_int = 0x2601;
+    pub const LINEAR_MIPMAP_LINEAR: c_int = 0x2703;
+    pub const REPEAT: c_int = 0x2901;
+    pub const CLAMP_TO_EDGE: c_int = 0x812F;
+    pub const MIRRORED_REPEAT: c_int = 0x8370;
+    pub const MIRROR_CLAMP_TO_EDGE: c_int = 0x8743;
+    pub const RGBA: c_uint = 0x1908;
+    pub const RGBA8: c_int = 0x8058;
+    pub const SRGB8_ALPHA8: c_int = 0x8C43;
+    pub const UNSIGNED_BYTE: c_uint = 0x1401;
+    pub const FLOAT: c_int = 0x1406;
+    pub const FRAMEBUFFER: c_uint = 0x8D40;
+    pub const FRAMEBUFFER_BINDING: c_uint = 0x8CA6;
+    pub const FRAMEBUFFER_SRGB: c_uint = 0x8DB9;
+    pub const BACK_LEFT: c_uint = 0x0402;
+    pub const DEPTH: c_uint = 0x1801;
+    pub const STENCIL: c_uint = 0x1802;
+    pub const FRAMEBUFFER_ATTACHMENT_COLOR_ENCODING: c_uint = 0x8210;
+    pub const FRAMEBUFFER_ATTACHMENT_DEPTH_SIZE: c_uint = 0x2216;
+    pub const FRAMEBUFFER_ATTACHMENT_STENCIL_SIZE: c_uint = 0x2217;
+    pub const SRGB: c_int = 0x8C40;
+    pub const DEPTH_BITS: c_uint = 0x0D56;
+    pub const STENCIL_BITS: c_uint = 0x0D57;
+    pub const SAMPLES: c_uint = 0x80A9;
+    pub const CONTEXT_FLAGS: c_uint = 0x821E;
+    pub const CONTEXT_FLAG_DEBUG_BIT: c_int = 0x2;
+    // rlFramebufferAttachType and rlFramebufferAttachTextureType values
+    pub const ATTACHMENT_COLOR_CHANNEL0: c_int = 0;
+    pub const ATTACHMENT_DEPTH: c_int = 100;
+    pub const ATTACHMENT_TEXTURE2D: c_int = 100;
+    pub const ATTACHMENT_RENDERBUFFER: c_int = 200;
+
+    pub use crate::rlgl::ffi::*;
+
+    /// Draw everything batched so far, before changing the OpenGL state
+    #[inline]
+    pub unsafe fn flush_batch() {
+        crate::stats::record_batch();
+        rlDrawRenderBatchActive();
+    }
+
+    /// OpenGL functions, each `None` if the context doesn't have it (e.g. `glPolygonMode` on OpenGL ES)
+    #[derive(Clone, Copy, Default)]
+    pub struct Functions {
+        pub enable: Option<unsafe extern "system" fn(c_uint)>,
+        pub disable: Option<unsafe extern "system" fn(c_uint)>,
+        pub stencil_func: Option<unsafe extern "system" fn(c_uint, c_int, c_uint)>,
+        pub stencil_op: Option<unsafe extern "system" fn(c_uint, c_uint, c_uint)>,
+        pub stencil_mask: Option<unsafe extern "system" fn(c_uint)>,
+        pub clear_stencil: Option<unsafe extern "system" fn(c_int)>,
+        pub clear: Option<unsafe extern "system" fn(c_uint)>,
+        pub color_mask: Option<unsafe extern "system" fn(c_uchar, c_uchar, c_uchar, c_uchar)>,
+        pub is_enabled: Option<unsafe extern "system" fn(c_uint) -> c_uchar>,
+        pub get_booleanv: Option<unsafe extern "system" fn(c_uint, *mut c_uchar)>,
+        pub get_integerv: Option<unsafe extern "system" fn(c_uint, *mut c_int)>,
+        pub polygon_mode: Option<unsafe extern "system" fn(c_uint, c_uint)>,
+        pub point_size: Option<unsafe extern "system" fn(c_float)>,
+        pub gen_textures: Option<unsafe extern "system" fn(c_int, *mut c_uint)>,
+        pub delete_textures: Option<unsafe extern "system" fn(c_int, *const c_uint)>,
+        pub bind_texture: Option<unsafe extern "system" fn(c_uint, c_uint)>,
+        pub active_texture: Option<unsafe extern "system" fn(c_uint)>,
+        pub tex_image_2d: Option<
+            unsafe extern "system" fn(
+                c_uint,
+                c_int,
+                c_int,
+                c_int,
+                c_int,
+                c_int,
+                c_uint,
+                c_uint,
+                *const c_void,
+            ),
+        >,
+        pub tex_image_3d: Option<
+            unsafe extern "system" fn(
+                c_uint,
+                c_int,
+                c_int,
+                c_int,
+                c_int,
+                c_int,
+                c_int,
+                c_uint,
+                c_uint,
+                *const c_void,
+            ),
+        >,
+        pub tex_sub_image_3d: Option<
+            unsafe extern "system" fn(
+                c_uint,
+                c_int,
+                c_int,
+                c_int,
+                c_int,
+                c_int,
+                c_int,
+                c_int,
+                c_uint,
+                c_uint,
+                *const c_void,
+            ),
+        >,
+        pub tex_parameteri: Option<unsafe extern "system" fn(c_uint, c_uint, c_int)>,
+        pub generate_mipmap: Option<unsafe extern "system" fn(c_uint)>,
+    }
+
+    static FUNCTIONS: Mutex<Option<Functions>> = Mutex::new(None);
+
+    /// Load the functions on first use, all `None` without a window (and its OpenGL context)
+    pub fn functions() -> Functions {
+        if !crate::core::is_window_alive() {
+            return Functions::default();
+        }
+
+        let mut functions = FUNCTIONS.lock().unwrap_or_else(|e| e.into_inner());
+
+        *functions.get_or_insert_with(|| unsafe { load() })
+    }
+
+    /// Forget the functions when the window closes, they belong to its context
+    pub fn forget_functions() {
+        *FUNCTIONS.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+
+    unsafe fn load() -> Functions {
+        unsafe fn get<F>(name: &[u8]) -> Option<F> {
+            let ptr = glfw::glfwGetProcAddress(name.as_ptr() as *const _);
+
+            if ptr.is_null() {
+                None
+            } else {
+                Some(std::mem::transmute_copy(&ptr))
+            }
+        }
+
+        Functions {
+            enable: get(b"glEnable\0"),
+            disable: get(b"glDisable\0"),
+            stencil_func: get(b"glStencilFunc\0"),
+            stencil_op: get(b"glStencilOp\0"),
+            stencil_mask: get(b"glStencilMask\0"),
+            clear_stencil: get(b"glClearStencil\0"),
+            clear: get(b"glClear\0"),
+            color_mask: get(b"glColorMask\0"),
+            is_enabled: get(b"glIsEnabled\0"),
+            get_booleanv: get(b"glGetBooleanv\0"),
+            get_integerv: get(b"glGetIntegerv\0"),
+            polygon_mode: get(b"glPolygonMode\0"),
+            point_size: get(b"glPointSize\0"),
+            gen_textures: get(b"glGenTextures\0"),
+            delete_textures: get(b"glDeleteTextures\0"),
+            bind_texture: get(b"glBindTexture\0"),
+            active_texture: get(b"glActiveTexture\0"),
+            tex_image_2d: get(b"glTexImage2D\0"),
+            tex_image_3d: get(b"glTexImage3D\0"),
+            tex_sub_image_3d: get(b"glTexSubImage3D\0"),
+            tex_parameteri: get(b"glTexParameteri\0"),
+            generate_mipmap: get(b"glGenerateMipmap\0"),
+        }
+    }
+
+    /// Query an attachment of the bound framebuffer, `None` if the function isn't available (before OpenGL 3.0)
+    pub fn framebuffer_attachment_parameter(attachment: c_uint, pname: c_uint) -> Option<c_int> {
+        type GetFramebufferAttachmentParameteriv =
+            unsafe extern "system" fn(c_uint, c_uint, c_uint, *mut c_int);
+
+        let ptr = unsafe {
+            glfw::glfwGetProcAddress(b"glGetFramebufferAttachmentParameteriv\0".as_ptr() as *const _)
+        };
+
+        if ptr.is_null() {
+            return None;
+        }
+
+        let get: GetFramebufferAttachmentParameteriv = unsafe { std::mem::transmute(ptr) };
+        let mut value = 0;
+
+        unsafe { get(FRAMEBUFFER, attachment, pname, &mut value) };
+
+        Some(value)
+    }
+}
+
+/// Stencil state of the active stencil mode
+static STENCIL_STATE: Mutex<Option<StencilState>> = Mutex::new(None);
+
+/// Set (or with `None` disable) the stencil test
+fn apply_stencil_state(state: Option<StencilState>) {
+    let functions = gl::functions();
+    let (
+        Some(enable),
+        Some(disable),
+        Some(stencil_func),
+        Some(stencil_op),
+        Some(stencil_mask),
+        Some(color_mask),
+    ) = (
+        functions.enable,
+        functions.disable,
+        functions.stencil_func,
+        functions.stencil_op,
+        functions.stencil_mask,
+        functions.color_mask,
+    )
+    else {
+        return;
+    };
+
+    *STENCIL_STATE.lock().unwrap_or_else(|e| e.into_inner()) = state;
+
+    unsafe {
+        gl::flush_batch();
+
+        match state {
+            Some(state) => {
+                let color = state.color_write as _;
+
+                enable(gl::STENCIL_TEST);
+                stencil_func(state.func as _, state.reference as _, state.read_mask as _);
+                stencil_op(state.fail as _, state.depth_fail as _, state.pass as _);
+                stencil_mask(state.write_mask as _);
+                color_mask(color, color, color, color);
+            }
+            None => {
+                disable(gl::STENCIL_TEST);
+                stencil_mask(0xFF);
+                color_mask(1, 1, 1, 1);
+            }
+        }
+    }
+}
+
+/// Stencil test comparison between the reference value and the stored value
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(u32)]
+pub enum StencilFunc {
+    /// Always fails
+    Never = 0x0200,
+    /// Passes if the reference is less than the stored value
+    Less = 0x0201,
+    /// Passes if the reference equals the stored value
+    Equal = 0x0202,
+    /// Passes if the reference is less than or equal to the stored value
+    LessEqual = 0x0203,
+    /// Passes if the reference is greater than the stored value
+    Greater = 0x0204,
+    /// Passes if the reference differs from the stored value
+    NotEqual = 0x0205,
+    /// Passes if the reference is greater than or equal to the stored value
+    GreaterEqual = 0x0206,
+    /// Always passes
+    Always = 0x0207,
+}
+
+/// Change of the stored stencil value
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(u32)]
+pub enum StencilOp {
+    /// Keep the value
+    Keep = 0x1E00,
+    /// Set to 0
+    Zero = 0,
+    /// Set to the reference value
+    Replace = 0x1E01,
+    /// Increment, clamped to 255
+    Increment = 0x1E02,
+    /// Decrement, clamped to 0
+    Decrement = 0x1E03,
+    /// Invert the bits
+    Invert = 0x150A,
+    /// Increment, wrapping to 0
+    IncrementWrap = 0x8507,
+    /// Decrement, wrapping to 255
+    DecrementWrap = 0x8508,
+}
+
+/// Stencil test and write configuration
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StencilState {
+    /// Comparison of the reference with the stored value, failing fragments aren't drawn
+    pub func: StencilFunc,
+    /// Reference value
+    pub reference: u8,
+    /// Mask applied to both the reference and the stored value before comparing
+    pub read_mask: u8,
+    /// Bits of the stored value that can be changed
+    pub write_mask: u8,
+    /// Change when the stencil test fails
+    pub fail: StencilOp,
+    /// Change when the stencil test passes but the depth test fails
+    pub depth_fail: StencilOp,
+    /// Change when both tests pass
+    pub pass: StencilOp,
+    /// Draw colors, disable to only write the stencil buffer
+    pub color_write: bool,
+}
+
+impl StencilState {
+    /// Write `reference` wherever something is drawn, without drawing colors (to build a mask)
+    #[inline]
+    pub const fn mask(reference: u8) -> Self {
+        Self {
+            func: StencilFunc::Always,
+            reference,
+            read_mask: 0xFF,
+            write_mask: 0xFF,
+            fail: StencilOp::Keep,
+            depth_fail: StencilOp::Keep,
+            pass: StencilOp::Replace,
+            color_write: false,
+        }
+    }
+
+    /// Draw only where the stored value equals `reference`
+    #[inline]
+    pub const fn inside(reference: u8) -> Self {
+        Self::test(StencilFunc::Equal, reference)
+    }
+
+    /// Draw only where the stored value differs from `reference`
+    #[inline]
+    pub const fn outside(reference: u8) -> Self {
+        Self::test(StencilFunc::NotEqual, reference)
+    }
+
+    /// Draw where the test passes, without changing the stored values
+    #[inline]
+    pub const fn test(func: StencilFunc, reference: u8) -> Self {
+        Self {
+            func,
+            reference,
+            read_mask: 0xFF,
+            write_mask: 0,
+            fail: StencilOp::Keep,
+            depth_fail: StencilOp::Keep,
+            pass: StencilOp::Keep,
+            color_write: true,
+        }
+    }
+}
+
+/// Color channels written when drawing
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ColorWriteMask {
+    /// Write the red channel
+    pub r: bool,
+    /// Write the green channel
+    pub g: bool,
+    /// Write the blue channel
+    pub b: bool,
+    /// Write the alpha channel
+    pub a: bool,
+}
+
+impl ColorWriteMask {
+    /// Write all channels (default)
+    pub const ALL: Self = Self::new(true, true, true, true);
+    /// Write no channels, e.g. for a depth pre-pass
+    pub const NONE: Self = Self::new(false, false, false, false);
+    /// Write the color but keep the alpha channel
+    pub const RGB: Self = Self::new(true, true, true, false);
+
+    /// Create new color write mask
+    #[inline]
+    pub const fn new(r: bool, g: bool, b: bool, a: bool) -> Self {
+        Self { r, g, b, a }
+    }
+}
+
+/// Faces skipped when drawing
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CullMode {
+    /// Draw both sides
+    None,
+    /// Skip back faces (default)
+    Back,
+    /// Skip front faces, e.g. for outlines drawn with inverted hulls
+    Front,
+}
+
+/// Check if the depth test is enabled, `false` (raylib's 2D default) if it can't be queried
+fn depth_test() -> bool {
+    match gl::functions().is_enabled {
+        Some(is_enabled) => unsafe { is_enabled(gl::DEPTH_TEST) != 0 },
+        None => false,
+    }
+}
+
+fn set_depth_test(enabled: bool) {
+    unsafe {
+        gl::flush_batch();
+
+        if enabled {
+            gl::rlEnableDepthTest();
+        } else {
+            gl::rlDisableDepthTest();
+        }
+    }
+}
+
+/// Check if depth writes are enabled, `true` (raylib's default) if it can't be queried
+fn depth_write() -> bool {
+    match gl::functions().get_booleanv {
+        Some(get_booleanv) => {
+            let mut enabled = 1;
+
+            unsafe { get_booleanv(gl::DEPTH_WRITEMASK, &mut enabled) };
+
+            enabled != 0
+        }
+        None => true,
+    }
+}
+
+fn set_depth_write(enabled: bool) {
+    unsafe {
+        gl::flush_batch();
+
+        if enabled {
+            gl::rlEnableDepthMask();
+        } else {
+            gl::rlDisableDepthMask();
+        }
+    }
+}
+
+/// Current color write mask, all channels if it can't be queried
+fn color_write_mask() -> ColorWriteMask {
+    match gl::functions().get_booleanv {
+        Some(get_booleanv) => {
+            let mut mask = [1; 4];
+
+            unsafe { get_booleanv(gl::COLOR_WRITEMASK, mask.as_mut_ptr()) };
+
+            ColorWriteMask::new(mask[0] != 0, mask[1] != 0, mask[2] != 0, mask[3] != 0)
+        }
+        None => ColorWriteMask::ALL,
+    }
+}
+
+fn set_color_write_mask(mask: ColorWriteMask) {
+    let color_mask = match gl::functions().color_mask {
+        Some(color_mask) => color_mask,
+        None => return,
+    };
+
+    unsafe {
+        gl::flush_batch();
+        color_mask(mask.r as _, mask.g as _, mask.b as _, mask.a as _);
+    }
+}
+
+/// Current face culling, back faces (raylib's default) if it can't be queried
+fn cull_mode() -> CullMode {
+    let functions = gl::functions();
+
+    match (functions.is_enabled, functions.get_integerv) {
+        (Some(is_enabled), Some(get_integerv)) => unsafe {
+            if is_enabled(gl::CULL_FACE) == 0 {
+                return CullMode::None;
+            }
+
+            let mut face = 0;
+            get_integerv(gl::CULL_FACE_MODE, &mut face);
+
+            if face == gl::FRONT {
+                CullMode::Front
+            } else {
+                CullMode::Back
+            }
+        },
+        _ => CullMode::Back,
+    }
+}
+
+fn set_cull_mode(mode: CullMode) {
+    unsafe {
+        gl::flush_batch();
+
+        match mode {
+            CullMode::None => gl::rlDisableBackfaceCulling(),
+            CullMode::Back => {
+                gl::rlSetCullFace(1);
+                gl::rlEnableBackfaceCulling();
+            }
+            CullMode::Front => {
+                gl::rlSetCullFace(0);
+                gl::rlEnableBackfaceCulling();
+            }
+        }
+    }
+}
+
 /// A struct containing the info for drawing textures.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DrawTextureParams {
     /// Part of texture to draw. If None - draw the whole texture.
     /// Default: None
@@ -49,6 +614,7 @@ impl Default for DrawTextureParams {
 
 /// A struct containing the info for drawing billboard textures.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DrawBillboardParams {
     /// Part of texture to draw. If None - draw the whole texture.
     /// Default: None
@@ -81,6 +647,36 @@ impl Default for DrawBillboardParams {
     }
 }
 
+/// raylib's main 2D batching trick: drawing shapes with a white pixel of the sprite atlas
+///
+/// Shapes normally use raylib's own 1x1 white texture, so every switch between shapes and sprites flushes the batch.
+/// Once shapes sample a white region of the atlas, shapes and sprites from it share a single draw batch.
+#[derive(Debug)]
+pub struct BatchOptimizer;
+
+impl BatchOptimizer {
+    /// Create the canonical 1x1 white texture
+    ///
+    /// Draw solid sprites with it, or copy its pixel into your atlas
+    #[inline]
+    pub fn white_texture() -> Result<Texture2D, RaylibError> {
+        Texture::from_image(&Image::generate_color(1, 1, Color::WHITE))
+    }
+
+    /// Draw shapes with `white_region` of `atlas`, which must be opaque white
+    ///
+    /// Shapes drawn afterwards batch together with sprites from `atlas`.
+    /// The atlas must stay loaded for as long as it's used for shapes.
+    #[inline]
+    pub fn unify_shapes_and_sprites<D: Draw + ?Sized>(
+        draw: &mut D,
+        atlas: &Texture2D,
+        white_region: Rectangle,
+    ) {
+        draw.set_shapes_texture(atlas, white_region);
+    }
+}
+
 /// An object that handles drawing
 pub struct DrawHandle<'a>(pub(crate) &'a mut Raylib);
 
@@ -103,7 +699,13 @@ impl<'a> Deref for DrawHandle<'a> {
 impl<'a> Drop for DrawHandle<'a> {
     #[inline]
     fn drop(&mut self) {
-        unsafe { ffi::EndDrawing() }
+        // also runs while unwinding, so a panic mid-frame still ends the frame
+        if DRAWING.swap(false, Ordering::Relaxed) {
+            stats::end_frame();
+            diagnostics::record_frame();
+            unsafe { ffi::EndDrawing() }
+            track_window_scale();
+        }
     }
 }
 
@@ -130,6 +732,7 @@ impl<'a, T> Deref for DrawMode2D<'a, T> {
 impl<'a, T> Drop for DrawMode2D<'a, T> {
     #[inline]
     fn drop(&mut self) {
+        stats::record_batch();
         unsafe { ffi::EndMode2D() }
     }
 }
@@ -157,6 +760,7 @@ impl<'a, T> Deref for DrawMode3D<'a, T> {
 impl<'a, T> Drop for DrawMode3D<'a, T> {
     #[inline]
     fn drop(&mut self) {
+        stats::record_batch();
         unsafe { ffi::EndMode3D() }
     }
 }
@@ -184,6 +788,7 @@ impl<'a, T> Deref for DrawTextureMode<'a, T> {
 impl<'a, T> Drop for DrawTextureMode<'a, T> {
     #[inline]
     fn drop(&mut self) {
+        stats::record_batch();
         unsafe { ffi::EndTextureMode() }
     }
 }
@@ -211,6 +816,7 @@ impl<'a, T> Deref for DrawShaderMode<'a, T> {
 impl<'a, T> Drop for DrawShaderMode<'a, T> {
     #[inline]
     fn drop(&mut self) {
+        stats::record_batch();
         unsafe { ffi::EndShaderMode() }
     }
 }
@@ -238,6 +844,7 @@ impl<'a, T> Deref for DrawBlendMode<'a, T> {
 impl<'a, T> Drop for DrawBlendMode<'a, T> {
     #[inline]
     fn drop(&mut self) {
+        stats::record_batch();
         unsafe { ffi::EndBlendMode() }
     }
 }
@@ -265,6 +872,7 @@ impl<'a, T> Deref for DrawScissorMode<'a, T> {
 impl<'a, T> Drop for DrawScissorMode<'a, T> {
     #[inline]
     fn drop(&mut self) {
+        stats::record_batch();
         unsafe { ffi::EndScissorMode() }
     }
 }
@@ -292,10 +900,224 @@ impl<'a, T> Deref for DrawVrStereoMode<'a, T> {
 impl<'a, T> Drop for DrawVrStereoMode<'a, T> {
     #[inline]
     fn drop(&mut self) {
+        stats::record_batch();
         unsafe { ffi::EndVrStereoMode() }
     }
 }
 
+/// An object that handles drawing with the stencil test
+pub struct DrawStencilMode<'a, T>(&'a mut T);
+
+impl<'a, T> DrawStencilMode<'a, T> {
+    /// Change the stencil state, e.g. from building a mask to drawing inside it
+    #[inline]
+    pub fn set_state(&mut self, state: StencilState) {
+        apply_stencil_state(Some(state));
+    }
+
+    /// End stencil mode
+    #[inline]
+    pub fn end_stencil_mode(self) {
+        drop(self)
+    }
+}
+
+impl<'a, T> Deref for DrawStencilMode<'a, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+impl<'a, T> Drop for DrawStencilMode<'a, T> {
+    #[inline]
+    fn drop(&mut self) {
+        apply_stencil_state(None);
+    }
+}
+
+/// An object that handles drawing with the depth test enabled or disabled
+pub struct DrawDepthTestMode<'a, T>(&'a mut T, bool);
+
+impl<'a, T> DrawDepthTestMode<'a, T> {
+    /// End depth test mode, restoring the previous state
+    #[inline]
+    pub fn end_depth_test_mode(self) {
+        drop(self)
+    }
+}
+
+impl<'a, T> Deref for DrawDepthTestMode<'a, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+impl<'a, T> Drop for DrawDepthTestMode<'a, T> {
+    #[inline]
+    fn drop(&mut self) {
+        set_depth_test(self.1);
+    }
+}
+
+/// An object that handles drawing with depth writes enabled or disabled
+pub struct DrawDepthWriteMode<'a, T>(&'a mut T, bool);
+
+impl<'a, T> DrawDepthWriteMode<'a, T> {
+    /// End depth write mode, restoring the previous state
+    #[inline]
+    pub fn end_depth_write_mode(self) {
+        drop(self)
+    }
+}
+
+impl<'a, T> Deref for DrawDepthWriteMode<'a, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+impl<'a, T> Drop for DrawDepthWriteMode<'a, T> {
+    #[inline]
+    fn drop(&mut self) {
+        set_depth_write(self.1);
+    }
+}
+
+/// An object that handles drawing with a color write mask
+pub struct DrawColorWriteMode<'a, T>(&'a mut T, ColorWriteMask);
+
+impl<'a, T> DrawColorWriteMode<'a, T> {
+    /// End color write mode, restoring the previous mask
+    #[inline]
+    pub fn end_color_write_mode(self) {
+        drop(self)
+    }
+}
+
+impl<'a, T> Deref for DrawColorWriteMode<'a, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+impl<'a, T> Drop for DrawColorWriteMode<'a, T> {
+    #[inline]
+    fn drop(&mut self) {
+        set_color_write_mask(self.1);
+    }
+}
+
+/// An object that handles drawing with a face culling mode
+pub struct DrawCullMode<'a, T>(&'a mut T, CullMode);
+
+impl<'a, T> DrawCullMode<'a, T> {
+    /// End cull mode, restoring the previous mode
+    #[inline]
+    pub fn end_cull_mode(self) {
+        drop(self)
+    }
+}
+
+impl<'a, T> Deref for DrawCullMode<'a, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+impl<'a, T> Drop for DrawCullMode<'a, T> {
+    #[inline]
+    fn drop(&mut self) {
+        set_cull_mode(self.1);
+    }
+}
+
+/// An object that handles drawing polygons as outlines
+pub struct DrawWireframeMode<'a, T>(&'a mut T);
+
+impl<'a, T> DrawWireframeMode<'a, T> {
+    /// End wireframe mode, drawing filled polygons again
+    #[inline]
+    pub fn end_wireframe_mode(self) {
+        drop(self)
+    }
+}
+
+impl<'a, T> Deref for DrawWireframeMode<'a, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+impl<'a, T> Drop for DrawWireframeMode<'a, T> {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            gl::flush_batch();
+            gl::rlDisableWireMode();
+        }
+    }
+}
+
+/// Set how polygons are rasterized, with the point size for [`gl::POINT`]
+fn set_polygon_mode(mode: u32, size: f32) {
+    let functions = gl::functions();
+    let (Some(polygon_mode), Some(point_size)) = (functions.polygon_mode, functions.point_size)
+    else {
+        return;
+    };
+
+    unsafe {
+        gl::flush_batch();
+        polygon_mode(gl::FRONT_AND_BACK, mode);
+        point_size(size);
+    }
+}
+
+/// An object that handles drawing polygons as points
+pub struct DrawPointsMode<'a, T>(&'a mut T);
+
+impl<'a, T> DrawPointsMode<'a, T> {
+    /// End points mode, drawing filled polygons again
+    #[inline]
+    pub fn end_points_mode(self) {
+        drop(self)
+    }
+}
+
+impl<'a, T> Deref for DrawPointsMode<'a, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+impl<'a, T> Drop for DrawPointsMode<'a, T> {
+    #[inline]
+    fn drop(&mut self) {
+        set_polygon_mode(gl::FILL, 1.);
+    }
+}
+
 /// A trait that contains all the drawing functions
 pub trait Draw
 where
@@ -310,6 +1132,8 @@ where
     /// Begin 2D mode with custom camera (2D)
     #[inline]
     fn begin_mode_2d(&mut self, camera: Camera2D) -> DrawMode2D<Self> {
+        stats::record_batch();
+
         unsafe {
             ffi::BeginMode2D(camera.into());
         }
@@ -320,6 +1144,8 @@ where
     /// Begin 3D mode with custom camera (3D)
     #[inline]
     fn begin_mode_3d(&mut self, camera: Camera3D) -> DrawMode3D<Self> {
+        stats::record_batch();
+
         unsafe {
             ffi::BeginMode3D(camera.into());
         }
@@ -330,6 +1156,8 @@ where
     /// Begin drawing to render texture
     #[inline]
     fn begin_texture_mode(&mut self, target: &RenderTexture2D) -> DrawTextureMode<Self> {
+        stats::record_batch();
+
         unsafe {
             ffi::BeginTextureMode(target.raw.clone());
         }
@@ -337,9 +1165,19 @@ where
         DrawTextureMode(self)
     }
 
+    /// Draw to render texture inside the closure, see [`Draw::begin_texture_mode`]
+    #[inline]
+    fn with_texture_mode(&mut self, target: &RenderTexture2D, f: impl FnOnce(&mut Self)) {
+        let mut mode = self.begin_texture_mode(target);
+
+        f(&mut *mode.0);
+    }
+
     /// Begin custom shader drawing
     #[inline]
     fn begin_shader_mode(&mut self, shader: &Shader) -> DrawShaderMode<Self> {
+        stats::record_batch();
+
         unsafe {
             ffi::BeginShaderMode(shader.raw.clone());
         }
@@ -350,6 +1188,8 @@ where
     /// Begin blending mode (alpha, additive, multiplied, subtract, custom)
     #[inline]
     fn begin_blend_mode(&mut self, mode: BlendMode) -> DrawBlendMode<Self> {
+        stats::record_batch();
+
         unsafe {
             ffi::BeginBlendMode(mode as _);
         }
@@ -366,6 +1206,8 @@ where
         width: u32,
         height: u32,
     ) -> DrawScissorMode<Self> {
+        stats::record_batch();
+
         unsafe {
             ffi::BeginScissorMode(x as _, y as _, width as _, height as _);
         }
@@ -376,6 +1218,8 @@ where
     /// Begin stereo rendering (requires VR simulator)
     #[inline]
     fn begin_vr_stereo_mode(&mut self, config: VrStereoConfig) -> DrawVrStereoMode<Self> {
+        stats::record_batch();
+
         unsafe {
             ffi::BeginVrStereoMode(config.into());
         }
@@ -383,9 +1227,134 @@ where
         DrawVrStereoMode(self)
     }
 
+    /// Begin stencil mode (portals, masks, cutouts)
+    ///
+    /// Only the screen has a stencil buffer, render textures don't. Not available on OpenGL ES platforms.
+    #[inline]
+    fn begin_stencil_mode(&mut self, state: StencilState) -> DrawStencilMode<Self> {
+        apply_stencil_state(Some(state));
+
+        DrawStencilMode(self)
+    }
+
+    /// Set the whole stencil buffer to a value
+    #[inline]
+    fn clear_stencil(&mut self, value: u8) {
+        let functions = gl::functions();
+        let (Some(stencil_mask), Some(clear_stencil), Some(clear)) = (
+            functions.stencil_mask,
+            functions.clear_stencil,
+            functions.clear,
+        ) else {
+            return;
+        };
+
+        let state = *STENCIL_STATE.lock().unwrap_or_else(|e| e.into_inner());
+
+        unsafe {
+            gl::flush_batch();
+
+            // clearing respects the write mask
+            stencil_mask(0xFF);
+            clear_stencil(value as _);
+            clear(gl::STENCIL_BUFFER_BIT);
+            stencil_mask(state.map_or(0xFF, |state| state.write_mask as _));
+        }
+    }
+
+    /// Begin drawing with the depth test enabled or disabled (e.g. disabled for x-ray views)
+    ///
+    /// The previous state is restored when the mode ends.
+    #[inline]
+    fn begin_depth_test_mode(&mut self, enabled: bool) -> DrawDepthTestMode<Self> {
+        let previous = depth_test();
+        set_depth_test(enabled);
+
+        DrawDepthTestMode(self, previous)
+    }
+
+    /// Begin drawing with depth writes enabled or disabled (e.g. disabled for transparent objects)
+    ///
+    /// The previous state is restored when the mode ends.
+    #[inline]
+    fn begin_depth_write_mode(&mut self, enabled: bool) -> DrawDepthWriteMode<Self> {
+        let previous = depth_write();
+        set_depth_write(enabled);
+
+        DrawDepthWriteMode(self, previous)
+    }
+
+    /// Begin drawing only some color channels (e.g. none for a depth pre-pass)
+    ///
+    /// The previous mask is restored when the mode ends. Not available on OpenGL ES platforms.
+    #[inline]
+    fn begin_color_write_mode(&mut self, mask: ColorWriteMask) -> DrawColorWriteMode<Self> {
+        let previous = color_write_mask();
+        set_color_write_mask(mask);
+
+        DrawColorWriteMode(self, previous)
+    }
+
+    /// Begin drawing with a face culling mode
+    ///
+    /// The previous mode is restored when the mode ends.
+    #[inline]
+    fn begin_cull_mode(&mut self, mode: CullMode) -> DrawCullMode<Self> {
+        let previous = cull_mode();
+        set_cull_mode(mode);
+
+        DrawCullMode(self, previous)
+    }
+
+    /// Begin drawing polygons as outlines (e.g. debug views of meshes and terrain)
+    ///
+    /// Not available on OpenGL ES platforms.
+    #[inline]
+    fn begin_wireframe_mode(&mut self) -> DrawWireframeMode<Self> {
+        unsafe {
+            gl::flush_batch();
+            gl::rlEnableWireMode();
+        }
+
+        DrawWireframeMode(self)
+    }
+
+    /// Draw polygons as outlines inside the closure, see [`Draw::begin_wireframe_mode`]
+    #[inline]
+    fn with_wireframe(&mut self, f: impl FnOnce(&mut Self)) {
+        let mut mode = self.begin_wireframe_mode();
+
+        f(&mut *mode.0);
+    }
+
+    /// Begin drawing polygons as points of a size (in pixels)
+    ///
+    /// Not available on OpenGL ES platforms.
+    #[inline]
+    fn begin_points_mode(&mut self, size: f32) -> DrawPointsMode<Self> {
+        set_polygon_mode(gl::POINT, size);
+
+        DrawPointsMode(self)
+    }
+
+    /// Draw polygons as points of a size (in pixels) inside the closure, see [`Draw::begin_points_mode`]
+    #[inline]
+    fn with_points(&mut self, size: f32, f: impl FnOnce(&mut Self)) {
+        let mut mode = self.begin_points_mode(size);
+
+        f(&mut *mode.0);
+    }
+
     /// Draw a part of a texture defined by source and destination rectangles
     #[inline]
-    fn draw_texture(&mut self, tex: &Texture, position: Vector2, params: DrawTextureParams) {
+    fn draw_texture(
+        &mut self,
+        tex: &Texture,
+        position: impl Into<Vector2>,
+        params: DrawTextureParams,
+    ) {
+        let position: Vector2 = position.into();
+
         // rectangle checks?
         let source =
             params
@@ -396,16 +1365,84 @@ where
             ffi::DrawTexturePro(
                 tex.raw.clone(),
                 source.into(),
-                Rectangle::new(
-                    position.x,
-                    position.y,
-                    params.scale.x * source.width,
-                    params.scale.y * source.height,
-                )
-                .into(),
-                params.origin.into(),
-                params.rotation,
-                params.tint.into(),
+                Rectangle::new(
+                    position.x,
+                    position.y,
+                    params.scale.x * source.width,
+                    params.scale.y * source.height,
+                )
+                .into(),
+                params.origin.into(),
+                params.rotation,
+                params.tint.into(),
+            )
+        }
+    }
+
+    /// Draw a texture (same as raylib's `DrawTexture`)
+    #[inline]
+    fn draw_texture_simple(&mut self, tex: &Texture, x: i32, y: i32, tint: Color) {
+        unsafe { ffi::DrawTexture(tex.raw.clone(), x, y, tint.into()) }
+    }
+
+    /// Draw a texture with extended parameters (same as raylib's `DrawTextureEx`)
+    #[inline]
+    fn draw_texture_ex(
+        &mut self,
+        tex: &Texture,
+        position: impl Into<Vector2>,
+        rotation: f32,
+        scale: f32,
+        tint: Color,
+    ) {
+        let position: Vector2 = position.into();
+
+        unsafe {
+            ffi::DrawTextureEx(
+                tex.raw.clone(),
+                position.into(),
+                rotation,
+                scale,
+                tint.into(),
+            )
+        }
+    }
+
+    /// Draw a part of a texture defined by a rectangle (same as raylib's `DrawTextureRec`)
+    #[inline]
+    fn draw_texture_rect(
+        &mut self,
+        tex: &Texture,
+        source: Rectangle,
+        position: impl Into<Vector2>,
+        tint: Color,
+    ) {
+        let position: Vector2 = position.into();
+
+        unsafe { ffi::DrawTextureRec(tex.raw.clone(), source.into(), position.into(), tint.into()) }
+    }
+
+    /// Draw a part of a texture defined by source and destination rectangles (same as raylib's `DrawTexturePro`)
+    #[inline]
+    fn draw_texture_pro(
+        &mut self,
+        tex: &Texture,
+        source: Rectangle,
+        dest: Rectangle,
+        origin: impl Into<Vector2>,
+        rotation: f32,
+        tint: Color,
+    ) {
+        let origin: Vector2 = origin.into();
+
+        unsafe {
+            ffi::DrawTexturePro(
+                tex.raw.clone(),
+                source.into(),
+                dest.into(),
+                origin.into(),
+                rotation,
+                tint.into(),
             )
         }
     }
@@ -415,10 +1452,12 @@ where
     fn draw_texture_patch(
         &mut self,
         tex: &Texture,
-        position: Vector2,
+        position: impl Into<Vector2>,
         params: DrawTextureParams,
         patch_info: NPatchInfo,
     ) {
+        let position: Vector2 = position.into();
+
         let source = patch_info.source;
 
         unsafe {
@@ -439,7 +1478,46 @@ where
         }
     }
 
+    /// Fill `dest` with a texture tiled `uv_scale` times and shifted by `uv_offset` (in texture sizes)
+    ///
+    /// For scrolling backgrounds and conveyor belts: move `uv_offset` over time, e.g. slower for far parallax layers.
+    /// Negative scales mirror the texture. The texture has to use [`TextureWrap::Repeat`](crate::texture::TextureWrap::Repeat)
+    /// (the default), and on OpenGL ES 2 it has to be a power of two in size.
+    #[inline]
+    fn draw_texture_scrolled(
+        &mut self,
+        tex: &Texture,
+        dest: Rectangle,
+        uv_offset: impl Into<Vector2>,
+        uv_scale: impl Into<Vector2>,
+        tint: Color,
+    ) {
+        let (uv_offset, uv_scale): (Vector2, Vector2) = (uv_offset.into(), uv_scale.into());
+        let (width, height) = (tex.width() as f32, tex.height() as f32);
+
+        // texture coordinates past the edges repeat the texture
+        let source = Rectangle::new(
+            uv_offset.x.rem_euclid(1.) * width,
+            uv_offset.y.rem_euclid(1.) * height,
+            uv_scale.x * width,
+            uv_scale.y * height,
+        );
+
+        unsafe {
+            ffi::DrawTexturePro(
+                tex.raw.clone(),
+                source.into(),
+                dest.into(),
+                Vector2 { x: 0., y: 0. }.into(),
+                0.,
+                tint.into(),
+            )
+        }
+    }
+
     /// Set texture and rectangle to be used on shapes drawing
+    ///
+    /// See [`BatchOptimizer`] for why you would want to
     #[inline]
     fn set_shapes_texture(&mut self, texture: &Texture, source: Rectangle) {
         unsafe { ffi::SetShapesTexture(texture.raw.clone(), source.into()) }
@@ -447,25 +1525,64 @@ where
 
     /// Draw a pixel
     #[inline]
-    fn draw_pixel(&mut self, position: Vector2, color: Color) {
+    fn draw_pixel(&mut self, position: impl Into<Vector2>, color: Color) {
+        let position: Vector2 = position.into();
+
         unsafe { ffi::DrawPixelV(position.into(), color.into()) }
     }
 
+    /// Draw multiple pixels
+    #[inline]
+    fn draw_pixels(&mut self, pixels: &[(Vector2, Color)]) {
+        for (position, color) in pixels.iter() {
+            unsafe { ffi::DrawPixelV((*position).into(), (*color).into()) }
+        }
+    }
+
     /// Draw a line
     #[inline]
-    fn draw_line(&mut self, start: Vector2, end: Vector2, color: Color) {
+    fn draw_line(&mut self, start: impl Into<Vector2>, end: impl Into<Vector2>, color: Color) {
+        let start: Vector2 = start.into();
+        let end: Vector2 = end.into();
+
         unsafe { ffi::DrawLineV(start.into(), end.into(), color.into()) }
     }
 
+    /// Draw multiple separate lines, given as (start, end) pairs
+    #[inline]
+    fn draw_lines(&mut self, lines: &[(Vector2, Vector2)], color: Color) {
+        for (start, end) in lines.iter() {
+            unsafe { ffi::DrawLineV((*start).into(), (*end).into(), color.into()) }
+        }
+    }
+
     /// Draw a line defining thickness
     #[inline]
-    fn draw_line_thick(&mut self, start: Vector2, end: Vector2, thickness: f32, color: Color) {
+    fn draw_line_thick(
+        &mut self,
+        start: impl Into<Vector2>,
+        end: impl Into<Vector2>,
+        thickness: f32,
+        color: Color,
+    ) {
+        let start: Vector2 = start.into();
+        let end: Vector2 = end.into();
+
         unsafe { ffi::DrawLineEx(start.into(), end.into(), thickness, color.into()) }
     }
 
     /// Draw a line using cubic-bezier curves in-out
     #[inline]
-    fn draw_line_bezier(&mut self, start: Vector2, end: Vector2, thickness: f32, color: Color) {
+    fn draw_line_bezier(
+        &mut self,
+        start: impl Into<Vector2>,
+        end: impl Into<Vector2>,
+        thickness: f32,
+        color: Color,
+    ) {
+        let start: Vector2 = start.into();
+        let end: Vector2 = end.into();
+
         unsafe { ffi::DrawLineBezier(start.into(), end.into(), thickness, color.into()) }
     }
 
@@ -473,12 +1590,16 @@ where
     #[inline]
     fn draw_line_bezier_quad(
         &mut self,
-        start: Vector2,
-        end: Vector2,
-        control_pos: Vector2,
+        start: impl Into<Vector2>,
+        end: impl Into<Vector2>,
+        control_pos: impl Into<Vector2>,
         thickness: f32,
         color: Color,
     ) {
+        let start: Vector2 = start.into();
+        let end: Vector2 = end.into();
+        let control_pos: Vector2 = control_pos.into();
+
         unsafe {
             ffi::DrawLineBezierQuad(
                 start.into(),
@@ -494,13 +1615,18 @@ where
     #[inline]
     fn draw_line_bezier_cubic(
         &mut self,
-        start: Vector2,
-        end: Vector2,
-        start_control_pos: Vector2,
-        end_control_pos: Vector2,
+        start: impl Into<Vector2>,
+        end: impl Into<Vector2>,
+        start_control_pos: impl Into<Vector2>,
+        end_control_pos: impl Into<Vector2>,
         thickness: f32,
         color: Color,
     ) {
+        let start: Vector2 = start.into();
+        let end: Vector2 = end.into();
+        let start_control_pos: Vector2 = start_control_pos.into();
+        let end_control_pos: Vector2 = end_control_pos.into();
+
         unsafe {
             ffi::DrawLineBezierCubic(
                 start.into(),
@@ -515,13 +1641,21 @@ where
 
     /// Draw lines sequence
     #[inline]
-    fn draw_line_strip(&mut self, points: &[Vector2], color: Color) {
-        unsafe { ffi::DrawLineStrip(points.as_ptr() as *mut _, points.len() as _, color.into()) }
+    fn draw_line_strip(
+        &mut self,
+        points: impl IntoIterator<Item = impl Into<ffi::Vector2>>,
+        color: Color,
+    ) {
+        with_scratch(&SCRATCH_2D, points, |points| unsafe {
+            ffi::DrawLineStrip(points.as_mut_ptr(), points.len() as _, color.into())
+        })
     }
 
     /// Draw a color-filled circle
     #[inline]
-    fn draw_circle(&mut self, center: Vector2, radius: f32, color: Color) {
+    fn draw_circle(&mut self, center: impl Into<Vector2>, radius: f32, color: Color) {
+        let center: Vector2 = center.into();
+
         unsafe { ffi::DrawCircleV(center.into(), radius, color.into()) }
     }
 
@@ -533,7 +1667,15 @@ where
 
     /// Draw ellipse
     #[inline]
-    fn draw_ellipse(&mut self, center: Vector2, radius: Vector2, color: Color) {
+    fn draw_ellipse(
+        &mut self,
+        center: impl Into<Vector2>,
+        radius: impl Into<Vector2>,
+        color: Color,
+    ) {
+        let center: Vector2 = center.into();
+        let radius: Vector2 = radius.into();
+
         unsafe {
             ffi::DrawEllipse(
                 center.x as _,
@@ -547,7 +1689,15 @@ where
 
     /// Draw ellipse outline
     #[inline]
-    fn draw_ellipse_lines(&mut self, center: Vector2, radius: Vector2, color: Color) {
+    fn draw_ellipse_lines(
+        &mut self,
+        center: impl Into<Vector2>,
+        radius: impl Into<Vector2>,
+        color: Color,
+    ) {
+        let center: Vector2 = center.into();
+        let radius: Vector2 = radius.into();
+
         unsafe {
             ffi::DrawEllipseLines(
                 center.x as _,
@@ -563,12 +1713,14 @@ where
     #[inline]
     fn draw_circle_sector(
         &mut self,
-        center: Vector2,
+        center: impl Into<Vector2>,
         radius: f32,
         angle: Range<f32>,
         segments: u32,
         color: Color,
     ) {
+        let center: Vector2 = center.into();
+
         unsafe {
             ffi::DrawCircleSector(
                 center.into(),
@@ -585,12 +1737,14 @@ where
     #[inline]
     fn draw_circle_sector_lines(
         &mut self,
-        center: Vector2,
+        center: impl Into<Vector2>,
         radius: f32,
         angle: Range<f32>,
         segments: u32,
         color: Color,
     ) {
+        let center: Vector2 = center.into();
+
         unsafe {
             ffi::DrawCircleSectorLines(
                 center.into(),
@@ -605,7 +1759,15 @@ where
 
     /// Draw a gradient-filled circle
     #[inline]
-    fn draw_circle_gradient(&mut self, center: Vector2, radius: f32, color1: Color, color2: Color) {
+    fn draw_circle_gradient(
+        &mut self,
+        center: impl Into<Vector2>,
+        radius: f32,
+        color1: Color,
+        color2: Color,
+    ) {
+        let center: Vector2 = center.into();
+
         unsafe {
             ffi::DrawCircleGradient(
                 center.x as _,
@@ -622,7 +1784,7 @@ where
     #[allow(clippy::too_many_arguments)]
     fn draw_ring(
         &mut self,
-        center: Vector2,
+        center: impl Into<Vector2>,
         inner_radius: f32,
         outer_radius: f32,
         start_angle: f32,
@@ -630,6 +1792,8 @@ where
         segments: u32,
         color: Color,
     ) {
+        let center: Vector2 = center.into();
+
         unsafe {
             ffi::DrawRing(
                 center.into(),
@@ -647,12 +1811,14 @@ where
     #[inline]
     fn draw_ring_lines(
         &mut self,
-        center: Vector2,
+        center: impl Into<Vector2>,
         radius: Range<f32>,
         angle: Range<f32>,
         segments: u32,
         color: Color,
     ) {
+        let center: Vector2 = center.into();
+
         unsafe {
             ffi::DrawRingLines(
                 center.into(),
@@ -672,6 +1838,14 @@ where
         unsafe { ffi::DrawRectangleRec(rect.into(), color.into()) }
     }
 
+    /// Draw multiple color-filled rectangles
+    #[inline]
+    fn draw_rectangles(&mut self, rects: &[(Rectangle, Color)]) {
+        for (rect, color) in rects.iter() {
+            unsafe { ffi::DrawRectangleRec((*rect).into(), (*color).into()) }
+        }
+    }
+
     /// Draw rectangle outline
     #[inline]
     fn draw_rectangle_lines(&mut self, rect: Rectangle, color: Color) {
@@ -697,10 +1871,12 @@ where
     fn draw_rectangle_rotated(
         &mut self,
         rect: Rectangle,
-        origin: Vector2,
+        origin: impl Into<Vector2>,
         rotation: f32,
         color: Color,
     ) {
+        let origin: Vector2 = origin.into();
+
         unsafe { ffi::DrawRectanglePro(rect.into(), origin.into(), rotation, color.into()) }
     }
 
@@ -795,40 +1971,72 @@ where
 
     /// Draw a color-filled triangle (vertex in counter-clockwise order!)
     #[inline]
-    fn draw_triangle(&mut self, v1: Vector2, v2: Vector2, v3: Vector2, color: Color) {
+    fn draw_triangle(
+        &mut self,
+        v1: impl Into<Vector2>,
+        v2: impl Into<Vector2>,
+        v3: impl Into<Vector2>,
+        color: Color,
+    ) {
+        let v1: Vector2 = v1.into();
+        let v2: Vector2 = v2.into();
+        let v3: Vector2 = v3.into();
+
         unsafe { ffi::DrawTriangle(v1.into(), v2.into(), v3.into(), color.into()) }
     }
 
     /// Draw triangle outline (vertex in counter-clockwise order!)
     #[inline]
-    fn draw_triangle_lines(&mut self, v1: Vector2, v2: Vector2, v3: Vector2, color: Color) {
+    fn draw_triangle_lines(
+        &mut self,
+        v1: impl Into<Vector2>,
+        v2: impl Into<Vector2>,
+        v3: impl Into<Vector2>,
+        color: Color,
+    ) {
+        let v1: Vector2 = v1.into();
+        let v2: Vector2 = v2.into();
+        let v3: Vector2 = v3.into();
+
         unsafe { ffi::DrawTriangleLines(v1.into(), v2.into(), v3.into(), color.into()) }
     }
 
     /// Draw a triangle fan defined by points (first vertex is the center)
     #[inline]
-    fn draw_triangle_fan(&mut self, points: &[Vector2], color: Color) {
-        unsafe { ffi::DrawTriangleFan(points.as_ptr() as *mut _, points.len() as _, color.into()) }
+    fn draw_triangle_fan(
+        &mut self,
+        points: impl IntoIterator<Item = impl Into<ffi::Vector2>>,
+        color: Color,
+    ) {
+        with_scratch(&SCRATCH_2D, points, |points| unsafe {
+            ffi::DrawTriangleFan(points.as_mut_ptr(), points.len() as _, color.into())
+        })
     }
 
     /// Draw a triangle strip defined by points
     #[inline]
-    fn draw_triangle_strip(&mut self, points: &[Vector2], color: Color) {
-        unsafe {
-            ffi::DrawTriangleStrip(points.as_ptr() as *mut _, points.len() as _, color.into())
-        }
+    fn draw_triangle_strip(
+        &mut self,
+        points: impl IntoIterator<Item = impl Into<ffi::Vector2>>,
+        color: Color,
+    ) {
+        with_scratch(&SCRATCH_2D, points, |points| unsafe {
+            ffi::DrawTriangleStrip(points.as_mut_ptr(), points.len() as _, color.into())
+        })
     }
 
     /// Draw a regular polygon (Vector version)
     #[inline]
     fn draw_polygon(
         &mut self,
-        center: Vector2,
+        center: impl Into<Vector2>,
         sides: u32,
         radius: f32,
         rotation: f32,
         color: Color,
     ) {
+        let center: Vector2 = center.into();
+
         unsafe { ffi::DrawPoly(center.into(), sides as _, radius, rotation, color.into()) }
     }
 
@@ -836,12 +2044,14 @@ where
     #[inline]
     fn draw_polygon_lines(
         &mut self,
-        center: Vector2,
+        center: impl Into<Vector2>,
         sides: u32,
         radius: f32,
         rotation: f32,
         color: Color,
     ) {
+        let center: Vector2 = center.into();
+
         unsafe { ffi::DrawPolyLines(center.into(), sides as _, radius, rotation, color.into()) }
     }
 
@@ -849,13 +2059,15 @@ where
     #[inline]
     fn draw_polygon_lines_thick(
         &mut self,
-        center: Vector2,
+        center: impl Into<Vector2>,
         sides: u32,
         radius: f32,
         rotation: f32,
         line_thickness: f32,
         color: Color,
     ) {
+        let center: Vector2 = center.into();
+
         unsafe {
             ffi::DrawPolyLinesEx(
                 center.into(),
@@ -870,14 +2082,38 @@ where
 
     /// Draw current FPS
     #[inline]
-    fn draw_fps(&mut self, position: Vector2) {
+    fn draw_fps(&mut self, position: impl Into<Vector2>) {
+        let position: Vector2 = position.into();
+
         unsafe { ffi::DrawFPS(position.x as _, position.y as _) }
     }
 
     /// Draw text (using default font)
     #[inline]
-    fn draw_text(&mut self, text: &str, position: Vector2, font_size: u32, color: Color) {
-        let text = CString::new(text).unwrap();
+    fn draw_text(
+        &mut self,
+        text: &str,
+        position: impl Into<Vector2>,
+        font_size: u32,
+        color: Color,
+    ) {
+        let position: Vector2 = position.into();
+
+        let text = text_to_cstring(text);
+
+        self.draw_text_cstr(&text, position, font_size, color)
+    }
+
+    /// Draw text (using default font), without converting it first
+    #[inline]
+    fn draw_text_cstr(
+        &mut self,
+        text: &CStr,
+        position: impl Into<Vector2>,
+        font_size: u32,
+        color: Color,
+    ) {
+        let position: Vector2 = position.into();
 
         unsafe {
             ffi::DrawText(
@@ -895,13 +2131,31 @@ where
     fn draw_text_with_font(
         &mut self,
         text: &str,
-        pos: Vector2,
+        pos: impl Into<Vector2>,
+        font: &Font,
+        font_size: f32,
+        spacing: f32,
+        tint: Color,
+    ) {
+        let pos: Vector2 = pos.into();
+
+        let text = text_to_cstring(text);
+
+        self.draw_text_with_font_cstr(&text, pos, font, font_size, spacing, tint)
+    }
+
+    /// Draw text using font and additional parameters, without converting it first
+    #[inline]
+    fn draw_text_with_font_cstr(
+        &mut self,
+        text: &CStr,
+        pos: impl Into<Vector2>,
         font: &Font,
         font_size: f32,
         spacing: f32,
         tint: Color,
     ) {
-        let text = CString::new(text).unwrap();
+        let pos: Vector2 = pos.into();
 
         unsafe {
             ffi::DrawTextEx(
@@ -915,21 +2169,62 @@ where
         }
     }
 
+    /// Draw prepared text (using default font)
+    #[inline]
+    fn draw_static_text(&mut self, text: &StaticText, pos: impl Into<Vector2>, tint: Color) {
+        let pos: Vector2 = pos.into();
+
+        unsafe {
+            ffi::DrawTextEx(
+                ffi::GetFontDefault(),
+                text.as_cstr().as_ptr(),
+                pos.into(),
+                text.font_size(),
+                text.spacing(),
+                tint.into(),
+            )
+        }
+    }
+
+    /// Draw prepared text using font
+    #[inline]
+    fn draw_static_text_with_font(
+        &mut self,
+        text: &StaticText,
+        pos: impl Into<Vector2>,
+        font: &Font,
+        tint: Color,
+    ) {
+        let pos: Vector2 = pos.into();
+
+        self.draw_text_with_font_cstr(
+            text.as_cstr(),
+            pos,
+            font,
+            text.font_size(),
+            text.spacing(),
+            tint,
+        )
+    }
+
     /// Draw text using Font and pro parameters (rotation)
     #[inline]
     #[allow(clippy::too_many_arguments)]
     fn draw_text_with_font_and_rotation(
         &mut self,
         text: &str,
-        pos: Vector2,
-        origin: Vector2,
+        pos: impl Into<Vector2>,
+        origin: impl Into<Vector2>,
         rotation: f32,
         font: &Font,
         font_size: f32,
         spacing: f32,
         tint: Color,
     ) {
-        let text = CString::new(text).unwrap();
+        let pos: Vector2 = pos.into();
+        let origin: Vector2 = origin.into();
+
+        let text = text_to_cstring(text);
 
         unsafe {
             ffi::DrawTextPro(
@@ -945,9 +2240,65 @@ where
         }
     }
 
+    /// Draw text with outline and shadow, the shadow first, then the outline
+    ///
+    /// The outline is made by drawing the text around its position several times,
+    /// so it works with any font but overlaps in translucent colors.
+    /// [`SdfFont::draw_text_styled`](crate::text::SdfFont::draw_text_styled) draws outlines in one pass.
+    #[allow(clippy::too_many_arguments)]
+    fn draw_text_styled(
+        &mut self,
+        text: &str,
+        pos: impl Into<Vector2>,
+        font: &Font,
+        font_size: f32,
+        spacing: f32,
+        tint: Color,
+        style: &TextStyle,
+    ) {
+        let pos: Vector2 = pos.into();
+        let text = text_to_cstring(text);
+        let offsets = style.outline_offsets();
+
+        let mut draw_outlined = |pos: Vector2, fill: Color, outline: Color| {
+            for offset in offsets.iter() {
+                let pos = Vector2 {
+                    x: pos.x + offset.x,
+                    y: pos.y + offset.y,
+                };
+
+                self.draw_text_with_font_cstr(&text, pos, font, font_size, spacing, outline);
+            }
+
+            self.draw_text_with_font_cstr(&text, pos, font, font_size, spacing, fill);
+        };
+
+        if let Some((offset, color)) = style.shadow {
+            let pos = Vector2 {
+                x: pos.x + offset.x,
+                y: pos.y + offset.y,
+            };
+
+            draw_outlined(pos, color, color);
+        }
+
+        let outline = style.outline.map_or(tint, |(_, color)| color);
+
+        draw_outlined(pos, tint, outline);
+    }
+
     /// Draw one character
     #[inline]
-    fn draw_char(&mut self, ch: char, pos: Vector2, font: &Font, font_size: f32, tint: Color) {
+    fn draw_char(
+        &mut self,
+        ch: char,
+        pos: impl Into<Vector2>,
+        font: &Font,
+        font_size: f32,
+        tint: Color,
+    ) {
+        let pos: Vector2 = pos.into();
+
         unsafe {
             ffi::DrawTextCodepoint(
                 font.raw.clone(),
@@ -964,16 +2315,20 @@ where
     fn draw_chars(
         &mut self,
         chars: &[char],
-        pos: Vector2,
+        pos: impl Into<Vector2>,
         font: &Font,
         font_size: f32,
         spacing: f32,
         tint: Color,
     ) {
+        let pos: Vector2 = pos.into();
+
+        // every `char` is a unicode scalar value (<= 0x10FFFF), so it's also a valid non-negative C int
+        // with the same size and alignment; no conversion is needed
         unsafe {
             ffi::DrawTextCodepoints(
                 font.raw.clone(),
-                chars.as_ptr() as *const _,
+                chars.as_ptr() as *const core::ffi::c_int,
                 chars.len() as _,
                 pos.into(),
                 font_size,
@@ -983,15 +2338,77 @@ where
         }
     }
 
+    /// Draw the characters of a string one by one, without allocating
+    ///
+    /// Same layout as [`Self::draw_chars`].
+    #[inline]
+    fn draw_text_codepoints(
+        &mut self,
+        text: &str,
+        pos: impl Into<Vector2>,
+        font: &Font,
+        font_size: f32,
+        spacing: f32,
+        tint: Color,
+    ) {
+        let pos: Vector2 = pos.into();
+
+        let scale = font_size / font.base_size() as f32;
+
+        let mut offset_x = 0.;
+        let mut offset_y = 0.;
+
+        for ch in text.chars() {
+            if ch == '\n' {
+                offset_y += (font.base_size() + font.base_size() / 2) as f32 * scale;
+                offset_x = 0.;
+
+                continue;
+            }
+
+            if ch != ' ' && ch != '\t' {
+                self.draw_char(
+                    ch,
+                    Vector2 {
+                        x: pos.x + offset_x,
+                        y: pos.y + offset_y,
+                    },
+                    font,
+                    font_size,
+                    tint,
+                );
+            }
+
+            let index = font.get_glyph_index(ch);
+            let advance = unsafe { (*font.raw.glyphs.add(index)).advanceX };
+
+            offset_x += if advance == 0 {
+                unsafe { (*font.raw.recs.add(index)).width * scale + spacing }
+            } else {
+                advance as f32 * scale + spacing
+            };
+        }
+    }
+
     /// Draw a line in 3D world space
     #[inline]
-    fn draw_line_3d(&mut self, start_pos: Vector3, end_pos: Vector3, color: Color) {
+    fn draw_line_3d(
+        &mut self,
+        start_pos: impl Into<Vector3>,
+        end_pos: impl Into<Vector3>,
+        color: Color,
+    ) {
+        let start_pos: Vector3 = start_pos.into();
+        let end_pos: Vector3 = end_pos.into();
+
         unsafe { ffi::DrawLine3D(start_pos.into(), end_pos.into(), color.into()) }
     }
 
     /// Draw a point in 3D space, actually a small line
     #[inline]
-    fn draw_point_3d(&mut self, position: Vector3, color: Color) {
+    fn draw_point_3d(&mut self, position: impl Into<Vector3>, color: Color) {
+        let position: Vector3 = position.into();
+
         unsafe { ffi::DrawPoint3D(position.into(), color.into()) }
     }
 
@@ -999,12 +2416,15 @@ where
     #[inline]
     fn draw_circle_3d(
         &mut self,
-        center: Vector3,
+        center: impl Into<Vector3>,
         radius: f32,
-        rotation_axis: Vector3,
+        rotation_axis: impl Into<Vector3>,
         rotation_angle: f32,
         color: Color,
     ) {
+        let center: Vector3 = center.into();
+        let rotation_axis: Vector3 = rotation_axis.into();
+
         unsafe {
             ffi::DrawCircle3D(
                 center.into(),
@@ -1018,33 +2438,60 @@ where
 
     /// Draw a color-filled triangle (vertex in counter-clockwise order!)
     #[inline]
-    fn draw_triangle_3d(&mut self, v1: Vector3, v2: Vector3, v3: Vector3, color: Color) {
+    fn draw_triangle_3d(
+        &mut self,
+        v1: impl Into<Vector3>,
+        v2: impl Into<Vector3>,
+        v3: impl Into<Vector3>,
+        color: Color,
+    ) {
+        let v1: Vector3 = v1.into();
+        let v2: Vector3 = v2.into();
+        let v3: Vector3 = v3.into();
+
         unsafe { ffi::DrawTriangle3D(v1.into(), v2.into(), v3.into(), color.into()) }
     }
 
     /// Draw a triangle strip defined by points
     #[inline]
-    fn draw_triangle_strip_3d(&mut self, points: &[Vector3], color: Color) {
-        unsafe {
-            ffi::DrawTriangleStrip3D(points.as_ptr() as *mut _, points.len() as _, color.into())
-        }
+    fn draw_triangle_strip_3d(
+        &mut self,
+        points: impl IntoIterator<Item = impl Into<ffi::Vector3>>,
+        color: Color,
+    ) {
+        with_scratch(&SCRATCH_3D, points, |points| unsafe {
+            ffi::DrawTriangleStrip3D(points.as_mut_ptr(), points.len() as _, color.into())
+        })
     }
 
     /// Draw cube
     #[inline]
-    fn draw_cube(&mut self, position: Vector3, size: Vector3, color: Color) {
+    fn draw_cube(&mut self, position: impl Into<Vector3>, size: impl Into<Vector3>, color: Color) {
+        let position: Vector3 = position.into();
+        let size: Vector3 = size.into();
+
         unsafe { ffi::DrawCubeV(position.into(), size.into(), color.into()) }
     }
 
     /// Draw cube wires (Vector version)
     #[inline]
-    fn draw_cube_wires(&mut self, position: Vector3, size: Vector3, color: Color) {
+    fn draw_cube_wires(
+        &mut self,
+        position: impl Into<Vector3>,
+        size: impl Into<Vector3>,
+        color: Color,
+    ) {
+        let position: Vector3 = position.into();
+        let size: Vector3 = size.into();
+
         unsafe { ffi::DrawCubeWiresV(position.into(), size.into(), color.into()) }
     }
 
     /// Draw sphere
     #[inline]
-    fn draw_sphere(&mut self, center_pos: Vector3, radius: f32, color: Color) {
+    fn draw_sphere(&mut self, center_pos: impl Into<Vector3>, radius: f32, color: Color) {
+        let center_pos: Vector3 = center_pos.into();
+
         unsafe { ffi::DrawSphere(center_pos.into(), radius, color.into()) }
     }
 
@@ -1052,12 +2499,14 @@ where
     #[inline]
     fn draw_sphere_ex(
         &mut self,
-        center_pos: Vector3,
+        center_pos: impl Into<Vector3>,
         radius: f32,
         rings: u32,
         slices: u32,
         color: Color,
     ) {
+        let center_pos: Vector3 = center_pos.into();
+
         unsafe {
             ffi::DrawSphereEx(
                 center_pos.into(),
@@ -1073,12 +2522,14 @@ where
     #[inline]
     fn draw_sphere_wires(
         &mut self,
-        center_pos: Vector3,
+        center_pos: impl Into<Vector3>,
         radius: f32,
         rings: u32,
         slices: u32,
         color: Color,
     ) {
+        let center_pos: Vector3 = center_pos.into();
+
         unsafe {
             ffi::DrawSphereWires(
                 center_pos.into(),
@@ -1094,13 +2545,15 @@ where
     #[inline]
     fn draw_cylinder(
         &mut self,
-        position: Vector3,
+        position: impl Into<Vector3>,
         radius_top: f32,
         radius_bottom: f32,
         height: f32,
         slices: u32,
         color: Color,
     ) {
+        let position: Vector3 = position.into();
+
         unsafe {
             ffi::DrawCylinder(
                 position.into(),
@@ -1117,13 +2570,16 @@ where
     #[inline]
     fn draw_cylinder_ex(
         &mut self,
-        pos_top: Vector3,
-        pos_bottom: Vector3,
+        pos_top: impl Into<Vector3>,
+        pos_bottom: impl Into<Vector3>,
         radius_top: f32,
         radius_bottom: f32,
         sides: u32,
         color: Color,
     ) {
+        let pos_top: Vector3 = pos_top.into();
+        let pos_bottom: Vector3 = pos_bottom.into();
+
         unsafe {
             ffi::DrawCylinderEx(
                 pos_bottom.into(),
@@ -1140,13 +2596,15 @@ where
     #[inline]
     fn draw_cylinder_wires(
         &mut self,
-        position: Vector3,
+        position: impl Into<Vector3>,
         radius_top: f32,
         radius_bottom: f32,
         height: f32,
         slices: u32,
         color: Color,
     ) {
+        let position: Vector3 = position.into();
+
         unsafe {
             ffi::DrawCylinderWires(
                 position.into(),
@@ -1163,13 +2621,16 @@ where
     #[inline]
     fn draw_cylinder_wires_ex(
         &mut self,
-        pos_top: Vector3,
-        pos_bottom: Vector3,
+        pos_top: impl Into<Vector3>,
+        pos_bottom: impl Into<Vector3>,
         radius_top: f32,
         radius_bottom: f32,
         sides: u32,
         color: Color,
     ) {
+        let pos_top: Vector3 = pos_top.into();
+        let pos_bottom: Vector3 = pos_bottom.into();
+
         unsafe {
             ffi::DrawCylinderWiresEx(
                 pos_bottom.into(),
@@ -1186,13 +2647,16 @@ where
     #[inline]
     fn draw_capsule(
         &mut self,
-        start_pos: Vector3,
-        end_pos: Vector3,
+        start_pos: impl Into<Vector3>,
+        end_pos: impl Into<Vector3>,
         radius: f32,
         slices: u32,
         rings: u32,
         color: Color,
     ) {
+        let start_pos: Vector3 = start_pos.into();
+        let end_pos: Vector3 = end_pos.into();
+
         unsafe {
             ffi::DrawCapsule(
                 start_pos.into(),
@@ -1209,13 +2673,16 @@ where
     #[inline]
     fn draw_capsule_wires(
         &mut self,
-        start_pos: Vector3,
-        end_pos: Vector3,
+        start_pos: impl Into<Vector3>,
+        end_pos: impl Into<Vector3>,
         radius: f32,
         slices: u32,
         rings: u32,
         color: Color,
     ) {
+        let start_pos: Vector3 = start_pos.into();
+        let end_pos: Vector3 = end_pos.into();
+
         unsafe {
             ffi::DrawCapsuleWires(
                 start_pos.into(),
@@ -1230,7 +2697,15 @@ where
 
     /// Draw a plane XZ
     #[inline]
-    fn draw_plane(&mut self, center_pos: Vector3, size: Vector2, color: Color) {
+    fn draw_plane(
+        &mut self,
+        center_pos: impl Into<Vector3>,
+        size: impl Into<Vector2>,
+        color: Color,
+    ) {
+        let center_pos: Vector3 = center_pos.into();
+        let size: Vector2 = size.into();
+
         unsafe { ffi::DrawPlane(center_pos.into(), size.into(), color.into()) }
     }
 
@@ -1248,21 +2723,126 @@ where
 
     /// Draw a model (with texture if set)
     #[inline]
-    fn draw_model(&mut self, model: &Model, position: Vector3, scale: f32, tint: Color) {
+    fn draw_model(&mut self, model: &Model, position: impl Into<Vector3>, scale: f32, tint: Color) {
+        let position: Vector3 = position.into();
+
         unsafe { ffi::DrawModel(model.raw.clone(), position.into(), scale, tint.into()) }
     }
 
+    /// Draw a soft dark blob on the ground (at `ground_y`) below `center`, a cheap stand-in for a real shadow
+    fn draw_blob_shadow(
+        &mut self,
+        center: impl Into<Vector3>,
+        radius: f32,
+        ground_y: f32,
+        opacity: f32,
+    ) {
+        const LAYERS: u32 = 4;
+        const SEGMENTS: u32 = 24;
+
+        let center: Vector3 = center.into();
+        // stacked discs of decreasing size, so the blob gets darker towards the middle
+        let color = Color::new(
+            0,
+            0,
+            0,
+            (opacity.clamp(0., 1.) * 255. / LAYERS as f32) as u8,
+        );
+
+        for layer in 0..LAYERS {
+            let r = radius * (1. - layer as f32 / (2 * LAYERS) as f32);
+            let y = ground_y + 0.001 * (layer + 1) as f32;
+            let point = |i: u32| {
+                let angle = i as f32 / SEGMENTS as f32 * std::f32::consts::TAU;
+
+                Vector3 {
+                    x: center.x + r * angle.cos(),
+                    y,
+                    z: center.z + r * angle.sin(),
+                }
+            };
+
+            for i in 0..SEGMENTS {
+                unsafe {
+                    // counter-clockwise seen from above
+                    ffi::DrawTriangle3D(
+                        Vector3 {
+                            x: center.x,
+                            y,
+                            z: center.z,
+                        }
+                        .into(),
+                        point(i + 1).into(),
+                        point(i).into(),
+                        color.into(),
+                    )
+                }
+            }
+        }
+    }
+
+    /// Draw the shadow of a model flattened onto a plane along the light direction (planar projected shadow)
+    ///
+    /// The model is drawn a second time, squashed onto the plane and tinted black with `opacity`.
+    /// Overlapping parts of the model darken the shadow more.
+    fn draw_model_shadow(
+        &mut self,
+        model: &Model,
+        light_dir: impl Into<Vector3>,
+        plane_point: impl Into<Vector3>,
+        plane_normal: impl Into<Vector3>,
+        opacity: f32,
+    ) {
+        let plane_normal: Vector3 = plane_normal.into();
+        let plane_point: Vector3 = plane_point.into();
+
+        // lift the shadow off the plane to avoid z-fighting
+        let lifted = vec3::add(
+            plane_point,
+            vec3::scale(vec3::normalize(plane_normal), 0.01),
+        );
+
+        let shadow = match planar_shadow(light_dir.into(), lifted, plane_normal) {
+            Some(shadow) => shadow,
+            None => return,
+        };
+
+        // squash after the model's own transform
+        let mut raw = model.raw.clone();
+        raw.transform = from_columns(&multiply(&shadow, &columns(&model.transform()))).into();
+
+        let tint = Color::new(0, 0, 0, (opacity.clamp(0., 1.) * 255.) as u8);
+
+        unsafe {
+            ffi::DrawModel(
+                raw,
+                Vector3 {
+                    x: 0.,
+                    y: 0.,
+                    z: 0.,
+                }
+                .into(),
+                1.,
+                tint.into(),
+            )
+        }
+    }
+
     /// Draw a model with extended parameters
     #[inline]
     fn draw_model_ex(
         &mut self,
         model: &Model,
-        position: Vector3,
-        rotation_axis: Vector3,
+        position: impl Into<Vector3>,
+        rotation_axis: impl Into<Vector3>,
         rotation_angle: f32,
-        scale: Vector3,
+        scale: impl Into<Vector3>,
         tint: Color,
     ) {
+        let position: Vector3 = position.into();
+        let rotation_axis: Vector3 = rotation_axis.into();
+        let scale: Vector3 = scale.into();
+
         unsafe {
             ffi::DrawModelEx(
                 model.raw.clone(),
@@ -1277,7 +2857,15 @@ where
 
     /// Draw a model wires (with texture if set)
     #[inline]
-    fn draw_model_wires(&mut self, model: &Model, position: Vector3, scale: f32, tint: Color) {
+    fn draw_model_wires(
+        &mut self,
+        model: &Model,
+        position: impl Into<Vector3>,
+        scale: f32,
+        tint: Color,
+    ) {
+        let position: Vector3 = position.into();
+
         unsafe { ffi::DrawModelWires(model.raw.clone(), position.into(), scale, tint.into()) }
     }
 
@@ -1286,12 +2874,16 @@ where
     fn draw_model_wires_ex(
         &mut self,
         model: &Model,
-        position: Vector3,
-        rotation_axis: Vector3,
+        position: impl Into<Vector3>,
+        rotation_axis: impl Into<Vector3>,
         rotation_angle: f32,
-        scale: Vector3,
+        scale: impl Into<Vector3>,
         tint: Color,
     ) {
+        let position: Vector3 = position.into();
+        let rotation_axis: Vector3 = rotation_axis.into();
+        let scale: Vector3 = scale.into();
+
         unsafe {
             ffi::DrawModelWiresEx(
                 model.raw.clone(),
@@ -1304,6 +2896,41 @@ where
         }
     }
 
+    /// Draw every mesh of a model with one material instead of the model's own, e.g. a
+    /// translucent preview
+    ///
+    /// `transform` is applied on top of the model's transform, the model isn't changed.
+    fn draw_model_with_material(&mut self, model: &Model, material: &Material, transform: Matrix) {
+        let transform = model_mesh_transform(model, transform);
+
+        for mesh in model.meshes() {
+            unsafe { ffi::DrawMesh(mesh.raw.clone(), material.raw.clone(), transform.clone()) }
+        }
+    }
+
+    /// Draw a single mesh of a model with the material it uses, nothing if there's no such mesh or it
+    /// refers to a material the model doesn't have
+    ///
+    /// `transform` is applied on top of the model's transform.
+    fn draw_mesh_of_model(&mut self, model: &Model, mesh_index: usize, transform: Matrix) {
+        let material = model
+            .mesh_material(mesh_index as u32)
+            .and_then(|material_id| model.materials().get(material_id as usize));
+
+        let (mesh, material) = match (model.meshes().get(mesh_index), material) {
+            (Some(mesh), Some(material)) => (mesh, material),
+            _ => return,
+        };
+
+        unsafe {
+            ffi::DrawMesh(
+                mesh.raw.clone(),
+                material.raw.clone(),
+                model_mesh_transform(model, transform),
+            )
+        }
+    }
+
     /// Draw bounding box (wires)
     #[inline]
     fn draw_bounding_box(&mut self, bbox: BoundingBox, color: Color) {
@@ -1316,10 +2943,13 @@ where
         &mut self,
         camera: Camera,
         texture: &Texture2D,
-        position: Vector3,
-        size: Vector2,
+        position: impl Into<Vector3>,
+        size: impl Into<Vector2>,
         params: DrawBillboardParams,
     ) {
+        let position: Vector3 = position.into();
+        let size: Vector2 = size.into();
+
         unsafe {
             ffi::DrawBillboardPro(
                 camera.into(),
@@ -1365,9 +2995,16 @@ where
 
 impl<'a> Draw for DrawHandle<'a> {}
 impl<'a, T> Draw for DrawBlendMode<'a, T> {}
+impl<'a, T> Draw for DrawColorWriteMode<'a, T> {}
+impl<'a, T> Draw for DrawCullMode<'a, T> {}
+impl<'a, T> Draw for DrawDepthTestMode<'a, T> {}
+impl<'a, T> Draw for DrawDepthWriteMode<'a, T> {}
 impl<'a, T> Draw for DrawMode2D<'a, T> {}
 impl<'a, T> Draw for DrawMode3D<'a, T> {}
+impl<'a, T> Draw for DrawPointsMode<'a, T> {}
 impl<'a, T> Draw for DrawScissorMode<'a, T> {}
 impl<'a, T> Draw for DrawShaderMode<'a, T> {}
+impl<'a, T> Draw for DrawStencilMode<'a, T> {}
 impl<'a, T> Draw for DrawTextureMode<'a, T> {}
+impl<'a, T> Draw for DrawWireframeMode<'a, T> {}
 impl<'a, T> Draw for DrawVrStereoMode<'a, T> {}
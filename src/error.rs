@@ -0,0 +1,144 @@
+use std::{
+    ffi::CString,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Why loading a resource failed
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RaylibError {
+    /// The file doesn't exist
+    FileNotFound(PathBuf),
+    /// The file exists but couldn't be read (e.g. no permission, or it's a directory)
+    ReadFailed {
+        /// Path of the file
+        path: PathBuf,
+        /// Kind of the I/O error
+        kind: io::ErrorKind,
+        /// Message of the I/O error
+        message: String,
+    },
+    /// The data couldn't be decoded (unsupported or corrupt format), with what was being loaded
+    DecodeFailed(String),
+    /// The resource couldn't be created on the GPU or audio device (e.g. a shader didn't compile), with its kind
+    NotReady(&'static str),
+    /// An argument can't be used, e.g. a file name with a nul byte
+    InvalidArgument(String),
+}
+
+impl RaylibError {
+    /// Convert a file name for raylib, checking that the file exists
+    pub(crate) fn file_name(file_name: &str) -> Result<CString, Self> {
+        let c_file_name = CString::new(file_name).map_err(|_| {
+            Self::InvalidArgument(format!("file name {file_name:?} contains a nul byte"))
+        })?;
+
+        if Path::new(file_name).is_file() {
+            Ok(c_file_name)
+        } else {
+            Err(Self::FileNotFound(file_name.into()))
+        }
+    }
+
+    /// Read a whole file, [`RaylibError::FileNotFound`] only if it doesn't exist
+    pub(crate) fn read_file(file_name: &str) -> Result<Vec<u8>, Self> {
+        fs::read(file_name).map_err(|error| match error.kind() {
+            io::ErrorKind::NotFound => Self::FileNotFound(file_name.into()),
+            kind => Self::ReadFailed {
+                path: file_name.into(),
+                kind,
+                message: error.to_string(),
+            },
+        })
+    }
+
+    /// Convert a path for the loaders taking `&str`
+    #[inline]
+    pub(crate) fn path_str(path: &Path) -> Result<&str, Self> {
+        path.to_str()
+            .ok_or_else(|| Self::InvalidArgument(format!("path {path:?} isn't valid UTF-8")))
+    }
+
+    /// Decoding a file failed
+    #[inline]
+    pub(crate) fn decode_file(file_name: &str) -> Self {
+        Self::DecodeFailed(format!("file {file_name:?}"))
+    }
+}
+
+impl fmt::Display for RaylibError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::FileNotFound(path) => write!(f, "file not found: {}", path.display()),
+            Self::ReadFailed { path, message, .. } => {
+                write!(f, "failed to read {}: {message}", path.display())
+            }
+            Self::DecodeFailed(what) => write!(f, "failed to decode {what}"),
+            Self::NotReady(kind) => write!(f, "{kind} isn't ready after loading"),
+            Self::InvalidArgument(message) => write!(f, "invalid argument: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for RaylibError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_name_with_nul_is_invalid() {
+        assert!(matches!(
+            RaylibError::file_name("image\0.png"),
+            Err(RaylibError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn missing_file_is_reported() {
+        assert_eq!(
+            RaylibError::file_name("does/not/exist.png"),
+            Err(RaylibError::FileNotFound("does/not/exist.png".into()))
+        );
+    }
+
+    #[test]
+    fn reading_a_missing_file_is_not_found() {
+        assert_eq!(
+            RaylibError::read_file("does/not/exist.ttf"),
+            Err(RaylibError::FileNotFound("does/not/exist.ttf".into()))
+        );
+    }
+
+    #[test]
+    fn reading_a_directory_fails_without_not_found() {
+        assert!(matches!(
+            RaylibError::read_file("src"),
+            Err(RaylibError::ReadFailed { .. })
+        ));
+    }
+
+    #[test]
+    fn existing_file_is_converted() {
+        let file_name = file!();
+
+        assert_eq!(
+            RaylibError::file_name(file_name).unwrap().to_str(),
+            Ok(file_name)
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn non_utf8_path_is_invalid() {
+        use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+        let path = Path::new(OsStr::from_bytes(b"caf\xE9.png"));
+
+        assert!(matches!(
+            RaylibError::path_str(path),
+            Err(RaylibError::InvalidArgument(_))
+        ));
+        assert_eq!(RaylibError::path_str(Path::new("café.png")), Ok("café.png"));
+    }
+}
@@ -0,0 +1,417 @@
+use crate::{
+    color::Color,
+    drawing::Draw,
+    error::RaylibError,
+    ffi,
+    math::{Camera3D, CameraProjection, Rectangle, Vector2, Vector3},
+    shader::{Shader, ShaderLoc},
+    texture::RenderTexture2D,
+};
+
+macro_rules! fog_glsl {
+    () => {
+        r#"
+uniform int fogMode;
+uniform vec3 fogColor;
+uniform float fogDensity;
+uniform float fogStart;
+uniform float fogEnd;
+
+// 1 when clear, 0 when fully fogged
+float fogVisibility(float distance)
+{
+    if (fogMode == 1) return clamp((fogEnd - distance)/max(fogEnd - fogStart, 0.0001), 0.0, 1.0);
+
+    if (fogMode == 2) return clamp(exp(-fogDensity*distance), 0.0, 1.0);
+
+    if (fogMode == 3)
+    {
+        float amount = fogDensity*distance;
+        return clamp(exp(-amount*amount), 0.0, 1.0);
+    }
+
+    return 1.0;
+}
+
+vec4 applyFog(vec4 color, float distance)
+{
+    return vec4(mix(fogColor, color.rgb, fogVisibility(distance)), color.a);
+}
+"#
+    };
+}
+
+/// GLSL uniforms and functions set by [`Fog::apply`], to paste into fragment shaders after the `#version` line
+///
+/// Provides `float fogVisibility(float distance)` and `vec4 applyFog(vec4 color, float distance)`,
+/// e.g. `finalColor = applyFog(finalColor, length(viewPos - fragPosition));` at the end of a lighting shader.
+pub const FOG_GLSL: &str = fog_glsl!();
+
+const FOG_FS: &str = concat!(
+    r#"#version 330
+in vec2 fragTexCoord;
+in vec4 fragColor;
+
+uniform sampler2D texture0;
+uniform sampler2D depthTexture;
+uniform float nearPlane;
+uniform float farPlane;
+uniform int orthographic;
+uniform vec2 viewScale;
+
+out vec4 finalColor;
+"#,
+    fog_glsl!(),
+    r#"
+void main()
+{
+    vec4 color = texture(texture0, fragTexCoord);
+    float depth = texture(depthTexture, fragTexCoord).r;
+
+    float viewDepth;
+    if (orthographic == 1) viewDepth = nearPlane + depth*(farPlane - nearPlane);
+    else viewDepth = 2.0*nearPlane*farPlane/(farPlane + nearPlane - (depth*2.0 - 1.0)*(farPlane - nearPlane));
+
+    // distance along the view ray, so it matches fog computed from positions
+    vec2 ray = (fragTexCoord*2.0 - 1.0)*viewScale;
+    float distance = viewDepth*length(vec3(ray, 1.0));
+
+    finalColor = applyFog(color, distance)*fragColor;
+}
+"#
+);
+
+/// Insert [`FOG_GLSL`] into fragment shader source, after its `#version` line if it has one
+pub fn with_fog_include(fragment_source: &str) -> String {
+    let split = if fragment_source.trim_start().starts_with("#version") {
+        fragment_source
+            .find('\n')
+            .map_or(fragment_source.len(), |i| i + 1)
+    } else {
+        0
+    };
+
+    let (version, rest) = fragment_source.split_at(split);
+    let mut source = String::with_capacity(fragment_source.len() + FOG_GLSL.len() + 1);
+
+    source.push_str(version);
+
+    if !version.is_empty() && !version.ends_with('\n') {
+        source.push('\n');
+    }
+
+    source.push_str(FOG_GLSL);
+    source.push_str(rest);
+    source
+}
+
+/// How fog thickens with distance
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FogMode {
+    /// Clear until [`Fog::start`], fully fogged at [`Fog::end`]
+    Linear,
+    /// `exp(-density * distance)`
+    Exponential,
+    /// `exp(-(density * distance)^2)`, stays clear longer and thickens faster
+    ExponentialSquared,
+}
+
+impl FogMode {
+    #[inline]
+    fn id(self) -> i32 {
+        match self {
+            Self::Linear => 1,
+            Self::Exponential => 2,
+            Self::ExponentialSquared => 3,
+        }
+    }
+}
+
+/// Distance fog settings
+///
+/// Set them on shaders including [`FOG_GLSL`] with [`Fog::apply`], or apply the fog to a whole frame
+/// with [`FogPostFx`]. Keep the far plane beyond [`Fog::opaque_distance`] so geometry fades out
+/// before it is clipped.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Fog {
+    /// Falloff curve
+    pub mode: FogMode,
+    /// Fog color, alpha is ignored
+    pub color: Color,
+    /// Thickness of exponential fog
+    pub density: f32,
+    /// Distance linear fog starts at
+    pub start: f32,
+    /// Distance linear fog becomes opaque at
+    pub end: f32,
+}
+
+impl Fog {
+    /// Linear fog between two distances
+    #[inline]
+    pub fn linear(color: Color, start: f32, end: f32) -> Self {
+        Self {
+            mode: FogMode::Linear,
+            color,
+            density: 0.,
+            start,
+            end,
+        }
+    }
+
+    /// Exponential fog
+    #[inline]
+    pub fn exponential(color: Color, density: f32) -> Self {
+        Self {
+            mode: FogMode::Exponential,
+            color,
+            density,
+            start: 0.,
+            end: 0.,
+        }
+    }
+
+    /// Squared exponential fog
+    #[inline]
+    pub fn exponential_squared(color: Color, density: f32) -> Self {
+        Self {
+            mode: FogMode::ExponentialSquared,
+            color,
+            density,
+            start: 0.,
+            end: 0.,
+        }
+    }
+
+    /// How much of an object's color is left at a distance, 1 when clear and 0 when fully fogged
+    ///
+    /// Same as `fogVisibility` in [`FOG_GLSL`].
+    #[inline]
+    pub fn visibility(&self, distance: f32) -> f32 {
+        let visibility = match self.mode {
+            FogMode::Linear => (self.end - distance) / (self.end - self.start).max(0.0001),
+            FogMode::Exponential => (-self.density * distance).exp(),
+            FogMode::ExponentialSquared => (-(self.density * distance).powi(2)).exp(),
+        };
+
+        visibility.clamp(0., 1.)
+    }
+
+    /// Distance past which objects are hidden (less than 1/255 of their color is left)
+    ///
+    /// Infinite for exponential fog without density. Objects further away can be culled.
+    #[inline]
+    pub fn opaque_distance(&self) -> f32 {
+        let ln_255 = 255f32.ln();
+
+        match self.mode {
+            FogMode::Linear => self.end,
+            FogMode::Exponential => ln_255 / self.density.max(0.),
+            FogMode::ExponentialSquared => ln_255.sqrt() / self.density.max(0.),
+        }
+    }
+
+    /// Set the fog uniforms of a shader including [`FOG_GLSL`]
+    ///
+    /// Uniforms the shader doesn't use are skipped. The shader still needs the camera position
+    /// (e.g. `viewPos`) to compute distances.
+    pub fn apply(&self, shader: &mut Shader) {
+        FogLocations::new(shader).set(shader, self);
+    }
+
+    /// Turn fog off in a shader including [`FOG_GLSL`]
+    #[inline]
+    pub fn disable(shader: &mut Shader) {
+        if let Some(loc) = shader.get_location("fogMode") {
+            shader.set_value(loc, 0i32);
+        }
+    }
+}
+
+#[derive(Debug)]
+struct FogLocations {
+    mode: Option<ShaderLoc>,
+    color: Option<ShaderLoc>,
+    density: Option<ShaderLoc>,
+    start: Option<ShaderLoc>,
+    end: Option<ShaderLoc>,
+}
+
+impl FogLocations {
+    fn new(shader: &Shader) -> Self {
+        Self {
+            mode: shader.get_location("fogMode"),
+            color: shader.get_location("fogColor"),
+            density: shader.get_location("fogDensity"),
+            start: shader.get_location("fogStart"),
+            end: shader.get_location("fogEnd"),
+        }
+    }
+
+    fn set(&self, shader: &mut Shader, fog: &Fog) {
+        if let Some(loc) = self.mode {
+            shader.set_value(loc, fog.mode.id());
+        }
+
+        if let Some(loc) = self.color {
+            let color = fog.color.normalize();
+
+            shader.set_value(
+                loc,
+                Vector3 {
+                    x: color.x,
+                    y: color.y,
+                    z: color.z,
+                },
+            );
+        }
+
+        for (loc, value) in [
+            (self.density, fog.density),
+            (self.start, fog.start),
+            (self.end, fog.end),
+        ] {
+            if let Some(loc) = loc {
+                shader.set_value(loc, value);
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct FogPostLocations {
+    fog: FogLocations,
+    depth_texture: Option<ShaderLoc>,
+    near_plane: Option<ShaderLoc>,
+    far_plane: Option<ShaderLoc>,
+    orthographic: Option<ShaderLoc>,
+    view_scale: Option<ShaderLoc>,
+}
+
+/// Post effect that fogs a frame using its depth, for scenes drawn with shaders without fog
+///
+/// Draw the scene to a render texture loaded with [`RenderTexture2D::with_depth_texture`], then draw
+/// it with [`FogPostFx::draw`]. Empty pixels count as being at the far plane, so the background gets
+/// the fog color too. The shader targets desktop OpenGL 3.3.
+#[derive(Debug)]
+pub struct FogPostFx {
+    shader: Shader,
+    locs: FogPostLocations,
+    near: f32,
+    far: f32,
+}
+
+impl FogPostFx {
+    /// Load the effect, with the clip planes raylib uses by default
+    pub fn new() -> Result<Self, RaylibError> {
+        let shader = Shader::from_memory(None, Some(FOG_FS))?;
+
+        let locs = FogPostLocations {
+            fog: FogLocations::new(&shader),
+            depth_texture: shader.get_location("depthTexture"),
+            near_plane: shader.get_location("nearPlane"),
+            far_plane: shader.get_location("farPlane"),
+            orthographic: shader.get_location("orthographic"),
+            view_scale: shader.get_location("viewScale"),
+        };
+
+        Ok(Self {
+            shader,
+            locs,
+            // RL_CULL_DISTANCE_NEAR and RL_CULL_DISTANCE_FAR
+            near: 0.01,
+            far: 1000.,
+        })
+    }
+
+    /// Set the clip planes the scene was drawn with, needed to convert depth back to distance
+    #[inline]
+    pub fn set_clip_planes(&mut self, near: f32, far: f32) {
+        self.near = near;
+        self.far = far;
+    }
+
+    /// Set the clip planes, see [`FogPostFx::set_clip_planes`]
+    #[inline]
+    pub fn with_clip_planes(mut self, near: f32, far: f32) -> Self {
+        self.set_clip_planes(near, far);
+        self
+    }
+
+    /// Draw a render texture fogged into `dest`, `camera` is the one the scene was drawn with
+    ///
+    /// Draws the render texture unchanged if it has no depth texture.
+    pub fn draw<D: Draw>(
+        &mut self,
+        d: &mut D,
+        source: &RenderTexture2D,
+        fog: &Fog,
+        camera: &Camera3D,
+        dest: Rectangle,
+    ) {
+        let (width, height) = (source.width() as f32, source.height() as f32);
+        let depth = source.depth_texture();
+
+        let shader_mode = depth.map(|_| d.begin_shader_mode(&self.shader));
+
+        if let Some(depth) = depth {
+            // samplers have to be set while the shader is active, they are reset after every batch
+            if let Some(loc) = self.locs.depth_texture {
+                self.shader.set_value_texture(loc, depth);
+            }
+
+            self.locs.fog.set(&mut self.shader, fog);
+
+            let orthographic = camera.projection == CameraProjection::Orthographic;
+
+            // half size of the near plane at distance 1, orthographic rays are parallel
+            let view_scale = if orthographic {
+                Vector2 { x: 0., y: 0. }
+            } else {
+                let tan = (camera.fovy.to_radians() / 2.).tan();
+
+                Vector2 {
+                    x: tan * width / height,
+                    y: tan,
+                }
+            };
+
+            let values = [
+                (self.locs.near_plane, self.near),
+                (self.locs.far_plane, self.far),
+            ];
+
+            for (loc, value) in values {
+                if let Some(loc) = loc {
+                    self.shader.set_value(loc, value);
+                }
+            }
+
+            if let Some(loc) = self.locs.orthographic {
+                self.shader.set_value(loc, orthographic as i32);
+            }
+
+            if let Some(loc) = self.locs.view_scale {
+                self.shader.set_value(loc, view_scale);
+            }
+        }
+
+        unsafe {
+            ffi::DrawTexturePro(
+                source.raw.texture.clone(),
+                // render textures are upside down
+                Rectangle::new(0., 0., width, -height).into(),
+                dest.into(),
+                Vector2 { x: 0., y: 0. }.into(),
+                0.,
+                Color::WHITE.into(),
+            )
+        }
+
+        if let Some(shader_mode) = shader_mode {
+            shader_mode.end_shader_mode();
+        }
+    }
+}
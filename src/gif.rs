@@ -0,0 +1,543 @@
+use crate::{
+    color::Color,
+    ffi,
+    texture::{Image, RenderTexture2D},
+};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    time::Duration,
+};
+
+/// Palette index of pixels that didn't change since the previous frame
+const TRANSPARENT: u8 = 255;
+/// Colors in the palette, one index is kept for transparency
+const MAX_COLORS: usize = 255;
+/// Pixels looked at when building the palette
+const PALETTE_SAMPLES: usize = 1 << 18;
+/// Minimum LZW code size for 8-bit indices
+const MIN_CODE_SIZE: u8 = 8;
+/// Largest LZW code
+const MAX_CODE: u16 = 4095;
+
+/// Animated GIF writer
+///
+/// Frames are kept in memory until [`GifEncoder::encode`] or [`GifEncoder::save`].
+/// One palette is built for the whole animation (median cut, exact when there are 255 colors
+/// or less), later frames only store the rectangle that changed and repeated frames are merged.
+/// Alpha is ignored.
+#[derive(Clone)]
+pub struct GifEncoder {
+    width: u32,
+    height: u32,
+    delay: u16,
+    looping: bool,
+    frames: Vec<Vec<Color>>,
+}
+
+impl fmt::Debug for GifEncoder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GifEncoder")
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .field("delay", &self.delay)
+            .field("looping", &self.looping)
+            .field("frame_count", &self.frames.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl GifEncoder {
+    /// Create new encoder for frames of the given size, at 10 frames per second and looping
+    #[inline]
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width: width.min(u16::MAX as u32),
+            height: height.min(u16::MAX as u32),
+            delay: 10,
+            looping: true,
+            frames: Vec::new(),
+        }
+    }
+
+    /// Set the time each frame is shown (GIF stores it in hundredths of a second)
+    #[inline]
+    pub fn with_frame_delay(mut self, delay: Duration) -> Self {
+        self.delay = (delay.as_millis() / 10).clamp(1, u16::MAX as u128) as u16;
+        self
+    }
+
+    /// Set whether the animation loops forever or plays once
+    #[inline]
+    pub fn with_looping(mut self, looping: bool) -> Self {
+        self.looping = looping;
+        self
+    }
+
+    /// Frame width
+    #[inline]
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Frame height
+    #[inline]
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Number of frames added so far
+    #[inline]
+    pub fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Add a frame, `false` if the image size doesn't match
+    pub fn add_frame(&mut self, image: &Image) -> bool {
+        if image.width() != self.width || image.height() != self.height {
+            #[cfg(debug_assertions)]
+            crate::core::warn(&format!(
+                "GifEncoder: frame is {}x{}, expected {}x{}",
+                image.width(),
+                image.height(),
+                self.width,
+                self.height
+            ));
+
+            return false;
+        }
+
+        self.frames.push(image.load_colors());
+
+        true
+    }
+
+    /// Add the current contents of a render texture as a frame (flipped upright)
+    pub fn add_render_texture(&mut self, target: &RenderTexture2D) -> bool {
+        let raw = unsafe { ffi::LoadImageFromTexture(target.raw.texture.clone()) };
+
+        if !unsafe { ffi::IsImageReady(raw.clone()) } {
+            return false;
+        }
+
+        let mut image = Image { raw };
+        image.flip_vertical();
+
+        self.add_frame(&image)
+    }
+
+    /// Add `count` frames produced by a closure from the frame number
+    ///
+    /// Stops at the first frame that doesn't match the size and returns `false`.
+    pub fn add_frames(&mut self, count: usize, mut frame: impl FnMut(usize) -> Image) -> bool {
+        (0..count).all(|index| self.add_frame(&frame(index)))
+    }
+
+    /// Remove all frames
+    #[inline]
+    pub fn clear(&mut self) {
+        self.frames.clear();
+    }
+
+    /// Encode the frames to GIF file data
+    pub fn encode(&self) -> Vec<u8> {
+        let palette = build_palette(&self.frames);
+        let mut lookup = PaletteLookup::new(&palette);
+
+        let mut out = Vec::new();
+
+        out.extend_from_slice(b"GIF89a");
+        out.extend_from_slice(&(self.width as u16).to_le_bytes());
+        out.extend_from_slice(&(self.height as u16).to_le_bytes());
+        // global color table of 256 entries, 8 bits per channel
+        out.extend_from_slice(&[0xF7, 0, 0]);
+
+        for index in 0..256 {
+            let color = palette.get(index).copied().unwrap_or([0; 3]);
+            out.extend_from_slice(&color);
+        }
+
+        if self.looping {
+            out.extend_from_slice(&[0x21, 0xFF, 0x0B]);
+            out.extend_from_slice(b"NETSCAPE2.0");
+            out.extend_from_slice(&[0x03, 0x01, 0x00, 0x00, 0x00]);
+        }
+
+        let (width, height) = (self.width as usize, self.height as usize);
+        let mut previous: Option<Vec<u8>> = None;
+        // frames waiting for their delay to be known, since repeated frames extend it
+        let mut pending: Option<(u16, bool, Vec<u8>)> = None;
+
+        for colors in &self.frames {
+            let indices: Vec<u8> = colors.iter().map(|c| lookup.index(*c)).collect();
+
+            let block = match &previous {
+                None => Some(image_block(&indices, width, 0, 0, width, height, None)),
+                Some(previous) => changed_rect(previous, &indices, width)
+                    .map(|(x, y, w, h)| image_block(&indices, width, x, y, w, h, Some(previous))),
+            };
+
+            match block {
+                Some(block) => {
+                    if let Some((delay, transparent, block)) = pending.take() {
+                        write_frame(&mut out, delay, transparent, &block);
+                    }
+
+                    pending = Some((self.delay, previous.is_some(), block));
+                }
+                None => {
+                    if let Some((delay, _, _)) = &mut pending {
+                        *delay = delay.saturating_add(self.delay);
+                    }
+                }
+            }
+
+            previous = Some(indices);
+        }
+
+        if let Some((delay, transparent, block)) = pending {
+            write_frame(&mut out, delay, transparent, &block);
+        }
+
+        out.push(0x3B);
+
+        out
+    }
+
+    /// Encode the frames and write them to a file, `false` on failure
+    pub fn save(&self, file_name: &str) -> bool {
+        match std::fs::write(file_name, self.encode()) {
+            Ok(()) => true,
+            Err(_err) => {
+                #[cfg(debug_assertions)]
+                crate::core::warn(&format!("GifEncoder: can't write {}: {}", file_name, _err));
+
+                false
+            }
+        }
+    }
+}
+
+/// Write a graphic control extension followed by an image block
+fn write_frame(out: &mut Vec<u8>, delay: u16, transparent: bool, block: &[u8]) {
+    // disposal method 1 keeps the frame, so later ones can be drawn over it
+    let packed = (1 << 2) | transparent as u8;
+
+    out.extend_from_slice(&[0x21, 0xF9, 0x04, packed]);
+    out.extend_from_slice(&delay.to_le_bytes());
+    out.extend_from_slice(&[TRANSPARENT, 0x00]);
+    out.extend_from_slice(block);
+}
+
+/// Bounding rectangle of the pixels that differ between two frames
+fn changed_rect(
+    previous: &[u8],
+    current: &[u8],
+    width: usize,
+) -> Option<(usize, usize, usize, usize)> {
+    let mut min = (usize::MAX, usize::MAX);
+    let mut max = (0, 0);
+
+    for (index, (a, b)) in previous.iter().zip(current).enumerate() {
+        if a != b {
+            let (x, y) = (index % width, index / width);
+
+            min = (min.0.min(x), min.1.min(y));
+            max = (max.0.max(x), max.1.max(y));
+        }
+    }
+
+    if min.0 == usize::MAX {
+        None
+    } else {
+        Some((min.0, min.1, max.0 - min.0 + 1, max.1 - min.1 + 1))
+    }
+}
+
+/// Image descriptor and LZW data of a rectangle of a frame
+///
+/// With a previous frame, pixels that didn't change become transparent so they compress better.
+fn image_block(
+    indices: &[u8],
+    stride: usize,
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+    previous: Option<&[u8]>,
+) -> Vec<u8> {
+    let mut pixels = Vec::with_capacity(width * height);
+
+    for row in y..y + height {
+        let start = row * stride + x;
+        let current = &indices[start..start + width];
+
+        match previous {
+            Some(previous) => {
+                let previous = &previous[start..start + width];
+
+                pixels.extend(current.iter().zip(previous).map(|(&c, &p)| {
+                    if c == p {
+                        TRANSPARENT
+                    } else {
+                        c
+                    }
+                }));
+            }
+            None => pixels.extend_from_slice(current),
+        }
+    }
+
+    let mut block = vec![0x2C];
+
+    for value in [x, y, width, height] {
+        block.extend_from_slice(&(value as u16).to_le_bytes());
+    }
+
+    // no local color table, not interlaced
+    block.push(0);
+    block.push(MIN_CODE_SIZE);
+
+    let data = lzw_encode(&pixels);
+
+    for chunk in data.chunks(255) {
+        block.push(chunk.len() as u8);
+        block.extend_from_slice(chunk);
+    }
+
+    block.push(0);
+
+    block
+}
+
+/// LSB first bit packing of variable width codes
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    bits: u8,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, size: u8) {
+        self.buffer |= (code as u32) << self.bits;
+        self.bits += size;
+
+        while self.bits >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.bits -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.bits > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+
+        self.bytes
+    }
+}
+
+/// GIF flavoured LZW compression of 8-bit indices
+fn lzw_encode(pixels: &[u8]) -> Vec<u8> {
+    let clear = 1u16 << MIN_CODE_SIZE;
+    let end = clear + 1;
+
+    let mut writer = BitWriter {
+        bytes: Vec::new(),
+        buffer: 0,
+        bits: 0,
+    };
+    let mut table: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut code_size = MIN_CODE_SIZE + 1;
+    let mut next_code = end + 1;
+
+    writer.write(clear, code_size);
+
+    let mut pixels = pixels.iter();
+    let mut prefix = match pixels.next() {
+        Some(&first) => first as u16,
+        None => {
+            writer.write(end, code_size);
+            return writer.finish();
+        }
+    };
+
+    for &pixel in pixels {
+        if let Some(&code) = table.get(&(prefix, pixel)) {
+            prefix = code;
+            continue;
+        }
+
+        writer.write(prefix, code_size);
+
+        if next_code <= MAX_CODE {
+            table.insert((prefix, pixel), next_code);
+            next_code += 1;
+
+            if next_code > 1 << code_size && code_size < 12 {
+                code_size += 1;
+            }
+        } else {
+            writer.write(clear, code_size);
+            table.clear();
+            code_size = MIN_CODE_SIZE + 1;
+            next_code = end + 1;
+        }
+
+        prefix = pixel as u16;
+    }
+
+    writer.write(prefix, code_size);
+
+    // the decoder adds an entry for the last code too, which can widen the end code
+    if next_code == 1 << code_size && code_size < 12 {
+        code_size += 1;
+    }
+
+    writer.write(end, code_size);
+
+    writer.finish()
+}
+
+/// Palette for all frames, exact if they have few enough colors, median cut otherwise
+fn build_palette(frames: &[Vec<Color>]) -> Vec<[u8; 3]> {
+    let total: usize = frames.iter().map(Vec::len).sum();
+    let step = (total / PALETTE_SAMPLES).max(1);
+
+    let mut unique = HashSet::new();
+    let mut samples = Vec::with_capacity(total.min(PALETTE_SAMPLES + frames.len()));
+
+    for frame in frames {
+        for color in frame.iter().step_by(step) {
+            let rgb = [color.r, color.g, color.b];
+
+            if unique.len() <= MAX_COLORS {
+                unique.insert(rgb);
+            }
+
+            samples.push(rgb);
+        }
+    }
+
+    if unique.len() <= MAX_COLORS {
+        // subsampling may have missed colors, check every pixel before using the exact palette
+        let exact = frames
+            .iter()
+            .flatten()
+            .try_fold(HashSet::new(), |mut colors, color| {
+                colors.insert([color.r, color.g, color.b]);
+                (colors.len() <= MAX_COLORS).then_some(colors)
+            });
+
+        if let Some(colors) = exact {
+            let mut palette: Vec<_> = colors.into_iter().collect();
+            palette.sort_unstable();
+            return palette;
+        }
+    }
+
+    median_cut(samples, MAX_COLORS)
+}
+
+/// Split the colors into boxes along their widest channel, the palette is the box averages
+fn median_cut(colors: Vec<[u8; 3]>, count: usize) -> Vec<[u8; 3]> {
+    if colors.is_empty() {
+        return vec![[0; 3]];
+    }
+
+    let mut boxes = vec![colors];
+
+    while boxes.len() < count {
+        let widest = boxes
+            .iter()
+            .enumerate()
+            .filter(|(_, colors)| colors.len() > 1)
+            .map(|(index, colors)| {
+                let (channel, range) = widest_channel(colors);
+                (index, channel, range as usize * colors.len())
+            })
+            .max_by_key(|&(_, _, score)| score);
+
+        let (index, channel) = match widest {
+            Some((index, channel, score)) if score > 0 => (index, channel),
+            _ => break,
+        };
+
+        let mut colors = boxes.swap_remove(index);
+        colors.sort_unstable_by_key(|color| color[channel]);
+
+        let upper = colors.split_off(colors.len() / 2);
+        boxes.push(colors);
+        boxes.push(upper);
+    }
+
+    boxes
+        .iter()
+        .map(|colors| {
+            let mut sum = [0usize; 3];
+
+            for color in colors {
+                for channel in 0..3 {
+                    sum[channel] += color[channel] as usize;
+                }
+            }
+
+            sum.map(|value| ((value + colors.len() / 2) / colors.len()) as u8)
+        })
+        .collect()
+}
+
+/// Channel with the largest spread and the spread
+fn widest_channel(colors: &[[u8; 3]]) -> (usize, u8) {
+    let mut min = [u8::MAX; 3];
+    let mut max = [0; 3];
+
+    for color in colors {
+        for channel in 0..3 {
+            min[channel] = min[channel].min(color[channel]);
+            max[channel] = max[channel].max(color[channel]);
+        }
+    }
+
+    (0..3)
+        .map(|channel| (channel, max[channel] - min[channel]))
+        .max_by_key(|&(_, range)| range)
+        .unwrap_or((0, 0))
+}
+
+/// Nearest palette color, cached per color
+struct PaletteLookup<'a> {
+    palette: &'a [[u8; 3]],
+    cache: HashMap<[u8; 3], u8>,
+}
+
+impl<'a> PaletteLookup<'a> {
+    fn new(palette: &'a [[u8; 3]]) -> Self {
+        Self {
+            palette,
+            cache: HashMap::new(),
+        }
+    }
+
+    fn index(&mut self, color: Color) -> u8 {
+        let rgb = [color.r, color.g, color.b];
+        let palette = self.palette;
+
+        *self.cache.entry(rgb).or_insert_with(|| {
+            palette
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, entry)| {
+                    (0..3)
+                        .map(|channel| {
+                            let d = entry[channel] as i32 - rgb[channel] as i32;
+                            (d * d) as u32
+                        })
+                        .sum::<u32>()
+                })
+                .map(|(index, _)| index as u8)
+                .unwrap_or(0)
+        })
+    }
+}
@@ -0,0 +1,103 @@
+use crate::{
+    ffi,
+    math::{columns, from_columns, Transform},
+};
+
+use ::glam::{Mat4, Quat, Vec2, Vec3, Vec4};
+
+pub use ::glam;
+
+impl From<Vec2> for ffi::Vector2 {
+    #[inline]
+    fn from(v: Vec2) -> Self {
+        Self { x: v.x, y: v.y }
+    }
+}
+
+impl From<ffi::Vector2> for Vec2 {
+    #[inline]
+    fn from(v: ffi::Vector2) -> Self {
+        Vec2::new(v.x, v.y)
+    }
+}
+
+impl From<Vec3> for ffi::Vector3 {
+    #[inline]
+    fn from(v: Vec3) -> Self {
+        Self {
+            x: v.x,
+            y: v.y,
+            z: v.z,
+        }
+    }
+}
+
+impl From<ffi::Vector3> for Vec3 {
+    #[inline]
+    fn from(v: ffi::Vector3) -> Self {
+        Vec3::new(v.x, v.y, v.z)
+    }
+}
+
+impl From<Vec4> for ffi::Vector4 {
+    #[inline]
+    fn from(v: Vec4) -> Self {
+        Self {
+            x: v.x,
+            y: v.y,
+            z: v.z,
+            w: v.w,
+        }
+    }
+}
+
+impl From<ffi::Vector4> for Vec4 {
+    #[inline]
+    fn from(v: ffi::Vector4) -> Self {
+        Vec4::new(v.x, v.y, v.z, v.w)
+    }
+}
+
+impl From<Quat> for ffi::Quaternion {
+    #[inline]
+    fn from(q: Quat) -> Self {
+        Self {
+            x: q.x,
+            y: q.y,
+            z: q.z,
+            w: q.w,
+        }
+    }
+}
+
+impl From<ffi::Quaternion> for Quat {
+    #[inline]
+    fn from(q: ffi::Quaternion) -> Self {
+        Quat::from_xyzw(q.x, q.y, q.z, q.w)
+    }
+}
+
+impl From<Mat4> for ffi::Matrix {
+    #[inline]
+    fn from(m: Mat4) -> Self {
+        from_columns(&m.to_cols_array_2d()).into()
+    }
+}
+
+impl From<ffi::Matrix> for Mat4 {
+    #[inline]
+    fn from(m: ffi::Matrix) -> Self {
+        Mat4::from_cols_array_2d(&columns(&m.into()))
+    }
+}
+
+impl From<Transform> for Mat4 {
+    #[inline]
+    fn from(transform: Transform) -> Self {
+        Mat4::from_scale_rotation_translation(
+            transform.scale.into(),
+            transform.rotation.into(),
+            transform.translation.into(),
+        )
+    }
+}
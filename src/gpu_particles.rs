@@ -0,0 +1,675 @@
+use crate::{
+    color::Color,
+    core::{release_context_object, track_context_object, ContextObject},
+    drawing::{gl, Draw, DrawMode3D},
+    error::RaylibError,
+    ffi,
+    math::{vec3, Rectangle, Vector2, Vector3},
+    shader::{Shader, ShaderLoc},
+    texture::{PixelFormat, RenderTexture2D, Texture2D, TextureArray},
+};
+
+const UPDATE_FS: &str = r#"#version 330
+uniform sampler2D positions;
+uniform sampler2D velocities;
+uniform int stage;
+uniform int stateSize;
+uniform int capacity;
+uniform int spawnStart;
+uniform int spawnCount;
+uniform int seed;
+uniform vec3 emitterPosition;
+uniform float spawnRadius;
+uniform vec3 direction;
+uniform float spreadCos;
+uniform vec2 speed;
+uniform vec2 lifetime;
+uniform vec3 gravity;
+uniform float drag;
+uniform float delta;
+
+out vec4 finalColor;
+
+uint hash(uint x)
+{
+    x ^= x >> 16;
+    x *= 0x7feb352dU;
+    x ^= x >> 15;
+    x *= 0x846ca68bU;
+    x ^= x >> 16;
+    return x;
+}
+
+// uniform in 0..1, different for every particle, update and salt
+float random(int index, int salt)
+{
+    return float(hash(uint(index) ^ hash(uint(seed)*16u + uint(salt))))/4294967295.0;
+}
+
+vec3 randomInCone(int index)
+{
+    float cosAngle = mix(1.0, spreadCos, random(index, 0));
+    float sinAngle = sqrt(max(1.0 - cosAngle*cosAngle, 0.0));
+    float phi = 6.2831853*random(index, 1);
+
+    vec3 helper = abs(direction.y) < 0.99 ? vec3(0.0, 1.0, 0.0) : vec3(1.0, 0.0, 0.0);
+    vec3 tangent = normalize(cross(helper, direction));
+    vec3 bitangent = cross(direction, tangent);
+
+    return (tangent*cos(phi) + bitangent*sin(phi))*sinAngle + direction*cosAngle;
+}
+
+vec3 randomInSphere(int index)
+{
+    float z = random(index, 4)*2.0 - 1.0;
+    float phi = 6.2831853*random(index, 5);
+    float r = sqrt(1.0 - z*z);
+
+    return vec3(r*cos(phi), z, r*sin(phi))*pow(random(index, 6), 1.0/3.0);
+}
+
+void main()
+{
+    ivec2 texel = ivec2(gl_FragCoord.xy);
+    int index = texel.y*stateSize + texel.x;
+
+    // position.w is the age, velocity.w the lifetime
+    vec4 position = texelFetch(positions, texel, 0);
+    vec4 velocity = texelFetch(velocities, texel, 0);
+
+    // spawns reuse the slots after the last spawned particle, wrapping around
+    bool spawn = index < capacity && (index - spawnStart + capacity)%capacity < spawnCount;
+    bool dead = position.w >= velocity.w;
+
+    if (stage == 0)
+    {
+        if (spawn)
+        {
+            vec3 value = randomInCone(index)*mix(speed.x, speed.y, random(index, 2));
+            finalColor = vec4(value, mix(lifetime.x, lifetime.y, random(index, 3)));
+        }
+        else if (dead) finalColor = velocity;
+        else finalColor = vec4((velocity.xyz + gravity*delta)*max(1.0 - drag*delta, 0.0), velocity.w);
+    }
+    else
+    {
+        if (spawn) finalColor = vec4(emitterPosition + randomInSphere(index)*spawnRadius, 0.0);
+        else if (dead) finalColor = position;
+        else finalColor = vec4(position.xyz + velocity.xyz*delta, position.w + delta);
+    }
+}
+"#;
+
+const RENDER_VS: &str = r#"#version 330
+layout(location = 0) in vec2 corner;
+
+uniform sampler2D positions;
+uniform sampler2D velocities;
+uniform int stateSize;
+uniform mat4 matView;
+uniform mat4 matProjection;
+uniform vec2 size;
+uniform vec4 colorStart;
+uniform vec4 colorEnd;
+
+out vec2 fragTexCoord;
+out vec4 fragColor;
+
+void main()
+{
+    ivec2 texel = ivec2(gl_InstanceID%stateSize, gl_InstanceID/stateSize);
+    vec4 position = texelFetch(positions, texel, 0);
+    float lifetime = texelFetch(velocities, texel, 0).w;
+
+    // dead particles collapse to a point and produce no fragments
+    float t = lifetime > 0.0 ? position.w/lifetime : 1.0;
+    float scale = t < 1.0 ? mix(size.x, size.y, t) : 0.0;
+
+    vec4 viewPosition = matView*vec4(position.xyz, 1.0);
+    viewPosition.xy += corner*scale;
+
+    fragTexCoord = vec2(corner.x + 0.5, 0.5 - corner.y);
+    fragColor = mix(colorStart, colorEnd, t);
+    gl_Position = matProjection*viewPosition;
+}
+"#;
+
+const RENDER_FS: &str = r#"#version 330
+in vec2 fragTexCoord;
+in vec4 fragColor;
+
+uniform sampler2D sprite;
+uniform int useSprite;
+
+out vec4 finalColor;
+
+void main()
+{
+    vec4 texel;
+
+    if (useSprite == 1) texel = texture(sprite, fragTexCoord);
+    else texel = vec4(1.0, 1.0, 1.0, 1.0 - smoothstep(0.25, 0.5, length(fragTexCoord - 0.5)));
+
+    finalColor = texel*fragColor;
+}
+"#;
+
+/// Two triangles of a particle quad, counter-clockwise facing the camera
+const QUAD_CORNERS: [[f32; 2]; 6] = [
+    [-0.5, -0.5],
+    [0.5, -0.5],
+    [0.5, 0.5],
+    [-0.5, -0.5],
+    [0.5, 0.5],
+    [-0.5, 0.5],
+];
+
+/// Spawn parameters of GPU particles
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParticleEmitter {
+    /// Center of the spawn area
+    pub position: Vector3,
+    /// Radius of the sphere particles spawn in, 0 spawns them all at [`ParticleEmitter::position`]
+    pub radius: f32,
+    /// Main direction particles are emitted in
+    pub direction: Vector3,
+    /// Angle between the emitted directions and [`ParticleEmitter::direction`], in degrees (180 for all directions)
+    pub spread: f32,
+    /// Slowest initial speed
+    pub speed_min: f32,
+    /// Fastest initial speed
+    pub speed_max: f32,
+    /// Shortest lifetime, in seconds
+    pub lifetime_min: f32,
+    /// Longest lifetime, in seconds
+    pub lifetime_max: f32,
+    /// Particles spawned per second
+    pub rate: f32,
+    /// Acceleration applied to every particle
+    pub gravity: Vector3,
+    /// Fraction of the velocity lost per second
+    pub drag: f32,
+    /// Size of new particles, in world units
+    pub size_start: f32,
+    /// Size of particles at the end of their lifetime
+    pub size_end: f32,
+    /// Color of new particles
+    pub color_start: Color,
+    /// Color of particles at the end of their lifetime
+    pub color_end: Color,
+}
+
+impl Default for ParticleEmitter {
+    /// Upward fountain of white particles fading out
+    #[inline]
+    fn default() -> Self {
+        Self {
+            position: Vector3 {
+                x: 0.,
+                y: 0.,
+                z: 0.,
+            },
+            radius: 0.,
+            direction: Vector3 {
+                x: 0.,
+                y: 1.,
+                z: 0.,
+            },
+            spread: 20.,
+            speed_min: 4.,
+            speed_max: 6.,
+            lifetime_min: 1.,
+            lifetime_max: 2.,
+            rate: 1000.,
+            gravity: Vector3 {
+                x: 0.,
+                y: -9.8,
+                z: 0.,
+            },
+            drag: 0.,
+            size_start: 0.1,
+            size_end: 0.05,
+            color_start: Color::WHITE,
+            color_end: Color {
+                a: 0,
+                ..Color::WHITE
+            },
+        }
+    }
+}
+
+#[derive(Debug)]
+struct UpdateLocations {
+    stage: Option<ShaderLoc>,
+    spawn_start: Option<ShaderLoc>,
+    spawn_count: Option<ShaderLoc>,
+    seed: Option<ShaderLoc>,
+    emitter_position: Option<ShaderLoc>,
+    spawn_radius: Option<ShaderLoc>,
+    direction: Option<ShaderLoc>,
+    spread_cos: Option<ShaderLoc>,
+    speed: Option<ShaderLoc>,
+    lifetime: Option<ShaderLoc>,
+    gravity: Option<ShaderLoc>,
+    drag: Option<ShaderLoc>,
+    delta: Option<ShaderLoc>,
+}
+
+#[derive(Debug)]
+struct RenderLocations {
+    view: Option<ShaderLoc>,
+    projection: Option<ShaderLoc>,
+    size: Option<ShaderLoc>,
+    color_start: Option<ShaderLoc>,
+    color_end: Option<ShaderLoc>,
+    use_sprite: Option<ShaderLoc>,
+}
+
+/// Particles simulated and drawn entirely on the GPU, for effects with 100k+ particles
+///
+/// Positions and velocities live in floating point textures, updated by a fragment shader that reads
+/// one pair of textures and writes the other. Particles are drawn as camera facing quads with a
+/// single instanced draw call. New particles reuse the slots of the oldest ones, so when more than
+/// the capacity are alive, the oldest disappear early.
+///
+/// Needs OpenGL 3.3 with floating point render targets (not available on OpenGL ES 2).
+#[derive(Debug)]
+pub struct GpuParticles {
+    /// Spawn parameters, read on every update
+    pub emitter: ParticleEmitter,
+    capacity: u32,
+    size: u32,
+    positions: [RenderTexture2D; 2],
+    velocities: [RenderTexture2D; 2],
+    current: usize,
+    head: u32,
+    pending: f32,
+    bursts: u32,
+    seed: i32,
+    update_shader: Shader,
+    update_locs: UpdateLocations,
+    render_shader: Shader,
+    render_locs: RenderLocations,
+    vao: u32,
+    vbo: u32,
+}
+
+impl GpuParticles {
+    /// Allocate room for `capacity` particles, fails if the GPU lacks what's needed
+    pub fn new(capacity: u32, emitter: ParticleEmitter) -> Result<Self, RaylibError> {
+        let capacity = capacity.max(1);
+        let size = (capacity as f64).sqrt().ceil() as u32;
+
+        let state = || {
+            let texture = RenderTexture2D::with_format(size, size, PixelFormat::R32G32B32A32)?;
+
+            // everything starts dead: age 0 with a lifetime of 0
+            unsafe {
+                ffi::BeginTextureMode(texture.raw.clone());
+                ffi::ClearBackground(Color::BLANK.into());
+                ffi::EndTextureMode();
+            }
+
+            Ok::<_, RaylibError>(texture)
+        };
+
+        let positions = [state()?, state()?];
+        let velocities = [state()?, state()?];
+
+        let mut update_shader = Shader::from_memory(None, Some(UPDATE_FS))?;
+        let mut render_shader = Shader::from_memory(Some(RENDER_VS), Some(RENDER_FS))?;
+
+        // state and sprite textures stay bound to units raylib's batch doesn't use
+        let values = [
+            ("positions", TextureArray::FIRST_FREE_UNIT),
+            ("velocities", TextureArray::FIRST_FREE_UNIT + 1),
+            ("sprite", TextureArray::FIRST_FREE_UNIT + 2),
+            ("stateSize", size),
+            ("capacity", capacity),
+        ];
+
+        for shader in [&mut update_shader, &mut render_shader] {
+            for (name, value) in values {
+                if let Some(loc) = shader.get_location(name) {
+                    shader.set_value(loc, value as i32);
+                }
+            }
+        }
+
+        let update_locs = UpdateLocations {
+            stage: update_shader.get_location("stage"),
+            spawn_start: update_shader.get_location("spawnStart"),
+            spawn_count: update_shader.get_location("spawnCount"),
+            seed: update_shader.get_location("seed"),
+            emitter_position: update_shader.get_location("emitterPosition"),
+            spawn_radius: update_shader.get_location("spawnRadius"),
+            direction: update_shader.get_location("direction"),
+            spread_cos: update_shader.get_location("spreadCos"),
+            speed: update_shader.get_location("speed"),
+            lifetime: update_shader.get_location("lifetime"),
+            gravity: update_shader.get_location("gravity"),
+            drag: update_shader.get_location("drag"),
+            delta: update_shader.get_location("delta"),
+        };
+
+        let render_locs = RenderLocations {
+            view: render_shader.get_location("matView"),
+            projection: render_shader.get_location("matProjection"),
+            size: render_shader.get_location("size"),
+            color_start: render_shader.get_location("colorStart"),
+            color_end: render_shader.get_location("colorEnd"),
+            use_sprite: render_shader.get_location("useSprite"),
+        };
+
+        let (vao, vbo) = unsafe {
+            let vao = gl::rlLoadVertexArray();
+
+            if vao == 0 || !gl::rlEnableVertexArray(vao) {
+                #[cfg(debug_assertions)]
+                crate::core::warn("GpuParticles: vertex arrays are not supported");
+
+                return Err(RaylibError::NotReady("vertex array"));
+            }
+
+            let vbo = gl::rlLoadVertexBuffer(
+                QUAD_CORNERS.as_ptr() as *const _,
+                std::mem::size_of_val(&QUAD_CORNERS) as _,
+                false,
+            );
+            gl::rlSetVertexAttribute(0, 2, gl::FLOAT, false, 0, std::ptr::null());
+            gl::rlEnableVertexAttribute(0);
+            gl::rlDisableVertexArray();
+
+            (vao, vbo)
+        };
+
+        track_context_object(ContextObject::VertexArray(vao));
+
+        Ok(Self {
+            emitter,
+            capacity,
+            size,
+            positions,
+            velocities,
+            current: 0,
+            head: 0,
+            pending: 0.,
+            bursts: 0,
+            seed: 0,
+            update_shader,
+            update_locs,
+            render_shader,
+            render_locs,
+            vao,
+            vbo,
+        })
+    }
+
+    /// Most particles alive at once
+    #[inline]
+    pub fn capacity(&self) -> u32 {
+        self.capacity
+    }
+
+    /// Spawn particles on the next update, on top of the emitter's rate
+    #[inline]
+    pub fn burst(&mut self, count: u32) {
+        self.bursts = self.bursts.saturating_add(count);
+    }
+
+    /// Spawn new particles and move the living ones `delta` seconds forward
+    ///
+    /// Renders into the state textures, so call it outside of texture and 3D modes.
+    pub fn update<D: Draw>(&mut self, d: &mut D, delta: f32) {
+        let functions = gl::functions();
+        let (Some(enable), Some(disable), Some(active_texture), Some(bind_texture)) = (
+            functions.enable,
+            functions.disable,
+            functions.active_texture,
+            functions.bind_texture,
+        ) else {
+            return;
+        };
+
+        self.pending += self.emitter.rate.max(0.) * delta.max(0.);
+
+        let spawn = (self.pending as u32)
+            .saturating_add(self.bursts)
+            .min(self.capacity);
+
+        self.pending = self.pending.fract();
+        self.bursts = 0;
+
+        self.set_update_values(spawn, delta);
+
+        let next = 1 - self.current;
+        let textures = |positions: &RenderTexture2D, velocities: &RenderTexture2D| {
+            [positions.raw.texture.id, velocities.raw.texture.id]
+        };
+
+        // velocities first, then positions move with the new velocities
+        let passes = [
+            (
+                textures(
+                    &self.positions[self.current],
+                    &self.velocities[self.current],
+                ),
+                &self.velocities[next],
+            ),
+            (
+                textures(&self.positions[self.current], &self.velocities[next]),
+                &self.positions[next],
+            ),
+        ];
+
+        for (stage, (inputs, target)) in passes.into_iter().enumerate() {
+            unsafe { bind_textures(active_texture, bind_texture, &inputs) };
+
+            if let Some(loc) = self.update_locs.stage {
+                self.update_shader.set_value(loc, stage as i32);
+            }
+
+            let mut texture_mode = d.begin_texture_mode(target);
+            let mut shader_mode = texture_mode.begin_shader_mode(&self.update_shader);
+
+            // the state is data, alpha blending would mix it with the previous contents
+            unsafe { disable(gl::BLEND) };
+
+            shader_mode.draw_rectangle(
+                Rectangle::new(0., 0., self.size as f32, self.size as f32),
+                Color::WHITE,
+            );
+            shader_mode.end_shader_mode();
+
+            unsafe { enable(gl::BLEND) };
+
+            texture_mode.end_texture_mode();
+        }
+
+        self.current = next;
+        self.head = (self.head + spawn) % self.capacity;
+        self.seed = self.seed.wrapping_add(1) & i32::MAX;
+    }
+
+    fn set_update_values(&mut self, spawn: u32, delta: f32) {
+        let emitter = &self.emitter;
+        let shader = &mut self.update_shader;
+        let locs = &self.update_locs;
+
+        let direction = if vec3::length(emitter.direction) > 0. {
+            vec3::normalize(emitter.direction)
+        } else {
+            vec3::new(0., 1., 0.)
+        };
+
+        let ints = [
+            (locs.spawn_start, self.head as i32),
+            (locs.spawn_count, spawn as i32),
+            (locs.seed, self.seed),
+        ];
+
+        let floats = [
+            (locs.spawn_radius, emitter.radius.max(0.)),
+            (
+                locs.spread_cos,
+                emitter.spread.clamp(0., 180.).to_radians().cos(),
+            ),
+            (locs.drag, emitter.drag.max(0.)),
+            (locs.delta, delta.max(0.)),
+        ];
+
+        let vec2s = [
+            (
+                locs.speed,
+                Vector2 {
+                    x: emitter.speed_min,
+                    y: emitter.speed_max,
+                },
+            ),
+            (
+                locs.lifetime,
+                Vector2 {
+                    x: emitter.lifetime_min.max(0.),
+                    y: emitter.lifetime_max.max(0.),
+                },
+            ),
+        ];
+
+        let vec3s = [
+            (locs.emitter_position, emitter.position),
+            (locs.direction, direction),
+            (locs.gravity, emitter.gravity),
+        ];
+
+        for (loc, value) in ints {
+            if let Some(loc) = loc {
+                shader.set_value(loc, value);
+            }
+        }
+
+        for (loc, value) in floats {
+            if let Some(loc) = loc {
+                shader.set_value(loc, value);
+            }
+        }
+
+        for (loc, value) in vec2s {
+            if let Some(loc) = loc {
+                shader.set_value(loc, value);
+            }
+        }
+
+        for (loc, value) in vec3s {
+            if let Some(loc) = loc {
+                shader.set_value(loc, value);
+            }
+        }
+    }
+
+    /// Draw the living particles, textured with `sprite` or as soft round dots
+    ///
+    /// Particles don't write depth, so they don't hide each other. Draw them inside
+    /// [`BlendMode::Additive`](crate::drawing::BlendMode::Additive) blend mode for glowing effects.
+    pub fn draw<D>(&mut self, _d: &mut DrawMode3D<'_, D>, sprite: Option<&Texture2D>) {
+        let functions = gl::functions();
+        let (Some(get_booleanv), Some(active_texture), Some(bind_texture)) = (
+            functions.get_booleanv,
+            functions.active_texture,
+            functions.bind_texture,
+        ) else {
+            return;
+        };
+
+        let textures = [
+            self.positions[self.current].raw.texture.id,
+            self.velocities[self.current].raw.texture.id,
+            sprite.map_or(0, |sprite| sprite.raw.id),
+        ];
+
+        unsafe { bind_textures(active_texture, bind_texture, &textures) };
+
+        let shader = &mut self.render_shader;
+        let locs = &self.render_locs;
+
+        let matrices = unsafe { [gl::rlGetMatrixModelview(), gl::rlGetMatrixProjection()] };
+
+        for (loc, matrix) in [locs.view, locs.projection].into_iter().zip(matrices) {
+            if let Some(loc) = loc {
+                shader.set_value_matrix(loc, matrix.into());
+            }
+        }
+
+        if let Some(loc) = locs.size {
+            shader.set_value(
+                loc,
+                Vector2 {
+                    x: self.emitter.size_start,
+                    y: self.emitter.size_end,
+                },
+            );
+        }
+
+        for (loc, color) in [
+            (locs.color_start, self.emitter.color_start),
+            (locs.color_end, self.emitter.color_end),
+        ] {
+            if let Some(loc) = loc {
+                shader.set_value(loc, color.normalize());
+            }
+        }
+
+        if let Some(loc) = locs.use_sprite {
+            shader.set_value(loc, sprite.is_some() as i32);
+        }
+
+        unsafe {
+            let mut depth_write = 1;
+            get_booleanv(gl::DEPTH_WRITEMASK, &mut depth_write);
+
+            gl::rlDisableDepthMask();
+            gl::rlEnableShader(shader.raw.id);
+
+            if gl::rlEnableVertexArray(self.vao) {
+                gl::rlDrawVertexArrayInstanced(0, QUAD_CORNERS.len() as _, self.capacity as _);
+                gl::rlDisableVertexArray();
+            }
+
+            // the batch enables its own shader again on the next draw
+            gl::rlDisableShader();
+
+            if depth_write != 0 {
+                gl::rlEnableDepthMask();
+            }
+        }
+    }
+}
+
+impl Drop for GpuParticles {
+    #[inline]
+    fn drop(&mut self) {
+        if release_context_object(ContextObject::VertexArray(self.vao)) {
+            unsafe {
+                gl::rlUnloadVertexArray(self.vao);
+                gl::rlUnloadVertexBuffer(self.vbo);
+            }
+        }
+    }
+}
+
+/// Bind textures to the units from [`TextureArray::FIRST_FREE_UNIT`] on, in order
+unsafe fn bind_textures(
+    active_texture: unsafe extern "system" fn(u32),
+    bind_texture: unsafe extern "system" fn(u32, u32),
+    textures: &[u32],
+) {
+    // draws already batched may use whatever was bound to the units before
+    gl::flush_batch();
+
+    for (unit, &id) in textures.iter().enumerate() {
+        active_texture(gl::TEXTURE0 + TextureArray::FIRST_FREE_UNIT + unit as u32);
+        bind_texture(gl::TEXTURE_2D, id);
+    }
+
+    active_texture(gl::TEXTURE0);
+}
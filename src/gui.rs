@@ -0,0 +1,1110 @@
+use crate::{
+    color::Color,
+    drawing::Draw,
+    math::{Rectangle, Vector2},
+    text::{text_to_cstring, Font},
+};
+
+use std::{
+    ffi::{c_char, CString},
+    ops::RangeInclusive,
+    path::Path,
+    ptr,
+    sync::Mutex,
+};
+
+/// Tooltip text, raygui keeps a pointer to it until it's changed
+static TOOLTIP: Mutex<Option<CString>> = Mutex::new(None);
+
+/// Raw raygui 3.6 bindings
+///
+/// The enum values are checked against raygui's when it's compiled (build/raygui.c).
+#[allow(non_snake_case)]
+pub mod ffi {
+    use crate::ffi::{Color, Font, Rectangle, Vector2};
+    use core::ffi::{c_char, c_float, c_int, c_uint};
+
+    /// Control state
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    pub enum GuiState {
+        /// Normal
+        Normal = 0,
+        /// Hovered
+        Focused,
+        /// Held down
+        Pressed,
+        /// Disabled
+        Disabled,
+    }
+
+    /// Text alignment inside controls
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    pub enum GuiTextAlignment {
+        /// Left
+        Left = 0,
+        /// Center
+        Center,
+        /// Right
+        Right,
+    }
+
+    /// Controls with their own style properties
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    pub enum GuiControl {
+        /// Defaults of all controls, also has the [`GuiDefaultProperty`] properties
+        Default = 0,
+        /// Label, also used by other controls' text
+        Label,
+        /// Button
+        Button,
+        /// Toggle and toggle group
+        Toggle,
+        /// Slider and slider bar
+        Slider,
+        /// Progress bar
+        ProgressBar,
+        /// Check box
+        CheckBox,
+        /// Combo box
+        ComboBox,
+        /// Dropdown box
+        DropdownBox,
+        /// Text box
+        TextBox,
+        /// Value box
+        ValueBox,
+        /// Spinner
+        Spinner,
+        /// List view
+        ListView,
+        /// Color picker
+        ColorPicker,
+        /// Scroll bar
+        ScrollBar,
+        /// Status bar
+        StatusBar,
+    }
+
+    /// Style properties every control has
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    pub enum GuiControlProperty {
+        /// Border color in the normal state
+        BorderColorNormal = 0,
+        /// Background color in the normal state
+        BaseColorNormal,
+        /// Text color in the normal state
+        TextColorNormal,
+        /// Border color when hovered
+        BorderColorFocused,
+        /// Background color when hovered
+        BaseColorFocused,
+        /// Text color when hovered
+        TextColorFocused,
+        /// Border color when held down
+        BorderColorPressed,
+        /// Background color when held down
+        BaseColorPressed,
+        /// Text color when held down
+        TextColorPressed,
+        /// Border color when disabled
+        BorderColorDisabled,
+        /// Background color when disabled
+        BaseColorDisabled,
+        /// Text color when disabled
+        TextColorDisabled,
+        /// Border width
+        BorderWidth,
+        /// Text padding
+        TextPadding,
+        /// Text alignment, a [`GuiTextAlignment`]
+        TextAlignment,
+        /// Unused
+        Reserved,
+    }
+
+    /// Extra style properties of [`GuiControl::Default`]
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    pub enum GuiDefaultProperty {
+        /// Text size (glyphs base size)
+        TextSize = 16,
+        /// Spacing between characters
+        TextSpacing,
+        /// Color of lines
+        LineColor,
+        /// Background color
+        BackgroundColor,
+    }
+
+    extern "C" {
+        /// Enable gui controls (global state)
+        pub fn GuiEnable();
+        /// Disable gui controls (global state)
+        pub fn GuiDisable();
+        /// Lock gui controls (global state)
+        pub fn GuiLock();
+        /// Unlock gui controls (global state)
+        pub fn GuiUnlock();
+        /// Check if gui is locked (global state)
+        pub fn GuiIsLocked() -> bool;
+        /// Set gui controls alpha (global state), alpha goes from 0.0f to 1.0f
+        pub fn GuiFade(alpha: c_float);
+        /// Set gui state (global state)
+        pub fn GuiSetState(state: c_int);
+        /// Get gui state (global state)
+        pub fn GuiGetState() -> c_int;
+        /// Set gui custom font (global state)
+        pub fn GuiSetFont(font: Font);
+        /// Get gui custom font (global state)
+        pub fn GuiGetFont() -> Font;
+        /// Set one style property
+        pub fn GuiSetStyle(control: c_int, property: c_int, value: c_int);
+        /// Get one style property
+        pub fn GuiGetStyle(control: c_int, property: c_int) -> c_int;
+
+        /// Window Box control, shows a window that can be closed
+        pub fn GuiWindowBox(bounds: Rectangle, title: *const c_char) -> bool;
+        /// Group Box control with text name
+        pub fn GuiGroupBox(bounds: Rectangle, text: *const c_char);
+        /// Line separator control, could contain text
+        pub fn GuiLine(bounds: Rectangle, text: *const c_char);
+        /// Panel control, useful to group controls
+        pub fn GuiPanel(bounds: Rectangle, text: *const c_char);
+        /// Tab Bar control, returns TAB to be closed or -1
+        pub fn GuiTabBar(
+            bounds: Rectangle,
+            text: *mut *const c_char,
+            count: c_int,
+            active: *mut c_int,
+        ) -> c_int;
+        /// Scroll Panel control
+        pub fn GuiScrollPanel(
+            bounds: Rectangle,
+            text: *const c_char,
+            content: Rectangle,
+            scroll: *mut Vector2,
+        ) -> Rectangle;
+
+        /// Label control, shows text
+        pub fn GuiLabel(bounds: Rectangle, text: *const c_char);
+        /// Button control, returns true when clicked
+        pub fn GuiButton(bounds: Rectangle, text: *const c_char) -> bool;
+        /// Label button control, show true when clicked
+        pub fn GuiLabelButton(bounds: Rectangle, text: *const c_char) -> bool;
+        /// Toggle Button control, returns true when active
+        pub fn GuiToggle(bounds: Rectangle, text: *const c_char, active: bool) -> bool;
+        /// Toggle Group control, returns active toggle index
+        pub fn GuiToggleGroup(bounds: Rectangle, text: *const c_char, active: c_int) -> c_int;
+        /// Check Box control, returns true when active
+        pub fn GuiCheckBox(bounds: Rectangle, text: *const c_char, checked: bool) -> bool;
+        /// Combo Box control, returns selected item index
+        pub fn GuiComboBox(bounds: Rectangle, text: *const c_char, active: c_int) -> c_int;
+        /// Dropdown Box control, returns selected item
+        pub fn GuiDropdownBox(
+            bounds: Rectangle,
+            text: *const c_char,
+            active: *mut c_int,
+            editMode: bool,
+        ) -> bool;
+        /// Spinner control, returns selected value
+        pub fn GuiSpinner(
+            bounds: Rectangle,
+            text: *const c_char,
+            value: *mut c_int,
+            minValue: c_int,
+            maxValue: c_int,
+            editMode: bool,
+        ) -> bool;
+        /// Value Box control, updates input text with numbers
+        pub fn GuiValueBox(
+            bounds: Rectangle,
+            text: *const c_char,
+            value: *mut c_int,
+            minValue: c_int,
+            maxValue: c_int,
+            editMode: bool,
+        ) -> bool;
+        /// Text Box control, updates input text
+        pub fn GuiTextBox(
+            bounds: Rectangle,
+            text: *mut c_char,
+            textSize: c_int,
+            editMode: bool,
+        ) -> bool;
+        /// Slider control, returns selected value
+        pub fn GuiSlider(
+            bounds: Rectangle,
+            textLeft: *const c_char,
+            textRight: *const c_char,
+            value: c_float,
+            minValue: c_float,
+            maxValue: c_float,
+        ) -> c_float;
+        /// Slider Bar control, returns selected value
+        pub fn GuiSliderBar(
+            bounds: Rectangle,
+            textLeft: *const c_char,
+            textRight: *const c_char,
+            value: c_float,
+            minValue: c_float,
+            maxValue: c_float,
+        ) -> c_float;
+        /// Progress Bar control, shows current progress value
+        pub fn GuiProgressBar(
+            bounds: Rectangle,
+            textLeft: *const c_char,
+            textRight: *const c_char,
+            value: c_float,
+            minValue: c_float,
+            maxValue: c_float,
+        ) -> c_float;
+        /// Status Bar control, shows info text
+        pub fn GuiStatusBar(bounds: Rectangle, text: *const c_char);
+        /// Dummy control for placeholders
+        pub fn GuiDummyRec(bounds: Rectangle, text: *const c_char);
+        /// Grid control, returns mouse cell position
+        pub fn GuiGrid(
+            bounds: Rectangle,
+            text: *const c_char,
+            spacing: c_float,
+            subdivs: c_int,
+        ) -> Vector2;
+
+        /// List View control, returns selected list item index
+        pub fn GuiListView(
+            bounds: Rectangle,
+            text: *const c_char,
+            scrollIndex: *mut c_int,
+            active: c_int,
+        ) -> c_int;
+        /// List View with extended parameters
+        pub fn GuiListViewEx(
+            bounds: Rectangle,
+            text: *mut *const c_char,
+            count: c_int,
+            focus: *mut c_int,
+            scrollIndex: *mut c_int,
+            active: c_int,
+        ) -> c_int;
+        /// Message Box control, displays a message
+        pub fn GuiMessageBox(
+            bounds: Rectangle,
+            title: *const c_char,
+            message: *const c_char,
+            buttons: *const c_char,
+        ) -> c_int;
+        /// Text Input Box control, ask for text, supports secret
+        pub fn GuiTextInputBox(
+            bounds: Rectangle,
+            title: *const c_char,
+            message: *const c_char,
+            buttons: *const c_char,
+            text: *mut c_char,
+            textMaxSize: c_int,
+            secretViewActive: *mut c_int,
+        ) -> c_int;
+        /// Color Picker control (multiple color controls)
+        pub fn GuiColorPicker(bounds: Rectangle, text: *const c_char, color: Color) -> Color;
+        /// Color Panel control
+        pub fn GuiColorPanel(bounds: Rectangle, text: *const c_char, color: Color) -> Color;
+        /// Color Bar Alpha control
+        pub fn GuiColorBarAlpha(bounds: Rectangle, text: *const c_char, alpha: c_float) -> c_float;
+        /// Color Bar Hue control
+        pub fn GuiColorBarHue(bounds: Rectangle, text: *const c_char, value: c_float) -> c_float;
+
+        /// Load style file over global style variable (.rgs)
+        pub fn GuiLoadStyle(fileName: *const c_char);
+        /// Load style default over global style
+        pub fn GuiLoadStyleDefault();
+
+        /// Enable gui tooltips (global state)
+        pub fn GuiEnableTooltip();
+        /// Disable gui tooltips (global state)
+        pub fn GuiDisableTooltip();
+        /// Set tooltip string
+        pub fn GuiSetTooltip(tooltip: *const c_char);
+
+        /// Get text with icon id prepended (if supported)
+        pub fn GuiIconText(iconId: c_int, text: *const c_char) -> *const c_char;
+        /// Set icon scale (1 by default)
+        pub fn GuiSetIconScale(scale: c_int);
+        /// Get raygui icons data pointer
+        pub fn GuiGetIcons() -> *mut c_uint;
+        /// Draw icon using pixel size at specified position
+        pub fn GuiDrawIcon(iconId: c_int, posX: c_int, posY: c_int, pixelSize: c_int, color: Color);
+    }
+}
+
+pub use ffi::{GuiControl, GuiControlProperty, GuiDefaultProperty, GuiState, GuiTextAlignment};
+
+/// Enable the controls
+#[inline]
+pub fn enable() {
+    unsafe { ffi::GuiEnable() }
+}
+
+/// Disable the controls, they are drawn in the disabled state and ignore input
+#[inline]
+pub fn disable() {
+    unsafe { ffi::GuiDisable() }
+}
+
+/// Lock the controls, they are drawn normally but ignore input (e.g. while a dropdown is open)
+#[inline]
+pub fn lock() {
+    unsafe { ffi::GuiLock() }
+}
+
+/// Unlock the controls
+#[inline]
+pub fn unlock() {
+    unsafe { ffi::GuiUnlock() }
+}
+
+/// Are the controls locked?
+#[inline]
+pub fn is_locked() -> bool {
+    unsafe { ffi::GuiIsLocked() }
+}
+
+/// Set the opacity of the controls, from 0 to 1
+#[inline]
+pub fn fade(alpha: f32) {
+    unsafe { ffi::GuiFade(alpha) }
+}
+
+/// Set the state the following controls are drawn in
+#[inline]
+pub fn set_state(state: GuiState) {
+    unsafe { ffi::GuiSetState(state as _) }
+}
+
+/// State the controls are drawn in
+#[inline]
+pub fn state() -> GuiState {
+    match unsafe { ffi::GuiGetState() } {
+        1 => GuiState::Focused,
+        2 => GuiState::Pressed,
+        3 => GuiState::Disabled,
+        _ => GuiState::Normal,
+    }
+}
+
+/// Set the font of the controls
+///
+/// The font has to stay loaded while it's used, [`load_style_default`] resets it.
+#[inline]
+pub fn set_font(font: &Font) {
+    unsafe { ffi::GuiSetFont(font.raw.clone()) }
+}
+
+/// Set a style property of a control
+#[inline]
+pub fn set_style(control: GuiControl, property: GuiControlProperty, value: i32) {
+    unsafe { ffi::GuiSetStyle(control as _, property as _, value) }
+}
+
+/// Style property of a control
+#[inline]
+pub fn get_style(control: GuiControl, property: GuiControlProperty) -> i32 {
+    unsafe { ffi::GuiGetStyle(control as _, property as _) }
+}
+
+/// Set a color style property of a control
+#[inline]
+pub fn set_style_color(control: GuiControl, property: GuiControlProperty, color: Color) {
+    set_style(control, property, color_to_int(color));
+}
+
+/// Color style property of a control
+#[inline]
+pub fn get_style_color(control: GuiControl, property: GuiControlProperty) -> Color {
+    int_to_color(get_style(control, property))
+}
+
+/// Set a style property shared by all controls
+#[inline]
+pub fn set_default_style(property: GuiDefaultProperty, value: i32) {
+    unsafe { ffi::GuiSetStyle(GuiControl::Default as _, property as _, value) }
+}
+
+/// Style property shared by all controls
+#[inline]
+pub fn get_default_style(property: GuiDefaultProperty) -> i32 {
+    unsafe { ffi::GuiGetStyle(GuiControl::Default as _, property as _) }
+}
+
+/// Load a style file (`.rgs`), returns `false` if there's no such file
+#[inline]
+pub fn load_style(file_name: &str) -> bool {
+    if !Path::new(file_name).is_file() {
+        #[cfg(debug_assertions)]
+        crate::core::warn(&format!("gui: style file '{file_name}' not found"));
+
+        return false;
+    }
+
+    let file_name = text_to_cstring(file_name);
+
+    unsafe { ffi::GuiLoadStyle(file_name.as_ptr()) }
+
+    true
+}
+
+/// Load the default style (and font)
+#[inline]
+pub fn load_style_default() {
+    unsafe { ffi::GuiLoadStyleDefault() }
+}
+
+/// Enable tooltips
+#[inline]
+pub fn enable_tooltip() {
+    unsafe { ffi::GuiEnableTooltip() }
+}
+
+/// Disable tooltips
+#[inline]
+pub fn disable_tooltip() {
+    unsafe { ffi::GuiDisableTooltip() }
+}
+
+/// Set the tooltip shown over the following controls while they're hovered (`None` for no tooltip)
+#[inline]
+pub fn set_tooltip(tooltip: Option<&str>) {
+    let mut current = TOOLTIP.lock().unwrap_or_else(|e| e.into_inner());
+    *current = tooltip.map(text_to_cstring);
+
+    let ptr = current
+        .as_ref()
+        .map_or(ptr::null(), |tooltip| tooltip.as_ptr());
+
+    unsafe { ffi::GuiSetTooltip(ptr) }
+}
+
+/// Set the scale icons are drawn with (1 by default)
+#[inline]
+pub fn set_icon_scale(scale: u32) {
+    unsafe { ffi::GuiSetIconScale(scale as _) }
+}
+
+/// Text with an icon in front of it, for control texts
+#[inline]
+pub fn icon_text(icon: u8, text: &str) -> String {
+    format!("#{icon:03}#{text}")
+}
+
+/// raygui stores colors as `0xRRGGBBAA`
+#[inline]
+fn color_to_int(color: Color) -> i32 {
+    u32::from_be_bytes([color.r, color.g, color.b, color.a]) as i32
+}
+
+#[inline]
+fn int_to_color(value: i32) -> Color {
+    let [r, g, b, a] = (value as u32).to_be_bytes();
+
+    Color::new(r, g, b, a)
+}
+
+/// Items joined into raygui's `;` separated list text
+#[inline]
+fn list_text(items: &[&str]) -> CString {
+    text_to_cstring(&items.join(";"))
+}
+
+#[inline]
+fn optional_text(text: Option<&str>) -> Option<CString> {
+    text.map(text_to_cstring)
+}
+
+#[inline]
+fn text_ptr(text: &Option<CString>) -> *const c_char {
+    text.as_ref().map_or(ptr::null(), |text| text.as_ptr())
+}
+
+/// Zero padded buffer for raygui to edit text in
+#[inline]
+fn text_buffer(text: &str, max_len: usize) -> Vec<u8> {
+    let mut buffer = vec![0; max_len.max(text.len()) + 1];
+    buffer[..text.len()].copy_from_slice(text.as_bytes());
+    buffer
+}
+
+/// Copy edited text back, returns whether it changed
+#[inline]
+fn read_text_buffer(buffer: &[u8], text: &mut String) -> bool {
+    let len = buffer.iter().position(|&b| b == 0).unwrap_or(buffer.len());
+    let edited = String::from_utf8_lossy(&buffer[..len]);
+
+    if edited != *text {
+        *text = edited.into_owned();
+        true
+    } else {
+        false
+    }
+}
+
+/// Index raygui returns, -1 for none
+#[inline]
+fn to_index(index: i32) -> Option<usize> {
+    usize::try_from(index).ok()
+}
+
+#[inline]
+fn from_index(index: Option<usize>) -> i32 {
+    index.map_or(-1, |index| index as _)
+}
+
+/// raygui immediate mode controls, drawn right away and returning what the user did with them
+///
+/// Implemented for everything that can draw. Controls react to input of the frame they're drawn in,
+/// so draw each one every frame with the same state.
+pub trait Gui: Draw {
+    /// Window with a title bar, returns `true` when its close button is clicked
+    #[inline]
+    fn gui_window_box(&mut self, bounds: Rectangle, title: &str) -> bool {
+        let title = text_to_cstring(title);
+
+        unsafe { ffi::GuiWindowBox(bounds.into(), title.as_ptr()) }
+    }
+
+    /// Box with a title in its border
+    #[inline]
+    fn gui_group_box(&mut self, bounds: Rectangle, text: &str) {
+        let text = text_to_cstring(text);
+
+        unsafe { ffi::GuiGroupBox(bounds.into(), text.as_ptr()) }
+    }
+
+    /// Separator line, optionally with a title
+    #[inline]
+    fn gui_line(&mut self, bounds: Rectangle, text: Option<&str>) {
+        let text = optional_text(text);
+
+        unsafe { ffi::GuiLine(bounds.into(), text_ptr(&text)) }
+    }
+
+    /// Panel, with a header if there's a title
+    #[inline]
+    fn gui_panel(&mut self, bounds: Rectangle, title: Option<&str>) {
+        let title = optional_text(title);
+
+        unsafe { ffi::GuiPanel(bounds.into(), text_ptr(&title)) }
+    }
+
+    /// Tabs, returns the index of a tab whose close button was clicked
+    fn gui_tab_bar(
+        &mut self,
+        bounds: Rectangle,
+        tabs: &[&str],
+        active: &mut usize,
+    ) -> Option<usize> {
+        let texts: Vec<CString> = tabs.iter().map(|tab| text_to_cstring(tab)).collect();
+        let mut ptrs: Vec<*const c_char> = texts.iter().map(|text| text.as_ptr()).collect();
+        let mut raw_active = *active as i32;
+
+        let closed = unsafe {
+            ffi::GuiTabBar(
+                bounds.into(),
+                ptrs.as_mut_ptr(),
+                ptrs.len() as _,
+                &mut raw_active,
+            )
+        };
+
+        *active = raw_active.max(0) as _;
+
+        to_index(closed)
+    }
+
+    /// Scrollable panel showing part of `content`, returns the visible area to draw the content in
+    ///
+    /// Content drawn in the area has to be offset by `scroll`, and clipped with scissor mode.
+    #[inline]
+    fn gui_scroll_panel(
+        &mut self,
+        bounds: Rectangle,
+        title: Option<&str>,
+        content: Rectangle,
+        scroll: &mut Vector2,
+    ) -> Rectangle {
+        let title = optional_text(title);
+        let mut raw_scroll = (*scroll).into();
+
+        let view = unsafe {
+            ffi::GuiScrollPanel(
+                bounds.into(),
+                text_ptr(&title),
+                content.into(),
+                &mut raw_scroll,
+            )
+        };
+
+        *scroll = raw_scroll.into();
+
+        view.into()
+    }
+
+    /// Text
+    #[inline]
+    fn gui_label(&mut self, bounds: Rectangle, text: &str) {
+        let text = text_to_cstring(text);
+
+        unsafe { ffi::GuiLabel(bounds.into(), text.as_ptr()) }
+    }
+
+    /// Button, returns `true` when clicked
+    #[inline]
+    fn gui_button(&mut self, bounds: Rectangle, text: &str) -> bool {
+        let text = text_to_cstring(text);
+
+        unsafe { ffi::GuiButton(bounds.into(), text.as_ptr()) }
+    }
+
+    /// Text that works as a button, returns `true` when clicked
+    #[inline]
+    fn gui_label_button(&mut self, bounds: Rectangle, text: &str) -> bool {
+        let text = text_to_cstring(text);
+
+        unsafe { ffi::GuiLabelButton(bounds.into(), text.as_ptr()) }
+    }
+
+    /// Button that stays pressed, returns `true` when toggled
+    #[inline]
+    fn gui_toggle(&mut self, bounds: Rectangle, text: &str, active: &mut bool) -> bool {
+        let text = text_to_cstring(text);
+        let toggled = unsafe { ffi::GuiToggle(bounds.into(), text.as_ptr(), *active) };
+
+        let changed = toggled != *active;
+        *active = toggled;
+        changed
+    }
+
+    /// Row of toggles with one active, `bounds` is the size of one toggle, returns `true` when changed
+    #[inline]
+    fn gui_toggle_group(&mut self, bounds: Rectangle, items: &[&str], active: &mut usize) -> bool {
+        let text = list_text(items);
+        let selected = unsafe { ffi::GuiToggleGroup(bounds.into(), text.as_ptr(), *active as _) };
+
+        let selected = selected.max(0) as usize;
+        let changed = selected != *active;
+        *active = selected;
+        changed
+    }
+
+    /// Check box with a label on its right, returns `true` when toggled
+    #[inline]
+    fn gui_check_box(&mut self, bounds: Rectangle, text: &str, checked: &mut bool) -> bool {
+        let text = text_to_cstring(text);
+        let toggled = unsafe { ffi::GuiCheckBox(bounds.into(), text.as_ptr(), *checked) };
+
+        let changed = toggled != *checked;
+        *checked = toggled;
+        changed
+    }
+
+    /// Button cycling through items, returns `true` when changed
+    #[inline]
+    fn gui_combo_box(&mut self, bounds: Rectangle, items: &[&str], active: &mut usize) -> bool {
+        let text = list_text(items);
+        let selected = unsafe { ffi::GuiComboBox(bounds.into(), text.as_ptr(), *active as _) };
+
+        let selected = selected.max(0) as usize;
+        let changed = selected != *active;
+        *active = selected;
+        changed
+    }
+
+    /// Dropdown list, opened and closed by clicking it, returns `true` when the selection changed
+    ///
+    /// Draw it after the controls it can cover, and [`lock`] the others while it's open.
+    fn gui_dropdown_box(
+        &mut self,
+        bounds: Rectangle,
+        items: &[&str],
+        active: &mut usize,
+        open: &mut bool,
+    ) -> bool {
+        let text = list_text(items);
+        let mut selected = *active as i32;
+
+        if unsafe { ffi::GuiDropdownBox(bounds.into(), text.as_ptr(), &mut selected, *open) } {
+            *open = !*open;
+        }
+
+        let selected = selected.max(0) as usize;
+        let changed = selected != *active;
+        *active = selected;
+        changed
+    }
+
+    /// Number with buttons to change it, editable as text after clicking it, returns `true` when changed
+    fn gui_spinner(
+        &mut self,
+        bounds: Rectangle,
+        text: &str,
+        value: &mut i32,
+        range: RangeInclusive<i32>,
+        editing: &mut bool,
+    ) -> bool {
+        let text = text_to_cstring(text);
+        let mut edited = *value;
+
+        let toggled = unsafe {
+            ffi::GuiSpinner(
+                bounds.into(),
+                text.as_ptr(),
+                &mut edited,
+                *range.start(),
+                *range.end(),
+                *editing,
+            )
+        };
+
+        if toggled {
+            *editing = !*editing;
+        }
+
+        let changed = edited != *value;
+        *value = edited;
+        changed
+    }
+
+    /// Number editable as text after clicking it, returns `true` when changed
+    fn gui_value_box(
+        &mut self,
+        bounds: Rectangle,
+        text: &str,
+        value: &mut i32,
+        range: RangeInclusive<i32>,
+        editing: &mut bool,
+    ) -> bool {
+        let text = text_to_cstring(text);
+        let mut edited = *value;
+
+        let toggled = unsafe {
+            ffi::GuiValueBox(
+                bounds.into(),
+                text.as_ptr(),
+                &mut edited,
+                *range.start(),
+                *range.end(),
+                *editing,
+            )
+        };
+
+        if toggled {
+            *editing = !*editing;
+        }
+
+        let changed = edited != *value;
+        *value = edited;
+        changed
+    }
+
+    /// Text field of up to `max_len` bytes, editable after clicking it, returns `true` when changed
+    ///
+    /// Editing stops when enter is pressed or somewhere else is clicked.
+    fn gui_text_box(
+        &mut self,
+        bounds: Rectangle,
+        text: &mut String,
+        max_len: usize,
+        editing: &mut bool,
+    ) -> bool {
+        let mut buffer = text_buffer(text, max_len);
+
+        let toggled = unsafe {
+            ffi::GuiTextBox(
+                bounds.into(),
+                buffer.as_mut_ptr().cast(),
+                buffer.len() as _,
+                *editing,
+            )
+        };
+
+        if toggled {
+            *editing = !*editing;
+        }
+
+        read_text_buffer(&buffer, text)
+    }
+
+    /// Slider with optional labels on its sides, returns `true` when moved
+    #[inline]
+    fn gui_slider(
+        &mut self,
+        bounds: Rectangle,
+        text_left: Option<&str>,
+        text_right: Option<&str>,
+        value: &mut f32,
+        range: RangeInclusive<f32>,
+    ) -> bool {
+        let (left, right) = (optional_text(text_left), optional_text(text_right));
+
+        let moved = unsafe {
+            ffi::GuiSlider(
+                bounds.into(),
+                text_ptr(&left),
+                text_ptr(&right),
+                *value,
+                *range.start(),
+                *range.end(),
+            )
+        };
+
+        let changed = moved != *value;
+        *value = moved;
+        changed
+    }
+
+    /// Slider filled up to the value, returns `true` when moved
+    #[inline]
+    fn gui_slider_bar(
+        &mut self,
+        bounds: Rectangle,
+        text_left: Option<&str>,
+        text_right: Option<&str>,
+        value: &mut f32,
+        range: RangeInclusive<f32>,
+    ) -> bool {
+        let (left, right) = (optional_text(text_left), optional_text(text_right));
+
+        let moved = unsafe {
+            ffi::GuiSliderBar(
+                bounds.into(),
+                text_ptr(&left),
+                text_ptr(&right),
+                *value,
+                *range.start(),
+                *range.end(),
+            )
+        };
+
+        let changed = moved != *value;
+        *value = moved;
+        changed
+    }
+
+    /// Progress bar
+    #[inline]
+    fn gui_progress_bar(
+        &mut self,
+        bounds: Rectangle,
+        text_left: Option<&str>,
+        text_right: Option<&str>,
+        value: f32,
+        range: RangeInclusive<f32>,
+    ) {
+        let (left, right) = (optional_text(text_left), optional_text(text_right));
+
+        unsafe {
+            ffi::GuiProgressBar(
+                bounds.into(),
+                text_ptr(&left),
+                text_ptr(&right),
+                value,
+                *range.start(),
+                *range.end(),
+            );
+        }
+    }
+
+    /// Status bar
+    #[inline]
+    fn gui_status_bar(&mut self, bounds: Rectangle, text: &str) {
+        let text = text_to_cstring(text);
+
+        unsafe { ffi::GuiStatusBar(bounds.into(), text.as_ptr()) }
+    }
+
+    /// Placeholder box
+    #[inline]
+    fn gui_dummy_rec(&mut self, bounds: Rectangle, text: &str) {
+        let text = text_to_cstring(text);
+
+        unsafe { ffi::GuiDummyRec(bounds.into(), text.as_ptr()) }
+    }
+
+    /// Grid, returns the cell under the mouse
+    #[inline]
+    fn gui_grid(&mut self, bounds: Rectangle, spacing: f32, subdivisions: u32) -> Option<Vector2> {
+        let cell: Vector2 =
+            unsafe { ffi::GuiGrid(bounds.into(), ptr::null(), spacing, subdivisions as _) }.into();
+
+        if cell.x < 0. || cell.y < 0. {
+            None
+        } else {
+            Some(cell)
+        }
+    }
+
+    /// Scrollable list with an optional selected item, returns `true` when the selection changed
+    ///
+    /// `scroll` is the index of the first visible item.
+    fn gui_list_view(
+        &mut self,
+        bounds: Rectangle,
+        items: &[&str],
+        scroll: &mut usize,
+        active: &mut Option<usize>,
+    ) -> bool {
+        let texts: Vec<CString> = items.iter().map(|item| text_to_cstring(item)).collect();
+        let mut ptrs: Vec<*const c_char> = texts.iter().map(|text| text.as_ptr()).collect();
+        let mut raw_scroll = *scroll as i32;
+        let mut focus = -1;
+
+        let selected = unsafe {
+            ffi::GuiListViewEx(
+                bounds.into(),
+                ptrs.as_mut_ptr(),
+                ptrs.len() as _,
+                &mut focus,
+                &mut raw_scroll,
+                from_index(*active),
+            )
+        };
+
+        *scroll = raw_scroll.max(0) as _;
+
+        let selected = to_index(selected);
+        let changed = selected != *active;
+        *active = selected;
+        changed
+    }
+
+    /// Message window with buttons, returns `Some(0)` when closed and `Some(n)` when the n-th (from 1) button is clicked
+    #[inline]
+    fn gui_message_box(
+        &mut self,
+        bounds: Rectangle,
+        title: &str,
+        message: &str,
+        buttons: &[&str],
+    ) -> Option<usize> {
+        let (title, message, buttons) = (
+            text_to_cstring(title),
+            text_to_cstring(message),
+            list_text(buttons),
+        );
+
+        to_index(unsafe {
+            ffi::GuiMessageBox(
+                bounds.into(),
+                title.as_ptr(),
+                message.as_ptr(),
+                buttons.as_ptr(),
+            )
+        })
+    }
+
+    /// Message window with a text field of up to `max_len` bytes, returns like [`Gui::gui_message_box`]
+    ///
+    /// With `secret`, the text is hidden unless it's `true` (toggled by a button in the window).
+    #[allow(clippy::too_many_arguments)]
+    fn gui_text_input_box(
+        &mut self,
+        bounds: Rectangle,
+        title: &str,
+        message: &str,
+        buttons: &[&str],
+        text: &mut String,
+        max_len: usize,
+        secret: Option<&mut bool>,
+    ) -> Option<usize> {
+        let (title, message, buttons) = (
+            text_to_cstring(title),
+            text_to_cstring(message),
+            list_text(buttons),
+        );
+        let mut buffer = text_buffer(text, max_len);
+        let mut raw_secret = secret.as_deref().map_or(0, |&shown| shown as i32);
+
+        let result = unsafe {
+            ffi::GuiTextInputBox(
+                bounds.into(),
+                title.as_ptr(),
+                message.as_ptr(),
+                buttons.as_ptr(),
+                buffer.as_mut_ptr().cast(),
+                buffer.len() as _,
+                if secret.is_some() {
+                    &mut raw_secret
+                } else {
+                    ptr::null_mut()
+                },
+            )
+        };
+
+        if let Some(secret) = secret {
+            *secret = raw_secret != 0;
+        }
+
+        read_text_buffer(&buffer, text);
+
+        to_index(result)
+    }
+
+    /// Color picker with a hue bar, returns `true` when changed
+    #[inline]
+    fn gui_color_picker(&mut self, bounds: Rectangle, color: &mut Color) -> bool {
+        let picked: Color =
+            unsafe { ffi::GuiColorPicker(bounds.into(), ptr::null(), (*color).into()) }.into();
+
+        let changed = picked != *color;
+        *color = picked;
+        changed
+    }
+
+    /// Saturation and value panel of a color picker, returns `true` when changed
+    #[inline]
+    fn gui_color_panel(&mut self, bounds: Rectangle, color: &mut Color) -> bool {
+        let picked: Color =
+            unsafe { ffi::GuiColorPanel(bounds.into(), ptr::null(), (*color).into()) }.into();
+
+        let changed = picked != *color;
+        *color = picked;
+        changed
+    }
+
+    /// Alpha bar from 0 to 1, returns `true` when changed
+    #[inline]
+    fn gui_color_bar_alpha(&mut self, bounds: Rectangle, alpha: &mut f32) -> bool {
+        let picked = unsafe { ffi::GuiColorBarAlpha(bounds.into(), ptr::null(), *alpha) };
+
+        let changed = picked != *alpha;
+        *alpha = picked;
+        changed
+    }
+
+    /// Hue bar from 0 to 360, returns `true` when changed
+    #[inline]
+    fn gui_color_bar_hue(&mut self, bounds: Rectangle, hue: &mut f32) -> bool {
+        let picked = unsafe { ffi::GuiColorBarHue(bounds.into(), ptr::null(), *hue) };
+
+        let changed = picked != *hue;
+        *hue = picked;
+        changed
+    }
+
+    /// Draw an icon, each of its 16x16 pixels `pixel_size` wide
+    #[inline]
+    fn gui_draw_icon(&mut self, icon: u8, position: Vector2, pixel_size: u32, color: Color) {
+        unsafe {
+            ffi::GuiDrawIcon(
+                icon as _,
+                position.x as _,
+                position.y as _,
+                pixel_size as _,
+                color.into(),
+            )
+        }
+    }
+}
+
+impl<D: Draw> Gui for D {}
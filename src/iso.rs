@@ -0,0 +1,250 @@
+use crate::{
+    color::Color,
+    drawing::Draw,
+    math::{vec3, Camera3D, CameraProjection, Vector2, Vector3},
+};
+use std::{cmp::Ordering, fmt};
+
+/// Projection of a tile grid onto the screen as diamonds (isometric or dimetric)
+///
+/// Grid X goes down-right on the screen, grid Y down-left, elevation up.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IsoProjection {
+    /// Screen width of a tile diamond
+    pub tile_width: f32,
+    /// Screen height of a tile diamond (half the width for the usual 2:1 tiles)
+    pub tile_height: f32,
+    /// Screen offset of one unit of elevation
+    pub elevation_height: f32,
+    /// Screen position of the top corner of tile (0, 0) at elevation 0
+    pub origin: Vector2,
+}
+
+impl IsoProjection {
+    /// Create new projection with the origin at (0, 0), one unit of elevation is half a tile high
+    #[inline]
+    pub fn new(tile_width: f32, tile_height: f32) -> Self {
+        Self {
+            tile_width,
+            tile_height,
+            elevation_height: tile_height / 2.,
+            origin: Vector2 { x: 0., y: 0. },
+        }
+    }
+
+    /// Set the screen position of the top corner of tile (0, 0)
+    #[inline]
+    pub fn with_origin(mut self, origin: impl Into<Vector2>) -> Self {
+        self.origin = origin.into();
+        self
+    }
+
+    /// Set the screen offset of one unit of elevation
+    #[inline]
+    pub fn with_elevation_height(mut self, elevation_height: f32) -> Self {
+        self.elevation_height = elevation_height;
+        self
+    }
+
+    /// Grid position (in tiles, fractions allowed) to screen position
+    #[inline]
+    pub fn to_screen(&self, grid: impl Into<Vector2>, elevation: f32) -> Vector2 {
+        let grid: Vector2 = grid.into();
+
+        Vector2 {
+            x: self.origin.x + (grid.x - grid.y) * self.tile_width / 2.,
+            y: self.origin.y + (grid.x + grid.y) * self.tile_height / 2.
+                - elevation * self.elevation_height,
+        }
+    }
+
+    /// Screen position to grid position, on the plane at an elevation
+    #[inline]
+    pub fn to_grid(&self, screen: impl Into<Vector2>, elevation: f32) -> Vector2 {
+        let screen: Vector2 = screen.into();
+
+        let x = (screen.x - self.origin.x) / (self.tile_width / 2.);
+        let y = (screen.y - self.origin.y + elevation * self.elevation_height)
+            / (self.tile_height / 2.);
+
+        Vector2 {
+            x: (y + x) / 2.,
+            y: (y - x) / 2.,
+        }
+    }
+
+    /// Tile under a screen position (e.g. the mouse), on the plane at an elevation
+    #[inline]
+    pub fn tile_at(&self, screen: impl Into<Vector2>, elevation: f32) -> (i32, i32) {
+        let grid = self.to_grid(screen, elevation);
+
+        (grid.x.floor() as i32, grid.y.floor() as i32)
+    }
+
+    /// Screen position of the center of a tile
+    #[inline]
+    pub fn tile_center(&self, x: i32, y: i32, elevation: f32) -> Vector2 {
+        self.to_screen(
+            Vector2 {
+                x: x as f32 + 0.5,
+                y: y as f32 + 0.5,
+            },
+            elevation,
+        )
+    }
+
+    /// Screen positions of the top, right, bottom and left corners of a tile
+    #[inline]
+    pub fn tile_corners(&self, x: i32, y: i32, elevation: f32) -> [Vector2; 4] {
+        let (x, y) = (x as f32, y as f32);
+
+        [
+            self.to_screen(Vector2 { x, y }, elevation),
+            self.to_screen(Vector2 { x: x + 1., y }, elevation),
+            self.to_screen(
+                Vector2 {
+                    x: x + 1.,
+                    y: y + 1.,
+                },
+                elevation,
+            ),
+            self.to_screen(Vector2 { x, y: y + 1. }, elevation),
+        ]
+    }
+
+    /// Sort key of something standing on a grid position, larger keys are drawn later
+    ///
+    /// Elevation only breaks ties, things on a raised tile stay behind what's in front of it.
+    #[inline]
+    pub fn depth(&self, grid: impl Into<Vector2>, elevation: f32) -> f32 {
+        let grid: Vector2 = grid.into();
+
+        grid.x + grid.y + elevation * 1e-3
+    }
+
+    /// Draw the outline of a tile
+    pub fn draw_tile_outline<D: Draw + ?Sized>(
+        &self,
+        d: &mut D,
+        x: i32,
+        y: i32,
+        elevation: f32,
+        color: Color,
+    ) {
+        let [top, right, bottom, left] = self.tile_corners(x, y, elevation);
+
+        d.draw_line(top, right, color);
+        d.draw_line(right, bottom, color);
+        d.draw_line(bottom, left, color);
+        d.draw_line(left, top, color);
+    }
+
+    /// Draw the outlines of a grid of tiles at elevation 0
+    pub fn draw_grid<D: Draw + ?Sized>(&self, d: &mut D, width: u32, height: u32, color: Color) {
+        for x in 0..=width {
+            let x = x as f32;
+            let start = self.to_screen(Vector2 { x, y: 0. }, 0.);
+            let end = self.to_screen(
+                Vector2 {
+                    x,
+                    y: height as f32,
+                },
+                0.,
+            );
+
+            d.draw_line(start, end, color);
+        }
+
+        for y in 0..=height {
+            let y = y as f32;
+            let start = self.to_screen(Vector2 { x: 0., y }, 0.);
+            let end = self.to_screen(Vector2 { x: width as f32, y }, 0.);
+
+            d.draw_line(start, end, color);
+        }
+    }
+}
+
+/// Orthographic camera looking at a target from the isometric angle (45° around, 35.26° down)
+///
+/// `view_height` is the height of the visible area in world units.
+/// Combine with billboards for 2.5D in 3D mode.
+#[inline]
+pub fn iso_camera(target: impl Into<Vector3>, distance: f32, view_height: f32) -> Camera3D {
+    let target: Vector3 = target.into();
+    let direction = vec3::normalize(vec3::new(1., 1., 1.));
+
+    Camera3D {
+        position: vec3::add(target, vec3::scale(direction, distance)),
+        target,
+        up: vec3::new(0., 1., 0.),
+        fovy: view_height,
+        projection: CameraProjection::Orthographic,
+    }
+}
+
+/// Draw calls collected in any order and drawn back to front
+///
+/// The key is usually [`IsoProjection::depth`], or the negated distance to the camera for
+/// transparent billboards. Equal keys keep the order they were added in.
+pub struct SortedDrawList<'a, D> {
+    items: Vec<(f32, Box<dyn FnOnce(&mut D) + 'a>)>,
+}
+
+impl<'a, D> fmt::Debug for SortedDrawList<'a, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SortedDrawList")
+            .field("len", &self.items.len())
+            .finish()
+    }
+}
+
+impl<'a, D> Default for SortedDrawList<'a, D> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, D> SortedDrawList<'a, D> {
+    /// Create empty list
+    #[inline]
+    pub fn new() -> Self {
+        Self { items: Vec::new() }
+    }
+
+    /// Add a draw call, smaller keys are drawn first
+    #[inline]
+    pub fn push(&mut self, key: f32, draw: impl FnOnce(&mut D) + 'a) {
+        self.items.push((key, Box::new(draw)));
+    }
+
+    /// Number of draw calls in the list
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Check if the list is empty
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Remove all draw calls without drawing them
+    #[inline]
+    pub fn clear(&mut self) {
+        self.items.clear();
+    }
+
+    /// Sort and run all draw calls, leaving the list empty (its memory is reused)
+    pub fn draw(&mut self, d: &mut D) {
+        self.items
+            .sort_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+
+        for (_, draw) in self.items.drain(..) {
+            draw(d);
+        }
+    }
+}
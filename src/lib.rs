@@ -1,32 +1,117 @@
 #![doc = include_str!("../README.md")]
 
+// raylib 4.5's GLFW is built for a single window system
+#[cfg(all(feature = "x11", feature = "wayland"))]
+compile_error!("the x11 and wayland features are mutually exclusive");
+
 /// Raw ffi bindings
 pub mod ffi;
 pub use ffi::{RAYLIB_VERSION, RAYLIB_VERSION_MAJOR, RAYLIB_VERSION_MINOR, RAYLIB_VERSION_PATCH};
 
 /// Audio
 pub mod audio;
+/// Webcam capture into a texture
+#[cfg(feature = "capture")]
+pub mod capture;
+/// Kinematic character controller
+pub mod character;
 /// Collision checks between different shapes
 pub mod collision;
 /// Color type and color constants
 pub mod color;
+/// Diagnostic and crash report bundles
+pub mod diagnostics;
 /// Drawing traits and functions
 pub mod drawing;
+/// Error type of the loaders
+pub mod error;
+/// Distance fog for 3D scenes, in shaders or as a post effect
+pub mod fog;
+/// Animated GIF encoding
+pub mod gif;
+/// Interop with glam (glam types convert to the math types with `.into()`)
+#[cfg(feature = "glam")]
+pub mod glam;
+/// Particles simulated in floating point textures and drawn with instancing
+pub mod gpu_particles;
+/// raygui immediate mode controls
+#[cfg(feature = "raygui")]
+pub mod gui;
+/// Isometric and 2.5D projection helpers with depth sorted drawing
+pub mod iso;
+/// 2D lights rendered into a light map, with normal mapped sprites
+pub mod light2d;
+/// Forwarding of raylib's log messages to the `log` crate
+#[cfg(feature = "log-bridge")]
+pub mod log_bridge;
+/// String tables and the `tr!` macro for translated text
+pub mod localization;
 /// Math types
 pub mod math;
 /// 3D models
 pub mod model;
+/// Monitors and video modes
+pub mod monitor;
+/// Navmesh baking and pathfinding
+pub mod nav;
+/// Small reliable UDP transport for multiplayer games
+#[cfg(feature = "net")]
+pub mod net;
+/// Parallax scrolling backgrounds behind a 2D camera
+pub mod parallax;
+/// Grid pathfinding (A* and flow fields)
+pub mod pathfind;
+/// Physac 2D physics bindings
+#[cfg(feature = "physac")]
+pub mod physac;
+/// Tiny verlet physics for 2D ropes and soft bodies
+pub mod physics2d;
+/// Built-in post effects (palette quantization with dithering, sRGB output, HDR tone mapping)
+pub mod post_fx;
+/// Rich presence (Discord, Steam, ...) hook points
+pub mod presence;
+/// Interop with the rapier physics engine (mint and nalgebra types convert with `.into()`)
+#[cfg(feature = "physics-rapier")]
+pub mod rapier;
+/// Render graph of named passes
+pub mod render_graph;
+/// Replayable random numbers and game time
+pub mod replay;
+/// rlgl bindings, immediate mode primitives, matrix stack and vertex arrays
+pub mod rlgl;
+/// Versioned save files with checksums and atomic writes
+#[cfg(feature = "saves")]
+pub mod saves;
+/// Scene stack with fade transitions
+pub mod scene;
 /// Shader type
 pub mod shader;
+/// Sprite sheet animations
+pub mod sprite;
+/// Render batch statistics (draw calls, vertices, flushes) and frame time graphs
+pub mod stats;
 /// Fonts and text related types and functions
 pub mod text;
 /// Images and textures
 pub mod texture;
+/// Tile layers, tilesets, auto-tiling and chunk streaming
+pub mod tilemap;
+/// Screen transition effects between render textures
+pub mod transition;
+/// Pointer hit testing against rotated and scaled drawn rectangles
+pub mod ui;
+/// MPEG-1 video playback
+#[cfg(feature = "video")]
+pub mod video;
 /// VR related types
 pub mod vr;
+/// Reflective water surface effect
+pub mod water;
 
 mod core;
+mod qr;
 pub use crate::core::*;
+pub use crate::error::RaylibError;
 
 /*
     // Loser List: functions that aren't included in the wrapper, because there are better and more idiomatic solutions available
@@ -43,9 +128,6 @@ pub use crate::core::*;
         /// Internal memory free
         pub fn MemFree(ptr: *mut core::ffi::c_void) {}
 
-        /// Set custom trace log
-        pub fn SetTraceLogCallback(callback: TraceLogCallback) {}
-
         /// Set custom file binary data loader
         pub fn SetLoadFileDataCallback(callback: LoadFileDataCallback) {}
 
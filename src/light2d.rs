@@ -0,0 +1,533 @@
+use crate::{
+    color::Color,
+    drawing::{gl, BlendMode, Draw, DrawMode2D, DrawTextureMode},
+    error::RaylibError,
+    ffi,
+    math::{Camera2D, Rectangle, Vector2},
+    shader::{Shader, ShaderLoc},
+    texture::{Image, RenderTexture2D, Texture2D},
+};
+
+const LIGHT_FS: &str = r#"#version 330
+in vec2 fragTexCoord;
+in vec4 fragColor;
+
+uniform sampler2D normalTexture;
+uniform int useNormals;
+uniform vec2 resolution;
+uniform float intensity;
+uniform float height;
+uniform int cone;
+uniform vec2 coneDirection;
+uniform float coneCos;
+uniform float coneSoftness;
+
+out vec4 finalColor;
+
+void main()
+{
+    // from the light to the fragment in radii, y goes down like on screen
+    vec2 offset = (fragTexCoord - 0.5)*2.0;
+    float distance = length(offset);
+    float attenuation = pow(clamp(1.0 - distance, 0.0, 1.0), 2.0);
+
+    if ((cone == 1) && (distance > 0.0))
+    {
+        float angleCos = dot(offset/distance, coneDirection);
+        attenuation *= smoothstep(coneCos - coneSoftness, coneCos, angleCos);
+    }
+
+    float diffuse = 1.0;
+
+    if (useNormals == 1)
+    {
+        // both render textures are upside down, fragment coordinates line up
+        vec3 normal = normalize(texture(normalTexture, gl_FragCoord.xy/resolution).rgb*2.0 - 1.0);
+        // normal maps have green pointing up
+        vec3 toLight = normalize(vec3(-offset.x, offset.y, height));
+        diffuse = max(dot(normal, toLight), 0.0);
+    }
+
+    finalColor = vec4(fragColor.rgb*intensity*attenuation*diffuse, 1.0);
+}
+"#;
+
+/// Normal buffer color where no normal map was drawn, facing the viewer
+const FLAT_NORMAL: Color = Color::new(128, 128, 255, 255);
+
+/// Shape of the area a [`Light2D`] lights
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LightShape {
+    /// Light in every direction
+    Point,
+    /// Light in a cone
+    Cone {
+        /// Direction of the cone in degrees, clockwise from the x axis like raylib rotations
+        direction: f32,
+        /// Angle between the cone's edges in degrees
+        angle: f32,
+    },
+}
+
+/// Light in a [`Lighting2D`] light map
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Light2D {
+    /// Position, in the coordinates of [`Lighting2D::camera`]
+    pub position: Vector2,
+    /// Distance the light reaches
+    pub radius: f32,
+    /// Light color
+    pub color: Color,
+    /// Brightness multiplier
+    pub intensity: f32,
+    /// Height above the sprites as a fraction of the radius, only used with normal maps
+    ///
+    /// Low lights graze the sprites and bring out their bumps.
+    pub height: f32,
+    /// Shape of the lit area
+    pub shape: LightShape,
+}
+
+impl Light2D {
+    /// Point light
+    #[inline]
+    pub fn point(position: Vector2, radius: f32, color: Color) -> Self {
+        Self {
+            position,
+            radius,
+            color,
+            intensity: 1.,
+            height: 0.2,
+            shape: LightShape::Point,
+        }
+    }
+
+    /// Cone light, `direction` and `angle` in degrees
+    #[inline]
+    pub fn cone(position: Vector2, radius: f32, color: Color, direction: f32, angle: f32) -> Self {
+        Self {
+            shape: LightShape::Cone { direction, angle },
+            ..Self::point(position, radius, color)
+        }
+    }
+
+    /// Set brightness multiplier
+    #[inline]
+    pub fn with_intensity(mut self, intensity: f32) -> Self {
+        self.intensity = intensity;
+        self
+    }
+
+    /// Set height above the sprites, see [`Light2D::height`]
+    #[inline]
+    pub fn with_height(mut self, height: f32) -> Self {
+        self.height = height;
+        self
+    }
+}
+
+#[derive(Debug)]
+struct LightLocations {
+    normal_texture: Option<ShaderLoc>,
+    use_normals: Option<ShaderLoc>,
+    resolution: Option<ShaderLoc>,
+    intensity: Option<ShaderLoc>,
+    height: Option<ShaderLoc>,
+    cone: Option<ShaderLoc>,
+    cone_direction: Option<ShaderLoc>,
+    cone_cos: Option<ShaderLoc>,
+    cone_softness: Option<ShaderLoc>,
+}
+
+/// 2D lighting: lights are added up in a light map, which then multiplies the scene
+///
+/// Every frame, optionally draw the sprites' normal maps with [`Lighting2D::draw_normals`], render
+/// the lights with [`Lighting2D::render_lights`], then draw the scene and multiply it by the light
+/// map with [`Lighting2D::apply`] (or [`Lighting2D::composite`] for a scene in a render texture).
+///
+/// The light map holds colors up to white, lights don't brighten the scene past its own colors.
+/// The shader targets desktop OpenGL 3.3.
+#[derive(Debug)]
+pub struct Lighting2D {
+    /// Light where no light reaches
+    pub ambient: Color,
+    /// Camera the lights and normal maps are drawn with, the same as the scene's
+    pub camera: Camera2D,
+    lightmap: RenderTexture2D,
+    normals: RenderTexture2D,
+    white: Texture2D,
+    shader: Shader,
+    locs: LightLocations,
+    normals_drawn: bool,
+}
+
+impl Lighting2D {
+    /// Create light map and normal buffer of the given size, usually the size of the screen
+    pub fn new(width: u32, height: u32) -> Result<Self, RaylibError> {
+        let lightmap = RenderTexture2D::new(width, height)?;
+        let normals = RenderTexture2D::new(width, height)?;
+        let white = Texture2D::from_image(&Image::generate_color(1, 1, Color::WHITE))?;
+        let shader = Shader::from_memory(None, Some(LIGHT_FS))?;
+
+        let locs = LightLocations {
+            normal_texture: shader.get_location("normalTexture"),
+            use_normals: shader.get_location("useNormals"),
+            resolution: shader.get_location("resolution"),
+            intensity: shader.get_location("intensity"),
+            height: shader.get_location("height"),
+            cone: shader.get_location("cone"),
+            cone_direction: shader.get_location("coneDirection"),
+            cone_cos: shader.get_location("coneCos"),
+            cone_softness: shader.get_location("coneSoftness"),
+        };
+
+        Ok(Self {
+            ambient: Color::new(40, 40, 60, 255),
+            camera: Camera2D {
+                offset: Vector2 { x: 0., y: 0. },
+                target: Vector2 { x: 0., y: 0. },
+                rotation: 0.,
+                zoom: 1.,
+            },
+            lightmap,
+            normals,
+            white,
+            shader,
+            locs,
+            normals_drawn: false,
+        })
+    }
+
+    /// Recreate the light map and normal buffer, e.g. after the window was resized
+    ///
+    /// Returns `false` and keeps the old ones if they couldn't be created.
+    pub fn resize(&mut self, width: u32, height: u32) -> bool {
+        match (
+            RenderTexture2D::new(width, height),
+            RenderTexture2D::new(width, height),
+        ) {
+            (Ok(lightmap), Ok(normals)) => {
+                self.lightmap = lightmap;
+                self.normals = normals;
+
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Light map, from the last [`Lighting2D::render_lights`]
+    #[inline]
+    pub fn lightmap(&self) -> &RenderTexture2D {
+        &self.lightmap
+    }
+
+    /// Draw the sprites' normal maps for the next [`Lighting2D::render_lights`]
+    ///
+    /// `normals` is called in 2D mode with [`Lighting2D::camera`], draw every sprite with its normal
+    /// map texture where it's drawn in the scene. Normal maps have green pointing up (OpenGL
+    /// convention), rotated and flipped sprites aren't corrected for.
+    pub fn draw_normals<D: Draw>(
+        &mut self,
+        d: &mut D,
+        normals: impl FnOnce(&mut DrawMode2D<'_, DrawTextureMode<'_, D>>),
+    ) {
+        let mut target = d.begin_texture_mode(&self.normals);
+        target.clear_background(FLAT_NORMAL);
+
+        let mut mode_2d = target.begin_mode_2d(self.camera);
+        normals(&mut mode_2d);
+        mode_2d.end_mode_2d();
+
+        target.end_texture_mode();
+
+        self.normals_drawn = true;
+    }
+
+    /// Render lights into the light map, starting from the ambient light
+    pub fn render_lights<D: Draw>(&mut self, d: &mut D, lights: &[Light2D]) {
+        let use_normals = std::mem::take(&mut self.normals_drawn);
+
+        let mut target = d.begin_texture_mode(&self.lightmap);
+        target.clear_background(self.ambient);
+
+        let mut blend = target.begin_blend_mode(BlendMode::Additive);
+        let mut shader_mode = blend.begin_shader_mode(&self.shader);
+        let mode_2d = shader_mode.begin_mode_2d(self.camera);
+
+        if let Some(loc) = self.locs.use_normals {
+            self.shader.set_value(loc, use_normals as i32);
+        }
+
+        if let Some(loc) = self.locs.resolution {
+            self.shader.set_value(
+                loc,
+                Vector2 {
+                    x: self.lightmap.width() as f32,
+                    y: self.lightmap.height() as f32,
+                },
+            );
+        }
+
+        for light in lights {
+            self.set_light_values(light, use_normals);
+
+            let radius = light.radius.max(0.);
+
+            unsafe {
+                ffi::DrawTexturePro(
+                    self.white.raw.clone(),
+                    Rectangle::new(0., 0., 1., 1.).into(),
+                    Rectangle::new(
+                        light.position.x - radius,
+                        light.position.y - radius,
+                        radius * 2.,
+                        radius * 2.,
+                    )
+                    .into(),
+                    Vector2 { x: 0., y: 0. }.into(),
+                    0.,
+                    light.color.into(),
+                );
+
+                // the uniforms are per light, draw it before they change
+                gl::flush_batch();
+            }
+        }
+
+        mode_2d.end_mode_2d();
+        shader_mode.end_shader_mode();
+        drop(blend);
+        target.end_texture_mode();
+    }
+
+    fn set_light_values(&mut self, light: &Light2D, use_normals: bool) {
+        // samplers have to be set while the shader is active, they are reset after every batch
+        if let Some(loc) = self.locs.normal_texture.filter(|_| use_normals) {
+            unsafe {
+                ffi::SetShaderValueTexture(
+                    self.shader.raw.clone(),
+                    loc.index() as _,
+                    self.normals.raw.texture.clone(),
+                )
+            }
+        }
+
+        let (cone, direction, angle) = match light.shape {
+            LightShape::Point => (0, 0., 360.),
+            LightShape::Cone { direction, angle } => (1, direction, angle.clamp(0., 360.)),
+        };
+        let (sin, cos) = direction.to_radians().sin_cos();
+        let cone_cos = (angle / 2.).to_radians().cos();
+
+        let values = [
+            (self.locs.intensity, light.intensity.max(0.)),
+            (self.locs.height, light.height.max(0.)),
+            (self.locs.cone_cos, cone_cos),
+            // fade over a tenth of the cone, smoothstep is undefined for an empty range
+            (self.locs.cone_softness, ((1. - cone_cos) * 0.1).max(1e-4)),
+        ];
+
+        for (loc, value) in values {
+            if let Some(loc) = loc {
+                self.shader.set_value(loc, value);
+            }
+        }
+
+        if let Some(loc) = self.locs.cone {
+            self.shader.set_value(loc, cone);
+        }
+
+        if let Some(loc) = self.locs.cone_direction {
+            self.shader.set_value(loc, Vector2 { x: cos, y: sin });
+        }
+    }
+
+    /// Multiply what's drawn in `dest` by the light map
+    pub fn apply<D: Draw>(&self, d: &mut D, dest: Rectangle) {
+        let blend = d.begin_blend_mode(BlendMode::Multiplied);
+        draw_render_texture(&self.lightmap, dest);
+        drop(blend);
+    }
+
+    /// Draw a scene from a render texture into `dest`, multiplied by the light map
+    pub fn composite<D: Draw>(&self, d: &mut D, scene: &RenderTexture2D, dest: Rectangle) {
+        draw_render_texture(scene, dest);
+        self.apply(d, dest);
+    }
+}
+
+fn draw_render_texture(texture: &RenderTexture2D, dest: Rectangle) {
+    let (width, height) = (texture.width() as f32, texture.height() as f32);
+
+    unsafe {
+        ffi::DrawTexturePro(
+            texture.raw.texture.clone(),
+            // render textures are upside down
+            Rectangle::new(0., 0., width, -height).into(),
+            dest.into(),
+            Vector2 { x: 0., y: 0. }.into(),
+            0.,
+            Color::WHITE.into(),
+        )
+    }
+}
+
+/// Hours in a day, the range of [`AmbientController::time`]
+const DAY_HOURS: f32 = 24.;
+
+/// Ambient light at a time of day
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AmbientKeyframe {
+    /// Time of day in hours, from 0 up to 24
+    pub time: f32,
+    /// Ambient color
+    pub color: Color,
+    /// Brightness multiplier of the color
+    pub intensity: f32,
+}
+
+impl AmbientKeyframe {
+    /// Create keyframe with an intensity of 1
+    #[inline]
+    pub fn new(time: f32, color: Color) -> Self {
+        Self {
+            time,
+            color,
+            intensity: 1.,
+        }
+    }
+}
+
+/// Day/night cycle of the ambient light, interpolated between keyframes over the time of day
+///
+/// Use [`AmbientController::tint`] as [`Lighting2D::ambient`] (see [`AmbientController::apply`])
+/// or to tint the background color. The day wraps around, from the last keyframe back to the
+/// first.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AmbientController {
+    /// Keyframes, in any order
+    pub keyframes: Vec<AmbientKeyframe>,
+    /// Time of day in hours, from 0 up to 24
+    pub time: f32,
+    /// Hours that pass per second, e.g. 0.04 for 10 minute days
+    pub speed: f32,
+}
+
+impl AmbientController {
+    /// Create controller at midnight, with time standing still
+    #[inline]
+    pub fn new(keyframes: Vec<AmbientKeyframe>) -> Self {
+        Self {
+            keyframes,
+            time: 0.,
+            speed: 0.,
+        }
+    }
+
+    /// Dark blue nights, orange dawns and dusks and white days, with 10 minute days
+    pub fn day_night() -> Self {
+        Self::new(vec![
+            AmbientKeyframe::new(0., Color::new(24, 28, 56, 255)),
+            AmbientKeyframe::new(5., Color::new(40, 40, 80, 255)),
+            AmbientKeyframe::new(6.5, Color::new(255, 170, 130, 255)),
+            AmbientKeyframe::new(9., Color::new(250, 245, 235, 255)),
+            AmbientKeyframe::new(16., Color::new(255, 245, 225, 255)),
+            AmbientKeyframe::new(18.5, Color::new(255, 150, 110, 255)),
+            AmbientKeyframe::new(20., Color::new(70, 50, 100, 255)),
+            AmbientKeyframe::new(21.5, Color::new(24, 28, 56, 255)),
+        ])
+        .with_speed(DAY_HOURS / 600.)
+    }
+
+    /// Set hours that pass per second
+    #[inline]
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Set time of day in hours
+    #[inline]
+    pub fn with_time(mut self, time: f32) -> Self {
+        self.time = time.rem_euclid(DAY_HOURS);
+        self
+    }
+
+    /// Advance the time of day by `delta` seconds
+    #[inline]
+    pub fn update(&mut self, delta: f32) {
+        self.time = (self.time + self.speed * delta).rem_euclid(DAY_HOURS);
+    }
+
+    /// Ambient light at the current time of day
+    #[inline]
+    pub fn tint(&self) -> Color {
+        self.tint_at(self.time)
+    }
+
+    /// Ambient light at a time of day in hours, white without keyframes
+    pub fn tint_at(&self, time: f32) -> Color {
+        let time = time.rem_euclid(DAY_HOURS);
+
+        // keyframes before and after, from the day before or after if there are none today
+        let previous = self
+            .keyframes
+            .iter()
+            .filter(|keyframe| keyframe.time <= time)
+            .max_by(|a, b| a.time.total_cmp(&b.time))
+            .map(|keyframe| (keyframe, keyframe.time))
+            .or_else(|| {
+                self.keyframes
+                    .iter()
+                    .max_by(|a, b| a.time.total_cmp(&b.time))
+                    .map(|keyframe| (keyframe, keyframe.time - DAY_HOURS))
+            });
+        let next = self
+            .keyframes
+            .iter()
+            .filter(|keyframe| keyframe.time > time)
+            .min_by(|a, b| a.time.total_cmp(&b.time))
+            .map(|keyframe| (keyframe, keyframe.time))
+            .or_else(|| {
+                self.keyframes
+                    .iter()
+                    .min_by(|a, b| a.time.total_cmp(&b.time))
+                    .map(|keyframe| (keyframe, keyframe.time + DAY_HOURS))
+            });
+
+        let ((from, from_time), (to, to_time)) = match (previous, next) {
+            (Some(previous), Some(next)) => (previous, next),
+            _ => return Color::WHITE,
+        };
+
+        let t = if to_time > from_time {
+            ((time - from_time) / (to_time - from_time)).clamp(0., 1.)
+        } else {
+            0.
+        };
+
+        let lerp = |a: u8, b: u8, intensity: f32| {
+            let value = a as f32 + (b as f32 - a as f32) * t;
+            (value * intensity).round().clamp(0., 255.) as u8
+        };
+        let intensity = from.intensity + (to.intensity - from.intensity) * t;
+
+        Color::new(
+            lerp(from.color.r, to.color.r, intensity),
+            lerp(from.color.g, to.color.g, intensity),
+            lerp(from.color.b, to.color.b, intensity),
+            lerp(from.color.a, to.color.a, 1.),
+        )
+    }
+
+    /// Set the ambient light of a [`Lighting2D`] to the current tint
+    #[inline]
+    pub fn apply(&self, lighting: &mut Lighting2D) {
+        lighting.ambient = self.tint();
+    }
+}
@@ -0,0 +1,574 @@
+use crate::{ffi, text::Font};
+use std::{collections::HashMap, fmt, fmt::Write, path::Path, sync::Mutex};
+
+/// References nested deeper than this are left as they are (they are most likely a cycle)
+const MAX_DEPTH: usize = 8;
+
+/// String table used by [`translate`] and [`tr!`](crate::tr)
+static ACTIVE: Mutex<Option<StringTable>> = Mutex::new(None);
+
+/// Translated messages of one locale
+///
+/// Messages can contain placeables in braces, filled in by [`StringTable::format`]:
+/// `{ $name }` is an argument, `{ -term }` a term, `{ other-message }` another message
+/// (or an argument if there's no message with that name) and `{ "text" }` a literal.
+#[derive(Clone, Debug, Default)]
+pub struct StringTable {
+    locale: String,
+    messages: HashMap<String, String>,
+}
+
+impl StringTable {
+    /// Create empty table
+    #[inline]
+    pub fn new(locale: &str) -> Self {
+        Self {
+            locale: locale.to_string(),
+            messages: HashMap::new(),
+        }
+    }
+
+    /// Load Fluent (`.ftl`) or JSON (any other extension) file
+    pub fn from_file(locale: &str, file_name: &str) -> Option<Self> {
+        let source = std::fs::read_to_string(file_name).ok()?;
+
+        match Path::new(file_name).extension() {
+            Some(extension) if extension.eq_ignore_ascii_case("ftl") => {
+                Self::from_fluent(locale, &source)
+            }
+            _ => Self::from_json(locale, &source),
+        }
+    }
+
+    /// Parse Fluent source, `None` on syntax errors (debug builds print them)
+    ///
+    /// Supports messages, terms, attributes (stored as `message.attribute`), multiline values
+    /// and comments. Select expressions aren't supported, they are kept as text.
+    pub fn from_fluent(locale: &str, source: &str) -> Option<Self> {
+        let mut table = Self::new(locale);
+        let mut current: Option<String> = None;
+
+        for (number, line) in source.lines().enumerate() {
+            let trimmed = line.trim();
+
+            if line.starts_with('#') || trimmed.is_empty() {
+                if line.starts_with('#') {
+                    current = None;
+                }
+                continue;
+            }
+
+            if line.starts_with(char::is_whitespace) {
+                let key = match &current {
+                    Some(key) => key,
+                    None => return fluent_error(number, "indented line outside of a message"),
+                };
+
+                if let Some(attribute) = trimmed.strip_prefix('.') {
+                    let (name, value) = match split_entry(attribute) {
+                        Some(entry) => entry,
+                        None => return fluent_error(number, "expected `.attribute = value`"),
+                    };
+                    let message = key.split('.').next().unwrap_or_default();
+                    let attribute_key = format!("{message}.{name}");
+
+                    table
+                        .messages
+                        .insert(attribute_key.clone(), value.to_string());
+                    current = Some(attribute_key);
+                } else {
+                    let value = table.messages.entry(key.clone()).or_default();
+
+                    if !value.is_empty() {
+                        value.push('\n');
+                    }
+                    value.push_str(trimmed);
+                }
+
+                continue;
+            }
+
+            match split_entry(line) {
+                Some((key, value)) if is_identifier(key.trim_start_matches('-')) => {
+                    table.messages.insert(key.to_string(), value.to_string());
+                    current = Some(key.to_string());
+                }
+                _ => return fluent_error(number, "expected `key = value`"),
+            }
+        }
+
+        Some(table)
+    }
+
+    /// Parse a JSON object of strings, `None` if it isn't one (debug builds print why)
+    ///
+    /// Escapes of lone UTF-16 surrogates are rejected, they aren't valid in a Rust string.
+    ///
+    /// Nested objects are flattened, `{"menu": {"start": "Start"}}` has the key `menu.start`.
+    pub fn from_json(locale: &str, source: &str) -> Option<Self> {
+        let mut table = Self::new(locale);
+        let mut parser = JsonParser {
+            chars: source.chars().peekable(),
+        };
+
+        parser.skip_whitespace();
+
+        let parsed = parser.object("", &mut table.messages).and_then(|()| {
+            parser.skip_whitespace();
+
+            match parser.chars.next() {
+                None => Ok(()),
+                Some(_) => Err("unexpected text after the object"),
+            }
+        });
+
+        match parsed {
+            Ok(()) => Some(table),
+            Err(_message) => {
+                #[cfg(debug_assertions)]
+                crate::core::warn(&format!("StringTable: invalid JSON: {}", _message));
+
+                None
+            }
+        }
+    }
+
+    /// Locale name
+    #[inline]
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+
+    /// Number of messages (including terms and attributes)
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    /// Check if the table is empty
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    /// Add or replace a message
+    #[inline]
+    pub fn insert(&mut self, key: &str, value: &str) {
+        self.messages.insert(key.to_string(), value.to_string());
+    }
+
+    /// Message as written, without filling in placeables
+    #[inline]
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.messages.get(key).map(String::as_str)
+    }
+
+    /// Message with its placeables filled in, the key itself if there's no such message
+    pub fn format(&self, key: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+        match self.messages.get(key) {
+            Some(message) => {
+                let mut out = String::with_capacity(message.len());
+                self.resolve(message, args, &mut out, 0);
+                out
+            }
+            None => key.to_string(),
+        }
+    }
+
+    /// Characters used by the messages (placeables are left out, arguments aren't known)
+    pub fn chars(&self) -> String {
+        let mut chars: Vec<char> = self
+            .messages
+            .values()
+            .flat_map(|message| {
+                let mut depth = 0;
+
+                message.chars().filter(move |&ch| match ch {
+                    '{' => {
+                        depth += 1;
+                        false
+                    }
+                    '}' => {
+                        depth = (depth - 1).max(0);
+                        false
+                    }
+                    _ => depth == 0 && !ch.is_control(),
+                })
+            })
+            .collect();
+
+        chars.sort_unstable();
+        chars.dedup();
+        chars.into_iter().collect()
+    }
+
+    /// Characters of the table none of the fonts has a glyph for
+    ///
+    /// Fonts are a fallback chain, a character is only missing if every font lacks it.
+    /// For a [`DynamicFont`](crate::text::DynamicFont), request [`StringTable::chars`] and
+    /// update it first.
+    pub fn missing_glyphs(&self, fonts: &[&Font]) -> Vec<char> {
+        self.chars()
+            .chars()
+            .filter(|&ch| ch != ' ' && !fonts.iter().any(|font| has_glyph(font, ch)))
+            .collect()
+    }
+
+    fn resolve(
+        &self,
+        message: &str,
+        args: &[(&str, &dyn fmt::Display)],
+        out: &mut String,
+        depth: usize,
+    ) {
+        let mut rest = message;
+
+        while let Some(start) = rest.find('{') {
+            out.push_str(&rest[..start]);
+
+            let end = match rest[start..].find('}') {
+                Some(end) => start + end,
+                None => {
+                    rest = &rest[start..];
+                    break;
+                }
+            };
+
+            let placeable = rest[start + 1..end].trim();
+            let arg = |name: &str| args.iter().find(|(arg, _)| *arg == name).map(|(_, v)| v);
+
+            if let Some(literal) = placeable
+                .strip_prefix('"')
+                .and_then(|p| p.strip_suffix('"'))
+            {
+                out.push_str(literal);
+            } else if let Some(value) = placeable.strip_prefix('$').and_then(arg) {
+                let _ = write!(out, "{value}");
+            } else if let Some(reference) =
+                self.messages.get(placeable).filter(|_| depth < MAX_DEPTH)
+            {
+                self.resolve(reference, args, out, depth + 1);
+            } else if let Some(value) = arg(placeable) {
+                let _ = write!(out, "{value}");
+            } else {
+                out.push_str(&rest[start..=end]);
+            }
+
+            rest = &rest[end + 1..];
+        }
+
+        out.push_str(rest);
+    }
+}
+
+/// Make a table active for [`translate`] and [`tr!`](crate::tr), returning the previous one
+#[inline]
+pub fn set_string_table(table: StringTable) -> Option<StringTable> {
+    ACTIVE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .replace(table)
+}
+
+/// Locale of the active table
+#[inline]
+pub fn locale() -> Option<String> {
+    ACTIVE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+        .map(|table| table.locale.clone())
+}
+
+/// Format a message of the active table, the key itself if there's no table or no such message
+#[inline]
+pub fn translate(key: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+    match &*ACTIVE.lock().unwrap_or_else(|e| e.into_inner()) {
+        Some(table) => table.format(key, args),
+        None => key.to_string(),
+    }
+}
+
+/// Characters of the active table none of the fonts has a glyph for
+///
+/// See [`StringTable::missing_glyphs`].
+#[inline]
+pub fn missing_glyphs(fonts: &[&Font]) -> Vec<char> {
+    match &*ACTIVE.lock().unwrap_or_else(|e| e.into_inner()) {
+        Some(table) => table.missing_glyphs(fonts),
+        None => Vec::new(),
+    }
+}
+
+/// Translate a message of the active [`StringTable`](crate::localization::StringTable)
+///
+/// `tr!("greeting")` or with arguments `tr!("greeting", name = player.name, count = 3)`.
+#[macro_export]
+macro_rules! tr {
+    ($key:expr) => {
+        $crate::localization::translate($key, &[])
+    };
+    ($key:expr, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::localization::translate(
+            $key,
+            &[$((stringify!($name), &$value as &dyn ::std::fmt::Display)),+],
+        )
+    };
+}
+
+/// Check if a font has its own glyph for a character, not the '?' fallback
+#[inline]
+fn has_glyph(font: &Font, ch: char) -> bool {
+    if font.raw.glyphs.is_null() || font.raw.glyphCount <= 0 {
+        return false;
+    }
+
+    let index = unsafe { ffi::GetGlyphIndex(font.raw.clone(), ch as _) };
+    let glyph = unsafe { &*font.raw.glyphs.add(index as usize) };
+
+    glyph.value == ch as i32
+}
+
+#[inline]
+fn is_identifier(key: &str) -> bool {
+    key.starts_with(|ch: char| ch.is_ascii_alphabetic())
+        && key
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+}
+
+/// Split `key = value`, trimming both
+#[inline]
+fn split_entry(line: &str) -> Option<(&str, &str)> {
+    let (key, value) = line.split_once('=')?;
+
+    Some((key.trim(), value.trim()))
+}
+
+#[inline]
+fn fluent_error(_line: usize, _message: &str) -> Option<StringTable> {
+    #[cfg(debug_assertions)]
+    crate::core::warn(&format!(
+        "StringTable: Fluent syntax error on line {}: {}",
+        _line + 1,
+        _message
+    ));
+
+    None
+}
+
+struct JsonParser<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+}
+
+impl JsonParser<'_> {
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|ch| ch.is_whitespace()).is_some() {}
+    }
+
+    #[inline]
+    fn expect(&mut self, expected: char, error: &'static str) -> Result<(), &'static str> {
+        if self.chars.next() == Some(expected) {
+            Ok(())
+        } else {
+            Err(error)
+        }
+    }
+
+    /// Parse an object (the opening brace is next) into `messages`, keys prefixed with `prefix`
+    fn object(
+        &mut self,
+        prefix: &str,
+        messages: &mut HashMap<String, String>,
+    ) -> Result<(), &'static str> {
+        self.expect('{', "expected an object")?;
+        self.skip_whitespace();
+
+        if self.chars.next_if_eq(&'}').is_some() {
+            return Ok(());
+        }
+
+        loop {
+            self.skip_whitespace();
+
+            let key = self.string()?;
+            let key = if prefix.is_empty() {
+                key
+            } else {
+                format!("{prefix}.{key}")
+            };
+
+            self.skip_whitespace();
+            self.expect(':', "expected `:` after a key")?;
+            self.skip_whitespace();
+
+            match self.chars.peek() {
+                Some('{') => self.object(&key, messages)?,
+                Some('"') => {
+                    let value = self.string()?;
+                    messages.insert(key, value);
+                }
+                _ => return Err("values must be strings or objects"),
+            }
+
+            self.skip_whitespace();
+
+            match self.chars.next() {
+                Some(',') => continue,
+                Some('}') => return Ok(()),
+                _ => return Err("expected `,` or `}` after a value"),
+            }
+        }
+    }
+
+    /// Parse a string (the opening quote is next)
+    fn string(&mut self) -> Result<String, &'static str> {
+        const UNTERMINATED: &str = "unterminated string";
+
+        self.expect('"', "expected a string")?;
+
+        let mut string = String::new();
+
+        loop {
+            match self.chars.next().ok_or(UNTERMINATED)? {
+                '"' => return Ok(string),
+                '\\' => match self.chars.next().ok_or(UNTERMINATED)? {
+                    '"' => string.push('"'),
+                    '\\' => string.push('\\'),
+                    '/' => string.push('/'),
+                    'b' => string.push('\u{8}'),
+                    'f' => string.push('\u{c}'),
+                    'n' => string.push('\n'),
+                    'r' => string.push('\r'),
+                    't' => string.push('\t'),
+                    'u' => string.push(self.unicode_escape()?),
+                    _ => return Err("invalid escape sequence"),
+                },
+                ch => string.push(ch),
+            }
+        }
+    }
+
+    /// Parse the code point of a `\u` escape (after the `u`), a surrogate pair takes two escapes
+    fn unicode_escape(&mut self) -> Result<char, &'static str> {
+        const LONE_SURROGATE: &str = "lone surrogate in a `\\u` escape";
+
+        let high = self.hex4()?;
+
+        let code = match high {
+            0xD800..=0xDBFF => {
+                if self.chars.next() != Some('\\') || self.chars.next() != Some('u') {
+                    return Err(LONE_SURROGATE);
+                }
+
+                let low = self.hex4()?;
+
+                if !(0xDC00..=0xDFFF).contains(&low) {
+                    return Err(LONE_SURROGATE);
+                }
+
+                0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+            }
+            0xDC00..=0xDFFF => return Err(LONE_SURROGATE),
+            _ => high,
+        };
+
+        char::from_u32(code).ok_or(LONE_SURROGATE)
+    }
+
+    fn hex4(&mut self) -> Result<u32, &'static str> {
+        let mut value = 0;
+
+        for _ in 0..4 {
+            let digit = self.chars.next().and_then(|ch| ch.to_digit(16));
+
+            value = value * 16 + digit.ok_or("expected 4 hex digits after `\\u`")?;
+        }
+
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fluent() {
+        let source = "\
+# comment
+hello = Hello, { $name }!
+-brand = raylib
+about = Made with { -brand }
+    and a lot of coffee
+button = Start
+    .tooltip = Starts the game
+";
+        let table = StringTable::from_fluent("en", source).unwrap();
+
+        assert_eq!(table.len(), 5);
+        assert_eq!(table.get("hello"), Some("Hello, { $name }!"));
+        assert_eq!(
+            table.get("about"),
+            Some("Made with { -brand }\nand a lot of coffee")
+        );
+        assert_eq!(table.get("button.tooltip"), Some("Starts the game"));
+        assert_eq!(table.format("hello", &[("name", &"Ray")]), "Hello, Ray!");
+        assert_eq!(
+            table.format("about", &[]),
+            "Made with raylib\nand a lot of coffee"
+        );
+    }
+
+    #[test]
+    fn fluent_errors() {
+        assert!(StringTable::from_fluent("en", "    indented = value").is_none());
+        assert!(StringTable::from_fluent("en", "no value").is_none());
+        assert!(StringTable::from_fluent("en", "1st = value").is_none());
+        assert!(StringTable::from_fluent("en", "key = value\n    .attribute").is_none());
+    }
+
+    #[test]
+    fn json() {
+        let source = r#" {
+            "title": "Game",
+            "menu": { "start": "Start \"now\"", "quit": "Quit\n" },
+            "empty": {}
+        } "#;
+        let table = StringTable::from_json("en", source).unwrap();
+
+        assert_eq!(table.len(), 3);
+        assert_eq!(table.get("title"), Some("Game"));
+        assert_eq!(table.get("menu.start"), Some("Start \"now\""));
+        assert_eq!(table.get("menu.quit"), Some("Quit\n"));
+    }
+
+    #[test]
+    fn json_unicode_escapes() {
+        let table = StringTable::from_json("en", r#"{"a": "\u00e9\ud83d\ude00\/"}"#).unwrap();
+
+        assert_eq!(table.get("a"), Some("é😀/"));
+    }
+
+    #[test]
+    fn json_errors() {
+        for source in [
+            "",
+            "[]",
+            r#"{"a": 1}"#,
+            r#"{"a": "b",}"#,
+            r#"{"a": "b"} x"#,
+            r#"{"a": "b"#,
+            r#"{"a": "\x"}"#,
+            r#"{"a": "\u12"}"#,
+            // lone surrogates
+            r#"{"a": "\ud83d"}"#,
+            r#"{"a": "\ud83dx"}"#,
+            r#"{"a": "\ude00"}"#,
+            r#"{"a": "\ud83d\u0041"}"#,
+            r#"{"a": "\ud83d\ud83d"}"#,
+        ] {
+            assert!(StringTable::from_json("en", source).is_none(), "{source}");
+        }
+    }
+}
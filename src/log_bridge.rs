@@ -0,0 +1,41 @@
+use crate::{core::Raylib, ffi, TraceLogLevel};
+
+use ::log::{Level, LevelFilter};
+
+/// Forward raylib's log messages to the `log` crate, with `raylib` as the target
+///
+/// Also limits raylib to [`log::max_level`], so messages the logger would drop aren't formatted.
+/// Call it after setting up the logger and before [`Raylib::init_window`] to include the
+/// initialization messages.
+pub fn install() {
+    set_trace_log_level(::log::max_level());
+
+    Raylib::set_trace_log(|level, message| {
+        let level = match level {
+            TraceLogLevel::Trace => Level::Trace,
+            TraceLogLevel::Debug => Level::Debug,
+            TraceLogLevel::Warning => Level::Warn,
+            TraceLogLevel::Error | TraceLogLevel::Fatal => Level::Error,
+            _ => Level::Info,
+        };
+
+        ::log::log!(target: "raylib", level, "{message}");
+    });
+}
+
+/// Set the lowest level raylib logs at, e.g. [`LevelFilter::Warn`] to skip its INFO output
+///
+/// Same as [`Raylib::set_trace_log_level`], but usable before the window is created.
+#[inline]
+pub fn set_trace_log_level(level: LevelFilter) {
+    let level = match level {
+        LevelFilter::Off => TraceLogLevel::None,
+        LevelFilter::Error => TraceLogLevel::Error,
+        LevelFilter::Warn => TraceLogLevel::Warning,
+        LevelFilter::Info => TraceLogLevel::Info,
+        LevelFilter::Debug => TraceLogLevel::Debug,
+        LevelFilter::Trace => TraceLogLevel::Trace,
+    };
+
+    unsafe { ffi::SetTraceLogLevel(level as _) }
+}
@@ -1,6 +1,6 @@
 use crate::ffi;
 use static_assertions::{assert_eq_align, assert_eq_size};
-use std::mem::transmute;
+use std::{fmt, mem::transmute};
 
 pub use crate::ffi::{CameraMode, CameraProjection};
 
@@ -23,6 +23,13 @@ impl From<ffi::Vector2> for Vector2 {
     }
 }
 
+impl From<&Vector2> for ffi::Vector2 {
+    #[inline]
+    fn from(val: &Vector2) -> Self {
+        (*val).into()
+    }
+}
+
 /// Vector3, 3x f32 components
 pub type Vector3 = mint::Vector3<f32>;
 assert_eq_size!(Vector3, ffi::Vector3);
@@ -42,6 +49,13 @@ impl From<ffi::Vector3> for Vector3 {
     }
 }
 
+impl From<&Vector3> for ffi::Vector3 {
+    #[inline]
+    fn from(val: &Vector3) -> Self {
+        (*val).into()
+    }
+}
+
 /// Vector4, 4x f32 components
 pub type Vector4 = mint::Vector4<f32>;
 assert_eq_size!(Vector4, ffi::Vector4);
@@ -81,6 +95,8 @@ impl From<ffi::Vector4> for Quaternion {
 }
 
 /// Matrix, 4x4 f32 components, column major
+///
+/// The memory layout is raylib's: `x` holds `m0, m4, m8, m12`, the first row of the matrix.
 pub type Matrix = mint::ColumnMatrix4<f32>;
 assert_eq_size!(Matrix, ffi::Matrix);
 assert_eq_align!(Matrix, ffi::Matrix);
@@ -130,6 +146,14 @@ impl Rectangle {
     }
 }
 
+impl fmt::Display for Rectangle {
+    /// `(x, y, width, height)`, float formatting options apply to every component
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_components(f, &[self.x, self.y, self.width, self.height])
+    }
+}
+
 impl From<Rectangle> for ffi::Rectangle {
     #[inline]
     fn from(val: Rectangle) -> Self {
@@ -147,6 +171,7 @@ impl From<ffi::Rectangle> for Rectangle {
 /// Ray, ray for raycasting
 #[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Ray {
     /// Ray position (origin)
     pub position: Vector3,
@@ -157,6 +182,53 @@ pub struct Ray {
 assert_eq_size!(Ray, ffi::Ray);
 assert_eq_align!(Ray, ffi::Ray);
 
+impl Ray {
+    /// Point along the ray, `t` times the direction away from its origin
+    #[inline]
+    pub fn point_at(&self, t: f32) -> Vector3 {
+        Vector3 {
+            x: self.position.x + self.direction.x * t,
+            y: self.position.y + self.direction.y * t,
+            z: self.position.z + self.direction.z * t,
+        }
+    }
+
+    /// Intersect the ray with a plane going through `point` with the given `normal`
+    ///
+    /// Returns `t` such that [`Self::point_at`] gives the intersection point,
+    /// `None` if the ray is parallel to the plane or points away from it.
+    #[inline]
+    pub fn intersect_plane(&self, point: Vector3, normal: Vector3) -> Option<f32> {
+        let denom =
+            self.direction.x * normal.x + self.direction.y * normal.y + self.direction.z * normal.z;
+
+        if denom.abs() <= f32::EPSILON {
+            return None;
+        }
+
+        let t = ((point.x - self.position.x) * normal.x
+            + (point.y - self.position.y) * normal.y
+            + (point.z - self.position.z) * normal.z)
+            / denom;
+
+        if t >= 0. {
+            Some(t)
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for Ray {
+    /// `(x, y, z) -> (x, y, z)`, float formatting options apply to every component
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_vector3(f, self.position)?;
+        f.write_str(" -> ")?;
+        fmt_vector3(f, self.direction)
+    }
+}
+
 impl From<Ray> for ffi::Ray {
     #[inline]
     fn from(val: Ray) -> Self {
@@ -174,6 +246,7 @@ impl From<ffi::Ray> for Ray {
 /// RayCollision, ray hit information
 #[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RayCollision {
     /// Did the ray hit something?
     pub hit: bool,
@@ -205,6 +278,7 @@ impl From<ffi::RayCollision> for RayCollision {
 /// BoundingBox
 #[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BoundingBox {
     /// Minimum vertex box-corner
     pub min: Vector3,
@@ -212,8 +286,102 @@ pub struct BoundingBox {
     pub max: Vector3,
 }
 
-assert_eq_size!(Ray, ffi::BoundingBox);
-assert_eq_align!(Ray, ffi::BoundingBox);
+assert_eq_size!(BoundingBox, ffi::BoundingBox);
+assert_eq_align!(BoundingBox, ffi::BoundingBox);
+
+impl BoundingBox {
+    /// Create new bounding box
+    #[inline]
+    pub const fn new(min: Vector3, max: Vector3) -> Self {
+        Self { min, max }
+    }
+
+    /// Smallest bounding box containing all the points, `None` if there are none
+    #[inline]
+    pub fn from_points(points: &[Vector3]) -> Option<Self> {
+        let (first, rest) = points.split_first()?;
+
+        Some(rest.iter().fold(Self::new(*first, *first), |bbox, point| {
+            bbox.merge(&Self::new(*point, *point))
+        }))
+    }
+
+    /// Center of the box
+    #[inline]
+    pub fn center(&self) -> Vector3 {
+        Vector3 {
+            x: (self.min.x + self.max.x) / 2.,
+            y: (self.min.y + self.max.y) / 2.,
+            z: (self.min.z + self.max.z) / 2.,
+        }
+    }
+
+    /// Half of the box size along each axis
+    #[inline]
+    pub fn extents(&self) -> Vector3 {
+        Vector3 {
+            x: (self.max.x - self.min.x) / 2.,
+            y: (self.max.y - self.min.y) / 2.,
+            z: (self.max.z - self.min.z) / 2.,
+        }
+    }
+
+    /// Check if a point is inside the box (or on its surface)
+    #[inline]
+    pub fn contains(&self, point: Vector3) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x)
+            && (self.min.y..=self.max.y).contains(&point.y)
+            && (self.min.z..=self.max.z).contains(&point.z)
+    }
+
+    /// Smallest bounding box containing both boxes
+    #[inline]
+    pub fn merge(&self, other: &BoundingBox) -> Self {
+        Self {
+            min: Vector3 {
+                x: self.min.x.min(other.min.x),
+                y: self.min.y.min(other.min.y),
+                z: self.min.z.min(other.min.z),
+            },
+            max: Vector3 {
+                x: self.max.x.max(other.max.x),
+                y: self.max.y.max(other.max.y),
+                z: self.max.z.max(other.max.z),
+            },
+        }
+    }
+
+    /// Axis-aligned bounding box of this box transformed by a matrix
+    #[inline]
+    pub fn transform(&self, matrix: &Matrix) -> Self {
+        let m = columns(matrix);
+
+        let corners = [
+            [self.min.x, self.min.y, self.min.z],
+            [self.max.x, self.min.y, self.min.z],
+            [self.min.x, self.max.y, self.min.z],
+            [self.max.x, self.max.y, self.min.z],
+            [self.min.x, self.min.y, self.max.z],
+            [self.max.x, self.min.y, self.max.z],
+            [self.min.x, self.max.y, self.max.z],
+            [self.max.x, self.max.y, self.max.z],
+        ]
+        .map(|corner| transform_point(&m, corner));
+
+        // 8 corners, never empty
+        Self::from_points(&corners).unwrap()
+    }
+}
+
+impl fmt::Display for BoundingBox {
+    /// `(x, y, z)..(x, y, z)`, float formatting options apply to every component
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_vector3(f, self.min)?;
+        f.write_str("..")?;
+        fmt_vector3(f, self.max)
+    }
+}
 
 impl From<BoundingBox> for ffi::BoundingBox {
     #[inline]
@@ -232,6 +400,7 @@ impl From<ffi::BoundingBox> for BoundingBox {
 /// Transform, vertex transformation data
 #[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Transform {
     /// Translation
     pub translation: Vector3,
@@ -294,6 +463,60 @@ impl Camera2D {
     pub fn world_to_screen(&self, position: Vector2) -> Vector2 {
         unsafe { ffi::GetWorldToScreen2D(position.into(), (*self).into()).into() }
     }
+
+    /// Get the world space area visible on a screen of the given size (accounts for offset, zoom and rotation)
+    ///
+    /// If the camera is rotated, this is the bounding rectangle of the visible area.
+    #[inline]
+    pub fn visible_rect(&self, screen_width: u32, screen_height: u32) -> Rectangle {
+        let (width, height) = (screen_width as f32, screen_height as f32);
+
+        let corners = [
+            self.screen_to_world(Vector2 { x: 0., y: 0. }),
+            self.screen_to_world(Vector2 { x: width, y: 0. }),
+            self.screen_to_world(Vector2 { x: 0., y: height }),
+            self.screen_to_world(Vector2 {
+                x: width,
+                y: height,
+            }),
+        ];
+
+        let (mut min, mut max) = (corners[0], corners[0]);
+
+        for corner in corners.iter().skip(1) {
+            min.x = min.x.min(corner.x);
+            min.y = min.y.min(corner.y);
+            max.x = max.x.max(corner.x);
+            max.y = max.y.max(corner.y);
+        }
+
+        Rectangle::new(min.x, min.y, max.x - min.x, max.y - min.y)
+    }
+
+    /// Center the camera on a world space rectangle and zoom so it fits entirely on a screen of the given size
+    ///
+    /// Rotation is kept as is and accounted for.
+    #[inline]
+    pub fn fit(&mut self, rect: Rectangle, screen_width: u32, screen_height: u32) {
+        let (sin, cos) = self.rotation.to_radians().sin_cos();
+
+        // size of the rectangle as seen by the rotated camera
+        let width = rect.width * cos.abs() + rect.height * sin.abs();
+        let height = rect.width * sin.abs() + rect.height * cos.abs();
+
+        self.target = Vector2 {
+            x: rect.x + rect.width / 2.,
+            y: rect.y + rect.height / 2.,
+        };
+        self.offset = Vector2 {
+            x: screen_width as f32 / 2.,
+            y: screen_height as f32 / 2.,
+        };
+
+        if width > 0. && height > 0. {
+            self.zoom = (screen_width as f32 / width).min(screen_height as f32 / height);
+        }
+    }
 }
 
 impl From<Camera2D> for ffi::Camera2D {
@@ -356,6 +579,94 @@ impl Camera3D {
         unsafe { ffi::GetMouseRay(mouse_position.into(), (*self).into()).into() }
     }
 
+    /// Get a ray trace from a screen position, for a 3D view drawn to the `viewport` area
+    /// (of the window or a render texture) instead of the whole window
+    #[inline]
+    pub fn screen_point_to_ray(&self, point: Vector2, viewport: Rectangle) -> Ray {
+        let x = 2. * (point.x - viewport.x) / viewport.width - 1.;
+        let y = 1. - 2. * (point.y - viewport.y) / viewport.height;
+
+        let inv_view_proj = self.inverse_view_projection(viewport.width / viewport.height);
+
+        let near = unproject_ndc(&inv_view_proj, [x, y, 0.]);
+        let far = unproject_ndc(&inv_view_proj, [x, y, 1.]);
+
+        let position = if self.projection == CameraProjection::Perspective {
+            self.position
+        } else {
+            unproject_ndc(&inv_view_proj, [x, y, -1.])
+        };
+
+        let direction = Vector3 {
+            x: far.x - near.x,
+            y: far.y - near.y,
+            z: far.z - near.z,
+        };
+        let length =
+            (direction.x * direction.x + direction.y * direction.y + direction.z * direction.z)
+                .sqrt();
+
+        Ray {
+            position,
+            direction: if length > 0. {
+                Vector3 {
+                    x: direction.x / length,
+                    y: direction.y / length,
+                    z: direction.z / length,
+                }
+            } else {
+                direction
+            },
+        }
+    }
+
+    /// Get the world space position under a screen position, `depth` units away from the camera
+    ///
+    /// Like [`Self::get_mouse_ray`], assumes the 3D view covers the whole window.
+    #[inline]
+    pub fn unproject(&self, screen: Vector2, depth: f32) -> Vector3 {
+        self.get_mouse_ray(screen).point_at(depth)
+    }
+
+    /// Get camera projection matrix, same as raylib uses for the given aspect ratio
+    #[inline]
+    pub fn get_projection_matrix(&self, aspect: f32) -> Matrix {
+        // RL_CULL_DISTANCE_NEAR and RL_CULL_DISTANCE_FAR
+        let (near, far) = (0.01, 1000.);
+
+        // columns
+        let m = if self.projection == CameraProjection::Perspective {
+            let f = 1. / (self.fovy.to_radians() / 2.).tan();
+
+            [
+                [f / aspect, 0., 0., 0.],
+                [0., f, 0., 0.],
+                [0., 0., (far + near) / (near - far), -1.],
+                [0., 0., 2. * far * near / (near - far), 0.],
+            ]
+        } else {
+            let top = self.fovy / 2.;
+            let right = top * aspect;
+
+            [
+                [1. / right, 0., 0., 0.],
+                [0., 1. / top, 0., 0.],
+                [0., 0., -2. / (far - near), 0.],
+                [0., 0., -(far + near) / (far - near), 1.],
+            ]
+        };
+
+        from_columns(&m)
+    }
+
+    #[inline]
+    fn inverse_view_projection(&self, aspect: f32) -> [[f32; 4]; 4] {
+        let view = columns(&self.get_matrix());
+        let proj = columns(&self.get_projection_matrix(aspect));
+
+        invert(&multiply(&proj, &view))
+    }
+
     /// Get camera transform matrix (view matrix)
     #[inline]
     pub fn get_matrix(&self) -> Matrix {
@@ -408,3 +719,485 @@ impl From<ffi::Camera3D> for Camera3D {
 
 /// Camera type fallback, defaults to Camera3D
 pub type Camera = Camera3D;
+
+/// Write `(a, b, ...)`, forwarding the formatter options to every component
+fn fmt_components(f: &mut fmt::Formatter<'_>, components: &[f32]) -> fmt::Result {
+    f.write_str("(")?;
+
+    for (i, component) in components.iter().enumerate() {
+        if i > 0 {
+            f.write_str(", ")?;
+        }
+
+        fmt::Display::fmt(component, f)?;
+    }
+
+    f.write_str(")")
+}
+
+#[inline]
+fn fmt_vector3(f: &mut fmt::Formatter<'_>, v: Vector3) -> fmt::Result {
+    fmt_components(f, &[v.x, v.y, v.z])
+}
+
+// column major 4x4 matrix helpers (raymath isn't part of the bindings)
+
+/// Columns of a matrix, raylib stores the matrix row by row (`m0, m4, m8, m12, m1, ...`)
+pub(crate) fn columns(m: &Matrix) -> [[f32; 4]; 4] {
+    let m = ffi::Matrix::from(*m);
+
+    [
+        [m.m0, m.m1, m.m2, m.m3],
+        [m.m4, m.m5, m.m6, m.m7],
+        [m.m8, m.m9, m.m10, m.m11],
+        [m.m12, m.m13, m.m14, m.m15],
+    ]
+}
+
+/// Matrix in raylib's layout from its columns
+pub(crate) fn from_columns(c: &[[f32; 4]; 4]) -> Matrix {
+    ffi::Matrix {
+        m0: c[0][0],
+        m1: c[0][1],
+        m2: c[0][2],
+        m3: c[0][3],
+        m4: c[1][0],
+        m5: c[1][1],
+        m6: c[1][2],
+        m7: c[1][3],
+        m8: c[2][0],
+        m9: c[2][1],
+        m10: c[2][2],
+        m11: c[2][3],
+        m12: c[3][0],
+        m13: c[3][1],
+        m14: c[3][2],
+        m15: c[3][3],
+    }
+    .into()
+}
+
+pub(crate) fn multiply(a: &[[f32; 4]; 4], b: &[[f32; 4]; 4]) -> [[f32; 4]; 4] {
+    let mut out = [[0.; 4]; 4];
+
+    for (c, column) in out.iter_mut().enumerate() {
+        for (r, value) in column.iter_mut().enumerate() {
+            *value = (0..4).map(|k| a[k][r] * b[c][k]).sum();
+        }
+    }
+
+    out
+}
+
+pub(crate) fn invert(m: &[[f32; 4]; 4]) -> [[f32; 4]; 4] {
+    // gauss-jordan elimination on rows, the inverse of the transpose is the transpose of the inverse
+    let mut a = *m;
+    let mut inv = [
+        [1., 0., 0., 0.],
+        [0., 1., 0., 0.],
+        [0., 0., 1., 0.],
+        [0., 0., 0., 1.],
+    ];
+
+    for i in 0..4 {
+        let pivot = (i..4)
+            .max_by(|&x, &y| a[x][i].abs().total_cmp(&a[y][i].abs()))
+            .unwrap();
+
+        if a[pivot][i] == 0. {
+            // singular
+            return [[0.; 4]; 4];
+        }
+
+        a.swap(i, pivot);
+        inv.swap(i, pivot);
+
+        let d = a[i][i];
+        a[i].iter_mut().for_each(|x| *x /= d);
+        inv[i].iter_mut().for_each(|x| *x /= d);
+
+        let (row, inv_row) = (a[i], inv[i]);
+
+        for j in (0..4).filter(|&j| j != i) {
+            let f = a[j][i];
+
+            for (x, y) in a[j].iter_mut().zip(row) {
+                *x -= f * y;
+            }
+            for (x, y) in inv[j].iter_mut().zip(inv_row) {
+                *x -= f * y;
+            }
+        }
+    }
+
+    inv
+}
+
+/// Projection of points along the light direction onto a plane, `None` if the light is parallel to the plane
+pub(crate) fn planar_shadow(
+    light_dir: Vector3,
+    plane_point: Vector3,
+    plane_normal: Vector3,
+) -> Option<[[f32; 4]; 4]> {
+    // towards the light, a direction (w = 0)
+    let l = vec3::normalize(vec3::scale(light_dir, -1.));
+    let n = vec3::normalize(plane_normal);
+    let plane = [n.x, n.y, n.z, -vec3::dot(n, plane_point)];
+    let light = [l.x, l.y, l.z, 0.];
+
+    let dot = vec3::dot(n, l);
+
+    if dot.abs() < 1e-6 {
+        return None;
+    }
+
+    let mut m = [[0.; 4]; 4];
+
+    for (c, column) in m.iter_mut().enumerate() {
+        for (r, value) in column.iter_mut().enumerate() {
+            *value = if r == c { dot } else { 0. } - light[r] * plane[c];
+        }
+    }
+
+    Some(m)
+}
+
+pub(crate) fn transform_point(m: &[[f32; 4]; 4], point: [f32; 3]) -> Vector3 {
+    let v = [point[0], point[1], point[2], 1.];
+    let mut out = [0.; 3];
+
+    for (r, value) in out.iter_mut().enumerate() {
+        *value = (0..4).map(|c| m[c][r] * v[c]).sum();
+    }
+
+    out.into()
+}
+
+fn unproject_ndc(inv_view_proj: &[[f32; 4]; 4], point: [f32; 3]) -> Vector3 {
+    let v = [point[0], point[1], point[2], 1.];
+    let mut out = [0.; 4];
+
+    for (r, value) in out.iter_mut().enumerate() {
+        *value = (0..4).map(|c| inv_view_proj[c][r] * v[c]).sum();
+    }
+
+    Vector3 {
+        x: out[0] / out[3],
+        y: out[1] / out[3],
+        z: out[2] / out[3],
+    }
+}
+
+/// Vector3 arithmetic (mint types don't implement any)
+pub(crate) mod vec3 {
+    use super::Vector3;
+
+    #[inline]
+    pub fn new(x: f32, y: f32, z: f32) -> Vector3 {
+        Vector3 { x, y, z }
+    }
+
+    #[inline]
+    pub fn add(a: Vector3, b: Vector3) -> Vector3 {
+        new(a.x + b.x, a.y + b.y, a.z + b.z)
+    }
+
+    #[inline]
+    pub fn sub(a: Vector3, b: Vector3) -> Vector3 {
+        new(a.x - b.x, a.y - b.y, a.z - b.z)
+    }
+
+    #[inline]
+    pub fn scale(v: Vector3, s: f32) -> Vector3 {
+        new(v.x * s, v.y * s, v.z * s)
+    }
+
+    #[inline]
+    pub fn dot(a: Vector3, b: Vector3) -> f32 {
+        a.x * b.x + a.y * b.y + a.z * b.z
+    }
+
+    #[inline]
+    pub fn cross(a: Vector3, b: Vector3) -> Vector3 {
+        new(
+            a.y * b.z - a.z * b.y,
+            a.z * b.x - a.x * b.z,
+            a.x * b.y - a.y * b.x,
+        )
+    }
+
+    #[inline]
+    pub fn length(v: Vector3) -> f32 {
+        dot(v, v).sqrt()
+    }
+
+    /// Zero vectors stay zero
+    #[inline]
+    pub fn normalize(v: Vector3) -> Vector3 {
+        let len = length(v);
+
+        if len > 0. {
+            scale(v, 1. / len)
+        } else {
+            v
+        }
+    }
+
+    #[inline]
+    pub fn lerp(a: Vector3, b: Vector3, t: f32) -> Vector3 {
+        add(a, scale(sub(b, a), t))
+    }
+}
+
+/// `approx` comparisons, componentwise over the float fields
+#[cfg(feature = "approx")]
+mod approx_impls {
+    use super::{BoundingBox, Camera2D, Ray, Rectangle, Transform};
+    use approx::{AbsDiffEq, RelativeEq, UlpsEq};
+
+    macro_rules! impl_approx {
+        ($ty:ty, |$v:ident| $components:expr) => {
+            impl AbsDiffEq for $ty {
+                type Epsilon = f32;
+
+                #[inline]
+                fn default_epsilon() -> f32 {
+                    f32::default_epsilon()
+                }
+
+                #[inline]
+                fn abs_diff_eq(&self, other: &Self, epsilon: f32) -> bool {
+                    let components = |$v: &Self| $components;
+
+                    components(self)
+                        .iter()
+                        .zip(components(other).iter())
+                        .all(|(a, b)| a.abs_diff_eq(b, epsilon))
+                }
+            }
+
+            impl RelativeEq for $ty {
+                #[inline]
+                fn default_max_relative() -> f32 {
+                    f32::default_max_relative()
+                }
+
+                #[inline]
+                fn relative_eq(&self, other: &Self, epsilon: f32, max_relative: f32) -> bool {
+                    let components = |$v: &Self| $components;
+
+                    components(self)
+                        .iter()
+                        .zip(components(other).iter())
+                        .all(|(a, b)| a.relative_eq(b, epsilon, max_relative))
+                }
+            }
+
+            impl UlpsEq for $ty {
+                #[inline]
+                fn default_max_ulps() -> u32 {
+                    f32::default_max_ulps()
+                }
+
+                #[inline]
+                fn ulps_eq(&self, other: &Self, epsilon: f32, max_ulps: u32) -> bool {
+                    let components = |$v: &Self| $components;
+
+                    components(self)
+                        .iter()
+                        .zip(components(other).iter())
+                        .all(|(a, b)| a.ulps_eq(b, epsilon, max_ulps))
+                }
+            }
+        };
+    }
+
+    impl_approx!(Rectangle, |r| [r.x, r.y, r.width, r.height]);
+    impl_approx!(Ray, |r| [
+        r.position.x,
+        r.position.y,
+        r.position.z,
+        r.direction.x,
+        r.direction.y,
+        r.direction.z,
+    ]);
+    impl_approx!(BoundingBox, |b| [
+        b.min.x, b.min.y, b.min.z, b.max.x, b.max.y, b.max.z,
+    ]);
+    impl_approx!(Transform, |t| [
+        t.translation.x,
+        t.translation.y,
+        t.translation.z,
+        t.rotation.v.x,
+        t.rotation.v.y,
+        t.rotation.v.z,
+        t.rotation.s,
+        t.scale.x,
+        t.scale.y,
+        t.scale.z,
+    ]);
+    impl_approx!(Camera2D, |c| [
+        c.offset.x, c.offset.y, c.target.x, c.target.y, c.rotation, c.zoom,
+    ]);
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub(crate) fn assert_near(a: Vector3, b: Vector3) {
+        let d = vec3::sub(a, b);
+
+        assert!(vec3::dot(d, d).sqrt() < 1e-3, "{a:?} != {b:?}");
+    }
+
+    fn camera() -> Camera3D {
+        Camera3D {
+            position: vec3::new(3., 4., 10.),
+            target: vec3::new(1., 0., 0.),
+            up: vec3::new(0., 1., 0.),
+            fovy: 45.,
+            projection: CameraProjection::Perspective,
+        }
+    }
+
+    #[test]
+    fn columns_round_trip() {
+        let m = [
+            [1., 2., 3., 4.],
+            [5., 6., 7., 8.],
+            [9., 10., 11., 12.],
+            [13., 14., 15., 16.],
+        ];
+        let raw = ffi::Matrix::from(from_columns(&m));
+
+        // translation is the last column, m12..m14 in raylib
+        assert_eq!((raw.m12, raw.m13, raw.m14, raw.m15), (13., 14., 15., 16.));
+        assert_eq!(columns(&from_columns(&m)), m);
+    }
+
+    /// 90 degrees around Z, then moved by (5, 6, 7), like `MatrixMultiply(MatrixRotateZ(PI / 2), MatrixTranslate(5, 6, 7))`
+    pub(crate) fn rotate_translate() -> Matrix {
+        from_columns(&[
+            [0., 1., 0., 0.],
+            [-1., 0., 0., 0.],
+            [0., 0., 1., 0.],
+            [5., 6., 7., 1.],
+        ])
+    }
+
+    #[test]
+    fn transform_point_translates() {
+        // MatrixTranslate(5, 6, 7)
+        let translate = ffi::Matrix {
+            m0: 1.,
+            m4: 0.,
+            m8: 0.,
+            m12: 5.,
+            m1: 0.,
+            m5: 1.,
+            m9: 0.,
+            m13: 6.,
+            m2: 0.,
+            m6: 0.,
+            m10: 1.,
+            m14: 7.,
+            m3: 0.,
+            m7: 0.,
+            m11: 0.,
+            m15: 1.,
+        };
+
+        assert_near(
+            transform_point(&columns(&translate.into()), [1., 2., 3.]),
+            vec3::new(6., 8., 10.),
+        );
+        assert_near(
+            transform_point(&columns(&rotate_translate()), [1., 2., 3.]),
+            vec3::new(3., 7., 10.),
+        );
+    }
+
+    #[test]
+    fn bounding_box_transform() {
+        let bbox = BoundingBox::new(vec3::new(0., 0., 0.), vec3::new(1., 1., 1.));
+        let transformed = bbox.transform(&rotate_translate());
+
+        assert_near(transformed.min, vec3::new(4., 6., 7.));
+        assert_near(transformed.max, vec3::new(5., 7., 8.));
+    }
+
+    #[test]
+    fn planar_shadow_after_model_transform() {
+        // light straight down onto y = 0, slanted light onto y = 1
+        let shadow = planar_shadow(
+            vec3::new(0., -1., 0.),
+            vec3::new(0., 0., 0.),
+            vec3::new(0., 1., 0.),
+        )
+        .unwrap();
+        let m = multiply(&shadow, &columns(&rotate_translate()));
+
+        assert_near(transform_point(&m, [1., 2., 3.]), vec3::new(3., 0., 10.));
+
+        let shadow = planar_shadow(
+            vec3::new(1., -1., 0.),
+            vec3::new(0., 1., 0.),
+            vec3::new(0., 1., 0.),
+        )
+        .unwrap();
+        let projected = transform_point(&shadow, [0., 3., 0.]);
+
+        // transform_point drops w, the projection has w = dot(normal, towards light)
+        let w = std::f32::consts::FRAC_1_SQRT_2;
+        assert_near(vec3::scale(projected, 1. / w), vec3::new(2., 1., 0.));
+    }
+
+    #[test]
+    fn projection_matrix_layout() {
+        let proj = ffi::Matrix::from(camera().get_projection_matrix(16. / 9.));
+
+        assert_eq!(proj.m11, -1.);
+        assert!((proj.m10 + 1.).abs() < 1e-3);
+        assert!((proj.m14 + 0.02).abs() < 1e-4);
+        assert_eq!((proj.m3, proj.m7, proj.m15), (0., 0., 0.));
+    }
+
+    #[test]
+    fn screen_point_to_ray_center() {
+        let camera = camera();
+        let viewport = Rectangle::new(0., 0., 800., 450.);
+        let ray = camera.screen_point_to_ray(Vector2 { x: 400., y: 225. }, viewport);
+
+        assert_near(ray.position, camera.position);
+        assert_near(
+            ray.direction,
+            vec3::normalize(vec3::sub(camera.target, camera.position)),
+        );
+        assert_near(ray.direction, vec3::new(-0.1826, -0.3651, -0.9129));
+    }
+
+    #[test]
+    #[ignore = "opens a window"]
+    fn screen_point_to_ray_matches_get_mouse_ray() {
+        let rl = crate::core::Raylib::init_window(800, 450, "screen_point_to_ray").unwrap();
+        let camera = camera();
+        let viewport = Rectangle::new(
+            0.,
+            0.,
+            rl.get_screen_width() as f32,
+            rl.get_screen_height() as f32,
+        );
+
+        for (x, y) in [(400., 225.), (0., 0.), (800., 450.), (123., 400.)] {
+            let point = Vector2 { x, y };
+            let expected = camera.get_mouse_ray(point);
+            let ray = camera.screen_point_to_ray(point, viewport);
+
+            assert_near(ray.position, expected.position);
+            assert_near(ray.direction, expected.direction);
+        }
+
+        rl.close_window();
+    }
+}
@@ -1,17 +1,79 @@
-use std::{ffi::CString, mem::ManuallyDrop};
+use std::{
+    cell::OnceCell, collections::HashMap, ffi::CString, fmt, mem::ManuallyDrop, sync::Mutex,
+};
 
 use static_assertions::{assert_eq_align, assert_eq_size};
 
 use crate::{
     color::Color,
+    core::{release_context_object, track_context_object, untrack_context_object, ContextObject},
+    drawing::gl,
+    error::RaylibError,
     ffi,
-    math::{BoundingBox, Matrix, Transform, Vector2, Vector3, Vector4},
-    shader::Shader,
+    math::{
+        vec3, BoundingBox, Camera3D, CameraProjection, Matrix, Quaternion, Transform, Vector2,
+        Vector3, Vector4,
+    },
+    shader::{Shader, ShaderLoc},
     texture::{Image, Texture2D},
 };
 
 pub use crate::ffi::MaterialMapIndex;
 
+/// Texture slot of a material, e.g. `MaterialSlot::Albedo`
+pub type MaterialSlot = MaterialMapIndex;
+
+/// Extra vertex buffers added with [`Mesh::set_attribute`]: vao id, attribute location, vbo id
+static EXTRA_BUFFERS: Mutex<Vec<(u32, u32, u32)>> = Mutex::new(Vec::new());
+
+/// Unload the extra vertex buffers of a vao, before the mesh itself is unloaded
+fn unload_extra_buffers(vao: u32) {
+    if vao == 0 {
+        return;
+    }
+
+    let mut buffers = EXTRA_BUFFERS.lock().unwrap_or_else(|e| e.into_inner());
+
+    buffers.retain(|&(buffer_vao, _, vbo)| {
+        if buffer_vao == vao {
+            unsafe { gl::rlUnloadVertexBuffer(vbo) }
+        }
+
+        buffer_vao != vao
+    });
+}
+
+/// Forget the extra vertex buffers when the window closes, their ids belong to the closed context
+pub(crate) fn forget_extra_buffers() {
+    EXTRA_BUFFERS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clear();
+}
+
+/// Per-vertex data of custom mesh attributes
+/// You shouldn't need to implement this trait yourself.
+pub trait VertexAttribute: Copy {
+    /// Float components per vertex
+    const COMPONENTS: i32;
+}
+
+impl VertexAttribute for f32 {
+    const COMPONENTS: i32 = 1;
+}
+
+impl VertexAttribute for Vector2 {
+    const COMPONENTS: i32 = 2;
+}
+
+impl VertexAttribute for Vector3 {
+    const COMPONENTS: i32 = 3;
+}
+
+impl VertexAttribute for Vector4 {
+    const COMPONENTS: i32 = 4;
+}
+
 /// Mesh, vertex data and vao/vbo
 #[derive(Debug)]
 #[repr(transparent)]
@@ -132,10 +194,50 @@ impl Mesh {
         }
     }
 
+    /// Vertex positions after the last skinning (animated vertex positions), `None` without bones
+    #[inline]
+    pub fn skinned_vertices(&self) -> Option<&[Vector3]> {
+        if self.raw.animVertices.is_null() {
+            None
+        } else {
+            Some(unsafe {
+                std::slice::from_raw_parts(
+                    self.raw.animVertices as *const _,
+                    self.raw.vertexCount as _,
+                )
+            })
+        }
+    }
+
+    /// Normals after the last skinning (animated normals), `None` without bones
+    #[inline]
+    pub fn skinned_normals(&self) -> Option<&[Vector3]> {
+        if self.raw.animNormals.is_null() {
+            None
+        } else {
+            Some(unsafe {
+                std::slice::from_raw_parts(
+                    self.raw.animNormals as *const _,
+                    self.raw.vertexCount as _,
+                )
+            })
+        }
+    }
+
     /// Upload mesh vertex data in GPU and provide VAO/VBO ids
     #[inline]
     pub fn upload(&mut self, dynamic: bool) {
+        let buffers = self.raw.vboId;
+
         unsafe { ffi::UploadMesh(&mut self.raw as *mut _, dynamic) }
+
+        if self.raw.vboId != buffers {
+            if !buffers.is_null() {
+                untrack_context_object(ContextObject::Alloc(buffers as usize));
+            }
+
+            track_context_object(ContextObject::Alloc(self.raw.vboId as usize));
+        }
     }
 
     /// Update mesh vertex data in GPU for a specific buffer index
@@ -152,10 +254,138 @@ impl Mesh {
         }
     }
 
+    /// Attach per-vertex data to a named `in` attribute of a shader, `false` on failure
+    ///
+    /// The data goes into an extra vertex buffer owned by the mesh, setting the same attribute
+    /// again replaces it. The mesh has to be uploaded and `data` needs one value per vertex.
+    /// The buffer is bound to the attribute location in the mesh's VAO, so other shaders drawing
+    /// the mesh have to use the same location (e.g. with `layout(location = 8)`).
+    /// Not available on OpenGL ES 2.
+    pub fn set_attribute<A: VertexAttribute>(
+        &mut self,
+        shader: &Shader,
+        name: &str,
+        data: &[A],
+    ) -> bool {
+        match shader.get_location_attribute(name) {
+            Some(loc) => self.set_attribute_at(loc, data),
+            None => false,
+        }
+    }
+
+    /// Attach per-vertex data to an attribute location, see [`Mesh::set_attribute`]
+    pub fn set_attribute_at<A: VertexAttribute>(&mut self, loc: ShaderLoc, data: &[A]) -> bool {
+        let vao = self.raw.vaoId;
+
+        if vao == 0 || data.len() != self.raw.vertexCount as usize {
+            #[cfg(debug_assertions)]
+            crate::core::warn(
+                "Mesh::set_attribute: the mesh must be uploaded and have one value per vertex",
+            );
+
+            return false;
+        }
+
+        let location = loc.index();
+        let size = std::mem::size_of_val(data) as i32;
+
+        self.remove_attribute(loc);
+
+        unsafe {
+            if !gl::rlEnableVertexArray(vao) {
+                return false;
+            }
+
+            let vbo = gl::rlLoadVertexBuffer(data.as_ptr() as *const _, size, false);
+            gl::rlSetVertexAttribute(
+                location,
+                A::COMPONENTS,
+                gl::FLOAT,
+                false,
+                0,
+                std::ptr::null(),
+            );
+            gl::rlEnableVertexAttribute(location);
+            gl::rlDisableVertexArray();
+
+            EXTRA_BUFFERS
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .push((vao, location, vbo));
+        }
+
+        true
+    }
+
+    /// Update the data of an attribute added with [`Mesh::set_attribute`], starting at a vertex
+    ///
+    /// `false` if there's no such attribute or the data goes past the last vertex.
+    pub fn update_attribute<A: VertexAttribute>(
+        &mut self,
+        loc: ShaderLoc,
+        data: &[A],
+        first_vertex: usize,
+    ) -> bool {
+        if first_vertex + data.len() > self.raw.vertexCount as usize {
+            return false;
+        }
+
+        let buffers = EXTRA_BUFFERS.lock().unwrap_or_else(|e| e.into_inner());
+
+        let vbo = match buffers
+            .iter()
+            .find(|&&(vao, location, _)| vao == self.raw.vaoId && location == loc.index())
+        {
+            Some(&(_, _, vbo)) => vbo,
+            None => return false,
+        };
+
+        unsafe {
+            gl::rlUpdateVertexBuffer(
+                vbo,
+                data.as_ptr() as *const _,
+                std::mem::size_of_val(data) as _,
+                (first_vertex * std::mem::size_of::<A>()) as _,
+            )
+        }
+
+        true
+    }
+
+    /// Remove an attribute added with [`Mesh::set_attribute`], `false` if there's none
+    pub fn remove_attribute(&mut self, loc: ShaderLoc) -> bool {
+        let vao = self.raw.vaoId;
+        let mut buffers = EXTRA_BUFFERS.lock().unwrap_or_else(|e| e.into_inner());
+
+        let index = match buffers
+            .iter()
+            .position(|&(buffer_vao, location, _)| buffer_vao == vao && location == loc.index())
+        {
+            Some(index) => index,
+            None => return false,
+        };
+
+        let (_, location, vbo) = buffers.swap_remove(index);
+
+        unsafe {
+            if gl::rlEnableVertexArray(vao) {
+                gl::rlDisableVertexAttribute(location);
+                gl::rlDisableVertexArray();
+            }
+
+            gl::rlUnloadVertexBuffer(vbo);
+        }
+
+        true
+    }
+
     /// Export mesh data to file, returns true on success
     #[inline]
     pub fn export(&self, file_name: &str) -> bool {
-        let file_name = CString::new(file_name).unwrap();
+        let file_name = match CString::new(file_name) {
+            Ok(file_name) => file_name,
+            Err(_) => return false,
+        };
 
         unsafe { ffi::ExportMesh(self.raw.clone(), file_name.as_ptr()) }
     }
@@ -172,123 +402,1330 @@ impl Mesh {
         unsafe { ffi::GenMeshTangents(&mut self.raw as *mut _) }
     }
 
-    /// Generate polygonal mesh
-    #[inline]
-    pub fn generate_polygon(sides: u32, radius: f32) -> Self {
-        Self {
-            raw: unsafe { ffi::GenMeshPoly(sides as _, radius) },
+    /// Generate polygonal mesh
+    #[inline]
+    pub fn generate_polygon(sides: u32, radius: f32) -> Self {
+        Self::tracked(unsafe { ffi::GenMeshPoly(sides as _, radius) })
+    }
+
+    /// Generate plane mesh (with subdivisions)
+    #[inline]
+    pub fn generate_plane(width: f32, length: f32, res_x: u32, res_z: u32) -> Self {
+        Self::tracked(unsafe { ffi::GenMeshPlane(width, length, res_x as _, res_z as _) })
+    }
+
+    /// Generate cuboid mesh
+    #[inline]
+    pub fn generate_cube(width: f32, height: f32, length: f32) -> Self {
+        Self::tracked(unsafe { ffi::GenMeshCube(width, height, length) })
+    }
+
+    /// Generate sphere mesh (standard sphere)
+    #[inline]
+    pub fn generate_sphere(radius: f32, rings: u32, slices: u32) -> Self {
+        Self::tracked(unsafe { ffi::GenMeshSphere(radius, rings as _, slices as _) })
+    }
+
+    /// Generate half-sphere mesh (no bottom cap)
+    #[inline]
+    pub fn generate_hemisphere(radius: f32, rings: u32, slices: u32) -> Self {
+        Self::tracked(unsafe { ffi::GenMeshHemiSphere(radius, rings as _, slices as _) })
+    }
+
+    /// Generate cylinder mesh
+    #[inline]
+    pub fn generate_cylinder(radius: f32, height: f32, slices: u32) -> Self {
+        Self::tracked(unsafe { ffi::GenMeshCylinder(radius, height, slices as _) })
+    }
+
+    /// Generate cone/pyramid mesh
+    #[inline]
+    pub fn generate_cone(radius: f32, height: f32, slices: u32) -> Self {
+        Self::tracked(unsafe { ffi::GenMeshCone(radius, height, slices as _) })
+    }
+
+    /// Generate torus mesh
+    #[inline]
+    pub fn generate_torus(radius: f32, size: f32, rad_seg: u32, sides: u32) -> Self {
+        Self::tracked(unsafe { ffi::GenMeshTorus(radius, size, rad_seg as _, sides as _) })
+    }
+
+    /// Generate trefoil knot mesh
+    #[inline]
+    pub fn generate_knot(radius: f32, size: f32, rad_seg: u32, sides: u32) -> Self {
+        Self::tracked(unsafe { ffi::GenMeshKnot(radius, size, rad_seg as _, sides as _) })
+    }
+
+    /// Generate heightmap mesh from image data
+    #[inline]
+    pub fn generate_heightmap(heightmap: &Image, size: Vector3) -> Self {
+        Self::tracked(unsafe { ffi::GenMeshHeightmap(heightmap.raw.clone(), size.into()) })
+    }
+
+    /// Generate cubes-based map mesh from image data
+    #[inline]
+    pub fn generate_cubicmap(cubicmap: &Image, cube_size: Vector3) -> Self {
+        Self::tracked(unsafe { ffi::GenMeshCubicmap(cubicmap.raw.clone(), cube_size.into()) })
+    }
+
+    /// Generate a cuboid mesh with rounded edges and corners, made of `segments` steps each
+    ///
+    /// The radius is limited to half the smallest side.
+    pub fn generate_rounded_cube(
+        width: f32,
+        height: f32,
+        length: f32,
+        radius: f32,
+        segments: u32,
+    ) -> Self {
+        let half = vec3::new(width.abs() / 2., height.abs() / 2., length.abs() / 2.);
+        let radius = radius.clamp(0., half.x.min(half.y).min(half.z));
+        let inner = vec3::new(half.x - radius, half.y - radius, half.z - radius);
+
+        // extent of the box along a (positive or negative) unit axis
+        let extent =
+            |axis: Vector3| axis.x.abs() * half.x + axis.y.abs() * half.y + axis.z.abs() * half.z;
+        // positions across a face, evenly spread over each rounded part
+        let samples = |extent: f32| {
+            let mut samples = vec![-extent];
+
+            if radius > 0. && segments > 0 {
+                for step in 1..=segments {
+                    samples.push(-extent + radius * step as f32 / segments as f32);
+                }
+                for step in 0..segments {
+                    samples.push(extent - radius + radius * step as f32 / segments as f32);
+                }
+            }
+
+            samples.push(extent);
+            samples.dedup();
+            samples
+        };
+
+        // normal, then the face's right and up, counter-clockwise seen from the outside
+        let faces = [
+            [[1., 0., 0.], [0., 0., -1.], [0., 1., 0.]],
+            [[-1., 0., 0.], [0., 0., 1.], [0., 1., 0.]],
+            [[0., 1., 0.], [1., 0., 0.], [0., 0., -1.]],
+            [[0., -1., 0.], [1., 0., 0.], [0., 0., 1.]],
+            [[0., 0., 1.], [1., 0., 0.], [0., 1., 0.]],
+            [[0., 0., -1.], [-1., 0., 0.], [0., 1., 0.]],
+        ];
+
+        let mut data = MeshData::default();
+
+        for face in faces {
+            let [normal, right, up] = face.map(Vector3::from);
+            let (extent_u, extent_v) = (extent(right), extent(up));
+            let (samples_u, samples_v) = (samples(extent_u), samples(extent_v));
+            let center = vec3::scale(normal, extent(normal));
+            let first = data.vertices.len() as u32;
+
+            for &v in samples_v.iter() {
+                for &u in samples_u.iter() {
+                    let point =
+                        vec3::add(center, vec3::add(vec3::scale(right, u), vec3::scale(up, v)));
+                    let clamped = vec3::new(
+                        point.x.clamp(-inner.x, inner.x),
+                        point.y.clamp(-inner.y, inner.y),
+                        point.z.clamp(-inner.z, inner.z),
+                    );
+                    let direction = vec3::sub(point, clamped);
+
+                    let vertex_normal = if radius > 0. {
+                        vec3::normalize(direction)
+                    } else {
+                        normal
+                    };
+
+                    data.push_vertex(
+                        vec3::add(clamped, vec3::scale(vertex_normal, radius)),
+                        vertex_normal,
+                        Vector2 {
+                            x: (u + extent_u) / (2. * extent_u).max(f32::EPSILON),
+                            y: 1. - (v + extent_v) / (2. * extent_v).max(f32::EPSILON),
+                        },
+                    );
+                }
+            }
+
+            let columns = samples_u.len() as u32;
+
+            for row in 0..samples_v.len() as u32 - 1 {
+                for column in 0..columns - 1 {
+                    let a = first + row * columns + column;
+                    let (b, c, d) = (a + 1, a + columns + 1, a + columns);
+
+                    data.indices.extend([a, b, c, a, c, d]);
+                }
+            }
+        }
+
+        data.into_mesh()
+    }
+
+    /// Generate a capsule mesh along the Y axis, `height` includes both caps
+    ///
+    /// Each cap has `rings` rings, the height is at least twice the radius.
+    pub fn generate_capsule(radius: f32, height: f32, rings: u32, slices: u32) -> Self {
+        let rings = rings.max(1);
+        let radius = radius.max(0.);
+        let half = (height / 2. - radius).max(0.);
+
+        let mut profile = Vec::new();
+
+        // top pole to the equator, then the bottom equator to its pole
+        for ring in 0..=rings {
+            let (sin, cos) = match ring {
+                0 => (1., 0.),
+                _ => (std::f32::consts::FRAC_PI_2 * (1. - ring as f32 / rings as f32)).sin_cos(),
+            };
+
+            let normal = Vector2 { x: cos, y: sin };
+            profile.push((radius * cos, half + radius * sin, normal));
+        }
+
+        for ring in 0..=rings {
+            // a sphere doesn't need the equator twice
+            if ring == 0 && half == 0. {
+                continue;
+            }
+
+            let (sin, cos) = match ring {
+                ring if ring == rings => (-1., 0.),
+                _ => (-std::f32::consts::FRAC_PI_2 * ring as f32 / rings as f32).sin_cos(),
+            };
+
+            let normal = Vector2 { x: cos, y: sin };
+            profile.push((radius * cos, -half + radius * sin, normal));
+        }
+
+        let mut data = MeshData::default();
+        data.add_lathe(&profile, slices);
+
+        data.into_mesh()
+    }
+
+    /// Generate a torus knot mesh, winding `p` times around its axis and `q` times through its
+    /// hole
+    ///
+    /// Texture coordinates go `uv_scale.x` times along the knot and `uv_scale.y` times around
+    /// the tube. [`Mesh::generate_knot`] is the (2, 3) knot without the choice.
+    pub fn generate_torus_knot(
+        radius: f32,
+        tube_radius: f32,
+        p: u32,
+        q: u32,
+        segments: u32,
+        sides: u32,
+        uv_scale: Vector2,
+    ) -> Self {
+        let (p, q) = (p.max(1) as f32, q.max(1) as f32);
+        let (segments, sides) = (segments.max(3), sides.max(3));
+
+        let knot_point = |t: f32| {
+            let angle = q / p * t;
+            let r = radius * (2. + angle.cos()) / 2.;
+
+            vec3::new(r * t.cos(), r * t.sin(), radius * angle.sin() / 2.)
+        };
+
+        let mut data = MeshData::default();
+
+        for segment in 0..=segments {
+            let u = segment as f32 / segments as f32;
+            let t = u * p * std::f32::consts::TAU;
+
+            let center = knot_point(t);
+            let next = knot_point(t + 0.01);
+
+            // frame around the curve
+            let tangent = vec3::sub(next, center);
+            let binormal = vec3::normalize(vec3::cross(tangent, vec3::add(next, center)));
+            let normal = vec3::normalize(vec3::cross(binormal, tangent));
+
+            for side in 0..=sides {
+                let v = side as f32 / sides as f32;
+                let (sin, cos) = (v * std::f32::consts::TAU).sin_cos();
+
+                let offset = vec3::add(
+                    vec3::scale(normal, -tube_radius * cos),
+                    vec3::scale(binormal, tube_radius * sin),
+                );
+
+                data.push_vertex(
+                    vec3::add(center, offset),
+                    vec3::normalize(offset),
+                    Vector2 {
+                        x: u * uv_scale.x,
+                        y: v * uv_scale.y,
+                    },
+                );
+            }
+        }
+
+        for segment in 1..=segments {
+            for side in 1..=sides {
+                let a = (sides + 1) * (segment - 1) + side - 1;
+                let b = (sides + 1) * segment + side - 1;
+                let c = (sides + 1) * segment + side;
+                let d = (sides + 1) * (segment - 1) + side;
+
+                data.indices.extend([a, b, d, b, c, d]);
+            }
+        }
+
+        data.into_mesh()
+    }
+
+    /// Generate an arrow mesh pointing up the Y axis from the origin, e.g. for gizmos
+    ///
+    /// The cone shaped head is part of the length.
+    pub fn generate_arrow(
+        length: f32,
+        shaft_radius: f32,
+        head_length: f32,
+        head_radius: f32,
+        slices: u32,
+    ) -> Self {
+        let length = length.max(0.);
+        let head_length = head_length.clamp(0., length);
+        let base = length - head_length;
+        let slant = head_length.hypot(head_radius).max(f32::EPSILON);
+
+        let side = Vector2 {
+            x: head_length / slant,
+            y: head_radius / slant,
+        };
+        let (out, down) = (Vector2 { x: 1., y: 0. }, Vector2 { x: 0., y: -1. });
+
+        let mut data = MeshData::default();
+
+        // separate strips keep the edges sharp
+        data.add_lathe(&[(0., length, side), (head_radius, base, side)], slices);
+        data.add_lathe(
+            &[(head_radius, base, down), (shaft_radius, base, down)],
+            slices,
+        );
+        data.add_lathe(
+            &[(shaft_radius, base, out), (shaft_radius, 0., out)],
+            slices,
+        );
+        data.add_lathe(&[(shaft_radius, 0., down), (0., 0., down)], slices);
+
+        data.into_mesh()
+    }
+
+    /// Generate a mesh of the volume a camera sees between two distances, for editor views
+    ///
+    /// Draw it in wire mode to get the usual outline.
+    pub fn generate_frustum(camera: &Camera3D, aspect: f32, near: f32, far: f32) -> Self {
+        let forward = vec3::normalize(vec3::sub(camera.target, camera.position));
+        let right = vec3::normalize(vec3::cross(forward, camera.up));
+        let up = vec3::cross(right, forward);
+
+        // bottom left, bottom right, top right, top left
+        let corners = |distance: f32| {
+            let half_height = match camera.projection {
+                CameraProjection::Perspective => (camera.fovy.to_radians() / 2.).tan() * distance,
+                CameraProjection::Orthographic => camera.fovy / 2.,
+            };
+            let center = vec3::add(camera.position, vec3::scale(forward, distance));
+            let (x, y) = (
+                vec3::scale(right, half_height * aspect),
+                vec3::scale(up, half_height),
+            );
+
+            [
+                vec3::sub(vec3::sub(center, x), y),
+                vec3::sub(vec3::add(center, x), y),
+                vec3::add(vec3::add(center, x), y),
+                vec3::add(vec3::sub(center, x), y),
+            ]
+        };
+
+        let [nbl, nbr, ntr, ntl] = corners(near);
+        let [fbl, fbr, ftr, ftl] = corners(far);
+
+        let mut data = MeshData::default();
+
+        data.add_quad([nbl, nbr, ntr, ntl]);
+        data.add_quad([fbr, fbl, ftl, ftr]);
+        data.add_quad([fbl, nbl, ntl, ftl]);
+        data.add_quad([nbr, fbr, ftr, ntr]);
+        data.add_quad([ntl, ntr, ftr, ftl]);
+        data.add_quad([fbl, fbr, nbr, nbl]);
+
+        data.into_mesh()
+    }
+
+    /// Merge meshes into one, with each mesh's transform baked into its vertices
+    ///
+    /// Texture coordinates, normals, tangents and colors are kept if any of the meshes has them,
+    /// the others get defaults (white for colors). Bone data is dropped. The result is indexed,
+    /// unless it has more vertices than 16-bit indices can address.
+    pub fn merge(meshes: &[(&Mesh, Matrix)]) -> Self {
+        let mut merged = MeshData::default();
+
+        for (mesh, transform) in meshes {
+            let mut data = MeshData::from_raw(&mesh.raw);
+            data.bone_ids = None;
+            data.bone_weights = None;
+            data.transform(transform);
+
+            merged.append(data);
+        }
+
+        merged.into_mesh()
+    }
+
+    /// Recalculate normals from the triangles, smoothed over the ones sharing a vertex
+    ///
+    /// Meshes split into separate vertices per face (like most OBJ files) stay faceted,
+    /// [`Mesh::weld_vertices`] them first for smooth shading.
+    /// The mesh is uploaded again, attributes set with [`Mesh::set_attribute`] are removed.
+    pub fn recalculate_normals(&mut self) {
+        self.rebuild(MeshData::recalculate_normals);
+    }
+
+    /// Merge vertices closer than `epsilon`, returns the number of vertices removed
+    ///
+    /// Only vertices with the same texture coordinates, colors and bone data are merged, keeping
+    /// the normal and tangent of the first one. Triangles that collapse are removed.
+    /// The mesh is uploaded again, attributes set with [`Mesh::set_attribute`] are removed.
+    pub fn weld_vertices(&mut self, epsilon: f32) -> usize {
+        let mut removed = 0;
+
+        self.rebuild(|data| removed = data.weld(epsilon));
+
+        removed
+    }
+
+    /// Reorder triangles and vertices so the GPU's vertex cache is hit more often
+    ///
+    /// Doesn't change what the mesh looks like, except that unused vertices are removed.
+    /// The mesh is uploaded again, attributes set with [`Mesh::set_attribute`] are removed.
+    pub fn optimize_for_cache(&mut self) {
+        self.rebuild(MeshData::optimize_for_cache);
+    }
+
+    /// Edit a copy of the vertex data and replace the mesh with it
+    fn rebuild(&mut self, edit: impl FnOnce(&mut MeshData)) {
+        let mut data = MeshData::from_raw(&self.raw);
+
+        edit(&mut data);
+
+        *self = data.into_mesh();
+    }
+
+    /// Get the 'raw' ffi type
+    /// Take caution when cloning so it doesn't outlive the original
+    #[inline]
+    pub fn as_raw(&self) -> &ffi::Mesh {
+        &self.raw
+    }
+
+    /// Get the 'raw' ffi type
+    /// Take caution when cloning so it doesn't outlive the original
+    #[inline]
+    pub fn as_raw_mut(&mut self) -> &mut ffi::Mesh {
+        &mut self.raw
+    }
+
+    /// Convert a 'raw' ffi object to a safe wrapper
+    ///
+    /// # Safety
+    /// * The raw object must be correctly initialized
+    /// * The raw object should be unique. Otherwise, make sure its clones don't outlive the newly created object.
+    #[inline]
+    pub unsafe fn from_raw(raw: ffi::Mesh) -> Self {
+        Self::tracked(raw)
+    }
+
+    /// Wrap a mesh of the current context, an uploaded mesh owns its buffers through `vboId`
+    #[inline]
+    fn tracked(raw: ffi::Mesh) -> Self {
+        if !raw.vboId.is_null() {
+            track_context_object(ContextObject::Alloc(raw.vboId as usize));
+        }
+
+        Self { raw }
+    }
+}
+
+impl Drop for Mesh {
+    #[inline]
+    fn drop(&mut self) {
+        if release_context_object(ContextObject::Alloc(self.raw.vboId as usize)) {
+            unload_extra_buffers(self.raw.vaoId);
+
+            unsafe { ffi::UnloadMesh(self.raw.clone()) }
+        } else {
+            unsafe { free_mesh_data(&self.raw) }
+        }
+    }
+}
+
+/// Free the CPU side of a mesh whose GPU side went with its context, like `UnloadMesh` does
+unsafe fn free_mesh_data(mesh: &ffi::Mesh) {
+    for data in [
+        mesh.vertices.cast(),
+        mesh.texcoords.cast(),
+        mesh.texcoords2.cast(),
+        mesh.normals.cast(),
+        mesh.tangents.cast(),
+        mesh.colors.cast(),
+        mesh.indices.cast(),
+        mesh.animVertices.cast(),
+        mesh.animNormals.cast(),
+        mesh.boneIds.cast(),
+        mesh.boneWeights.cast(),
+        mesh.vboId.cast(),
+    ] {
+        ffi::MemFree(data);
+    }
+}
+
+/// Vertex data copied out of a mesh, to build new meshes from
+#[derive(Default)]
+struct MeshData {
+    vertices: Vec<Vector3>,
+    texcoords: Option<Vec<Vector2>>,
+    texcoords2: Option<Vec<Vector2>>,
+    normals: Option<Vec<Vector3>>,
+    tangents: Option<Vec<Vector4>>,
+    colors: Option<Vec<Color>>,
+    bone_ids: Option<Vec<[u8; 4]>>,
+    bone_weights: Option<Vec<[f32; 4]>>,
+    /// Triangle list, non-indexed meshes get `0..vertex count`
+    indices: Vec<u32>,
+}
+
+/// Copy of the vertex data behind a mesh pointer, `None` if the mesh doesn't have it
+fn copy_attribute<T: Copy>(ptr: *const T, count: usize) -> Option<Vec<T>> {
+    (!ptr.is_null()).then(|| unsafe { std::slice::from_raw_parts(ptr, count) }.to_vec())
+}
+
+/// Append one mesh's attribute to a merged one, padding whichever side lacks it with `default`
+fn append_attribute<T: Copy>(
+    merged: &mut Option<Vec<T>>,
+    data: Option<Vec<T>>,
+    merged_count: usize,
+    count: usize,
+    default: T,
+) {
+    match (merged.as_mut(), data) {
+        (Some(merged), Some(data)) => merged.extend(data),
+        (Some(merged), None) => merged.resize(merged.len() + count, default),
+        (None, Some(data)) => {
+            let mut padded = vec![default; merged_count];
+            padded.extend(data);
+            *merged = Some(padded);
+        }
+        (None, None) => {}
+    }
+}
+
+/// Copy of a slice in memory raylib frees when the mesh is unloaded, null if it's empty
+fn alloc_attribute<T: Copy>(data: &[T]) -> *mut T {
+    if data.is_empty() {
+        return std::ptr::null_mut();
+    }
+
+    unsafe {
+        let ptr = ffi::MemAlloc(std::mem::size_of_val(data) as _) as *mut T;
+        std::ptr::copy_nonoverlapping(data.as_ptr(), ptr, data.len());
+        ptr
+    }
+}
+
+impl MeshData {
+    fn from_raw(mesh: &ffi::Mesh) -> Self {
+        let count = mesh.vertexCount.max(0) as usize;
+
+        let indices = if mesh.indices.is_null() {
+            (0..count as u32).collect()
+        } else {
+            let len = mesh.triangleCount.max(0) as usize * 3;
+
+            unsafe { std::slice::from_raw_parts(mesh.indices as *const u16, len) }
+                .iter()
+                .map(|&index| index as u32)
+                .collect()
+        };
+
+        Self {
+            vertices: copy_attribute(mesh.vertices as *const Vector3, count).unwrap_or_default(),
+            texcoords: copy_attribute(mesh.texcoords as *const Vector2, count),
+            texcoords2: copy_attribute(mesh.texcoords2 as *const Vector2, count),
+            normals: copy_attribute(mesh.normals as *const Vector3, count),
+            tangents: copy_attribute(mesh.tangents as *const Vector4, count),
+            colors: copy_attribute(mesh.colors as *const Color, count),
+            bone_ids: copy_attribute(mesh.boneIds as *const [u8; 4], count),
+            bone_weights: copy_attribute(mesh.boneWeights as *const [f32; 4], count),
+            indices,
+        }
+    }
+
+    /// Add a vertex of a generated shape
+    fn push_vertex(&mut self, position: Vector3, normal: Vector3, texcoord: Vector2) {
+        self.vertices.push(position);
+        self.normals.get_or_insert_with(Vec::new).push(normal);
+        self.texcoords.get_or_insert_with(Vec::new).push(texcoord);
+    }
+
+    /// Add a flat quad, corners counter-clockwise seen from the front starting at the bottom left
+    fn add_quad(&mut self, corners: [Vector3; 4]) {
+        let [a, b, c, _] = corners;
+        let normal = vec3::normalize(vec3::cross(vec3::sub(b, a), vec3::sub(c, a)));
+        let first = self.vertices.len() as u32;
+
+        let texcoords = [(0., 1.), (1., 1.), (1., 0.), (0., 0.)];
+
+        for (corner, (x, y)) in corners.into_iter().zip(texcoords) {
+            self.push_vertex(corner, normal, Vector2 { x, y });
+        }
+
+        self.indices
+            .extend([0, 1, 2, 0, 2, 3].map(|index| first + index));
+    }
+
+    /// Add a surface of revolution around the Y axis
+    ///
+    /// `profile` holds `(radius, y, normal)` points, the normal in the radius/y plane. Going
+    /// along it, the outside is on the right (top to bottom for the side of a cylinder).
+    fn add_lathe(&mut self, profile: &[(f32, f32, Vector2)], slices: u32) {
+        let slices = slices.max(3);
+        let rows = profile.len() as u32;
+        let first = self.vertices.len() as u32;
+
+        // texture v goes along the profile's length
+        let mut lengths = vec![0.; profile.len()];
+
+        for i in 1..profile.len() {
+            let ((r0, y0, _), (r1, y1, _)) = (profile[i - 1], profile[i]);
+            lengths[i] = lengths[i - 1] + (r1 - r0).hypot(y1 - y0);
+        }
+
+        let total = lengths.last().copied().unwrap_or(0.).max(f32::EPSILON);
+
+        for slice in 0..=slices {
+            let u = slice as f32 / slices as f32;
+            let (sin, cos) = (u * std::f32::consts::TAU).sin_cos();
+
+            for (&(radius, y, normal), length) in profile.iter().zip(&lengths) {
+                self.push_vertex(
+                    vec3::new(radius * sin, y, radius * cos),
+                    vec3::new(normal.x * sin, normal.y, normal.x * cos),
+                    Vector2 {
+                        x: u,
+                        y: length / total,
+                    },
+                );
+            }
+        }
+
+        for slice in 0..slices {
+            for row in 0..rows.saturating_sub(1) {
+                let a = first + slice * rows + row;
+                let (b, c, d) = (a + rows, a + 1, a + rows + 1);
+
+                // points on the axis would make empty triangles
+                if profile[row as usize + 1].0 != 0. {
+                    self.indices.extend([a, c, d]);
+                }
+                if profile[row as usize].0 != 0. {
+                    self.indices.extend([a, d, b]);
+                }
+            }
+        }
+    }
+
+    /// Append another mesh's data, bone data is only kept if both have it
+    fn append(&mut self, other: MeshData) {
+        let (merged_count, count) = (self.vertices.len(), other.vertices.len());
+        let first = merged_count as u32;
+
+        append_attribute(
+            &mut self.texcoords,
+            other.texcoords,
+            merged_count,
+            count,
+            Vector2 { x: 0., y: 0. },
+        );
+        append_attribute(
+            &mut self.texcoords2,
+            other.texcoords2,
+            merged_count,
+            count,
+            Vector2 { x: 0., y: 0. },
+        );
+        append_attribute(
+            &mut self.normals,
+            other.normals,
+            merged_count,
+            count,
+            vec3::new(0., 1., 0.),
+        );
+        append_attribute(
+            &mut self.tangents,
+            other.tangents,
+            merged_count,
+            count,
+            Vector4 {
+                x: 1.,
+                y: 0.,
+                z: 0.,
+                w: 1.,
+            },
+        );
+        append_attribute(
+            &mut self.colors,
+            other.colors,
+            merged_count,
+            count,
+            Color::WHITE,
+        );
+
+        if merged_count == 0 {
+            self.bone_ids = other.bone_ids;
+            self.bone_weights = other.bone_weights;
+        } else {
+            match (self.bone_ids.as_mut(), other.bone_ids) {
+                (Some(ids), Some(other)) => ids.extend(other),
+                _ => self.bone_ids = None,
+            }
+            match (self.bone_weights.as_mut(), other.bone_weights) {
+                (Some(weights), Some(other)) => weights.extend(other),
+                _ => self.bone_weights = None,
+            }
+        }
+
+        self.vertices.extend(other.vertices);
+        self.indices
+            .extend(other.indices.into_iter().map(|index| first + index));
+    }
+
+    /// Transform positions by a matrix, and normals and tangents to match
+    fn transform(&mut self, matrix: &Matrix) {
+        let m = crate::math::columns(matrix);
+        let inverse = crate::math::invert(&m);
+
+        // directions only go through the upper 3x3, normals through its inverse transpose
+        let direction = |v: Vector3| {
+            vec3::new(
+                m[0][0] * v.x + m[1][0] * v.y + m[2][0] * v.z,
+                m[0][1] * v.x + m[1][1] * v.y + m[2][1] * v.z,
+                m[0][2] * v.x + m[1][2] * v.y + m[2][2] * v.z,
+            )
+        };
+        let normal = |v: Vector3| {
+            vec3::normalize(vec3::new(
+                inverse[0][0] * v.x + inverse[0][1] * v.y + inverse[0][2] * v.z,
+                inverse[1][0] * v.x + inverse[1][1] * v.y + inverse[1][2] * v.z,
+                inverse[2][0] * v.x + inverse[2][1] * v.y + inverse[2][2] * v.z,
+            ))
+        };
+
+        let determinant = vec3::dot(
+            vec3::new(m[0][0], m[0][1], m[0][2]),
+            vec3::cross(
+                vec3::new(m[1][0], m[1][1], m[1][2]),
+                vec3::new(m[2][0], m[2][1], m[2][2]),
+            ),
+        );
+
+        for vertex in self.vertices.iter_mut() {
+            *vertex = crate::math::transform_point(&m, [vertex.x, vertex.y, vertex.z]);
+        }
+
+        for n in self.normals.iter_mut().flatten() {
+            *n = normal(*n);
+        }
+
+        for tangent in self.tangents.iter_mut().flatten() {
+            let t = vec3::normalize(direction(vec3::new(tangent.x, tangent.y, tangent.z)));
+            *tangent = Vector4 {
+                x: t.x,
+                y: t.y,
+                z: t.z,
+                w: tangent.w * determinant.signum(),
+            };
+        }
+
+        // mirroring turns triangles inside out, swap their winding back
+        if determinant < 0. {
+            for triangle in self.indices.chunks_exact_mut(3) {
+                triangle.swap(1, 2);
+            }
+        }
+    }
+
+    /// Keep the vertices at `order`, in that order (repeats allowed), indices aren't touched
+    fn select_vertices(&mut self, order: &[u32]) {
+        fn select<T: Copy>(data: &mut Vec<T>, order: &[u32]) {
+            *data = order.iter().map(|&index| data[index as usize]).collect();
+        }
+
+        select(&mut self.vertices, order);
+
+        for data in self.texcoords.iter_mut().chain(self.texcoords2.iter_mut()) {
+            select(data, order);
+        }
+        for data in self.normals.iter_mut() {
+            select(data, order);
+        }
+        for data in self.tangents.iter_mut() {
+            select(data, order);
+        }
+        for data in self.colors.iter_mut() {
+            select(data, order);
+        }
+        for data in self.bone_ids.iter_mut() {
+            select(data, order);
+        }
+        for data in self.bone_weights.iter_mut() {
+            select(data, order);
+        }
+    }
+
+    /// Turn into a plain triangle list, one vertex per index
+    fn expand(&mut self) {
+        let indices = std::mem::take(&mut self.indices);
+
+        self.select_vertices(&indices);
+        self.indices = (0..self.vertices.len() as u32).collect();
+    }
+
+    /// Smooth normals of the triangles around each vertex, weighted by triangle area
+    fn recalculate_normals(&mut self) {
+        let mut normals = vec![vec3::new(0., 0., 0.); self.vertices.len()];
+
+        for triangle in self.indices.chunks_exact(3) {
+            let [a, b, c] = [0, 1, 2].map(|i| self.vertices[triangle[i] as usize]);
+            // twice the triangle area long
+            let face = vec3::cross(vec3::sub(b, a), vec3::sub(c, a));
+
+            for &index in triangle {
+                normals[index as usize] = vec3::add(normals[index as usize], face);
+            }
+        }
+
+        self.normals = Some(normals.into_iter().map(vec3::normalize).collect());
+    }
+
+    /// Check if two vertices can be welded: positions within `epsilon`, same texture
+    /// coordinates (within `epsilon`), colors and bone data
+    fn can_weld(&self, a: usize, b: usize, epsilon: f32) -> bool {
+        let close = |a: &[f32], b: &[f32]| a.iter().zip(b).all(|(a, b)| (a - b).abs() <= epsilon);
+        let close_uv = |texcoords: &Option<Vec<Vector2>>| match texcoords {
+            Some(texcoords) => {
+                let (a, b) = (texcoords[a], texcoords[b]);
+                close(&[a.x, a.y], &[b.x, b.y])
+            }
+            None => true,
+        };
+        let (pa, pb) = (self.vertices[a], self.vertices[b]);
+
+        close(&[pa.x, pa.y, pa.z], &[pb.x, pb.y, pb.z])
+            && close_uv(&self.texcoords)
+            && close_uv(&self.texcoords2)
+            && self
+                .colors
+                .as_ref()
+                .map_or(true, |colors| colors[a] == colors[b])
+            && self.bone_ids.as_ref().map_or(true, |ids| ids[a] == ids[b])
+            && self
+                .bone_weights
+                .as_ref()
+                .map_or(true, |weights| weights[a] == weights[b])
+    }
+
+    /// Merge vertices that [`MeshData::can_weld`], drop the triangles that collapse, returns the
+    /// number of vertices removed
+    fn weld(&mut self, epsilon: f32) -> usize {
+        let epsilon = epsilon.max(0.);
+        // vertices within epsilon are at most a cell apart
+        let cell_size = epsilon.max(1e-6);
+        let cell = |v: Vector3| [v.x, v.y, v.z].map(|c| (c / cell_size).floor() as i64);
+
+        let mut cells: HashMap<[i64; 3], Vec<u32>> = HashMap::new();
+        // old index of every kept vertex, and new index of every old one
+        let mut kept = Vec::new();
+        let mut remap = Vec::with_capacity(self.vertices.len());
+
+        for index in 0..self.vertices.len() {
+            let [x, y, z] = cell(self.vertices[index]);
+            let mut welded = None;
+
+            'search: for dx in -1..=1 {
+                for dy in -1..=1 {
+                    for dz in -1..=1 {
+                        let key = [
+                            x.saturating_add(dx),
+                            y.saturating_add(dy),
+                            z.saturating_add(dz),
+                        ];
+
+                        let found = cells.get(&key).and_then(|candidates| {
+                            candidates.iter().find(|&&new| {
+                                self.can_weld(kept[new as usize] as usize, index, epsilon)
+                            })
+                        });
+
+                        if let Some(&new) = found {
+                            welded = Some(new);
+                            break 'search;
+                        }
+                    }
+                }
+            }
+
+            let new = welded.unwrap_or_else(|| {
+                let new = kept.len() as u32;
+                kept.push(index as u32);
+                cells.entry([x, y, z]).or_default().push(new);
+                new
+            });
+
+            remap.push(new);
+        }
+
+        let removed = self.vertices.len() - kept.len();
+
+        self.select_vertices(&kept);
+        self.indices = self
+            .indices
+            .chunks_exact(3)
+            .map(|triangle| [0, 1, 2].map(|i| remap[triangle[i] as usize]))
+            .filter(|[a, b, c]| a != b && b != c && c != a)
+            .flatten()
+            .collect();
+
+        removed
+    }
+
+    /// Reorder triangles for the GPU's post-transform vertex cache (Tom Forsyth's linear-speed
+    /// algorithm), then vertices in the order triangles first use them
+    fn optimize_for_cache(&mut self) {
+        const CACHE_SIZE: usize = 32;
+
+        fn vertex_score(cache_position: Option<usize>, remaining: usize) -> f32 {
+            if remaining == 0 {
+                return -1.;
+            }
+
+            let cache_score = match cache_position {
+                None => 0.,
+                // the last triangle's vertices, fixed so the next triangle doesn't just reuse them
+                Some(position) if position < 3 => 0.75,
+                Some(position) => {
+                    let scaled = (position - 3) as f32 / (CACHE_SIZE - 3) as f32;
+                    (1. - scaled).powf(1.5)
+                }
+            };
+
+            // favor vertices with few triangles left, so they're done with and leave the cache
+            cache_score + 2. * (remaining as f32).powf(-0.5)
+        }
+
+        let triangle_count = self.indices.len() / 3;
+        let vertex_count = self.vertices.len();
+
+        // triangles not drawn yet of every vertex
+        let mut vertex_triangles = vec![Vec::new(); vertex_count];
+
+        for (triangle, indices) in self.indices.chunks_exact(3).enumerate() {
+            for &index in indices {
+                vertex_triangles[index as usize].push(triangle as u32);
+            }
+        }
+
+        let mut cache_positions = vec![None; vertex_count];
+        let mut vertex_scores: Vec<f32> = vertex_triangles
+            .iter()
+            .map(|triangles| vertex_score(None, triangles.len()))
+            .collect();
+
+        let triangle_score = |triangle: usize, vertex_scores: &[f32]| -> f32 {
+            self.indices[triangle * 3..triangle * 3 + 3]
+                .iter()
+                .map(|&index| vertex_scores[index as usize])
+                .sum()
+        };
+
+        let mut drawn = vec![false; triangle_count];
+        let mut cache: Vec<u32> = Vec::with_capacity(CACHE_SIZE + 3);
+        let mut order = Vec::with_capacity(self.indices.len());
+        // next triangle to check when nothing in the cache is left to draw
+        let mut next_unused = 0;
+        let mut best = None;
+
+        for _ in 0..triangle_count {
+            let triangle = match best.take() {
+                Some(triangle) => triangle,
+                None => {
+                    while drawn[next_unused] {
+                        next_unused += 1;
+                    }
+                    next_unused
+                }
+            };
+
+            drawn[triangle] = true;
+
+            let indices = [0, 1, 2].map(|i| self.indices[triangle * 3 + i]);
+            order.extend(indices);
+
+            for index in indices {
+                let triangles = &mut vertex_triangles[index as usize];
+                triangles.retain(|&other| other as usize != triangle);
+            }
+
+            // the triangle's vertices move to the front, pushing the least recent ones out
+            let previous = std::mem::take(&mut cache);
+            cache.extend(indices);
+            cache.extend(previous.iter().filter(|index| !indices.contains(index)));
+
+            for &index in cache.iter().skip(CACHE_SIZE) {
+                cache_positions[index as usize] = None;
+                vertex_scores[index as usize] =
+                    vertex_score(None, vertex_triangles[index as usize].len());
+            }
+
+            cache.truncate(CACHE_SIZE);
+
+            for (position, &index) in cache.iter().enumerate() {
+                cache_positions[index as usize] = Some(position);
+                vertex_scores[index as usize] =
+                    vertex_score(Some(position), vertex_triangles[index as usize].len());
+            }
+
+            // only triangles around the cache changed score
+            let mut best_score = f32::MIN;
+
+            for &index in cache.iter() {
+                for &other in vertex_triangles[index as usize].iter() {
+                    let score = triangle_score(other as usize, &vertex_scores);
+
+                    if score > best_score {
+                        best_score = score;
+                        best = Some(other as usize);
+                    }
+                }
+            }
+        }
+
+        // vertices by first use, unused ones are dropped
+        let mut remap = vec![u32::MAX; vertex_count];
+        let mut kept = Vec::with_capacity(vertex_count);
+
+        for index in order.iter_mut() {
+            if remap[*index as usize] == u32::MAX {
+                remap[*index as usize] = kept.len() as u32;
+                kept.push(*index);
+            }
+
+            *index = remap[*index as usize];
+        }
+
+        self.select_vertices(&kept);
+        self.indices = order;
+    }
+
+    /// Build a mesh and upload it, as a triangle list when there are too many vertices for
+    /// 16-bit indices
+    fn into_mesh(mut self) -> Mesh {
+        let indexed = self.vertices.len() <= u16::MAX as usize + 1;
+
+        if !indexed {
+            self.expand();
+        }
+
+        let indices: Vec<u16> = if indexed {
+            self.indices.iter().map(|&index| index as u16).collect()
+        } else {
+            Vec::new()
+        };
+
+        // skinning writes the animated copies, starting from the bind pose
+        let skinning = match (&self.bone_ids, &self.bone_weights) {
+            (Some(ids), Some(weights)) => Some((ids.as_slice(), weights.as_slice())),
+            _ => None,
+        };
+        let (anim_vertices, anim_normals, bone_ids, bone_weights) = match skinning {
+            Some((ids, weights)) => (
+                alloc_attribute(&self.vertices),
+                alloc_attribute(self.normals.as_deref().unwrap_or_default()),
+                alloc_attribute(ids),
+                alloc_attribute(weights),
+            ),
+            None => (
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+            ),
+        };
+
+        let raw = ffi::Mesh {
+            vertexCount: self.vertices.len() as _,
+            triangleCount: (self.indices.len() / 3) as _,
+            vertices: alloc_attribute(&self.vertices) as *mut _,
+            texcoords: alloc_attribute(self.texcoords.as_deref().unwrap_or_default()) as *mut _,
+            texcoords2: alloc_attribute(self.texcoords2.as_deref().unwrap_or_default()) as *mut _,
+            normals: alloc_attribute(self.normals.as_deref().unwrap_or_default()) as *mut _,
+            tangents: alloc_attribute(self.tangents.as_deref().unwrap_or_default()) as *mut _,
+            colors: alloc_attribute(self.colors.as_deref().unwrap_or_default()) as *mut _,
+            indices: alloc_attribute(&indices),
+            animVertices: anim_vertices as *mut _,
+            animNormals: anim_normals as *mut _,
+            boneIds: bone_ids as *mut _,
+            boneWeights: bone_weights as *mut _,
+            vaoId: 0,
+            vboId: std::ptr::null_mut(),
+        };
+
+        let mut mesh = Mesh { raw };
+        mesh.upload(false);
+
+        mesh
+    }
+}
+
+/// Shape of a [`MorphMesh`], stored as offsets from the base mesh
+struct MorphTarget {
+    name: String,
+    vertex_offsets: Vec<Vector3>,
+    normal_offsets: Option<Vec<Vector3>>,
+}
+
+/// Mesh blended between its base shape and morph targets (blend shapes)
+///
+/// Targets are added as whole vertex arrays, e.g. a face with closed eyes or a smile.
+/// [`MorphMesh::update`] blends them on the CPU by their weights and uploads the result.
+/// For a single target, [`MorphMesh::set_target_attribute`] hands the offsets to a vertex shader
+/// instead (`position + offset*weight`), leaving the CPU copy alone.
+pub struct MorphMesh {
+    mesh: Mesh,
+    base_vertices: Vec<Vector3>,
+    base_normals: Option<Vec<Vector3>>,
+    targets: Vec<MorphTarget>,
+    weights: Vec<f32>,
+    dirty: bool,
+}
+
+impl fmt::Debug for MorphMesh {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MorphMesh")
+            .field("mesh", &self.mesh)
+            .field("targets", &self.targets.len())
+            .field("weights", &self.weights)
+            .finish_non_exhaustive()
+    }
+}
+
+impl MorphMesh {
+    /// Create morph mesh from its base shape, the mesh is uploaded as dynamic if it isn't yet
+    pub fn new(mut mesh: Mesh) -> Self {
+        if mesh.raw.vaoId == 0 {
+            mesh.upload(true);
         }
-    }
 
-    /// Generate plane mesh (with subdivisions)
-    #[inline]
-    pub fn generate_plane(width: f32, length: f32, res_x: u32, res_z: u32) -> Self {
+        let base_vertices = mesh.vertices().to_vec();
+        let base_normals = (!mesh.raw.normals.is_null()).then(|| mesh.normals().to_vec());
+
         Self {
-            raw: unsafe { ffi::GenMeshPlane(width, length, res_x as _, res_z as _) },
+            mesh,
+            base_vertices,
+            base_normals,
+            targets: Vec::new(),
+            weights: Vec::new(),
+            dirty: false,
         }
     }
 
-    /// Generate cuboid mesh
-    #[inline]
-    pub fn generate_cube(width: f32, height: f32, length: f32) -> Self {
-        Self {
-            raw: unsafe { ffi::GenMeshCube(width, height, length) },
+    /// Add a target shape with one position (and optionally normal) per vertex
+    ///
+    /// Returns the target index, `None` if an array doesn't match the vertex count.
+    /// Normals are ignored when the base mesh has none.
+    pub fn add_target(
+        &mut self,
+        name: &str,
+        vertices: &[Vector3],
+        normals: Option<&[Vector3]>,
+    ) -> Option<usize> {
+        let count = self.base_vertices.len();
+
+        if vertices.len() != count || normals.is_some_and(|normals| normals.len() != count) {
+            #[cfg(debug_assertions)]
+            crate::core::warn(&format!(
+                "MorphMesh: target {:?} doesn't have one value per vertex ({})",
+                name, count
+            ));
+
+            return None;
         }
+
+        let offsets = |target: &[Vector3], base: &[Vector3]| {
+            target
+                .iter()
+                .zip(base)
+                .map(|(&target, &base)| vec3::sub(target, base))
+                .collect::<Vec<_>>()
+        };
+
+        let normal_offsets = match (normals, &self.base_normals) {
+            (Some(normals), Some(base)) => Some(offsets(normals, base)),
+            _ => None,
+        };
+
+        self.targets.push(MorphTarget {
+            name: name.to_string(),
+            vertex_offsets: offsets(vertices, &self.base_vertices),
+            normal_offsets,
+        });
+        self.weights.push(0.);
+
+        Some(self.targets.len() - 1)
     }
 
-    /// Generate sphere mesh (standard sphere)
+    /// Number of targets
     #[inline]
-    pub fn generate_sphere(radius: f32, rings: u32, slices: u32) -> Self {
-        Self {
-            raw: unsafe { ffi::GenMeshSphere(radius, rings as _, slices as _) },
-        }
+    pub fn target_count(&self) -> usize {
+        self.targets.len()
     }
 
-    /// Generate half-sphere mesh (no bottom cap)
+    /// Index of a target by name
     #[inline]
-    pub fn generate_hemisphere(radius: f32, rings: u32, slices: u32) -> Self {
-        Self {
-            raw: unsafe { ffi::GenMeshHemiSphere(radius, rings as _, slices as _) },
-        }
+    pub fn target_index(&self, name: &str) -> Option<usize> {
+        self.targets.iter().position(|target| target.name == name)
     }
 
-    /// Generate cylinder mesh
+    /// Name of a target
     #[inline]
-    pub fn generate_cylinder(radius: f32, height: f32, slices: u32) -> Self {
-        Self {
-            raw: unsafe { ffi::GenMeshCylinder(radius, height, slices as _) },
-        }
+    pub fn target_name(&self, index: usize) -> Option<&str> {
+        self.targets.get(index).map(|target| target.name.as_str())
     }
 
-    /// Generate cone/pyramid mesh
+    /// Blend weight of a target, 0 when it doesn't exist
     #[inline]
-    pub fn generate_cone(radius: f32, height: f32, slices: u32) -> Self {
-        Self {
-            raw: unsafe { ffi::GenMeshCone(radius, height, slices as _) },
-        }
+    pub fn weight(&self, index: usize) -> f32 {
+        self.weights.get(index).copied().unwrap_or(0.)
     }
 
-    /// Generate torus mesh
+    /// Set the blend weight of a target (usually 0 to 1), `false` if it doesn't exist
     #[inline]
-    pub fn generate_torus(radius: f32, size: f32, rad_seg: u32, sides: u32) -> Self {
-        Self {
-            raw: unsafe { ffi::GenMeshTorus(radius, size, rad_seg as _, sides as _) },
+    pub fn set_weight(&mut self, index: usize, weight: f32) -> bool {
+        match self.weights.get_mut(index) {
+            Some(current) => {
+                if *current != weight {
+                    *current = weight;
+                    self.dirty = true;
+                }
+
+                true
+            }
+            None => false,
         }
     }
 
-    /// Generate trefoil knot mesh
+    /// Set the blend weight of a target by name, `false` if it doesn't exist
     #[inline]
-    pub fn generate_knot(radius: f32, size: f32, rad_seg: u32, sides: u32) -> Self {
-        Self {
-            raw: unsafe { ffi::GenMeshKnot(radius, size, rad_seg as _, sides as _) },
+    pub fn set_weight_by_name(&mut self, name: &str, weight: f32) -> bool {
+        match self.target_index(name) {
+            Some(index) => self.set_weight(index, weight),
+            None => false,
         }
     }
 
-    /// Generate heightmap mesh from image data
+    /// Set all weights to 0, back to the base shape
     #[inline]
-    pub fn generate_heightmap(heightmap: &Image, size: Vector3) -> Self {
-        Self {
-            raw: unsafe { ffi::GenMeshHeightmap(heightmap.raw.clone(), size.into()) },
+    pub fn reset_weights(&mut self) {
+        for index in 0..self.weights.len() {
+            self.set_weight(index, 0.);
         }
     }
 
-    /// Generate cubes-based map mesh from image data
-    #[inline]
-    pub fn generate_cubicmap(cubicmap: &Image, cube_size: Vector3) -> Self {
-        Self {
-            raw: unsafe { ffi::GenMeshCubicmap(cubicmap.raw.clone(), cube_size.into()) },
+    /// Blend the targets and upload the vertices (and normals), if any weight changed
+    pub fn update(&mut self) {
+        if !self.dirty {
+            return;
         }
-    }
 
-    /// Get the 'raw' ffi type
-    /// Take caution when cloning so it doesn't outlive the original
-    #[inline]
-    pub fn as_raw(&self) -> &ffi::Mesh {
-        &self.raw
+        self.dirty = false;
+
+        let active: Vec<_> = self
+            .targets
+            .iter()
+            .zip(&self.weights)
+            .filter(|(_, &weight)| weight != 0.)
+            .collect();
+
+        let vertices = self.mesh.vertices_mut();
+        vertices.copy_from_slice(&self.base_vertices);
+
+        for (target, &weight) in &active {
+            for (vertex, &offset) in vertices.iter_mut().zip(&target.vertex_offsets) {
+                *vertex = vec3::add(*vertex, vec3::scale(offset, weight));
+            }
+        }
+
+        self.upload_buffer(0, self.mesh.raw.vertices);
+
+        if let Some(base) = &self.base_normals {
+            let normals = self.mesh.normals_mut();
+            normals.copy_from_slice(base);
+
+            for (target, &weight) in &active {
+                if let Some(offsets) = &target.normal_offsets {
+                    for (normal, &offset) in normals.iter_mut().zip(offsets) {
+                        *normal = vec3::add(*normal, vec3::scale(offset, weight));
+                    }
+                }
+            }
+
+            for normal in normals.iter_mut() {
+                *normal = vec3::normalize(*normal);
+            }
+
+            self.upload_buffer(2, self.mesh.raw.normals);
+        }
     }
 
-    /// Get the 'raw' ffi type
-    /// Take caution when cloning so it doesn't outlive the original
-    #[inline]
-    pub fn as_raw_mut(&mut self) -> &mut ffi::Mesh {
-        &mut self.raw
+    /// Upload one of the mesh's float3 buffers (0 positions, 2 normals)
+    fn upload_buffer(&self, index: i32, data: *mut f32) {
+        let size = self.base_vertices.len() * std::mem::size_of::<Vector3>();
+
+        unsafe {
+            ffi::UpdateMeshBuffer(self.mesh.raw.clone(), index, data as *const _, size as _, 0)
+        }
     }
 
-    /// Convert a 'raw' ffi object to a safe wrapper
+    /// Upload the offsets of a target as a vertex attribute of a shader, for blending on the GPU
     ///
-    /// # Safety
-    /// * The raw object must be correctly initialized
-    /// * The raw object should be unique. Otherwise, make sure its clones don't outlive the newly created object.
+    /// The shader adds `offset*weight` to the position itself, weights set here are only used by
+    /// [`MorphMesh::update`]. See [`Mesh::set_attribute`].
+    pub fn set_target_attribute(&mut self, shader: &Shader, name: &str, index: usize) -> bool {
+        match self.targets.get(index) {
+            Some(target) => self
+                .mesh
+                .set_attribute(shader, name, &target.vertex_offsets),
+            None => false,
+        }
+    }
+
+    /// The blended mesh, for drawing
     #[inline]
-    pub unsafe fn from_raw(raw: ffi::Mesh) -> Self {
-        Self { raw }
+    pub fn mesh(&self) -> &Mesh {
+        &self.mesh
     }
-}
 
-impl Drop for Mesh {
+    /// Take the mesh back, as it was blended last
     #[inline]
-    fn drop(&mut self) {
-        unsafe { ffi::UnloadMesh(self.raw.clone()) }
+    pub fn into_mesh(self) -> Mesh {
+        self.mesh
     }
 }
 
@@ -383,15 +1820,15 @@ impl Model {
 
     /// Load model from files (meshes and materials)
     #[inline]
-    pub fn from_file(file_name: &str) -> Option<Self> {
-        let file_name = CString::new(file_name).unwrap();
+    pub fn from_file(file_name: &str) -> Result<Self, RaylibError> {
+        let c_file_name = RaylibError::file_name(file_name)?;
 
-        let raw = unsafe { ffi::LoadModel(file_name.as_ptr()) };
+        let raw = unsafe { ffi::LoadModel(c_file_name.as_ptr()) };
 
         if unsafe { ffi::IsModelReady(raw.clone()) } {
-            Some(Self { raw })
+            Ok(Self::tracked(raw))
         } else {
-            None
+            Err(RaylibError::decode_file(file_name))
         }
     }
 
@@ -400,9 +1837,10 @@ impl Model {
     pub fn from_mesh(mesh: Mesh) -> Self {
         let mesh = ManuallyDrop::new(mesh);
 
-        Self {
-            raw: unsafe { ffi::LoadModelFromMesh(mesh.raw.clone()) },
-        }
+        // the model unloads the mesh from now on
+        untrack_context_object(ContextObject::Alloc(mesh.raw.vboId as usize));
+
+        Self::tracked(unsafe { ffi::LoadModelFromMesh(mesh.raw.clone()) })
     }
 
     /// Compute model bounding box limits (considers all meshes)
@@ -411,6 +1849,57 @@ impl Model {
         unsafe { ffi::GetModelBoundingBox(self.raw.clone()).into() }
     }
 
+    /// Skin the meshes to a pose on the CPU and upload them, `false` if the bone count differs
+    ///
+    /// Same as [`Model::update_animation`], but for any pose, e.g. one blended from several
+    /// animations. The result is readable with [`Mesh::skinned_vertices`], to attach particles
+    /// or hit tests to the animated mesh. Meshes without bone data are left alone.
+    pub fn cpu_skin(&mut self, pose: &Pose) -> bool {
+        let bind_pose = self.bind_pose();
+
+        if pose.transforms.len() != bind_pose.len() {
+            return false;
+        }
+
+        let deltas: Vec<_> = bind_pose
+            .iter()
+            .zip(&pose.transforms)
+            .map(|(bind, pose)| BoneDelta::new(bind, pose))
+            .collect();
+
+        for mesh in self.meshes_mut() {
+            let raw = &mesh.raw;
+
+            if raw.animVertices.is_null() {
+                continue;
+            }
+
+            let count = raw.vertexCount as usize;
+            let positions =
+                unsafe { std::slice::from_raw_parts_mut(raw.animVertices as *mut Vector3, count) };
+            let normals = (!raw.animNormals.is_null()).then(|| unsafe {
+                std::slice::from_raw_parts_mut(raw.animNormals as *mut Vector3, count)
+            });
+            let has_normals = normals.is_some() && !raw.normals.is_null();
+
+            if !skin_mesh(raw, &deltas, positions, normals) {
+                continue;
+            }
+
+            let size = (count * std::mem::size_of::<Vector3>()) as i32;
+
+            unsafe {
+                ffi::UpdateMeshBuffer(raw.clone(), 0, raw.animVertices as *const _, size, 0);
+
+                if has_normals {
+                    ffi::UpdateMeshBuffer(raw.clone(), 2, raw.animNormals as *const _, size, 0);
+                }
+            }
+        }
+
+        true
+    }
+
     /// Set material for a mesh
     #[inline]
     pub fn set_mesh_material(&mut self, mesh_id: u32, material_id: u32) {
@@ -419,6 +1908,66 @@ impl Model {
         }
     }
 
+    /// Index of the material used by a mesh, `None` if there's no such mesh
+    #[inline]
+    pub fn mesh_material(&self, mesh_id: u32) -> Option<u32> {
+        if mesh_id >= self.raw.meshCount as u32 || self.raw.meshMaterial.is_null() {
+            return None;
+        }
+
+        let material_id = unsafe { self.raw.meshMaterial.add(mesh_id as usize).read() };
+
+        (0..self.raw.materialCount)
+            .contains(&material_id)
+            .then_some(material_id as u32)
+    }
+
+    /// Set the texture of a slot in every material
+    ///
+    /// The model doesn't take ownership of the texture (raylib never unloads model textures),
+    /// keep it alive for as long as the model is drawn with it.
+    pub fn set_texture(&mut self, slot: MaterialSlot, texture: &Texture2D) {
+        for material in self.materials_mut() {
+            material.maps_mut()[slot as usize].texture.raw = texture.raw.clone();
+        }
+    }
+
+    /// Set the texture of a slot in the material used by a mesh, `false` if there's no such mesh
+    ///
+    /// Other meshes sharing the material change too. See [`Model::set_texture`] about ownership.
+    pub fn set_mesh_texture(
+        &mut self,
+        mesh_id: u32,
+        slot: MaterialSlot,
+        texture: &Texture2D,
+    ) -> bool {
+        match self.mesh_material(mesh_id) {
+            Some(material_id) => {
+                self.materials_mut()[material_id as usize].maps_mut()[slot as usize]
+                    .texture
+                    .raw = texture.raw.clone();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Texture of a slot in the first material, `None` if the model has no materials
+    #[inline]
+    pub fn texture(&self, slot: MaterialSlot) -> Option<&Texture2D> {
+        let material = self.materials().first()?;
+
+        Some(&*material.maps()[slot as usize].texture)
+    }
+
+    /// Texture of a slot in the material used by a mesh, `None` if there's no such mesh
+    #[inline]
+    pub fn mesh_texture(&self, mesh_id: u32, slot: MaterialSlot) -> Option<&Texture2D> {
+        let material_id = self.mesh_material(mesh_id)?;
+
+        Some(&*self.materials()[material_id as usize].maps()[slot as usize].texture)
+    }
+
     /// Update model animation pose
     #[inline]
     pub fn update_animation(&self, anim: &ModelAnimation, frame: u32) {
@@ -452,6 +2001,14 @@ impl Model {
     /// * The raw object should be unique. Otherwise, make sure its clones don't outlive the newly created object.
     #[inline]
     pub unsafe fn from_raw(raw: ffi::Model) -> Self {
+        Self::tracked(raw)
+    }
+
+    /// Wrap a model of the current context, it owns its meshes and materials through the `meshes` array
+    #[inline]
+    fn tracked(raw: ffi::Model) -> Self {
+        track_context_object(ContextObject::Alloc(raw.meshes as usize));
+
         Self { raw }
     }
 }
@@ -459,7 +2016,34 @@ impl Model {
 impl Drop for Model {
     #[inline]
     fn drop(&mut self) {
-        unsafe { ffi::UnloadModel(self.raw.clone()) }
+        if release_context_object(ContextObject::Alloc(self.raw.meshes as usize)) {
+            for mesh in self.meshes() {
+                unload_extra_buffers(mesh.raw.vaoId);
+            }
+
+            unsafe { ffi::UnloadModel(self.raw.clone()) }
+        } else {
+            // the CPU side, like `UnloadModel` does
+            unsafe {
+                for mesh in self.meshes() {
+                    free_mesh_data(&mesh.raw);
+                }
+
+                for material in self.materials() {
+                    ffi::MemFree(material.raw.maps.cast());
+                }
+
+                for data in [
+                    self.raw.meshes.cast(),
+                    self.raw.materials.cast(),
+                    self.raw.meshMaterial.cast(),
+                    self.raw.bones.cast(),
+                    self.raw.bindPose.cast(),
+                ] {
+                    ffi::MemFree(data);
+                }
+            }
+        }
     }
 }
 
@@ -468,6 +2052,8 @@ impl Drop for Model {
 #[derive(Debug)]
 pub struct MaterialMap {
     /// Material map texture
+    ///
+    /// Can be raylib's shared default texture, never take it out of the `ManuallyDrop`
     pub texture: ManuallyDrop<Texture2D>,
     /// Material map color
     pub color: Color,
@@ -487,12 +2073,16 @@ pub struct Material {
 
 impl Material {
     /// Material shader
+    ///
+    /// Note that calling `ManuallyDrop::drop` on the returned value is a *very very bad* idea.
     #[inline]
     pub fn shader(&self) -> &ManuallyDrop<Shader> {
         unsafe { std::mem::transmute(&self.raw.shader) }
     }
 
     /// Material shader
+    ///
+    /// Note that calling `ManuallyDrop::drop` on the returned value is a *very very bad* idea.
     #[inline]
     pub fn shader_mut(&mut self) -> &mut ManuallyDrop<Shader> {
         unsafe { std::mem::transmute(&mut self.raw.shader) }
@@ -525,7 +2115,10 @@ impl Material {
     /// Load materials from model file
     #[inline]
     pub fn from_file(file_name: &str) -> Vec<Self> {
-        let file_name = CString::new(file_name).unwrap();
+        let file_name = match CString::new(file_name) {
+            Ok(file_name) => file_name,
+            Err(_) => return Vec::new(),
+        };
         let mut count: i32 = 0;
 
         let mats = unsafe { ffi::LoadMaterials(file_name.as_ptr(), &mut count as *mut _) };
@@ -536,7 +2129,7 @@ impl Material {
             let mat = unsafe { mats.add(i).read() };
 
             if unsafe { ffi::IsMaterialReady(mat.clone()) } {
-                vec.push(Self { raw: mat });
+                vec.push(Self::tracked(mat));
             }
         }
 
@@ -548,6 +2141,9 @@ impl Material {
     pub fn set_texture(&mut self, map_type: MaterialMapIndex, texture: Texture2D) {
         let texture = ManuallyDrop::new(texture);
 
+        // the material unloads the texture from now on
+        untrack_context_object(ContextObject::Texture(texture.raw.id));
+
         unsafe {
             ffi::SetMaterialTexture(&mut self.raw as *mut _, map_type as _, texture.raw.clone());
         }
@@ -574,6 +2170,14 @@ impl Material {
     /// * The raw object should be unique. Otherwise, make sure its clones don't outlive the newly created object.
     #[inline]
     pub unsafe fn from_raw(raw: ffi::Material) -> Self {
+        Self::tracked(raw)
+    }
+
+    /// Wrap a material of the current context, it owns its shader and textures through the `maps` array
+    #[inline]
+    fn tracked(raw: ffi::Material) -> Self {
+        track_context_object(ContextObject::Alloc(raw.maps as usize));
+
         Self { raw }
     }
 }
@@ -581,24 +2185,28 @@ impl Material {
 impl Default for Material {
     #[inline]
     fn default() -> Self {
-        Self {
-            raw: unsafe { ffi::LoadMaterialDefault() },
-        }
+        Self::tracked(unsafe { ffi::LoadMaterialDefault() })
     }
 }
 
 impl Drop for Material {
     #[inline]
     fn drop(&mut self) {
-        unsafe { ffi::UnloadMaterial(self.raw.clone()) }
+        if release_context_object(ContextObject::Alloc(self.raw.maps as usize)) {
+            unsafe { ffi::UnloadMaterial(self.raw.clone()) }
+        } else {
+            unsafe { ffi::MemFree(self.raw.maps.cast()) }
+        }
     }
 }
 
 /// Model animation
+///
+/// Not `#[repr(transparent)]`, it keeps the bounds from [`ModelAnimation::compute_bounds`] next to the raw animation.
 #[derive(Debug)]
-#[repr(transparent)]
 pub struct ModelAnimation {
     raw: ffi::ModelAnimation,
+    bounds: OnceCell<Option<AnimationBounds>>,
 }
 
 impl ModelAnimation {
@@ -614,44 +2222,165 @@ impl ModelAnimation {
         unsafe { std::slice::from_raw_parts_mut(self.raw.bones, self.raw.boneCount as _) }
     }
 
-    /// Poses array by frame
+    /// Number of bones
     #[inline]
-    pub fn frame_poses(&self) -> Vec<&[Transform]> {
-        let mut vec = Vec::new();
+    pub fn bone_count(&self) -> usize {
+        self.raw.boneCount as usize
+    }
+
+    /// Number of frames
+    #[inline]
+    pub fn frame_count(&self) -> usize {
+        self.raw.frameCount as usize
+    }
 
-        for i in 0..(self.raw.frameCount as usize) {
-            vec.push(unsafe {
+    /// Bone transforms of a frame, borrowed from the animation
+    #[inline]
+    pub fn frame_pose(&self, frame: usize) -> Option<&[Transform]> {
+        if frame < self.frame_count() {
+            Some(unsafe {
                 std::slice::from_raw_parts(
-                    self.raw.framePoses.add(i).read() as *const _,
-                    self.raw.boneCount as _,
+                    self.raw.framePoses.add(frame).read() as *const _,
+                    self.bone_count(),
                 )
             })
+        } else {
+            None
         }
-
-        vec
     }
 
-    /// Poses array by frame
+    /// Bone transforms of a frame, borrowed from the animation
     #[inline]
-    pub fn frame_poses_mut(&mut self) -> Vec<&mut [Transform]> {
-        let mut vec = Vec::new();
+    pub fn frame_pose_mut(&mut self, frame: usize) -> Option<&mut [Transform]> {
+        self.bounds.take();
 
-        for i in 0..(self.raw.frameCount as usize) {
-            vec.push(unsafe {
+        if frame < self.frame_count() {
+            Some(unsafe {
                 std::slice::from_raw_parts_mut(
-                    self.raw.framePoses.add(i).read() as *mut _,
-                    self.raw.boneCount as _,
+                    self.raw.framePoses.add(frame).read() as *mut _,
+                    self.bone_count(),
                 )
             })
+        } else {
+            None
         }
+    }
 
-        vec
+    /// Iterate over the poses by frame without allocating
+    #[inline]
+    pub fn iter_frame_poses(&self) -> impl Iterator<Item = &[Transform]> {
+        (0..self.frame_count()).filter_map(|i| self.frame_pose(i))
+    }
+
+    /// Iterate over the poses by frame without allocating
+    #[inline]
+    pub fn iter_frame_poses_mut(&mut self) -> impl Iterator<Item = &mut [Transform]> {
+        self.bounds.take();
+
+        let poses = self.raw.framePoses;
+        let bone_count = self.bone_count();
+
+        // every frame has its own allocation, so the slices never alias
+        (0..self.frame_count()).map(move |i| unsafe {
+            std::slice::from_raw_parts_mut(poses.add(i).read() as *mut _, bone_count)
+        })
+    }
+
+    /// Copy the bone transforms of a frame
+    #[inline]
+    pub fn pose(&self, frame: usize) -> Option<Pose> {
+        self.frame_pose(frame).map(|transforms| Pose {
+            transforms: transforms.to_vec(),
+        })
+    }
+
+    /// Overwrite the bone transforms of a frame
+    ///
+    /// Returns `false` if the frame doesn't exist or the bone count doesn't match
+    #[inline]
+    pub fn set_pose(&mut self, frame: usize, pose: &Pose) -> bool {
+        match self.frame_pose_mut(frame) {
+            Some(transforms) if transforms.len() == pose.transforms.len() => {
+                transforms.copy_from_slice(&pose.transforms);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Bounding box of a model at every frame of the animation, in model space
+    ///
+    /// Skins every mesh on the CPU for every frame. The first call does and caches the result,
+    /// later calls return it whatever model they pass, editing the poses clears it.
+    /// Meshes without bone data count with their static vertices.
+    /// `None` if the animation doesn't fit the model's skeleton or the model has no vertices.
+    #[inline]
+    pub fn compute_bounds(&self, model: &Model) -> Option<&AnimationBounds> {
+        self.bounds
+            .get_or_init(|| self.skinned_bounds(model))
+            .as_ref()
+    }
+
+    /// Skin every mesh for every frame and bound it
+    fn skinned_bounds(&self, model: &Model) -> Option<AnimationBounds> {
+        let bind_pose = model.bind_pose();
+
+        if bind_pose.len() != self.bone_count() {
+            return None;
+        }
+
+        let mut positions = Vec::new();
+        let mut frames = Vec::with_capacity(self.frame_count());
+
+        for pose in self.iter_frame_poses() {
+            let deltas: Vec<_> = bind_pose
+                .iter()
+                .zip(pose)
+                .map(|(bind, pose)| BoneDelta::new(bind, pose))
+                .collect();
+
+            let mut bounds: Option<BoundingBox> = None;
+
+            for mesh in model.meshes() {
+                if mesh.raw.vertices.is_null() {
+                    continue;
+                }
+
+                positions.clear();
+                positions.resize(mesh.raw.vertexCount as usize, vec3::new(0., 0., 0.));
+
+                let points = if skin_mesh(&mesh.raw, &deltas, &mut positions, None) {
+                    &positions[..]
+                } else {
+                    mesh.vertices()
+                };
+
+                if let Some(mesh_bounds) = BoundingBox::from_points(points) {
+                    bounds = Some(match bounds {
+                        Some(bounds) => bounds.merge(&mesh_bounds),
+                        None => mesh_bounds,
+                    });
+                }
+            }
+
+            frames.push(bounds?);
+        }
+
+        let max = frames
+            .iter()
+            .skip(1)
+            .fold(*frames.first()?, |max, bounds| max.merge(bounds));
+
+        Some(AnimationBounds { frames, max })
     }
 
     /// Load model animations from file
     #[inline]
     pub fn from_file(file_name: &str) -> Vec<Self> {
-        let file_name = CString::new(file_name).unwrap();
+        let file_name = match CString::new(file_name) {
+            Ok(file_name) => file_name,
+            Err(_) => return Vec::new(),
+        };
         let mut count: u32 = 0;
 
         let anims = unsafe { ffi::LoadModelAnimations(file_name.as_ptr(), &mut count as *mut _) };
@@ -661,11 +2390,13 @@ impl ModelAnimation {
         for i in 0..(count as usize) {
             vec.push(ModelAnimation {
                 raw: unsafe { anims.add(i).read() },
+                bounds: OnceCell::new(),
             })
         }
 
+        // the animations now own their bones and poses, only the array itself is freed
         unsafe {
-            ffi::UnloadModelAnimations(anims, count);
+            ffi::MemFree(anims as *mut _);
         }
 
         vec
@@ -682,6 +2413,8 @@ impl ModelAnimation {
     /// Take caution when cloning so it doesn't outlive the original
     #[inline]
     pub fn as_raw_mut(&mut self) -> &mut ffi::ModelAnimation {
+        self.bounds.take();
+
         &mut self.raw
     }
 
@@ -690,15 +2423,224 @@ impl ModelAnimation {
     /// # Safety
     /// * The raw object must be correctly initialized
     /// * The raw object should be unique. Otherwise, make sure its clones don't outlive the newly created object.
+    /// * Every frame of `framePoses` must be a separate allocation
     #[inline]
     pub unsafe fn from_raw(raw: ffi::ModelAnimation) -> Self {
-        Self { raw }
+        Self {
+            raw,
+            bounds: OnceCell::new(),
+        }
+    }
+}
+
+/// Bounding boxes of an animated model, from [`ModelAnimation::compute_bounds`]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AnimationBounds {
+    frames: Vec<BoundingBox>,
+    max: BoundingBox,
+}
+
+impl AnimationBounds {
+    /// Bounding box at a frame
+    #[inline]
+    pub fn frame(&self, frame: usize) -> Option<BoundingBox> {
+        self.frames.get(frame).copied()
+    }
+
+    /// Bounding box of every frame
+    #[inline]
+    pub fn frames(&self) -> &[BoundingBox] {
+        &self.frames
+    }
+
+    /// Bounding box containing all frames, for culling without tracking the frame
+    #[inline]
+    pub fn max(&self) -> BoundingBox {
+        self.max
+    }
+}
+
+/// Bone transforms of a single animation frame, copied out of a [`ModelAnimation`]
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Pose {
+    /// Transform of every bone
+    pub transforms: Vec<Transform>,
+}
+
+/// Movement of a bone from the bind pose to a pose
+struct BoneDelta {
+    bind_translation: Vector3,
+    translation: Vector3,
+    scale: Vector3,
+    rotation: Quaternion,
+}
+
+impl BoneDelta {
+    fn new(bind: &Transform, pose: &Transform) -> Self {
+        Self {
+            bind_translation: bind.translation,
+            translation: pose.translation,
+            scale: pose.scale,
+            rotation: quat_multiply(pose.rotation, quat_invert(bind.rotation)),
+        }
+    }
+
+    /// Move a bind pose position with the bone
+    #[inline]
+    fn apply(&self, position: Vector3) -> Vector3 {
+        let local = vec3::sub(position, self.bind_translation);
+        let scaled = vec3::new(
+            local.x * self.scale.x,
+            local.y * self.scale.y,
+            local.z * self.scale.z,
+        );
+
+        vec3::add(quat_rotate(self.rotation, scaled), self.translation)
+    }
+}
+
+fn quat_multiply(a: Quaternion, b: Quaternion) -> Quaternion {
+    let (a, aw, b, bw) = (a.v, a.s, b.v, b.s);
+
+    Quaternion {
+        v: vec3::new(
+            a.x * bw + aw * b.x + a.y * b.z - a.z * b.y,
+            a.y * bw + aw * b.y + a.z * b.x - a.x * b.z,
+            a.z * bw + aw * b.z + a.x * b.y - a.y * b.x,
+        ),
+        s: aw * bw - a.x * b.x - a.y * b.y - a.z * b.z,
+    }
+}
+
+fn quat_invert(q: Quaternion) -> Quaternion {
+    let length_sq = vec3::dot(q.v, q.v) + q.s * q.s;
+
+    if length_sq == 0. {
+        return q;
+    }
+
+    Quaternion {
+        v: vec3::scale(q.v, -1. / length_sq),
+        s: q.s / length_sq,
     }
 }
 
+fn quat_rotate(q: Quaternion, v: Vector3) -> Vector3 {
+    // v + 2w(q × v) + 2q × (q × v)
+    let t = vec3::scale(vec3::cross(q.v, v), 2.);
+
+    vec3::add(vec3::add(v, vec3::scale(t, q.s)), vec3::cross(q.v, t))
+}
+
+/// Skin the vertices (and normals) of a mesh the way raylib's `UpdateModelAnimation` does
+///
+/// Returns `false` if the mesh has no bone data. `normals` is skipped when the mesh has none.
+fn skin_mesh(
+    mesh: &ffi::Mesh,
+    deltas: &[BoneDelta],
+    positions: &mut [Vector3],
+    mut normals: Option<&mut [Vector3]>,
+) -> bool {
+    if mesh.boneIds.is_null() || mesh.boneWeights.is_null() || mesh.vertices.is_null() {
+        return false;
+    }
+
+    let count = mesh.vertexCount as usize;
+    let (vertices, bone_ids, bone_weights) = unsafe {
+        (
+            std::slice::from_raw_parts(mesh.vertices as *const Vector3, count),
+            std::slice::from_raw_parts(mesh.boneIds as *const u8, count * 4),
+            std::slice::from_raw_parts(mesh.boneWeights as *const f32, count * 4),
+        )
+    };
+    let base_normals = (!mesh.normals.is_null())
+        .then(|| unsafe { std::slice::from_raw_parts(mesh.normals as *const Vector3, count) });
+
+    for index in 0..count.min(positions.len()) {
+        let mut position = vec3::new(0., 0., 0.);
+        let mut normal = vec3::new(0., 0., 0.);
+
+        for influence in index * 4..index * 4 + 4 {
+            let weight = bone_weights[influence];
+
+            let delta = match deltas.get(bone_ids[influence] as usize) {
+                Some(delta) if weight != 0. => delta,
+                _ => continue,
+            };
+
+            position = vec3::add(position, vec3::scale(delta.apply(vertices[index]), weight));
+
+            if let Some(base_normals) = base_normals {
+                let rotated = quat_rotate(delta.rotation, base_normals[index]);
+                normal = vec3::add(normal, vec3::scale(rotated, weight));
+            }
+        }
+
+        positions[index] = position;
+
+        if base_normals.is_some() {
+            if let Some(target) = normals
+                .as_deref_mut()
+                .and_then(|normals| normals.get_mut(index))
+            {
+                *target = normal;
+            }
+        }
+    }
+
+    true
+}
+
 impl Drop for ModelAnimation {
     #[inline]
     fn drop(&mut self) {
         unsafe { ffi::UnloadModelAnimation(self.raw.clone()) }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::{from_columns, tests::assert_near};
+
+    fn quad() -> MeshData {
+        let mut data = MeshData::default();
+
+        data.add_quad([
+            vec3::new(0., 0., 0.),
+            vec3::new(1., 0., 0.),
+            vec3::new(1., 1., 0.),
+            vec3::new(0., 1., 0.),
+        ]);
+        data
+    }
+
+    #[test]
+    fn merge_translated_parts() {
+        // 90 degrees around Y, then moved by (5, 6, 7)
+        let transform = from_columns(&[
+            [0., 0., -1., 0.],
+            [0., 1., 0., 0.],
+            [1., 0., 0., 0.],
+            [5., 6., 7., 1.],
+        ]);
+
+        let mut merged = quad();
+        let mut part = quad();
+        part.transform(&transform);
+        merged.append(part);
+
+        assert_eq!(merged.vertices.len(), 8);
+        assert_eq!(merged.indices[6..], [4, 5, 6, 4, 6, 7]);
+
+        // the first part stays where it is
+        assert_near(merged.vertices[2], vec3::new(1., 1., 0.));
+        assert_near(merged.vertices[6], vec3::new(5., 7., 6.));
+
+        let normals = merged.normals.unwrap();
+        assert_near(normals[0], vec3::new(0., 0., 1.));
+        assert_near(normals[4], vec3::new(1., 0., 0.));
+    }
+}
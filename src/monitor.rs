@@ -0,0 +1,234 @@
+use crate::{ffi, math::Vector2};
+
+use std::{ffi::CStr, sync::Mutex};
+
+/// Window position and size saved before switching to exclusive fullscreen
+pub(crate) static WINDOWED_STATE: Mutex<Option<[i32; 4]>> = Mutex::new(None);
+
+/// glfw functions (raylib links glfw statically on desktop platforms)
+///
+/// Only a static raylib is known to provide them. With a shared one (`RAYLIB_LINK_KIND=dylib`)
+/// they're replaced by stubs finding no window, monitors or OpenGL functions.
+#[allow(non_snake_case)]
+pub(crate) mod glfw {
+    use core::ffi::{c_char, c_int, c_void};
+
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug)]
+    pub struct GLFWvidmode {
+        pub width: c_int,
+        pub height: c_int,
+        pub redBits: c_int,
+        pub greenBits: c_int,
+        pub blueBits: c_int,
+        pub refreshRate: c_int,
+    }
+
+    #[cfg(raylib_glfw)]
+    extern "C" {
+        pub fn glfwGetCurrentContext() -> *mut c_void;
+        pub fn glfwGetProcAddress(procname: *const c_char) -> *const c_void;
+        pub fn glfwGetMonitors(count: *mut c_int) -> *mut *mut c_void;
+        pub fn glfwGetVideoModes(monitor: *mut c_void, count: *mut c_int) -> *const GLFWvidmode;
+        pub fn glfwGetVideoMode(monitor: *mut c_void) -> *const GLFWvidmode;
+        pub fn glfwGetWindowPos(window: *mut c_void, xpos: *mut c_int, ypos: *mut c_int);
+        pub fn glfwGetWindowSize(window: *mut c_void, width: *mut c_int, height: *mut c_int);
+        pub fn glfwSetWindowMonitor(
+            window: *mut c_void,
+            monitor: *mut c_void,
+            xpos: c_int,
+            ypos: c_int,
+            width: c_int,
+            height: c_int,
+            refreshRate: c_int,
+        );
+    }
+
+    #[cfg(not(raylib_glfw))]
+    pub use self::stubs::*;
+
+    #[cfg(not(raylib_glfw))]
+    mod stubs {
+        use super::{c_char, c_int, c_void, GLFWvidmode};
+        use std::ptr;
+
+        pub unsafe fn glfwGetCurrentContext() -> *mut c_void {
+            ptr::null_mut()
+        }
+
+        pub unsafe fn glfwGetProcAddress(_procname: *const c_char) -> *const c_void {
+            ptr::null()
+        }
+
+        pub unsafe fn glfwGetMonitors(count: *mut c_int) -> *mut *mut c_void {
+            *count = 0;
+            ptr::null_mut()
+        }
+
+        pub unsafe fn glfwGetVideoModes(
+            _monitor: *mut c_void,
+            count: *mut c_int,
+        ) -> *const GLFWvidmode {
+            *count = 0;
+            ptr::null()
+        }
+
+        pub unsafe fn glfwGetVideoMode(_monitor: *mut c_void) -> *const GLFWvidmode {
+            ptr::null()
+        }
+
+        pub unsafe fn glfwGetWindowPos(_window: *mut c_void, _xpos: *mut c_int, _ypos: *mut c_int) {
+        }
+
+        pub unsafe fn glfwGetWindowSize(
+            _window: *mut c_void,
+            _width: *mut c_int,
+            _height: *mut c_int,
+        ) {
+        }
+
+        pub unsafe fn glfwSetWindowMonitor(
+            _window: *mut c_void,
+            _monitor: *mut c_void,
+            _xpos: c_int,
+            _ypos: c_int,
+            _width: c_int,
+            _height: c_int,
+            _refreshRate: c_int,
+        ) {
+        }
+    }
+}
+
+/// Video mode of a monitor
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VideoMode {
+    /// Width in screen coordinates
+    pub width: u32,
+    /// Height in screen coordinates
+    pub height: u32,
+    /// Bit depth of the red channel
+    pub red_bits: u32,
+    /// Bit depth of the green channel
+    pub green_bits: u32,
+    /// Bit depth of the blue channel
+    pub blue_bits: u32,
+    /// Refresh rate in Hz
+    pub refresh_rate: u32,
+}
+
+impl From<glfw::GLFWvidmode> for VideoMode {
+    #[inline]
+    fn from(mode: glfw::GLFWvidmode) -> Self {
+        Self {
+            width: mode.width as _,
+            height: mode.height as _,
+            red_bits: mode.redBits as _,
+            green_bits: mode.greenBits as _,
+            blue_bits: mode.blueBits as _,
+            refresh_rate: mode.refreshRate as _,
+        }
+    }
+}
+
+/// A connected monitor, get one with [`Raylib::get_monitor`](crate::Raylib::get_monitor)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Monitor {
+    pub(crate) index: u32,
+}
+
+impl Monitor {
+    /// Monitor index, as used by raylib
+    #[inline]
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// Human-readable, UTF-8 encoded name of the monitor
+    #[inline]
+    pub fn name(&self) -> String {
+        let name = unsafe { ffi::GetMonitorName(self.index as _) };
+
+        if name.is_null() {
+            String::new()
+        } else {
+            unsafe { CStr::from_ptr(name) }
+                .to_string_lossy()
+                .into_owned()
+        }
+    }
+
+    /// Monitor position
+    #[inline]
+    pub fn position(&self) -> Vector2 {
+        unsafe { ffi::GetMonitorPosition(self.index as _).into() }
+    }
+
+    /// Physical size in millimetres
+    #[inline]
+    pub fn physical_size(&self) -> (u32, u32) {
+        unsafe {
+            (
+                ffi::GetMonitorPhysicalWidth(self.index as _) as _,
+                ffi::GetMonitorPhysicalHeight(self.index as _) as _,
+            )
+        }
+    }
+
+    /// All video modes supported by the monitor, sorted by size and refresh rate (ascending)
+    #[inline]
+    pub fn video_modes(&self) -> Vec<VideoMode> {
+        let handle = match self.glfw_handle() {
+            Some(handle) => handle,
+            None => return Vec::new(),
+        };
+
+        let mut count = 0;
+        let modes = unsafe { glfw::glfwGetVideoModes(handle, &mut count as *mut _) };
+
+        if modes.is_null() {
+            return Vec::new();
+        }
+
+        unsafe { std::slice::from_raw_parts(modes, count as _) }
+            .iter()
+            .map(|mode| (*mode).into())
+            .collect()
+    }
+
+    /// Current video mode of the monitor
+    #[inline]
+    pub fn current_video_mode(&self) -> Option<VideoMode> {
+        let mode = unsafe { glfw::glfwGetVideoMode(self.glfw_handle()?) };
+
+        if mode.is_null() {
+            None
+        } else {
+            Some(unsafe { mode.read() }.into())
+        }
+    }
+
+    /// The closest supported video mode to the requested size and refresh rate
+    #[inline]
+    pub fn find_video_mode(&self, width: u32, height: u32, refresh_rate: u32) -> Option<VideoMode> {
+        self.video_modes().into_iter().min_by_key(|mode| {
+            (
+                mode.width.abs_diff(width) + mode.height.abs_diff(height),
+                mode.refresh_rate.abs_diff(refresh_rate),
+            )
+        })
+    }
+
+    #[inline]
+    pub(crate) fn glfw_handle(&self) -> Option<*mut core::ffi::c_void> {
+        let mut count = 0;
+        let monitors = unsafe { glfw::glfwGetMonitors(&mut count as *mut _) };
+
+        if monitors.is_null() || self.index >= count as u32 {
+            None
+        } else {
+            Some(unsafe { monitors.add(self.index as _).read() })
+        }
+    }
+}
@@ -0,0 +1,390 @@
+use crate::{
+    collision::{closest_point_triangle, mesh_triangles},
+    color::Color,
+    drawing::Draw,
+    math::{vec3, Matrix, Vector3},
+    model::Mesh,
+    texture::Image,
+};
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap},
+};
+
+/// Vertices closer than this are welded when looking for shared edges
+const WELD_DISTANCE: f32 = 1e-4;
+
+#[derive(Clone, Debug)]
+struct NavLink {
+    to: usize,
+    portal: [Vector3; 2],
+}
+
+/// Walkable triangle graph for pathfinding
+///
+/// Up is +Y. Triangles are connected when they share an edge (vertices are welded, so unindexed meshes work too).
+#[derive(Clone, Debug, Default)]
+pub struct NavMesh {
+    triangles: Vec<[Vector3; 3]>,
+    centers: Vec<Vector3>,
+    links: Vec<Vec<NavLink>>,
+}
+
+impl NavMesh {
+    /// Bake from triangles (counter-clockwise, in world space)
+    ///
+    /// Triangles steeper than `max_slope` degrees aren't walkable and are left out.
+    pub fn from_triangles(
+        triangles: impl IntoIterator<Item = [Vector3; 3]>,
+        max_slope: f32,
+    ) -> Self {
+        let min_normal_y = max_slope.to_radians().cos();
+
+        let triangles: Vec<[Vector3; 3]> = triangles
+            .into_iter()
+            .filter(|&[a, b, c]| {
+                let normal = vec3::cross(vec3::sub(b, a), vec3::sub(c, a));
+                let len = vec3::length(normal);
+
+                len > f32::EPSILON && normal.y / len >= min_normal_y
+            })
+            .collect();
+
+        let centers = triangles
+            .iter()
+            .map(|&[a, b, c]| vec3::scale(vec3::add(vec3::add(a, b), c), 1. / 3.))
+            .collect();
+
+        let key = |v: Vector3| {
+            (
+                (v.x / WELD_DISTANCE).round() as i64,
+                (v.y / WELD_DISTANCE).round() as i64,
+                (v.z / WELD_DISTANCE).round() as i64,
+            )
+        };
+
+        let mut edges = HashMap::new();
+
+        for (index, triangle) in triangles.iter().enumerate() {
+            for i in 0..3 {
+                let (a, b) = (triangle[i], triangle[(i + 1) % 3]);
+                let (ka, kb) = (key(a), key(b));
+                let edge = if ka < kb { (ka, kb) } else { (kb, ka) };
+
+                edges
+                    .entry(edge)
+                    .or_insert_with(Vec::new)
+                    .push((index, [a, b]));
+            }
+        }
+
+        let mut links = vec![Vec::new(); triangles.len()];
+
+        for sharing in edges.values() {
+            for (i, &(from, portal)) in sharing.iter().enumerate() {
+                for &(to, _) in sharing[i + 1..].iter() {
+                    if from != to {
+                        links[from].push(NavLink { to, portal });
+                        links[to].push(NavLink { to: from, portal });
+                    }
+                }
+            }
+        }
+
+        Self {
+            triangles,
+            centers,
+            links,
+        }
+    }
+
+    /// Bake from mesh vertex data (must be available on the CPU), transformed into world space
+    #[inline]
+    pub fn from_mesh(mesh: &Mesh, transform: Matrix, max_slope: f32) -> Self {
+        Self::from_triangles(mesh_triangles(mesh, transform), max_slope)
+    }
+
+    /// Bake from heightmap image data, with the same layout as [`Mesh::generate_heightmap`]
+    ///
+    /// Doesn't need a window, unlike generating the mesh.
+    pub fn from_heightmap(heightmap: &Image, size: Vector3, max_slope: f32) -> Self {
+        let (width, height) = (heightmap.width() as usize, heightmap.height() as usize);
+
+        if width < 2 || height < 2 {
+            return Self::default();
+        }
+
+        let colors = heightmap.load_colors();
+        let scale = vec3::new(
+            size.x / (width - 1) as f32,
+            size.y / 255.,
+            size.z / (height - 1) as f32,
+        );
+        let vertex = |x: usize, z: usize| {
+            let color = colors[x + z * width];
+            let gray = (color.r as f32 + color.g as f32 + color.b as f32) / 3.;
+
+            vec3::new(x as f32 * scale.x, gray * scale.y, z as f32 * scale.z)
+        };
+
+        let triangles = (0..height - 1).flat_map(|z| {
+            (0..width - 1).flat_map(move |x| {
+                [
+                    [vertex(x, z), vertex(x, z + 1), vertex(x + 1, z)],
+                    [vertex(x + 1, z), vertex(x, z + 1), vertex(x + 1, z + 1)],
+                ]
+            })
+        });
+
+        Self::from_triangles(triangles.collect::<Vec<_>>(), max_slope)
+    }
+
+    /// Number of walkable triangles
+    #[inline]
+    pub fn triangle_count(&self) -> usize {
+        self.triangles.len()
+    }
+
+    /// Walkable triangles
+    #[inline]
+    pub fn triangles(&self) -> &[[Vector3; 3]] {
+        &self.triangles
+    }
+
+    /// Indices of the triangles sharing an edge with a triangle
+    #[inline]
+    pub fn neighbors(&self, triangle: usize) -> impl Iterator<Item = usize> + '_ {
+        self.links
+            .get(triangle)
+            .into_iter()
+            .flatten()
+            .map(|link| link.to)
+    }
+
+    /// Triangle closest to a point and the closest point on it, `None` if the navmesh is empty
+    pub fn find_nearest(&self, point: Vector3) -> Option<(usize, Vector3)> {
+        self.triangles
+            .iter()
+            .enumerate()
+            .map(|(index, &triangle)| (index, closest_point_triangle(point, triangle)))
+            .min_by(|(_, a), (_, b)| {
+                vec3::length(vec3::sub(*a, point))
+                    .partial_cmp(&vec3::length(vec3::sub(*b, point)))
+                    .unwrap_or(Ordering::Equal)
+            })
+    }
+
+    /// Find the triangles to cross from one point to another with A*, `None` if they aren't connected
+    ///
+    /// The points are snapped to the closest triangles.
+    pub fn find_corridor(&self, start: Vector3, goal: Vector3) -> Option<Vec<usize>> {
+        let (start, _) = self.find_nearest(start)?;
+        let (goal, goal_point) = self.find_nearest(goal)?;
+
+        self.astar(start, goal, goal_point)
+    }
+
+    /// Find a path from one point to another, `None` if they aren't connected
+    ///
+    /// The points are snapped to the navmesh. The corridor found by A* is straightened with the funnel algorithm,
+    /// the path contains only the start, the corners it turns around and the goal.
+    pub fn find_path(&self, start: Vector3, goal: Vector3) -> Option<Vec<Vector3>> {
+        let (start_triangle, start) = self.find_nearest(start)?;
+        let (goal_triangle, goal) = self.find_nearest(goal)?;
+
+        let corridor = self.astar(start_triangle, goal_triangle, goal)?;
+
+        Some(self.string_pull(&corridor, start, goal))
+    }
+
+    /// Draw the triangle edges (and links between triangle centers)
+    pub fn draw_debug<D: Draw + ?Sized>(&self, d: &mut D, color: Color, link_color: Color) {
+        for &[a, b, c] in self.triangles.iter() {
+            d.draw_line_3d(a, b, color);
+            d.draw_line_3d(b, c, color);
+            d.draw_line_3d(c, a, color);
+        }
+
+        for (from, links) in self.links.iter().enumerate() {
+            for link in links.iter().filter(|link| link.to > from) {
+                d.draw_line_3d(self.centers[from], self.centers[link.to], link_color);
+            }
+        }
+    }
+
+    /// Draw a path returned by [`NavMesh::find_path`]
+    pub fn draw_path<D: Draw + ?Sized>(d: &mut D, path: &[Vector3], color: Color) {
+        for segment in path.windows(2) {
+            d.draw_line_3d(segment[0], segment[1], color);
+        }
+
+        for &point in path.iter() {
+            d.draw_sphere(point, 0.05, color);
+        }
+    }
+
+    fn astar(&self, start: usize, goal: usize, goal_point: Vector3) -> Option<Vec<usize>> {
+        let distance = |a: Vector3, b: Vector3| vec3::length(vec3::sub(a, b));
+
+        let mut cost = vec![f32::INFINITY; self.triangles.len()];
+        let mut came_from = vec![usize::MAX; self.triangles.len()];
+        let mut open = BinaryHeap::new();
+
+        cost[start] = 0.;
+        open.push(OpenNode {
+            score: distance(self.centers[start], goal_point),
+            index: start,
+        });
+
+        while let Some(OpenNode { score, index }) = open.pop() {
+            if index == goal {
+                let mut corridor = vec![goal];
+                let mut current = goal;
+
+                while current != start {
+                    current = came_from[current];
+                    corridor.push(current);
+                }
+
+                corridor.reverse();
+
+                return Some(corridor);
+            }
+
+            // stale entry, a cheaper one was already expanded
+            if score > cost[index] + distance(self.centers[index], goal_point) {
+                continue;
+            }
+
+            for link in self.links[index].iter() {
+                let new_cost = cost[index] + distance(self.centers[index], self.centers[link.to]);
+
+                if new_cost < cost[link.to] {
+                    cost[link.to] = new_cost;
+                    came_from[link.to] = index;
+                    open.push(OpenNode {
+                        score: new_cost + distance(self.centers[link.to], goal_point),
+                        index: link.to,
+                    });
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Simple stupid funnel algorithm, on the XZ plane
+    fn string_pull(&self, corridor: &[usize], start: Vector3, goal: Vector3) -> Vec<Vector3> {
+        let mut portals = vec![(start, start)];
+
+        for pair in corridor.windows(2) {
+            let link = self.links[pair[0]].iter().find(|link| link.to == pair[1]);
+
+            if let Some(&NavLink { portal: [a, b], .. }) = link {
+                if area2(self.centers[pair[0]], a, b) < 0. {
+                    portals.push((b, a));
+                } else {
+                    portals.push((a, b));
+                }
+            }
+        }
+
+        portals.push((goal, goal));
+
+        let mut path = vec![start];
+        let (mut apex, mut left, mut right) = (start, start, start);
+        let (mut apex_index, mut left_index, mut right_index) = (0, 0, 0);
+
+        let mut i = 1;
+        while i < portals.len() {
+            let (new_left, new_right) = portals[i];
+
+            if area2(apex, right, new_right) <= 0. {
+                if same_xz(apex, right) || area2(apex, left, new_right) > 0. {
+                    right = new_right;
+                    right_index = i;
+                } else {
+                    // right crossed over left, left becomes a corner of the path
+                    path.push(left);
+                    apex = left;
+                    apex_index = left_index;
+                    right = apex;
+                    right_index = apex_index;
+                    i = apex_index + 1;
+
+                    continue;
+                }
+            }
+
+            if area2(apex, left, new_left) >= 0. {
+                if same_xz(apex, left) || area2(apex, right, new_left) < 0. {
+                    left = new_left;
+                    left_index = i;
+                } else {
+                    // left crossed over right, right becomes a corner of the path
+                    path.push(right);
+                    apex = right;
+                    apex_index = right_index;
+                    left = apex;
+                    left_index = apex_index;
+                    i = apex_index + 1;
+
+                    continue;
+                }
+            }
+
+            i += 1;
+        }
+
+        if !path.last().is_some_and(|&last| same_xz(last, goal)) {
+            path.push(goal);
+        }
+
+        path
+    }
+}
+
+/// Twice the signed area of the triangle on the XZ plane
+#[inline]
+fn area2(a: Vector3, b: Vector3, c: Vector3) -> f32 {
+    (c.x - a.x) * (b.z - a.z) - (b.x - a.x) * (c.z - a.z)
+}
+
+#[inline]
+fn same_xz(a: Vector3, b: Vector3) -> bool {
+    (a.x - b.x).abs() < WELD_DISTANCE && (a.z - b.z).abs() < WELD_DISTANCE
+}
+
+/// A* open set entry, ordered so the binary heap pops the lowest score first
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct OpenNode {
+    pub(crate) score: f32,
+    pub(crate) index: usize,
+}
+
+impl PartialEq for OpenNode {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for OpenNode {}
+
+impl PartialOrd for OpenNode {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OpenNode {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .score
+            .partial_cmp(&self.score)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| other.index.cmp(&self.index))
+    }
+}
@@ -0,0 +1,624 @@
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    io,
+    marker::PhantomData,
+    net::{SocketAddr, ToSocketAddrs, UdpSocket},
+    time::{Duration, Instant},
+};
+
+/// Largest packet sent, small enough to not be fragmented on most networks
+pub const MAX_PACKET_SIZE: usize = 1200;
+
+/// Largest serialized message
+pub const MAX_MESSAGE_SIZE: usize = MAX_PACKET_SIZE - HEADER_SIZE - 5;
+
+/// Protocol id and packet kind
+const HEADER_SIZE: usize = 4 + 1;
+/// Reliable messages received this far ahead of the next expected one are dropped (and resent later)
+const RECEIVE_WINDOW: u32 = 1024;
+
+const CONNECT_INTERVAL: Duration = Duration::from_millis(250);
+const PING_INTERVAL: Duration = Duration::from_secs(1);
+const MIN_RESEND_INTERVAL: Duration = Duration::from_millis(100);
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+enum PacketKind {
+    Connect = 1,
+    Accept,
+    Deny,
+    Disconnect,
+    Ping,
+    Pong,
+    Unreliable,
+    Reliable,
+    Ack,
+}
+
+impl PacketKind {
+    fn from_u8(value: u8) -> Option<Self> {
+        Some(match value {
+            1 => Self::Connect,
+            2 => Self::Accept,
+            3 => Self::Deny,
+            4 => Self::Disconnect,
+            5 => Self::Ping,
+            6 => Self::Pong,
+            7 => Self::Unreliable,
+            8 => Self::Reliable,
+            9 => Self::Ack,
+            _ => return None,
+        })
+    }
+}
+
+/// Id of a connection, not reused for later connections
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PeerId(u32);
+
+/// How a message is sent
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Delivery {
+    /// Sent once, may be lost, duplicated or arrive out of order (e.g. positions sent every frame)
+    Unreliable,
+    /// Resent until it arrives, received once and in the order reliable messages were sent
+    Reliable,
+}
+
+/// Why a connection ended
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DisconnectReason {
+    /// Closed with [`Transport::disconnect`] on this side
+    Local,
+    /// Closed by the other side
+    Remote,
+    /// Nothing was received for longer than the timeout
+    TimedOut,
+    /// The server is full or doesn't accept connections
+    Denied,
+}
+
+/// Something that happened since the last [`Transport::poll`]
+#[derive(Clone, Debug, PartialEq)]
+pub enum NetEvent<M> {
+    /// A connection was established (an incoming one on servers, the one from [`Transport::connect`] on clients)
+    Connected(PeerId),
+    /// A connection ended, or never got established
+    Disconnected(PeerId, DisconnectReason),
+    /// A message arrived
+    Message(PeerId, M),
+}
+
+/// Connection statistics
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PeerStats {
+    /// Round trip time, smoothed over the last pings (`None` before the first one returned)
+    pub rtt: Option<Duration>,
+    /// Packets sent
+    pub packets_sent: u64,
+    /// Packets received
+    pub packets_received: u64,
+    /// Reliable messages sent again because they weren't acknowledged in time
+    pub resent: u64,
+    /// Reliable messages sent but not acknowledged yet
+    pub pending_reliable: usize,
+}
+
+#[derive(Debug)]
+enum PeerState {
+    Connecting { last_attempt: Instant },
+    Connected,
+}
+
+#[derive(Debug)]
+struct PendingMessage {
+    seq: u32,
+    packet: Vec<u8>,
+    sent: Instant,
+}
+
+#[derive(Debug)]
+struct Peer {
+    addr: SocketAddr,
+    state: PeerState,
+    last_received: Instant,
+    send_seq: u32,
+    pending: VecDeque<PendingMessage>,
+    receive_seq: u32,
+    out_of_order: BTreeMap<u32, Vec<u8>>,
+    ping_id: u32,
+    ping_sent: Option<Instant>,
+    last_ping: Instant,
+    stats: PeerStats,
+}
+
+impl Peer {
+    fn new(addr: SocketAddr, state: PeerState, now: Instant) -> Self {
+        Self {
+            addr,
+            state,
+            last_received: now,
+            send_seq: 0,
+            pending: VecDeque::new(),
+            receive_seq: 0,
+            out_of_order: BTreeMap::new(),
+            ping_id: 0,
+            ping_sent: None,
+            last_ping: now,
+            stats: PeerStats::default(),
+        }
+    }
+
+    #[inline]
+    fn is_connected(&self) -> bool {
+        matches!(self.state, PeerState::Connected)
+    }
+
+    /// Resend interval, a bit over the round trip time
+    #[inline]
+    fn resend_interval(&self) -> Duration {
+        self.stats
+            .rtt
+            .map_or(MIN_RESEND_INTERVAL * 2, |rtt| rtt * 3 / 2)
+            .max(MIN_RESEND_INTERVAL)
+    }
+}
+
+/// Small reliable UDP transport for game loops
+///
+/// Everything happens in [`Transport::poll`], which never blocks: call it once per frame,
+/// handle the returned events, and send messages of type `M` (serialized with bincode).
+/// Connections are kept alive with pings, which also measure the round trip time.
+/// There's no encryption or authentication, it's meant for jams and trusted networks.
+///
+/// A server listens with [`Transport::server`], clients connect with [`Transport::client`] and
+/// [`Transport::connect`]. Both sides have the same API after that.
+#[derive(Debug)]
+pub struct Transport<M> {
+    socket: UdpSocket,
+    protocol: u32,
+    accepting: bool,
+    max_peers: usize,
+    timeout: Duration,
+    next_id: u32,
+    peers: HashMap<PeerId, Peer>,
+    by_addr: HashMap<SocketAddr, PeerId>,
+    events: Vec<NetEvent<M>>,
+    _marker: PhantomData<fn(M) -> M>,
+}
+
+impl<M> Transport<M> {
+    fn bind(addr: impl ToSocketAddrs, protocol: u32, accepting: bool) -> io::Result<Self> {
+        let socket = UdpSocket::bind(addr)?;
+        socket.set_nonblocking(true)?;
+
+        Ok(Self {
+            socket,
+            protocol,
+            accepting,
+            max_peers: usize::MAX,
+            timeout: DEFAULT_TIMEOUT,
+            next_id: 0,
+            peers: HashMap::new(),
+            by_addr: HashMap::new(),
+            events: Vec::new(),
+            _marker: PhantomData,
+        })
+    }
+
+    /// Listen for up to `max_peers` connections on `addr` (e.g. `"0.0.0.0:7777"`)
+    ///
+    /// Only peers using the same `protocol` id can connect, use a different one per game and version.
+    #[inline]
+    pub fn server(addr: impl ToSocketAddrs, protocol: u32, max_peers: usize) -> io::Result<Self> {
+        let mut transport = Self::bind(addr, protocol, true)?;
+        transport.max_peers = max_peers;
+
+        Ok(transport)
+    }
+
+    /// Socket on any free port that doesn't accept connections, see [`Transport::connect`]
+    #[inline]
+    pub fn client(protocol: u32) -> io::Result<Self> {
+        Self::bind(("0.0.0.0", 0), protocol, false)
+    }
+
+    /// Set how long a connection lasts without receiving anything (5 seconds by default)
+    ///
+    /// Pings are sent at least every second, more often with timeouts under 4 seconds.
+    #[inline]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Address the socket is bound to
+    #[inline]
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.socket.local_addr()
+    }
+
+    /// Start connecting to a server
+    ///
+    /// [`NetEvent::Connected`] or [`NetEvent::Disconnected`] follows when it succeeds or fails.
+    pub fn connect(&mut self, addr: impl ToSocketAddrs) -> io::Result<PeerId> {
+        let addr = addr.to_socket_addrs()?.next().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "no address to connect to")
+        })?;
+
+        if let Some(&id) = self.by_addr.get(&addr) {
+            return Ok(id);
+        }
+
+        let now = Instant::now();
+        let id = self.add_peer(
+            addr,
+            PeerState::Connecting {
+                last_attempt: now - CONNECT_INTERVAL,
+            },
+            now,
+        );
+
+        Ok(id)
+    }
+
+    /// Close a connection, the other side is told about it
+    pub fn disconnect(&mut self, peer: PeerId) {
+        if let Some(removed) = self.peers.remove(&peer) {
+            self.by_addr.remove(&removed.addr);
+
+            // unreliable, the other side times out if all of them are lost
+            for _ in 0..3 {
+                self.send_raw(removed.addr, PacketKind::Disconnect, &[]);
+            }
+
+            self.events
+                .push(NetEvent::Disconnected(peer, DisconnectReason::Local));
+        }
+    }
+
+    /// Connected peers
+    #[inline]
+    pub fn peers(&self) -> impl Iterator<Item = PeerId> + '_ {
+        self.peers
+            .iter()
+            .filter(|(_, peer)| peer.is_connected())
+            .map(|(&id, _)| id)
+    }
+
+    /// Is a peer connected?
+    #[inline]
+    pub fn is_connected(&self, peer: PeerId) -> bool {
+        self.peers.get(&peer).is_some_and(Peer::is_connected)
+    }
+
+    /// Address of a peer
+    #[inline]
+    pub fn peer_addr(&self, peer: PeerId) -> Option<SocketAddr> {
+        self.peers.get(&peer).map(|peer| peer.addr)
+    }
+
+    /// Statistics of a connection
+    #[inline]
+    pub fn stats(&self, peer: PeerId) -> Option<PeerStats> {
+        self.peers.get(&peer).map(|peer| PeerStats {
+            pending_reliable: peer.pending.len(),
+            ..peer.stats
+        })
+    }
+
+    fn add_peer(&mut self, addr: SocketAddr, state: PeerState, now: Instant) -> PeerId {
+        let id = PeerId(self.next_id);
+        self.next_id = self.next_id.wrapping_add(1);
+
+        self.peers.insert(id, Peer::new(addr, state, now));
+        self.by_addr.insert(addr, id);
+
+        id
+    }
+
+    fn send_raw(&self, addr: SocketAddr, kind: PacketKind, body: &[u8]) -> bool {
+        self.send_packet(addr, &packet(self.protocol, kind, body))
+    }
+
+    fn send_packet(&self, addr: SocketAddr, packet: &[u8]) -> bool {
+        match self.socket.send_to(packet, addr) {
+            Ok(_) => true,
+            Err(_error) => {
+                #[cfg(debug_assertions)]
+                if _error.kind() != io::ErrorKind::WouldBlock {
+                    crate::core::warn(&format!("Transport: sending to {addr} failed: {_error}"));
+                }
+
+                false
+            }
+        }
+    }
+
+    fn remove_peer(&mut self, id: PeerId, reason: DisconnectReason) {
+        if let Some(peer) = self.peers.remove(&id) {
+            self.by_addr.remove(&peer.addr);
+            self.events.push(NetEvent::Disconnected(id, reason));
+        }
+    }
+}
+
+impl<M: Serialize + DeserializeOwned> Transport<M> {
+    /// Send a message to a connected peer
+    ///
+    /// Returns `false` if the peer isn't connected, or the message is larger than [`MAX_MESSAGE_SIZE`]
+    /// once serialized. Reliable messages are queued and sent again until they arrive.
+    pub fn send(&mut self, peer: PeerId, message: &M, delivery: Delivery) -> bool {
+        let payload = match bincode::serialize(message) {
+            Ok(payload) if payload.len() <= MAX_MESSAGE_SIZE => payload,
+            Ok(_) => {
+                #[cfg(debug_assertions)]
+                crate::core::warn("Transport: message larger than MAX_MESSAGE_SIZE dropped");
+
+                return false;
+            }
+            Err(_error) => {
+                #[cfg(debug_assertions)]
+                crate::core::warn(&format!(
+                    "Transport: message serialization failed: {_error}"
+                ));
+
+                return false;
+            }
+        };
+
+        let addr = match self.peers.get(&peer) {
+            Some(peer) if peer.is_connected() => peer.addr,
+            _ => return false,
+        };
+
+        match delivery {
+            Delivery::Unreliable => {
+                self.send_raw(addr, PacketKind::Unreliable, &payload);
+            }
+            Delivery::Reliable => {
+                let peer = self.peers.get_mut(&peer).unwrap();
+                let seq = peer.send_seq;
+                peer.send_seq = seq.wrapping_add(1);
+
+                let mut body = seq.to_le_bytes().to_vec();
+                body.extend_from_slice(&payload);
+
+                let packet = packet(self.protocol, PacketKind::Reliable, &body);
+
+                peer.pending.push_back(PendingMessage {
+                    seq,
+                    packet: packet.clone(),
+                    sent: Instant::now(),
+                });
+
+                self.send_packet(addr, &packet);
+            }
+        }
+
+        if let Some(peer) = self.peers.get_mut(&peer) {
+            peer.stats.packets_sent += 1;
+        }
+
+        true
+    }
+
+    /// Send a message to every connected peer
+    #[inline]
+    pub fn broadcast(&mut self, message: &M, delivery: Delivery) {
+        let peers: Vec<PeerId> = self.peers().collect();
+
+        for peer in peers {
+            self.send(peer, message, delivery);
+        }
+    }
+
+    /// Receive packets, resend what wasn't acknowledged, ping and time out connections
+    ///
+    /// Returns what happened since the last call, call it once per frame.
+    pub fn poll(&mut self) -> Vec<NetEvent<M>> {
+        let now = Instant::now();
+
+        self.receive(now);
+        self.maintain(now);
+
+        std::mem::take(&mut self.events)
+    }
+
+    fn receive(&mut self, now: Instant) {
+        let mut buffer = [0; MAX_PACKET_SIZE];
+
+        loop {
+            let (len, addr) = match self.socket.recv_from(&mut buffer) {
+                Ok(received) => received,
+                Err(error) if error.kind() == io::ErrorKind::WouldBlock => break,
+                // windows reports ICMP port unreachable of earlier sends this way
+                Err(error) if error.kind() == io::ErrorKind::ConnectionReset => continue,
+                Err(_error) => {
+                    #[cfg(debug_assertions)]
+                    crate::core::warn(&format!("Transport: receiving failed: {_error}"));
+
+                    break;
+                }
+            };
+
+            let packet = &buffer[..len];
+
+            if len < HEADER_SIZE || packet[..4] != self.protocol.to_le_bytes() {
+                continue;
+            }
+
+            if let Some(kind) = PacketKind::from_u8(packet[4]) {
+                self.handle_packet(addr, kind, &packet[HEADER_SIZE..], now);
+            }
+        }
+    }
+
+    fn handle_packet(&mut self, addr: SocketAddr, kind: PacketKind, body: &[u8], now: Instant) {
+        let id = match self.by_addr.get(&addr) {
+            Some(&id) => id,
+            None if kind == PacketKind::Connect => {
+                if self.accepting && self.peers.len() < self.max_peers {
+                    let id = self.add_peer(addr, PeerState::Connected, now);
+
+                    self.events.push(NetEvent::Connected(id));
+                    self.send_raw(addr, PacketKind::Accept, &[]);
+                } else {
+                    self.send_raw(addr, PacketKind::Deny, &[]);
+                }
+
+                return;
+            }
+            None => return,
+        };
+
+        let peer = self.peers.get_mut(&id).unwrap();
+        peer.last_received = now;
+        peer.stats.packets_received += 1;
+
+        // anything but a refusal from the server means the connection was accepted (the accept may be lost)
+        if !peer.is_connected() && !matches!(kind, PacketKind::Deny | PacketKind::Disconnect) {
+            peer.state = PeerState::Connected;
+            self.events.push(NetEvent::Connected(id));
+        }
+
+        match kind {
+            PacketKind::Connect => {
+                // our accept was lost
+                self.send_raw(addr, PacketKind::Accept, &[]);
+            }
+            PacketKind::Accept => {}
+            PacketKind::Deny => self.remove_peer(id, DisconnectReason::Denied),
+            PacketKind::Disconnect => self.remove_peer(id, DisconnectReason::Remote),
+            PacketKind::Ping => {
+                self.send_raw(addr, PacketKind::Pong, body);
+            }
+            PacketKind::Pong => {
+                if let (Some(ping_id), Some(sent)) = (read_u32(body), peer.ping_sent) {
+                    if ping_id == peer.ping_id {
+                        let sample = now - sent;
+
+                        peer.stats.rtt = Some(match peer.stats.rtt {
+                            Some(rtt) => (rtt * 7 + sample) / 8,
+                            None => sample,
+                        });
+                        peer.ping_sent = None;
+                    }
+                }
+            }
+            PacketKind::Unreliable => self.deliver(id, body),
+            PacketKind::Reliable => {
+                let Some(seq) = read_u32(body) else {
+                    return;
+                };
+
+                self.send_raw(addr, PacketKind::Ack, &seq.to_le_bytes());
+
+                let peer = self.peers.get_mut(&id).unwrap();
+                let ahead = seq.wrapping_sub(peer.receive_seq);
+
+                // older than the next expected one means it's a duplicate
+                if ahead == 0 {
+                    peer.receive_seq = peer.receive_seq.wrapping_add(1);
+
+                    let mut ready = vec![body[4..].to_vec()];
+
+                    while let Some(payload) = peer.out_of_order.remove(&peer.receive_seq) {
+                        peer.receive_seq = peer.receive_seq.wrapping_add(1);
+                        ready.push(payload);
+                    }
+
+                    for payload in ready {
+                        self.deliver(id, &payload);
+                    }
+                } else if ahead < RECEIVE_WINDOW {
+                    peer.out_of_order
+                        .entry(seq)
+                        .or_insert_with(|| body[4..].to_vec());
+                }
+            }
+            PacketKind::Ack => {
+                if let Some(seq) = read_u32(body) {
+                    peer.pending.retain(|pending| pending.seq != seq);
+                }
+            }
+        }
+    }
+
+    fn deliver(&mut self, id: PeerId, payload: &[u8]) {
+        match bincode::deserialize(payload) {
+            Ok(message) => self.events.push(NetEvent::Message(id, message)),
+            Err(_error) => {
+                #[cfg(debug_assertions)]
+                crate::core::warn(&format!("Transport: undecodable message dropped: {_error}"));
+            }
+        }
+    }
+
+    fn maintain(&mut self, now: Instant) {
+        // several pings per timeout, so short timeouts don't drop connections
+        let ping_interval = PING_INTERVAL.min(self.timeout / 4);
+        let mut timed_out = Vec::new();
+
+        for (&id, peer) in &mut self.peers {
+            match &mut peer.state {
+                PeerState::Connecting { last_attempt } => {
+                    if now - peer.last_received > self.timeout {
+                        timed_out.push(id);
+                    } else if now - *last_attempt >= CONNECT_INTERVAL {
+                        *last_attempt = now;
+                        let packet = packet(self.protocol, PacketKind::Connect, &[]);
+                        let _ = self.socket.send_to(&packet, peer.addr);
+                    }
+                }
+                PeerState::Connected => {
+                    if now - peer.last_received > self.timeout {
+                        timed_out.push(id);
+                        continue;
+                    }
+
+                    if now - peer.last_ping >= ping_interval {
+                        peer.last_ping = now;
+                        peer.ping_id = peer.ping_id.wrapping_add(1);
+                        peer.ping_sent = Some(now);
+
+                        let body = peer.ping_id.to_le_bytes();
+                        let packet = packet(self.protocol, PacketKind::Ping, &body);
+                        let _ = self.socket.send_to(&packet, peer.addr);
+                    }
+
+                    let interval = peer.resend_interval();
+
+                    for pending in &mut peer.pending {
+                        if now - pending.sent >= interval {
+                            pending.sent = now;
+                            peer.stats.resent += 1;
+
+                            let _ = self.socket.send_to(&pending.packet, peer.addr);
+                        }
+                    }
+                }
+            }
+        }
+
+        for id in timed_out {
+            self.remove_peer(id, DisconnectReason::TimedOut);
+        }
+    }
+}
+
+fn packet(protocol: u32, kind: PacketKind, body: &[u8]) -> Vec<u8> {
+    let mut packet = Vec::with_capacity(HEADER_SIZE + body.len());
+    packet.extend_from_slice(&protocol.to_le_bytes());
+    packet.push(kind as u8);
+    packet.extend_from_slice(body);
+
+    packet
+}
+
+#[inline]
+fn read_u32(bytes: &[u8]) -> Option<u32> {
+    Some(u32::from_le_bytes(bytes.get(..4)?.try_into().ok()?))
+}
@@ -0,0 +1,235 @@
+use crate::{
+    color::Color,
+    drawing::Draw,
+    math::{Camera2D, Rectangle, Vector2},
+    texture::Texture2D,
+};
+
+/// Directions a [`ParallaxLayer`] repeats in
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LayerRepeat {
+    /// Fill the whole view, e.g. a starfield
+    Both,
+    /// A horizontal strip, e.g. hills or a skyline
+    Horizontal,
+    /// A vertical strip, e.g. a shaft wall
+    Vertical,
+    /// A single image, e.g. the moon
+    None,
+}
+
+/// Layer of a [`ParallaxBackground`]
+///
+/// Repeated layers need their texture to use [`TextureWrap::Repeat`](crate::texture::TextureWrap::Repeat) (the default).
+#[derive(Debug)]
+pub struct ParallaxLayer {
+    /// Layer image
+    pub texture: Texture2D,
+    /// How much the layer follows the camera: 0 stays fixed on screen (far away), 1 moves with the world
+    pub scroll_factor: Vector2,
+    /// Constant movement in world units per second, e.g. drifting clouds
+    pub auto_scroll: Vector2,
+    /// Position of the texture's top left corner, in world units
+    pub offset: Vector2,
+    /// Texture scale
+    pub scale: f32,
+    /// Directions the texture repeats in
+    pub repeat: LayerRepeat,
+    /// Color the texture is tinted with
+    pub tint: Color,
+    scrolled: Vector2,
+}
+
+impl ParallaxLayer {
+    /// Create layer repeating in both directions, halfway between the screen and the world
+    #[inline]
+    pub fn new(texture: Texture2D) -> Self {
+        Self {
+            texture,
+            scroll_factor: Vector2 { x: 0.5, y: 0.5 },
+            auto_scroll: Vector2 { x: 0., y: 0. },
+            offset: Vector2 { x: 0., y: 0. },
+            scale: 1.,
+            repeat: LayerRepeat::Both,
+            tint: Color::WHITE,
+            scrolled: Vector2 { x: 0., y: 0. },
+        }
+    }
+
+    /// Set how much the layer follows the camera
+    #[inline]
+    pub fn with_scroll_factor(mut self, scroll_factor: impl Into<Vector2>) -> Self {
+        self.scroll_factor = scroll_factor.into();
+        self
+    }
+
+    /// Set the constant movement per second
+    #[inline]
+    pub fn with_auto_scroll(mut self, auto_scroll: impl Into<Vector2>) -> Self {
+        self.auto_scroll = auto_scroll.into();
+        self
+    }
+
+    /// Set the position of the texture's top left corner
+    #[inline]
+    pub fn with_offset(mut self, offset: impl Into<Vector2>) -> Self {
+        self.offset = offset.into();
+        self
+    }
+
+    /// Set the texture scale
+    #[inline]
+    pub fn with_scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Set the directions the texture repeats in
+    #[inline]
+    pub fn with_repeat(mut self, repeat: LayerRepeat) -> Self {
+        self.repeat = repeat;
+        self
+    }
+
+    /// Set the tint
+    #[inline]
+    pub fn with_tint(mut self, tint: Color) -> Self {
+        self.tint = tint;
+        self
+    }
+
+    fn update(&mut self, delta: f32) {
+        let (width, height) = self.tile_size();
+
+        self.scrolled.x += self.auto_scroll.x * delta;
+        self.scrolled.y += self.auto_scroll.y * delta;
+
+        // repeated directions wrap around, so the offset doesn't lose precision over time
+        if width > 0. && matches!(self.repeat, LayerRepeat::Both | LayerRepeat::Horizontal) {
+            self.scrolled.x = self.scrolled.x.rem_euclid(width);
+        }
+
+        if height > 0. && matches!(self.repeat, LayerRepeat::Both | LayerRepeat::Vertical) {
+            self.scrolled.y = self.scrolled.y.rem_euclid(height);
+        }
+    }
+
+    #[inline]
+    fn tile_size(&self) -> (f32, f32) {
+        (
+            self.texture.width() as f32 * self.scale,
+            self.texture.height() as f32 * self.scale,
+        )
+    }
+
+    fn draw<D: Draw + ?Sized>(&self, d: &mut D, camera: &Camera2D, view: Rectangle) {
+        let (tile_width, tile_height) = self.tile_size();
+        let (width, height) = (tile_width * camera.zoom, tile_height * camera.zoom);
+
+        if width <= 0. || height <= 0. {
+            return;
+        }
+
+        // screen position of the texture's top left corner
+        let x = camera.offset.x
+            + camera.zoom
+                * (self.offset.x + self.scrolled.x - camera.target.x * self.scroll_factor.x);
+        let y = camera.offset.y
+            + camera.zoom
+                * (self.offset.y + self.scrolled.y - camera.target.y * self.scroll_factor.y);
+
+        let (dest, uv_offset) = match self.repeat {
+            LayerRepeat::Both => (
+                view,
+                Vector2 {
+                    x: (view.x - x) / width,
+                    y: (view.y - y) / height,
+                },
+            ),
+            LayerRepeat::Horizontal => (
+                Rectangle::new(view.x, y, view.width, height),
+                Vector2 {
+                    x: (view.x - x) / width,
+                    y: 0.,
+                },
+            ),
+            LayerRepeat::Vertical => (
+                Rectangle::new(x, view.y, width, view.height),
+                Vector2 {
+                    x: 0.,
+                    y: (view.y - y) / height,
+                },
+            ),
+            LayerRepeat::None => (
+                Rectangle::new(x, y, width, height),
+                Vector2 { x: 0., y: 0. },
+            ),
+        };
+
+        let uv_scale = Vector2 {
+            x: dest.width / width,
+            y: dest.height / height,
+        };
+
+        d.draw_texture_scrolled(&self.texture, dest, uv_offset, uv_scale, self.tint);
+    }
+}
+
+/// Background of layers scrolling at different speeds behind a 2D camera
+///
+/// Layers are drawn in the order they were added, so add the farthest first.
+/// Draw it in screen space (outside of 2D mode) before the world, with the camera the world is drawn with.
+/// Camera rotation is ignored.
+#[derive(Debug, Default)]
+pub struct ParallaxBackground {
+    layers: Vec<ParallaxLayer>,
+}
+
+impl ParallaxBackground {
+    /// Create background without layers
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a layer in front of the others
+    #[inline]
+    pub fn add_layer(&mut self, layer: ParallaxLayer) {
+        self.layers.push(layer);
+    }
+
+    /// Add a layer in front of the others
+    #[inline]
+    pub fn with_layer(mut self, layer: ParallaxLayer) -> Self {
+        self.add_layer(layer);
+        self
+    }
+
+    /// Layers, farthest first
+    #[inline]
+    pub fn layers(&self) -> &[ParallaxLayer] {
+        &self.layers
+    }
+
+    /// Layers, farthest first
+    #[inline]
+    pub fn layers_mut(&mut self) -> &mut [ParallaxLayer] {
+        &mut self.layers
+    }
+
+    /// Move auto scrolling layers `delta` seconds forward
+    #[inline]
+    pub fn update(&mut self, delta: f32) {
+        for layer in self.layers.iter_mut() {
+            layer.update(delta);
+        }
+    }
+
+    /// Draw the layers into `view` (usually the whole screen)
+    pub fn draw<D: Draw + ?Sized>(&self, d: &mut D, camera: &Camera2D, view: Rectangle) {
+        for layer in self.layers.iter() {
+            layer.draw(d, camera, view);
+        }
+    }
+}
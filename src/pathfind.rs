@@ -0,0 +1,687 @@
+use crate::{
+    color::Color,
+    drawing::Draw,
+    math::{Rectangle, Vector2},
+    nav::OpenNode,
+};
+use std::collections::BinaryHeap;
+
+/// When a path may move diagonally
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DiagonalMovement {
+    /// Only horizontal and vertical moves
+    Never,
+    /// Diagonal moves when both cells next to the corner are walkable
+    #[default]
+    NoCornerCutting,
+    /// Diagonal moves whenever the target cell is walkable
+    Always,
+}
+
+/// Grid of walkable cells with movement costs
+///
+/// Moving into a cell costs its weight, times `sqrt(2)` for diagonal moves.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "GridMapData"))]
+pub struct GridMap {
+    width: u32,
+    height: u32,
+    /// `None` for blocked cells
+    weights: Vec<Option<f32>>,
+    /// Diagonal movement rule
+    pub diagonal: DiagonalMovement,
+}
+
+/// Deserialized [`GridMap`] before its size is checked
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct GridMapData {
+    width: u32,
+    height: u32,
+    weights: Vec<Option<f32>>,
+    diagonal: DiagonalMovement,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<GridMapData> for GridMap {
+    type Error = String;
+
+    fn try_from(data: GridMapData) -> Result<Self, Self::Error> {
+        let cells = (data.width as usize).checked_mul(data.height as usize);
+
+        if cells != Some(data.weights.len()) {
+            return Err(format!(
+                "a {}x{} grid needs {} weights, got {}",
+                data.width,
+                data.height,
+                data.width as u64 * data.height as u64,
+                data.weights.len()
+            ));
+        }
+
+        Ok(Self {
+            width: data.width,
+            height: data.height,
+            weights: data.weights,
+            diagonal: data.diagonal,
+        })
+    }
+}
+
+impl GridMap {
+    /// Create grid with all cells walkable with weight 1
+    #[inline]
+    pub fn new(width: u32, height: u32) -> Self {
+        Self::from_fn(width, height, |_, _| Some(1.))
+    }
+
+    /// Create grid with the weight of every cell, `None` for blocked cells
+    #[inline]
+    pub fn from_fn(
+        width: u32,
+        height: u32,
+        mut weight: impl FnMut(u32, u32) -> Option<f32>,
+    ) -> Self {
+        let weights = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| weight(x, y))
+            .collect();
+
+        Self {
+            width,
+            height,
+            weights,
+            diagonal: DiagonalMovement::default(),
+        }
+    }
+
+    /// Grid width in cells
+    #[inline]
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Grid height in cells
+    #[inline]
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Weight of a cell, `None` if it's blocked or outside the grid
+    #[inline]
+    pub fn weight(&self, x: u32, y: u32) -> Option<f32> {
+        self.index(x, y).and_then(|i| self.weights[i])
+    }
+
+    /// Set weight of a cell, `None` to block it (ignored outside the grid)
+    ///
+    /// Weights should be positive.
+    #[inline]
+    pub fn set_weight(&mut self, x: u32, y: u32, weight: Option<f32>) {
+        if let Some(i) = self.index(x, y) {
+            self.weights[i] = weight;
+        }
+    }
+
+    /// Check if a cell is inside the grid and not blocked
+    #[inline]
+    pub fn is_walkable(&self, x: u32, y: u32) -> bool {
+        self.weight(x, y).is_some()
+    }
+
+    /// Draw blocked cells, with the top left corner of the grid at `origin`
+    pub fn draw_debug<D: Draw + ?Sized>(
+        &self,
+        d: &mut D,
+        origin: Vector2,
+        cell_size: f32,
+        blocked_color: Color,
+    ) {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if !self.is_walkable(x, y) {
+                    d.draw_rectangle(cell_rect(origin, cell_size, (x, y)), blocked_color);
+                }
+            }
+        }
+    }
+
+    #[inline]
+    fn index(&self, x: u32, y: u32) -> Option<usize> {
+        if x < self.width && y < self.height {
+            Some(y as usize * self.width as usize + x as usize)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn cell(&self, index: usize) -> (u32, u32) {
+        (
+            (index % self.width as usize) as u32,
+            (index / self.width as usize) as u32,
+        )
+    }
+
+    /// Walkable neighbors of a cell and the length of the step to them
+    fn neighbors(&self, x: u32, y: u32) -> impl Iterator<Item = (usize, f32)> + '_ {
+        const OFFSETS: [(i32, i32); 8] = [
+            (1, 0),
+            (-1, 0),
+            (0, 1),
+            (0, -1),
+            (1, 1),
+            (-1, 1),
+            (1, -1),
+            (-1, -1),
+        ];
+
+        let walkable = move |dx: i32, dy: i32| {
+            let (nx, ny) = (x as i64 + dx as i64, y as i64 + dy as i64);
+
+            nx >= 0 && ny >= 0 && self.is_walkable(nx as _, ny as _)
+        };
+
+        OFFSETS.into_iter().filter_map(move |(dx, dy)| {
+            if !walkable(dx, dy) {
+                return None;
+            }
+
+            let diagonal = dx != 0 && dy != 0;
+            let allowed = match self.diagonal {
+                _ if !diagonal => true,
+                DiagonalMovement::Never => false,
+                DiagonalMovement::NoCornerCutting => walkable(dx, 0) && walkable(0, dy),
+                DiagonalMovement::Always => true,
+            };
+
+            if allowed {
+                let index = self.index((x as i32 + dx) as _, (y as i32 + dy) as _)?;
+                let step = if diagonal {
+                    std::f32::consts::SQRT_2
+                } else {
+                    1.
+                };
+
+                Some((index, step))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Lowest weight of any walkable cell, keeps the heuristic admissible
+    fn min_weight(&self) -> f32 {
+        self.weights
+            .iter()
+            .flatten()
+            .fold(f32::INFINITY, |min, &weight| min.min(weight))
+    }
+}
+
+/// Find the cheapest path between two cells with A*, including both, `None` if there's none
+#[inline]
+pub fn astar_grid(map: &GridMap, start: (u32, u32), goal: (u32, u32)) -> Option<Vec<(u32, u32)>> {
+    GridSearch::new(map, start, goal).path
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum CellState {
+    Unvisited,
+    Open,
+    Closed,
+}
+
+/// Result of an A* grid search, with the open and closed sets for debugging
+#[derive(Clone, Debug)]
+pub struct GridSearch {
+    width: u32,
+    path: Option<Vec<(u32, u32)>>,
+    cost: f32,
+    states: Vec<CellState>,
+}
+
+impl GridSearch {
+    /// Search for the cheapest path between two cells with A*
+    pub fn new(map: &GridMap, start: (u32, u32), goal: (u32, u32)) -> Self {
+        let len = map.weights.len();
+
+        let mut search = Self {
+            width: map.width,
+            path: None,
+            cost: f32::INFINITY,
+            states: vec![CellState::Unvisited; len],
+        };
+
+        let (start_index, goal_index) =
+            match (map.index(start.0, start.1), map.index(goal.0, goal.1)) {
+                (Some(start), Some(goal))
+                    if map.weights[start].is_some() && map.weights[goal].is_some() =>
+                {
+                    (start, goal)
+                }
+                _ => return search,
+            };
+
+        let min_weight = map.min_weight();
+        // octile distance
+        let heuristic = |(x, y): (u32, u32)| {
+            let dx = x.abs_diff(goal.0) as f32;
+            let dy = y.abs_diff(goal.1) as f32;
+
+            let distance = match map.diagonal {
+                DiagonalMovement::Never => dx + dy,
+                _ => dx.max(dy) + (std::f32::consts::SQRT_2 - 1.) * dx.min(dy),
+            };
+
+            distance * min_weight
+        };
+
+        let mut cost = vec![f32::INFINITY; len];
+        let mut came_from = vec![usize::MAX; len];
+        let mut open = BinaryHeap::new();
+
+        cost[start_index] = 0.;
+        search.states[start_index] = CellState::Open;
+        open.push(OpenNode {
+            score: heuristic(start),
+            index: start_index,
+        });
+
+        while let Some(OpenNode { index, .. }) = open.pop() {
+            if search.states[index] == CellState::Closed {
+                continue;
+            }
+
+            search.states[index] = CellState::Closed;
+
+            if index == goal_index {
+                let mut path = vec![map.cell(goal_index)];
+                let mut current = goal_index;
+
+                while current != start_index {
+                    current = came_from[current];
+                    path.push(map.cell(current));
+                }
+
+                path.reverse();
+
+                search.path = Some(path);
+                search.cost = cost[goal_index];
+
+                break;
+            }
+
+            let (x, y) = map.cell(index);
+
+            for (neighbor, step) in map.neighbors(x, y) {
+                if search.states[neighbor] == CellState::Closed {
+                    continue;
+                }
+
+                let new_cost = cost[index] + step * map.weights[neighbor].unwrap_or(f32::INFINITY);
+
+                if new_cost < cost[neighbor] {
+                    cost[neighbor] = new_cost;
+                    came_from[neighbor] = index;
+                    search.states[neighbor] = CellState::Open;
+                    open.push(OpenNode {
+                        score: new_cost + heuristic(map.cell(neighbor)),
+                        index: neighbor,
+                    });
+                }
+            }
+        }
+
+        search
+    }
+
+    /// Path found, including the start and goal cells
+    #[inline]
+    pub fn path(&self) -> Option<&[(u32, u32)]> {
+        self.path.as_deref()
+    }
+
+    /// Take the path found, including the start and goal cells
+    #[inline]
+    pub fn into_path(self) -> Option<Vec<(u32, u32)>> {
+        self.path
+    }
+
+    /// Total cost of the path, infinity if there's none
+    #[inline]
+    pub fn cost(&self) -> f32 {
+        self.cost
+    }
+
+    /// Check if a cell was in the open set when the search ended
+    #[inline]
+    pub fn is_open(&self, x: u32, y: u32) -> bool {
+        self.state(x, y) == Some(CellState::Open)
+    }
+
+    /// Check if a cell was expanded by the search
+    #[inline]
+    pub fn is_closed(&self, x: u32, y: u32) -> bool {
+        self.state(x, y) == Some(CellState::Closed)
+    }
+
+    /// Draw the open and closed sets and the path, with the top left corner of the grid at `origin`
+    pub fn draw_debug<D: Draw + ?Sized>(
+        &self,
+        d: &mut D,
+        origin: Vector2,
+        cell_size: f32,
+        open_color: Color,
+        closed_color: Color,
+        path_color: Color,
+    ) {
+        for (index, state) in self.states.iter().enumerate() {
+            let cell = (
+                (index % self.width as usize) as u32,
+                (index / self.width as usize) as u32,
+            );
+
+            match state {
+                CellState::Open => d.draw_rectangle(cell_rect(origin, cell_size, cell), open_color),
+                CellState::Closed => {
+                    d.draw_rectangle(cell_rect(origin, cell_size, cell), closed_color)
+                }
+                CellState::Unvisited => {}
+            }
+        }
+
+        if let Some(path) = &self.path {
+            for pair in path.windows(2) {
+                d.draw_line(
+                    cell_center(origin, cell_size, pair[0]),
+                    cell_center(origin, cell_size, pair[1]),
+                    path_color,
+                );
+            }
+        }
+    }
+
+    #[inline]
+    fn state(&self, x: u32, y: u32) -> Option<CellState> {
+        if x < self.width {
+            self.states
+                .get(y as usize * self.width as usize + x as usize)
+                .copied()
+        } else {
+            None
+        }
+    }
+}
+
+/// Cost to reach a goal cell and the direction to move in from every cell of a grid
+///
+/// Made for many agents heading to the same goal, each one follows the direction of the cell it's in.
+#[derive(Clone, Debug)]
+pub struct FlowField {
+    width: u32,
+    height: u32,
+    goal: (u32, u32),
+    /// Cost to reach the goal, infinity if it can't be reached
+    costs: Vec<f32>,
+    /// Next cell towards the goal
+    next: Vec<Option<usize>>,
+}
+
+impl FlowField {
+    /// Generate flow field towards a goal cell (Dijkstra from the goal)
+    pub fn new(map: &GridMap, goal: (u32, u32)) -> Self {
+        let len = map.weights.len();
+
+        let mut field = Self {
+            width: map.width,
+            height: map.height,
+            goal,
+            costs: vec![f32::INFINITY; len],
+            next: vec![None; len],
+        };
+
+        let goal_index = match map.index(goal.0, goal.1) {
+            Some(goal) if map.weights[goal].is_some() => goal,
+            _ => return field,
+        };
+
+        let mut open = BinaryHeap::new();
+        field.costs[goal_index] = 0.;
+        open.push(OpenNode {
+            score: 0.,
+            index: goal_index,
+        });
+
+        while let Some(OpenNode { score, index }) = open.pop() {
+            if score > field.costs[index] {
+                continue;
+            }
+
+            let (x, y) = map.cell(index);
+            let weight = map.weights[index].unwrap_or(f32::INFINITY);
+
+            // moves are symmetric, so the neighbors are also the cells that can step into this one
+            for (neighbor, step) in map.neighbors(x, y) {
+                let new_cost = score + step * weight;
+
+                if new_cost < field.costs[neighbor] {
+                    field.costs[neighbor] = new_cost;
+                    field.next[neighbor] = Some(index);
+                    open.push(OpenNode {
+                        score: new_cost,
+                        index: neighbor,
+                    });
+                }
+            }
+        }
+
+        field
+    }
+
+    /// Goal cell
+    #[inline]
+    pub fn goal(&self) -> (u32, u32) {
+        self.goal
+    }
+
+    /// Cost to reach the goal from a cell, `None` if it can't be reached
+    #[inline]
+    pub fn cost(&self, x: u32, y: u32) -> Option<f32> {
+        self.index(x, y)
+            .map(|i| self.costs[i])
+            .filter(|cost| cost.is_finite())
+    }
+
+    /// Next cell to move to from a cell, `None` for the goal and unreachable cells
+    #[inline]
+    pub fn next_cell(&self, x: u32, y: u32) -> Option<(u32, u32)> {
+        let next = self.next[self.index(x, y)?]?;
+
+        Some((
+            (next % self.width as usize) as u32,
+            (next / self.width as usize) as u32,
+        ))
+    }
+
+    /// Normalized direction to move in from a cell, `None` for the goal and unreachable cells
+    #[inline]
+    pub fn direction(&self, x: u32, y: u32) -> Option<Vector2> {
+        let (nx, ny) = self.next_cell(x, y)?;
+        let (dx, dy) = (nx as f32 - x as f32, ny as f32 - y as f32);
+        let len = (dx * dx + dy * dy).sqrt();
+
+        Some(Vector2 {
+            x: dx / len,
+            y: dy / len,
+        })
+    }
+
+    /// Draw the direction of every cell, with the top left corner of the grid at `origin`
+    pub fn draw_debug<D: Draw + ?Sized>(
+        &self,
+        d: &mut D,
+        origin: Vector2,
+        cell_size: f32,
+        color: Color,
+    ) {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if let Some(direction) = self.direction(x, y) {
+                    let center = cell_center(origin, cell_size, (x, y));
+                    let length = cell_size * 0.4;
+
+                    d.draw_line(
+                        center,
+                        Vector2 {
+                            x: center.x + direction.x * length,
+                            y: center.y + direction.y * length,
+                        },
+                        color,
+                    );
+                    d.draw_circle(center, cell_size * 0.08, color);
+                }
+            }
+        }
+    }
+
+    #[inline]
+    fn index(&self, x: u32, y: u32) -> Option<usize> {
+        if x < self.width && y < self.height {
+            Some(y as usize * self.width as usize + x as usize)
+        } else {
+            None
+        }
+    }
+}
+
+#[inline]
+fn cell_rect(origin: Vector2, cell_size: f32, (x, y): (u32, u32)) -> Rectangle {
+    Rectangle::new(
+        origin.x + x as f32 * cell_size,
+        origin.y + y as f32 * cell_size,
+        cell_size,
+        cell_size,
+    )
+}
+
+#[inline]
+fn cell_center(origin: Vector2, cell_size: f32, (x, y): (u32, u32)) -> Vector2 {
+    Vector2 {
+        x: origin.x + (x as f32 + 0.5) * cell_size,
+        y: origin.y + (y as f32 + 0.5) * cell_size,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn straight_path_visits_every_cell() {
+        let map = GridMap::new(5, 3);
+        let search = GridSearch::new(&map, (0, 1), (4, 1));
+
+        assert_eq!(
+            search.path(),
+            Some(&[(0, 1), (1, 1), (2, 1), (3, 1), (4, 1)][..])
+        );
+        assert_eq!(search.cost(), 4.);
+    }
+
+    #[test]
+    fn blocked_start_or_goal_has_no_path() {
+        let mut map = GridMap::new(4, 4);
+        map.set_weight(3, 3, None);
+
+        assert_eq!(astar_grid(&map, (0, 0), (3, 3)), None);
+        assert_eq!(astar_grid(&map, (3, 3), (0, 0)), None);
+        assert_eq!(astar_grid(&map, (0, 0), (4, 0)), None);
+
+        let search = GridSearch::new(&map, (0, 0), (3, 3));
+        assert_eq!(search.cost(), f32::INFINITY);
+    }
+
+    #[test]
+    fn walled_off_goal_has_no_path() {
+        let map = GridMap::from_fn(5, 5, |x, _| (x != 2).then_some(1.));
+
+        assert_eq!(astar_grid(&map, (0, 0), (4, 4)), None);
+    }
+
+    #[test]
+    fn expensive_cells_are_avoided() {
+        let mut map = GridMap::new(3, 3);
+        map.diagonal = DiagonalMovement::Never;
+        map.set_weight(1, 1, Some(10.));
+
+        let search = GridSearch::new(&map, (0, 1), (2, 1));
+        let path = search.path().unwrap();
+
+        assert!(!path.contains(&(1, 1)));
+        assert_eq!(path.len(), 5);
+        assert_eq!(search.cost(), 4.);
+    }
+
+    #[test]
+    fn cheap_detour_beats_the_direct_path() {
+        // the direct row costs 5 per cell, the row below 1
+        let mut map = GridMap::from_fn(5, 2, |_, y| Some(if y == 0 { 5. } else { 1. }));
+        map.diagonal = DiagonalMovement::Never;
+
+        let search = GridSearch::new(&map, (0, 0), (4, 0));
+
+        assert_eq!(
+            search.path(),
+            Some(&[(0, 0), (0, 1), (1, 1), (2, 1), (3, 1), (4, 1), (4, 0)][..])
+        );
+        assert_eq!(search.cost(), 10.);
+    }
+
+    #[test]
+    fn corners_are_not_cut() {
+        let mut map = GridMap::new(2, 2);
+        map.set_weight(1, 0, None);
+
+        map.diagonal = DiagonalMovement::NoCornerCutting;
+        assert_eq!(
+            astar_grid(&map, (0, 0), (1, 1)),
+            Some(vec![(0, 0), (0, 1), (1, 1)])
+        );
+
+        map.diagonal = DiagonalMovement::Always;
+        assert_eq!(astar_grid(&map, (0, 0), (1, 1)), Some(vec![(0, 0), (1, 1)]));
+    }
+
+    #[test]
+    fn diagonal_moves_cost_sqrt_2() {
+        let map = GridMap::new(4, 4);
+        let search = GridSearch::new(&map, (0, 0), (3, 3));
+
+        assert_eq!(search.path().map(<[_]>::len), Some(4));
+        assert!((search.cost() - 3. * std::f32::consts::SQRT_2).abs() < 1e-5);
+    }
+
+    #[test]
+    fn flow_field_leads_to_the_goal() {
+        let mut map = GridMap::new(3, 3);
+        map.diagonal = DiagonalMovement::Never;
+        map.set_weight(1, 1, None);
+
+        let field = FlowField::new(&map, (0, 0));
+        let mut cell = (2, 2);
+        let mut steps = 0;
+
+        while let Some(next) = field.next_cell(cell.0, cell.1) {
+            cell = next;
+            steps += 1;
+        }
+
+        assert_eq!(cell, (0, 0));
+        assert_eq!(steps, 4);
+        assert_eq!(field.cost(2, 2), Some(4.));
+        assert_eq!(field.cost(1, 1), None);
+    }
+}
@@ -0,0 +1,540 @@
+use crate::{ffi::Vector2 as RawVector2, math::Vector2};
+
+use std::{
+    marker::PhantomData,
+    mem::size_of,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+static INITIALIZED: AtomicBool = AtomicBool::new(false);
+
+/// Raw physac bindings
+#[allow(non_snake_case)]
+pub mod ffi {
+    use super::RawVector2;
+    use core::ffi::{c_double, c_float, c_int, c_uint};
+
+    /// Maximum number of vertices of a polygon shape
+    pub const PHYSAC_MAX_VERTICES: usize = 24;
+
+    /// Shape type
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    pub enum PhysicsShapeType {
+        /// Circle
+        Circle = 0,
+        /// Polygon
+        Polygon,
+    }
+
+    /// 2x2 matrix
+    #[repr(C)]
+    #[derive(Clone, Debug)]
+    pub struct Matrix2x2 {
+        pub m00: c_float,
+        pub m01: c_float,
+        pub m10: c_float,
+        pub m11: c_float,
+    }
+
+    /// Polygon vertices and normals
+    #[repr(C)]
+    #[derive(Clone, Debug)]
+    pub struct PhysicsVertexData {
+        pub vertexCount: c_uint,
+        pub positions: [RawVector2; PHYSAC_MAX_VERTICES],
+        pub normals: [RawVector2; PHYSAC_MAX_VERTICES],
+    }
+
+    /// Body shape
+    #[repr(C)]
+    #[derive(Clone, Debug)]
+    pub struct PhysicsShape {
+        pub r#type: PhysicsShapeType,
+        pub body: PhysicsBody,
+        pub vertexData: PhysicsVertexData,
+        pub radius: c_float,
+        pub transform: Matrix2x2,
+    }
+
+    /// Physics body data
+    #[repr(C)]
+    #[derive(Clone, Debug)]
+    pub struct PhysicsBodyData {
+        pub id: c_uint,
+        pub enabled: bool,
+        pub position: RawVector2,
+        pub velocity: RawVector2,
+        pub force: RawVector2,
+        pub angularVelocity: c_float,
+        pub torque: c_float,
+        pub orient: c_float,
+        pub inertia: c_float,
+        pub inverseInertia: c_float,
+        pub mass: c_float,
+        pub inverseMass: c_float,
+        pub staticFriction: c_float,
+        pub dynamicFriction: c_float,
+        pub restitution: c_float,
+        pub useGravity: bool,
+        pub isGrounded: bool,
+        pub freezeOrient: bool,
+        pub shape: PhysicsShape,
+    }
+
+    /// Physics body handle
+    pub type PhysicsBody = *mut PhysicsBodyData;
+
+    extern "C" {
+        /// Initializes physics system
+        pub fn InitPhysics();
+        /// Update physics system
+        pub fn UpdatePhysics();
+        /// Reset physics system (global variables)
+        pub fn ResetPhysics();
+        /// Close physics system and unload used memory
+        pub fn ClosePhysics();
+        /// Sets physics fixed time step in milliseconds. 1.666666 by default
+        pub fn SetPhysicsTimeStep(delta: c_double);
+        /// Sets physics global gravity force
+        pub fn SetPhysicsGravity(x: c_float, y: c_float);
+        /// Creates a new circle physics body with generic parameters
+        pub fn CreatePhysicsBodyCircle(
+            pos: RawVector2,
+            radius: c_float,
+            density: c_float,
+        ) -> PhysicsBody;
+        /// Creates a new rectangle physics body with generic parameters
+        pub fn CreatePhysicsBodyRectangle(
+            pos: RawVector2,
+            width: c_float,
+            height: c_float,
+            density: c_float,
+        ) -> PhysicsBody;
+        /// Creates a new polygon physics body with generic parameters
+        pub fn CreatePhysicsBodyPolygon(
+            pos: RawVector2,
+            radius: c_float,
+            sides: c_int,
+            density: c_float,
+        ) -> PhysicsBody;
+        /// Destroy a physics body
+        pub fn DestroyPhysicsBody(body: PhysicsBody);
+        /// Adds a force to a physics body
+        pub fn PhysicsAddForce(body: PhysicsBody, force: RawVector2);
+        /// Adds an angular force to a physics body
+        pub fn PhysicsAddTorque(body: PhysicsBody, amount: c_float);
+        /// Shatters a polygon shape physics body to little physics bodies with explosion force
+        pub fn PhysicsShatter(body: PhysicsBody, position: RawVector2, force: c_float);
+        /// Sets physics body shape transform based on radians parameter
+        pub fn SetPhysicsBodyRotation(body: PhysicsBody, radians: c_float);
+        /// Returns a physics body of the bodies pool at a specific index
+        pub fn GetPhysicsBody(index: c_int) -> PhysicsBody;
+        /// Returns the current amount of created physics bodies
+        pub fn GetPhysicsBodiesCount() -> c_int;
+        /// Returns the physics body shape type (PHYSICS_CIRCLE or PHYSICS_POLYGON)
+        pub fn GetPhysicsShapeType(index: c_int) -> c_int;
+        /// Returns the amount of vertices of a physics body shape
+        pub fn GetPhysicsShapeVerticesCount(index: c_int) -> c_int;
+        /// Returns transformed position of a body shape (body position + vertex transformed position)
+        pub fn GetPhysicsShapeVertex(body: PhysicsBody, vertex: c_int) -> RawVector2;
+
+        /// Struct sizes and offsets as compiled, see build/physac.c
+        pub static rust_raylib_physac_layout: [usize; 5];
+    }
+}
+
+pub use ffi::PhysicsShapeType;
+
+/// Compare the layout physac was compiled with to the Rust declarations
+fn check_layout() {
+    let declared = [
+        size_of::<ffi::Matrix2x2>(),
+        size_of::<ffi::PhysicsVertexData>(),
+        size_of::<ffi::PhysicsShape>(),
+        size_of::<ffi::PhysicsBodyData>(),
+        // shape is the last field
+        size_of::<ffi::PhysicsBodyData>() - size_of::<ffi::PhysicsShape>(),
+    ];
+    let compiled = unsafe { ffi::rust_raylib_physac_layout };
+
+    assert_eq!(
+        declared, compiled,
+        "physac's struct layout doesn't match src/physac.rs, physac 1.1 is expected"
+    );
+}
+
+/// Id of a physics body, stays valid until the body is destroyed
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PhysicsBodyId(u32);
+
+/// A physics body, borrowed from the [`Physics`] context
+#[derive(Debug)]
+#[repr(transparent)]
+pub struct PhysicsBody {
+    raw: ffi::PhysicsBodyData,
+}
+
+impl PhysicsBody {
+    /// Body id
+    #[inline]
+    pub fn id(&self) -> PhysicsBodyId {
+        PhysicsBodyId(self.raw.id)
+    }
+
+    /// Is the body simulated?
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.raw.enabled
+    }
+
+    /// Enable or disable simulation of the body
+    #[inline]
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.raw.enabled = enabled;
+    }
+
+    /// Position
+    #[inline]
+    pub fn position(&self) -> Vector2 {
+        self.raw.position.clone().into()
+    }
+
+    /// Set position
+    #[inline]
+    pub fn set_position(&mut self, position: Vector2) {
+        self.raw.position = position.into();
+    }
+
+    /// Velocity
+    #[inline]
+    pub fn velocity(&self) -> Vector2 {
+        self.raw.velocity.clone().into()
+    }
+
+    /// Set velocity
+    #[inline]
+    pub fn set_velocity(&mut self, velocity: Vector2) {
+        self.raw.velocity = velocity.into();
+    }
+
+    /// Angular velocity
+    #[inline]
+    pub fn angular_velocity(&self) -> f32 {
+        self.raw.angularVelocity
+    }
+
+    /// Set angular velocity
+    #[inline]
+    pub fn set_angular_velocity(&mut self, velocity: f32) {
+        self.raw.angularVelocity = velocity;
+    }
+
+    /// Rotation in radians
+    #[inline]
+    pub fn rotation(&self) -> f32 {
+        self.raw.orient
+    }
+
+    /// Set rotation in radians
+    #[inline]
+    pub fn set_rotation(&mut self, radians: f32) {
+        unsafe { ffi::SetPhysicsBodyRotation(self.as_ptr(), radians) }
+    }
+
+    /// Add a force
+    #[inline]
+    pub fn add_force(&mut self, force: Vector2) {
+        unsafe { ffi::PhysicsAddForce(self.as_ptr(), force.into()) }
+    }
+
+    /// Add an angular force
+    #[inline]
+    pub fn add_torque(&mut self, amount: f32) {
+        unsafe { ffi::PhysicsAddTorque(self.as_ptr(), amount) }
+    }
+
+    /// Mass (0 for static bodies)
+    #[inline]
+    pub fn mass(&self) -> f32 {
+        self.raw.mass
+    }
+
+    /// Static friction
+    #[inline]
+    pub fn static_friction(&self) -> f32 {
+        self.raw.staticFriction
+    }
+
+    /// Set static friction
+    #[inline]
+    pub fn set_static_friction(&mut self, friction: f32) {
+        self.raw.staticFriction = friction;
+    }
+
+    /// Dynamic friction
+    #[inline]
+    pub fn dynamic_friction(&self) -> f32 {
+        self.raw.dynamicFriction
+    }
+
+    /// Set dynamic friction
+    #[inline]
+    pub fn set_dynamic_friction(&mut self, friction: f32) {
+        self.raw.dynamicFriction = friction;
+    }
+
+    /// Restitution (bounciness)
+    #[inline]
+    pub fn restitution(&self) -> f32 {
+        self.raw.restitution
+    }
+
+    /// Set restitution (bounciness)
+    #[inline]
+    pub fn set_restitution(&mut self, restitution: f32) {
+        self.raw.restitution = restitution;
+    }
+
+    /// Is the body affected by gravity?
+    #[inline]
+    pub fn uses_gravity(&self) -> bool {
+        self.raw.useGravity
+    }
+
+    /// Set whether the body is affected by gravity
+    #[inline]
+    pub fn set_uses_gravity(&mut self, use_gravity: bool) {
+        self.raw.useGravity = use_gravity;
+    }
+
+    /// Is the body standing on another body?
+    #[inline]
+    pub fn is_grounded(&self) -> bool {
+        self.raw.isGrounded
+    }
+
+    /// Is the rotation frozen?
+    #[inline]
+    pub fn is_rotation_frozen(&self) -> bool {
+        self.raw.freezeOrient
+    }
+
+    /// Freeze or unfreeze the rotation
+    #[inline]
+    pub fn set_rotation_frozen(&mut self, frozen: bool) {
+        self.raw.freezeOrient = frozen;
+    }
+
+    /// Shape type
+    #[inline]
+    pub fn shape_type(&self) -> PhysicsShapeType {
+        self.raw.shape.r#type
+    }
+
+    /// Circle radius (circle shapes only)
+    #[inline]
+    pub fn radius(&self) -> f32 {
+        self.raw.shape.radius
+    }
+
+    /// Transformed polygon vertices (polygon shapes only)
+    #[inline]
+    pub fn vertices(&self) -> Vec<Vector2> {
+        if self.shape_type() != PhysicsShapeType::Polygon {
+            return Vec::new();
+        }
+
+        (0..self.raw.shape.vertexData.vertexCount)
+            .map(|i| unsafe { ffi::GetPhysicsShapeVertex(self.as_ptr(), i as _).into() })
+            .collect()
+    }
+
+    /// Get the 'raw' ffi type
+    #[inline]
+    pub fn as_raw(&self) -> &ffi::PhysicsBodyData {
+        &self.raw
+    }
+
+    /// Get the 'raw' ffi type
+    #[inline]
+    pub fn as_raw_mut(&mut self) -> &mut ffi::PhysicsBodyData {
+        &mut self.raw
+    }
+
+    #[inline]
+    fn as_ptr(&self) -> ffi::PhysicsBody {
+        &self.raw as *const _ as *mut _
+    }
+}
+
+/// Physac physics context, owns all bodies
+///
+/// Only one context can exist at a time.
+#[derive(Debug)]
+pub struct Physics(PhantomData<*const ()>);
+
+impl Physics {
+    /// Initialize physics system
+    ///
+    /// Panics if the compiled physac's structs don't match the declarations in [`ffi`],
+    /// i.e. `PHYSAC_INCLUDE_DIR` points to a physac other than 1.1.
+    #[inline]
+    pub fn init() -> Option<Self> {
+        if INITIALIZED.swap(true, Ordering::Relaxed) {
+            None
+        } else {
+            check_layout();
+
+            unsafe { ffi::InitPhysics() }
+
+            Some(Self(PhantomData))
+        }
+    }
+
+    /// Run the simulation steps for the time passed since the last update
+    #[inline]
+    pub fn update(&mut self) {
+        unsafe { ffi::UpdatePhysics() }
+    }
+
+    /// Destroy all bodies and reset the system
+    #[inline]
+    pub fn reset(&mut self) {
+        unsafe { ffi::ResetPhysics() }
+    }
+
+    /// Set fixed time step in milliseconds (1.666666 by default)
+    #[inline]
+    pub fn set_time_step(&mut self, delta: f64) {
+        unsafe { ffi::SetPhysicsTimeStep(delta) }
+    }
+
+    /// Set global gravity force
+    #[inline]
+    pub fn set_gravity(&mut self, gravity: Vector2) {
+        unsafe { ffi::SetPhysicsGravity(gravity.x, gravity.y) }
+    }
+
+    /// Create a circle body
+    #[inline]
+    pub fn create_circle(
+        &mut self,
+        position: Vector2,
+        radius: f32,
+        density: f32,
+    ) -> Option<PhysicsBodyId> {
+        Self::id_of(unsafe { ffi::CreatePhysicsBodyCircle(position.into(), radius, density) })
+    }
+
+    /// Create a rectangle body
+    #[inline]
+    pub fn create_rectangle(
+        &mut self,
+        position: Vector2,
+        width: f32,
+        height: f32,
+        density: f32,
+    ) -> Option<PhysicsBodyId> {
+        Self::id_of(unsafe {
+            ffi::CreatePhysicsBodyRectangle(position.into(), width, height, density)
+        })
+    }
+
+    /// Create a regular polygon body
+    #[inline]
+    pub fn create_polygon(
+        &mut self,
+        position: Vector2,
+        radius: f32,
+        sides: u32,
+        density: f32,
+    ) -> Option<PhysicsBodyId> {
+        Self::id_of(unsafe {
+            ffi::CreatePhysicsBodyPolygon(position.into(), radius, sides as _, density)
+        })
+    }
+
+    /// Destroy a body, returns false if it doesn't exist
+    #[inline]
+    pub fn destroy(&mut self, id: PhysicsBodyId) -> bool {
+        match self.find(id) {
+            Some(body) => {
+                unsafe { ffi::DestroyPhysicsBody(body) }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Shatter a polygon body into little bodies with an explosion force, the original body is destroyed
+    #[inline]
+    pub fn shatter(&mut self, id: PhysicsBodyId, position: Vector2, force: f32) -> bool {
+        match self.find(id) {
+            Some(body) => {
+                unsafe { ffi::PhysicsShatter(body, position.into(), force) }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Number of bodies
+    #[inline]
+    pub fn body_count(&self) -> usize {
+        unsafe { ffi::GetPhysicsBodiesCount() as _ }
+    }
+
+    /// Get a body by its id
+    #[inline]
+    pub fn body(&self, id: PhysicsBodyId) -> Option<&PhysicsBody> {
+        self.find(id)
+            .map(|body| unsafe { &*(body as *const PhysicsBody) })
+    }
+
+    /// Get a body by its id
+    #[inline]
+    pub fn body_mut(&mut self, id: PhysicsBodyId) -> Option<&mut PhysicsBody> {
+        self.find(id)
+            .map(|body| unsafe { &mut *(body as *mut PhysicsBody) })
+    }
+
+    /// All bodies
+    #[inline]
+    pub fn bodies(&self) -> impl Iterator<Item = &PhysicsBody> {
+        (0..self.body_count()).filter_map(|i| {
+            let body = unsafe { ffi::GetPhysicsBody(i as _) };
+
+            if body.is_null() {
+                None
+            } else {
+                Some(unsafe { &*(body as *const PhysicsBody) })
+            }
+        })
+    }
+
+    #[inline]
+    fn find(&self, id: PhysicsBodyId) -> Option<ffi::PhysicsBody> {
+        (0..self.body_count())
+            .map(|i| unsafe { ffi::GetPhysicsBody(i as _) })
+            .find(|body| !body.is_null() && unsafe { (**body).id } == id.0)
+    }
+
+    #[inline]
+    fn id_of(body: ffi::PhysicsBody) -> Option<PhysicsBodyId> {
+        if body.is_null() {
+            None
+        } else {
+            Some(PhysicsBodyId(unsafe { (*body).id }))
+        }
+    }
+}
+
+impl Drop for Physics {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe { ffi::ClosePhysics() }
+
+        INITIALIZED.store(false, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,427 @@
+use crate::{
+    collision::{check_collision_circle_rect, check_collision_circles},
+    color::Color,
+    drawing::Draw,
+    math::{Rectangle, Vector2},
+};
+
+use std::ops::Range;
+
+/// A point mass simulated with verlet integration
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PointMass {
+    /// Current position
+    pub position: Vector2,
+    /// Position in the previous step (the velocity is implied by the difference)
+    pub previous: Vector2,
+    /// Acceleration applied in the next step (in addition to gravity), reset after every step
+    pub acceleration: Vector2,
+    /// Inverse of the mass, 0 for pinned points
+    pub inv_mass: f32,
+    /// Collision radius
+    pub radius: f32,
+}
+
+impl PointMass {
+    /// Create new point mass at rest
+    #[inline]
+    pub fn new(position: Vector2, mass: f32, radius: f32) -> Self {
+        Self {
+            position,
+            previous: position,
+            acceleration: Vector2 { x: 0., y: 0. },
+            inv_mass: if mass > 0. { 1. / mass } else { 0. },
+            radius,
+        }
+    }
+
+    /// Is the point pinned in place?
+    #[inline]
+    pub fn is_pinned(&self) -> bool {
+        self.inv_mass == 0.
+    }
+
+    /// Velocity per step
+    #[inline]
+    pub fn velocity(&self) -> Vector2 {
+        Vector2 {
+            x: self.position.x - self.previous.x,
+            y: self.position.y - self.previous.y,
+        }
+    }
+}
+
+/// Keeps two points at a distance
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DistanceConstraint {
+    /// First point index
+    pub a: usize,
+    /// Second point index
+    pub b: usize,
+    /// Rest length
+    pub length: f32,
+    /// How strongly the length is enforced each iteration, 0..=1
+    pub stiffness: f32,
+}
+
+/// Static collider
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Collider2D {
+    /// Rectangle
+    Rect(Rectangle),
+    /// Circle
+    Circle {
+        /// Circle center
+        center: Vector2,
+        /// Circle radius
+        radius: f32,
+    },
+}
+
+/// Tiny verlet physics world: point masses, distance constraints and static colliders
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct World2D {
+    /// Gravity acceleration
+    pub gravity: Vector2,
+    /// Constraint solver iterations per step
+    pub iterations: u32,
+    /// Velocity kept each step, 0..=1
+    pub damping: f32,
+    points: Vec<PointMass>,
+    constraints: Vec<DistanceConstraint>,
+    colliders: Vec<Collider2D>,
+}
+
+impl Default for World2D {
+    #[inline]
+    fn default() -> Self {
+        Self::new(Vector2 { x: 0., y: 980. })
+    }
+}
+
+impl World2D {
+    /// Create new empty world
+    #[inline]
+    pub fn new(gravity: Vector2) -> Self {
+        Self {
+            gravity,
+            iterations: 8,
+            damping: 0.99,
+            points: Vec::new(),
+            constraints: Vec::new(),
+            colliders: Vec::new(),
+        }
+    }
+
+    /// Add a point, returns its index
+    #[inline]
+    pub fn add_point(&mut self, point: PointMass) -> usize {
+        self.points.push(point);
+        self.points.len() - 1
+    }
+
+    /// Connect two points with a constraint keeping their current distance, returns its index
+    #[inline]
+    pub fn add_constraint(&mut self, a: usize, b: usize, stiffness: f32) -> usize {
+        let (pa, pb) = (self.points[a].position, self.points[b].position);
+
+        self.constraints.push(DistanceConstraint {
+            a,
+            b,
+            length: ((pb.x - pa.x).powi(2) + (pb.y - pa.y).powi(2)).sqrt(),
+            stiffness,
+        });
+        self.constraints.len() - 1
+    }
+
+    /// Add a static collider
+    #[inline]
+    pub fn add_collider(&mut self, collider: Collider2D) {
+        self.colliders.push(collider);
+    }
+
+    /// Add a rope of `segments` links between two positions, returns the range of its point indices
+    #[inline]
+    pub fn add_rope(
+        &mut self,
+        start: Vector2,
+        end: Vector2,
+        segments: usize,
+        mass: f32,
+        stiffness: f32,
+    ) -> Range<usize> {
+        let first = self.points.len();
+        let segments = segments.max(1);
+
+        for i in 0..=segments {
+            let t = i as f32 / segments as f32;
+            let position = Vector2 {
+                x: start.x + (end.x - start.x) * t,
+                y: start.y + (end.y - start.y) * t,
+            };
+
+            let index = self.add_point(PointMass::new(position, mass, 0.));
+
+            if i > 0 {
+                self.add_constraint(index - 1, index, stiffness);
+            }
+        }
+
+        first..self.points.len()
+    }
+
+    /// Add a soft box: 4 corner points with edges and diagonals, returns the range of its point indices
+    #[inline]
+    pub fn add_soft_box(&mut self, rect: Rectangle, mass: f32, stiffness: f32) -> Range<usize> {
+        let first = self.points.len();
+        let corners = [
+            Vector2 {
+                x: rect.x,
+                y: rect.y,
+            },
+            Vector2 {
+                x: rect.x + rect.width,
+                y: rect.y,
+            },
+            Vector2 {
+                x: rect.x + rect.width,
+                y: rect.y + rect.height,
+            },
+            Vector2 {
+                x: rect.x,
+                y: rect.y + rect.height,
+            },
+        ];
+
+        for corner in corners {
+            self.add_point(PointMass::new(corner, mass / 4., 0.));
+        }
+
+        for (a, b) in [(0, 1), (1, 2), (2, 3), (3, 0), (0, 2), (1, 3)] {
+            self.add_constraint(first + a, first + b, stiffness);
+        }
+
+        first..self.points.len()
+    }
+
+    /// Pin a point in place (or unpin it with the given mass)
+    #[inline]
+    pub fn set_pinned(&mut self, index: usize, pinned: bool, mass: f32) {
+        self.points[index].inv_mass = if !pinned && mass > 0. { 1. / mass } else { 0. };
+    }
+
+    /// Points in the world
+    #[inline]
+    pub fn points(&self) -> &[PointMass] {
+        &self.points
+    }
+
+    /// Points in the world
+    #[inline]
+    pub fn points_mut(&mut self) -> &mut [PointMass] {
+        &mut self.points
+    }
+
+    /// Constraints in the world
+    #[inline]
+    pub fn constraints(&self) -> &[DistanceConstraint] {
+        &self.constraints
+    }
+
+    /// Constraints in the world
+    #[inline]
+    pub fn constraints_mut(&mut self) -> &mut [DistanceConstraint] {
+        &mut self.constraints
+    }
+
+    /// Static colliders in the world
+    #[inline]
+    pub fn colliders(&self) -> &[Collider2D] {
+        &self.colliders
+    }
+
+    /// Remove everything from the world
+    #[inline]
+    pub fn clear(&mut self) {
+        self.points.clear();
+        self.constraints.clear();
+        self.colliders.clear();
+    }
+
+    /// Advance the simulation by `dt` seconds (use a fixed time step for stability)
+    #[inline]
+    pub fn step(&mut self, dt: f32) {
+        let dt2 = dt * dt;
+
+        for point in self.points.iter_mut() {
+            if !point.is_pinned() {
+                let velocity = point.velocity();
+
+                point.previous = point.position;
+                point.position.x +=
+                    velocity.x * self.damping + (self.gravity.x + point.acceleration.x) * dt2;
+                point.position.y +=
+                    velocity.y * self.damping + (self.gravity.y + point.acceleration.y) * dt2;
+            }
+
+            point.acceleration = Vector2 { x: 0., y: 0. };
+        }
+
+        for _ in 0..self.iterations {
+            self.solve_constraints();
+            self.solve_collisions();
+        }
+    }
+
+    /// Draw points, constraints and colliders
+    #[inline]
+    pub fn draw_debug<D: Draw + ?Sized>(&self, d: &mut D, color: Color) {
+        for collider in self.colliders.iter() {
+            match *collider {
+                Collider2D::Rect(rect) => d.draw_rectangle_lines(rect, color),
+                Collider2D::Circle { center, radius } => {
+                    d.draw_circle_lines(center.x as _, center.y as _, radius, color)
+                }
+            }
+        }
+
+        for constraint in self.constraints.iter() {
+            d.draw_line(
+                self.points[constraint.a].position,
+                self.points[constraint.b].position,
+                color,
+            );
+        }
+
+        for point in self.points.iter() {
+            d.draw_circle(point.position, point.radius.max(2.), color);
+        }
+    }
+
+    fn solve_constraints(&mut self) {
+        for constraint in self.constraints.iter() {
+            let (a, b) = (self.points[constraint.a], self.points[constraint.b]);
+
+            let total = a.inv_mass + b.inv_mass;
+            if total == 0. {
+                continue;
+            }
+
+            let delta = Vector2 {
+                x: b.position.x - a.position.x,
+                y: b.position.y - a.position.y,
+            };
+            let distance = (delta.x * delta.x + delta.y * delta.y).sqrt();
+            if distance <= f32::EPSILON {
+                continue;
+            }
+
+            let diff = (distance - constraint.length) / distance * constraint.stiffness;
+            let (wa, wb) = (a.inv_mass / total, b.inv_mass / total);
+
+            let pa = &mut self.points[constraint.a].position;
+            pa.x += delta.x * diff * wa;
+            pa.y += delta.y * diff * wa;
+
+            let pb = &mut self.points[constraint.b].position;
+            pb.x -= delta.x * diff * wb;
+            pb.y -= delta.y * diff * wb;
+        }
+    }
+
+    fn solve_collisions(&mut self) {
+        for point in self.points.iter_mut().filter(|point| !point.is_pinned()) {
+            for collider in self.colliders.iter() {
+                if let Some(pushed) = push_out(point.position, point.radius, collider) {
+                    point.position = pushed;
+                }
+            }
+        }
+    }
+}
+
+/// Position of a circle moved out of a collider, `None` if they don't overlap
+fn push_out(position: Vector2, radius: f32, collider: &Collider2D) -> Option<Vector2> {
+    match *collider {
+        Collider2D::Rect(rect) => {
+            if !check_collision_circle_rect(position, radius, rect) {
+                return None;
+            }
+
+            let closest = Vector2 {
+                x: position.x.clamp(rect.x, rect.x + rect.width),
+                y: position.y.clamp(rect.y, rect.y + rect.height),
+            };
+            let (dx, dy) = (position.x - closest.x, position.y - closest.y);
+            let distance = (dx * dx + dy * dy).sqrt();
+
+            if distance > f32::EPSILON {
+                // center outside the rectangle
+                let push = (radius - distance) / distance;
+
+                Some(Vector2 {
+                    x: position.x + dx * push,
+                    y: position.y + dy * push,
+                })
+            } else {
+                // center inside, move to the nearest edge
+                let left = position.x - rect.x;
+                let right = rect.x + rect.width - position.x;
+                let top = position.y - rect.y;
+                let bottom = rect.y + rect.height - position.y;
+
+                let min = left.min(right).min(top).min(bottom);
+
+                Some(if min == left {
+                    Vector2 {
+                        x: rect.x - radius,
+                        y: position.y,
+                    }
+                } else if min == right {
+                    Vector2 {
+                        x: rect.x + rect.width + radius,
+                        y: position.y,
+                    }
+                } else if min == top {
+                    Vector2 {
+                        x: position.x,
+                        y: rect.y - radius,
+                    }
+                } else {
+                    Vector2 {
+                        x: position.x,
+                        y: rect.y + rect.height + radius,
+                    }
+                })
+            }
+        }
+        Collider2D::Circle {
+            center,
+            radius: circle_radius,
+        } => {
+            if !check_collision_circles(position, radius, center, circle_radius) {
+                return None;
+            }
+
+            let (dx, dy) = (position.x - center.x, position.y - center.y);
+            let distance = (dx * dx + dy * dy).sqrt();
+            let target = radius + circle_radius;
+
+            Some(if distance > f32::EPSILON {
+                Vector2 {
+                    x: center.x + dx / distance * target,
+                    y: center.y + dy / distance * target,
+                }
+            } else {
+                Vector2 {
+                    x: center.x,
+                    y: center.y - target,
+                }
+            })
+        }
+    }
+}
@@ -0,0 +1,791 @@
+use crate::{
+    color::Color,
+    drawing::Draw,
+    error::RaylibError,
+    ffi,
+    math::{Rectangle, Vector2},
+    shader::{Shader, ShaderLoc},
+    texture::{Image, PixelFormat, RenderTexture2D, Texture2D, TextureFilter},
+};
+
+/// Most colors a [`Palette`] can hold
+pub const MAX_PALETTE_COLORS: usize = 256;
+
+const PALETTE_FS: &str = r#"#version 330
+in vec2 fragTexCoord;
+in vec4 fragColor;
+
+uniform sampler2D texture0;
+uniform sampler2D paletteTexture;
+uniform int paletteSize;
+uniform int ditherLevels;
+uniform float ditherStrength;
+uniform float pixelSize;
+uniform vec2 resolution;
+
+out vec4 finalColor;
+
+// ordered dithering threshold in -0.5..0.5, the matrix is 2^levels wide
+float bayer(ivec2 p, int levels)
+{
+    int value = 0;
+
+    for (int i = 0; i < levels; i++)
+    {
+        int x = (p.x >> i) & 1;
+        int y = (p.y >> i) & 1;
+        value |= (2*(x ^ y) + y) << (2*(levels - 1 - i));
+    }
+
+    float size = float(1 << levels);
+    return (float(value) + 0.5)/(size*size) - 0.5;
+}
+
+void main()
+{
+    vec2 pixel = floor(fragTexCoord*resolution/pixelSize);
+    vec2 uv = (pixel + 0.5)*pixelSize/resolution;
+    vec4 source = texture(texture0, uv);
+
+    vec3 color = source.rgb;
+    if (ditherLevels > 0) color += bayer(ivec2(pixel), ditherLevels)*ditherStrength;
+
+    vec3 nearest = texelFetch(paletteTexture, ivec2(0, 0), 0).rgb;
+    float nearestDistance = 1e9;
+
+    for (int i = 0; i < paletteSize; i++)
+    {
+        vec3 entry = texelFetch(paletteTexture, ivec2(i, 0), 0).rgb;
+        vec3 delta = entry - color;
+        float distance = dot(delta, delta);
+
+        if (distance < nearestDistance)
+        {
+            nearest = entry;
+            nearestDistance = distance;
+        }
+    }
+
+    finalColor = vec4(nearest, source.a)*fragColor;
+}
+"#;
+
+const SRGB_FS: &str = r#"#version 330
+in vec2 fragTexCoord;
+in vec4 fragColor;
+
+uniform sampler2D texture0;
+
+out vec4 finalColor;
+
+void main()
+{
+    vec4 source = texture(texture0, fragTexCoord)*fragColor;
+    vec3 color = clamp(source.rgb, 0.0, 1.0);
+
+    // exact sRGB transfer function
+    vec3 low = color*12.92;
+    vec3 high = 1.055*pow(color, vec3(1.0/2.4)) - 0.055;
+
+    finalColor = vec4(mix(low, high, step(0.0031308, color)), source.a);
+}
+"#;
+
+const LUMINANCE_FS: &str = r#"#version 330
+in vec2 fragTexCoord;
+
+uniform sampler2D texture0;
+
+out vec4 finalColor;
+
+void main()
+{
+    vec3 color = texture(texture0, fragTexCoord).rgb;
+    float luminance = dot(color, vec3(0.2126, 0.7152, 0.0722));
+
+    // averaged by the mipmaps, a log average isn't dominated by a few bright pixels
+    finalColor = vec4(log(max(luminance, 1e-4)), 0.0, 0.0, 1.0);
+}
+"#;
+
+const ADAPT_FS: &str = r#"#version 330
+uniform sampler2D luminanceTexture;
+uniform sampler2D previousTexture;
+uniform float level;
+uniform float adaptation;
+uniform vec2 range;
+
+out vec4 finalColor;
+
+void main()
+{
+    float average = exp(textureLod(luminanceTexture, vec2(0.5), level).r);
+    float target = clamp(average, range.x, range.y);
+    float previous = texture(previousTexture, vec2(0.5)).r;
+
+    finalColor = vec4(mix(previous, target, adaptation), 0.0, 0.0, 1.0);
+}
+"#;
+
+const TONE_MAP_FS: &str = r#"#version 330
+in vec2 fragTexCoord;
+in vec4 fragColor;
+
+uniform sampler2D texture0;
+uniform sampler2D exposureTexture;
+uniform int mode;
+uniform float exposure;
+uniform int autoExposure;
+uniform float key;
+uniform int srgbOutput;
+
+out vec4 finalColor;
+
+// Krzysztof Narkowicz's fit of the ACES filmic curve
+vec3 aces(vec3 x)
+{
+    return (x*(2.51*x + 0.03))/(x*(2.43*x + 0.59) + 0.14);
+}
+
+void main()
+{
+    vec4 source = texture(texture0, fragTexCoord);
+
+    float scale = exposure;
+    if (autoExposure == 1) scale *= key/max(texture(exposureTexture, vec2(0.5)).r, 1e-4);
+
+    vec3 color = max(source.rgb*scale, 0.0);
+
+    if (mode == 1) color = color/(1.0 + color);
+    else if (mode == 2) color = aces(color);
+
+    color = clamp(color, 0.0, 1.0);
+
+    if (srgbOutput == 1)
+    {
+        color = mix(color*12.92, 1.055*pow(color, vec3(1.0/2.4)) - 0.055, step(0.0031308, color));
+    }
+
+    finalColor = vec4(color, source.a)*fragColor;
+}
+"#;
+
+/// Set of colors to constrain an image to
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Palette {
+    colors: Vec<Color>,
+}
+
+impl Palette {
+    /// Create palette from colors, only the first [`MAX_PALETTE_COLORS`] are used
+    #[inline]
+    pub fn new(colors: &[Color]) -> Self {
+        Self {
+            colors: colors[..colors.len().min(MAX_PALETTE_COLORS)].to_vec(),
+        }
+    }
+
+    /// Palette of the distinct colors of an image (e.g. a palette swatch), up to `max_size`
+    #[inline]
+    pub fn from_image(image: &Image, max_size: usize) -> Self {
+        Self::new(&image.load_palette(max_size.min(MAX_PALETTE_COLORS)))
+    }
+
+    /// Palette colors
+    #[inline]
+    pub fn colors(&self) -> &[Color] {
+        &self.colors
+    }
+
+    /// Number of colors
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.colors.len()
+    }
+
+    /// Check if the palette has no colors
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.colors.is_empty()
+    }
+}
+
+/// Ordered dithering pattern
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DitherMode {
+    /// Plain nearest color, flat areas band
+    None,
+    /// 2x2 Bayer matrix, coarse pattern
+    Bayer2x2,
+    /// 4x4 Bayer matrix
+    Bayer4x4,
+    /// 8x8 Bayer matrix, finest gradients
+    Bayer8x8,
+}
+
+impl DitherMode {
+    #[inline]
+    fn levels(self) -> i32 {
+        match self {
+            Self::None => 0,
+            Self::Bayer2x2 => 1,
+            Self::Bayer4x4 => 2,
+            Self::Bayer8x8 => 3,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct PaletteLocations {
+    palette_texture: Option<ShaderLoc>,
+    resolution: Option<ShaderLoc>,
+    dither_strength: Option<ShaderLoc>,
+    pixel_size: Option<ShaderLoc>,
+}
+
+/// Post effect that pixelates the frame and quantizes it to a palette, with ordered dithering
+///
+/// Draw the scene to a render texture, then draw it with [`PalettePostFx::draw`].
+/// The shader targets desktop OpenGL 3.3.
+#[derive(Debug)]
+pub struct PalettePostFx {
+    shader: Shader,
+    palette: Texture2D,
+    locs: PaletteLocations,
+}
+
+impl PalettePostFx {
+    /// Load the effect for a palette, fails if the palette is empty or the shader fails
+    ///
+    /// Dithering strength defaults to a fraction of the average spacing between palette colors,
+    /// with a pixel size of 1 (no pixelation).
+    pub fn new(palette: &Palette, dither: DitherMode) -> Result<Self, RaylibError> {
+        if palette.is_empty() {
+            return Err(RaylibError::InvalidArgument("the palette is empty".into()));
+        }
+
+        let mut image = Image::generate_color(palette.len() as u32, 1, Color::BLACK);
+
+        for (x, color) in palette.colors().iter().enumerate() {
+            image.draw_pixel(Vector2 { x: x as f32, y: 0. }, *color);
+        }
+
+        let palette_texture = Texture2D::from_image(&image)?;
+        let mut shader = Shader::from_memory(None, Some(PALETTE_FS))?;
+
+        let locs = PaletteLocations {
+            palette_texture: shader.get_location("paletteTexture"),
+            resolution: shader.get_location("resolution"),
+            dither_strength: shader.get_location("ditherStrength"),
+            pixel_size: shader.get_location("pixelSize"),
+        };
+
+        if let Some(loc) = shader.get_location("paletteSize") {
+            shader.set_value(loc, palette.len() as i32);
+        }
+
+        if let Some(loc) = shader.get_location("ditherLevels") {
+            shader.set_value(loc, dither.levels());
+        }
+
+        let mut fx = Self {
+            shader,
+            palette: palette_texture,
+            locs,
+        };
+
+        // colors of an evenly spread palette are about 1/cbrt(n) apart per channel
+        fx.set_dither_strength(1. / (palette.len() as f32).cbrt());
+        fx.set_pixel_size(1.);
+
+        Ok(fx)
+    }
+
+    /// Set how far colors are pushed by the dither pattern (0 turns it off, 1 is the full range)
+    #[inline]
+    pub fn set_dither_strength(&mut self, strength: f32) {
+        if let Some(loc) = self.locs.dither_strength {
+            self.shader.set_value(loc, strength.max(0.));
+        }
+    }
+
+    /// Set the size of the pixelation blocks in pixels of the source texture
+    #[inline]
+    pub fn set_pixel_size(&mut self, size: f32) {
+        if let Some(loc) = self.locs.pixel_size {
+            self.shader.set_value(loc, size.max(1.));
+        }
+    }
+
+    /// Set the dithering strength, see [`PalettePostFx::set_dither_strength`]
+    #[inline]
+    pub fn with_dither_strength(mut self, strength: f32) -> Self {
+        self.set_dither_strength(strength);
+        self
+    }
+
+    /// Set the pixelation block size, see [`PalettePostFx::set_pixel_size`]
+    #[inline]
+    pub fn with_pixel_size(mut self, size: f32) -> Self {
+        self.set_pixel_size(size);
+        self
+    }
+
+    /// Draw a render texture through the effect into `dest`
+    pub fn draw<D: Draw>(&mut self, d: &mut D, source: &RenderTexture2D, dest: Rectangle) {
+        let (width, height) = (source.width() as f32, source.height() as f32);
+
+        let shader_mode = d.begin_shader_mode(&self.shader);
+
+        // samplers have to be set while the shader is active, they are reset after every batch
+        if let Some(loc) = self.locs.palette_texture {
+            self.shader.set_value_texture(loc, &self.palette);
+        }
+
+        if let Some(loc) = self.locs.resolution {
+            self.shader.set_value(
+                loc,
+                Vector2 {
+                    x: width,
+                    y: height,
+                },
+            );
+        }
+
+        unsafe {
+            ffi::DrawTexturePro(
+                source.raw.texture.clone(),
+                // render textures are upside down
+                Rectangle::new(0., 0., width, -height).into(),
+                dest.into(),
+                Vector2 { x: 0., y: 0. }.into(),
+                0.,
+                Color::WHITE.into(),
+            )
+        }
+
+        shader_mode.end_shader_mode();
+    }
+}
+
+/// Post effect that converts a frame rendered in linear space to sRGB for the screen
+///
+/// The end of a linear workflow (with [`Texture::from_image_srgb`](crate::texture::Texture::from_image_srgb)
+/// and [`Color::to_linear`]), for when [`Raylib::set_srgb_framebuffer`](crate::Raylib::set_srgb_framebuffer)
+/// isn't available or the frame goes through other effects first.
+/// Render textures with 8 bit channels band in dark gradients,
+/// see [`RenderTexture::with_format`](crate::texture::RenderTexture::with_format) for float formats.
+/// The shader targets desktop OpenGL 3.3.
+#[derive(Debug)]
+pub struct SrgbPostFx {
+    shader: Shader,
+}
+
+impl SrgbPostFx {
+    /// Load the effect
+    #[inline]
+    pub fn new() -> Result<Self, RaylibError> {
+        Ok(Self {
+            shader: Shader::from_memory(None, Some(SRGB_FS))?,
+        })
+    }
+
+    /// Draw a render texture through the effect into `dest`
+    pub fn draw<D: Draw>(&self, d: &mut D, source: &RenderTexture2D, dest: Rectangle) {
+        let (width, height) = (source.width() as f32, source.height() as f32);
+
+        let shader_mode = d.begin_shader_mode(&self.shader);
+
+        unsafe {
+            ffi::DrawTexturePro(
+                source.raw.texture.clone(),
+                // render textures are upside down
+                Rectangle::new(0., 0., width, -height).into(),
+                dest.into(),
+                Vector2 { x: 0., y: 0. }.into(),
+                0.,
+                Color::WHITE.into(),
+            )
+        }
+
+        shader_mode.end_shader_mode();
+    }
+}
+
+/// Curve mapping HDR colors into the displayable range
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ToneMapOperator {
+    /// Clip everything brighter than 1
+    Clamp,
+    /// Reinhard, `c / (1 + c)`, soft highlights that never reach white
+    Reinhard,
+    /// Approximation of the ACES filmic curve, more contrast and saturated highlights
+    Aces,
+}
+
+impl ToneMapOperator {
+    #[inline]
+    fn mode(self) -> i32 {
+        match self {
+            Self::Clamp => 0,
+            Self::Reinhard => 1,
+            Self::Aces => 2,
+        }
+    }
+}
+
+/// Exposure that follows the average brightness of the frame, like an eye adapting
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AutoExposure {
+    /// Brightness the average luminance is mapped to (middle gray)
+    pub key: f32,
+    /// How fast the exposure follows changes, higher is faster (per second)
+    pub speed: f32,
+    /// Darkest average luminance to adapt to, so dark scenes stay dark
+    pub min_luminance: f32,
+    /// Brightest average luminance to adapt to
+    pub max_luminance: f32,
+}
+
+impl Default for AutoExposure {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            key: 0.18,
+            speed: 1.5,
+            min_luminance: 0.03,
+            max_luminance: 8.,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ToneMapLocations {
+    exposure_texture: Option<ShaderLoc>,
+    mode: Option<ShaderLoc>,
+    exposure: Option<ShaderLoc>,
+    auto_exposure: Option<ShaderLoc>,
+    key: Option<ShaderLoc>,
+    srgb_output: Option<ShaderLoc>,
+}
+
+#[derive(Debug)]
+struct AdaptLocations {
+    luminance_texture: Option<ShaderLoc>,
+    previous_texture: Option<ShaderLoc>,
+    level: Option<ShaderLoc>,
+    adaptation: Option<ShaderLoc>,
+    range: Option<ShaderLoc>,
+}
+
+#[derive(Debug)]
+struct Adaptation {
+    settings: AutoExposure,
+    luminance: RenderTexture2D,
+    adapted: [RenderTexture2D; 2],
+    current: usize,
+    reset: bool,
+    luminance_shader: Shader,
+    adapt_shader: Shader,
+    locs: AdaptLocations,
+}
+
+impl Adaptation {
+    /// Size of the downsampled luminance, mipmapped down to the average
+    const LUMINANCE_SIZE: u32 = 256;
+
+    fn new(settings: AutoExposure) -> Result<Self, RaylibError> {
+        let size = Self::LUMINANCE_SIZE;
+        let luminance = RenderTexture2D::with_format(size, size, PixelFormat::R32)?;
+        let adapted = [
+            RenderTexture2D::with_format(1, 1, PixelFormat::R32)?,
+            RenderTexture2D::with_format(1, 1, PixelFormat::R32)?,
+        ];
+
+        let luminance_shader = Shader::from_memory(None, Some(LUMINANCE_FS))?;
+        let adapt_shader = Shader::from_memory(None, Some(ADAPT_FS))?;
+
+        let locs = AdaptLocations {
+            luminance_texture: adapt_shader.get_location("luminanceTexture"),
+            previous_texture: adapt_shader.get_location("previousTexture"),
+            level: adapt_shader.get_location("level"),
+            adaptation: adapt_shader.get_location("adaptation"),
+            range: adapt_shader.get_location("range"),
+        };
+
+        Ok(Self {
+            settings,
+            luminance,
+            adapted,
+            current: 0,
+            reset: true,
+            luminance_shader,
+            adapt_shader,
+            locs,
+        })
+    }
+
+    fn update<D: Draw>(&mut self, d: &mut D, source: &RenderTexture2D, delta: f32) {
+        let size = Self::LUMINANCE_SIZE as f32;
+        let (width, height) = (source.width() as f32, source.height() as f32);
+
+        let mut texture_mode = d.begin_texture_mode(&self.luminance);
+        let shader_mode = texture_mode.begin_shader_mode(&self.luminance_shader);
+
+        unsafe {
+            ffi::DrawTexturePro(
+                source.raw.texture.clone(),
+                // render textures are upside down
+                Rectangle::new(0., 0., width, -height).into(),
+                Rectangle::new(0., 0., size, size).into(),
+                Vector2 { x: 0., y: 0. }.into(),
+                0.,
+                Color::WHITE.into(),
+            )
+        }
+
+        shader_mode.end_shader_mode();
+        texture_mode.end_texture_mode();
+
+        // the last mipmap level is the average, sampled with trilinear filtering
+        let level = unsafe {
+            ffi::GenTextureMipmaps(&mut self.luminance.raw.texture);
+            ffi::SetTextureFilter(
+                self.luminance.raw.texture.clone(),
+                TextureFilter::Trilinear as _,
+            );
+
+            (self.luminance.raw.texture.mipmaps - 1) as f32
+        };
+
+        let adaptation = if self.reset {
+            1.
+        } else {
+            1. - (-self.settings.speed.max(0.) * delta.max(0.)).exp()
+        };
+
+        if let Some(loc) = self.locs.level {
+            self.adapt_shader.set_value(loc, level);
+        }
+
+        if let Some(loc) = self.locs.adaptation {
+            self.adapt_shader.set_value(loc, adaptation);
+        }
+
+        if let Some(loc) = self.locs.range {
+            let min = self.settings.min_luminance.max(1e-4);
+
+            self.adapt_shader.set_value(
+                loc,
+                Vector2 {
+                    x: min,
+                    y: self.settings.max_luminance.max(min),
+                },
+            );
+        }
+
+        let next = 1 - self.current;
+
+        let mut texture_mode = d.begin_texture_mode(&self.adapted[next]);
+        let mut shader_mode = texture_mode.begin_shader_mode(&self.adapt_shader);
+
+        // samplers have to be set while the shader is active, they are reset after every batch
+        let samplers = [
+            (self.locs.luminance_texture, &self.luminance),
+            (self.locs.previous_texture, &self.adapted[self.current]),
+        ];
+
+        for (loc, texture) in samplers {
+            if let Some(loc) = loc {
+                unsafe {
+                    ffi::SetShaderValueTexture(
+                        self.adapt_shader.raw.clone(),
+                        loc.index() as _,
+                        texture.raw.texture.clone(),
+                    )
+                }
+            }
+        }
+
+        shader_mode.draw_rectangle(Rectangle::new(0., 0., 1., 1.), Color::WHITE);
+        shader_mode.end_shader_mode();
+        texture_mode.end_texture_mode();
+
+        self.current = next;
+        self.reset = false;
+    }
+}
+
+/// Post effect that tone maps a frame rendered in HDR (to a floating point render texture)
+///
+/// Draw the scene to a render texture loaded with [`RenderTexture::with_format`](crate::texture::RenderTexture::with_format)
+/// (e.g. [`PixelFormat::R32G32B32A32`]), then draw it with [`ToneMapPostFx::draw`].
+/// With auto exposure, call [`ToneMapPostFx::update`] every frame before drawing.
+/// The output is converted to sRGB by default, see [`ToneMapPostFx::set_srgb_output`].
+/// The shaders target desktop OpenGL 3.3.
+#[derive(Debug)]
+pub struct ToneMapPostFx {
+    shader: Shader,
+    locs: ToneMapLocations,
+    adaptation: Option<Adaptation>,
+}
+
+impl ToneMapPostFx {
+    /// Load the effect with an exposure of 1 and no auto exposure
+    pub fn new(operator: ToneMapOperator) -> Result<Self, RaylibError> {
+        let shader = Shader::from_memory(None, Some(TONE_MAP_FS))?;
+
+        let locs = ToneMapLocations {
+            exposure_texture: shader.get_location("exposureTexture"),
+            mode: shader.get_location("mode"),
+            exposure: shader.get_location("exposure"),
+            auto_exposure: shader.get_location("autoExposure"),
+            key: shader.get_location("key"),
+            srgb_output: shader.get_location("srgbOutput"),
+        };
+
+        let mut fx = Self {
+            shader,
+            locs,
+            adaptation: None,
+        };
+
+        fx.set_operator(operator);
+        fx.set_exposure(1.);
+        fx.set_srgb_output(true);
+
+        Ok(fx)
+    }
+
+    /// Set the tone mapping curve
+    #[inline]
+    pub fn set_operator(&mut self, operator: ToneMapOperator) {
+        if let Some(loc) = self.locs.mode {
+            self.shader.set_value(loc, operator.mode());
+        }
+    }
+
+    /// Set the exposure multiplier, applied on top of auto exposure (as exposure compensation)
+    #[inline]
+    pub fn set_exposure(&mut self, exposure: f32) {
+        if let Some(loc) = self.locs.exposure {
+            self.shader.set_value(loc, exposure.max(0.));
+        }
+    }
+
+    /// Set if the output is converted to sRGB
+    ///
+    /// Turn it off when the output goes to an sRGB framebuffer
+    /// (see [`Raylib::set_srgb_framebuffer`](crate::Raylib::set_srgb_framebuffer)) or to more linear post effects.
+    #[inline]
+    pub fn set_srgb_output(&mut self, srgb: bool) {
+        if let Some(loc) = self.locs.srgb_output {
+            self.shader.set_value(loc, srgb as i32);
+        }
+    }
+
+    /// Turn auto exposure on (or change its settings) or off
+    ///
+    /// Fails if floating point render textures can't be created, auto exposure stays off then.
+    pub fn set_auto_exposure(
+        &mut self,
+        auto_exposure: Option<AutoExposure>,
+    ) -> Result<(), RaylibError> {
+        match (auto_exposure, &mut self.adaptation) {
+            (Some(settings), Some(adaptation)) => adaptation.settings = settings,
+            (Some(settings), None) => self.adaptation = Some(Adaptation::new(settings)?),
+            (None, _) => self.adaptation = None,
+        }
+
+        if let Some(loc) = self.locs.auto_exposure {
+            self.shader.set_value(loc, self.adaptation.is_some() as i32);
+        }
+
+        if let (Some(loc), Some(settings)) = (self.locs.key, auto_exposure) {
+            self.shader.set_value(loc, settings.key.max(0.));
+        }
+
+        Ok(())
+    }
+
+    /// Jump to the current frame's exposure on the next update, e.g. after a cut to another scene
+    #[inline]
+    pub fn reset_exposure(&mut self) {
+        if let Some(adaptation) = &mut self.adaptation {
+            adaptation.reset = true;
+        }
+    }
+
+    /// Set the tone mapping curve, see [`ToneMapPostFx::set_operator`]
+    #[inline]
+    pub fn with_operator(mut self, operator: ToneMapOperator) -> Self {
+        self.set_operator(operator);
+        self
+    }
+
+    /// Set the exposure multiplier, see [`ToneMapPostFx::set_exposure`]
+    #[inline]
+    pub fn with_exposure(mut self, exposure: f32) -> Self {
+        self.set_exposure(exposure);
+        self
+    }
+
+    /// Set if the output is converted to sRGB, see [`ToneMapPostFx::set_srgb_output`]
+    #[inline]
+    pub fn with_srgb_output(mut self, srgb: bool) -> Self {
+        self.set_srgb_output(srgb);
+        self
+    }
+
+    /// Turn auto exposure on, see [`ToneMapPostFx::set_auto_exposure`]
+    #[inline]
+    pub fn with_auto_exposure(mut self, auto_exposure: AutoExposure) -> Result<Self, RaylibError> {
+        self.set_auto_exposure(Some(auto_exposure))?;
+        Ok(self)
+    }
+
+    /// Measure the average luminance of `source` and move the exposure `delta` seconds towards it
+    ///
+    /// Does nothing without auto exposure. Renders into its own textures, so call it outside of texture and 3D modes.
+    pub fn update<D: Draw>(&mut self, d: &mut D, source: &RenderTexture2D, delta: f32) {
+        if let Some(adaptation) = &mut self.adaptation {
+            adaptation.update(d, source, delta);
+        }
+    }
+
+    /// Draw a render texture through the effect into `dest`
+    pub fn draw<D: Draw>(&self, d: &mut D, source: &RenderTexture2D, dest: Rectangle) {
+        let (width, height) = (source.width() as f32, source.height() as f32);
+
+        let shader_mode = d.begin_shader_mode(&self.shader);
+
+        // samplers have to be set while the shader is active, they are reset after every batch
+        if let (Some(loc), Some(adaptation)) = (self.locs.exposure_texture, &self.adaptation) {
+            unsafe {
+                ffi::SetShaderValueTexture(
+                    self.shader.raw.clone(),
+                    loc.index() as _,
+                    adaptation.adapted[adaptation.current].raw.texture.clone(),
+                )
+            }
+        }
+
+        unsafe {
+            ffi::DrawTexturePro(
+                source.raw.texture.clone(),
+                // render textures are upside down
+                Rectangle::new(0., 0., width, -height).into(),
+                dest.into(),
+                Vector2 { x: 0., y: 0. }.into(),
+                0.,
+                Color::WHITE.into(),
+            )
+        }
+
+        shader_mode.end_shader_mode();
+    }
+}
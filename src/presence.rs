@@ -0,0 +1,277 @@
+use std::time::SystemTime;
+
+/// What the player is doing, shown by rich presence integrations (Discord, Steam, ...)
+///
+/// Which fields are shown, and how, depends on the platform.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Presence {
+    /// Main line, e.g. "Exploring the Caves"
+    pub details: Option<String>,
+    /// Second line, e.g. "In a party"
+    pub state: Option<String>,
+    /// Key of the large image (uploaded to the platform beforehand)
+    pub large_image: Option<String>,
+    /// Tooltip of the large image
+    pub large_text: Option<String>,
+    /// Key of the small image
+    pub small_image: Option<String>,
+    /// Tooltip of the small image
+    pub small_text: Option<String>,
+    /// When the activity started, shown as elapsed time
+    pub start: Option<SystemTime>,
+    /// Party size and maximum size
+    pub party: Option<(u32, u32)>,
+}
+
+impl Presence {
+    /// Create presence with only the main line
+    #[inline]
+    pub fn new(details: impl Into<String>) -> Self {
+        Self {
+            details: Some(details.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Set the second line
+    #[inline]
+    pub fn with_state(mut self, state: impl Into<String>) -> Self {
+        self.state = Some(state.into());
+        self
+    }
+
+    /// Set the large image and its tooltip
+    #[inline]
+    pub fn with_large_image(mut self, key: impl Into<String>, text: impl Into<String>) -> Self {
+        self.large_image = Some(key.into());
+        self.large_text = Some(text.into());
+        self
+    }
+
+    /// Set the small image and its tooltip
+    #[inline]
+    pub fn with_small_image(mut self, key: impl Into<String>, text: impl Into<String>) -> Self {
+        self.small_image = Some(key.into());
+        self.small_text = Some(text.into());
+        self
+    }
+
+    /// Set when the activity started
+    #[inline]
+    pub fn with_start(mut self, start: SystemTime) -> Self {
+        self.start = Some(start);
+        self
+    }
+
+    /// Set the party size and maximum size
+    #[inline]
+    pub fn with_party(mut self, size: u32, max: u32) -> Self {
+        self.party = Some((size, max));
+        self
+    }
+}
+
+/// Platform integration showing the player's [`Presence`]
+///
+/// Driven by [`SceneManager`](crate::scene::SceneManager), which calls [`PresenceProvider::init`] when its
+/// main loop starts (so after the window was created, whenever the provider was), [`PresenceProvider::update`]
+/// every frame, [`PresenceProvider::set_presence`] when the current scene's presence changes
+/// and [`PresenceProvider::shutdown`] when the loop ends.
+/// Every method does nothing by default.
+pub trait PresenceProvider {
+    /// Connect to the platform
+    #[inline]
+    fn init(&mut self) {}
+
+    /// Called every frame, `dt` is the frame time in seconds (e.g. to run callbacks or reconnect)
+    #[inline]
+    fn update(&mut self, _dt: f32) {}
+
+    /// Show a presence
+    #[inline]
+    fn set_presence(&mut self, _presence: &Presence) {}
+
+    /// Stop showing a presence
+    #[inline]
+    fn clear(&mut self) {}
+
+    /// Disconnect from the platform
+    #[inline]
+    fn shutdown(&mut self) {}
+}
+
+/// Provider that doesn't show anything
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct NoPresence;
+
+impl PresenceProvider for NoPresence {}
+
+/// Discord rich presence over Discord's local IPC
+///
+/// Does nothing while Discord isn't running, and reconnects every few seconds.
+#[cfg(feature = "discord")]
+pub struct DiscordPresence {
+    client: discord_rich_presence::DiscordIpcClient,
+    connected: bool,
+    retry_in: f32,
+    presence: Option<Presence>,
+}
+
+#[cfg(feature = "discord")]
+impl DiscordPresence {
+    /// Seconds between connection attempts
+    const RETRY_INTERVAL: f32 = 10.;
+
+    /// Create provider for a Discord application id (from the developer portal)
+    #[inline]
+    pub fn new(app_id: &str) -> Self {
+        Self {
+            client: discord_rich_presence::DiscordIpcClient::new(app_id),
+            connected: false,
+            retry_in: 0.,
+            presence: None,
+        }
+    }
+
+    /// Is it connected to Discord?
+    #[inline]
+    pub fn is_connected(&self) -> bool {
+        self.connected
+    }
+
+    fn connect(&mut self) {
+        use discord_rich_presence::DiscordIpc;
+
+        self.retry_in = Self::RETRY_INTERVAL;
+
+        match self.client.connect() {
+            Ok(()) => {
+                self.connected = true;
+
+                if let Some(presence) = self.presence.take() {
+                    self.set_presence(&presence);
+                }
+            }
+            Err(_error) => {
+                #[cfg(debug_assertions)]
+                crate::core::warn(&format!("DiscordPresence: connecting failed: {_error}"));
+            }
+        }
+    }
+
+    fn disconnected(&mut self, _error: discord_rich_presence::error::Error) {
+        #[cfg(debug_assertions)]
+        crate::core::warn(&format!("DiscordPresence: connection lost: {_error}"));
+
+        self.connected = false;
+        self.retry_in = Self::RETRY_INTERVAL;
+    }
+}
+
+#[cfg(feature = "discord")]
+impl PresenceProvider for DiscordPresence {
+    #[inline]
+    fn init(&mut self) {
+        if !self.connected {
+            self.connect();
+        }
+    }
+
+    fn update(&mut self, dt: f32) {
+        if !self.connected {
+            self.retry_in -= dt;
+
+            if self.retry_in <= 0. {
+                self.connect();
+            }
+        }
+    }
+
+    fn set_presence(&mut self, presence: &Presence) {
+        use discord_rich_presence::{activity, DiscordIpc};
+
+        // sent again after (re)connecting
+        self.presence = Some(presence.clone());
+
+        if !self.connected {
+            return;
+        }
+
+        let mut assets = activity::Assets::new();
+        if let Some(image) = &presence.large_image {
+            assets = assets.large_image(image.as_str());
+        }
+        if let Some(text) = &presence.large_text {
+            assets = assets.large_text(text.as_str());
+        }
+        if let Some(image) = &presence.small_image {
+            assets = assets.small_image(image.as_str());
+        }
+        if let Some(text) = &presence.small_text {
+            assets = assets.small_text(text.as_str());
+        }
+
+        let mut activity = activity::Activity::new().assets(assets);
+        if let Some(details) = &presence.details {
+            activity = activity.details(details.as_str());
+        }
+        if let Some(state) = &presence.state {
+            activity = activity.state(state.as_str());
+        }
+        if let Some(start) = presence.start {
+            let secs = start
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .map_or(0, |since| since.as_secs() as i64);
+
+            activity = activity.timestamps(activity::Timestamps::new().start(secs));
+        }
+        if let Some((size, max)) = presence.party {
+            activity = activity.party(activity::Party::new().size([size as i32, max as i32]));
+        }
+
+        if let Err(error) = self.client.set_activity(activity) {
+            self.disconnected(error);
+        }
+    }
+
+    fn clear(&mut self) {
+        use discord_rich_presence::DiscordIpc;
+
+        self.presence = None;
+
+        if self.connected {
+            if let Err(error) = self.client.clear_activity() {
+                self.disconnected(error);
+            }
+        }
+    }
+
+    fn shutdown(&mut self) {
+        use discord_rich_presence::DiscordIpc;
+
+        if self.connected {
+            let _ = self.client.clear_activity();
+            let _ = self.client.close();
+            self.connected = false;
+        }
+    }
+}
+
+#[cfg(feature = "discord")]
+impl std::fmt::Debug for DiscordPresence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DiscordPresence")
+            .field("connected", &self.connected)
+            .field("presence", &self.presence)
+            .finish()
+    }
+}
+
+#[cfg(feature = "discord")]
+impl Drop for DiscordPresence {
+    #[inline]
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
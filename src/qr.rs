@@ -0,0 +1,657 @@
+// QR Code encoding (byte mode, medium error correction), ported from
+// Project Nayuki's QR Code generator library: https://www.nayuki.io/page/qr-code-generator-library
+//
+// Copyright (c) Project Nayuki. (MIT License)
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+// - The above copyright notice and this permission notice shall be included in
+//   all copies or substantial portions of the Software.
+// - The Software is provided "as is", without warranty of any kind, express or
+//   implied, including but not limited to the warranties of merchantability,
+//   fitness for a particular purpose and noninfringement. In no event shall the
+//   authors or copyright holders be liable for any claim, damages or other
+//   liability, whether in an action of contract, tort or otherwise, arising from,
+//   out of or in connection with the Software or the use or other dealings in the
+//   Software.
+
+/// Error correction codewords per block, by version (medium level)
+const ECC_CODEWORDS_PER_BLOCK: [usize; 41] = [
+    0, 10, 16, 26, 18, 24, 16, 18, 22, 22, 26, 30, 22, 22, 24, 24, 28, 28, 26, 26, 26, 26, 28, 28,
+    28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28,
+];
+
+/// Error correction blocks, by version (medium level)
+const ERROR_CORRECTION_BLOCKS: [usize; 41] = [
+    0, 1, 1, 1, 2, 2, 4, 4, 4, 5, 5, 5, 8, 9, 9, 10, 10, 11, 13, 14, 16, 17, 17, 18, 20, 21, 23,
+    25, 26, 28, 29, 31, 33, 35, 37, 38, 40, 43, 45, 47, 49,
+];
+
+/// Format bits of the medium error correction level
+const FORMAT_BITS: u32 = 0;
+
+const PENALTY_N1: i32 = 3;
+const PENALTY_N2: i32 = 3;
+const PENALTY_N3: i32 = 40;
+const PENALTY_N4: i32 = 10;
+
+/// Square grid of modules, `true` is dark
+#[derive(Clone, Debug)]
+pub(crate) struct QrCode {
+    size: i32,
+    modules: Vec<bool>,
+    function: Vec<bool>,
+}
+
+impl QrCode {
+    /// Encode bytes in the smallest version that fits, `None` if the data is too long
+    pub(crate) fn encode(data: &[u8]) -> Option<Self> {
+        let version = (1..=40).find(|&version| {
+            let count_bits = if version < 10 { 8 } else { 16 };
+
+            data.len() < (1 << count_bits)
+                && 4 + count_bits + data.len() * 8 <= data_codewords(version) * 8
+        })?;
+
+        let capacity = data_codewords(version) * 8;
+        let mut bits = BitBuffer::default();
+
+        // byte mode
+        bits.push(0b0100, 4);
+        bits.push(data.len() as u32, if version < 10 { 8 } else { 16 });
+
+        for &byte in data {
+            bits.push(byte as u32, 8);
+        }
+
+        // terminator, then padding to whole bytes and pad codewords
+        bits.push(0, (capacity - bits.len).min(4));
+        bits.push(0, (8 - bits.len % 8) % 8);
+
+        for pad in [0xEC, 0x11].iter().cycle() {
+            if bits.len >= capacity {
+                break;
+            }
+            bits.push(*pad, 8);
+        }
+
+        let size = version as i32 * 4 + 17;
+        let mut qr = Self {
+            size,
+            modules: vec![false; (size * size) as usize],
+            function: vec![false; (size * size) as usize],
+        };
+
+        qr.draw_function_patterns(version);
+        qr.draw_codewords(&add_ecc_and_interleave(&bits.bytes, version));
+
+        let mut best = (i32::MAX, 0);
+
+        for mask in 0..8 {
+            qr.apply_mask(mask);
+            qr.draw_format_bits(mask);
+
+            let penalty = qr.penalty_score();
+            if penalty < best.0 {
+                best = (penalty, mask);
+            }
+
+            // masking twice undoes it
+            qr.apply_mask(mask);
+        }
+
+        qr.apply_mask(best.1);
+        qr.draw_format_bits(best.1);
+
+        Some(qr)
+    }
+
+    /// Width and height in modules
+    #[inline]
+    pub(crate) fn size(&self) -> u32 {
+        self.size as u32
+    }
+
+    /// Check if a module is dark
+    #[inline]
+    pub(crate) fn module(&self, x: u32, y: u32) -> bool {
+        self.get(x as i32, y as i32)
+    }
+
+    #[inline]
+    fn get(&self, x: i32, y: i32) -> bool {
+        self.modules[(y * self.size + x) as usize]
+    }
+
+    #[inline]
+    fn set_function(&mut self, x: i32, y: i32, dark: bool) {
+        let index = (y * self.size + x) as usize;
+
+        self.modules[index] = dark;
+        self.function[index] = true;
+    }
+
+    fn draw_function_patterns(&mut self, version: usize) {
+        for i in 0..self.size {
+            self.set_function(6, i, i % 2 == 0);
+            self.set_function(i, 6, i % 2 == 0);
+        }
+
+        self.draw_finder_pattern(3, 3);
+        self.draw_finder_pattern(self.size - 4, 3);
+        self.draw_finder_pattern(3, self.size - 4);
+
+        let positions = alignment_pattern_positions(version);
+        let last = positions.len().saturating_sub(1);
+
+        for (i, &x) in positions.iter().enumerate() {
+            for (j, &y) in positions.iter().enumerate() {
+                // the finder patterns are there
+                if !((i == 0 && j == 0) || (i == 0 && j == last) || (i == last && j == 0)) {
+                    self.draw_alignment_pattern(x, y);
+                }
+            }
+        }
+
+        // reserve the format areas, they are drawn for real after masking
+        self.draw_format_bits(0);
+        self.draw_version(version);
+    }
+
+    fn draw_finder_pattern(&mut self, x: i32, y: i32) {
+        for dy in -4..=4 {
+            for dx in -4..=4 {
+                let (xx, yy) = (x + dx, y + dy);
+                let distance = i32::max(dx.abs(), dy.abs());
+
+                if (0..self.size).contains(&xx) && (0..self.size).contains(&yy) {
+                    self.set_function(xx, yy, distance != 2 && distance != 4);
+                }
+            }
+        }
+    }
+
+    fn draw_alignment_pattern(&mut self, x: i32, y: i32) {
+        for dy in -2..=2 {
+            for dx in -2..=2 {
+                self.set_function(x + dx, y + dy, i32::max(dx.abs(), dy.abs()) != 1);
+            }
+        }
+    }
+
+    fn draw_format_bits(&mut self, mask: u32) {
+        let data = FORMAT_BITS << 3 | mask;
+        let mut remainder = data;
+
+        for _ in 0..10 {
+            remainder = (remainder << 1) ^ ((remainder >> 9) * 0x537);
+        }
+
+        let bits = (data << 10 | remainder) ^ 0x5412;
+        let size = self.size;
+
+        // around the top left finder pattern
+        for i in 0..6 {
+            self.set_function(8, i, bit(bits, i));
+        }
+        self.set_function(8, 7, bit(bits, 6));
+        self.set_function(8, 8, bit(bits, 7));
+        self.set_function(7, 8, bit(bits, 8));
+        for i in 9..15 {
+            self.set_function(14 - i, 8, bit(bits, i));
+        }
+
+        // split between the other finder patterns
+        for i in 0..8 {
+            self.set_function(size - 1 - i, 8, bit(bits, i));
+        }
+        for i in 8..15 {
+            self.set_function(8, size - 15 + i, bit(bits, i));
+        }
+        self.set_function(8, size - 8, true);
+    }
+
+    fn draw_version(&mut self, version: usize) {
+        if version < 7 {
+            return;
+        }
+
+        let mut remainder = version as u32;
+
+        for _ in 0..12 {
+            remainder = (remainder << 1) ^ ((remainder >> 11) * 0x1F25);
+        }
+
+        let bits = (version as u32) << 12 | remainder;
+
+        for i in 0..18 {
+            let dark = bit(bits, i);
+            let (a, b) = (self.size - 11 + i % 3, i / 3);
+
+            self.set_function(a, b, dark);
+            self.set_function(b, a, dark);
+        }
+    }
+
+    /// Fill the data area in the zigzag order, two columns at a time from the bottom right
+    fn draw_codewords(&mut self, data: &[u8]) {
+        let mut i = 0;
+        let mut right = self.size - 1;
+
+        while right >= 1 {
+            // skip the vertical timing pattern
+            if right == 6 {
+                right = 5;
+            }
+
+            for vertical in 0..self.size {
+                for j in 0..2 {
+                    let x = right - j;
+                    let upward = (right + 1) & 2 == 0;
+                    let y = if upward {
+                        self.size - 1 - vertical
+                    } else {
+                        vertical
+                    };
+                    let index = (y * self.size + x) as usize;
+
+                    if !self.function[index] && i < data.len() * 8 {
+                        self.modules[index] = (data[i >> 3] >> (7 - (i & 7))) & 1 != 0;
+                        i += 1;
+                    }
+                }
+            }
+
+            right -= 2;
+        }
+    }
+
+    fn apply_mask(&mut self, mask: u32) {
+        for y in 0..self.size {
+            for x in 0..self.size {
+                let invert = match mask {
+                    0 => (x + y) % 2 == 0,
+                    1 => y % 2 == 0,
+                    2 => x % 3 == 0,
+                    3 => (x + y) % 3 == 0,
+                    4 => (x / 3 + y / 2) % 2 == 0,
+                    5 => x * y % 2 + x * y % 3 == 0,
+                    6 => (x * y % 2 + x * y % 3) % 2 == 0,
+                    _ => ((x + y) % 2 + x * y % 3) % 2 == 0,
+                };
+                let index = (y * self.size + x) as usize;
+
+                if invert && !self.function[index] {
+                    self.modules[index] = !self.modules[index];
+                }
+            }
+        }
+    }
+
+    /// Penalty for patterns that are hard to scan (runs, boxes, finder look-alikes, imbalance)
+    fn penalty_score(&self) -> i32 {
+        let size = self.size;
+        let mut result = 0;
+
+        for transposed in [false, true] {
+            for a in 0..size {
+                let mut run_color = false;
+                let mut run_length = 0;
+                let mut history = [0; 7];
+
+                for b in 0..size {
+                    let color = if transposed {
+                        self.get(a, b)
+                    } else {
+                        self.get(b, a)
+                    };
+
+                    if color == run_color {
+                        run_length += 1;
+
+                        if run_length == 5 {
+                            result += PENALTY_N1;
+                        } else if run_length > 5 {
+                            result += 1;
+                        }
+                    } else {
+                        add_history(run_length, &mut history, size);
+
+                        if !run_color {
+                            result += count_finder_patterns(&history) * PENALTY_N3;
+                        }
+
+                        run_color = color;
+                        run_length = 1;
+                    }
+                }
+
+                // the light border outside the code ends the last run
+                if run_color {
+                    add_history(run_length, &mut history, size);
+                    run_length = 0;
+                }
+                add_history(run_length + size, &mut history, size);
+                result += count_finder_patterns(&history) * PENALTY_N3;
+            }
+        }
+
+        for y in 0..size - 1 {
+            for x in 0..size - 1 {
+                let color = self.get(x, y);
+
+                if color == self.get(x + 1, y)
+                    && color == self.get(x, y + 1)
+                    && color == self.get(x + 1, y + 1)
+                {
+                    result += PENALTY_N2;
+                }
+            }
+        }
+
+        let dark = self.modules.iter().filter(|&&dark| dark).count() as i32;
+        let total = size * size;
+        let k = ((dark * 20 - total * 10).abs() + total - 1) / total - 1;
+
+        result + k * PENALTY_N4
+    }
+}
+
+#[derive(Default)]
+struct BitBuffer {
+    bytes: Vec<u8>,
+    len: usize,
+}
+
+impl BitBuffer {
+    /// Append the lowest `count` bits of `value`, most significant first
+    fn push(&mut self, value: u32, count: usize) {
+        for i in (0..count).rev() {
+            if self.len % 8 == 0 {
+                self.bytes.push(0);
+            }
+
+            if (value >> i) & 1 != 0 {
+                self.bytes[self.len / 8] |= 0x80 >> (self.len % 8);
+            }
+
+            self.len += 1;
+        }
+    }
+}
+
+#[inline]
+fn bit(value: u32, index: i32) -> bool {
+    (value >> index) & 1 != 0
+}
+
+/// Number of modules available for data and error correction
+fn raw_data_modules(version: usize) -> usize {
+    let mut result = (16 * version + 128) * version + 64;
+
+    if version >= 2 {
+        let alignments = version / 7 + 2;
+        result -= (25 * alignments - 10) * alignments - 55;
+
+        if version >= 7 {
+            result -= 36;
+        }
+    }
+
+    result
+}
+
+#[inline]
+fn data_codewords(version: usize) -> usize {
+    raw_data_modules(version) / 8
+        - ECC_CODEWORDS_PER_BLOCK[version] * ERROR_CORRECTION_BLOCKS[version]
+}
+
+fn alignment_pattern_positions(version: usize) -> Vec<i32> {
+    if version == 1 {
+        return Vec::new();
+    }
+
+    let count = version as i32 / 7 + 2;
+    let step = (version as i32 * 8 + count * 3 + 5) / (count * 4 - 4) * 2;
+    let size = version as i32 * 4 + 17;
+
+    let mut positions: Vec<i32> = (0..count - 1).map(|i| size - 7 - i * step).collect();
+    positions.push(6);
+    positions.reverse();
+
+    positions
+}
+
+/// Split data into blocks, append Reed-Solomon codes and interleave the blocks
+fn add_ecc_and_interleave(data: &[u8], version: usize) -> Vec<u8> {
+    let blocks = ERROR_CORRECTION_BLOCKS[version];
+    let ecc_len = ECC_CODEWORDS_PER_BLOCK[version];
+    let raw_codewords = raw_data_modules(version) / 8;
+    let short_blocks = blocks - raw_codewords % blocks;
+    let short_block_len = raw_codewords / blocks;
+
+    let divisor = reed_solomon_divisor(ecc_len);
+    let mut split = Vec::with_capacity(blocks);
+    let mut start = 0;
+
+    for i in 0..blocks {
+        let data_len = short_block_len - ecc_len + usize::from(i >= short_blocks);
+        let mut block = data[start..start + data_len].to_vec();
+        start += data_len;
+
+        let ecc = reed_solomon_remainder(&block, &divisor);
+
+        // short blocks get a dummy byte, skipped when interleaving
+        if i < short_blocks {
+            block.push(0);
+        }
+        block.extend(ecc);
+        split.push(block);
+    }
+
+    let mut result = Vec::with_capacity(raw_codewords);
+
+    for i in 0..split[0].len() {
+        for (j, block) in split.iter().enumerate() {
+            if i != short_block_len - ecc_len || j >= short_blocks {
+                result.push(block[i]);
+            }
+        }
+    }
+
+    result
+}
+
+fn reed_solomon_divisor(degree: usize) -> Vec<u8> {
+    let mut result = vec![0; degree - 1];
+    result.push(1);
+
+    let mut root = 1;
+
+    for _ in 0..degree {
+        for j in 0..degree {
+            result[j] = gf_multiply(result[j], root);
+
+            if j + 1 < degree {
+                result[j] ^= result[j + 1];
+            }
+        }
+
+        root = gf_multiply(root, 0x02);
+    }
+
+    result
+}
+
+fn reed_solomon_remainder(data: &[u8], divisor: &[u8]) -> Vec<u8> {
+    let mut result = vec![0; divisor.len()];
+
+    for &byte in data {
+        let factor = byte ^ result.remove(0);
+        result.push(0);
+
+        for (x, &y) in result.iter_mut().zip(divisor.iter()) {
+            *x ^= gf_multiply(y, factor);
+        }
+    }
+
+    result
+}
+
+/// Multiplication in GF(2^8) modulo x^8 + x^4 + x^3 + x^2 + 1
+#[inline]
+fn gf_multiply(x: u8, y: u8) -> u8 {
+    let mut z: u8 = 0;
+
+    for i in (0..8).rev() {
+        z = (z << 1) ^ ((z >> 7) * 0x1D);
+        z ^= ((y >> i) & 1) * x;
+    }
+
+    z
+}
+
+/// Shift a run length into the finder pattern history (newest first)
+#[inline]
+fn add_history(mut run_length: i32, history: &mut [i32; 7], size: i32) {
+    // the light border before the first run
+    if history[0] == 0 {
+        run_length += size;
+    }
+
+    history.copy_within(0..6, 1);
+    history[0] = run_length;
+}
+
+/// Finder-like 1:1:3:1:1 patterns with 4 light modules on either side
+#[inline]
+fn count_finder_patterns(history: &[i32; 7]) -> i32 {
+    let n = history[1];
+    let core =
+        n > 0 && history[2] == n && history[3] == n * 3 && history[4] == n && history[5] == n;
+
+    i32::from(core && history[0] >= n * 4 && history[6] >= n)
+        + i32::from(core && history[6] >= n * 4 && history[0] >= n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Data codewords of "HELLO WORLD" in alphanumeric mode, version 1-M
+    const HELLO_WORLD: [u8; 16] = [
+        32, 91, 11, 120, 209, 114, 220, 77, 67, 64, 236, 17, 236, 17, 236, 17,
+    ];
+    const HELLO_WORLD_ECC: [u8; 10] = [196, 35, 39, 119, 235, 215, 231, 226, 93, 23];
+
+    /// Format bits of the medium level, by mask
+    const MEDIUM_FORMAT_BITS: [u32; 8] = [
+        0b101010000010010,
+        0b101000100100101,
+        0b101111001111100,
+        0b101101101001011,
+        0b100010111111001,
+        0b100000011001110,
+        0b100111110010111,
+        0b100101010100000,
+    ];
+
+    #[test]
+    fn reed_solomon() {
+        let divisor = reed_solomon_divisor(10);
+
+        assert_eq!(
+            reed_solomon_remainder(&HELLO_WORLD, &divisor),
+            HELLO_WORLD_ECC
+        );
+
+        // "01234567" in numeric mode, version 1-M (ISO/IEC 18004 annex I)
+        let data = [
+            0b00010000, 0b00100000, 0b00001100, 0b01010110, 0b01100001, 0b10000000, 236, 17, 236,
+            17, 236, 17, 236, 17, 236, 17,
+        ];
+        let ecc = [165, 36, 212, 193, 237, 54, 199, 135, 44, 85];
+
+        assert_eq!(reed_solomon_remainder(&data, &divisor), ecc);
+    }
+
+    #[test]
+    fn single_block_ecc() {
+        let codewords = add_ecc_and_interleave(&HELLO_WORLD, 1);
+
+        assert_eq!(codewords[..16], HELLO_WORLD);
+        assert_eq!(codewords[16..], HELLO_WORLD_ECC);
+    }
+
+    #[test]
+    fn capacity() {
+        assert_eq!(data_codewords(1), 16);
+        assert_eq!(data_codewords(40), 2334);
+
+        assert_eq!(QrCode::encode(&[0; 14]).unwrap().size(), 21);
+        assert_eq!(QrCode::encode(&[0; 15]).unwrap().size(), 25);
+        assert_eq!(QrCode::encode(&[0; 2331]).unwrap().size(), 177);
+        assert!(QrCode::encode(&[0; 2332]).is_none());
+    }
+
+    #[test]
+    fn alignment_patterns() {
+        assert!(alignment_pattern_positions(1).is_empty());
+        assert_eq!(alignment_pattern_positions(2), [6, 18]);
+        assert_eq!(alignment_pattern_positions(7), [6, 22, 38]);
+        assert_eq!(alignment_pattern_positions(32), [6, 34, 60, 86, 112, 138]);
+        assert_eq!(
+            alignment_pattern_positions(40),
+            [6, 30, 58, 86, 114, 142, 170]
+        );
+    }
+
+    #[test]
+    fn format_bits() {
+        let code = QrCode::encode(b"https://www.raylib.com").unwrap();
+        let size = code.size();
+
+        // around the top left finder pattern, bit 14 first
+        let mut positions: Vec<(u32, u32)> = (0..6).map(|x| (x, 8)).collect();
+        positions.extend([(7, 8), (8, 8), (8, 7)]);
+        positions.extend((0..6).rev().map(|y| (8, y)));
+
+        let bits = positions
+            .iter()
+            .fold(0, |bits, &(x, y)| bits << 1 | u32::from(code.module(x, y)));
+
+        assert!(MEDIUM_FORMAT_BITS.contains(&bits));
+
+        // the copy split between the other finder patterns
+        let mut positions: Vec<(u32, u32)> = (size - 7..size).rev().map(|y| (8, y)).collect();
+        positions.extend((size - 8..size).map(|x| (x, 8)));
+
+        let copy = positions
+            .iter()
+            .fold(0, |bits, &(x, y)| bits << 1 | u32::from(code.module(x, y)));
+
+        assert_eq!(copy, bits);
+        assert!(code.module(8, size - 8));
+    }
+
+    #[test]
+    fn version_bits() {
+        // 106 bytes fit in version 6
+        let code = QrCode::encode(&[0; 107]).unwrap();
+        let size = code.size();
+
+        assert_eq!(size, 45);
+
+        for i in 0..18 {
+            let dark = bit(0b000111110010010100, i as i32);
+
+            assert_eq!(code.module(size - 11 + i % 3, i / 3), dark);
+            assert_eq!(code.module(i / 3, size - 11 + i % 3), dark);
+        }
+    }
+}
@@ -0,0 +1,214 @@
+use crate::{
+    color::Color,
+    drawing::Draw,
+    math::{BoundingBox, Quaternion, Ray, Transform, Vector3},
+    model::Mesh,
+};
+
+use rapier3d::{
+    na::{Quaternion as NaQuaternion, Translation3, UnitQuaternion},
+    parry::{bounding_volume::Aabb, query},
+    prelude::{Collider, ColliderBuilder, ColliderSet, Isometry, Point, Real, TypedShape},
+};
+
+pub use rapier3d;
+
+impl From<Ray> for query::Ray {
+    #[inline]
+    fn from(ray: Ray) -> Self {
+        query::Ray::new(to_point(ray.position), to_point(ray.direction).coords)
+    }
+}
+
+impl From<query::Ray> for Ray {
+    #[inline]
+    fn from(ray: query::Ray) -> Self {
+        Ray {
+            position: from_point(ray.origin),
+            direction: from_point(Point::from(ray.dir)),
+        }
+    }
+}
+
+impl From<BoundingBox> for Aabb {
+    #[inline]
+    fn from(bbox: BoundingBox) -> Self {
+        Aabb::new(to_point(bbox.min), to_point(bbox.max))
+    }
+}
+
+impl From<Aabb> for BoundingBox {
+    #[inline]
+    fn from(aabb: Aabb) -> Self {
+        BoundingBox::new(from_point(aabb.mins), from_point(aabb.maxs))
+    }
+}
+
+impl From<Transform> for Isometry<Real> {
+    /// Scale is ignored, rapier shapes carry their own size
+    #[inline]
+    fn from(transform: Transform) -> Self {
+        let t = transform.translation;
+        let r = transform.rotation;
+
+        Isometry::from_parts(
+            Translation3::new(t.x, t.y, t.z),
+            UnitQuaternion::from_quaternion(NaQuaternion::new(r.s, r.v.x, r.v.y, r.v.z)),
+        )
+    }
+}
+
+impl From<Isometry<Real>> for Transform {
+    #[inline]
+    fn from(iso: Isometry<Real>) -> Self {
+        let t = iso.translation.vector;
+        let r = iso.rotation;
+
+        Transform {
+            translation: Vector3 {
+                x: t.x,
+                y: t.y,
+                z: t.z,
+            },
+            rotation: Quaternion {
+                v: Vector3 {
+                    x: r.i,
+                    y: r.j,
+                    z: r.k,
+                },
+                s: r.w,
+            },
+            scale: Vector3 {
+                x: 1.,
+                y: 1.,
+                z: 1.,
+            },
+        }
+    }
+}
+
+/// Build a triangle mesh collider from mesh vertex data (must be available on the CPU)
+#[inline]
+pub fn trimesh_from_mesh(mesh: &Mesh) -> Option<ColliderBuilder> {
+    let (vertices, indices) = mesh_geometry(mesh)?;
+
+    Some(ColliderBuilder::trimesh(vertices, indices))
+}
+
+/// Build a convex hull collider from mesh vertex data (must be available on the CPU)
+#[inline]
+pub fn convex_hull_from_mesh(mesh: &Mesh) -> Option<ColliderBuilder> {
+    let (vertices, _) = mesh_geometry(mesh)?;
+
+    ColliderBuilder::convex_hull(&vertices)
+}
+
+/// Debug drawing of rapier colliders, implemented for everything that can [`Draw`]
+pub trait DrawColliders: Draw {
+    /// Draw the wireframe of a collider
+    ///
+    /// Balls, cuboids, capsules and triangle meshes are drawn as is, other shapes as their bounding box.
+    #[inline]
+    fn draw_collider(&mut self, collider: &Collider, color: Color) {
+        let pos = collider.position();
+
+        match collider.shape().as_typed_shape() {
+            TypedShape::Ball(ball) => {
+                self.draw_sphere_wires(from_point(pos * Point::origin()), ball.radius, 8, 8, color)
+            }
+            TypedShape::Cuboid(cuboid) => {
+                let h = cuboid.half_extents;
+                let corner = |i: usize| {
+                    let sign = |bit: usize| if i & bit == 0 { -1. } else { 1. };
+
+                    from_point(pos * Point::new(h.x * sign(1), h.y * sign(2), h.z * sign(4)))
+                };
+
+                // connect corners that differ in one coordinate
+                for i in 0..8 {
+                    for bit in [1, 2, 4] {
+                        if i & bit == 0 {
+                            self.draw_line_3d(corner(i), corner(i | bit), color);
+                        }
+                    }
+                }
+            }
+            TypedShape::Capsule(capsule) => self.draw_capsule_wires(
+                from_point(pos * capsule.segment.a),
+                from_point(pos * capsule.segment.b),
+                capsule.radius,
+                8,
+                4,
+                color,
+            ),
+            TypedShape::TriMesh(trimesh) => {
+                for triangle in trimesh.triangles() {
+                    let (a, b, c) = (
+                        from_point(pos * triangle.a),
+                        from_point(pos * triangle.b),
+                        from_point(pos * triangle.c),
+                    );
+
+                    self.draw_line_3d(a, b, color);
+                    self.draw_line_3d(b, c, color);
+                    self.draw_line_3d(c, a, color);
+                }
+            }
+            _ => self.draw_bounding_box(collider.compute_aabb().into(), color),
+        }
+    }
+
+    /// Draw the wireframes of all colliders in a set
+    #[inline]
+    fn draw_colliders(&mut self, colliders: &ColliderSet, color: Color) {
+        for (_, collider) in colliders.iter() {
+            self.draw_collider(collider, color);
+        }
+    }
+}
+
+impl<T: Draw + ?Sized> DrawColliders for T {}
+
+fn mesh_geometry(mesh: &Mesh) -> Option<(Vec<Point<Real>>, Vec<[u32; 3]>)> {
+    let raw = mesh.as_raw();
+
+    if raw.vertices.is_null() {
+        return None;
+    }
+
+    let vertices: Vec<_> = unsafe {
+        std::slice::from_raw_parts(raw.vertices as *const [f32; 3], raw.vertexCount as _)
+    }
+    .iter()
+    .map(|[x, y, z]| Point::new(*x, *y, *z))
+    .collect();
+
+    let indices = if raw.indices.is_null() {
+        (0..(vertices.len() as u32 / 3))
+            .map(|i| [i * 3, i * 3 + 1, i * 3 + 2])
+            .collect()
+    } else {
+        unsafe {
+            std::slice::from_raw_parts(raw.indices as *const u16, raw.triangleCount as usize * 3)
+        }
+        .chunks_exact(3)
+        .map(|tri| [tri[0] as u32, tri[1] as u32, tri[2] as u32])
+        .collect()
+    };
+
+    Some((vertices, indices))
+}
+
+#[inline]
+fn to_point(v: Vector3) -> Point<Real> {
+    Point::new(v.x, v.y, v.z)
+}
+
+#[inline]
+fn from_point(p: Point<Real>) -> Vector3 {
+    Vector3 {
+        x: p.x,
+        y: p.y,
+        z: p.z,
+    }
+}
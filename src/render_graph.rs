@@ -0,0 +1,366 @@
+use crate::{
+    color::Color,
+    drawing::{Draw, DrawHandle},
+    math::{Camera2D, Camera3D},
+    texture::RenderTexture2D,
+};
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+
+/// Camera a pass is drawn with
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PassCamera {
+    /// 2D mode
+    Camera2D(Camera2D),
+    /// 3D mode
+    Camera3D(Camera3D),
+}
+
+/// Named render pass of a [`RenderGraph`]
+pub struct RenderPass<'a> {
+    name: String,
+    target: Option<String>,
+    clear_color: Option<Color>,
+    camera: Option<PassCamera>,
+    dependencies: Vec<String>,
+    draw: Box<dyn FnMut(&mut PassHandle<'_>) + 'a>,
+}
+
+impl<'a> fmt::Debug for RenderPass<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RenderPass")
+            .field("name", &self.name)
+            .field("target", &self.target)
+            .field("clear_color", &self.clear_color)
+            .field("camera", &self.camera)
+            .field("dependencies", &self.dependencies)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a> RenderPass<'a> {
+    /// Create pass drawing to the screen, without clearing it and without a camera
+    #[inline]
+    pub fn new(name: &str, draw: impl FnMut(&mut PassHandle<'_>) + 'a) -> Self {
+        Self {
+            name: name.to_string(),
+            target: None,
+            clear_color: None,
+            camera: None,
+            dependencies: Vec::new(),
+            draw: Box::new(draw),
+        }
+    }
+
+    /// Draw to a target of the graph instead of the screen
+    #[inline]
+    pub fn with_target(mut self, target: &str) -> Self {
+        self.target = Some(target.to_string());
+        self
+    }
+
+    /// Clear the target before drawing
+    #[inline]
+    pub fn with_clear_color(mut self, color: Color) -> Self {
+        self.clear_color = Some(color);
+        self
+    }
+
+    /// Draw in 2D or 3D mode with a camera
+    #[inline]
+    pub fn with_camera(mut self, camera: PassCamera) -> Self {
+        self.camera = Some(camera);
+        self
+    }
+
+    /// Run after another pass
+    #[inline]
+    pub fn with_dependency(mut self, pass: &str) -> Self {
+        self.dependencies.push(pass.to_string());
+        self
+    }
+
+    /// Pass name
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// Draw handle of a pass, the target and camera are already set up
+#[derive(Debug)]
+pub struct PassHandle<'g> {
+    name: &'g str,
+    target: Option<&'g str>,
+    targets: &'g [(String, RenderTexture2D)],
+}
+
+impl<'g> PassHandle<'g> {
+    /// Name of the pass being drawn
+    #[inline]
+    pub fn name(&self) -> &str {
+        self.name
+    }
+
+    /// Target of the graph, e.g. to sample the output of a previous pass
+    ///
+    /// `None` for the target of the pass being drawn, it can't be read while drawing to it.
+    #[inline]
+    pub fn texture(&self, name: &str) -> Option<&'g RenderTexture2D> {
+        if self.target == Some(name) {
+            return None;
+        }
+
+        self.targets
+            .iter()
+            .find(|(target, _)| target == name)
+            .map(|(_, texture)| texture)
+    }
+}
+
+impl<'g> Draw for PassHandle<'g> {}
+
+/// Render passes run in dependency order
+///
+/// Passes draw to render textures owned by the graph or to the screen.
+///
+/// The graph begins and ends texture and camera modes itself, so they are always paired.
+pub struct RenderGraph<'a> {
+    targets: Vec<(String, RenderTexture2D)>,
+    passes: Vec<RenderPass<'a>>,
+    timings: Vec<(usize, Duration)>,
+}
+
+impl<'a> fmt::Debug for RenderGraph<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RenderGraph")
+            .field("targets", &self.targets)
+            .field("passes", &self.passes)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a> Default for RenderGraph<'a> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> RenderGraph<'a> {
+    /// Create empty graph
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            targets: Vec::new(),
+            passes: Vec::new(),
+            timings: Vec::new(),
+        }
+    }
+
+    /// Add (or recreate) a render texture target, `false` if it couldn't be created
+    pub fn add_target(&mut self, name: &str, width: u32, height: u32) -> bool {
+        let texture = match RenderTexture2D::new(width, height) {
+            Ok(texture) => texture,
+            Err(_) => return false,
+        };
+
+        match self.targets.iter_mut().find(|(target, _)| target == name) {
+            Some((_, target)) => *target = texture,
+            None => self.targets.push((name.to_string(), texture)),
+        }
+
+        true
+    }
+
+    /// Render texture target
+    #[inline]
+    pub fn target(&self, name: &str) -> Option<&RenderTexture2D> {
+        self.targets
+            .iter()
+            .find(|(target, _)| target == name)
+            .map(|(_, texture)| texture)
+    }
+
+    /// Add pass
+    #[inline]
+    pub fn add_pass(&mut self, pass: RenderPass<'a>) {
+        self.passes.push(pass);
+    }
+
+    /// Remove pass, `None` if there's no pass with that name
+    #[inline]
+    pub fn remove_pass(&mut self, name: &str) -> Option<RenderPass<'a>> {
+        let index = self.passes.iter().position(|pass| pass.name == name)?;
+
+        Some(self.passes.remove(index))
+    }
+
+    /// Pass names in execution order, `None` if the graph isn't valid
+    ///
+    /// Passes run after their dependencies, otherwise in the order they were added.
+    /// A graph is invalid if pass names are duplicated, a pass depends on an unknown pass or draws to an unknown
+    /// target, or the dependencies have a cycle. Debug builds print what's wrong.
+    pub fn execution_order(&self) -> Option<Vec<&str>> {
+        let order = self.order()?;
+
+        Some(
+            order
+                .into_iter()
+                .map(|i| self.passes[i].name.as_str())
+                .collect(),
+        )
+    }
+
+    /// Run all passes, `false` (without drawing anything) if the graph isn't valid
+    pub fn execute(&mut self, d: &mut DrawHandle<'_>) -> bool {
+        let order = match self.order() {
+            Some(order) => order,
+            None => return false,
+        };
+
+        self.timings.clear();
+
+        for index in order {
+            let start = Instant::now();
+            let pass = &mut self.passes[index];
+            let targets = &self.targets;
+
+            let target = pass.target.as_deref().and_then(|name| {
+                targets
+                    .iter()
+                    .find(|(target, _)| target == name)
+                    .map(|(_, texture)| texture)
+            });
+
+            match target {
+                Some(target) => d.with_texture_mode(target, |d| draw_pass(d, pass, targets)),
+                None => draw_pass(d, pass, targets),
+            }
+
+            self.timings.push((index, start.elapsed()));
+        }
+
+        true
+    }
+
+    /// CPU time spent in every pass during the last [`RenderGraph::execute`], in execution order
+    ///
+    /// GPU work is asynchronous and only partly included.
+    #[inline]
+    pub fn timings(&self) -> impl Iterator<Item = (&str, Duration)> + '_ {
+        self.timings
+            .iter()
+            .filter_map(|&(index, time)| Some((self.passes.get(index)?.name.as_str(), time)))
+    }
+
+    fn order(&self) -> Option<Vec<usize>> {
+        let find = |name: &str| self.passes.iter().position(|pass| pass.name == name);
+
+        for (i, pass) in self.passes.iter().enumerate() {
+            if find(&pass.name) != Some(i) {
+                #[cfg(debug_assertions)]
+                crate::core::warn(&format!(
+                    "RenderGraph: pass name '{}' is used twice",
+                    pass.name
+                ));
+
+                return None;
+            }
+
+            if let Some(target) = &pass.target {
+                if self.target(target).is_none() {
+                    #[cfg(debug_assertions)]
+                    crate::core::warn(&format!(
+                        "RenderGraph: pass '{}' draws to unknown target '{}'",
+                        pass.name, target
+                    ));
+
+                    return None;
+                }
+            }
+        }
+
+        let mut dependencies = Vec::with_capacity(self.passes.len());
+
+        for pass in self.passes.iter() {
+            let mut indices = Vec::with_capacity(pass.dependencies.len());
+
+            for dependency in pass.dependencies.iter() {
+                match find(dependency) {
+                    Some(index) => indices.push(index),
+                    None => {
+                        #[cfg(debug_assertions)]
+                        crate::core::warn(&format!(
+                            "RenderGraph: pass '{}' depends on unknown pass '{}'",
+                            pass.name, dependency
+                        ));
+
+                        return None;
+                    }
+                }
+            }
+
+            dependencies.push(indices);
+        }
+
+        // repeatedly take the first pass whose dependencies all ran
+        let mut done = vec![false; self.passes.len()];
+        let mut order = Vec::with_capacity(self.passes.len());
+
+        while order.len() < self.passes.len() {
+            let next = (0..self.passes.len())
+                .find(|&i| !done[i] && dependencies[i].iter().all(|&dependency| done[dependency]));
+
+            match next {
+                Some(next) => {
+                    done[next] = true;
+                    order.push(next);
+                }
+                None => {
+                    #[cfg(debug_assertions)]
+                    crate::core::warn(&format!(
+                        "RenderGraph: dependency cycle between passes {:?}",
+                        self.passes
+                            .iter()
+                            .zip(done.iter())
+                            .filter(|(_, &done)| !done)
+                            .map(|(pass, _)| pass.name.as_str())
+                            .collect::<Vec<_>>()
+                    ));
+
+                    return None;
+                }
+            }
+        }
+
+        Some(order)
+    }
+}
+
+/// Clear the pass's target and draw the pass with its camera, the target is already set up
+fn draw_pass<D: Draw>(d: &mut D, pass: &mut RenderPass<'_>, targets: &[(String, RenderTexture2D)]) {
+    if let Some(color) = pass.clear_color {
+        d.clear_background(color);
+    }
+
+    let mut handle = PassHandle {
+        name: &pass.name,
+        target: pass.target.as_deref(),
+        targets,
+    };
+
+    match pass.camera {
+        Some(PassCamera::Camera2D(camera)) => {
+            let _mode = d.begin_mode_2d(camera);
+            (pass.draw)(&mut handle);
+        }
+        Some(PassCamera::Camera3D(camera)) => {
+            let _mode = d.begin_mode_3d(camera);
+            (pass.draw)(&mut handle);
+        }
+        None => (pass.draw)(&mut handle),
+    }
+}
@@ -0,0 +1,286 @@
+use crate::Raylib;
+
+/// Seedable random number generator (xorshift64*) whose whole state can be saved and restored
+///
+/// raylib's [`Raylib::get_random_value`] can't be snapshotted, so anything that has to play back the
+/// same way in a replay or test should take its random numbers from a `GameRng` instead.
+/// Cloning it snapshots the state.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameRng {
+    state: u64,
+}
+
+impl GameRng {
+    /// Create a generator from a seed, every seed gives a different sequence
+    #[inline]
+    pub fn new(seed: u64) -> Self {
+        // splitmix64, so similar seeds don't start with similar states
+        let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+
+        Self::from_state(z)
+    }
+
+    /// Restore a generator from [`GameRng::state`]
+    #[inline]
+    pub fn from_state(state: u64) -> Self {
+        Self {
+            // xorshift gets stuck on 0
+            state: if state == 0 {
+                0x9E37_79B9_7F4A_7C15
+            } else {
+                state
+            },
+        }
+    }
+
+    /// Current state, restore it with [`GameRng::from_state`]
+    #[inline]
+    pub fn state(&self) -> u64 {
+        self.state
+    }
+
+    /// Independent generator seeded from this one, e.g. one per system so they don't affect each other
+    #[inline]
+    pub fn fork(&mut self) -> Self {
+        Self::new(self.next_u64())
+    }
+
+    /// Random 64 bits
+    #[inline]
+    pub fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        self.state = x;
+
+        x.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// Random 32 bits
+    #[inline]
+    pub fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    /// Random value in [0..1)
+    #[inline]
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u32() >> 8) as f32 / (1u32 << 24) as f32
+    }
+
+    /// Random value in [0..1)
+    #[inline]
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Random value between min and max (both included), like [`Raylib::get_random_value`]
+    #[inline]
+    pub fn range_i32(&mut self, min: i32, max: i32) -> i32 {
+        let (min, max) = if min <= max { (min, max) } else { (max, min) };
+        let span = (max as i64 - min as i64 + 1) as u64;
+
+        (min as i64 + ((self.next_u32() as u64 * span) >> 32) as i64) as i32
+    }
+
+    /// Random value in [min..max)
+    #[inline]
+    pub fn range_f32(&mut self, min: f32, max: f32) -> f32 {
+        min + (max - min) * self.next_f32()
+    }
+
+    /// `true` with a probability from 0 to 1
+    #[inline]
+    pub fn chance(&mut self, probability: f32) -> bool {
+        self.next_f32() < probability
+    }
+
+    /// Random element, `None` if the slice is empty
+    #[inline]
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+        if items.is_empty() {
+            return None;
+        }
+
+        let index = (self.next_u32() as u64 * items.len() as u64) >> 32;
+
+        items.get(index as usize)
+    }
+
+    /// Shuffle a slice
+    #[inline]
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = ((self.next_u32() as u64 * (i as u64 + 1)) >> 32) as usize;
+            items.swap(i, j);
+        }
+    }
+}
+
+/// Game time advanced only by the frame times it's given, with an optional fixed time step
+///
+/// Feed it [`Raylib::get_frame_time`] while playing and the recorded frame times during a replay,
+/// and everything reading the time from it plays back the same way. Cloning it snapshots the state.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameClock {
+    time: f64,
+    frame: u64,
+    delta: f32,
+    step: Option<f32>,
+    accumulator: f32,
+    max_steps: u32,
+    time_scale: f32,
+    paused: bool,
+}
+
+impl GameClock {
+    /// Clock that advances by the frame time every tick
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            time: 0.,
+            frame: 0,
+            delta: 0.,
+            step: None,
+            accumulator: 0.,
+            max_steps: 8,
+            time_scale: 1.,
+            paused: false,
+        }
+    }
+
+    /// Clock that advances in steps of `step` seconds, as many as fit in the frame times so far
+    #[inline]
+    pub fn fixed(step: f32) -> Self {
+        Self {
+            step: Some(step.max(f32::EPSILON)),
+            delta: step.max(f32::EPSILON),
+            ..Self::new()
+        }
+    }
+
+    /// Limit the steps of one tick so a long frame doesn't make the game fall further behind
+    /// (8 by default, fixed step clocks only)
+    #[inline]
+    pub fn with_max_steps(mut self, max_steps: u32) -> Self {
+        self.max_steps = max_steps.max(1);
+        self
+    }
+
+    /// Advance by a frame time in seconds, returns how many updates to run (always 1 without a fixed step)
+    ///
+    /// Negative and non-finite frame times count as 0.
+    pub fn tick(&mut self, frame_time: f32) -> u32 {
+        let frame_time = if frame_time.is_finite() {
+            frame_time.max(0.)
+        } else {
+            0.
+        };
+        let scaled = if self.paused {
+            0.
+        } else {
+            frame_time * self.time_scale
+        };
+
+        self.frame += 1;
+
+        match self.step {
+            Some(step) => {
+                self.accumulator += scaled;
+
+                let steps = ((self.accumulator / step) as u32).min(self.max_steps);
+
+                self.accumulator -= steps as f32 * step;
+                // drop what couldn't be caught up with
+                self.accumulator = self.accumulator.min(step);
+                self.time += steps as f64 * step as f64;
+
+                steps
+            }
+            None => {
+                self.delta = scaled;
+                self.time += scaled as f64;
+
+                1
+            }
+        }
+    }
+
+    /// Advance by raylib's last frame time, see [`GameClock::tick`]
+    #[inline]
+    pub fn tick_frame(&mut self, rl: &Raylib) -> u32 {
+        self.tick(rl.get_frame_time().as_secs_f32())
+    }
+
+    /// Seconds each update covers: the fixed step, or the scaled last frame time
+    #[inline]
+    pub fn delta(&self) -> f32 {
+        self.delta
+    }
+
+    /// Game time in seconds
+    #[inline]
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+
+    /// Number of ticks so far
+    #[inline]
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    /// Fixed step, if there's one
+    #[inline]
+    pub fn step(&self) -> Option<f32> {
+        self.step
+    }
+
+    /// How far into the next fixed step the game is, from 0 to 1, for interpolating what's drawn
+    ///
+    /// Always 1 without a fixed step.
+    #[inline]
+    pub fn alpha(&self) -> f32 {
+        match self.step {
+            Some(step) => (self.accumulator / step).clamp(0., 1.),
+            None => 1.,
+        }
+    }
+
+    /// Speed of game time (1 by default)
+    #[inline]
+    pub fn time_scale(&self) -> f32 {
+        self.time_scale
+    }
+
+    /// Set the speed of game time
+    #[inline]
+    pub fn set_time_scale(&mut self, time_scale: f32) {
+        self.time_scale = time_scale.max(0.);
+    }
+
+    /// Is game time stopped?
+    #[inline]
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Stop or resume game time, ticks still count frames
+    #[inline]
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+}
+
+impl Default for GameClock {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,640 @@
+use crate::{
+    color::Color,
+    core::{release_context_object, track_context_object, ContextObject},
+    drawing::Draw,
+    math::{columns, from_columns, multiply, Matrix, Vector2, Vector3},
+    model::VertexAttribute,
+    shader::{Shader, ShaderLocationIndex},
+    stats,
+    texture::Texture2D,
+};
+
+use std::{ffi::c_void, ops::Deref};
+
+/// Raw rlgl bindings (rlgl.h isn't part of the generated bindings)
+///
+/// Everything here talks to the render batch and the OpenGL state directly,
+/// flush the batch with `rlDrawRenderBatchActive` before changing state that affects it.
+#[allow(non_snake_case)]
+pub mod ffi {
+    use crate::ffi::Matrix;
+    use core::ffi::{c_double, c_float, c_int, c_uchar, c_uint, c_void};
+
+    /// Primitive for `rlBegin`
+    pub const RL_LINES: c_int = 0x0001;
+    /// Primitive for `rlBegin`
+    pub const RL_TRIANGLES: c_int = 0x0004;
+    /// Primitive for `rlBegin`
+    pub const RL_QUADS: c_int = 0x0007;
+
+    /// Matrix for `rlMatrixMode`
+    pub const RL_MODELVIEW: c_int = 0x1700;
+    /// Matrix for `rlMatrixMode`
+    pub const RL_PROJECTION: c_int = 0x1701;
+    /// Matrix for `rlMatrixMode`
+    pub const RL_TEXTURE: c_int = 0x1702;
+
+    /// Vertex attribute type for `rlSetVertexAttribute`
+    pub const RL_UNSIGNED_BYTE: c_int = 0x1401;
+    /// Vertex attribute type for `rlSetVertexAttribute`
+    pub const RL_FLOAT: c_int = 0x1406;
+
+    /// `rlFramebufferAttachType`
+    pub const RL_ATTACHMENT_COLOR_CHANNEL0: c_int = 0;
+    /// `rlFramebufferAttachType`
+    pub const RL_ATTACHMENT_DEPTH: c_int = 100;
+    /// `rlFramebufferAttachType`
+    pub const RL_ATTACHMENT_STENCIL: c_int = 200;
+    /// `rlFramebufferAttachTextureType`
+    pub const RL_ATTACHMENT_TEXTURE2D: c_int = 100;
+    /// `rlFramebufferAttachTextureType`
+    pub const RL_ATTACHMENT_RENDERBUFFER: c_int = 200;
+
+    extern "C" {
+        // matrix stack
+        pub fn rlMatrixMode(mode: c_int);
+        pub fn rlPushMatrix();
+        pub fn rlPopMatrix();
+        pub fn rlLoadIdentity();
+        pub fn rlTranslatef(x: c_float, y: c_float, z: c_float);
+        /// Rotate by `angle` degrees around an axis
+        pub fn rlRotatef(angle: c_float, x: c_float, y: c_float, z: c_float);
+        pub fn rlScalef(x: c_float, y: c_float, z: c_float);
+        /// Multiply the current matrix by 16 column major floats
+        pub fn rlMultMatrixf(matf: *const c_float);
+        pub fn rlFrustum(
+            left: c_double,
+            right: c_double,
+            bottom: c_double,
+            top: c_double,
+            znear: c_double,
+            zfar: c_double,
+        );
+        pub fn rlOrtho(
+            left: c_double,
+            right: c_double,
+            bottom: c_double,
+            top: c_double,
+            znear: c_double,
+            zfar: c_double,
+        );
+        pub fn rlViewport(x: c_int, y: c_int, width: c_int, height: c_int);
+        pub fn rlGetMatrixModelview() -> Matrix;
+        pub fn rlGetMatrixProjection() -> Matrix;
+        /// Matrix pushed on top of the modelview with `rlPushMatrix`
+        pub fn rlGetMatrixTransform() -> Matrix;
+        pub fn rlSetMatrixModelview(view: Matrix);
+        pub fn rlSetMatrixProjection(proj: Matrix);
+
+        // immediate mode vertices, set the color, texcoord and normal before each vertex
+        pub fn rlBegin(mode: c_int);
+        pub fn rlEnd();
+        pub fn rlVertex2i(x: c_int, y: c_int);
+        pub fn rlVertex2f(x: c_float, y: c_float);
+        pub fn rlVertex3f(x: c_float, y: c_float, z: c_float);
+        pub fn rlTexCoord2f(x: c_float, y: c_float);
+        pub fn rlNormal3f(x: c_float, y: c_float, z: c_float);
+        pub fn rlColor4ub(r: c_uchar, g: c_uchar, b: c_uchar, a: c_uchar);
+        pub fn rlColor3f(x: c_float, y: c_float, z: c_float);
+        pub fn rlColor4f(x: c_float, y: c_float, z: c_float, w: c_float);
+        /// Texture of the following vertices, 0 checks the batch limit instead
+        pub fn rlSetTexture(id: c_uint);
+
+        // render batch
+        pub fn rlDrawRenderBatchActive();
+        /// Flush the batch if `vCount` more vertices don't fit, returns whether it was flushed
+        pub fn rlCheckRenderBatchLimit(vCount: c_int) -> bool;
+
+        // state
+        pub fn rlActiveTextureSlot(slot: c_int);
+        pub fn rlEnableTexture(id: c_uint);
+        pub fn rlDisableTexture();
+        pub fn rlEnableShader(id: c_uint);
+        pub fn rlDisableShader();
+        pub fn rlEnableFramebuffer(id: c_uint);
+        pub fn rlDisableFramebuffer();
+        pub fn rlActiveDrawBuffers(count: c_int);
+        pub fn rlEnableColorBlend();
+        pub fn rlDisableColorBlend();
+        pub fn rlEnableDepthTest();
+        pub fn rlDisableDepthTest();
+        pub fn rlEnableDepthMask();
+        pub fn rlDisableDepthMask();
+        pub fn rlEnableBackfaceCulling();
+        pub fn rlDisableBackfaceCulling();
+        /// 0 for front faces, 1 for back faces
+        pub fn rlSetCullFace(mode: c_int);
+        pub fn rlEnableScissorTest();
+        pub fn rlDisableScissorTest();
+        pub fn rlScissor(x: c_int, y: c_int, width: c_int, height: c_int);
+        pub fn rlEnableWireMode();
+        pub fn rlDisableWireMode();
+        pub fn rlSetLineWidth(width: c_float);
+        pub fn rlGetLineWidth() -> c_float;
+        pub fn rlEnableSmoothLines();
+        pub fn rlDisableSmoothLines();
+        pub fn rlClearColor(r: c_uchar, g: c_uchar, b: c_uchar, a: c_uchar);
+        pub fn rlClearScreenBuffers();
+        pub fn rlSetBlendMode(mode: c_int);
+        /// `rlGlVersion`, 3 for OpenGL 3.3
+        pub fn rlGetVersion() -> c_int;
+        pub fn rlGetFramebufferWidth() -> c_int;
+        pub fn rlGetFramebufferHeight() -> c_int;
+        /// 1x1 white texture shapes are drawn with
+        pub fn rlGetTextureIdDefault() -> c_uint;
+        pub fn rlGetShaderIdDefault() -> c_uint;
+
+        // vertex arrays and buffers
+        pub fn rlLoadVertexArray() -> c_uint;
+        pub fn rlUnloadVertexArray(vaoId: c_uint);
+        pub fn rlEnableVertexArray(vaoId: c_uint) -> bool;
+        pub fn rlDisableVertexArray();
+        pub fn rlLoadVertexBuffer(buffer: *const c_void, size: c_int, dynamic: bool) -> c_uint;
+        pub fn rlLoadVertexBufferElement(
+            buffer: *const c_void,
+            size: c_int,
+            dynamic: bool,
+        ) -> c_uint;
+        pub fn rlUpdateVertexBuffer(
+            bufferId: c_uint,
+            data: *const c_void,
+            dataSize: c_int,
+            offset: c_int,
+        );
+        pub fn rlUpdateVertexBufferElements(
+            id: c_uint,
+            data: *const c_void,
+            dataSize: c_int,
+            offset: c_int,
+        );
+        pub fn rlUnloadVertexBuffer(vboId: c_uint);
+        pub fn rlEnableVertexBuffer(id: c_uint);
+        pub fn rlDisableVertexBuffer();
+        pub fn rlEnableVertexBufferElement(id: c_uint);
+        pub fn rlDisableVertexBufferElement();
+        /// Describe the bound vertex buffer for an attribute location
+        pub fn rlSetVertexAttribute(
+            index: c_uint,
+            compSize: c_int,
+            type_: c_int,
+            normalized: bool,
+            stride: c_int,
+            pointer: *const c_void,
+        );
+        pub fn rlSetVertexAttributeDivisor(index: c_uint, divisor: c_int);
+        pub fn rlEnableVertexAttribute(index: c_uint);
+        pub fn rlDisableVertexAttribute(index: c_uint);
+        /// Draw `count` vertices of the bound vertex array as triangles
+        pub fn rlDrawVertexArray(offset: c_int, count: c_int);
+        /// Draw `count` 16 bit indices of the bound element buffer as triangles
+        pub fn rlDrawVertexArrayElements(offset: c_int, count: c_int, buffer: *const c_void);
+        /// Draw `count` vertices of the bound vertex array as triangles, `instances` times
+        pub fn rlDrawVertexArrayInstanced(offset: c_int, count: c_int, instances: c_int);
+        pub fn rlDrawVertexArrayElementsInstanced(
+            offset: c_int,
+            count: c_int,
+            buffer: *const c_void,
+            instances: c_int,
+        );
+
+        // textures and framebuffers
+        pub fn rlLoadTexture(
+            data: *const c_void,
+            width: c_int,
+            height: c_int,
+            format: c_int,
+            mipmapCount: c_int,
+        ) -> c_uint;
+        pub fn rlLoadTextureDepth(width: c_int, height: c_int, useRenderBuffer: bool) -> c_uint;
+        pub fn rlUnloadTexture(id: c_uint);
+        pub fn rlLoadFramebuffer(width: c_int, height: c_int) -> c_uint;
+        pub fn rlFramebufferAttach(
+            fboId: c_uint,
+            texId: c_uint,
+            attachType: c_int,
+            texType: c_int,
+            mipLevel: c_int,
+        );
+        pub fn rlFramebufferComplete(id: c_uint) -> bool;
+        pub fn rlUnloadFramebuffer(id: c_uint);
+
+        // shaders
+        pub fn rlSetUniformMatrix(locIndex: c_int, mat: Matrix);
+    }
+}
+
+/// Primitive drawn by [`begin`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Primitive {
+    /// Every 2 vertices are a line
+    Lines,
+    /// Every 3 vertices are a triangle
+    Triangles,
+    /// Every 4 vertices are a quad, in counter clockwise order
+    Quads,
+}
+
+impl Primitive {
+    #[inline]
+    fn raw(self) -> i32 {
+        match self {
+            Self::Lines => ffi::RL_LINES,
+            Self::Triangles => ffi::RL_TRIANGLES,
+            Self::Quads => ffi::RL_QUADS,
+        }
+    }
+}
+
+/// Begin adding vertices of a primitive to the render batch, textured with `texture` if there's one
+///
+/// Vertices go through the same batch as raylib's shapes, with the current shader, blend mode and
+/// [`push_matrix`] transform. The batch is flushed by itself when it fills up.
+#[inline]
+pub fn begin<'a, D: Draw>(
+    d: &'a mut D,
+    primitive: Primitive,
+    texture: Option<&Texture2D>,
+) -> Primitives<'a, D> {
+    unsafe {
+        let id = texture.map_or_else(|| ffi::rlGetTextureIdDefault(), |texture| texture.raw.id);
+
+        // rlBegin resets the texture when the primitive changes, and a new draw call
+        // started by rlSetTexture doesn't know the primitive yet, so set it on both sides
+        ffi::rlSetTexture(id);
+        ffi::rlBegin(primitive.raw());
+        ffi::rlSetTexture(id);
+    }
+
+    Primitives(d)
+}
+
+/// Vertices being added to the render batch, see [`begin`]
+///
+/// The color, texture coordinates and normal apply to the vertices added after them.
+pub struct Primitives<'a, D>(&'a mut D);
+
+impl<'a, D> Primitives<'a, D> {
+    /// Color of the next vertices
+    #[inline]
+    pub fn color(&mut self, color: Color) -> &mut Self {
+        unsafe { ffi::rlColor4ub(color.r, color.g, color.b, color.a) }
+        self
+    }
+
+    /// Texture coordinates of the next vertices
+    #[inline]
+    pub fn tex_coord(&mut self, uv: Vector2) -> &mut Self {
+        unsafe { ffi::rlTexCoord2f(uv.x, uv.y) }
+        self
+    }
+
+    /// Normal of the next vertices
+    #[inline]
+    pub fn normal(&mut self, normal: Vector3) -> &mut Self {
+        unsafe { ffi::rlNormal3f(normal.x, normal.y, normal.z) }
+        self
+    }
+
+    /// Add a vertex on the 2D plane (z is the current batch depth)
+    #[inline]
+    pub fn vertex_2d(&mut self, position: Vector2) -> &mut Self {
+        unsafe { ffi::rlVertex2f(position.x, position.y) }
+        self
+    }
+
+    /// Add a vertex
+    #[inline]
+    pub fn vertex_3d(&mut self, position: Vector3) -> &mut Self {
+        unsafe { ffi::rlVertex3f(position.x, position.y, position.z) }
+        self
+    }
+
+    /// End the primitives
+    #[inline]
+    pub fn end(self) {
+        drop(self)
+    }
+}
+
+impl<'a, D> Drop for Primitives<'a, D> {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            ffi::rlEnd();
+            ffi::rlSetTexture(0);
+        }
+    }
+}
+
+/// Push a matrix on top of the current transform, popped when the returned object is dropped
+///
+/// Transforms everything drawn through it, raylib's own shapes included, without flushing the batch.
+#[inline]
+pub fn push_matrix<D: Draw>(d: &mut D) -> DrawMatrix<'_, D> {
+    unsafe { ffi::rlPushMatrix() }
+
+    DrawMatrix(d)
+}
+
+/// An object that handles drawing with a pushed matrix, see [`push_matrix`]
+///
+/// Transformations apply in reverse order: the last one is applied to vertices first.
+pub struct DrawMatrix<'a, T>(&'a mut T);
+
+impl<'a, T> DrawMatrix<'a, T> {
+    /// Translate
+    #[inline]
+    pub fn translate(&mut self, offset: Vector3) -> &mut Self {
+        unsafe { ffi::rlTranslatef(offset.x, offset.y, offset.z) }
+        self
+    }
+
+    /// Rotate `angle` degrees around `axis`
+    #[inline]
+    pub fn rotate(&mut self, angle: f32, axis: Vector3) -> &mut Self {
+        unsafe { ffi::rlRotatef(angle, axis.x, axis.y, axis.z) }
+        self
+    }
+
+    /// Scale
+    #[inline]
+    pub fn scale(&mut self, scale: Vector3) -> &mut Self {
+        unsafe { ffi::rlScalef(scale.x, scale.y, scale.z) }
+        self
+    }
+
+    /// Multiply by a matrix
+    #[inline]
+    pub fn multiply(&mut self, matrix: Matrix) -> &mut Self {
+        let m = crate::ffi::Matrix::from(matrix);
+        // rlgl reads the floats in m0, m1, m2... order
+        let matf = [
+            m.m0, m.m1, m.m2, m.m3, m.m4, m.m5, m.m6, m.m7, m.m8, m.m9, m.m10, m.m11, m.m12, m.m13,
+            m.m14, m.m15,
+        ];
+
+        unsafe { ffi::rlMultMatrixf(matf.as_ptr()) }
+        self
+    }
+
+    /// Pop the matrix
+    #[inline]
+    pub fn pop_matrix(self) {
+        drop(self)
+    }
+}
+
+impl<'a, T> Deref for DrawMatrix<'a, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+impl<'a, T> Drop for DrawMatrix<'a, T> {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe { ffi::rlPopMatrix() }
+    }
+}
+
+impl<'a, T> Draw for DrawMatrix<'a, T> {}
+
+/// Current modelview, projection and pushed matrices multiplied together, what `mvp` is for raylib's shaders
+#[inline]
+pub fn mvp_matrix() -> Matrix {
+    let [modelview, projection, transform]: [[[f32; 4]; 4]; 3] = unsafe {
+        [
+            ffi::rlGetMatrixModelview(),
+            ffi::rlGetMatrixProjection(),
+            ffi::rlGetMatrixTransform(),
+        ]
+        .map(|matrix| columns(&matrix.into()))
+    };
+
+    // MatrixMultiply(MatrixMultiply(transform, modelview), projection)
+    from_columns(&multiply(&projection, &multiply(&modelview, &transform)))
+}
+
+/// Draw everything batched so far
+///
+/// Needed before changing OpenGL state the batch doesn't track.
+#[inline]
+pub fn flush<D: Draw>(_d: &mut D) {
+    stats::record_batch();
+    unsafe { ffi::rlDrawRenderBatchActive() }
+}
+
+/// Vertex attributes in GPU buffers, drawn as triangles with a shader
+///
+/// For meshes that change every frame or need custom attributes without a [`crate::model::Mesh`].
+/// Needs vertex array objects (OpenGL 3.3 or ES 3).
+#[derive(Debug)]
+pub struct VertexArray {
+    id: u32,
+    // (buffer id, values it holds)
+    buffers: Vec<(u32, usize)>,
+    indices: Option<(u32, usize)>,
+    vertex_count: usize,
+}
+
+impl VertexArray {
+    /// Create an empty vertex array
+    #[inline]
+    pub fn new() -> Option<Self> {
+        let id = unsafe { ffi::rlLoadVertexArray() };
+
+        if id == 0 {
+            #[cfg(debug_assertions)]
+            crate::core::warn("rlgl: vertex arrays aren't supported");
+
+            return None;
+        }
+
+        track_context_object(ContextObject::VertexArray(id));
+
+        Some(Self {
+            id,
+            buffers: Vec::new(),
+            indices: None,
+            vertex_count: 0,
+        })
+    }
+
+    /// Vertices drawn, the length of the shortest attribute
+    #[inline]
+    pub fn vertex_count(&self) -> usize {
+        self.vertex_count
+    }
+
+    /// Upload an attribute for shader attribute `location`, returns its buffer index
+    ///
+    /// `divisor` 0 reads a value per vertex, 1 or more a value per that many instances.
+    /// `dynamic` buffers are meant to be rewritten with [`VertexArray::update_attribute`].
+    pub fn add_attribute<A: VertexAttribute>(
+        &mut self,
+        location: u32,
+        data: &[A],
+        divisor: u32,
+        dynamic: bool,
+    ) -> usize {
+        unsafe {
+            ffi::rlEnableVertexArray(self.id);
+
+            let buffer = ffi::rlLoadVertexBuffer(
+                data.as_ptr().cast(),
+                std::mem::size_of_val(data) as _,
+                dynamic,
+            );
+
+            ffi::rlSetVertexAttribute(
+                location,
+                A::COMPONENTS,
+                ffi::RL_FLOAT,
+                false,
+                0,
+                std::ptr::null(),
+            );
+            ffi::rlSetVertexAttributeDivisor(location, divisor as _);
+            ffi::rlEnableVertexAttribute(location);
+            ffi::rlDisableVertexArray();
+
+            self.buffers.push((buffer, data.len()));
+        }
+
+        if divisor == 0 {
+            self.vertex_count = self
+                .buffers
+                .iter()
+                .map(|&(_, len)| len)
+                .min()
+                .unwrap_or(0)
+                .min(data.len());
+        }
+
+        self.buffers.len() - 1
+    }
+
+    /// Upload an attribute, see [`VertexArray::add_attribute`]
+    #[inline]
+    pub fn with_attribute<A: VertexAttribute>(
+        mut self,
+        location: u32,
+        data: &[A],
+        divisor: u32,
+        dynamic: bool,
+    ) -> Self {
+        self.add_attribute(location, data, divisor, dynamic);
+        self
+    }
+
+    /// Overwrite the values of an attribute starting at `first`
+    ///
+    /// Returns `false` if the buffer doesn't exist or the data doesn't fit.
+    pub fn update_attribute<A: VertexAttribute>(
+        &mut self,
+        buffer: usize,
+        first: usize,
+        data: &[A],
+    ) -> bool {
+        let Some(&(id, len)) = self.buffers.get(buffer) else {
+            return false;
+        };
+
+        if first + data.len() > len {
+            return false;
+        }
+
+        unsafe {
+            ffi::rlUpdateVertexBuffer(
+                id,
+                data.as_ptr().cast(),
+                std::mem::size_of_val(data) as _,
+                (first * std::mem::size_of::<A>()) as _,
+            );
+        }
+
+        true
+    }
+
+    /// Set triangle indices, drawing them instead of the vertices in order
+    pub fn set_indices(&mut self, indices: &[u16]) {
+        unsafe {
+            ffi::rlEnableVertexArray(self.id);
+
+            if let Some((id, _)) = self.indices.take() {
+                ffi::rlUnloadVertexBuffer(id);
+            }
+
+            let id = ffi::rlLoadVertexBufferElement(
+                indices.as_ptr().cast(),
+                std::mem::size_of_val(indices) as _,
+                false,
+            );
+
+            ffi::rlDisableVertexArray();
+
+            self.indices = Some((id, indices.len()));
+        }
+    }
+
+    /// Draw the triangles `instances` times with a shader
+    ///
+    /// Sets the shader's `mvp` location (bound by raylib to the `mvp` uniform) to [`mvp_matrix`],
+    /// other uniforms have to be set beforehand.
+    pub fn draw<D: Draw>(&self, d: &mut D, shader: &mut Shader, instances: u32) {
+        if instances == 0 {
+            return;
+        }
+
+        flush(d);
+
+        if let Some(loc) = shader.builtin_location(ShaderLocationIndex::MatrixMvp) {
+            shader.set_value_matrix(loc, mvp_matrix());
+        }
+
+        unsafe {
+            ffi::rlEnableShader(shader.raw.id);
+            ffi::rlEnableVertexArray(self.id);
+
+            match self.indices {
+                Some((_, count)) => {
+                    ffi::rlDrawVertexArrayElementsInstanced(
+                        0,
+                        count as _,
+                        std::ptr::null::<c_void>(),
+                        instances as _,
+                    );
+                }
+                None => {
+                    ffi::rlDrawVertexArrayInstanced(0, self.vertex_count as _, instances as _);
+                }
+            }
+
+            ffi::rlDisableVertexArray();
+            ffi::rlDisableShader();
+        }
+    }
+}
+
+impl Drop for VertexArray {
+    fn drop(&mut self) {
+        if !release_context_object(ContextObject::VertexArray(self.id)) {
+            return;
+        }
+
+        unsafe {
+            for &(id, _) in &self.buffers {
+                ffi::rlUnloadVertexBuffer(id);
+            }
+
+            if let Some((id, _)) = self.indices {
+                ffi::rlUnloadVertexBuffer(id);
+            }
+
+            ffi::rlUnloadVertexArray(self.id);
+        }
+    }
+}
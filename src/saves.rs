@@ -0,0 +1,313 @@
+use crate::diagnostics::crc32;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    collections::HashMap,
+    env,
+    ffi::OsString,
+    fmt,
+    fs::{self, File},
+    io::{self, Write},
+    marker::PhantomData,
+    path::{Path, PathBuf},
+};
+
+/// First bytes of every save file
+const MAGIC: [u8; 4] = *b"RSAV";
+/// Magic, version, payload length and checksum
+const HEADER_LEN: usize = 4 + 4 + 8 + 4;
+
+/// Why a save couldn't be written or read
+#[derive(Debug)]
+pub enum SaveError {
+    /// Reading or writing the file failed, including the file not existing (see [`SaveError::is_missing`])
+    Io(io::Error),
+    /// The file isn't a save file, is truncated or fails its checksum
+    Corrupt,
+    /// The file was saved with this version and there's no migration for it
+    Version(u32),
+    /// The data couldn't be encoded, or decoded into the saved type
+    Serialize(bincode::Error),
+}
+
+impl SaveError {
+    /// Whether there's no save file at all
+    #[inline]
+    pub fn is_missing(&self) -> bool {
+        matches!(self, Self::Io(error) if error.kind() == io::ErrorKind::NotFound)
+    }
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "save file i/o error: {error}"),
+            Self::Corrupt => f.write_str("save file is corrupted"),
+            Self::Version(version) => write!(f, "save file version {version} isn't supported"),
+            Self::Serialize(error) => write!(f, "save data serialization failed: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for SaveError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::Serialize(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for SaveError {
+    #[inline]
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<bincode::Error> for SaveError {
+    #[inline]
+    fn from(error: bincode::Error) -> Self {
+        Self::Serialize(error)
+    }
+}
+
+/// Decode the payload of an older save version, for migrations
+#[inline]
+pub fn decode<O: DeserializeOwned>(payload: &[u8]) -> Result<O, SaveError> {
+    Ok(bincode::deserialize(payload)?)
+}
+
+type Migration<T> = Box<dyn Fn(&[u8]) -> Result<T, SaveError> + Send + Sync>;
+
+/// A versioned save file holding a `T`
+///
+/// Saves are written with bincode after a header with the version and a CRC-32 of the data.
+/// They're written to a temporary file which then replaces the old save, so a crash mid-save
+/// never leaves a half written file, and the previous save is kept next to it as a `.bak` that
+/// [`SaveFile::load`] falls back to if the save is corrupted.
+pub struct SaveFile<T> {
+    path: PathBuf,
+    version: u32,
+    migrations: HashMap<u32, Migration<T>>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> SaveFile<T> {
+    /// Save file at `path`, with the current data version
+    #[inline]
+    pub fn new(path: impl Into<PathBuf>, version: u32) -> Self {
+        Self {
+            path: path.into(),
+            version,
+            migrations: HashMap::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Save file of a numbered slot in the app's data directory (`slot<N>.sav`)
+    ///
+    /// `None` if the data directory is unknown, see [`data_dir`].
+    #[inline]
+    pub fn slot(app_name: &str, slot: u32, version: u32) -> Option<Self> {
+        let path = data_dir(app_name)?.join(format!("slot{slot}.sav"));
+
+        Some(Self::new(path, version))
+    }
+
+    /// Convert saves of an older version, `migration` gets their payload (see [`decode`])
+    #[inline]
+    pub fn with_migration(
+        mut self,
+        version: u32,
+        migration: impl Fn(&[u8]) -> Result<T, SaveError> + Send + Sync + 'static,
+    ) -> Self {
+        self.migrations.insert(version, Box::new(migration));
+        self
+    }
+
+    /// File path
+    #[inline]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Current data version
+    #[inline]
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Whether there's a save (or a backup of one)
+    #[inline]
+    pub fn exists(&self) -> bool {
+        self.path.exists() || self.backup_path().exists()
+    }
+
+    /// Path of the previous save
+    #[inline]
+    pub fn backup_path(&self) -> PathBuf {
+        with_suffix(&self.path, ".bak")
+    }
+
+    /// Delete the save and its backup
+    pub fn delete(&self) -> Result<(), SaveError> {
+        for path in [self.path.clone(), self.backup_path()] {
+            match fs::remove_file(path) {
+                Err(error) if error.kind() != io::ErrorKind::NotFound => return Err(error.into()),
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<T: Serialize> SaveFile<T> {
+    /// Write the save, keeping the previous one as a backup
+    pub fn save(&self, data: &T) -> Result<(), SaveError> {
+        let payload = bincode::serialize(data)?;
+
+        let mut bytes = Vec::with_capacity(HEADER_LEN + payload.len());
+        bytes.extend_from_slice(&MAGIC);
+        bytes.extend_from_slice(&self.version.to_le_bytes());
+        bytes.extend_from_slice(&(payload.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(&crc32(&payload).to_le_bytes());
+        bytes.extend_from_slice(&payload);
+
+        if let Some(dir) = self.path.parent() {
+            if !dir.as_os_str().is_empty() {
+                fs::create_dir_all(dir)?;
+            }
+        }
+
+        let temp = with_suffix(&self.path, ".tmp");
+
+        let mut file = File::create(&temp)?;
+        file.write_all(&bytes)?;
+        file.sync_all()?;
+        drop(file);
+
+        if self.path.exists() {
+            fs::rename(&self.path, self.backup_path())?;
+        }
+
+        fs::rename(&temp, &self.path)?;
+
+        Ok(())
+    }
+}
+
+impl<T: DeserializeOwned> SaveFile<T> {
+    /// Read the save, migrating it from older versions
+    ///
+    /// Falls back to the backup if the save is missing or corrupted.
+    pub fn load(&self) -> Result<T, SaveError> {
+        match self.load_from(&self.path) {
+            Err(error) if error.is_missing() || matches!(error, SaveError::Corrupt) => {
+                #[cfg(debug_assertions)]
+                if !error.is_missing() {
+                    crate::core::warn(&format!(
+                        "SaveFile: {}: {error}, trying the backup",
+                        self.path.display()
+                    ));
+                }
+
+                self.load_from(&self.backup_path()).map_err(|_| error)
+            }
+            result => result,
+        }
+    }
+
+    fn load_from(&self, path: &Path) -> Result<T, SaveError> {
+        let bytes = fs::read(path)?;
+
+        if bytes.len() < HEADER_LEN || bytes[..4] != MAGIC {
+            return Err(SaveError::Corrupt);
+        }
+
+        let version = u32::from_le_bytes(bytes[4..8].try_into().unwrap());
+        let len = u64::from_le_bytes(bytes[8..16].try_into().unwrap());
+        let checksum = u32::from_le_bytes(bytes[16..20].try_into().unwrap());
+        let payload = &bytes[HEADER_LEN..];
+
+        if payload.len() as u64 != len || crc32(payload) != checksum {
+            return Err(SaveError::Corrupt);
+        }
+
+        if version == self.version {
+            decode(payload)
+        } else if let Some(migration) = self.migrations.get(&version) {
+            migration(payload)
+        } else {
+            Err(SaveError::Version(version))
+        }
+    }
+}
+
+impl<T> fmt::Debug for SaveFile<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut migrations: Vec<_> = self.migrations.keys().collect();
+        migrations.sort_unstable();
+
+        f.debug_struct("SaveFile")
+            .field("path", &self.path)
+            .field("version", &self.version)
+            .field("migrations", &migrations)
+            .finish()
+    }
+}
+
+/// Slots with a save in the app's data directory, in order
+pub fn slots(app_name: &str) -> Vec<u32> {
+    let entries = match data_dir(app_name).map(fs::read_dir) {
+        Some(Ok(entries)) => entries,
+        _ => return Vec::new(),
+    };
+
+    let mut slots: Vec<u32> = entries
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name();
+            let name = name.to_str()?;
+
+            name.strip_prefix("slot")?
+                .strip_suffix(".sav")?
+                .parse()
+                .ok()
+        })
+        .collect();
+
+    slots.sort_unstable();
+    slots
+}
+
+/// Directory for an app's data, e.g. `%APPDATA%\<app>` on Windows,
+/// `~/Library/Application Support/<app>` on macOS and `~/.local/share/<app>` on Linux
+///
+/// `None` if the environment doesn't say where the home directory is.
+pub fn data_dir(app_name: &str) -> Option<PathBuf> {
+    let home = || {
+        env::var_os("HOME")
+            .filter(|home| !home.is_empty())
+            .map(PathBuf::from)
+    };
+
+    let base = if cfg!(windows) {
+        env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        home().map(|home| home.join("Library").join("Application Support"))
+    } else {
+        env::var_os("XDG_DATA_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| home().map(|home| home.join(".local").join("share")))
+    };
+
+    Some(base?.join(app_name))
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = OsString::from(path.as_os_str());
+    name.push(suffix);
+    name.into()
+}
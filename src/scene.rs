@@ -0,0 +1,474 @@
+use crate::{
+    color::Color,
+    drawing::{Draw, DrawHandle},
+    math::Rectangle,
+    presence::{NoPresence, Presence, PresenceProvider},
+    replay::GameClock,
+    texture::RenderTexture2D,
+    transition::{ScreenTransition, TransitionEffect},
+    Raylib,
+};
+use std::fmt;
+
+/// Game state (title screen, level, pause menu, ...) managed by a [`SceneManager`]
+pub trait Scene {
+    /// Update the scene, `dt` is the frame time in seconds
+    fn update(&mut self, rl: &mut Raylib, dt: f32);
+
+    /// Draw the scene (clearing the background is up to the scene)
+    fn draw(&mut self, d: &mut DrawHandle<'_>);
+
+    /// Scene change requested by the scene, checked after every update
+    #[inline]
+    fn transition(&mut self) -> Option<SceneChange> {
+        None
+    }
+
+    /// Rich presence while this is the current scene, checked every frame by the main loops
+    #[inline]
+    fn presence(&self) -> Option<Presence> {
+        None
+    }
+}
+
+/// Change of the scene stack
+pub enum SceneChange {
+    /// Put a scene on top of the current one
+    Push(Box<dyn Scene>),
+    /// Remove the current scene, going back to the one below
+    Pop,
+    /// Swap the current scene for another one
+    Replace(Box<dyn Scene>),
+    /// Remove all scenes
+    Clear,
+}
+
+impl fmt::Debug for SceneChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Push(_) => f.write_str("Push(..)"),
+            Self::Pop => f.write_str("Pop"),
+            Self::Replace(_) => f.write_str("Replace(..)"),
+            Self::Clear => f.write_str("Clear"),
+        }
+    }
+}
+
+/// Fade to a color and back, the scene changes when the screen is fully covered
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Fade {
+    /// Total duration in seconds, half of it fading out and half fading in
+    pub duration: f32,
+    /// Color the screen fades to
+    pub color: Color,
+}
+
+impl Fade {
+    /// Create new fade
+    #[inline]
+    pub const fn new(duration: f32, color: Color) -> Self {
+        Self { duration, color }
+    }
+}
+
+impl Default for Fade {
+    #[inline]
+    fn default() -> Self {
+        Self::new(0.5, Color::BLACK)
+    }
+}
+
+enum Pending {
+    Fade {
+        change: Option<SceneChange>,
+        fade: Fade,
+        elapsed: f32,
+    },
+    Effect {
+        change: Option<SceneChange>,
+        effect: TransitionEffect,
+        duration: f32,
+        elapsed: f32,
+    },
+}
+
+/// Render textures the old and new scenes are drawn to for [`TransitionEffect`]s
+struct Captures {
+    transition: ScreenTransition,
+    from: RenderTexture2D,
+    to: RenderTexture2D,
+}
+
+/// Stack of scenes, only the top one is updated and drawn
+pub struct SceneManager {
+    scenes: Vec<Box<dyn Scene>>,
+    pending: Option<Pending>,
+    captures: Option<Captures>,
+    /// Fade used for the changes requested by scenes, `None` to change immediately
+    pub fade: Option<Fade>,
+    presence: Box<dyn PresenceProvider>,
+    shown_presence: Option<Presence>,
+    presence_started: bool,
+}
+
+impl fmt::Debug for SceneManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SceneManager")
+            .field("scenes", &self.scenes.len())
+            .field("transitioning", &self.is_transitioning())
+            .field("fade", &self.fade)
+            .field("presence", &self.shown_presence)
+            .finish()
+    }
+}
+
+impl SceneManager {
+    /// Create scene manager with an initial scene
+    ///
+    /// Changes requested by scenes use the default [`Fade`].
+    #[inline]
+    pub fn new(initial: Box<dyn Scene>) -> Self {
+        Self {
+            scenes: vec![initial],
+            pending: None,
+            captures: None,
+            fade: Some(Fade::default()),
+            presence: Box::new(NoPresence),
+            shown_presence: None,
+            presence_started: false,
+        }
+    }
+
+    /// Show the current scene's [`Scene::presence`] with a provider (nothing is shown by default)
+    #[inline]
+    pub fn with_presence(mut self, provider: Box<dyn PresenceProvider>) -> Self {
+        self.presence = provider;
+        self
+    }
+
+    /// Number of scenes on the stack
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.scenes.len()
+    }
+
+    /// Check if there are no scenes left (and no change is in progress)
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.scenes.is_empty() && self.pending.is_none()
+    }
+
+    /// Check if a transition is in progress
+    #[inline]
+    pub fn is_transitioning(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Put a scene on top of the current one, immediately
+    #[inline]
+    pub fn push(&mut self, scene: Box<dyn Scene>) {
+        self.scenes.push(scene);
+    }
+
+    /// Remove the current scene, immediately
+    #[inline]
+    pub fn pop(&mut self) -> Option<Box<dyn Scene>> {
+        self.scenes.pop()
+    }
+
+    /// Swap the current scene for another one, immediately
+    #[inline]
+    pub fn replace(&mut self, scene: Box<dyn Scene>) -> Option<Box<dyn Scene>> {
+        let old = self.scenes.pop();
+        self.scenes.push(scene);
+
+        old
+    }
+
+    /// Change the scene stack, with a fade or immediately
+    ///
+    /// Starting a fade during another one restarts it, dropping the old change if it wasn't applied yet.
+    pub fn transition(&mut self, change: SceneChange, fade: Option<Fade>) {
+        match fade {
+            Some(fade) if fade.duration > 0. => {
+                self.pending = Some(Pending::Fade {
+                    change: Some(change),
+                    fade,
+                    elapsed: 0.,
+                })
+            }
+            _ => apply(&mut self.scenes, change),
+        }
+    }
+
+    /// Change the scene stack with an effect between the old and new scene
+    ///
+    /// The old scene is captured and the change applied on the next [`SceneManager::draw`], from then on the new
+    /// scene is drawn to a render texture every frame until the effect is done.
+    /// Falls back to an immediate change if the render textures or the shader can't be loaded.
+    pub fn transition_effect(
+        &mut self,
+        change: SceneChange,
+        effect: TransitionEffect,
+        duration: f32,
+    ) {
+        if duration > 0. {
+            self.pending = Some(Pending::Effect {
+                change: Some(change),
+                effect,
+                duration,
+                elapsed: 0.,
+            });
+        } else {
+            apply(&mut self.scenes, change);
+        }
+    }
+
+    /// Update the current scene, or advance the transition in progress
+    ///
+    /// Scenes aren't updated during transitions.
+    pub fn update(&mut self, rl: &mut Raylib, dt: f32) {
+        match &mut self.pending {
+            Some(Pending::Fade {
+                change,
+                fade,
+                elapsed,
+            }) => {
+                *elapsed += dt;
+
+                // the screen is covered halfway through
+                let change = if *elapsed >= fade.duration / 2. {
+                    change.take()
+                } else {
+                    None
+                };
+                let done = *elapsed >= fade.duration;
+
+                if let Some(change) = change {
+                    apply(&mut self.scenes, change);
+                }
+
+                if done {
+                    self.pending = None;
+                }
+
+                return;
+            }
+            Some(Pending::Effect {
+                change,
+                duration,
+                elapsed,
+                ..
+            }) => {
+                // the change is applied when the old scene has been captured
+                if change.is_none() {
+                    *elapsed += dt;
+
+                    if *elapsed >= *duration {
+                        self.pending = None;
+                    }
+                }
+
+                return;
+            }
+            None => {}
+        }
+
+        if let Some(scene) = self.scenes.last_mut() {
+            scene.update(rl, dt);
+
+            if let Some(change) = scene.transition() {
+                self.transition(change, self.fade);
+            }
+        }
+    }
+
+    /// Draw the current scene and the transition in progress
+    pub fn draw(&mut self, d: &mut DrawHandle<'_>) {
+        if let Some(Pending::Effect { .. }) = self.pending {
+            self.draw_effect(d);
+
+            return;
+        }
+
+        if let Some(scene) = self.scenes.last_mut() {
+            scene.draw(d);
+        }
+
+        if let Some(Pending::Fade { fade, elapsed, .. }) = &self.pending {
+            let half = fade.duration / 2.;
+            let alpha = if *elapsed < half {
+                elapsed / half
+            } else {
+                1. - (elapsed - half) / half
+            };
+
+            d.draw_rectangle(screen_rect(d), fade.color.fade(alpha.min(1.).max(0.)));
+        }
+    }
+
+    /// Update the presence provider and show the current scene's presence if it changed
+    ///
+    /// Starts the provider on the first call. Called every frame by the main loops,
+    /// call it after [`SceneManager::update`] when running your own loop.
+    pub fn update_presence(&mut self, dt: f32) {
+        if !self.presence_started {
+            self.presence.init();
+            self.presence_started = true;
+        }
+
+        self.presence.update(dt);
+
+        let current = self.scenes.last().and_then(|scene| scene.presence());
+
+        if current != self.shown_presence {
+            match &current {
+                Some(presence) => self.presence.set_presence(presence),
+                None => self.presence.clear(),
+            }
+
+            self.shown_presence = current;
+        }
+    }
+
+    /// Shut the presence provider down, it starts again on the next [`SceneManager::update_presence`]
+    ///
+    /// Called when the main loops end.
+    pub fn shutdown_presence(&mut self) {
+        if self.presence_started {
+            self.presence.shutdown();
+            self.presence_started = false;
+            self.shown_presence = None;
+        }
+    }
+
+    /// Run the main loop until the window should close or there are no scenes left
+    pub fn run(&mut self, rl: &mut Raylib) {
+        while !rl.window_should_close() && !self.is_empty() {
+            let dt = rl.get_frame_time().as_secs_f32();
+            self.update(rl, dt);
+            self.update_presence(dt);
+
+            let mut d = rl.begin_drawing();
+            self.draw(&mut d);
+            d.end_drawing();
+        }
+
+        self.shutdown_presence();
+    }
+
+    /// Run the main loop like [`SceneManager::run`], with the frame times going through a clock
+    ///
+    /// Scenes are updated once per fixed step of the clock (once per frame without one), with its delta.
+    pub fn run_with_clock(&mut self, rl: &mut Raylib, clock: &mut GameClock) {
+        while !rl.window_should_close() && !self.is_empty() {
+            for _ in 0..clock.tick_frame(rl) {
+                self.update(rl, clock.delta());
+            }
+
+            self.update_presence(rl.get_frame_time().as_secs_f32());
+
+            let mut d = rl.begin_drawing();
+            self.draw(&mut d);
+            d.end_drawing();
+        }
+
+        self.shutdown_presence();
+    }
+
+    fn draw_effect(&mut self, d: &mut DrawHandle<'_>) {
+        let (width, height) = (d.get_screen_width(), d.get_screen_height());
+
+        let resized = match &self.captures {
+            Some(captures) => captures.from.width() != width || captures.from.height() != height,
+            None => true,
+        };
+
+        if resized {
+            self.captures = ScreenTransition::new().ok().and_then(|transition| {
+                Some(Captures {
+                    transition,
+                    from: RenderTexture2D::new(width, height).ok()?,
+                    to: RenderTexture2D::new(width, height).ok()?,
+                })
+            });
+        }
+
+        let (change, effect, duration, elapsed) = match &mut self.pending {
+            Some(Pending::Effect {
+                change,
+                effect,
+                duration,
+                elapsed,
+            }) => (change, effect, *duration, *elapsed),
+            _ => return,
+        };
+
+        let captures = match &mut self.captures {
+            Some(captures) => captures,
+            None => {
+                if let Some(change) = change.take() {
+                    apply(&mut self.scenes, change);
+                }
+
+                self.pending = None;
+
+                if let Some(scene) = self.scenes.last_mut() {
+                    scene.draw(d);
+                }
+
+                return;
+            }
+        };
+
+        if let Some(change) = change.take() {
+            draw_to(&captures.from, self.scenes.last_mut(), d);
+            apply(&mut self.scenes, change);
+        }
+
+        draw_to(&captures.to, self.scenes.last_mut(), d);
+
+        let dest = screen_rect(d);
+        captures.transition.draw(
+            d,
+            effect,
+            &captures.from,
+            &captures.to,
+            elapsed / duration,
+            dest,
+        );
+    }
+}
+
+fn apply(scenes: &mut Vec<Box<dyn Scene>>, change: SceneChange) {
+    match change {
+        SceneChange::Push(scene) => scenes.push(scene),
+        SceneChange::Pop => {
+            scenes.pop();
+        }
+        SceneChange::Replace(scene) => {
+            scenes.pop();
+            scenes.push(scene);
+        }
+        SceneChange::Clear => scenes.clear(),
+    }
+}
+
+/// Draw a scene to a render texture, cleared to black when there's no scene
+fn draw_to(target: &RenderTexture2D, scene: Option<&mut Box<dyn Scene>>, d: &mut DrawHandle<'_>) {
+    d.with_texture_mode(target, |d| match scene {
+        Some(scene) => scene.draw(d),
+        None => d.clear_background(Color::BLACK),
+    });
+}
+
+#[inline]
+fn screen_rect(d: &DrawHandle<'_>) -> Rectangle {
+    Rectangle::new(
+        0.,
+        0.,
+        d.get_screen_width() as f32,
+        d.get_screen_height() as f32,
+    )
+}
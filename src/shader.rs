@@ -1,12 +1,55 @@
 use crate::{
+    core::{is_window_alive, track_context_object, untrack_context_object, ContextObject},
+    drawing::gl,
+    error::RaylibError,
     ffi,
     math::{Matrix, Vector2, Vector3, Vector4},
     texture::Texture2D,
 };
 use std::ffi::CString;
+#[cfg(debug_assertions)]
+use std::sync::Mutex;
 
 pub use crate::ffi::{ShaderAttributeDataType, ShaderLocationIndex, ShaderUniformDataType};
 
+/// Uniform names declared in the source of every loaded shader, by shader id (debug builds only)
+#[cfg(debug_assertions)]
+static SHADER_UNIFORMS: Mutex<Vec<(u32, Vec<String>)>> = Mutex::new(Vec::new());
+
+/// Check if raylib is built for OpenGL 3.3 or 4.3, the GLSL 330 shaders of the safe wrappers need it
+///
+/// Set at build time by the `opengl_*` features, it doesn't need a window.
+#[inline]
+pub(crate) fn supports_glsl_330() -> bool {
+    // rlGlVersion: 3 is OpenGL 3.3, 4 is OpenGL 4.3
+    matches!(unsafe { crate::rlgl::ffi::rlGetVersion() }, 3 | 4)
+}
+
+/// Shader uniform or attribute location
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ShaderLoc(u32);
+
+impl ShaderLoc {
+    /// Location with a known index
+    #[inline]
+    pub const fn new(index: u32) -> Self {
+        Self(index)
+    }
+
+    /// Location index
+    #[inline]
+    pub const fn index(&self) -> u32 {
+        self.0
+    }
+}
+
+impl From<ShaderLoc> for u32 {
+    #[inline]
+    fn from(loc: ShaderLoc) -> Self {
+        loc.0
+    }
+}
+
 /// Shader
 #[derive(Debug)]
 #[repr(transparent)]
@@ -15,7 +58,31 @@ pub struct Shader {
 }
 
 impl Shader {
-    /// Shader locations array
+    /// Conventional uniform names of the raylib lighting examples
+    pub const LIGHTING_LOCATIONS: &'static [(ShaderLocationIndex, &'static str)] = &[
+        (ShaderLocationIndex::MatrixModel, "matModel"),
+        (ShaderLocationIndex::MatrixNormal, "matNormal"),
+        (ShaderLocationIndex::VectorView, "viewPos"),
+    ];
+
+    /// Conventional uniform names of PBR material maps
+    pub const PBR_LOCATIONS: &'static [(ShaderLocationIndex, &'static str)] = &[
+        (ShaderLocationIndex::MapAlbedo, "albedoMap"),
+        (ShaderLocationIndex::MapMetalness, "metalnessMap"),
+        (ShaderLocationIndex::MapNormal, "normalMap"),
+        (ShaderLocationIndex::MapRoughness, "roughnessMap"),
+        (ShaderLocationIndex::MapOcclusion, "occlusionMap"),
+        (ShaderLocationIndex::MapEmission, "emissionMap"),
+        (ShaderLocationIndex::MapHeight, "heightMap"),
+        (ShaderLocationIndex::MapCubemap, "environmentMap"),
+        (ShaderLocationIndex::MapIrradiance, "irradianceMap"),
+        (ShaderLocationIndex::MapPrefilter, "prefilterMap"),
+        (ShaderLocationIndex::MapBrdf, "brdfLUT"),
+    ];
+
+    /// Shader locations array, indexed by [`ShaderLocationIndex`] (unbound locations are `u32::MAX`)
+    ///
+    /// Prefer [`Shader::builtin_location`]
     #[inline]
     pub fn locations(&self) -> &[u32] {
         unsafe {
@@ -23,7 +90,9 @@ impl Shader {
         }
     }
 
-    /// Shader locations array
+    /// Shader locations array, indexed by [`ShaderLocationIndex`] (unbound locations are `u32::MAX`)
+    ///
+    /// Prefer [`Shader::set_builtin_location`]
     #[inline]
     pub fn locations_mut(&mut self) -> &mut [u32] {
         unsafe {
@@ -31,19 +100,73 @@ impl Shader {
         }
     }
 
+    /// Location raylib uses for a builtin uniform or attribute, `None` if it isn't bound
+    #[inline]
+    pub fn builtin_location(&self, index: ShaderLocationIndex) -> Option<ShaderLoc> {
+        let loc = unsafe { self.raw.locs.add(index as usize).read() };
+
+        if loc < 0 {
+            None
+        } else {
+            Some(ShaderLoc(loc as _))
+        }
+    }
+
+    /// Set the location raylib uses for a builtin uniform or attribute (`None` unbinds it)
+    ///
+    /// Models and materials read these when drawing with this shader
+    #[inline]
+    pub fn set_builtin_location(&mut self, index: ShaderLocationIndex, loc: Option<ShaderLoc>) {
+        let loc = match loc {
+            Some(loc) => loc.0 as _,
+            None => -1,
+        };
+
+        unsafe { self.raw.locs.add(index as usize).write(loc) }
+    }
+
+    /// Look up uniforms by name and bind them as builtin locations, returns the number of uniforms found
+    ///
+    /// Missing uniforms leave their builtin location untouched. See [`Shader::LIGHTING_LOCATIONS`] and [`Shader::PBR_LOCATIONS`].
+    #[inline]
+    pub fn bind_builtin_locations(&mut self, names: &[(ShaderLocationIndex, &str)]) -> usize {
+        let mut count = 0;
+
+        for &(index, name) in names {
+            let name = match CString::new(name) {
+                Ok(name) => name,
+                Err(_) => continue,
+            };
+
+            let loc = unsafe { ffi::GetShaderLocation(self.raw.clone(), name.as_ptr()) };
+
+            if loc >= 0 {
+                self.set_builtin_location(index, Some(ShaderLoc(loc as _)));
+                count += 1;
+            }
+        }
+
+        count
+    }
+
     /// Load shader from files and bind default locations
+    ///
+    /// Fails with [`RaylibError::NotReady`] if the shader doesn't compile (the log has the compiler output).
     #[inline]
-    pub fn from_file(vs_filename: Option<&str>, fs_filename: Option<&str>) -> Option<Self> {
-        let vs_filename = vs_filename.map(|s| CString::new(s).unwrap());
-        let fs_filename = fs_filename.map(|s| CString::new(s).unwrap());
+    pub fn from_file(
+        vs_filename: Option<&str>,
+        fs_filename: Option<&str>,
+    ) -> Result<Self, RaylibError> {
+        let vs_filename = vs_filename.map(RaylibError::file_name).transpose()?;
+        let fs_filename = fs_filename.map(RaylibError::file_name).transpose()?;
 
         let raw = unsafe {
             ffi::LoadShader(
-                match vs_filename {
+                match &vs_filename {
                     Some(vs) => vs.as_ptr(),
                     None => std::ptr::null(),
                 },
-                match fs_filename {
+                match &fs_filename {
                     Some(fs) => fs.as_ptr(),
                     None => std::ptr::null(),
                 },
@@ -51,25 +174,42 @@ impl Shader {
         };
 
         if unsafe { ffi::IsShaderReady(raw.clone()) } {
-            Some(Self { raw })
+            #[cfg(debug_assertions)]
+            register_uniforms(
+                raw.id,
+                [&vs_filename, &fs_filename]
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|file_name| std::fs::read_to_string(file_name.to_str().ok()?).ok()),
+            );
+
+            Ok(Self::tracked(raw))
         } else {
-            None
+            Err(RaylibError::NotReady("shader"))
         }
     }
 
     /// Load shader from code strings and bind default locations
+    ///
+    /// Fails with [`RaylibError::NotReady`] if the shader doesn't compile (the log has the compiler output).
     #[inline]
-    pub fn from_memory(vs_code: Option<&str>, fs_code: Option<&str>) -> Option<Self> {
-        let vs_code = vs_code.map(|s| CString::new(s).unwrap());
-        let fs_code = fs_code.map(|s| CString::new(s).unwrap());
+    pub fn from_memory(vs_code: Option<&str>, fs_code: Option<&str>) -> Result<Self, RaylibError> {
+        let vs_code = vs_code
+            .map(CString::new)
+            .transpose()
+            .map_err(|_| RaylibError::InvalidArgument("shader code contains a nul byte".into()))?;
+        let fs_code = fs_code
+            .map(CString::new)
+            .transpose()
+            .map_err(|_| RaylibError::InvalidArgument("shader code contains a nul byte".into()))?;
 
         let raw = unsafe {
             ffi::LoadShaderFromMemory(
-                match vs_code {
+                match &vs_code {
                     Some(vs) => vs.as_ptr(),
                     None => std::ptr::null(),
                 },
-                match fs_code {
+                match &fs_code {
                     Some(fs) => fs.as_ptr(),
                     None => std::ptr::null(),
                 },
@@ -77,35 +217,62 @@ impl Shader {
         };
 
         if unsafe { ffi::IsShaderReady(raw.clone()) } {
-            Some(Self { raw })
+            #[cfg(debug_assertions)]
+            register_uniforms(
+                raw.id,
+                [&vs_code, &fs_code]
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|code| code.to_str().ok().map(str::to_string)),
+            );
+
+            Ok(Self::tracked(raw))
         } else {
-            None
+            Err(RaylibError::NotReady("shader"))
         }
     }
 
-    /// Get shader uniform location
+    /// Get shader uniform location, `None` if the shader has no active uniform with that name
+    ///
+    /// Debug builds print a warning listing the uniforms declared in the shader source.
     #[inline]
-    pub fn get_location(&self, uniform_name: &str) -> u32 {
-        let uniform_name = CString::new(uniform_name).unwrap();
+    pub fn get_location(&self, uniform_name: &str) -> Option<ShaderLoc> {
+        let c_name = CString::new(uniform_name).ok()?;
 
-        unsafe { ffi::GetShaderLocation(self.raw.clone(), uniform_name.as_ptr()) as _ }
+        let loc = unsafe { ffi::GetShaderLocation(self.raw.clone(), c_name.as_ptr()) };
+
+        if loc < 0 {
+            #[cfg(debug_assertions)]
+            warn_missing_uniform(self.raw.id, uniform_name);
+
+            None
+        } else {
+            Some(ShaderLoc(loc as _))
+        }
     }
 
-    /// Get shader attribute location
+    /// Get shader attribute location, `None` if the shader has no active attribute with that name
     #[inline]
-    pub fn get_location_attribute(&self, attribute_name: &str) -> u32 {
-        let attribute_name = CString::new(attribute_name).unwrap();
+    pub fn get_location_attribute(&self, attribute_name: &str) -> Option<ShaderLoc> {
+        let attribute_name = CString::new(attribute_name).ok()?;
 
-        unsafe { ffi::GetShaderLocationAttrib(self.raw.clone(), attribute_name.as_ptr()) as _ }
+        let loc =
+            unsafe { ffi::GetShaderLocationAttrib(self.raw.clone(), attribute_name.as_ptr()) };
+
+        if loc < 0 {
+            None
+        } else {
+            Some(ShaderLoc(loc as _))
+        }
     }
 
     /// Set shader uniform value
     #[inline]
-    pub fn set_value<S: ShaderValue>(&mut self, loc_index: u32, value: S) {
+    pub fn set_value<S: ShaderValue>(&mut self, loc: ShaderLoc, value: S) {
         unsafe {
             ffi::SetShaderValue(
                 self.raw.clone(),
-                loc_index as _,
+                loc.0 as _,
                 value.raw_value(),
                 S::UNIFORM_TYPE as _,
             )
@@ -114,11 +281,11 @@ impl Shader {
 
     /// Set shader uniform value vector
     #[inline]
-    pub fn set_value_vec<S: ShaderValue>(&mut self, loc_index: u32, values: &[S]) {
+    pub fn set_value_vec<S: ShaderValue>(&mut self, loc: ShaderLoc, values: &[S]) {
         unsafe {
             ffi::SetShaderValueV(
                 self.raw.clone(),
-                loc_index as _,
+                loc.0 as _,
                 values.as_ptr() as *const _,
                 S::UNIFORM_TYPE as _,
                 values.len() as _,
@@ -128,14 +295,14 @@ impl Shader {
 
     /// Set shader uniform value (matrix 4x4)
     #[inline]
-    pub fn set_value_matrix(&mut self, loc_index: u32, mat: Matrix) {
-        unsafe { ffi::SetShaderValueMatrix(self.raw.clone(), loc_index as _, mat.into()) }
+    pub fn set_value_matrix(&mut self, loc: ShaderLoc, mat: Matrix) {
+        unsafe { ffi::SetShaderValueMatrix(self.raw.clone(), loc.0 as _, mat.into()) }
     }
 
     /// Set shader uniform value for texture (sampler2d)
     #[inline]
-    pub fn set_value_texture(&mut self, loc_index: u32, texture: &Texture2D) {
-        unsafe { ffi::SetShaderValueTexture(self.raw.clone(), loc_index as _, texture.raw.clone()) }
+    pub fn set_value_texture(&mut self, loc: ShaderLoc, texture: &Texture2D) {
+        unsafe { ffi::SetShaderValueTexture(self.raw.clone(), loc.0 as _, texture.raw.clone()) }
     }
 
     /// Get the 'raw' ffi type
@@ -159,6 +326,17 @@ impl Shader {
     /// * The raw object should be unique. Otherwise, make sure its clones don't outlive the newly created object.
     #[inline]
     pub unsafe fn from_raw(raw: ffi::Shader) -> Self {
+        Self::tracked(raw)
+    }
+
+    /// Wrap a shader of the current context
+    #[inline]
+    fn tracked(raw: ffi::Shader) -> Self {
+        // raylib's default shader is shared, and never unloaded
+        if raw.id != unsafe { gl::rlGetShaderIdDefault() } {
+            track_context_object(ContextObject::Shader(raw.id));
+        }
+
         Self { raw }
     }
 }
@@ -166,7 +344,69 @@ impl Shader {
 impl Drop for Shader {
     #[inline]
     fn drop(&mut self) {
-        unsafe { ffi::UnloadShader(self.raw.clone()) }
+        #[cfg(debug_assertions)]
+        if let Ok(mut uniforms) = SHADER_UNIFORMS.lock() {
+            uniforms.retain(|(id, _)| *id != self.raw.id);
+        }
+
+        match untrack_context_object(ContextObject::Shader(self.raw.id)) {
+            Some(true) => unsafe { ffi::UnloadShader(self.raw.clone()) },
+            // only custom shaders are tracked, they own their locations
+            Some(false) => unsafe { ffi::MemFree(self.raw.locs.cast()) },
+            None => {
+                if is_window_alive() {
+                    unsafe { ffi::UnloadShader(self.raw.clone()) }
+                }
+            }
+        }
+    }
+}
+
+/// Remember the uniforms declared in the shader sources
+#[cfg(debug_assertions)]
+fn register_uniforms(id: u32, sources: impl Iterator<Item = String>) {
+    let mut names = Vec::new();
+
+    for source in sources {
+        for statement in source.split(';') {
+            let mut tokens = statement.split_whitespace().skip_while(|&t| t != "uniform");
+
+            if tokens.next().is_none() {
+                continue;
+            }
+
+            // skip precision qualifiers and the type
+            let declarators = tokens
+                .skip_while(|&t| matches!(t, "lowp" | "mediump" | "highp"))
+                .skip(1)
+                .collect::<String>();
+
+            for name in declarators.split(',') {
+                let name = name.split('[').next().unwrap_or_default().trim();
+
+                if !name.is_empty() && !names.iter().any(|n| n == name) {
+                    names.push(name.to_string());
+                }
+            }
+        }
+    }
+
+    if let Ok(mut uniforms) = SHADER_UNIFORMS.lock() {
+        uniforms.retain(|(other, _)| *other != id);
+        uniforms.push((id, names));
+    }
+}
+
+#[cfg(debug_assertions)]
+fn warn_missing_uniform(id: u32, name: &str) {
+    if let Ok(uniforms) = SHADER_UNIFORMS.lock() {
+        match uniforms.iter().find(|(other, _)| *other == id) {
+            Some((_, names)) => crate::core::warn(&format!(
+                "SHADER: [ID {id}] Uniform `{name}` not found, available uniforms: {}",
+                names.join(", ")
+            )),
+            None => crate::core::warn(&format!("SHADER: [ID {id}] Uniform `{name}` not found")),
+        }
     }
 }
 
@@ -0,0 +1,266 @@
+use crate::{
+    color::Color,
+    drawing::Draw,
+    math::{Rectangle, Vector2},
+    texture::Texture2D,
+    tilemap::Tileset,
+};
+use std::ops::Range;
+
+/// How an animation continues after its last frame
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AnimationMode {
+    /// Start over from the first frame
+    Loop,
+    /// Play backwards to the first frame, then forwards again
+    PingPong,
+    /// Stop on the last frame
+    Once,
+}
+
+/// Sprite sheet animation, a list of frames played at a fixed rate
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpriteAnimation {
+    /// Tile indices of the frames in a [`Tileset`]
+    pub frames: Vec<u32>,
+    /// Frames per second
+    pub fps: f32,
+    /// What happens after the last frame
+    pub mode: AnimationMode,
+}
+
+impl SpriteAnimation {
+    /// Create looping animation
+    #[inline]
+    pub fn new(frames: Vec<u32>, fps: f32) -> Self {
+        Self {
+            frames,
+            fps,
+            mode: AnimationMode::Loop,
+        }
+    }
+
+    /// Create looping animation of consecutive tiles, e.g. a row of the sprite sheet
+    #[inline]
+    pub fn from_range(frames: Range<u32>, fps: f32) -> Self {
+        Self::new(frames.collect(), fps)
+    }
+
+    /// Set what happens after the last frame
+    #[inline]
+    pub fn with_mode(mut self, mode: AnimationMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Time of one pass, there and back for [`AnimationMode::PingPong`]
+    #[inline]
+    pub fn duration(&self) -> f32 {
+        if self.fps > 0. {
+            self.step_count() as f32 / self.fps
+        } else {
+            0.
+        }
+    }
+
+    /// Frame steps until the animation repeats or ends
+    #[inline]
+    fn step_count(&self) -> usize {
+        match self.mode {
+            AnimationMode::PingPong if self.frames.len() > 1 => 2 * self.frames.len() - 2,
+            _ => self.frames.len(),
+        }
+    }
+
+    /// Index into `frames` at a frame step
+    #[inline]
+    fn frame_index(&self, step: usize) -> usize {
+        let len = self.frames.len();
+
+        match self.mode {
+            AnimationMode::Loop => step % len.max(1),
+            AnimationMode::Once => step.min(len.saturating_sub(1)),
+            AnimationMode::PingPong => {
+                let steps = self.step_count().max(1);
+                let step = step % steps;
+
+                if step < len {
+                    step
+                } else {
+                    steps - step
+                }
+            }
+        }
+    }
+}
+
+/// What happened during [`AnimationPlayer::update`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AnimationEvent {
+    /// A looping or ping-pong animation started over
+    Looped,
+    /// A one-shot animation reached its end, reported once
+    Finished,
+}
+
+/// Plays a [`SpriteAnimation`], advanced by the frame time
+#[derive(Clone, Debug, PartialEq)]
+pub struct AnimationPlayer {
+    animation: SpriteAnimation,
+    time: f32,
+    /// Playback speed multiplier
+    /// Default: 1.0
+    pub speed: f32,
+    paused: bool,
+    finished: bool,
+}
+
+impl AnimationPlayer {
+    /// Start playing an animation
+    #[inline]
+    pub fn new(animation: SpriteAnimation) -> Self {
+        Self {
+            animation,
+            time: 0.,
+            speed: 1.,
+            paused: false,
+            finished: false,
+        }
+    }
+
+    /// The animation being played
+    #[inline]
+    pub fn animation(&self) -> &SpriteAnimation {
+        &self.animation
+    }
+
+    /// Switch to another animation from its start, keeps playing the current one if it's the same
+    ///
+    /// Can be called every frame, e.g. with the walk or idle animation depending on the movement.
+    #[inline]
+    pub fn play(&mut self, animation: &SpriteAnimation) {
+        if self.animation != *animation {
+            self.animation = animation.clone();
+            self.restart();
+        }
+    }
+
+    /// Play the animation from the start
+    #[inline]
+    pub fn restart(&mut self) {
+        self.time = 0.;
+        self.paused = false;
+        self.finished = false;
+    }
+
+    /// Stop advancing the animation
+    #[inline]
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Continue after [`AnimationPlayer::pause`]
+    #[inline]
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    /// Check if the animation is paused
+    #[inline]
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Check if a one-shot animation reached its end
+    #[inline]
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Time into the current pass of the animation
+    #[inline]
+    pub fn time(&self) -> f32 {
+        self.time
+    }
+
+    /// Advance the animation by `delta` seconds
+    ///
+    /// Returns the event that happened, at most one per call.
+    pub fn update(&mut self, delta: f32) -> Option<AnimationEvent> {
+        if self.paused || self.finished {
+            return None;
+        }
+
+        let duration = self.animation.duration();
+
+        if duration <= 0. {
+            return None;
+        }
+
+        self.time += delta * self.speed;
+
+        if self.time < duration {
+            return None;
+        }
+
+        match self.animation.mode {
+            AnimationMode::Once => {
+                // stay on the last frame
+                self.time = duration;
+                self.finished = true;
+
+                Some(AnimationEvent::Finished)
+            }
+            AnimationMode::Loop | AnimationMode::PingPong => {
+                self.time %= duration;
+
+                Some(AnimationEvent::Looped)
+            }
+        }
+    }
+
+    /// Index of the current frame in the animation's frames
+    #[inline]
+    pub fn frame_index(&self) -> usize {
+        let step = (self.time * self.animation.fps.max(0.)) as usize;
+
+        self.animation.frame_index(step)
+    }
+
+    /// Tile index of the current frame, `None` for an animation without frames
+    #[inline]
+    pub fn current_frame(&self) -> Option<u32> {
+        self.animation.frames.get(self.frame_index()).copied()
+    }
+
+    /// Area of the current frame in the sprite sheet
+    #[inline]
+    pub fn source_rect(&self, tileset: &Tileset) -> Option<Rectangle> {
+        self.current_frame().map(|frame| tileset.source_rect(frame))
+    }
+
+    /// Draw the current frame with its top left corner at `position`, mirrored if `flip_x` is set
+    pub fn draw<D: Draw + ?Sized>(
+        &self,
+        d: &mut D,
+        texture: &Texture2D,
+        tileset: &Tileset,
+        position: impl Into<Vector2>,
+        flip_x: bool,
+        tint: Color,
+    ) {
+        let position: Vector2 = position.into();
+
+        if let Some(mut source) = self.source_rect(tileset) {
+            let dest = Rectangle::new(position.x, position.y, source.width, source.height);
+
+            if flip_x {
+                source.width = -source.width;
+            }
+
+            d.draw_texture_pro(texture, source, dest, Vector2 { x: 0., y: 0. }, 0., tint);
+        }
+    }
+}
@@ -0,0 +1,505 @@
+use crate::{color::Color, core::is_window_alive, drawing::Draw, ffi, math::Rectangle};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Mutex,
+};
+
+/// rlgl render batch types and functions (not part of the generated bindings)
+#[allow(dead_code, non_camel_case_types, non_snake_case)]
+mod rl {
+    use core::ffi::{c_float, c_int, c_uchar, c_uint};
+
+    /// Draw calls a batch holds before it's flushed (`RL_DEFAULT_BATCH_DRAWCALLS`)
+    pub const BATCH_DRAWCALLS: usize = 256;
+    /// Quads per vertex buffer (`RL_DEFAULT_BATCH_BUFFER_ELEMENTS`, smaller on OpenGL ES 2)
+    pub const BATCH_BUFFER_ELEMENTS: c_int = if cfg!(feature = "opengl_es2") {
+        2048
+    } else {
+        8192
+    };
+    /// Vertex buffers of the counted batch, every flush moves on to the next one
+    pub const BATCH_BUFFERS: c_int = 2;
+
+    #[repr(C)]
+    pub struct rlVertexBuffer {
+        pub elementCount: c_int,
+        pub vertices: *mut c_float,
+        pub texcoords: *mut c_float,
+        pub colors: *mut c_uchar,
+        pub indices: *mut c_uint,
+        pub vaoId: c_uint,
+        pub vboId: [c_uint; 4],
+    }
+
+    #[repr(C)]
+    pub struct rlDrawCall {
+        pub mode: c_int,
+        pub vertexCount: c_int,
+        pub vertexAlignment: c_int,
+        pub textureId: c_uint,
+    }
+
+    #[repr(C)]
+    pub struct rlRenderBatch {
+        pub bufferCount: c_int,
+        pub currentBuffer: c_int,
+        pub vertexBuffer: *mut rlVertexBuffer,
+        pub draws: *mut rlDrawCall,
+        pub drawCounter: c_int,
+        pub currentDepth: c_float,
+    }
+
+    extern "C" {
+        pub fn rlLoadRenderBatch(numBuffers: c_int, bufferElements: c_int) -> rlRenderBatch;
+        pub fn rlUnloadRenderBatch(batch: rlRenderBatch);
+        /// Flushes the current batch, a null pointer goes back to the default batch
+        pub fn rlSetRenderBatchActive(batch: *mut rlRenderBatch);
+    }
+}
+
+/// Render batch statistics of one frame
+///
+/// raylib collects 2D shapes, text and textures into a batch of vertices, split into a draw call
+/// per texture (and primitive) change. The batch is flushed to the GPU when a mode begins or ends
+/// (camera, texture, shader, blend, scissor...) and at the end of the frame.
+///
+/// Counting replaces raylib's default batch with one owned by this module, from the first
+/// [`RenderStats::collect`] on. It has two vertex buffers instead of one, flushes switch between
+/// them, which is how they're told apart. Flushes raylib does by itself when the batch is full (8192
+/// quads, 2048 on OpenGL ES 2, or 256 draw calls) are only partly seen, so busy frames undercount a bit.
+/// Meshes and models bypass the batch and aren't counted.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RenderStats {
+    /// Draw calls issued for batched geometry
+    pub draw_calls: u32,
+    /// Vertices drawn through the batch
+    pub vertices: u32,
+    /// Batch flushes that had something to draw
+    pub flushes: u32,
+    /// Draw calls started because the texture changed within a batch
+    pub texture_switches: u32,
+}
+
+impl RenderStats {
+    /// Stats of the last finished frame
+    ///
+    /// The first call starts counting and returns zeros, so does calling without a window.
+    pub fn collect() -> RenderStats {
+        if !is_window_alive() {
+            return RenderStats::default();
+        }
+
+        let mut state = STATE.lock().unwrap_or_else(|e| e.into_inner());
+
+        if state.batch.is_none() {
+            let mut batch = Box::new(unsafe {
+                rl::rlLoadRenderBatch(rl::BATCH_BUFFERS, rl::BATCH_BUFFER_ELEMENTS)
+            });
+
+            unsafe { rl::rlSetRenderBatchActive(&mut *batch) }
+
+            state.buffer = batch.currentBuffer;
+            state.batch = Some(Batch(batch));
+            COUNTING.store(true, Ordering::Relaxed);
+        }
+
+        state.last
+    }
+}
+
+/// Ring buffer graph of a value per frame: frame times, draw calls or any other metric
+///
+/// Drawn with rectangles only, without text, so the whole graph goes into one draw call of the batch.
+/// Bars at [`FrameGraph::spike_factor`] times the average or more are highlighted,
+/// lines mark the average, the 1% lows and the target.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FrameGraph {
+    samples: Vec<f32>,
+    capacity: usize,
+    next: usize,
+    /// Value at the top of the graph, `None` scales it to the largest sample
+    pub max_value: Option<f32>,
+    /// Value to mark with a line, e.g. the frame time budget
+    pub target: Option<f32>,
+    /// Samples at least this many times the average are spikes
+    /// Default: 2.0
+    pub spike_factor: f32,
+    /// Bar color
+    pub color: Color,
+    /// Bar color of spikes
+    pub spike_color: Color,
+    /// Average line color
+    pub average_color: Color,
+    /// 1% lows line color
+    pub low_color: Color,
+    /// Target line color
+    pub target_color: Color,
+    /// Background color
+    pub background: Color,
+}
+
+impl FrameGraph {
+    /// Create empty graph of the last `capacity` samples
+    #[inline]
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+
+        Self {
+            samples: Vec::with_capacity(capacity),
+            capacity,
+            next: 0,
+            max_value: None,
+            target: None,
+            spike_factor: 2.,
+            color: Color::LIME,
+            spike_color: Color::RED,
+            average_color: Color::SKYBLUE,
+            low_color: Color::ORANGE,
+            target_color: Color::RAYWHITE,
+            background: Color::new(0, 0, 0, 160),
+        }
+    }
+
+    /// Set value at the top of the graph
+    #[inline]
+    pub fn with_max_value(mut self, max_value: f32) -> Self {
+        self.max_value = Some(max_value);
+        self
+    }
+
+    /// Set value to mark with a line
+    #[inline]
+    pub fn with_target(mut self, target: f32) -> Self {
+        self.target = Some(target);
+        self
+    }
+
+    /// Number of samples kept
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of samples recorded so far, up to the capacity
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Check if nothing was recorded yet
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Forget all samples
+    #[inline]
+    pub fn clear(&mut self) {
+        self.samples.clear();
+        self.next = 0;
+    }
+
+    /// Add a sample, replacing the oldest one when the graph is full
+    #[inline]
+    pub fn push(&mut self, value: f32) {
+        if self.samples.len() < self.capacity {
+            self.samples.push(value);
+        } else {
+            self.samples[self.next] = value;
+            self.next = (self.next + 1) % self.capacity;
+        }
+    }
+
+    /// Add the last frame time in seconds
+    #[inline]
+    pub fn push_frame_time(&mut self) {
+        self.push(unsafe { ffi::GetFrameTime() });
+    }
+
+    /// Samples, oldest first
+    #[inline]
+    pub fn values(&self) -> impl Iterator<Item = f32> + '_ {
+        self.samples[self.next..]
+            .iter()
+            .chain(self.samples[..self.next].iter())
+            .copied()
+    }
+
+    /// Most recent sample
+    #[inline]
+    pub fn latest(&self) -> Option<f32> {
+        match self.next {
+            0 => self.samples.last().copied(),
+            next => Some(self.samples[next - 1]),
+        }
+    }
+
+    /// Average of the samples, 0 without samples
+    #[inline]
+    pub fn average(&self) -> f32 {
+        if self.samples.is_empty() {
+            0.
+        } else {
+            self.samples.iter().sum::<f32>() / self.samples.len() as f32
+        }
+    }
+
+    /// Largest sample, 0 without samples
+    #[inline]
+    pub fn max(&self) -> f32 {
+        self.samples.iter().copied().fold(0., f32::max)
+    }
+
+    /// Average of the largest 1% of the samples, the 1% lows of frame times
+    pub fn one_percent_low(&self) -> f32 {
+        if self.samples.is_empty() {
+            return 0.;
+        }
+
+        let count = self.samples.len().div_ceil(100);
+        let mut sorted = self.samples.clone();
+
+        sorted.sort_unstable_by(|a, b| b.total_cmp(a));
+
+        sorted[..count].iter().sum::<f32>() / count as f32
+    }
+
+    /// Draw the graph into `rect`, newest sample on the right
+    pub fn draw<D: Draw + ?Sized>(&self, d: &mut D, rect: Rectangle) {
+        d.draw_rectangle(rect, self.background);
+
+        let top = self.max_value.unwrap_or_else(|| self.max());
+
+        if top <= 0. {
+            return;
+        }
+
+        let bar_width = rect.width / self.capacity as f32;
+        let bottom = rect.y + rect.height;
+        let height = |value: f32| (value / top).clamp(0., 1.) * rect.height;
+
+        let average = self.average();
+        let spike = average * self.spike_factor;
+        // bars are aligned to the right, so the graph scrolls in from there until it's full
+        let start = rect.x + rect.width - bar_width * self.samples.len() as f32;
+
+        for (i, value) in self.values().enumerate() {
+            let color = if average > 0. && value >= spike {
+                self.spike_color
+            } else {
+                self.color
+            };
+            let bar_height = height(value);
+
+            d.draw_rectangle(
+                Rectangle::new(
+                    start + i as f32 * bar_width,
+                    bottom - bar_height,
+                    bar_width,
+                    bar_height,
+                ),
+                color,
+            );
+        }
+
+        // lines as thin rectangles, they would start another draw call otherwise
+        let lines = [
+            (Some(average), self.average_color),
+            (Some(self.one_percent_low()), self.low_color),
+            (self.target, self.target_color),
+        ];
+
+        for (value, color) in lines {
+            if let Some(value) = value {
+                let y = (bottom - height(value)).min(bottom - 1.);
+
+                d.draw_rectangle(Rectangle::new(rect.x, y, rect.width, 1.), color);
+            }
+        }
+    }
+}
+
+/// Frame time and draw call graphs for a debug overlay
+#[derive(Clone, Debug, PartialEq)]
+pub struct PerfOverlay {
+    /// Frame times in seconds, targeting 60 FPS
+    pub frame_times: FrameGraph,
+    /// Draw calls of the batch per frame, see [`RenderStats`]
+    pub draw_calls: FrameGraph,
+}
+
+impl PerfOverlay {
+    /// Create overlay of the last `frames` frames
+    #[inline]
+    pub fn new(frames: usize) -> Self {
+        Self {
+            frame_times: FrameGraph::new(frames).with_target(1. / 60.),
+            draw_calls: FrameGraph::new(frames),
+        }
+    }
+
+    /// Record the last frame, call once per frame
+    ///
+    /// Starts counting draw calls (see [`RenderStats::collect`]).
+    #[inline]
+    pub fn update(&mut self) {
+        self.frame_times.push_frame_time();
+        self.draw_calls
+            .push(RenderStats::collect().draw_calls as f32);
+    }
+
+    /// Draw both graphs into `rect`, frame times on top
+    pub fn draw<D: Draw + ?Sized>(&self, d: &mut D, rect: Rectangle) {
+        let split = (rect.height * 2. / 3.).floor();
+
+        self.frame_times
+            .draw(d, Rectangle::new(rect.x, rect.y, rect.width, split - 1.));
+        self.draw_calls.draw(
+            d,
+            Rectangle::new(rect.x, rect.y + split, rect.width, rect.height - split),
+        );
+    }
+}
+
+struct Batch(Box<rl::rlRenderBatch>);
+
+// the batch is only touched on the thread owning the window
+unsafe impl Send for Batch {}
+
+struct State {
+    batch: Option<Batch>,
+    current: RenderStats,
+    last: RenderStats,
+    /// Draws already counted, and the vertices counted of the last one (it can still grow)
+    seen: (usize, i32),
+    /// Counted vertices are waiting for a flush
+    pending: bool,
+    /// Vertex buffer the batch was on when last counted
+    buffer: i32,
+}
+
+const NO_STATS: RenderStats = RenderStats {
+    draw_calls: 0,
+    vertices: 0,
+    flushes: 0,
+    texture_switches: 0,
+};
+
+/// Whether a batch is counted, so drawing without stats doesn't lock [`STATE`]
+static COUNTING: AtomicBool = AtomicBool::new(false);
+
+static STATE: Mutex<State> = Mutex::new(State {
+    batch: None,
+    current: NO_STATS,
+    last: NO_STATS,
+    seen: (0, 0),
+    pending: false,
+    buffer: 0,
+});
+
+/// Count what was added to the batch since the last call, call right before anything that
+/// might flush it
+///
+/// Calling it more often than the batch is flushed doesn't count anything twice.
+pub(crate) fn record_batch() {
+    if !COUNTING.load(Ordering::Relaxed) {
+        return;
+    }
+
+    let mut state = STATE.lock().unwrap_or_else(|e| e.into_inner());
+    let state = &mut *state;
+
+    let batch = match &state.batch {
+        Some(batch) => &batch.0,
+        None => return,
+    };
+
+    let draws = unsafe { std::slice::from_raw_parts(batch.draws, rl::BATCH_DRAWCALLS) };
+    let count = (batch.drawCounter.max(0) as usize).min(rl::BATCH_DRAWCALLS);
+
+    if batch.currentBuffer != state.buffer {
+        if state.pending {
+            state.current.flushes += 1;
+        }
+
+        state.seen = (0, 0);
+        state.pending = false;
+    }
+
+    let (seen_draws, seen_vertices) = state.seen;
+    let first = seen_draws.saturating_sub(1);
+
+    for (index, draw) in draws.iter().enumerate().take(count).skip(first) {
+        let counted = if index + 1 == seen_draws {
+            seen_vertices
+        } else {
+            0
+        };
+
+        if draw.vertexCount <= counted {
+            continue;
+        }
+
+        if counted == 0 {
+            state.current.draw_calls += 1;
+
+            let previous = draws[..index]
+                .iter()
+                .rev()
+                .find(|draw| draw.vertexCount > 0);
+
+            if previous.is_some_and(|previous| previous.textureId != draw.textureId) {
+                state.current.texture_switches += 1;
+            }
+        }
+
+        state.current.vertices += (draw.vertexCount - counted) as u32;
+        state.pending = true;
+    }
+
+    state.seen = match count {
+        0 => (0, 0),
+        count => (count, draws[count - 1].vertexCount),
+    };
+    state.buffer = batch.currentBuffer;
+}
+
+/// Count the last flush of a frame and start the next one, call right before `EndDrawing`
+pub(crate) fn end_frame() {
+    if !COUNTING.load(Ordering::Relaxed) {
+        return;
+    }
+
+    record_batch();
+
+    let mut state = STATE.lock().unwrap_or_else(|e| e.into_inner());
+
+    if state.pending {
+        state.current.flushes += 1;
+        state.pending = false;
+    }
+
+    state.last = state.current;
+    state.current = NO_STATS;
+}
+
+/// Go back to the default batch, call while the OpenGL context is still alive
+pub(crate) fn release() {
+    COUNTING.store(false, Ordering::Relaxed);
+
+    let mut state = STATE.lock().unwrap_or_else(|e| e.into_inner());
+
+    if let Some(Batch(batch)) = state.batch.take() {
+        unsafe {
+            rl::rlSetRenderBatchActive(std::ptr::null_mut());
+            rl::rlUnloadRenderBatch(*batch);
+        }
+    }
+
+    state.current = NO_STATS;
+    state.last = NO_STATS;
+    state.seen = (0, 0);
+    state.pending = false;
+    state.buffer = 0;
+}
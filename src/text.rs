@@ -1,13 +1,44 @@
 use crate::{
     color::Color,
+    core::{release_context_object, track_context_object, ContextObject, Raylib},
+    drawing::Draw,
+    error::RaylibError,
     ffi,
     math::{Rectangle, Vector2},
-    texture::Image,
+    shader::{Shader, ShaderLoc},
+    texture::{Image, PixelFormat, Texture, TextureFilter},
 };
-use std::ffi::CString;
+use std::{
+    collections::HashMap,
+    ffi::{CStr, CString},
+    marker::PhantomData,
+    mem::ManuallyDrop,
+    ops::Deref,
+};
+
+use static_assertions::{assert_eq_align, assert_eq_size};
 
 pub use crate::ffi::FontType;
 
+// `char` slices are passed to raylib as codepoint (C int) arrays
+assert_eq_size!(char, core::ffi::c_int);
+assert_eq_align!(char, core::ffi::c_int);
+
+/// Convert text to a C string, dropping interior NUL bytes instead of panicking
+#[inline]
+pub(crate) fn text_to_cstring(text: &str) -> CString {
+    match CString::new(text) {
+        Ok(text) => text,
+        Err(err) => {
+            let mut bytes = err.into_vec();
+            bytes.retain(|&b| b != 0);
+
+            // no NUL bytes left
+            CString::new(bytes).unwrap()
+        }
+    }
+}
+
 /// Font, font texture and GlyphInfo array data
 #[derive(Debug)]
 #[repr(transparent)]
@@ -34,28 +65,43 @@ impl Font {
         self.raw.glyphPadding as _
     }
 
+    /// Get the default font, owned by raylib
+    #[inline]
+    pub fn get_default(_raylib: &Raylib) -> DefaultFont<'_> {
+        DefaultFont {
+            font: ManuallyDrop::new(Self {
+                raw: unsafe { ffi::GetFontDefault() },
+            }),
+            _marker: PhantomData,
+        }
+    }
+
     /// Load font from file into GPU memory (VRAM)
     #[inline]
-    pub fn from_file(file_name: &str) -> Option<Self> {
-        let file_name = CString::new(file_name).unwrap();
+    pub fn from_file(file_name: &str) -> Result<Self, RaylibError> {
+        let c_file_name = RaylibError::file_name(file_name)?;
 
-        let raw = unsafe { ffi::LoadFont(file_name.as_ptr()) };
+        let raw = unsafe { ffi::LoadFont(c_file_name.as_ptr()) };
 
         if unsafe { ffi::IsFontReady(raw.clone()) } {
-            Some(Self { raw })
+            Ok(Self::tracked(raw))
         } else {
-            None
+            Err(RaylibError::decode_file(file_name))
         }
     }
 
     /// Load font from file with extended parameters
     #[inline]
-    pub fn from_file_ex(file_name: &str, font_size: u32, chars: &[char]) -> Option<Self> {
-        let file_name = CString::new(file_name).unwrap();
+    pub fn from_file_ex(
+        file_name: &str,
+        font_size: u32,
+        chars: &[char],
+    ) -> Result<Self, RaylibError> {
+        let c_file_name = RaylibError::file_name(file_name)?;
 
         let raw = unsafe {
             ffi::LoadFontEx(
-                file_name.as_ptr(),
+                c_file_name.as_ptr(),
                 font_size as _,
                 chars.as_ptr() as *mut _,
                 chars.len() as _,
@@ -63,22 +109,26 @@ impl Font {
         };
 
         if unsafe { ffi::IsFontReady(raw.clone()) } {
-            Some(Self { raw })
+            Ok(Self::tracked(raw))
         } else {
-            None
+            Err(RaylibError::decode_file(file_name))
         }
     }
 
     /// Load font from Image (XNA style)
     #[inline]
-    pub fn from_image(image: &Image, key_color: Color, first_char: char) -> Option<Self> {
+    pub fn from_image(
+        image: &Image,
+        key_color: Color,
+        first_char: char,
+    ) -> Result<Self, RaylibError> {
         let raw =
             unsafe { ffi::LoadFontFromImage(image.raw.clone(), key_color.into(), first_char as _) };
 
         if unsafe { ffi::IsFontReady(raw.clone()) } {
-            Some(Self { raw })
+            Ok(Self::tracked(raw))
         } else {
-            None
+            Err(RaylibError::DecodeFailed("font image".into()))
         }
     }
 
@@ -89,12 +139,14 @@ impl Font {
         file_data: &[u8],
         font_size: u32,
         chars: &[char],
-    ) -> Option<Self> {
-        let file_type = CString::new(file_type).unwrap();
+    ) -> Result<Self, RaylibError> {
+        let c_file_type = CString::new(file_type).map_err(|_| {
+            RaylibError::InvalidArgument(format!("file type {file_type:?} contains a nul byte"))
+        })?;
 
         let raw = unsafe {
             ffi::LoadFontFromMemory(
-                file_type.as_ptr(),
+                c_file_type.as_ptr(),
                 file_data.as_ptr(),
                 file_data.len() as _,
                 font_size as _,
@@ -104,16 +156,19 @@ impl Font {
         };
 
         if unsafe { ffi::IsFontReady(raw.clone()) } {
-            Some(Self { raw })
+            Ok(Self::tracked(raw))
         } else {
-            None
+            Err(RaylibError::DecodeFailed(format!("{file_type} font data")))
         }
     }
 
     /// Export font as code file, returns true on success
     #[inline]
     pub fn export_as_code(&self, file_name: &str) -> bool {
-        let file_name = CString::new(file_name).unwrap();
+        let file_name = match CString::new(file_name) {
+            Ok(file_name) => file_name,
+            Err(_) => return false,
+        };
 
         unsafe { ffi::ExportFontAsCode(self.raw.clone(), file_name.as_ptr()) }
     }
@@ -121,16 +176,28 @@ impl Font {
     /// Measure string width for default font
     #[inline]
     pub fn measure_text(text: &str, font_size: u32) -> u32 {
-        let text = CString::new(text).unwrap();
+        let text = text_to_cstring(text);
 
         unsafe { ffi::MeasureText(text.as_ptr(), font_size as _) as _ }
     }
 
+    /// Measure string width for default font
+    #[inline]
+    pub fn measure_text_cstr(text: &CStr, font_size: u32) -> u32 {
+        unsafe { ffi::MeasureText(text.as_ptr(), font_size as _) as _ }
+    }
+
     /// Measure string size for Font
     #[inline]
     pub fn measure_text_ex(&self, text: &str, font_size: f32, spacing: f32) -> Vector2 {
-        let text = CString::new(text).unwrap();
+        let text = text_to_cstring(text);
+
+        self.measure_text_ex_cstr(&text, font_size, spacing)
+    }
 
+    /// Measure string size for Font
+    #[inline]
+    pub fn measure_text_ex_cstr(&self, text: &CStr, font_size: f32, spacing: f32) -> Vector2 {
         unsafe { ffi::MeasureTextEx(self.raw.clone(), text.as_ptr(), font_size, spacing).into() }
     }
 
@@ -183,24 +250,719 @@ impl Font {
     /// * The raw object should be unique. Otherwise, make sure its clones don't outlive the newly created object.
     #[inline]
     pub unsafe fn from_raw(raw: ffi::Font) -> Self {
+        Self::tracked(raw)
+    }
+
+    /// Wrap a font of the current context
+    #[inline]
+    fn tracked(raw: ffi::Font) -> Self {
+        track_context_object(ContextObject::Texture(raw.texture.id));
+
         Self { raw }
     }
 }
 
-impl Default for Font {
-    /// Get the default Font
+/// Text converted for drawing once, together with its measured size
+///
+/// Drawing it doesn't allocate, unlike [`Draw::draw_text`](crate::drawing::Draw::draw_text).
+#[derive(Clone, Debug, PartialEq)]
+pub struct StaticText {
+    text: CString,
+    font_size: f32,
+    spacing: f32,
+    size: Vector2,
+}
+
+impl StaticText {
+    /// Prepare text for drawing with the default font
     #[inline]
-    fn default() -> Self {
+    pub fn new(text: &str, font_size: u32) -> Self {
+        // same spacing as `DrawText` uses
+        let spacing = font_size.max(10) / 10;
+
+        let text = text_to_cstring(text);
+        let size = unsafe {
+            ffi::MeasureTextEx(
+                ffi::GetFontDefault(),
+                text.as_ptr(),
+                font_size as _,
+                spacing as _,
+            )
+        };
+
         Self {
-            raw: unsafe { ffi::GetFontDefault() },
+            text,
+            font_size: font_size as _,
+            spacing: spacing as _,
+            size: size.into(),
         }
     }
+
+    /// Prepare text for drawing with a font
+    #[inline]
+    pub fn with_font(text: &str, font: &Font, font_size: f32, spacing: f32) -> Self {
+        let text = text_to_cstring(text);
+        let size = font.measure_text_ex_cstr(&text, font_size, spacing);
+
+        Self {
+            text,
+            font_size,
+            spacing,
+            size,
+        }
+    }
+
+    /// The text
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        // created from a `&str`
+        self.text.to_str().unwrap()
+    }
+
+    /// The converted text
+    #[inline]
+    pub fn as_cstr(&self) -> &CStr {
+        &self.text
+    }
+
+    /// Font size
+    #[inline]
+    pub fn font_size(&self) -> f32 {
+        self.font_size
+    }
+
+    /// Spacing between characters
+    #[inline]
+    pub fn spacing(&self) -> f32 {
+        self.spacing
+    }
+
+    /// Size of the text, measured with the font it was created for
+    #[inline]
+    pub fn size(&self) -> Vector2 {
+        self.size
+    }
+}
+
+/// raylib's default font, borrowed for as long as the window is open
+///
+/// Dereferences to [`Font`] but is never unloaded.
+#[derive(Debug)]
+pub struct DefaultFont<'a> {
+    font: ManuallyDrop<Font>,
+    _marker: PhantomData<&'a Raylib>,
+}
+
+impl<'a> Deref for DefaultFont<'a> {
+    type Target = Font;
+
+    #[inline]
+    fn deref(&self) -> &Font {
+        &self.font
+    }
+}
+
+impl<'a> AsRef<Font> for DefaultFont<'a> {
+    #[inline]
+    fn as_ref(&self) -> &Font {
+        &self.font
+    }
 }
 
 impl Drop for Font {
     #[inline]
     fn drop(&mut self) {
-        unsafe { ffi::UnloadFont(self.raw.clone()) }
+        if release_context_object(ContextObject::Texture(self.raw.texture.id)) {
+            unsafe { ffi::UnloadFont(self.raw.clone()) }
+        } else {
+            unsafe {
+                ffi::UnloadFontData(self.raw.glyphs, self.raw.glyphCount);
+                ffi::MemFree(self.raw.recs.cast());
+            }
+        }
+    }
+}
+
+/// A font that rasterizes glyphs on demand and packs them into a growable atlas
+///
+/// Useful for user-generated text, where the set of characters isn't known up front.
+/// Missing characters are queued with [`DynamicFont::request`] and rasterized by [`DynamicFont::update`],
+/// which takes a per-call glyph budget so expansion can be spread over several frames.
+#[derive(Debug)]
+pub struct DynamicFont {
+    file_data: Vec<u8>,
+    font_size: u32,
+    padding: u32,
+    atlas: Image,
+    texture: Texture,
+    recs: Vec<ffi::Rectangle>,
+    glyphs: Vec<ffi::GlyphInfo>,
+    indices: HashMap<char, usize>,
+    pending: Vec<char>,
+    cursor_x: u32,
+    cursor_y: u32,
+    row_height: u32,
+    font: ManuallyDrop<Font>,
+}
+
+impl DynamicFont {
+    const INITIAL_ATLAS_SIZE: u32 = 256;
+
+    /// Load font file data for dynamic rasterization (TTF/OTF)
+    #[inline]
+    pub fn from_file(file_name: &str, font_size: u32) -> Result<Self, RaylibError> {
+        let file_data = RaylibError::read_file(file_name)?;
+
+        Self::from_memory(file_data, font_size)
+    }
+
+    /// Use font file data (TTF/OTF) for dynamic rasterization
+    ///
+    /// '?' (the fallback glyph) and ' ' are rasterized immediately.
+    pub fn from_memory(file_data: Vec<u8>, font_size: u32) -> Result<Self, RaylibError> {
+        let mut atlas = Image::generate_color(
+            Self::INITIAL_ATLAS_SIZE,
+            Self::INITIAL_ATLAS_SIZE,
+            Color::BLANK,
+        );
+        atlas.convert_to_format(PixelFormat::GrayAlpha);
+
+        let texture = Texture::from_image(&atlas)?;
+
+        let mut font = Self {
+            file_data,
+            font_size,
+            padding: 4,
+            font: ManuallyDrop::new(Font {
+                raw: ffi::Font {
+                    baseSize: font_size as _,
+                    glyphCount: 0,
+                    glyphPadding: 4,
+                    texture: texture.raw.clone(),
+                    recs: std::ptr::null_mut(),
+                    glyphs: std::ptr::null_mut(),
+                },
+            }),
+            atlas,
+            texture,
+            recs: Vec::new(),
+            glyphs: Vec::new(),
+            indices: HashMap::new(),
+            pending: vec!['?', ' '],
+            cursor_x: 0,
+            cursor_y: 0,
+            row_height: 0,
+        };
+
+        font.update(usize::MAX)?;
+
+        if font.contains('?') {
+            Ok(font)
+        } else {
+            Err(RaylibError::DecodeFailed("font data".into()))
+        }
+    }
+
+    /// Base size (default chars height)
+    #[inline]
+    pub fn base_size(&self) -> u32 {
+        self.font_size
+    }
+
+    /// Rasterize the font again at another size, e.g. after the DPI scale changed (see [`UiScale`](crate::UiScale))
+    ///
+    /// Every rasterized character is queued again for [`DynamicFont::update`].
+    /// Returns `false` and keeps the current size on failure.
+    pub fn set_base_size(&mut self, font_size: u32) -> bool {
+        if font_size == self.font_size {
+            return true;
+        }
+
+        match Self::from_memory(self.file_data.clone(), font_size) {
+            Ok(mut font) => {
+                let chars = self
+                    .indices
+                    .keys()
+                    .chain(self.pending.iter())
+                    .copied()
+                    .collect::<String>();
+
+                font.request(&chars);
+                *self = font;
+
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Number of rasterized glyphs
+    #[inline]
+    pub fn glyph_count(&self) -> usize {
+        self.glyphs.len()
+    }
+
+    /// Number of characters waiting to be rasterized
+    #[inline]
+    pub fn pending_count(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Check if a character can be drawn without rasterizing it first
+    #[inline]
+    pub fn contains(&self, ch: char) -> bool {
+        self.indices.contains_key(&ch)
+    }
+
+    /// Queue all characters of `text` that haven't been rasterized yet
+    #[inline]
+    pub fn request(&mut self, text: &str) {
+        for ch in text.chars() {
+            if !self.indices.contains_key(&ch) && !self.pending.contains(&ch) {
+                self.pending.push(ch);
+            }
+        }
+    }
+
+    /// Rasterize at most `max_glyphs` queued characters, growing the atlas if needed
+    ///
+    /// Returns the amount of glyphs added to the atlas.
+    /// Characters the font can't provide are dropped from the queue (they'll be drawn as '?').
+    /// If the grown atlas' texture can't be created, the characters stay queued and the error is returned.
+    pub fn update(&mut self, max_glyphs: usize) -> Result<usize, RaylibError> {
+        let count = max_glyphs.min(self.pending.len());
+
+        if count == 0 {
+            return Ok(0);
+        }
+
+        let chars: Vec<char> = self.pending.drain(..count).collect();
+        let infos =
+            GlyphInfo::from_file_data(&self.file_data, self.font_size, &chars, FontType::Default);
+
+        let old_size = (self.atlas.width(), self.atlas.height());
+        let old_glyph_count = self.glyphs.len();
+        let old_cursor = (self.cursor_x, self.cursor_y, self.row_height);
+        let mut added = 0;
+
+        for mut info in infos {
+            if info.image.raw.data.is_null() && info.value != ' ' {
+                // remember the failure, so the character isn't requested again
+                if let Some(&fallback) = self.indices.get(&'?') {
+                    self.indices.insert(info.value, fallback);
+                }
+                continue;
+            }
+
+            if info.image.format() != PixelFormat::Grayscale {
+                info.image.convert_to_format(PixelFormat::Grayscale);
+            }
+
+            let rect = self.pack(info.image.width(), info.image.height());
+            self.blit(&info.image, rect.x as u32, rect.y as u32);
+
+            self.indices.insert(info.value, self.glyphs.len());
+            self.recs.push(rect.into());
+            self.glyphs.push(ffi::GlyphInfo {
+                value: info.value as _,
+                offsetX: info.offset_x,
+                offsetY: info.offset_y,
+                advanceX: info.advance_x,
+                image: ffi::Image {
+                    data: std::ptr::null_mut(),
+                    width: 0,
+                    height: 0,
+                    mipmaps: 1,
+                    format: PixelFormat::Grayscale as _,
+                },
+            });
+
+            added += 1;
+        }
+
+        if (self.atlas.width(), self.atlas.height()) != old_size {
+            match Texture::from_image(&self.atlas) {
+                Ok(texture) => self.texture = texture,
+                Err(error) => {
+                    // the new glyphs are outside of the current texture, queue them again
+                    let chars: Vec<char> = self.glyphs[old_glyph_count..]
+                        .iter()
+                        .filter_map(|glyph| char::from_u32(glyph.value as u32))
+                        .collect();
+
+                    for ch in chars.iter() {
+                        self.indices.remove(ch);
+                    }
+
+                    self.glyphs.truncate(old_glyph_count);
+                    self.recs.truncate(old_glyph_count);
+                    (self.cursor_x, self.cursor_y, self.row_height) = old_cursor;
+                    self.atlas
+                        .resize_canvas(old_size.0, old_size.1, 0, 0, Color::BLANK);
+                    self.pending.splice(0..0, chars);
+
+                    // pushing the glyphs may have moved them
+                    self.font.raw.recs = self.recs.as_mut_ptr();
+                    self.font.raw.glyphs = self.glyphs.as_mut_ptr();
+
+                    return Err(error);
+                }
+            }
+        } else {
+            let data = unsafe {
+                std::slice::from_raw_parts(
+                    self.atlas.raw.data as *const u8,
+                    self.atlas.get_pixel_data_size(),
+                )
+            };
+
+            self.texture.update(data);
+        }
+
+        self.font.raw = ffi::Font {
+            baseSize: self.font_size as _,
+            glyphCount: self.glyphs.len() as _,
+            glyphPadding: self.padding as _,
+            texture: self.texture.raw.clone(),
+            recs: self.recs.as_mut_ptr(),
+            glyphs: self.glyphs.as_mut_ptr(),
+        };
+
+        Ok(added)
+    }
+
+    /// The font in its current state, for use with the regular text drawing functions
+    #[inline]
+    pub fn font(&self) -> &Font {
+        &self.font
+    }
+
+    /// Find room for a glyph in the atlas (shelf packing), growing it when full
+    fn pack(&mut self, width: u32, height: u32) -> Rectangle {
+        let cell_width = width + 2 * self.padding;
+        let cell_height = height + 2 * self.padding;
+
+        let mut atlas_width = self.atlas.width();
+        let mut atlas_height = self.atlas.height();
+
+        while cell_width > atlas_width {
+            atlas_width *= 2;
+        }
+
+        if self.cursor_x + cell_width > atlas_width {
+            self.cursor_x = 0;
+            self.cursor_y += self.row_height;
+            self.row_height = 0;
+        }
+
+        while self.cursor_y + cell_height > atlas_height {
+            atlas_height *= 2;
+        }
+
+        if (atlas_width, atlas_height) != (self.atlas.width(), self.atlas.height()) {
+            self.atlas
+                .resize_canvas(atlas_width, atlas_height, 0, 0, Color::BLANK);
+        }
+
+        let rect = Rectangle::new(
+            (self.cursor_x + self.padding) as f32,
+            (self.cursor_y + self.padding) as f32,
+            width as f32,
+            height as f32,
+        );
+
+        self.cursor_x += cell_width;
+        self.row_height = self.row_height.max(cell_height);
+
+        rect
+    }
+
+    /// Copy a grayscale glyph image into the gray-alpha atlas (gray value goes into alpha)
+    fn blit(&mut self, glyph: &Image, x: u32, y: u32) {
+        if glyph.raw.data.is_null() {
+            return;
+        }
+
+        let atlas_width = self.atlas.width() as usize;
+        let atlas = unsafe {
+            std::slice::from_raw_parts_mut(
+                self.atlas.raw.data as *mut u8,
+                self.atlas.get_pixel_data_size(),
+            )
+        };
+        let pixels = unsafe {
+            std::slice::from_raw_parts(glyph.raw.data as *const u8, glyph.get_pixel_data_size())
+        };
+
+        for row in 0..(glyph.height() as usize) {
+            for col in 0..(glyph.width() as usize) {
+                let dst = ((y as usize + row) * atlas_width + x as usize + col) * 2;
+
+                atlas[dst] = 255;
+                atlas[dst + 1] = pixels[row * glyph.width() as usize + col];
+            }
+        }
+    }
+}
+
+/// raylib's `FONT_SDF_PIXEL_DIST_SCALE`: distance field change per pixel at the base size, in 8 bit steps
+const SDF_PIXEL_DIST_SCALE: f32 = 64.;
+
+const SDF_FS: &str = r#"#version 330
+in vec2 fragTexCoord;
+in vec4 fragColor;
+
+uniform sampler2D texture0;
+uniform float outlineWidth;
+uniform vec4 outlineColor;
+
+out vec4 finalColor;
+
+void main()
+{
+    // 0.5 is on the glyph edge
+    float distance = texture(texture0, fragTexCoord).a - 0.5;
+    float smoothing = length(vec2(dFdx(distance), dFdy(distance)));
+
+    float fill = smoothstep(-smoothing, smoothing, distance);
+    float outline = smoothstep(-smoothing, smoothing, distance + outlineWidth);
+
+    finalColor = vec4(mix(outlineColor.rgb, fragColor.rgb, fill), mix(outlineColor.a, fragColor.a, fill)*outline);
+}
+"#;
+
+/// Outline and drop shadow for [`Draw::draw_text_styled`](crate::drawing::Draw::draw_text_styled)
+/// and [`SdfFont::draw_text_styled`]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TextStyle {
+    /// Outline thickness in pixels and color
+    pub outline: Option<(f32, Color)>,
+    /// Shadow offset in pixels and color, the shadow includes the outline
+    pub shadow: Option<(Vector2, Color)>,
+}
+
+impl TextStyle {
+    /// Create style without outline and shadow
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set outline thickness and color
+    #[inline]
+    pub fn with_outline(mut self, thickness: f32, color: Color) -> Self {
+        self.outline = Some((thickness, color));
+        self
+    }
+
+    /// Set shadow offset and color
+    #[inline]
+    pub fn with_shadow(mut self, offset: impl Into<Vector2>, color: Color) -> Self {
+        self.shadow = Some((offset.into(), color));
+        self
+    }
+
+    /// Offsets to draw bitmap text at to get an outline, rings up to the thickness
+    pub(crate) fn outline_offsets(&self) -> Vec<Vector2> {
+        let thickness = match self.outline {
+            Some((thickness, _)) if thickness > 0. => thickness,
+            _ => return Vec::new(),
+        };
+
+        // a ring per pixel, so thin strokes don't leave gaps in thick outlines
+        let rings = (thickness.ceil() as usize).min(4);
+        let mut offsets = Vec::new();
+
+        for ring in 1..=rings {
+            let radius = thickness * ring as f32 / rings as f32;
+            let samples = ((std::f32::consts::TAU * radius).ceil() as usize).clamp(8, 32);
+
+            for i in 0..samples {
+                let (sin, cos) = (std::f32::consts::TAU * i as f32 / samples as f32).sin_cos();
+
+                offsets.push(Vector2 {
+                    x: cos * radius,
+                    y: sin * radius,
+                });
+            }
+        }
+
+        offsets
+    }
+}
+
+/// Signed distance field font, stays sharp at any size and draws outlines in a single pass
+///
+/// Outlines can be up to about 2 pixels at the base size thick, scaled with the font size.
+/// The shader targets desktop OpenGL 3.3.
+#[derive(Debug)]
+pub struct SdfFont {
+    font: Font,
+    shader: Shader,
+    outline_width_loc: Option<ShaderLoc>,
+    outline_color_loc: Option<ShaderLoc>,
+}
+
+impl SdfFont {
+    /// Load font file (TTF/OTF) as a distance field, an empty `chars` loads the ASCII characters
+    #[inline]
+    pub fn from_file(file_name: &str, font_size: u32, chars: &[char]) -> Result<Self, RaylibError> {
+        let file_data = RaylibError::read_file(file_name)?;
+
+        Self::from_memory(&file_data, font_size, chars)
+    }
+
+    /// Load font file data (TTF/OTF) as a distance field, an empty `chars` loads the ASCII characters
+    pub fn from_memory(
+        file_data: &[u8],
+        font_size: u32,
+        chars: &[char],
+    ) -> Result<Self, RaylibError> {
+        // raylib loads its 95 default characters for a null array
+        let (chars_ptr, count) = if chars.is_empty() {
+            (std::ptr::null_mut(), 95)
+        } else {
+            (chars.as_ptr() as *mut _, chars.len())
+        };
+
+        let glyphs = unsafe {
+            ffi::LoadFontData(
+                file_data.as_ptr(),
+                file_data.len() as _,
+                font_size as _,
+                chars_ptr,
+                count as _,
+                FontType::Sdf as _,
+            )
+        };
+
+        if glyphs.is_null() {
+            return Err(RaylibError::DecodeFailed("font data".into()));
+        }
+
+        let mut recs: *mut ffi::Rectangle = std::ptr::null_mut();
+
+        let raw = unsafe {
+            let atlas = ffi::GenImageFontAtlas(
+                glyphs,
+                &mut recs as *mut _,
+                count as _,
+                font_size as _,
+                0,
+                1,
+            );
+            let texture = ffi::LoadTextureFromImage(atlas.clone());
+            ffi::UnloadImage(atlas);
+
+            // the distance field is interpolated between texels
+            ffi::SetTextureFilter(texture.clone(), TextureFilter::Bilinear as _);
+
+            ffi::Font {
+                baseSize: font_size as _,
+                glyphCount: count as _,
+                glyphPadding: 0,
+                texture,
+                recs,
+                glyphs,
+            }
+        };
+
+        // unloads the glyphs and the atlas if it isn't ready
+        let font = Font::tracked(raw);
+
+        if !unsafe { ffi::IsFontReady(font.raw.clone()) } {
+            return Err(RaylibError::NotReady("font"));
+        }
+
+        let shader = Shader::from_memory(None, Some(SDF_FS))?;
+
+        Ok(Self {
+            font,
+            outline_width_loc: shader.get_location("outlineWidth"),
+            outline_color_loc: shader.get_location("outlineColor"),
+            shader,
+        })
+    }
+
+    /// The font, for measuring text
+    ///
+    /// Drawing it without [`SdfFont::shader`] shows the raw distance field.
+    #[inline]
+    pub fn font(&self) -> &Font {
+        &self.font
+    }
+
+    /// Shader turning the distance field into text
+    #[inline]
+    pub fn shader(&self) -> &Shader {
+        &self.shader
+    }
+
+    /// Draw text with outline and shadow, the shadow first
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_text_styled<D: Draw>(
+        &mut self,
+        d: &mut D,
+        text: &str,
+        pos: impl Into<Vector2>,
+        font_size: f32,
+        spacing: f32,
+        tint: Color,
+        style: &TextStyle,
+    ) {
+        let pos: Vector2 = pos.into();
+        let text = text_to_cstring(text);
+
+        let scale = font_size / self.font.base_size() as f32;
+
+        // outline thickness in distance field units, it only reaches 2 pixels out of the glyphs
+        let (width, outline_color) = match style.outline {
+            Some((thickness, color)) if scale > 0. => (
+                (thickness / scale * SDF_PIXEL_DIST_SCALE / 255.).clamp(0., 0.5),
+                color,
+            ),
+            _ => (0., tint),
+        };
+
+        if let Some((offset, color)) = style.shadow {
+            self.set_outline(width, color);
+
+            let mut shader_mode = d.begin_shader_mode(&self.shader);
+
+            shader_mode.draw_text_with_font_cstr(
+                &text,
+                Vector2 {
+                    x: pos.x + offset.x,
+                    y: pos.y + offset.y,
+                },
+                &self.font,
+                font_size,
+                spacing,
+                color,
+            );
+
+            // ending the shader mode draws the batch, before the uniforms change
+            shader_mode.end_shader_mode();
+        }
+
+        self.set_outline(width, outline_color);
+
+        let mut shader_mode = d.begin_shader_mode(&self.shader);
+
+        shader_mode.draw_text_with_font_cstr(&text, pos, &self.font, font_size, spacing, tint);
+
+        shader_mode.end_shader_mode();
+    }
+
+    fn set_outline(&mut self, width: f32, color: Color) {
+        if let Some(loc) = self.outline_width_loc {
+            self.shader.set_value(loc, width);
+        }
+
+        if let Some(loc) = self.outline_color_loc {
+            self.shader.set_value(loc, color.normalize());
+        }
     }
 }
 
@@ -316,3 +1078,31 @@ impl GlyphInfo {
         vec
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_without_nul_is_kept() {
+        assert_eq!(
+            text_to_cstring("Hello, world!").as_bytes(),
+            b"Hello, world!"
+        );
+        assert_eq!(text_to_cstring("").as_bytes(), b"");
+    }
+
+    #[test]
+    fn interior_nul_bytes_are_dropped() {
+        assert_eq!(text_to_cstring("a\0b\0\0c").as_bytes(), b"abc");
+        assert_eq!(text_to_cstring("\0\0").as_bytes(), b"");
+        assert_eq!(text_to_cstring("\0end\0").as_bytes(), b"end");
+    }
+
+    #[test]
+    fn multibyte_text_survives() {
+        let text = text_to_cstring("héllo\0 wörld 日本");
+
+        assert_eq!(text.to_str(), Ok("héllo wörld 日本"));
+    }
+}
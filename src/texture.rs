@@ -1,9 +1,13 @@
 use crate::{
     color::Color,
-    core::Raylib,
+    core::{release_context_object, track_context_object, ContextObject, Raylib},
+    drawing::gl,
+    error::RaylibError,
     ffi,
     math::{Rectangle, Vector2},
-    text::Font,
+    qr::QrCode,
+    shader::{Shader, ShaderLoc},
+    text::{text_to_cstring, Font},
 };
 
 use std::ffi::{CStr, CString};
@@ -18,8 +22,19 @@ pub fn get_pixel_data_size(width: u32, height: u32, format: PixelFormat) -> usiz
     unsafe { ffi::GetPixelDataSize(width as _, height as _, format as _) as usize }
 }
 
+/// View a raylib-allocated color array as a slice (`Color` has the same layout as `ffi::Color`)
+#[inline]
+unsafe fn color_slice<'a>(colors: *const ffi::Color, len: usize) -> &'a [Color] {
+    if colors.is_null() || len == 0 {
+        &[]
+    } else {
+        std::slice::from_raw_parts(colors as *const Color, len)
+    }
+}
+
 /// Image file format
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ImageFormat {
     /// PNG
     Png,
@@ -81,6 +96,7 @@ impl ImageFormat {
 /// NPatchInfo, n-patch layout info
 #[repr(C)]
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NPatchInfo {
     /// Texture source rectangle
     pub source: Rectangle,
@@ -147,15 +163,15 @@ impl Image {
 
     /// Load image from file into CPU memory (RAM)
     #[inline]
-    pub fn from_file(file_name: &str) -> Option<Self> {
-        let file_name = CString::new(file_name).unwrap();
+    pub fn from_file(file_name: &str) -> Result<Self, RaylibError> {
+        let c_file_name = RaylibError::file_name(file_name)?;
 
-        let raw = unsafe { ffi::LoadImage(file_name.as_ptr()) };
+        let raw = unsafe { ffi::LoadImage(c_file_name.as_ptr()) };
 
         if unsafe { ffi::IsImageReady(raw.clone()) } {
-            Some(Self { raw })
+            Ok(Self { raw })
         } else {
-            None
+            Err(RaylibError::decode_file(file_name))
         }
     }
 
@@ -167,12 +183,12 @@ impl Image {
         height: u32,
         format: PixelFormat,
         header_size: u32,
-    ) -> Option<Self> {
-        let file_name = CString::new(file_name).unwrap();
+    ) -> Result<Self, RaylibError> {
+        let c_file_name = RaylibError::file_name(file_name)?;
 
         let raw = unsafe {
             ffi::LoadImageRaw(
-                file_name.as_ptr(),
+                c_file_name.as_ptr(),
                 width as _,
                 height as _,
                 format as _,
@@ -181,9 +197,9 @@ impl Image {
         };
 
         if unsafe { ffi::IsImageReady(raw.clone()) } {
-            Some(Self { raw })
+            Ok(Self { raw })
         } else {
-            None
+            Err(RaylibError::decode_file(file_name))
         }
     }
 
@@ -191,16 +207,16 @@ impl Image {
     ///
     /// Returns the amount of frames in the image.
     #[inline]
-    pub fn from_file_anim(file_name: &str) -> Option<(Self, usize)> {
-        let file_name = CString::new(file_name).unwrap();
+    pub fn from_file_anim(file_name: &str) -> Result<(Self, usize), RaylibError> {
+        let c_file_name = RaylibError::file_name(file_name)?;
         let mut frames: i32 = 0;
 
-        let image = unsafe { ffi::LoadImageAnim(file_name.as_ptr(), (&mut frames) as *mut _) };
+        let image = unsafe { ffi::LoadImageAnim(c_file_name.as_ptr(), (&mut frames) as *mut _) };
 
         if unsafe { ffi::IsImageReady(image.clone()) } {
-            Some((Self { raw: image }, frames as _))
+            Ok((Self { raw: image }, frames as _))
         } else {
-            None
+            Err(RaylibError::decode_file(file_name))
         }
     }
 
@@ -208,7 +224,7 @@ impl Image {
     ///
     /// If `format` is None, it will make an educated guess on the ImageFormat (not all formats are supported for guessing).
     #[inline]
-    pub fn from_memory(file_data: &[u8], format: Option<ImageFormat>) -> Option<Self> {
+    pub fn from_memory(file_data: &[u8], format: Option<ImageFormat>) -> Result<Self, RaylibError> {
         let raw = unsafe {
             let format = if let Some(format) = format {
                 format.as_cstr().as_ptr()
@@ -220,40 +236,43 @@ impl Image {
         };
 
         if unsafe { ffi::IsImageReady(raw.clone()) } {
-            Some(Self { raw })
+            Ok(Self { raw })
         } else {
-            None
+            Err(RaylibError::DecodeFailed("image data".into()))
         }
     }
 
     /// Load image from GPU texture data
     #[inline]
-    pub fn from_texture(texture: &Texture) -> Option<Self> {
+    pub fn from_texture(texture: &Texture) -> Result<Self, RaylibError> {
         let raw = unsafe { ffi::LoadImageFromTexture(texture.raw.clone()) };
 
         if unsafe { ffi::IsImageReady(raw.clone()) } {
-            Some(Self { raw })
+            Ok(Self { raw })
         } else {
-            None
+            Err(RaylibError::NotReady("image"))
         }
     }
 
     /// Load image from screen buffer and (screenshot)
     #[inline]
-    pub fn from_screen(_raylib: &Raylib) -> Option<Self> {
+    pub fn from_screen(_raylib: &Raylib) -> Result<Self, RaylibError> {
         let raw = unsafe { ffi::LoadImageFromScreen() };
 
         if unsafe { ffi::IsImageReady(raw.clone()) } {
-            Some(Self { raw })
+            Ok(Self { raw })
         } else {
-            None
+            Err(RaylibError::NotReady("image"))
         }
     }
 
     /// Export image data to file, returns true on success
     #[inline]
     pub fn export(&self, file_name: &str) -> bool {
-        let file_name = CString::new(file_name).unwrap();
+        let file_name = match CString::new(file_name) {
+            Ok(file_name) => file_name,
+            Err(_) => return false,
+        };
 
         unsafe { ffi::ExportImage(self.raw.clone(), file_name.as_ptr()) }
     }
@@ -261,7 +280,10 @@ impl Image {
     /// Export image as code file defining an array of bytes, returns true on success
     #[inline]
     pub fn export_as_code(&self, file_name: &str) -> bool {
-        let file_name = CString::new(file_name).unwrap();
+        let file_name = match CString::new(file_name) {
+            Ok(file_name) => file_name,
+            Err(_) => return false,
+        };
 
         unsafe { ffi::ExportImageAsCode(self.raw.clone(), file_name.as_ptr()) }
     }
@@ -380,13 +402,44 @@ impl Image {
     /// Generate image: grayscale image from text data
     #[inline]
     pub fn generate_text(width: u32, height: u32, text: &str) -> Self {
-        let text = CString::new(text).unwrap();
+        let text = text_to_cstring(text);
 
         Self {
             raw: unsafe { ffi::GenImageText(width as _, height as _, text.as_ptr()) },
         }
     }
 
+    /// Generate image: QR code of `text` with a quiet zone of 4 modules around it
+    ///
+    /// Uses byte mode and medium error correction, `None` if the text is over 2331 bytes.
+    pub fn generate_qr(text: &str, module_size: u32, fg: Color, bg: Color) -> Option<Self> {
+        const QUIET_ZONE: u32 = 4;
+
+        let qr = QrCode::encode(text.as_bytes())?;
+        let module_size = module_size.max(1);
+        let size = (qr.size() + QUIET_ZONE * 2) * module_size;
+
+        let mut image = Self::generate_color(size, size, bg);
+
+        for y in 0..qr.size() {
+            for x in 0..qr.size() {
+                if qr.module(x, y) {
+                    image.draw_rectangle(
+                        Rectangle::new(
+                            ((x + QUIET_ZONE) * module_size) as f32,
+                            ((y + QUIET_ZONE) * module_size) as f32,
+                            module_size as f32,
+                            module_size as f32,
+                        ),
+                        fg,
+                    );
+                }
+            }
+        }
+
+        Some(image)
+    }
+
     /// Create an image from another image piece
     #[inline]
     pub fn from_other_image(image: Self, rect: Rectangle) -> Self {
@@ -398,7 +451,7 @@ impl Image {
     /// Create an image from text (default font)
     #[inline]
     pub fn text(text: &str, font_size: u32, color: Color) -> Self {
-        let text = CString::new(text).unwrap();
+        let text = text_to_cstring(text);
 
         Self {
             raw: unsafe { ffi::ImageText(text.as_ptr(), font_size as _, color.into()) },
@@ -414,7 +467,7 @@ impl Image {
         spacing: f32,
         tint: Color,
     ) -> Self {
-        let text = CString::new(text).unwrap();
+        let text = text_to_cstring(text);
 
         Self {
             raw: unsafe {
@@ -592,17 +645,13 @@ impl Image {
     }
 
     /// Load color data from image as a Color array (RGBA - 32bit)
+    ///
+    /// Allocates a new `Vec` on every call, use [`Image::load_colors_into`] to reuse a buffer
     pub fn load_colors(&self) -> Vec<Color> {
         let colors = unsafe { ffi::LoadImageColors(self.raw.clone()) };
         let len = (self.width() * self.height()) as usize;
 
-        let mut vec = Vec::with_capacity(len);
-
-        for i in 0..len {
-            unsafe {
-                vec.push(colors.add(i).read().into());
-            }
-        }
+        let vec = unsafe { color_slice(colors, len) }.to_vec();
 
         unsafe {
             ffi::UnloadImageColors(colors);
@@ -611,26 +660,112 @@ impl Image {
         vec
     }
 
+    /// Load color data from image into `buffer` (RGBA - 32bit), replacing its contents
+    ///
+    /// Doesn't allocate if `buffer` has enough capacity.
+    /// R8G8B8A8 images are copied directly, other formats are converted by raylib in a temporary C buffer.
+    pub fn load_colors_into(&self, buffer: &mut Vec<Color>) {
+        let len = (self.width() * self.height()) as usize;
+
+        buffer.clear();
+
+        if self.format() == PixelFormat::R8G8B8A8 {
+            buffer.extend_from_slice(unsafe { color_slice(self.raw.data as *const _, len) });
+
+            return;
+        }
+
+        let colors = unsafe { ffi::LoadImageColors(self.raw.clone()) };
+
+        buffer.extend_from_slice(unsafe { color_slice(colors, len) });
+
+        unsafe {
+            ffi::UnloadImageColors(colors);
+        }
+    }
+
     /// Load colors palette from image as a Color array (RGBA - 32bit)
+    ///
+    /// Allocates a new `Vec` on every call, use [`Image::load_palette_into`] to reuse a buffer
     pub fn load_palette(&self, max_size: usize) -> Vec<Color> {
         let mut count: i32 = 0;
         let palette = unsafe {
             ffi::LoadImagePalette(self.raw.clone(), max_size as _, (&mut count) as *mut _)
         };
 
-        let mut vec = Vec::with_capacity(count as usize);
+        let vec = unsafe { color_slice(palette, count as usize) }.to_vec();
 
-        for i in 0..(count as usize) {
-            unsafe {
-                vec.push(palette.add(i).read().into());
-            }
+        unsafe {
+            ffi::UnloadImagePalette(palette);
         }
 
+        vec
+    }
+
+    /// Load colors palette from image into `buffer` (RGBA - 32bit), replacing its contents
+    ///
+    /// Doesn't allocate on the Rust side if `buffer` has enough capacity
+    /// (raylib still builds the palette in a temporary C buffer).
+    pub fn load_palette_into(&self, max_size: usize, buffer: &mut Vec<Color>) {
+        let mut count: i32 = 0;
+        let palette = unsafe {
+            ffi::LoadImagePalette(self.raw.clone(), max_size as _, (&mut count) as *mut _)
+        };
+
+        buffer.clear();
+        buffer.extend_from_slice(unsafe { color_slice(palette, count as usize) });
+
         unsafe {
             ffi::UnloadImagePalette(palette);
         }
+    }
 
-        vec
+    /// Compare with another image of the same size, e.g. a golden reference frame
+    ///
+    /// Returns a visualization of the per-pixel difference and the RMSE over all RGBA channels,
+    /// normalized to `0.0` (identical) ..= `1.0` (every channel differs fully, e.g. transparent black
+    /// vs opaque white). Opaque black vs white is about `0.866`, alpha matches there. `None` if the sizes differ.
+    pub fn diff(&self, other: &Image) -> Option<(Image, f64)> {
+        if self.width() != other.width() || self.height() != other.height() {
+            return None;
+        }
+
+        let a = self.load_colors();
+        let b = other.load_colors();
+
+        let mut diff = Image::generate_color(self.width(), self.height(), Color::BLACK);
+
+        if a.is_empty() {
+            return Some((diff, 0.));
+        }
+
+        // `GenImageColor` produces R8G8B8A8 data
+        let pixels =
+            unsafe { std::slice::from_raw_parts_mut(diff.raw.data as *mut Color, a.len()) };
+        let mut sum = 0.;
+
+        for ((pixel, a), b) in pixels.iter_mut().zip(a.iter()).zip(b.iter()) {
+            let delta = [
+                a.r.abs_diff(b.r),
+                a.g.abs_diff(b.g),
+                a.b.abs_diff(b.b),
+                a.a.abs_diff(b.a),
+            ];
+
+            sum += delta.iter().map(|&d| (d as f64) * (d as f64)).sum::<f64>();
+
+            // alpha differences show up in every channel
+            *pixel = Color::new(
+                delta[0].max(delta[3]),
+                delta[1].max(delta[3]),
+                delta[2].max(delta[3]),
+                255,
+            );
+        }
+
+        let rmse = (sum / (a.len() * 4) as f64).sqrt() / 255.;
+
+        Some((diff, rmse))
     }
 
     /// Get image alpha border rectangle
@@ -721,7 +856,7 @@ impl Image {
     /// Draw text (using default font) within an image (destination)
     #[inline]
     pub fn draw_text(&mut self, text: &str, position: Vector2, font_size: u32, color: Color) {
-        let text = CString::new(text).unwrap();
+        let text = text_to_cstring(text);
 
         unsafe {
             ffi::ImageDrawText(
@@ -746,7 +881,7 @@ impl Image {
         spacing: f32,
         tint: Color,
     ) {
-        let text = CString::new(text).unwrap();
+        let text = text_to_cstring(text);
 
         unsafe {
             ffi::ImageDrawTextEx(
@@ -853,39 +988,117 @@ impl Texture {
 
     /// Load texture from file into GPU memory (VRAM)
     #[inline]
-    pub fn from_file(file_name: &str) -> Option<Self> {
-        let file_name = CString::new(file_name).unwrap();
+    pub fn from_file(file_name: &str) -> Result<Self, RaylibError> {
+        let c_file_name = RaylibError::file_name(file_name)?;
 
-        let raw = unsafe { ffi::LoadTexture(file_name.as_ptr()) };
+        let raw = unsafe { ffi::LoadTexture(c_file_name.as_ptr()) };
 
         if unsafe { ffi::IsTextureReady(raw.clone()) } {
-            Some(Self { raw })
+            Ok(Self::tracked(raw))
         } else {
-            None
+            Err(RaylibError::decode_file(file_name))
         }
     }
 
     /// Load texture from image data
     #[inline]
-    pub fn from_image(image: &Image) -> Option<Self> {
+    pub fn from_image(image: &Image) -> Result<Self, RaylibError> {
         let raw = unsafe { ffi::LoadTextureFromImage(image.raw.clone()) };
 
         if unsafe { ffi::IsTextureReady(raw.clone()) } {
-            Some(Self { raw })
+            Ok(Self::tracked(raw))
         } else {
-            None
+            Err(RaylibError::NotReady("texture"))
+        }
+    }
+
+    /// Load texture from image data stored as sRGB, so shaders sample linear colors
+    ///
+    /// For a linear workflow: light and blend in linear space, then convert the output back
+    /// with [`Raylib::set_srgb_framebuffer`] or [`SrgbPostFx`](crate::post_fx::SrgbPostFx).
+    ///
+    /// Images are converted to RGBA (32bit). Filtering starts as point with repeated edges, like raylib's textures.
+    pub fn from_image_srgb(image: &Image) -> Result<Self, RaylibError> {
+        let functions = gl::functions();
+        let (
+            Some(gen_textures),
+            Some(active_texture),
+            Some(bind_texture),
+            Some(tex_image_2d),
+            Some(tex_parameteri),
+        ) = (
+            functions.gen_textures,
+            functions.active_texture,
+            functions.bind_texture,
+            functions.tex_image_2d,
+            functions.tex_parameteri,
+        )
+        else {
+            return Err(RaylibError::NotReady("texture"));
+        };
+
+        let colors = image.load_colors();
+        let mut id = 0;
+
+        unsafe {
+            // the batch may still draw with the texture bound to unit 0
+            gl::flush_batch();
+
+            gen_textures(1, &mut id);
+
+            if id == 0 {
+                return Err(RaylibError::NotReady("texture"));
+            }
+
+            active_texture(gl::TEXTURE0);
+            bind_texture(gl::TEXTURE_2D, id);
+            tex_image_2d(
+                gl::TEXTURE_2D,
+                0,
+                gl::SRGB8_ALPHA8,
+                image.width() as _,
+                image.height() as _,
+                0,
+                gl::RGBA,
+                gl::UNSIGNED_BYTE,
+                colors.as_ptr() as *const _,
+            );
+
+            let parameters = [
+                (gl::TEXTURE_MIN_FILTER, gl::NEAREST),
+                (gl::TEXTURE_MAG_FILTER, gl::NEAREST),
+                (gl::TEXTURE_WRAP_S, gl::REPEAT),
+                (gl::TEXTURE_WRAP_T, gl::REPEAT),
+            ];
+
+            for (name, value) in parameters {
+                tex_parameteri(gl::TEXTURE_2D, name, value);
+            }
+
+            bind_texture(gl::TEXTURE_2D, 0);
         }
+
+        Ok(Self::tracked(ffi::Texture {
+            id,
+            width: image.width() as _,
+            height: image.height() as _,
+            mipmaps: 1,
+            format: PixelFormat::R8G8B8A8 as _,
+        }))
     }
 
     /// Load cubemap from image, multiple image cubemap layouts supported
     #[inline]
-    pub fn from_cubemap(image: &Image, layout: CubemapLayout) -> Option<TextureCubemap> {
+    pub fn from_cubemap(
+        image: &Image,
+        layout: CubemapLayout,
+    ) -> Result<TextureCubemap, RaylibError> {
         let raw = unsafe { ffi::LoadTextureCubemap(image.raw.clone(), layout as _) };
 
         if unsafe { ffi::IsTextureReady(raw.clone()) } {
-            Some(Self { raw })
+            Ok(Self::tracked(raw))
         } else {
-            None
+            Err(RaylibError::NotReady("cubemap"))
         }
     }
 
@@ -971,6 +1184,14 @@ impl Texture {
     /// * The raw object should be unique. Otherwise, make sure its clones don't outlive the newly created object.
     #[inline]
     pub unsafe fn from_raw(raw: ffi::Texture) -> Self {
+        Self::tracked(raw)
+    }
+
+    /// Wrap a texture of the current context
+    #[inline]
+    fn tracked(raw: ffi::Texture) -> Self {
+        track_context_object(ContextObject::Texture(raw.id));
+
         Self { raw }
     }
 }
@@ -978,7 +1199,9 @@ impl Texture {
 impl Drop for Texture {
     #[inline]
     fn drop(&mut self) {
-        unsafe { ffi::UnloadTexture(self.raw.clone()) }
+        if release_context_object(ContextObject::Texture(self.raw.id)) {
+            unsafe { ffi::UnloadTexture(self.raw.clone()) }
+        }
     }
 }
 
@@ -1004,16 +1227,124 @@ impl RenderTexture {
 
     /// Load texture for rendering (framebuffer)
     #[inline]
-    pub fn new(width: u32, height: u32) -> Option<Self> {
+    pub fn new(width: u32, height: u32) -> Result<Self, RaylibError> {
         let raw = unsafe { ffi::LoadRenderTexture(width as _, height as _) };
 
         if unsafe { ffi::IsRenderTextureReady(raw.clone()) } {
-            Some(Self { raw })
+            Ok(Self::tracked(raw))
+        } else {
+            Err(RaylibError::NotReady("render texture"))
+        }
+    }
+
+    /// Load texture for rendering with a depth texture instead of a depth renderbuffer
+    ///
+    /// The depth can be sampled afterwards with [`RenderTexture::depth_texture`], e.g. by post effects.
+    #[inline]
+    pub fn with_depth_texture(width: u32, height: u32) -> Result<Self, RaylibError> {
+        Self::load(width, height, PixelFormat::R8G8B8A8, true)
+    }
+
+    /// Load texture for rendering with a color format other than RGBA8, e.g. floating point
+    ///
+    /// Fails if the format can't be rendered to (compressed formats, or float formats the GPU lacks).
+    #[inline]
+    pub fn with_format(width: u32, height: u32, format: PixelFormat) -> Result<Self, RaylibError> {
+        Self::load(width, height, format, false)
+    }
+
+    fn load(
+        width: u32,
+        height: u32,
+        format: PixelFormat,
+        depth_texture: bool,
+    ) -> Result<Self, RaylibError> {
+        let (width, height) = (width as i32, height as i32);
+
+        let raw = unsafe {
+            let id = gl::rlLoadFramebuffer(width, height);
+
+            if id == 0 {
+                return Err(RaylibError::NotReady("render texture"));
+            }
+
+            let color = gl::rlLoadTexture(std::ptr::null(), width, height, format as _, 1);
+            let depth = gl::rlLoadTextureDepth(width, height, !depth_texture);
+
+            let (depth_attachment, depth_format) = if depth_texture {
+                // raylib has no depth pixel format, LoadRenderTexture's renderbuffers use 19
+                (gl::ATTACHMENT_TEXTURE2D, PixelFormat::R32 as _)
+            } else {
+                (gl::ATTACHMENT_RENDERBUFFER, 19)
+            };
+
+            gl::rlFramebufferAttach(
+                id,
+                color,
+                gl::ATTACHMENT_COLOR_CHANNEL0,
+                gl::ATTACHMENT_TEXTURE2D,
+                0,
+            );
+            gl::rlFramebufferAttach(id, depth, gl::ATTACHMENT_DEPTH, depth_attachment, 0);
+
+            ffi::RenderTexture {
+                id,
+                texture: ffi::Texture {
+                    id: color,
+                    width,
+                    height,
+                    mipmaps: 1,
+                    format: format as _,
+                },
+                depth: ffi::Texture {
+                    id: depth,
+                    width,
+                    height,
+                    mipmaps: 1,
+                    format: depth_format,
+                },
+            }
+        };
+
+        // the framebuffer deletes its depth attachment when unloaded
+        if unsafe { gl::rlFramebufferComplete(raw.id) } {
+            Ok(Self::tracked(raw))
+        } else {
+            #[cfg(debug_assertions)]
+            crate::core::warn(&format!(
+                "RenderTexture: framebuffer with format {format:?} is incomplete"
+            ));
+
+            unsafe { ffi::UnloadRenderTexture(raw) }
+            Err(RaylibError::NotReady("render texture"))
+        }
+    }
+
+    /// Color attachment, e.g. to bind it with [`Shader::set_value_texture`]
+    #[inline]
+    pub fn texture(&self) -> &Texture2D {
+        // Texture is a transparent wrapper of ffi::Texture
+        unsafe { &*(&self.raw.texture as *const ffi::Texture as *const Texture2D) }
+    }
+
+    /// Depth attachment as a texture, `None` unless loaded with [`RenderTexture::with_depth_texture`]
+    ///
+    /// Depth is stored non-linearly in 0..1, see [`crate::fog::FogPostFx`] for converting it to a distance.
+    #[inline]
+    pub fn depth_texture(&self) -> Option<&Texture2D> {
+        if self.has_depth_texture() {
+            // Texture is a transparent wrapper of ffi::Texture
+            Some(unsafe { &*(&self.raw.depth as *const ffi::Texture as *const Texture2D) })
         } else {
             None
         }
     }
 
+    #[inline]
+    fn has_depth_texture(&self) -> bool {
+        self.raw.depth.id != 0 && self.raw.depth.format == PixelFormat::R32 as i32
+    }
+
     /// Get the 'raw' ffi type
     /// Take caution when cloning so it doesn't outlive the original
     #[inline]
@@ -1035,6 +1366,14 @@ impl RenderTexture {
     /// * The raw object should be unique. Otherwise, make sure its clones don't outlive the newly created object.
     #[inline]
     pub unsafe fn from_raw(raw: ffi::RenderTexture) -> Self {
+        Self::tracked(raw)
+    }
+
+    /// Wrap a render texture of the current context, it owns its attachments
+    #[inline]
+    fn tracked(raw: ffi::RenderTexture) -> Self {
+        track_context_object(ContextObject::Framebuffer(raw.id));
+
         Self { raw }
     }
 }
@@ -1042,7 +1381,366 @@ impl RenderTexture {
 impl Drop for RenderTexture {
     #[inline]
     fn drop(&mut self) {
-        unsafe { ffi::UnloadRenderTexture(self.raw.clone()) }
+        if release_context_object(ContextObject::Framebuffer(self.raw.id)) {
+            unsafe { ffi::UnloadRenderTexture(self.raw.clone()) }
+        }
+    }
+}
+
+/// Pair of render textures keeping the previous frame around, for temporal effects
+///
+/// Draw the frame into [`HistoryBuffer::current`] while sampling [`HistoryBuffer::previous_texture`]
+/// (motion blur, temporal anti-aliasing, feedback trails), then call [`HistoryBuffer::swap`] once the frame is done.
+/// After creation and resizing there's no previous frame yet, check [`HistoryBuffer::has_previous`]
+/// (e.g. to set the history weight uniform to 0).
+#[derive(Debug)]
+pub struct HistoryBuffer {
+    targets: [RenderTexture; 2],
+    current: usize,
+    format: PixelFormat,
+    has_previous: bool,
+}
+
+impl HistoryBuffer {
+    /// Create the buffer with RGBA (32bit) textures
+    #[inline]
+    pub fn new(width: u32, height: u32) -> Result<Self, RaylibError> {
+        Self::with_format(width, height, PixelFormat::R8G8B8A8)
+    }
+
+    /// Create the buffer with another color format, e.g. floating point for HDR
+    pub fn with_format(width: u32, height: u32, format: PixelFormat) -> Result<Self, RaylibError> {
+        Ok(Self {
+            targets: Self::load(width, height, format)?,
+            current: 0,
+            format,
+            has_previous: false,
+        })
+    }
+
+    fn load(
+        width: u32,
+        height: u32,
+        format: PixelFormat,
+    ) -> Result<[RenderTexture; 2], RaylibError> {
+        if format == PixelFormat::R8G8B8A8 {
+            Ok([
+                RenderTexture::new(width, height)?,
+                RenderTexture::new(width, height)?,
+            ])
+        } else {
+            Ok([
+                RenderTexture::with_format(width, height, format)?,
+                RenderTexture::with_format(width, height, format)?,
+            ])
+        }
+    }
+
+    /// Texture width
+    #[inline]
+    pub fn width(&self) -> u32 {
+        self.targets[0].width()
+    }
+
+    /// Texture height
+    #[inline]
+    pub fn height(&self) -> u32 {
+        self.targets[0].height()
+    }
+
+    /// Target for drawing this frame
+    #[inline]
+    pub fn current(&self) -> &RenderTexture {
+        &self.targets[self.current]
+    }
+
+    /// The frame drawn before the last [`HistoryBuffer::swap`]
+    #[inline]
+    pub fn previous(&self) -> &RenderTexture {
+        &self.targets[1 - self.current]
+    }
+
+    /// Color texture of the previous frame, to bind with [`Shader::set_value_texture`]
+    #[inline]
+    pub fn previous_texture(&self) -> &Texture2D {
+        self.previous().texture()
+    }
+
+    /// Check if the previous texture holds a frame (false after creation and resizing)
+    #[inline]
+    pub fn has_previous(&self) -> bool {
+        self.has_previous
+    }
+
+    /// Make the current frame the previous one, call it after the frame was drawn
+    #[inline]
+    pub fn swap(&mut self) {
+        self.current = 1 - self.current;
+        self.has_previous = true;
+    }
+
+    /// Forget the previous frame, e.g. after a camera cut
+    #[inline]
+    pub fn invalidate(&mut self) {
+        self.has_previous = false;
+    }
+
+    /// Recreate the textures at another size (e.g. when the window was resized), which drops the history
+    ///
+    /// Does nothing if the size didn't change. Returns `false` and keeps the old textures if they couldn't be created.
+    pub fn resize(&mut self, width: u32, height: u32) -> bool {
+        if (width, height) == (self.width(), self.height()) {
+            return true;
+        }
+
+        match Self::load(width, height, self.format) {
+            Ok(targets) => {
+                self.targets = targets;
+                self.current = 0;
+                self.has_previous = false;
+
+                true
+            }
+            Err(_) => false,
+        }
+    }
+}
+
+/// Array of same sized 2D textures in one GPU texture, sampled with a layer index
+///
+/// Bind it to a `sampler2DArray` uniform with [`TextureArray::bind`] and index layers in the
+/// shader, e.g. per instance or per tile. Needs OpenGL 3.3 (not available on OpenGL ES 2).
+#[derive(Debug)]
+pub struct TextureArray {
+    id: u32,
+    width: u32,
+    height: u32,
+    layers: u32,
+}
+
+impl TextureArray {
+    /// First texture unit raylib never binds textures to
+    pub const FIRST_FREE_UNIT: u32 = 8;
+
+    /// Create texture array with one layer per image
+    ///
+    /// `None` if there are no images or they differ in size.
+    ///
+    /// Images are converted to RGBA (32bit). Filtering starts as point with clamped edges.
+    pub fn from_images(images: &[Image]) -> Option<Self> {
+        let first = images.first()?;
+        let (width, height) = (first.width(), first.height());
+
+        if images
+            .iter()
+            .any(|image| image.width() != width || image.height() != height)
+        {
+            #[cfg(debug_assertions)]
+            crate::core::warn("TextureArray: all images must have the same size");
+
+            return None;
+        }
+
+        let functions = gl::functions();
+        let gen_textures = functions.gen_textures?;
+        let active_texture = functions.active_texture?;
+        let bind_texture = functions.bind_texture?;
+        let tex_image_3d = functions.tex_image_3d?;
+        let tex_sub_image_3d = functions.tex_sub_image_3d?;
+        let tex_parameteri = functions.tex_parameteri?;
+
+        let mut id = 0;
+        let mut colors = Vec::new();
+
+        unsafe {
+            // the batch may still draw with the texture bound to unit 0
+            gl::flush_batch();
+
+            gen_textures(1, &mut id);
+
+            if id == 0 {
+                return None;
+            }
+
+            active_texture(gl::TEXTURE0);
+            bind_texture(gl::TEXTURE_2D_ARRAY, id);
+            tex_image_3d(
+                gl::TEXTURE_2D_ARRAY,
+                0,
+                gl::RGBA8,
+                width as _,
+                height as _,
+                images.len() as _,
+                0,
+                gl::RGBA,
+                gl::UNSIGNED_BYTE,
+                std::ptr::null(),
+            );
+
+            for (layer, image) in images.iter().enumerate() {
+                image.load_colors_into(&mut colors);
+
+                tex_sub_image_3d(
+                    gl::TEXTURE_2D_ARRAY,
+                    0,
+                    0,
+                    0,
+                    layer as _,
+                    width as _,
+                    height as _,
+                    1,
+                    gl::RGBA,
+                    gl::UNSIGNED_BYTE,
+                    colors.as_ptr() as *const _,
+                );
+            }
+
+            let parameters = [
+                (gl::TEXTURE_MIN_FILTER, gl::NEAREST),
+                (gl::TEXTURE_MAG_FILTER, gl::NEAREST),
+                (gl::TEXTURE_WRAP_S, gl::CLAMP_TO_EDGE),
+                (gl::TEXTURE_WRAP_T, gl::CLAMP_TO_EDGE),
+            ];
+
+            for (name, value) in parameters {
+                tex_parameteri(gl::TEXTURE_2D_ARRAY, name, value);
+            }
+
+            bind_texture(gl::TEXTURE_2D_ARRAY, 0);
+        }
+
+        track_context_object(ContextObject::Texture(id));
+
+        Some(Self {
+            id,
+            width,
+            height,
+            layers: images.len() as u32,
+        })
+    }
+
+    /// Layer width
+    #[inline]
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Layer height
+    #[inline]
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Number of layers
+    #[inline]
+    pub fn layers(&self) -> u32 {
+        self.layers
+    }
+
+    /// OpenGL texture id
+    #[inline]
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Run `f` with the array bound to texture unit 0, restoring the binding afterwards
+    fn with_bound(&self, f: impl FnOnce(&gl::Functions)) {
+        let functions = gl::functions();
+        let (Some(active_texture), Some(bind_texture)) =
+            (functions.active_texture, functions.bind_texture)
+        else {
+            return;
+        };
+
+        unsafe {
+            gl::flush_batch();
+
+            active_texture(gl::TEXTURE0);
+            bind_texture(gl::TEXTURE_2D_ARRAY, self.id);
+            f(&functions);
+            bind_texture(gl::TEXTURE_2D_ARRAY, 0);
+        }
+    }
+
+    /// Generate GPU mipmaps for all layers
+    pub fn generate_mipmaps(&mut self) {
+        self.with_bound(|functions| {
+            if let Some(generate_mipmap) = functions.generate_mipmap {
+                unsafe { generate_mipmap(gl::TEXTURE_2D_ARRAY) }
+            }
+        });
+    }
+
+    /// Set texture scaling filter mode (trilinear and anisotropic need mipmaps)
+    pub fn set_filter(&mut self, filter: TextureFilter) {
+        let (min, mag) = match filter {
+            TextureFilter::Point => (gl::NEAREST, gl::NEAREST),
+            TextureFilter::Bilinear => (gl::LINEAR, gl::LINEAR),
+            _ => (gl::LINEAR_MIPMAP_LINEAR, gl::LINEAR),
+        };
+
+        self.with_bound(|functions| {
+            if let Some(tex_parameteri) = functions.tex_parameteri {
+                unsafe {
+                    tex_parameteri(gl::TEXTURE_2D_ARRAY, gl::TEXTURE_MIN_FILTER, min);
+                    tex_parameteri(gl::TEXTURE_2D_ARRAY, gl::TEXTURE_MAG_FILTER, mag);
+                }
+            }
+        });
+    }
+
+    /// Set texture wrapping mode
+    pub fn set_wrap(&mut self, wrap: TextureWrap) {
+        let value = match wrap {
+            TextureWrap::Repeat => gl::REPEAT,
+            TextureWrap::Clamp => gl::CLAMP_TO_EDGE,
+            TextureWrap::MirrorRepeat => gl::MIRRORED_REPEAT,
+            TextureWrap::MirrorClamp => gl::MIRROR_CLAMP_TO_EDGE,
+        };
+
+        self.with_bound(|functions| {
+            if let Some(tex_parameteri) = functions.tex_parameteri {
+                unsafe {
+                    tex_parameteri(gl::TEXTURE_2D_ARRAY, gl::TEXTURE_WRAP_S, value);
+                    tex_parameteri(gl::TEXTURE_2D_ARRAY, gl::TEXTURE_WRAP_T, value);
+                }
+            }
+        });
+    }
+
+    /// Bind the array to a texture unit and point a `sampler2DArray` uniform at it
+    ///
+    /// raylib's batch uses units 0 to 4 (`texture0` and [`Shader::set_value_texture`]),
+    /// units from [`TextureArray::FIRST_FREE_UNIT`] on keep their binding until changed.
+    pub fn bind(&self, shader: &mut Shader, loc: ShaderLoc, unit: u32) {
+        let functions = gl::functions();
+        let (Some(active_texture), Some(bind_texture)) =
+            (functions.active_texture, functions.bind_texture)
+        else {
+            return;
+        };
+
+        unsafe {
+            // draws already batched may use whatever was bound to the unit before
+            gl::flush_batch();
+
+            active_texture(gl::TEXTURE0 + unit);
+            bind_texture(gl::TEXTURE_2D_ARRAY, self.id);
+            active_texture(gl::TEXTURE0);
+        }
+
+        shader.set_value(loc, unit as i32);
+    }
+}
+
+impl Drop for TextureArray {
+    #[inline]
+    fn drop(&mut self) {
+        if release_context_object(ContextObject::Texture(self.id)) {
+            if let Some(delete_textures) = gl::functions().delete_textures {
+                unsafe { delete_textures(1, &self.id) }
+            }
+        }
     }
 }
 
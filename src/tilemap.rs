@@ -0,0 +1,881 @@
+use crate::{
+    color::Color,
+    drawing::Draw,
+    ffi,
+    math::{Rectangle, Vector2},
+    texture::{RenderTexture2D, Texture2D},
+};
+use std::{collections::HashMap, ops::RangeInclusive};
+
+/// Neighbor bits of an auto-tiling mask, clockwise from north (y grows downwards)
+pub mod neighbor {
+    /// Cell above
+    pub const N: u8 = 1;
+    /// Cell above and to the right
+    pub const NE: u8 = 2;
+    /// Cell to the right
+    pub const E: u8 = 4;
+    /// Cell below and to the right
+    pub const SE: u8 = 8;
+    /// Cell below
+    pub const S: u8 = 16;
+    /// Cell below and to the left
+    pub const SW: u8 = 32;
+    /// Cell to the left
+    pub const W: u8 = 64;
+    /// Cell above and to the left
+    pub const NW: u8 = 128;
+}
+
+/// The 47 distinct blob masks, corners only count when both edges next to them are set
+pub const BLOB_MASKS: [u8; 47] = [
+    0, 1, 4, 5, 7, 16, 17, 20, 21, 23, 28, 29, 31, 64, 65, 68, 69, 71, 80, 81, 84, 85, 87, 92, 93,
+    95, 112, 113, 116, 117, 119, 124, 125, 127, 193, 197, 199, 209, 213, 215, 221, 223, 241, 245,
+    247, 253, 255,
+];
+
+/// Offsets of the neighbors, in the order of the [`neighbor`] bits
+const NEIGHBORS: [(i64, i64); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+/// Grid layout of tiles in a texture, numbered left to right and top to bottom
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Tileset {
+    /// Tile width in pixels
+    pub tile_width: f32,
+    /// Tile height in pixels
+    pub tile_height: f32,
+    /// Tiles per row
+    pub columns: u32,
+    /// Pixels around the tiles
+    pub margin: f32,
+    /// Pixels between tiles
+    pub spacing: f32,
+}
+
+impl Tileset {
+    /// Tileset without margin or spacing
+    #[inline]
+    pub fn new(tile_width: f32, tile_height: f32, columns: u32) -> Self {
+        Self {
+            tile_width,
+            tile_height,
+            columns,
+            margin: 0.,
+            spacing: 0.,
+        }
+    }
+
+    /// Tileset of a texture packed with tiles without margin or spacing
+    #[inline]
+    pub fn from_texture(texture: &Texture2D, tile_width: f32, tile_height: f32) -> Self {
+        Self::new(
+            tile_width,
+            tile_height,
+            (texture.width() as f32 / tile_width) as u32,
+        )
+    }
+
+    /// Area of a tile in the texture
+    #[inline]
+    pub fn source_rect(&self, tile: u32) -> Rectangle {
+        let columns = self.columns.max(1);
+        let (column, row) = ((tile % columns) as f32, (tile / columns) as f32);
+
+        Rectangle::new(
+            self.margin + column * (self.tile_width + self.spacing),
+            self.margin + row * (self.tile_height + self.spacing),
+            self.tile_width,
+            self.tile_height,
+        )
+    }
+}
+
+/// Neighbor layout an [`AutoTiler`] distinguishes
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AutoTileMode {
+    /// 16 tiles for the combinations of the 4 edge neighbors, corners look blocky
+    Edges16,
+    /// 47 tiles for the edge and corner neighbors (blob tileset), see [`BLOB_MASKS`]
+    Blob47,
+}
+
+impl AutoTileMode {
+    /// Number of tiles the mode needs
+    #[inline]
+    pub fn tile_count(self) -> usize {
+        match self {
+            Self::Edges16 => 16,
+            Self::Blob47 => BLOB_MASKS.len(),
+        }
+    }
+}
+
+/// Picks tiles for terrain cells from which of their neighbors are terrain too
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "AutoTilerData"))]
+pub struct AutoTiler {
+    mode: AutoTileMode,
+    tiles: Vec<u32>,
+}
+
+/// Deserialized [`AutoTiler`] before its tile count is checked
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct AutoTilerData {
+    mode: AutoTileMode,
+    tiles: Vec<u32>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<AutoTilerData> for AutoTiler {
+    type Error = String;
+
+    fn try_from(data: AutoTilerData) -> Result<Self, Self::Error> {
+        if data.tiles.len() != data.mode.tile_count() {
+            return Err(format!(
+                "{:?} needs {} tiles, got {}",
+                data.mode,
+                data.mode.tile_count(),
+                data.tiles.len()
+            ));
+        }
+
+        Ok(Self {
+            mode: data.mode,
+            tiles: data.tiles,
+        })
+    }
+}
+
+impl AutoTiler {
+    /// Auto-tiler with a tile for each of the 16 edge masks
+    ///
+    /// `tiles[mask]` is used for a mask made of the [`neighbor::N`], [`neighbor::E`], [`neighbor::S`]
+    /// and [`neighbor::W`] bits, shifted down to 0..16 (N = 1, E = 2, S = 4, W = 8).
+    #[inline]
+    pub fn edges16(tiles: [u32; 16]) -> Self {
+        Self {
+            mode: AutoTileMode::Edges16,
+            tiles: tiles.to_vec(),
+        }
+    }
+
+    /// Auto-tiler with a tile for each of the 47 blob masks, `tiles[i]` is used for `BLOB_MASKS[i]`
+    #[inline]
+    pub fn blob47(tiles: [u32; 47]) -> Self {
+        Self {
+            mode: AutoTileMode::Blob47,
+            tiles: tiles.to_vec(),
+        }
+    }
+
+    /// Auto-tiler for a tileset with the tiles of a mode in a row, in mask order from `first_tile` on
+    #[inline]
+    pub fn sequential(mode: AutoTileMode, first_tile: u32) -> Self {
+        Self {
+            mode,
+            tiles: (first_tile..first_tile + mode.tile_count() as u32).collect(),
+        }
+    }
+
+    /// Neighbor layout
+    #[inline]
+    pub fn mode(&self) -> AutoTileMode {
+        self.mode
+    }
+
+    /// Tile for a full 8 bit neighbor mask (see [`neighbor`])
+    pub fn tile(&self, mask: u8) -> u32 {
+        use neighbor::*;
+
+        let edges = mask & (N | E | S | W);
+
+        match self.mode {
+            AutoTileMode::Edges16 => {
+                let index = (edges & N) | (edges & E) >> 1 | (edges & S) >> 2 | (edges & W) >> 3;
+
+                self.tiles[index as usize]
+            }
+            AutoTileMode::Blob47 => {
+                let mut reduced = edges;
+
+                for (corner, a, b) in [(NE, N, E), (SE, S, E), (SW, S, W), (NW, N, W)] {
+                    if mask & corner != 0 && edges & a != 0 && edges & b != 0 {
+                        reduced |= corner;
+                    }
+                }
+
+                // every reduced mask is in the table
+                let index = BLOB_MASKS.binary_search(&reduced).unwrap_or(0);
+
+                self.tiles[index]
+            }
+        }
+    }
+}
+
+/// Grid of terrain cells with tiles picked by an [`AutoTiler`]
+///
+/// Editing a cell only updates the tiles of it and its 8 neighbors.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "AutoTileLayerData"))]
+pub struct AutoTileLayer {
+    width: u32,
+    height: u32,
+    terrain: Vec<bool>,
+    tiles: Vec<Option<u32>>,
+    tiler: AutoTiler,
+    outside_filled: bool,
+}
+
+/// Deserialized [`AutoTileLayer`] before its size is checked
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct AutoTileLayerData {
+    width: u32,
+    height: u32,
+    terrain: Vec<bool>,
+    tiles: Vec<Option<u32>>,
+    tiler: AutoTiler,
+    outside_filled: bool,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<AutoTileLayerData> for AutoTileLayer {
+    type Error = String;
+
+    fn try_from(data: AutoTileLayerData) -> Result<Self, Self::Error> {
+        let cells = (data.width as usize).checked_mul(data.height as usize);
+
+        for (name, len) in [("terrain", data.terrain.len()), ("tiles", data.tiles.len())] {
+            if cells != Some(len) {
+                return Err(format!(
+                    "a {}x{} layer needs {} {name} cells, got {len}",
+                    data.width,
+                    data.height,
+                    data.width as u64 * data.height as u64,
+                ));
+            }
+        }
+
+        Ok(Self {
+            width: data.width,
+            height: data.height,
+            terrain: data.terrain,
+            tiles: data.tiles,
+            tiler: data.tiler,
+            outside_filled: data.outside_filled,
+        })
+    }
+}
+
+impl AutoTileLayer {
+    /// Create layer without terrain
+    #[inline]
+    pub fn new(width: u32, height: u32, tiler: AutoTiler) -> Self {
+        Self::from_fn(width, height, tiler, |_, _| false)
+    }
+
+    /// Create layer with the terrain of every cell
+    pub fn from_fn(
+        width: u32,
+        height: u32,
+        tiler: AutoTiler,
+        mut terrain: impl FnMut(u32, u32) -> bool,
+    ) -> Self {
+        let terrain = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| terrain(x, y))
+            .collect();
+
+        let mut layer = Self {
+            width,
+            height,
+            terrain,
+            tiles: vec![None; width as usize * height as usize],
+            tiler,
+            outside_filled: true,
+        };
+
+        layer.update_all();
+        layer
+    }
+
+    /// Set whether cells outside the layer count as terrain (the default), so terrain reaching the
+    /// border doesn't get an edge there
+    #[inline]
+    pub fn with_outside_filled(mut self, filled: bool) -> Self {
+        self.outside_filled = filled;
+        self.update_all();
+        self
+    }
+
+    /// Layer width in cells
+    #[inline]
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Layer height in cells
+    #[inline]
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Auto-tiler picking the tiles
+    #[inline]
+    pub fn tiler(&self) -> &AutoTiler {
+        &self.tiler
+    }
+
+    /// Replace the auto-tiler, updating every tile
+    #[inline]
+    pub fn set_tiler(&mut self, tiler: AutoTiler) {
+        self.tiler = tiler;
+        self.update_all();
+    }
+
+    /// Check if a cell is terrain, outside cells count as set if the outside is filled
+    #[inline]
+    pub fn is_set(&self, x: i64, y: i64) -> bool {
+        match self.index(x, y) {
+            Some(i) => self.terrain[i],
+            None => self.outside_filled,
+        }
+    }
+
+    /// Make a cell terrain or empty, updating the tiles around it (ignored outside the layer)
+    pub fn set(&mut self, x: u32, y: u32, terrain: bool) {
+        let i = match self.index(x as i64, y as i64) {
+            Some(i) => i,
+            None => return,
+        };
+
+        if self.terrain[i] == terrain {
+            return;
+        }
+
+        self.terrain[i] = terrain;
+
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                self.update_cell(x as i64 + dx, y as i64 + dy);
+            }
+        }
+    }
+
+    /// Tile of a cell, `None` for empty cells and outside the layer
+    #[inline]
+    pub fn tile(&self, x: u32, y: u32) -> Option<u32> {
+        self.index(x as i64, y as i64).and_then(|i| self.tiles[i])
+    }
+
+    /// Tiles of all cells, row by row
+    #[inline]
+    pub fn tiles(&self) -> &[Option<u32>] {
+        &self.tiles
+    }
+
+    /// 8 bit mask of the terrain neighbors of a cell (see [`neighbor`])
+    pub fn neighbor_mask(&self, x: i64, y: i64) -> u8 {
+        NEIGHBORS
+            .iter()
+            .enumerate()
+            .filter(|&(_, &(dx, dy))| self.is_set(x + dx, y + dy))
+            .fold(0, |mask, (bit, _)| mask | 1 << bit)
+    }
+
+    /// Draw the tiles with the top left corner of the layer at `origin`, tiles scaled by `scale`
+    pub fn draw<D: Draw + ?Sized>(
+        &self,
+        d: &mut D,
+        texture: &Texture2D,
+        tileset: &Tileset,
+        origin: Vector2,
+        scale: f32,
+    ) {
+        let (width, height) = (tileset.tile_width * scale, tileset.tile_height * scale);
+
+        for (i, tile) in self.tiles.iter().enumerate() {
+            if let Some(tile) = *tile {
+                let (x, y) = (i % self.width as usize, i / self.width as usize);
+
+                d.draw_texture_pro(
+                    texture,
+                    tileset.source_rect(tile),
+                    Rectangle::new(
+                        origin.x + x as f32 * width,
+                        origin.y + y as f32 * height,
+                        width,
+                        height,
+                    ),
+                    Vector2 { x: 0., y: 0. },
+                    0.,
+                    Color::WHITE,
+                );
+            }
+        }
+    }
+
+    fn update_all(&mut self) {
+        for y in 0..self.height as i64 {
+            for x in 0..self.width as i64 {
+                self.update_cell(x, y);
+            }
+        }
+    }
+
+    fn update_cell(&mut self, x: i64, y: i64) {
+        if let Some(i) = self.index(x, y) {
+            self.tiles[i] = if self.terrain[i] {
+                Some(self.tiler.tile(self.neighbor_mask(x, y)))
+            } else {
+                None
+            };
+        }
+    }
+
+    #[inline]
+    fn index(&self, x: i64, y: i64) -> Option<usize> {
+        if (0..self.width as i64).contains(&x) && (0..self.height as i64).contains(&y) {
+            Some(y as usize * self.width as usize + x as usize)
+        } else {
+            None
+        }
+    }
+}
+
+/// Square block of tiles of a [`ChunkedTileMap`]
+#[derive(Debug)]
+pub struct TileChunk {
+    size: u32,
+    tiles: Vec<Option<u32>>,
+    baked: Option<RenderTexture2D>,
+    dirty: bool,
+}
+
+impl TileChunk {
+    fn new(size: u32) -> Self {
+        Self {
+            size,
+            tiles: vec![None; size as usize * size as usize],
+            baked: None,
+            dirty: true,
+        }
+    }
+
+    /// Chunk width and height in cells
+    #[inline]
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+
+    /// Tile of a cell in the chunk, `None` for empty cells and outside the chunk
+    #[inline]
+    pub fn tile(&self, x: u32, y: u32) -> Option<u32> {
+        self.index(x, y).and_then(|i| self.tiles[i])
+    }
+
+    /// Set tile of a cell in the chunk, `None` to empty it (ignored outside the chunk)
+    #[inline]
+    pub fn set_tile(&mut self, x: u32, y: u32, tile: Option<u32>) {
+        if let Some(i) = self.index(x, y) {
+            if self.tiles[i] != tile {
+                self.tiles[i] = tile;
+                self.dirty = true;
+            }
+        }
+    }
+
+    /// Tiles of all cells, row by row
+    #[inline]
+    pub fn tiles(&self) -> &[Option<u32>] {
+        &self.tiles
+    }
+
+    /// Check if the tiles changed since the chunk was last baked
+    #[inline]
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    #[inline]
+    fn index(&self, x: u32, y: u32) -> Option<usize> {
+        if x < self.size && y < self.size {
+            Some(y as usize * self.size as usize + x as usize)
+        } else {
+            None
+        }
+    }
+
+    /// Draw the tiles one by one, with the top left corner of the chunk at `origin`
+    fn draw_tiles<D: Draw + ?Sized>(
+        &self,
+        d: &mut D,
+        texture: &Texture2D,
+        tileset: &Tileset,
+        origin: Vector2,
+    ) {
+        for (i, tile) in self.tiles.iter().enumerate() {
+            if let Some(tile) = *tile {
+                let (x, y) = (i % self.size as usize, i / self.size as usize);
+
+                d.draw_texture_rect(
+                    texture,
+                    tileset.source_rect(tile),
+                    Vector2 {
+                        x: origin.x + x as f32 * tileset.tile_width,
+                        y: origin.y + y as f32 * tileset.tile_height,
+                    },
+                    Color::WHITE,
+                );
+            }
+        }
+    }
+}
+
+/// Most chunks a [`ChunkedTileMap`] loads or draws across the view in each direction, margins included
+const MAX_CHUNKS_ACROSS: i32 = 128;
+
+/// Unbounded tile map split into chunks that are loaded around the view and unloaded far from it
+///
+/// Call [`ChunkedTileMap::update`] with the visible area every frame to stream chunks in and out,
+/// [`ChunkedTileMap::bake`] outside of 2D mode to render chunks into textures, and
+/// [`ChunkedTileMap::draw`] inside 2D mode. Baked chunks are drawn with one texture draw each,
+/// so the cost of drawing doesn't grow with the number of tiles on screen.
+///
+/// Tiles are drawn at their size in the tileset, one pixel per world unit, scale with the camera zoom.
+/// Views spanning more than 128 chunks are cut down to the 128 around their middle,
+/// and views that aren't finite load and draw nothing.
+#[derive(Debug)]
+pub struct ChunkedTileMap {
+    tileset: Tileset,
+    chunk_size: u32,
+    chunks: HashMap<(i32, i32), TileChunk>,
+    /// Chunks loaded around the view on every side
+    pub load_margin: u32,
+    /// Chunks further than this from the view are unloaded, keep it above the load margin so chunks
+    /// at the border don't load and unload repeatedly
+    pub unload_margin: u32,
+}
+
+impl ChunkedTileMap {
+    /// Create map without chunks, `chunk_size` cells wide and high
+    #[inline]
+    pub fn new(tileset: Tileset, chunk_size: u32) -> Self {
+        Self {
+            tileset,
+            chunk_size: chunk_size.max(1),
+            chunks: HashMap::new(),
+            load_margin: 1,
+            unload_margin: 2,
+        }
+    }
+
+    /// Tileset the tiles are drawn from
+    #[inline]
+    pub fn tileset(&self) -> &Tileset {
+        &self.tileset
+    }
+
+    /// Chunk width and height in cells
+    #[inline]
+    pub fn chunk_size(&self) -> u32 {
+        self.chunk_size
+    }
+
+    /// Chunk size in world units
+    #[inline]
+    pub fn chunk_world_size(&self) -> Vector2 {
+        Vector2 {
+            x: self.chunk_size as f32 * self.tileset.tile_width,
+            y: self.chunk_size as f32 * self.tileset.tile_height,
+        }
+    }
+
+    /// Cell at a world position
+    #[inline]
+    pub fn cell_at(&self, position: Vector2) -> (i64, i64) {
+        (
+            (position.x / self.tileset.tile_width).floor() as i64,
+            (position.y / self.tileset.tile_height).floor() as i64,
+        )
+    }
+
+    /// Chunk containing a cell, saturated to the `i32` range for cells further away
+    #[inline]
+    pub fn chunk_of(&self, x: i64, y: i64) -> (i32, i32) {
+        let size = self.chunk_size as i64;
+        let saturate = |chunk: i64| chunk.clamp(i32::MIN as i64, i32::MAX as i64) as i32;
+
+        (saturate(x.div_euclid(size)), saturate(y.div_euclid(size)))
+    }
+
+    /// Chunk containing a cell, `None` past the `i32` range
+    #[inline]
+    fn exact_chunk_of(&self, x: i64, y: i64) -> Option<(i32, i32)> {
+        let size = self.chunk_size as i64;
+
+        Some((
+            i32::try_from(x.div_euclid(size)).ok()?,
+            i32::try_from(y.div_euclid(size)).ok()?,
+        ))
+    }
+
+    /// Loaded chunk
+    #[inline]
+    pub fn chunk(&self, coords: (i32, i32)) -> Option<&TileChunk> {
+        self.chunks.get(&coords)
+    }
+
+    /// Loaded chunk, for editing
+    #[inline]
+    pub fn chunk_mut(&mut self, coords: (i32, i32)) -> Option<&mut TileChunk> {
+        self.chunks.get_mut(&coords)
+    }
+
+    /// Loaded chunks, in no particular order
+    #[inline]
+    pub fn chunks(&self) -> impl Iterator<Item = ((i32, i32), &TileChunk)> {
+        self.chunks.iter().map(|(&coords, chunk)| (coords, chunk))
+    }
+
+    /// Tile of a cell, `None` if it's empty or its chunk isn't loaded
+    #[inline]
+    pub fn tile(&self, x: i64, y: i64) -> Option<u32> {
+        let (local_x, local_y) = self.local(x, y);
+
+        self.exact_chunk_of(x, y)
+            .and_then(|coords| self.chunk(coords))
+            .and_then(|chunk| chunk.tile(local_x, local_y))
+    }
+
+    /// Set tile of a cell, `false` if its chunk isn't loaded
+    #[inline]
+    pub fn set_tile(&mut self, x: i64, y: i64, tile: Option<u32>) -> bool {
+        let (local_x, local_y) = self.local(x, y);
+        let chunk = match self.exact_chunk_of(x, y) {
+            Some(coords) => self.chunk_mut(coords),
+            None => None,
+        };
+
+        match chunk {
+            Some(chunk) => {
+                chunk.set_tile(local_x, local_y, tile);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Load the chunks around the visible area and unload the distant ones
+    ///
+    /// `generate` fills in new chunks, e.g. procedurally or from saved data. Unloaded chunks are
+    /// returned, to save edits made to them.
+    pub fn update(
+        &mut self,
+        view: Rectangle,
+        mut generate: impl FnMut((i32, i32), &mut TileChunk),
+    ) -> Vec<((i32, i32), TileChunk)> {
+        let (xs, ys) = match self.chunk_range(view, self.load_margin) {
+            Some(range) => range,
+            None => return Vec::new(),
+        };
+
+        // chunks past the unload margin, around what's loaded
+        let extra = self.unload_margin.saturating_sub(self.load_margin);
+        let extra = i32::try_from(extra).unwrap_or(i32::MAX);
+        let (keep_xs, keep_ys) = (
+            xs.start().saturating_sub(extra)..=xs.end().saturating_add(extra),
+            ys.start().saturating_sub(extra)..=ys.end().saturating_add(extra),
+        );
+
+        let far: Vec<_> = self
+            .chunks
+            .keys()
+            .copied()
+            .filter(|(x, y)| !keep_xs.contains(x) || !keep_ys.contains(y))
+            .collect();
+
+        let unloaded = far
+            .into_iter()
+            .filter_map(|coords| self.chunks.remove_entry(&coords))
+            .collect();
+
+        for y in ys {
+            for x in xs.clone() {
+                self.chunks.entry((x, y)).or_insert_with(|| {
+                    let mut chunk = TileChunk::new(self.chunk_size);
+                    generate((x, y), &mut chunk);
+                    chunk
+                });
+            }
+        }
+
+        unloaded
+    }
+
+    /// Render changed chunks into their textures, at most `budget` of them to spread the work over frames
+    ///
+    /// Call it outside of 2D and texture modes. Returns the number of chunks rendered.
+    pub fn bake<D: Draw>(&mut self, d: &mut D, texture: &Texture2D, budget: usize) -> usize {
+        let size = self.chunk_world_size();
+        let mut baked = 0;
+
+        for chunk in self.chunks.values_mut().filter(|chunk| chunk.dirty) {
+            if baked == budget {
+                break;
+            }
+
+            if chunk.baked.is_none() {
+                chunk.baked = RenderTexture2D::new(size.x as u32, size.y as u32).ok();
+            }
+
+            let target = match &chunk.baked {
+                Some(target) => target,
+                // drawn tile by tile instead
+                None => continue,
+            };
+
+            let mut texture_mode = d.begin_texture_mode(target);
+            texture_mode.clear_background(Color::BLANK);
+            chunk.draw_tiles(
+                &mut texture_mode,
+                texture,
+                &self.tileset,
+                Vector2 { x: 0., y: 0. },
+            );
+            texture_mode.end_texture_mode();
+
+            chunk.dirty = false;
+            baked += 1;
+        }
+
+        baked
+    }
+
+    /// Draw the loaded chunks overlapping the visible area
+    ///
+    /// Chunks baked since their last change are drawn from their texture, the rest tile by tile.
+    pub fn draw<D: Draw + ?Sized>(&self, d: &mut D, texture: &Texture2D, view: Rectangle) {
+        let (xs, ys) = match self.chunk_range(view, 0) {
+            Some(range) => range,
+            None => return,
+        };
+        let size = self.chunk_world_size();
+
+        for y in ys {
+            for x in xs.clone() {
+                let chunk = match self.chunks.get(&(x, y)) {
+                    Some(chunk) => chunk,
+                    None => continue,
+                };
+
+                let origin = Vector2 {
+                    x: x as f32 * size.x,
+                    y: y as f32 * size.y,
+                };
+
+                match &chunk.baked {
+                    Some(baked) if !chunk.dirty => unsafe {
+                        ffi::DrawTexturePro(
+                            baked.raw.texture.clone(),
+                            // render textures are upside down
+                            Rectangle::new(0., 0., size.x, -size.y).into(),
+                            Rectangle::new(origin.x, origin.y, size.x, size.y).into(),
+                            Vector2 { x: 0., y: 0. }.into(),
+                            0.,
+                            Color::WHITE.into(),
+                        )
+                    },
+                    _ => chunk.draw_tiles(d, texture, &self.tileset, origin),
+                }
+            }
+        }
+    }
+
+    /// Cell in its chunk
+    #[inline]
+    fn local(&self, x: i64, y: i64) -> (u32, u32) {
+        let size = self.chunk_size as i64;
+
+        (x.rem_euclid(size) as u32, y.rem_euclid(size) as u32)
+    }
+
+    /// Columns and rows of chunks overlapping an area widened by `margin` chunks on every side
+    ///
+    /// At most [`MAX_CHUNKS_ACROSS`] in each direction, `None` if the area isn't finite.
+    fn chunk_range(
+        &self,
+        view: Rectangle,
+        margin: u32,
+    ) -> Option<(RangeInclusive<i32>, RangeInclusive<i32>)> {
+        let (right, bottom) = (view.x + view.width, view.y + view.height);
+
+        if ![view.x, view.y, right, bottom]
+            .iter()
+            .all(|v| v.is_finite())
+        {
+            return None;
+        }
+
+        let min = self.cell_at(Vector2 {
+            x: view.x,
+            y: view.y,
+        });
+        let max = self.cell_at(Vector2 {
+            x: right,
+            y: bottom,
+        });
+        let (min, max) = (self.chunk_of(min.0, min.1), self.chunk_of(max.0, max.1));
+        let margin = i32::try_from(margin).unwrap_or(i32::MAX);
+
+        Some((
+            chunk_span(min.0, max.0, margin),
+            chunk_span(min.1, max.1, margin),
+        ))
+    }
+}
+
+/// Chunks from `min` to `max` widened by `margin`, cut down to [`MAX_CHUNKS_ACROSS`] around the middle
+fn chunk_span(min: i32, max: i32, margin: i32) -> RangeInclusive<i32> {
+    let (min, max) = (min.saturating_sub(margin), max.saturating_add(margin));
+
+    if (max as i64 - min as i64) < MAX_CHUNKS_ACROSS as i64 {
+        return min..=max;
+    }
+
+    let middle = (min as i64 + max as i64).div_euclid(2) as i32;
+    let half = MAX_CHUNKS_ACROSS / 2;
+
+    middle.saturating_sub(half)..=middle.saturating_add(half - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunk_span_is_capped_and_saturates() {
+        assert_eq!(chunk_span(-2, 3, 1), -3..=4);
+        assert_eq!(chunk_span(0, 10_000, 0), 4936..=5063);
+        assert_eq!(chunk_span(i32::MIN, i32::MAX, i32::MAX).count(), 128);
+        assert_eq!(
+            chunk_span(i32::MAX - 1, i32::MAX, 2),
+            i32::MAX - 3..=i32::MAX
+        );
+    }
+}
@@ -0,0 +1,278 @@
+use crate::{
+    color::Color,
+    drawing::Draw,
+    error::RaylibError,
+    ffi,
+    math::{Rectangle, Vector2},
+    shader::{Shader, ShaderLoc},
+    texture::{RenderTexture2D, Texture2D},
+};
+
+const TRANSITION_FS: &str = r#"#version 330
+in vec2 fragTexCoord;
+in vec4 fragColor;
+
+uniform sampler2D texture0;
+uniform sampler2D toTexture;
+uniform sampler2D maskTexture;
+uniform int mode;
+uniform float progress;
+uniform vec4 color;
+uniform vec2 direction;
+uniform float softness;
+uniform float blockSize;
+uniform vec2 resolution;
+
+out vec4 finalColor;
+
+void main()
+{
+    // render textures are upside down, the mask and wipe work in screen space
+    vec2 uv = fragTexCoord;
+    vec2 screenUv = vec2(uv.x, 1.0 - uv.y);
+
+    vec4 from = texture(texture0, uv);
+    vec4 to = texture(toTexture, uv);
+
+    if (mode == 1)
+    {
+        if (progress < 0.5) finalColor = mix(from, color, progress*2.0);
+        else finalColor = mix(color, to, progress*2.0 - 1.0);
+    }
+    else if (mode == 2)
+    {
+        float position = dot(screenUv - 0.5, direction) + 0.5;
+        float edge = progress*(1.0 + softness);
+        finalColor = mix(to, from, smoothstep(edge - softness, edge, position));
+    }
+    else if (mode == 3)
+    {
+        float size = max(1.0, blockSize*(1.0 - abs(2.0*progress - 1.0)));
+        vec2 block = size/resolution;
+        vec2 center = (floor(uv/block) + 0.5)*block;
+
+        if (progress < 0.5) finalColor = texture(texture0, center);
+        else finalColor = texture(toTexture, center);
+    }
+    else if (mode == 4)
+    {
+        float value = texture(maskTexture, screenUv).r;
+        float edge = progress*(1.0 + softness);
+        finalColor = mix(to, from, smoothstep(edge - softness, edge, value));
+    }
+    else
+    {
+        finalColor = mix(from, to, progress);
+    }
+
+    finalColor *= fragColor;
+}
+"#;
+
+/// Direction a wipe moves in
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WipeDirection {
+    /// The new image comes in from the left
+    LeftToRight,
+    /// The new image comes in from the right
+    RightToLeft,
+    /// The new image comes in from the top
+    TopToBottom,
+    /// The new image comes in from the bottom
+    BottomToTop,
+}
+
+impl WipeDirection {
+    #[inline]
+    fn vector(self) -> Vector2 {
+        match self {
+            Self::LeftToRight => Vector2 { x: 1., y: 0. },
+            Self::RightToLeft => Vector2 { x: -1., y: 0. },
+            Self::TopToBottom => Vector2 { x: 0., y: 1. },
+            Self::BottomToTop => Vector2 { x: 0., y: -1. },
+        }
+    }
+}
+
+/// Effect going from one image to another
+#[derive(Debug)]
+pub enum TransitionEffect {
+    /// Blend the images
+    Crossfade,
+    /// Fade to a color, then to the new image
+    FadeThrough(Color),
+    /// Move an edge across the screen
+    Wipe {
+        /// Direction the edge moves in
+        direction: WipeDirection,
+        /// Width of the edge, as a fraction of the screen
+        softness: f32,
+    },
+    /// Pixelate the old image more and more, then the new image less and less
+    Pixelate {
+        /// Block size in pixels halfway through
+        max_block_size: f32,
+    },
+    /// Dissolve in the order given by the red channel of a mask, dark parts first
+    Dissolve {
+        /// Mask, stretched over the screen
+        mask: Texture2D,
+        /// Width of the dissolving edge, as a fraction of the mask range
+        softness: f32,
+    },
+}
+
+impl TransitionEffect {
+    #[inline]
+    fn mode(&self) -> i32 {
+        match self {
+            Self::Crossfade => 0,
+            Self::FadeThrough(_) => 1,
+            Self::Wipe { .. } => 2,
+            Self::Pixelate { .. } => 3,
+            Self::Dissolve { .. } => 4,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct TransitionLocations {
+    to_texture: Option<ShaderLoc>,
+    mask_texture: Option<ShaderLoc>,
+    mode: Option<ShaderLoc>,
+    progress: Option<ShaderLoc>,
+    color: Option<ShaderLoc>,
+    direction: Option<ShaderLoc>,
+    softness: Option<ShaderLoc>,
+    block_size: Option<ShaderLoc>,
+    resolution: Option<ShaderLoc>,
+}
+
+/// Draws [`TransitionEffect`]s between two render textures
+///
+/// The shader targets desktop OpenGL 3.3.
+#[derive(Debug)]
+pub struct ScreenTransition {
+    shader: Shader,
+    locs: TransitionLocations,
+}
+
+impl ScreenTransition {
+    /// Load the transition shader
+    pub fn new() -> Result<Self, RaylibError> {
+        let shader = Shader::from_memory(None, Some(TRANSITION_FS))?;
+
+        let locs = TransitionLocations {
+            to_texture: shader.get_location("toTexture"),
+            mask_texture: shader.get_location("maskTexture"),
+            mode: shader.get_location("mode"),
+            progress: shader.get_location("progress"),
+            color: shader.get_location("color"),
+            direction: shader.get_location("direction"),
+            softness: shader.get_location("softness"),
+            block_size: shader.get_location("blockSize"),
+            resolution: shader.get_location("resolution"),
+        };
+
+        Ok(Self { shader, locs })
+    }
+
+    /// Draw a transition from one render texture to another into `dest`, `progress` goes from 0 to 1
+    ///
+    /// Both textures should have the same size.
+    pub fn draw<D: Draw>(
+        &mut self,
+        d: &mut D,
+        effect: &TransitionEffect,
+        from: &RenderTexture2D,
+        to: &RenderTexture2D,
+        progress: f32,
+        dest: Rectangle,
+    ) {
+        let shader_mode = d.begin_shader_mode(&self.shader);
+
+        // samplers have to be set while the shader is active, they are reset after every batch
+        if let Some(loc) = self.locs.to_texture {
+            unsafe {
+                ffi::SetShaderValueTexture(
+                    self.shader.raw.clone(),
+                    loc.index() as _,
+                    to.raw.texture.clone(),
+                )
+            }
+        }
+
+        if let (Some(loc), TransitionEffect::Dissolve { mask, .. }) =
+            (self.locs.mask_texture, effect)
+        {
+            self.shader.set_value_texture(loc, mask);
+        }
+
+        if let Some(loc) = self.locs.mode {
+            self.shader.set_value(loc, effect.mode());
+        }
+
+        if let Some(loc) = self.locs.progress {
+            self.shader.set_value(loc, progress.min(1.).max(0.));
+        }
+
+        match effect {
+            TransitionEffect::Crossfade => {}
+            TransitionEffect::FadeThrough(color) => {
+                if let Some(loc) = self.locs.color {
+                    self.shader.set_value(loc, color.normalize());
+                }
+            }
+            TransitionEffect::Wipe {
+                direction,
+                softness,
+            } => {
+                if let Some(loc) = self.locs.direction {
+                    self.shader.set_value(loc, direction.vector());
+                }
+
+                if let Some(loc) = self.locs.softness {
+                    // smoothstep is undefined for an empty range
+                    self.shader.set_value(loc, softness.max(1e-4));
+                }
+            }
+            TransitionEffect::Pixelate { max_block_size } => {
+                if let Some(loc) = self.locs.block_size {
+                    self.shader.set_value(loc, *max_block_size);
+                }
+
+                if let Some(loc) = self.locs.resolution {
+                    self.shader.set_value(
+                        loc,
+                        Vector2 {
+                            x: from.width() as f32,
+                            y: from.height() as f32,
+                        },
+                    );
+                }
+            }
+            TransitionEffect::Dissolve { softness, .. } => {
+                if let Some(loc) = self.locs.softness {
+                    self.shader.set_value(loc, softness.max(1e-4));
+                }
+            }
+        }
+
+        let (width, height) = (from.width() as f32, from.height() as f32);
+
+        unsafe {
+            ffi::DrawTexturePro(
+                from.raw.texture.clone(),
+                // render textures are upside down
+                Rectangle::new(0., 0., width, -height).into(),
+                dest.into(),
+                Vector2 { x: 0., y: 0. }.into(),
+                0.,
+                Color::WHITE.into(),
+            )
+        }
+
+        shader_mode.end_shader_mode();
+    }
+}
@@ -0,0 +1,192 @@
+use crate::{
+    color::Color,
+    drawing::Draw,
+    math::{Rectangle, Vector2},
+    texture::Texture,
+};
+
+/// Placement of a rectangle as passed to [`Draw::draw_texture_pro`] or [`Draw::draw_rectangle_rotated`]
+///
+/// `dest.x`/`dest.y` is where `origin` ends up, the rectangle is rotated around it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DrawnQuad {
+    /// Destination rectangle
+    pub dest: Rectangle,
+    /// Rotation and positioning origin, relative to the rectangle's top left corner
+    pub origin: Vector2,
+    /// Rotation in degrees
+    pub rotation: f32,
+}
+
+impl DrawnQuad {
+    /// Create quad from the values it's drawn with
+    #[inline]
+    pub fn new(dest: Rectangle, origin: impl Into<Vector2>, rotation: f32) -> Self {
+        Self {
+            dest,
+            origin: origin.into(),
+            rotation,
+        }
+    }
+
+    /// Quad drawn without rotation, e.g. with [`Draw::draw_rectangle`]
+    #[inline]
+    pub fn from_rect(rect: Rectangle) -> Self {
+        Self::new(rect, Vector2 { x: 0., y: 0. }, 0.)
+    }
+
+    /// Convert a point to the unrotated rectangle's space, (0, 0) is its top left corner
+    ///
+    /// Useful for controls reacting to where they were clicked, like sliders.
+    pub fn to_local(&self, point: impl Into<Vector2>) -> Vector2 {
+        let point: Vector2 = point.into();
+        let (sin, cos) = (-self.rotation.to_radians()).sin_cos();
+        let (x, y) = (point.x - self.dest.x, point.y - self.dest.y);
+
+        Vector2 {
+            x: x * cos - y * sin + self.origin.x,
+            y: x * sin + y * cos + self.origin.y,
+        }
+    }
+
+    /// Convert a point from the unrotated rectangle's space to where it's drawn
+    pub fn to_screen(&self, point: impl Into<Vector2>) -> Vector2 {
+        let point: Vector2 = point.into();
+        let (sin, cos) = self.rotation.to_radians().sin_cos();
+        let (x, y) = (point.x - self.origin.x, point.y - self.origin.y);
+
+        Vector2 {
+            x: x * cos - y * sin + self.dest.x,
+            y: x * sin + y * cos + self.dest.y,
+        }
+    }
+
+    /// Corners as drawn: top left, top right, bottom right, bottom left
+    #[inline]
+    pub fn corners(&self) -> [Vector2; 4] {
+        let (width, height) = (self.dest.width, self.dest.height);
+
+        [
+            self.to_screen(Vector2 { x: 0., y: 0. }),
+            self.to_screen(Vector2 { x: width, y: 0. }),
+            self.to_screen(Vector2 {
+                x: width,
+                y: height,
+            }),
+            self.to_screen(Vector2 { x: 0., y: height }),
+        ]
+    }
+
+    /// Check if a point is inside the quad as drawn
+    #[inline]
+    pub fn contains(&self, point: impl Into<Vector2>) -> bool {
+        let local = self.to_local(point);
+
+        // negative sizes flip the rectangle
+        let (x1, x2) = (self.dest.width.min(0.), self.dest.width.max(0.));
+        let (y1, y2) = (self.dest.height.min(0.), self.dest.height.max(0.));
+
+        local.x >= x1 && local.x < x2 && local.y >= y1 && local.y < y2
+    }
+}
+
+/// Check if the pointer is over a rotated/scaled rectangle as it's drawn
+///
+/// The pointer has to be in the space the quad was drawn in,
+/// use [`Camera2D::screen_to_world`](crate::math::Camera2D::screen_to_world) for quads drawn in 2D mode.
+#[inline]
+pub fn hit_test(pointer: impl Into<Vector2>, quad: &DrawnQuad) -> bool {
+    quad.contains(pointer)
+}
+
+/// Opt-in recorder of drawn quads for picking the one under the pointer
+///
+/// Draw through it instead of [`Draw`] to record the quads with an id, and [`clear`](Self::clear) it every frame.
+/// Later quads are on top of earlier ones.
+#[derive(Clone, Debug)]
+pub struct QuadRecorder<T> {
+    quads: Vec<(T, DrawnQuad)>,
+}
+
+impl<T> Default for QuadRecorder<T> {
+    #[inline]
+    fn default() -> Self {
+        Self { quads: Vec::new() }
+    }
+}
+
+impl<T> QuadRecorder<T> {
+    /// Create empty recorder
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forget the recorded quads
+    #[inline]
+    pub fn clear(&mut self) {
+        self.quads.clear();
+    }
+
+    /// Record a quad drawn some other way
+    #[inline]
+    pub fn record(&mut self, id: T, quad: DrawnQuad) {
+        self.quads.push((id, quad));
+    }
+
+    /// Recorded quads with their ids, in drawing order
+    #[inline]
+    pub fn quads(&self) -> &[(T, DrawnQuad)] {
+        &self.quads
+    }
+
+    /// Draw a part of a texture and record it
+    #[allow(clippy::too_many_arguments)]
+    #[inline]
+    pub fn draw_texture_pro<D: Draw + ?Sized>(
+        &mut self,
+        d: &mut D,
+        id: T,
+        tex: &Texture,
+        source: Rectangle,
+        dest: Rectangle,
+        origin: impl Into<Vector2>,
+        rotation: f32,
+        tint: Color,
+    ) {
+        let quad = DrawnQuad::new(dest, origin, rotation);
+
+        d.draw_texture_pro(tex, source, dest, quad.origin, rotation, tint);
+        self.record(id, quad);
+    }
+
+    /// Draw a color-filled rotated rectangle and record it
+    #[inline]
+    pub fn draw_rectangle_rotated<D: Draw + ?Sized>(
+        &mut self,
+        d: &mut D,
+        id: T,
+        rect: Rectangle,
+        origin: impl Into<Vector2>,
+        rotation: f32,
+        color: Color,
+    ) {
+        let quad = DrawnQuad::new(rect, origin, rotation);
+
+        d.draw_rectangle_rotated(rect, quad.origin, rotation, color);
+        self.record(id, quad);
+    }
+
+    /// Id of the topmost quad under the pointer
+    #[inline]
+    pub fn hit(&self, pointer: impl Into<Vector2>) -> Option<&T> {
+        let pointer: Vector2 = pointer.into();
+
+        self.quads
+            .iter()
+            .rev()
+            .find(|(_, quad)| quad.contains(pointer))
+            .map(|(id, _)| id)
+    }
+}
@@ -0,0 +1,527 @@
+use crate::{
+    audio::{is_audio_alive, AudioStream},
+    color::Color,
+    error::RaylibError,
+    texture::{Image, Texture2D},
+};
+use std::{
+    collections::VecDeque,
+    fmt,
+    mem::size_of,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, SyncSender, TryRecvError},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+/// Raw pl_mpeg bindings
+#[allow(non_camel_case_types)]
+pub mod ffi {
+    use core::ffi::{c_char, c_double, c_float, c_int, c_uint};
+
+    /// Samples decoded per audio frame (per channel)
+    pub const PLM_AUDIO_SAMPLES_PER_FRAME: usize = 1152;
+
+    /// Decoder (opaque)
+    #[repr(C)]
+    pub struct plm_t {
+        _private: [u8; 0],
+    }
+
+    /// Plane of a decoded frame
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug)]
+    pub struct plm_plane_t {
+        /// Width in pixels
+        pub width: c_uint,
+        /// Height in pixels
+        pub height: c_uint,
+        /// Pixel data
+        pub data: *mut u8,
+    }
+
+    /// Decoded video frame (YCrCb)
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug)]
+    pub struct plm_frame_t {
+        /// Presentation time in seconds
+        pub time: c_double,
+        /// Width in pixels
+        pub width: c_uint,
+        /// Height in pixels
+        pub height: c_uint,
+        /// Luma plane
+        pub y: plm_plane_t,
+        /// Red chroma plane
+        pub cr: plm_plane_t,
+        /// Blue chroma plane
+        pub cb: plm_plane_t,
+    }
+
+    /// Decoded audio samples (stereo, interleaved)
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug)]
+    pub struct plm_samples_t {
+        /// Presentation time in seconds
+        pub time: c_double,
+        /// Number of samples per channel
+        pub count: c_uint,
+        /// Left and right samples
+        pub interleaved: [c_float; PLM_AUDIO_SAMPLES_PER_FRAME * 2],
+    }
+
+    extern "C" {
+        /// Create decoder for a file, null if it can't be opened
+        pub fn plm_create_with_filename(filename: *const c_char) -> *mut plm_t;
+        /// Destroy decoder and close the file
+        pub fn plm_destroy(plm: *mut plm_t);
+        /// Video width in pixels, 0 if there's no video
+        pub fn plm_get_width(plm: *mut plm_t) -> c_int;
+        /// Video height in pixels, 0 if there's no video
+        pub fn plm_get_height(plm: *mut plm_t) -> c_int;
+        /// Frames per second
+        pub fn plm_get_framerate(plm: *mut plm_t) -> c_double;
+        /// Audio samples per second
+        pub fn plm_get_samplerate(plm: *mut plm_t) -> c_int;
+        /// Duration in seconds
+        pub fn plm_get_duration(plm: *mut plm_t) -> c_double;
+        /// Number of audio streams (0 or 1)
+        pub fn plm_get_num_audio_streams(plm: *mut plm_t) -> c_int;
+        /// Enable or disable audio decoding
+        pub fn plm_set_audio_enabled(plm: *mut plm_t, enabled: c_int);
+        /// Go back to the start
+        pub fn plm_rewind(plm: *mut plm_t);
+        /// Decode the next video frame, null at the end
+        pub fn plm_decode_video(plm: *mut plm_t) -> *mut plm_frame_t;
+        /// Decode the next audio frame, null at the end
+        pub fn plm_decode_audio(plm: *mut plm_t) -> *mut plm_samples_t;
+        /// Convert a frame to RGBA, the alpha channel is left untouched
+        pub fn plm_frame_to_rgba(frame: *mut plm_frame_t, dest: *mut u8, stride: c_int);
+
+        /// Struct sizes as compiled, see build/pl_mpeg.c
+        pub static rust_raylib_pl_mpeg_layout: [usize; 3];
+    }
+}
+
+/// Compare the layout pl_mpeg was compiled with to the Rust declarations
+fn check_layout() {
+    let declared = [
+        size_of::<ffi::plm_plane_t>(),
+        size_of::<ffi::plm_frame_t>(),
+        size_of::<ffi::plm_samples_t>(),
+    ];
+    let compiled = unsafe { ffi::rust_raylib_pl_mpeg_layout };
+
+    assert_eq!(
+        declared, compiled,
+        "pl_mpeg's struct layout doesn't match src/video.rs"
+    );
+}
+
+/// Decoded frames waiting to be shown, limits how far the decoder runs ahead
+const VIDEO_QUEUE: usize = 8;
+
+/// Decoded audio frames waiting to be played
+const AUDIO_QUEUE: usize = 64;
+
+/// Audio stream buffer size, in frames
+const AUDIO_CHUNK: usize = 2048;
+
+enum VideoMessage {
+    Frame { time: f64, pixels: Vec<u8> },
+    End,
+}
+
+/// Decoder owned by the worker thread
+struct Decoder(*mut ffi::plm_t);
+
+// pl_mpeg has no global state, a decoder can be used from any (single) thread
+unsafe impl Send for Decoder {}
+
+impl Drop for Decoder {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe { ffi::plm_destroy(self.0) }
+    }
+}
+
+/// MPEG-1 video (with MP2 audio) player, decoding on a worker thread
+///
+/// Needs the `video` feature, which builds [pl_mpeg](https://github.com/phoboslab/pl_mpeg).
+/// Frames are uploaded to a texture in [`VideoPlayer::update`], audio plays through
+/// an [`AudioStream`] if the audio device is initialized when the video is loaded.
+pub struct VideoPlayer {
+    texture: Texture2D,
+    stream: Option<AudioStream>,
+    frames: Receiver<VideoMessage>,
+    samples: Receiver<Vec<f32>>,
+    next_frame: Option<(f64, Vec<u8>)>,
+    audio: VecDeque<f32>,
+    stop: Arc<AtomicBool>,
+    looping: Arc<AtomicBool>,
+    frame_rate: f64,
+    duration: f64,
+    time: f64,
+    playing: bool,
+    ended: bool,
+}
+
+impl fmt::Debug for VideoPlayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VideoPlayer")
+            .field("texture", &self.texture)
+            .field("stream", &self.stream)
+            .field("frame_rate", &self.frame_rate)
+            .field("duration", &self.duration)
+            .field("time", &self.time)
+            .field("playing", &self.playing)
+            .field("ended", &self.ended)
+            .finish_non_exhaustive()
+    }
+}
+
+impl VideoPlayer {
+    /// Load MPEG-1 video file (`.mpg`), paused at the start
+    ///
+    /// Panics if the compiled pl_mpeg's structs don't match the declarations in [`ffi`],
+    /// i.e. `PL_MPEG_INCLUDE_DIR` points to another pl_mpeg version.
+    pub fn from_file(file_name: &str) -> Result<Self, RaylibError> {
+        check_layout();
+
+        let c_file_name = RaylibError::file_name(file_name)?;
+        let plm = unsafe { ffi::plm_create_with_filename(c_file_name.as_ptr()) };
+
+        if plm.is_null() {
+            return Err(RaylibError::decode_file(file_name));
+        }
+
+        let decoder = Decoder(plm);
+
+        let (width, height) = unsafe { (ffi::plm_get_width(plm), ffi::plm_get_height(plm)) };
+
+        if width <= 0 || height <= 0 {
+            return Err(RaylibError::decode_file(file_name));
+        }
+
+        let image = Image::generate_color(width as _, height as _, Color::BLACK);
+        let texture = Texture2D::from_image(&image)?;
+
+        let stream = if is_audio_alive() && unsafe { ffi::plm_get_num_audio_streams(plm) } > 0 {
+            let sample_rate = unsafe { ffi::plm_get_samplerate(plm) } as u32;
+
+            AudioStream::set_default_buffer_size(AUDIO_CHUNK);
+            let stream = AudioStream::new(sample_rate, 32, 2);
+            AudioStream::set_default_buffer_size(0);
+
+            stream
+        } else {
+            None
+        };
+
+        unsafe { ffi::plm_set_audio_enabled(plm, stream.is_some() as _) };
+
+        let (frame_rate, duration) =
+            unsafe { (ffi::plm_get_framerate(plm), ffi::plm_get_duration(plm)) };
+
+        let (frame_sender, frames) = mpsc::sync_channel(VIDEO_QUEUE);
+        let (sample_sender, samples) = mpsc::sync_channel(AUDIO_QUEUE);
+        let stop = Arc::new(AtomicBool::new(false));
+        let looping = Arc::new(AtomicBool::new(false));
+
+        let worker_stop = stop.clone();
+        let worker_looping = looping.clone();
+        let audio = stream.is_some();
+
+        thread::Builder::new()
+            .name("video decoder".to_string())
+            .spawn(move || {
+                decode(
+                    decoder,
+                    audio,
+                    duration,
+                    frame_sender,
+                    sample_sender,
+                    &worker_stop,
+                    &worker_looping,
+                )
+            })
+            .map_err(|_| RaylibError::NotReady("video decoder"))?;
+
+        Ok(Self {
+            texture,
+            stream,
+            frames,
+            samples,
+            next_frame: None,
+            audio: VecDeque::new(),
+            stop,
+            looping,
+            frame_rate,
+            duration,
+            time: 0.,
+            playing: false,
+            ended: false,
+        })
+    }
+
+    /// Texture showing the current frame
+    #[inline]
+    pub fn texture(&self) -> &Texture2D {
+        &self.texture
+    }
+
+    /// Video width in pixels
+    #[inline]
+    pub fn width(&self) -> u32 {
+        self.texture.width()
+    }
+
+    /// Video height in pixels
+    #[inline]
+    pub fn height(&self) -> u32 {
+        self.texture.height()
+    }
+
+    /// Frames per second
+    #[inline]
+    pub fn frame_rate(&self) -> f64 {
+        self.frame_rate
+    }
+
+    /// Length of the video
+    #[inline]
+    pub fn duration(&self) -> Duration {
+        Duration::from_secs_f64(self.duration.max(0.))
+    }
+
+    /// Time played so far (keeps counting when looping)
+    #[inline]
+    pub fn time(&self) -> Duration {
+        Duration::from_secs_f64(self.time)
+    }
+
+    /// Check if the video has audio that is being played
+    #[inline]
+    pub fn has_audio(&self) -> bool {
+        self.stream.is_some()
+    }
+
+    /// Check if the video is playing
+    #[inline]
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    /// Check if the last frame was shown (never when looping)
+    #[inline]
+    pub fn has_ended(&self) -> bool {
+        self.ended
+    }
+
+    /// Start over when the end is reached
+    #[inline]
+    pub fn set_looping(&mut self, looping: bool) {
+        self.looping.store(looping, Ordering::Relaxed);
+    }
+
+    /// Set audio volume (1.0 is max level)
+    #[inline]
+    pub fn set_volume(&mut self, volume: f32) {
+        if let Some(stream) = &self.stream {
+            unsafe { crate::ffi::SetAudioStreamVolume(stream.raw.clone(), volume) }
+        }
+    }
+
+    /// Start or resume playing
+    #[inline]
+    pub fn play(&mut self) {
+        if self.playing || self.ended {
+            return;
+        }
+
+        self.playing = true;
+
+        if let Some(stream) = &self.stream {
+            unsafe { crate::ffi::PlayAudioStream(stream.raw.clone()) }
+        }
+    }
+
+    /// Pause playing
+    #[inline]
+    pub fn pause(&mut self) {
+        self.playing = false;
+
+        if let Some(stream) = &self.stream {
+            unsafe { crate::ffi::PauseAudioStream(stream.raw.clone()) }
+        }
+    }
+
+    /// Advance playback by `dt` seconds, upload the current frame and queue audio
+    ///
+    /// Call once per frame. Frames the decoder couldn't keep up with are skipped.
+    pub fn update(&mut self, dt: f32) {
+        if !self.playing {
+            return;
+        }
+
+        self.time += dt as f64;
+
+        let mut current = None;
+
+        loop {
+            let (time, pixels) = match self.next_frame.take() {
+                Some(frame) => frame,
+                None => match self.frames.try_recv() {
+                    Ok(VideoMessage::Frame { time, pixels }) => (time, pixels),
+                    Ok(VideoMessage::End) | Err(TryRecvError::Disconnected) => {
+                        self.ended = true;
+                        break;
+                    }
+                    Err(TryRecvError::Empty) => break,
+                },
+            };
+
+            if time <= self.time {
+                current = Some(pixels);
+            } else {
+                self.next_frame = Some((time, pixels));
+                break;
+            }
+        }
+
+        if let Some(pixels) = current {
+            self.texture.update(&pixels);
+        }
+
+        self.queue_audio();
+
+        if self.ended {
+            self.pause();
+        }
+    }
+
+    fn queue_audio(&mut self) {
+        let stream = match &mut self.stream {
+            Some(stream) => stream,
+            None => return,
+        };
+
+        while let Ok(samples) = self.samples.try_recv() {
+            self.audio.extend(samples);
+        }
+
+        let chunk = AUDIO_CHUNK * 2;
+
+        while stream.is_processed() && (self.audio.len() >= chunk || self.ended) {
+            if self.audio.is_empty() {
+                break;
+            }
+
+            let mut samples: Vec<f32> = self.audio.drain(..chunk.min(self.audio.len())).collect();
+            samples.resize(chunk, 0.);
+
+            stream.update_samples_f32(&samples);
+        }
+    }
+}
+
+impl Drop for VideoPlayer {
+    #[inline]
+    fn drop(&mut self) {
+        // the worker exits when it sees the flag, or when sending fails after the receivers are dropped
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Worker thread: decode video and audio in presentation order until the end or until stopped
+fn decode(
+    decoder: Decoder,
+    audio: bool,
+    duration: f64,
+    frames: SyncSender<VideoMessage>,
+    samples: SyncSender<Vec<f32>>,
+    stop: &AtomicBool,
+    looping: &AtomicBool,
+) {
+    let plm = decoder.0;
+    let mut offset = 0.;
+
+    let decode_frame = |offset: f64| unsafe {
+        let frame = ffi::plm_decode_video(plm);
+
+        if frame.is_null() {
+            return None;
+        }
+
+        let (width, height) = ((*frame).width as usize, (*frame).height as usize);
+        let mut pixels = vec![255; width * height * 4];
+        ffi::plm_frame_to_rgba(frame, pixels.as_mut_ptr(), (width * 4) as _);
+
+        Some((offset + (*frame).time, pixels))
+    };
+
+    let decode_samples = |offset: f64| unsafe {
+        if !audio {
+            return None;
+        }
+
+        let samples = ffi::plm_decode_audio(plm);
+
+        if samples.is_null() {
+            return None;
+        }
+
+        let samples = &*samples;
+        let count = samples.count as usize * 2;
+
+        Some((offset + samples.time, samples.interleaved[..count].to_vec()))
+    };
+
+    let mut frame = decode_frame(offset);
+    let mut sample = decode_samples(offset);
+
+    while !stop.load(Ordering::Relaxed) {
+        let video_first = match (&frame, &sample) {
+            (Some((video_time, _)), Some((audio_time, _))) => video_time <= audio_time,
+            (Some(_), None) => true,
+            (None, Some(_)) => false,
+            (None, None) => {
+                if !looping.load(Ordering::Relaxed) || duration <= 0. {
+                    let _ = frames.send(VideoMessage::End);
+                    return;
+                }
+
+                unsafe { ffi::plm_rewind(plm) };
+                offset += duration;
+                frame = decode_frame(offset);
+                sample = decode_samples(offset);
+
+                if frame.is_none() {
+                    let _ = frames.send(VideoMessage::End);
+                    return;
+                }
+
+                continue;
+            }
+        };
+
+        let sent = if video_first {
+            let (time, pixels) = frame.take().unwrap();
+            frame = decode_frame(offset);
+
+            frames.send(VideoMessage::Frame { time, pixels }).is_ok()
+        } else {
+            let (_, data) = sample.take().unwrap();
+            sample = decode_samples(offset);
+
+            samples.send(data).is_ok()
+        };
+
+        if !sent {
+            return;
+        }
+    }
+}
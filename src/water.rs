@@ -0,0 +1,407 @@
+use crate::{
+    color::Color,
+    drawing::Draw,
+    error::RaylibError,
+    ffi,
+    math::{Camera3D, Vector2, Vector3, Vector4},
+    shader::{supports_glsl_330, Shader, ShaderLoc},
+    texture::{RenderTexture2D, Texture2D, TextureWrap},
+};
+use std::marker::PhantomData;
+
+const WATER_VS: &str = r#"#version 330
+in vec3 vertexPosition;
+
+uniform mat4 mvp;
+uniform float tiling;
+
+out vec4 clipSpace;
+out vec2 fragTexCoord;
+out vec3 fragPosition;
+
+void main()
+{
+    fragPosition = vertexPosition;
+    fragTexCoord = vertexPosition.xz*tiling;
+    clipSpace = mvp*vec4(vertexPosition, 1.0);
+    gl_Position = clipSpace;
+}
+"#;
+
+const WATER_FS: &str = r#"#version 330
+in vec4 clipSpace;
+in vec2 fragTexCoord;
+in vec3 fragPosition;
+
+uniform sampler2D reflectionTexture;
+uniform sampler2D refractionTexture;
+uniform sampler2D dudvMap;
+uniform int useDudvMap;
+uniform float moveFactor;
+uniform float waveStrength;
+uniform vec3 viewPos;
+uniform vec4 tint;
+
+out vec4 finalColor;
+
+vec2 distortion(vec2 uv)
+{
+    if (useDudvMap != 0)
+    {
+        vec2 d = texture(dudvMap, vec2(uv.x + moveFactor, uv.y)).rg*0.1;
+        d = uv + vec2(d.x, d.y + moveFactor);
+        return (texture(dudvMap, d).rg*2.0 - 1.0)*waveStrength;
+    }
+
+    float t = moveFactor*6.2831853;
+    return vec2(sin(uv.x*6.0 + t) + sin(uv.y*4.3 - t),
+                cos(uv.y*5.1 + t) + cos(uv.x*3.7 - t))*0.25*waveStrength;
+}
+
+void main()
+{
+    vec2 ndc = clipSpace.xy/clipSpace.w*0.5 + 0.5;
+    vec2 offset = distortion(fragTexCoord);
+
+    vec4 reflection = texture(reflectionTexture, clamp(vec2(ndc.x, 1.0 - ndc.y) + offset, 0.001, 0.999));
+    vec4 refraction = texture(refractionTexture, clamp(ndc + offset, 0.001, 0.999));
+
+    float fresnel = clamp(normalize(viewPos - fragPosition).y, 0.0, 1.0);
+    vec3 color = mix(reflection.rgb, refraction.rgb, fresnel);
+
+    finalColor = vec4(mix(color, tint.rgb, tint.a), 1.0);
+}
+"#;
+
+/// Scene pass rendered by [`WaterPlane::draw_scene`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum WaterPassKind {
+    /// Scene above the water, seen by the camera mirrored below the surface
+    Reflection,
+    /// Scene below the water
+    Refraction,
+    /// Scene seen by the camera, drawn before the water surface
+    Main,
+}
+
+/// Draw handle of one scene pass of [`WaterPlane::draw_scene`], already in 3D mode
+#[derive(Debug)]
+pub struct WaterPass<'a> {
+    kind: WaterPassKind,
+    clip_plane: Vector4,
+    camera: Camera3D,
+    _marker: PhantomData<&'a mut ()>,
+}
+
+impl<'a> WaterPass<'a> {
+    /// Which pass is being drawn
+    #[inline]
+    pub fn kind(&self) -> WaterPassKind {
+        self.kind
+    }
+
+    /// Clip plane `(normal, distance)` of the pass
+    ///
+    /// Geometry where `dot(vec4(position, 1.0), plane) < 0` should be discarded.
+    ///
+    /// The main pass doesn't clip anything, its plane is `(0, 0, 0, 1)`.
+    #[inline]
+    pub fn clip_plane(&self) -> Vector4 {
+        self.clip_plane
+    }
+
+    /// Camera the pass is drawn with
+    #[inline]
+    pub fn camera(&self) -> Camera3D {
+        self.camera
+    }
+
+    /// Set the clip plane uniform (see [`WaterPlane::CLIP_PLANE_UNIFORM`]) of a scene shader
+    ///
+    /// Call before drawing anything with the shader in this pass.
+    #[inline]
+    pub fn apply_clip_plane(&self, shader: &mut Shader, loc: ShaderLoc) {
+        shader.set_value(loc, self.clip_plane);
+    }
+}
+
+impl<'a> Draw for WaterPass<'a> {}
+
+#[derive(Debug)]
+struct WaterLocations {
+    reflection: Option<ShaderLoc>,
+    refraction: Option<ShaderLoc>,
+    dudv: Option<ShaderLoc>,
+    use_dudv: Option<ShaderLoc>,
+    move_factor: Option<ShaderLoc>,
+    wave_strength: Option<ShaderLoc>,
+    tiling: Option<ShaderLoc>,
+    view_pos: Option<ShaderLoc>,
+    tint: Option<ShaderLoc>,
+}
+
+/// Horizontal water surface with reflection and refraction
+///
+/// The scene is drawn three times per frame: mirrored into the reflection texture, into the refraction texture and
+/// to the current target. raylib doesn't expose clip distances, so shaders drawing the scene have to clip against
+/// [`WaterPass::clip_plane`] themselves, e.g.
+/// `if (dot(vec4(fragPosition, 1.0), clipPlane) < 0.0) discard;`.
+/// Geometry drawn with the default shader isn't clipped.
+///
+/// The water shader targets desktop OpenGL 3.3, [`WaterPlane::new`] fails when raylib is built for
+/// OpenGL 2.1, 1.1 or ES 2 (the `opengl_21`, `opengl_11` and `opengl_es2` features).
+#[derive(Debug)]
+pub struct WaterPlane {
+    /// Center of the surface, its y is the water level
+    pub center: Vector3,
+    /// Size of the surface on the XZ plane
+    pub size: Vector2,
+    /// Wave repetitions per world unit
+    pub tiling: f32,
+    /// Distortion of the reflection and refraction, in texture coordinates
+    pub wave_strength: f32,
+    /// Wave cycles per second
+    pub wave_speed: f32,
+    /// Color blended over the water, by its alpha
+    pub tint: Color,
+    /// Clear color of the reflection and refraction textures
+    pub sky_color: Color,
+    reflection: RenderTexture2D,
+    refraction: RenderTexture2D,
+    dudv_map: Option<Texture2D>,
+    shader: Shader,
+    locs: WaterLocations,
+    move_factor: f32,
+}
+
+impl WaterPlane {
+    /// Name of the clip plane uniform (`vec4`) expected in scene shaders
+    pub const CLIP_PLANE_UNIFORM: &'static str = "clipPlane";
+
+    /// Create water plane with reflection and refraction textures of the given size
+    ///
+    /// The textures should have the aspect ratio of the target the scene is drawn to (usually the screen).
+    /// Fails without OpenGL 3.3 or 4.3.
+    pub fn new(
+        center: Vector3,
+        size: Vector2,
+        texture_width: u32,
+        texture_height: u32,
+    ) -> Result<Self, RaylibError> {
+        // raylib would fall back to its default shader when the GLSL 330 source doesn't compile
+        if !supports_glsl_330() {
+            return Err(RaylibError::NotReady("water shader (needs OpenGL 3.3)"));
+        }
+
+        let reflection = RenderTexture2D::new(texture_width, texture_height)?;
+        let refraction = RenderTexture2D::new(texture_width, texture_height)?;
+        let shader = Shader::from_memory(Some(WATER_VS), Some(WATER_FS))?;
+
+        let locs = WaterLocations {
+            reflection: shader.get_location("reflectionTexture"),
+            refraction: shader.get_location("refractionTexture"),
+            dudv: shader.get_location("dudvMap"),
+            use_dudv: shader.get_location("useDudvMap"),
+            move_factor: shader.get_location("moveFactor"),
+            wave_strength: shader.get_location("waveStrength"),
+            tiling: shader.get_location("tiling"),
+            view_pos: shader.get_location("viewPos"),
+            tint: shader.get_location("tint"),
+        };
+
+        Ok(Self {
+            center,
+            size,
+            tiling: 0.25,
+            wave_strength: 0.02,
+            wave_speed: 0.03,
+            tint: Color::new(0, 60, 90, 50),
+            sky_color: Color::SKYBLUE,
+            reflection,
+            refraction,
+            dudv_map: None,
+            shader,
+            locs,
+            move_factor: 0.,
+        })
+    }
+
+    /// Recreate the reflection and refraction textures, e.g. after the window was resized
+    ///
+    /// Returns `false` and keeps the old textures if they couldn't be created.
+    pub fn resize(&mut self, texture_width: u32, texture_height: u32) -> bool {
+        match (
+            RenderTexture2D::new(texture_width, texture_height),
+            RenderTexture2D::new(texture_width, texture_height),
+        ) {
+            (Ok(reflection), Ok(refraction)) => {
+                self.reflection = reflection;
+                self.refraction = refraction;
+
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Reflection texture, from the last [`WaterPlane::draw_scene`]
+    #[inline]
+    pub fn reflection_texture(&self) -> &RenderTexture2D {
+        &self.reflection
+    }
+
+    /// Refraction texture, from the last [`WaterPlane::draw_scene`]
+    #[inline]
+    pub fn refraction_texture(&self) -> &RenderTexture2D {
+        &self.refraction
+    }
+
+    /// Distortion (du/dv) map, `None` to use procedural waves
+    #[inline]
+    pub fn dudv_map(&self) -> Option<&Texture2D> {
+        self.dudv_map.as_ref()
+    }
+
+    /// Set distortion (du/dv) map, `None` to use procedural waves
+    ///
+    /// The texture is set to repeat.
+    #[inline]
+    pub fn set_dudv_map(&mut self, dudv_map: Option<Texture2D>) {
+        self.dudv_map = dudv_map;
+
+        if let Some(dudv_map) = &mut self.dudv_map {
+            dudv_map.set_wrap(TextureWrap::Repeat);
+        }
+    }
+
+    /// Advance the waves by `delta` seconds
+    #[inline]
+    pub fn update(&mut self, delta: f32) {
+        self.move_factor = (self.move_factor + self.wave_speed * delta).rem_euclid(1.);
+    }
+
+    /// Draw the scene with the water surface
+    ///
+    /// `scene` is called once per [`WaterPassKind`], the reflection and refraction passes are drawn to the water
+    /// textures and the main pass to `draw`, followed by the water surface.
+    pub fn draw_scene<D: Draw>(
+        &mut self,
+        draw: &mut D,
+        camera: Camera3D,
+        mut scene: impl FnMut(&mut WaterPass<'_>),
+    ) {
+        let level = self.center.y;
+
+        // mirror the camera below the surface
+        let mut mirrored = camera;
+        mirrored.position.y = 2. * level - camera.position.y;
+        mirrored.target.y = 2. * level - camera.target.y;
+
+        let passes = [
+            (
+                WaterPassKind::Reflection,
+                &self.reflection,
+                mirrored,
+                Vector4 {
+                    x: 0.,
+                    y: 1.,
+                    z: 0.,
+                    w: -level,
+                },
+            ),
+            (
+                WaterPassKind::Refraction,
+                &self.refraction,
+                camera,
+                Vector4 {
+                    x: 0.,
+                    y: -1.,
+                    z: 0.,
+                    w: level,
+                },
+            ),
+        ];
+
+        for (kind, target, camera, clip_plane) in passes {
+            let mut target = draw.begin_texture_mode(target);
+            target.clear_background(self.sky_color);
+
+            let d3 = target.begin_mode_3d(camera);
+            scene(&mut WaterPass {
+                kind,
+                clip_plane,
+                camera,
+                _marker: PhantomData,
+            });
+            d3.end_mode_3d();
+
+            target.end_texture_mode();
+        }
+
+        let mut d3 = draw.begin_mode_3d(camera);
+        scene(&mut WaterPass {
+            kind: WaterPassKind::Main,
+            clip_plane: Vector4 {
+                x: 0.,
+                y: 0.,
+                z: 0.,
+                w: 1.,
+            },
+            camera,
+            _marker: PhantomData,
+        });
+        self.draw_surface(&mut d3, camera);
+        d3.end_mode_3d();
+    }
+
+    fn draw_surface<D: Draw>(&mut self, draw: &mut D, camera: Camera3D) {
+        let mut shader_mode = draw.begin_shader_mode(&self.shader);
+
+        // samplers have to be set while the shader is active, they are reset after every batch
+        for (loc, texture) in [
+            (self.locs.reflection, &self.reflection.raw.texture),
+            (self.locs.refraction, &self.refraction.raw.texture),
+        ] {
+            if let Some(loc) = loc {
+                unsafe {
+                    ffi::SetShaderValueTexture(
+                        self.shader.raw.clone(),
+                        loc.index() as _,
+                        texture.clone(),
+                    )
+                }
+            }
+        }
+
+        if let (Some(loc), Some(dudv_map)) = (self.locs.dudv, &self.dudv_map) {
+            self.shader.set_value_texture(loc, dudv_map);
+        }
+
+        let values = [
+            (self.locs.move_factor, self.move_factor),
+            (self.locs.wave_strength, self.wave_strength),
+            (self.locs.tiling, self.tiling),
+        ];
+
+        for (loc, value) in values {
+            if let Some(loc) = loc {
+                self.shader.set_value(loc, value);
+            }
+        }
+
+        if let Some(loc) = self.locs.use_dudv {
+            self.shader.set_value(loc, self.dudv_map.is_some() as i32);
+        }
+
+        if let Some(loc) = self.locs.view_pos {
+            self.shader.set_value(loc, camera.position);
+        }
+
+        if let Some(loc) = self.locs.tint {
+            self.shader.set_value(loc, self.tint.normalize());
+        }
+
+        shader_mode.draw_plane(self.center, self.size, Color::WHITE);
+        shader_mode.end_shader_mode();
+    }
+}